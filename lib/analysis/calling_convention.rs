@@ -6,11 +6,19 @@ use std::collections::HashSet;
 /// Available type of calling conventions
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CallingConventionType {
+    Aapcs,
+    Aapcs64,
     Amd64SystemV,
     Cdecl,
     MipsSystemV,
     MipselSystemV,
+    MipsN32,
+    MipsN64,
     PpcSystemV,
+    Ppc64ElfV1,
+    Ppc64ElfV2,
+    RiscvIlp32,
+    RiscvLp64,
 }
 
 /// The return type for a function.
@@ -97,10 +105,40 @@ pub struct CallingConvention {
 }
 
 /*
+    AAPCS:
+        r4-r11, sp and lr are saved.
+        Result is in r0.
+        r0-r3 and r12 (ip) are trashed.
+
+    AAPCS64:
+        x19-x28, x29 (fp), x30 (lr) and sp are saved.
+        Result is in x0.
+        x0-x18 are trashed.
+
     Mips System V:
         $16-$23 and $29-$31 are saved. This is $s0-S8, $sp and $ra.
         Result is in $v0.
         Everything else is trashed.
+
+    Mips n32 and n64:
+        $16-$23 and $28-$31 are saved. This is $s0-$s7, $gp, $sp, $fp and $ra.
+        Result is in $v0.
+        Arguments are passed in $4-$11, which the 32-bit ABIs name $a0-$a3
+        and $t0-$t3. Registers are 64 bits wide in both, and each stack
+        argument takes a doubleword.
+
+    PowerPC 64-bit ELFv1 and ELFv2:
+        r1, r2 (toc) and r14-r31 are saved.
+        Result is in r3.
+        r0 and r3-r12 are trashed.
+        The parameter save area begins at 48 (ELFv1) or 32 (ELFv2) bytes past
+        the stack pointer, and stack arguments follow the eight register
+        arguments' slots.
+
+    RISC-V ILP32 and LP64:
+        s0-s11 and sp are saved.
+        Result is in a0.
+        ra, t0-t6 and a0-a7 are trashed.
 */
 
 impl CallingConvention {
@@ -108,6 +146,105 @@ impl CallingConvention {
     /// `CallingConventionType`.
     pub fn new(typ: CallingConventionType) -> CallingConvention {
         match typ {
+            CallingConventionType::Aapcs => {
+                let argument_registers = vec![
+                    il::scalar("r0", 32),
+                    il::scalar("r1", 32),
+                    il::scalar("r2", 32),
+                    il::scalar("r3", 32),
+                ];
+
+                let mut preserved_registers = HashSet::new();
+                preserved_registers.insert(il::scalar("r4", 32));
+                preserved_registers.insert(il::scalar("r5", 32));
+                preserved_registers.insert(il::scalar("r6", 32));
+                preserved_registers.insert(il::scalar("r7", 32));
+                preserved_registers.insert(il::scalar("r8", 32));
+                preserved_registers.insert(il::scalar("r9", 32));
+                preserved_registers.insert(il::scalar("r10", 32));
+                preserved_registers.insert(il::scalar("r11", 32));
+                preserved_registers.insert(il::scalar("sp", 32));
+                preserved_registers.insert(il::scalar("lr", 32));
+
+                let mut trashed_registers = HashSet::new();
+                trashed_registers.insert(il::scalar("r0", 32));
+                trashed_registers.insert(il::scalar("r1", 32));
+                trashed_registers.insert(il::scalar("r2", 32));
+                trashed_registers.insert(il::scalar("r3", 32));
+                trashed_registers.insert(il::scalar("r12", 32));
+
+                let return_type = ReturnAddressType::Register(il::scalar("lr", 32));
+
+                CallingConvention {
+                    argument_registers,
+                    preserved_registers,
+                    trashed_registers,
+                    stack_argument_offset: 0,
+                    stack_argument_length: 4,
+                    return_address_type: return_type,
+                    return_register: il::scalar("r0", 32),
+                }
+            }
+            CallingConventionType::Aapcs64 => {
+                let argument_registers = vec![
+                    il::scalar("x0", 64),
+                    il::scalar("x1", 64),
+                    il::scalar("x2", 64),
+                    il::scalar("x3", 64),
+                    il::scalar("x4", 64),
+                    il::scalar("x5", 64),
+                    il::scalar("x6", 64),
+                    il::scalar("x7", 64),
+                ];
+
+                let mut preserved_registers = HashSet::new();
+                preserved_registers.insert(il::scalar("x19", 64));
+                preserved_registers.insert(il::scalar("x20", 64));
+                preserved_registers.insert(il::scalar("x21", 64));
+                preserved_registers.insert(il::scalar("x22", 64));
+                preserved_registers.insert(il::scalar("x23", 64));
+                preserved_registers.insert(il::scalar("x24", 64));
+                preserved_registers.insert(il::scalar("x25", 64));
+                preserved_registers.insert(il::scalar("x26", 64));
+                preserved_registers.insert(il::scalar("x27", 64));
+                preserved_registers.insert(il::scalar("x28", 64));
+                preserved_registers.insert(il::scalar("x29", 64));
+                preserved_registers.insert(il::scalar("x30", 64));
+                preserved_registers.insert(il::scalar("sp", 64));
+
+                let mut trashed_registers = HashSet::new();
+                trashed_registers.insert(il::scalar("x0", 64));
+                trashed_registers.insert(il::scalar("x1", 64));
+                trashed_registers.insert(il::scalar("x2", 64));
+                trashed_registers.insert(il::scalar("x3", 64));
+                trashed_registers.insert(il::scalar("x4", 64));
+                trashed_registers.insert(il::scalar("x5", 64));
+                trashed_registers.insert(il::scalar("x6", 64));
+                trashed_registers.insert(il::scalar("x7", 64));
+                trashed_registers.insert(il::scalar("x8", 64));
+                trashed_registers.insert(il::scalar("x9", 64));
+                trashed_registers.insert(il::scalar("x10", 64));
+                trashed_registers.insert(il::scalar("x11", 64));
+                trashed_registers.insert(il::scalar("x12", 64));
+                trashed_registers.insert(il::scalar("x13", 64));
+                trashed_registers.insert(il::scalar("x14", 64));
+                trashed_registers.insert(il::scalar("x15", 64));
+                trashed_registers.insert(il::scalar("x16", 64));
+                trashed_registers.insert(il::scalar("x17", 64));
+                trashed_registers.insert(il::scalar("x18", 64));
+
+                let return_type = ReturnAddressType::Register(il::scalar("x30", 64));
+
+                CallingConvention {
+                    argument_registers,
+                    preserved_registers,
+                    trashed_registers,
+                    stack_argument_offset: 0,
+                    stack_argument_length: 8,
+                    return_address_type: return_type,
+                    return_register: il::scalar("x0", 64),
+                }
+            }
             CallingConventionType::Amd64SystemV => {
                 let argument_registers = vec![
                     il::scalar("rdi", 64),
@@ -227,6 +364,63 @@ impl CallingConvention {
                     return_register: il::scalar("$v0", 32),
                 }
             }
+            CallingConventionType::MipsN32 | CallingConventionType::MipsN64 => {
+                let argument_registers = vec![
+                    il::scalar("$a0", 64),
+                    il::scalar("$a1", 64),
+                    il::scalar("$a2", 64),
+                    il::scalar("$a3", 64),
+                    il::scalar("$t0", 64),
+                    il::scalar("$t1", 64),
+                    il::scalar("$t2", 64),
+                    il::scalar("$t3", 64),
+                ];
+
+                let mut preserved_registers = HashSet::new();
+                preserved_registers.insert(il::scalar("$s0", 64));
+                preserved_registers.insert(il::scalar("$s1", 64));
+                preserved_registers.insert(il::scalar("$s2", 64));
+                preserved_registers.insert(il::scalar("$s3", 64));
+                preserved_registers.insert(il::scalar("$s4", 64));
+                preserved_registers.insert(il::scalar("$s5", 64));
+                preserved_registers.insert(il::scalar("$s6", 64));
+                preserved_registers.insert(il::scalar("$s7", 64));
+                preserved_registers.insert(il::scalar("$gp", 64));
+                preserved_registers.insert(il::scalar("$sp", 64));
+                preserved_registers.insert(il::scalar("$fp", 64));
+                preserved_registers.insert(il::scalar("$ra", 64));
+
+                let mut trashed_registers = HashSet::new();
+                trashed_registers.insert(il::scalar("$at", 64));
+                trashed_registers.insert(il::scalar("$v0", 64));
+                trashed_registers.insert(il::scalar("$v1", 64));
+                trashed_registers.insert(il::scalar("$a0", 64));
+                trashed_registers.insert(il::scalar("$a1", 64));
+                trashed_registers.insert(il::scalar("$a2", 64));
+                trashed_registers.insert(il::scalar("$a3", 64));
+                trashed_registers.insert(il::scalar("$t0", 64));
+                trashed_registers.insert(il::scalar("$t1", 64));
+                trashed_registers.insert(il::scalar("$t2", 64));
+                trashed_registers.insert(il::scalar("$t3", 64));
+                trashed_registers.insert(il::scalar("$t4", 64));
+                trashed_registers.insert(il::scalar("$t5", 64));
+                trashed_registers.insert(il::scalar("$t6", 64));
+                trashed_registers.insert(il::scalar("$t7", 64));
+                trashed_registers.insert(il::scalar("$t8", 64));
+                trashed_registers.insert(il::scalar("$t9", 64));
+
+                let return_type = ReturnAddressType::Register(il::scalar("$ra", 64));
+
+                CallingConvention {
+                    argument_registers,
+                    preserved_registers,
+                    trashed_registers,
+                    stack_argument_offset: 0,
+                    stack_argument_length: 8,
+                    return_address_type: return_type,
+                    return_register: il::scalar("$v0", 64),
+                }
+            }
             CallingConventionType::PpcSystemV => {
                 let argument_registers = vec![
                     il::scalar("r3", 32),
@@ -275,7 +469,7 @@ impl CallingConvention {
                 trashed_registers.insert(il::scalar("r12", 32));
                 trashed_registers.insert(il::scalar("r13", 32));
 
-                let return_type = ReturnAddressType::Register(il::scalar("r3", 32));
+                let return_type = ReturnAddressType::Register(il::scalar("lr", 32));
 
                 CallingConvention {
                     argument_registers,
@@ -284,7 +478,187 @@ impl CallingConvention {
                     stack_argument_offset: 4,
                     stack_argument_length: 4,
                     return_address_type: return_type,
-                    return_register: il::scalar("lr", 32),
+                    return_register: il::scalar("r3", 32),
+                }
+            }
+            CallingConventionType::Ppc64ElfV1 | CallingConventionType::Ppc64ElfV2 => {
+                let argument_registers = vec![
+                    il::scalar("r3", 64),
+                    il::scalar("r4", 64),
+                    il::scalar("r5", 64),
+                    il::scalar("r6", 64),
+                    il::scalar("r7", 64),
+                    il::scalar("r8", 64),
+                    il::scalar("r9", 64),
+                    il::scalar("r10", 64),
+                ];
+
+                let mut preserved_registers = HashSet::new();
+                preserved_registers.insert(il::scalar("r1", 64));
+                preserved_registers.insert(il::scalar("r2", 64));
+                preserved_registers.insert(il::scalar("r14", 64));
+                preserved_registers.insert(il::scalar("r15", 64));
+                preserved_registers.insert(il::scalar("r16", 64));
+                preserved_registers.insert(il::scalar("r17", 64));
+                preserved_registers.insert(il::scalar("r18", 64));
+                preserved_registers.insert(il::scalar("r19", 64));
+                preserved_registers.insert(il::scalar("r20", 64));
+                preserved_registers.insert(il::scalar("r21", 64));
+                preserved_registers.insert(il::scalar("r22", 64));
+                preserved_registers.insert(il::scalar("r23", 64));
+                preserved_registers.insert(il::scalar("r24", 64));
+                preserved_registers.insert(il::scalar("r25", 64));
+                preserved_registers.insert(il::scalar("r26", 64));
+                preserved_registers.insert(il::scalar("r27", 64));
+                preserved_registers.insert(il::scalar("r28", 64));
+                preserved_registers.insert(il::scalar("r29", 64));
+                preserved_registers.insert(il::scalar("r30", 64));
+                preserved_registers.insert(il::scalar("r31", 64));
+
+                let mut trashed_registers = HashSet::new();
+                trashed_registers.insert(il::scalar("r0", 64));
+                trashed_registers.insert(il::scalar("r3", 64));
+                trashed_registers.insert(il::scalar("r4", 64));
+                trashed_registers.insert(il::scalar("r5", 64));
+                trashed_registers.insert(il::scalar("r6", 64));
+                trashed_registers.insert(il::scalar("r7", 64));
+                trashed_registers.insert(il::scalar("r8", 64));
+                trashed_registers.insert(il::scalar("r9", 64));
+                trashed_registers.insert(il::scalar("r10", 64));
+                trashed_registers.insert(il::scalar("r11", 64));
+                trashed_registers.insert(il::scalar("r12", 64));
+
+                // The parameter save area reserves space for the eight
+                // register arguments.
+                let stack_argument_offset = match typ {
+                    CallingConventionType::Ppc64ElfV1 => 48 + 64,
+                    _ => 32 + 64,
+                };
+
+                let return_type = ReturnAddressType::Register(il::scalar("lr", 64));
+
+                CallingConvention {
+                    argument_registers,
+                    preserved_registers,
+                    trashed_registers,
+                    stack_argument_offset,
+                    stack_argument_length: 8,
+                    return_address_type: return_type,
+                    return_register: il::scalar("r3", 64),
+                }
+            }
+            CallingConventionType::RiscvIlp32 => {
+                let argument_registers = vec![
+                    il::scalar("a0", 32),
+                    il::scalar("a1", 32),
+                    il::scalar("a2", 32),
+                    il::scalar("a3", 32),
+                    il::scalar("a4", 32),
+                    il::scalar("a5", 32),
+                    il::scalar("a6", 32),
+                    il::scalar("a7", 32),
+                ];
+
+                let mut preserved_registers = HashSet::new();
+                preserved_registers.insert(il::scalar("s0", 32));
+                preserved_registers.insert(il::scalar("s1", 32));
+                preserved_registers.insert(il::scalar("s2", 32));
+                preserved_registers.insert(il::scalar("s3", 32));
+                preserved_registers.insert(il::scalar("s4", 32));
+                preserved_registers.insert(il::scalar("s5", 32));
+                preserved_registers.insert(il::scalar("s6", 32));
+                preserved_registers.insert(il::scalar("s7", 32));
+                preserved_registers.insert(il::scalar("s8", 32));
+                preserved_registers.insert(il::scalar("s9", 32));
+                preserved_registers.insert(il::scalar("s10", 32));
+                preserved_registers.insert(il::scalar("s11", 32));
+                preserved_registers.insert(il::scalar("sp", 32));
+
+                let mut trashed_registers = HashSet::new();
+                trashed_registers.insert(il::scalar("ra", 32));
+                trashed_registers.insert(il::scalar("t0", 32));
+                trashed_registers.insert(il::scalar("t1", 32));
+                trashed_registers.insert(il::scalar("t2", 32));
+                trashed_registers.insert(il::scalar("t3", 32));
+                trashed_registers.insert(il::scalar("t4", 32));
+                trashed_registers.insert(il::scalar("t5", 32));
+                trashed_registers.insert(il::scalar("t6", 32));
+                trashed_registers.insert(il::scalar("a0", 32));
+                trashed_registers.insert(il::scalar("a1", 32));
+                trashed_registers.insert(il::scalar("a2", 32));
+                trashed_registers.insert(il::scalar("a3", 32));
+                trashed_registers.insert(il::scalar("a4", 32));
+                trashed_registers.insert(il::scalar("a5", 32));
+                trashed_registers.insert(il::scalar("a6", 32));
+                trashed_registers.insert(il::scalar("a7", 32));
+
+                let return_type = ReturnAddressType::Register(il::scalar("ra", 32));
+
+                CallingConvention {
+                    argument_registers,
+                    preserved_registers,
+                    trashed_registers,
+                    stack_argument_offset: 0,
+                    stack_argument_length: 4,
+                    return_address_type: return_type,
+                    return_register: il::scalar("a0", 32),
+                }
+            }
+            CallingConventionType::RiscvLp64 => {
+                let argument_registers = vec![
+                    il::scalar("a0", 64),
+                    il::scalar("a1", 64),
+                    il::scalar("a2", 64),
+                    il::scalar("a3", 64),
+                    il::scalar("a4", 64),
+                    il::scalar("a5", 64),
+                    il::scalar("a6", 64),
+                    il::scalar("a7", 64),
+                ];
+
+                let mut preserved_registers = HashSet::new();
+                preserved_registers.insert(il::scalar("s0", 64));
+                preserved_registers.insert(il::scalar("s1", 64));
+                preserved_registers.insert(il::scalar("s2", 64));
+                preserved_registers.insert(il::scalar("s3", 64));
+                preserved_registers.insert(il::scalar("s4", 64));
+                preserved_registers.insert(il::scalar("s5", 64));
+                preserved_registers.insert(il::scalar("s6", 64));
+                preserved_registers.insert(il::scalar("s7", 64));
+                preserved_registers.insert(il::scalar("s8", 64));
+                preserved_registers.insert(il::scalar("s9", 64));
+                preserved_registers.insert(il::scalar("s10", 64));
+                preserved_registers.insert(il::scalar("s11", 64));
+                preserved_registers.insert(il::scalar("sp", 64));
+
+                let mut trashed_registers = HashSet::new();
+                trashed_registers.insert(il::scalar("ra", 64));
+                trashed_registers.insert(il::scalar("t0", 64));
+                trashed_registers.insert(il::scalar("t1", 64));
+                trashed_registers.insert(il::scalar("t2", 64));
+                trashed_registers.insert(il::scalar("t3", 64));
+                trashed_registers.insert(il::scalar("t4", 64));
+                trashed_registers.insert(il::scalar("t5", 64));
+                trashed_registers.insert(il::scalar("t6", 64));
+                trashed_registers.insert(il::scalar("a0", 64));
+                trashed_registers.insert(il::scalar("a1", 64));
+                trashed_registers.insert(il::scalar("a2", 64));
+                trashed_registers.insert(il::scalar("a3", 64));
+                trashed_registers.insert(il::scalar("a4", 64));
+                trashed_registers.insert(il::scalar("a5", 64));
+                trashed_registers.insert(il::scalar("a6", 64));
+                trashed_registers.insert(il::scalar("a7", 64));
+
+                let return_type = ReturnAddressType::Register(il::scalar("ra", 64));
+
+                CallingConvention {
+                    argument_registers,
+                    preserved_registers,
+                    trashed_registers,
+                    stack_argument_offset: 0,
+                    stack_argument_length: 8,
+                    return_address_type: return_type,
+                    return_register: il::scalar("a0", 64),
                 }
             }
         }
@@ -72,12 +72,14 @@ impl StackPointerOffset {
         Ok(match intermediate {
             IntermediateOffset::Top => StackPointerOffset::Top,
             IntermediateOffset::Bottom => StackPointerOffset::Bottom,
-            IntermediateOffset::Value(value) => StackPointerOffset::Value(
-                value
+            IntermediateOffset::Value(value) => {
+                let offset = value
                     .value_u64()
-                    .ok_or_else(|| ErrorKind::Analysis("Stack pointer was not u64".to_string()))?
-                    as isize,
-            ),
+                    .ok_or_else(|| ErrorKind::Analysis("Stack pointer was not u64".to_string()))?;
+                // Offsets below the entry value are negative
+                let shift = 64 - value.bits();
+                StackPointerOffset::Value(((offset << shift) as i64 >> shift) as isize)
+            }
         })
     }
 }
@@ -191,7 +193,7 @@ impl<'f> fixed_point::FixedPointAnalysis<'f, IntermediateOffset> for StackPointe
                     .ok_or("Unable to get function entry")??;
 
                 if location == function_entry {
-                    IntermediateOffset::Value(il::const_(0, 32))
+                    IntermediateOffset::Value(il::const_(0, self.stack_pointer.bits()))
                 } else {
                     IntermediateOffset::Top
                 }
@@ -232,3 +234,47 @@ impl<'f> fixed_point::FixedPointAnalysis<'f, IntermediateOffset> for StackPointe
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::architecture::Amd64;
+
+    #[test]
+    fn test_negative_offsets_64_bit() {
+        let architecture = Amd64::new();
+        let stack_pointer = architecture.stack_pointer();
+
+        let function = {
+            let mut cfg = il::ControlFlowGraph::new();
+
+            let index = {
+                let block = cfg.new_block().unwrap();
+                block.assign(
+                    stack_pointer.clone(),
+                    il::Expression::sub(stack_pointer.clone().into(), il::expr_const(8, 64))
+                        .unwrap(),
+                );
+                block.assign(
+                    stack_pointer.clone(),
+                    il::Expression::sub(stack_pointer.clone().into(), il::expr_const(0x20, 64))
+                        .unwrap(),
+                );
+                block.index()
+            };
+
+            cfg.set_entry(index).unwrap();
+            il::Function::new(0, cfg)
+        };
+
+        let offsets = stack_pointer_offsets(&function, &architecture).unwrap();
+
+        let mut values = offsets
+            .values()
+            .map(|offset| offset.value().unwrap())
+            .collect::<Vec<isize>>();
+        values.sort();
+
+        assert_eq!(values, vec![-0x28, -8]);
+    }
+}
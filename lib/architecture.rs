@@ -12,6 +12,66 @@ pub enum Endian {
     Little,
 }
 
+/// The general purpose registers of amd64, by register number, as the
+/// translator names them.
+pub const AMD64_REGISTERS: [&str; 16] = [
+    "rax", "rcx", "rdx", "rbx", "rsp", "rbp", "rsi", "rdi", "r8", "r9", "r10", "r11", "r12", "r13",
+    "r14", "r15",
+];
+
+/// The general purpose registers of x86, by register number.
+pub const X86_REGISTERS: [&str; 8] = ["eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi"];
+
+/// The general purpose registers of MIPS, by register number.
+pub const MIPS_REGISTERS: [&str; 32] = [
+    "$zero", "$at", "$v0", "$v1", "$a0", "$a1", "$a2", "$a3", "$t0", "$t1", "$t2", "$t3", "$t4",
+    "$t5", "$t6", "$t7", "$s0", "$s1", "$s2", "$s3", "$s4", "$s5", "$s6", "$s7", "$t8", "$t9",
+    "$k0", "$k1", "$gp", "$sp", "$fp", "$ra",
+];
+
+/// The general purpose registers of PowerPC, by register number.
+pub const PPC_REGISTERS: [&str; 32] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12", "r13", "r14",
+    "r15", "r16", "r17", "r18", "r19", "r20", "r21", "r22", "r23", "r24", "r25", "r26", "r27",
+    "r28", "r29", "r30", "r31",
+];
+
+/// The general purpose registers of AArch64, by register number, where 31 is
+/// the stack pointer.
+pub const AARCH64_REGISTERS: [&str; 32] = [
+    "x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9", "x10", "x11", "x12", "x13", "x14",
+    "x15", "x16", "x17", "x18", "x19", "x20", "x21", "x22", "x23", "x24", "x25", "x26", "x27",
+    "x28", "x29", "x30", "sp",
+];
+
+/// The general purpose registers of ARM, by register number, without the
+/// program counter.
+pub const ARM_REGISTERS: [&str; 15] = [
+    "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12", "sp", "lr",
+];
+
+/// The general purpose registers of RISC-V, by register number.
+pub const RISCV_REGISTERS: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+/// Get the general purpose registers of the architecture with the given name,
+/// by register number.
+pub fn general_purpose_registers(name: &str) -> Option<&'static [&'static str]> {
+    Some(match name {
+        "amd64" => &AMD64_REGISTERS,
+        "x86" => &X86_REGISTERS,
+        "mips" | "mipsel" | "mips64" | "mips64el" | "mipsn32" | "mipsn32el" => &MIPS_REGISTERS,
+        "ppc" | "ppc64" | "ppc64elfv2" | "ppc64le" => &PPC_REGISTERS,
+        "aarch64" => &AARCH64_REGISTERS,
+        "arm" => &ARM_REGISTERS,
+        "riscv32" | "riscv64" => &RISCV_REGISTERS,
+        _ => return None,
+    })
+}
+
 /// Necessary functions for analysis over architectures.
 pub trait Architecture: Debug + Send + Sync {
     /// Get the name of this architecture
@@ -31,6 +91,76 @@ pub trait Architecture: Debug + Send + Sync {
     fn box_clone(&self) -> Box<dyn Architecture>;
 }
 
+/// The 64-bit ARM Architecture.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct AArch64 {}
+
+impl AArch64 {
+    pub fn new() -> AArch64 {
+        AArch64 {}
+    }
+}
+
+impl Architecture for AArch64 {
+    fn name(&self) -> &str {
+        "aarch64"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Little
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::aarch64::AArch64::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::Aapcs64)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("sp", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 32-bit ARM Architecture, executing in either ARM or Thumb state.
+///
+/// Addresses of Thumb code carry the Thumb bit, the lowest bit, set.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Arm {}
+
+impl Arm {
+    pub fn new() -> Arm {
+        Arm {}
+    }
+}
+
+impl Architecture for Arm {
+    fn name(&self) -> &str {
+        "arm"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Little
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::arm::Arm::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::Aapcs)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("sp", 32)
+    }
+    fn word_size(&self) -> usize {
+        32
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
 /// The 64-bit X86 Architecture.
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct Amd64 {}
@@ -133,7 +263,143 @@ impl Architecture for Mipsel {
     }
 }
 
-/// The 32-bit Mips Architecture.
+/// The 64-bit Mips Architecture.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Mips64 {}
+
+impl Mips64 {
+    pub fn new() -> Mips64 {
+        Mips64 {}
+    }
+}
+
+impl Architecture for Mips64 {
+    fn name(&self) -> &str {
+        "mips64"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Big
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::mips::Mips64::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::MipsN64)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("$sp", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 64-bit Mipsel Architecture.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Mips64el {}
+
+impl Mips64el {
+    pub fn new() -> Mips64el {
+        Mips64el {}
+    }
+}
+
+impl Architecture for Mips64el {
+    fn name(&self) -> &str {
+        "mips64el"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Little
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::mips::Mips64el::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::MipsN64)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("$sp", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 64-bit Mips Architecture, with the n32 ABI's 32-bit pointers.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct MipsN32 {}
+
+impl MipsN32 {
+    pub fn new() -> MipsN32 {
+        MipsN32 {}
+    }
+}
+
+impl Architecture for MipsN32 {
+    fn name(&self) -> &str {
+        "mipsn32"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Big
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::mips::Mips64::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::MipsN32)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("$sp", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 64-bit Mipsel Architecture, with the n32 ABI's 32-bit pointers.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct MipselN32 {}
+
+impl MipselN32 {
+    pub fn new() -> MipselN32 {
+        MipselN32 {}
+    }
+}
+
+impl Architecture for MipselN32 {
+    fn name(&self) -> &str {
+        "mipsn32el"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Little
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::mips::Mips64el::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::MipsN32)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("$sp", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 32-bit PowerPC Architecture.
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct Ppc {}
 
@@ -167,6 +433,176 @@ impl Architecture for Ppc {
     }
 }
 
+/// The 64-bit big-endian PowerPC Architecture, with the ELFv1 ABI.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Ppc64 {}
+
+impl Ppc64 {
+    pub fn new() -> Ppc64 {
+        Ppc64 {}
+    }
+}
+
+impl Architecture for Ppc64 {
+    fn name(&self) -> &str {
+        "ppc64"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Big
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::ppc::Ppc64::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::Ppc64ElfV1)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("r1", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 64-bit big-endian PowerPC Architecture, with the ELFv2 ABI.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Ppc64ElfV2 {}
+
+impl Ppc64ElfV2 {
+    pub fn new() -> Ppc64ElfV2 {
+        Ppc64ElfV2 {}
+    }
+}
+
+impl Architecture for Ppc64ElfV2 {
+    fn name(&self) -> &str {
+        "ppc64elfv2"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Big
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::ppc::Ppc64::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::Ppc64ElfV2)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("r1", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 64-bit little-endian PowerPC Architecture.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Ppc64le {}
+
+impl Ppc64le {
+    pub fn new() -> Ppc64le {
+        Ppc64le {}
+    }
+}
+
+impl Architecture for Ppc64le {
+    fn name(&self) -> &str {
+        "ppc64le"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Little
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::ppc::Ppc64le::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::Ppc64ElfV2)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("r1", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 32-bit RISC-V Architecture.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Riscv32 {}
+
+impl Riscv32 {
+    pub fn new() -> Riscv32 {
+        Riscv32 {}
+    }
+}
+
+impl Architecture for Riscv32 {
+    fn name(&self) -> &str {
+        "riscv32"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Little
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::riscv::Riscv32::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::RiscvIlp32)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("sp", 32)
+    }
+    fn word_size(&self) -> usize {
+        32
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
+/// The 64-bit RISC-V Architecture.
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
+pub struct Riscv64 {}
+
+impl Riscv64 {
+    pub fn new() -> Riscv64 {
+        Riscv64 {}
+    }
+}
+
+impl Architecture for Riscv64 {
+    fn name(&self) -> &str {
+        "riscv64"
+    }
+    fn endian(&self) -> Endian {
+        Endian::Little
+    }
+    fn translator(&self) -> Box<dyn translator::Translator> {
+        Box::new(translator::riscv::Riscv64::new())
+    }
+    fn calling_convention(&self) -> CallingConvention {
+        CallingConvention::new(CallingConventionType::RiscvLp64)
+    }
+    fn stack_pointer(&self) -> il::Scalar {
+        il::scalar("sp", 64)
+    }
+    fn word_size(&self) -> usize {
+        64
+    }
+    fn box_clone(&self) -> Box<dyn Architecture> {
+        Box::new(self.clone())
+    }
+}
+
 /// The 32-bit X86 Architecture.
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct X86 {}
@@ -0,0 +1,113 @@
+//! Demangling of C++ and Rust symbol names.
+//!
+//! Compilers mangle the names of functions to encode their namespaces, types
+//! and parameters. `demangle` recovers a readable name from the Itanium C++
+//! ABI mangling used by GCC and Clang, both the legacy and v0 Rust manglings,
+//! and the MSVC mangling.
+//!
+//! Demangling needs the `demangle` feature, which is on by default. Without it,
+//! no name is demangled.
+//!
+//! ```
+//! # #[cfg(feature = "demangle")]
+//! # {
+//! use falcon::demangle::demangle;
+//!
+//! assert_eq!(
+//!     demangle("_ZNSt6vectorIiSaIiEE9push_backERKi").unwrap(),
+//!     "std::vector<int, std::allocator<int> >::push_back(int const&)"
+//! );
+//! assert_eq!(demangle("main"), None);
+//! # }
+//! ```
+
+/// Demangle a C++ name under the Itanium ABI.
+#[cfg(feature = "demangle")]
+fn demangle_itanium(name: &str) -> Option<String> {
+    let symbol = cpp_demangle::Symbol::new(name).ok()?;
+    symbol.demangle(&cpp_demangle::DemangleOptions::new()).ok()
+}
+
+/// Demangle a Rust name. Legacy Rust names look like Itanium C++ names, and
+/// are only taken as Rust if they demangle fully as Rust.
+#[cfg(feature = "demangle")]
+fn demangle_rust(name: &str) -> Option<String> {
+    let demangled = rustc_demangle::try_demangle(name).ok()?;
+    // The alternate form leaves out the hash
+    Some(format!("{:#}", demangled))
+}
+
+/// Demangle a C++ name under the MSVC mangling.
+#[cfg(feature = "demangle")]
+fn demangle_msvc(name: &str) -> Option<String> {
+    msvc_demangler::demangle(name, msvc_demangler::DemangleFlags::llvm()).ok()
+}
+
+/// Demangle a symbol name, or return `None` if it is not mangled, or can not
+/// be demangled.
+///
+/// A suffix added after mangling, such as the `@plt` of a PLT stub, is kept.
+#[cfg(feature = "demangle")]
+pub fn demangle(name: &str) -> Option<String> {
+    if name.starts_with('?') {
+        return demangle_msvc(name);
+    }
+
+    let (mangled, suffix) = match name.find('@') {
+        Some(index) => name.split_at(index),
+        None => (name, ""),
+    };
+
+    let demangled = if mangled.starts_with("_R") {
+        demangle_rust(mangled)
+    } else if mangled.starts_with("_Z") || mangled.starts_with("__Z") {
+        demangle_rust(mangled).or_else(|| demangle_itanium(mangled))
+    } else {
+        None
+    };
+
+    demangled.map(|demangled| format!("{}{}", demangled, suffix))
+}
+
+/// Demangle a symbol name. Without the `demangle` feature, this always
+/// returns `None`.
+#[cfg(not(feature = "demangle"))]
+pub fn demangle(_name: &str) -> Option<String> {
+    None
+}
+
+#[cfg(all(test, feature = "demangle"))]
+mod tests {
+    use super::demangle;
+
+    #[test]
+    fn itanium() {
+        assert_eq!(
+            demangle("_ZNSt6vectorIiSaIiEE9push_backERKi").unwrap(),
+            "std::vector<int, std::allocator<int> >::push_back(int const&)"
+        );
+        assert_eq!(demangle("_ZN3foo3barEv").unwrap(), "foo::bar()");
+        assert_eq!(demangle("_ZN3foo3barEv@plt").unwrap(), "foo::bar()@plt");
+    }
+
+    #[test]
+    fn rust() {
+        assert_eq!(
+            demangle("_ZN4core3fmt5write17h0123456789abcdefE").unwrap(),
+            "core::fmt::write"
+        );
+        assert_eq!(demangle("_RNvC6_123foo3bar").unwrap(), "123foo::bar");
+    }
+
+    #[test]
+    fn msvc() {
+        assert_eq!(demangle("?foo@@YAHH@Z").unwrap(), "int __cdecl foo(int)");
+    }
+
+    #[test]
+    fn not_mangled() {
+        assert_eq!(demangle("main"), None);
+        assert_eq!(demangle("printf@plt"), None);
+        assert_eq!(demangle("_Znot_mangled"), None);
+    }
+}
@@ -35,6 +35,17 @@ pub fn eval(expr: &il::Expression) -> Result<il::Constant> {
                 eval(else_)?
             }
         }
+        il::Expression::Fadd(ref lhs, ref rhs) => eval(lhs)?.fadd(&eval(rhs)?)?,
+        il::Expression::Fsub(ref lhs, ref rhs) => eval(lhs)?.fsub(&eval(rhs)?)?,
+        il::Expression::Fmul(ref lhs, ref rhs) => eval(lhs)?.fmul(&eval(rhs)?)?,
+        il::Expression::Fdiv(ref lhs, ref rhs) => eval(lhs)?.fdiv(&eval(rhs)?)?,
+        il::Expression::Fcmpeq(ref lhs, ref rhs) => eval(lhs)?.fcmpeq(&eval(rhs)?)?,
+        il::Expression::Fcmplt(ref lhs, ref rhs) => eval(lhs)?.fcmplt(&eval(rhs)?)?,
+        il::Expression::Fcmpuno(ref lhs, ref rhs) => eval(lhs)?.fcmpuno(&eval(rhs)?)?,
+        il::Expression::Itof(bits, ref rhs) => eval(rhs)?.itof(bits)?,
+        il::Expression::Ftoi(bits, ref rhs) => eval(rhs)?.ftoi(bits)?,
+        il::Expression::Fconv(bits, ref rhs) => eval(rhs)?.fconv(bits)?,
+        il::Expression::Fround(ref rhs) => eval(rhs)?.fround()?,
     })
 }
 
@@ -63,3 +74,22 @@ fn cmplts() {
     let expr = il::Expression::cmplts(lhs, rhs).unwrap();
     assert_eq!(eval(&expr).unwrap(), il::const_(0, 1));
 }
+
+#[test]
+fn fdiv() {
+    let lhs = il::expr_const(1f64.to_bits(), 64);
+    let rhs = il::expr_const(4f64.to_bits(), 64);
+    let expr = il::Expression::fdiv(lhs, rhs).unwrap();
+    assert_eq!(eval(&expr).unwrap(), il::const_(0.25f64.to_bits(), 64));
+
+    let expr = il::Expression::ftoi(
+        32,
+        il::Expression::fmul(
+            il::Expression::itof(64, il::expr_const(7, 32)).unwrap(),
+            il::expr_const(1.5f64.to_bits(), 64),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(eval(&expr).unwrap(), il::const_(10, 32));
+}
@@ -6,11 +6,13 @@ use crate::memory;
 
 mod driver;
 mod eval;
+mod process;
 mod state;
 mod successor;
 
 pub use self::driver::*;
 pub use self::eval::eval;
+pub use self::process::ProcessBuilder;
 pub use self::state::*;
 pub use self::successor::*;
 
@@ -0,0 +1,407 @@
+//! The initial image of a process, for concrete execution from its entry.
+
+use crate::architecture::Endian;
+use crate::error::*;
+use crate::executor::{Driver, Memory, State};
+use crate::il;
+use crate::loader::Loader;
+use crate::memory::MemoryPermissions;
+use crate::RC;
+
+/// The auxiliary vector entry which ends the auxiliary vector.
+const AT_NULL: u64 = 0;
+
+/// The stack pointer is aligned to this many bytes at the program entry.
+const STACK_ALIGNMENT: u64 = 16;
+
+/// The size of the stack, when none is given.
+const DEFAULT_STACK_SIZE: u64 = 0x10_0000;
+
+/// The address the stack ends at, when none is given, by word size.
+fn default_stack_end(word_size: usize) -> u64 {
+    match word_size {
+        32 => 0x8000_0000,
+        _ => 0x7fff_ffff_0000,
+    }
+}
+
+/// Write a word to `bytes` at `offset`.
+fn put_word(bytes: &mut [u8], offset: usize, value: u64, word_bytes: usize, endian: &Endian) {
+    for i in 0..word_bytes {
+        let byte = (value >> (i * 8)) as u8;
+        match *endian {
+            Endian::Big => bytes[offset + word_bytes - 1 - i] = byte,
+            Endian::Little => bytes[offset + i] = byte,
+        }
+    }
+}
+
+/// A helper to build the initial image of a process using the builder
+/// pattern.
+///
+/// The stack is laid out as the Linux kernel lays it out for a new process.
+/// The stack pointer points to `argc`, which is followed by the `argv`
+/// pointers, the `envp` pointers and the auxiliary vector, each ended by a
+/// null entry. The strings they point to sit above them, at the end of the
+/// stack. Registers which the architecture's ABI gives a value at the program
+/// entry are set:
+///
+/// * **x86** and **amd64** - `edx`/`rdx` is zero, for no termination function.
+/// * **mips** - `$t9` holds the program entry, and `$v0` and `$ra` are zero.
+/// * **ppc** - `r3` to `r6` hold `argc`, `argv`, `envp` and the auxiliary
+/// vector, and `r7` is zero.
+///
+/// Every other register is left unset, and should be set through
+/// `Driver::state_mut` if the program reads it before writing it.
+///
+/// ```
+/// # use falcon::error::*;
+/// use falcon::executor::ProcessBuilder;
+/// use falcon::loader::Elf;
+/// use std::path::Path;
+///
+/// # fn example () -> Result<()> {
+/// let elf = Elf::from_file(Path::new("test_binaries/simple-0/simple-0"))?;
+/// let mut driver = ProcessBuilder::new()
+///     .argv(vec!["simple-0", "--verbose"])
+///     .envp(vec!["HOME=/root"])
+///     .build(&elf)?;
+/// for _ in 0..100 {
+///     driver = driver.step()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ProcessBuilder {
+    argv: Vec<String>,
+    envp: Vec<String>,
+    auxv: Vec<(u64, u64)>,
+    stack: Option<(u64, u64)>,
+}
+
+impl ProcessBuilder {
+    /// Create a new `ProcessBuilder`, with no arguments, environment or
+    /// auxiliary vector.
+    pub fn new() -> ProcessBuilder {
+        ProcessBuilder::default()
+    }
+
+    /// Set the arguments of the process, starting with the program name.
+    pub fn argv<S: Into<String>>(mut self, argv: Vec<S>) -> Self {
+        self.argv = argv.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    /// Set the environment of the process, as `NAME=value` strings.
+    pub fn envp<S: Into<String>>(mut self, envp: Vec<S>) -> Self {
+        self.envp = envp.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    /// Set the auxiliary vector of the process, as pairs of type and value.
+    ///
+    /// The `AT_NULL` entry which ends the auxiliary vector is added for you.
+    pub fn auxv(mut self, auxv: Vec<(u64, u64)>) -> Self {
+        self.auxv = auxv;
+        self
+    }
+
+    /// Set the lowest address and the size of the stack.
+    ///
+    /// By default, a 1 MiB stack ends at `0x8000_0000` for 32-bit
+    /// architectures, or at `0x7fff_ffff_0000` for 64-bit architectures.
+    pub fn stack(mut self, address: u64, size: u64) -> Self {
+        self.stack = Some((address, size));
+        self
+    }
+
+    /// Create the stack of the process, returning its bytes and the address
+    /// of `argc`, the initial stack pointer.
+    fn stack_image(
+        &self,
+        address: u64,
+        size: u64,
+        word_bytes: usize,
+        endian: &Endian,
+    ) -> Result<(Vec<u8>, u64)> {
+        let mut stack = vec![0; size as usize];
+        let mut offset = stack.len();
+
+        let mut push_string = |string: &str| -> Result<u64> {
+            let bytes = string.as_bytes();
+            offset = offset
+                .checked_sub(bytes.len() + 1)
+                .ok_or("Arguments and environment do not fit on the stack")?;
+            stack[offset..(offset + bytes.len())].copy_from_slice(bytes);
+            stack[offset + bytes.len()] = 0;
+            Ok(address + offset as u64)
+        };
+
+        // The strings go at the very end of the stack, environment first
+        let mut envp = Vec::new();
+        for string in self.envp.iter().rev() {
+            envp.push(push_string(string)?);
+        }
+        envp.reverse();
+        let mut argv = Vec::new();
+        for string in self.argv.iter().rev() {
+            argv.push(push_string(string)?);
+        }
+        argv.reverse();
+
+        let mut words = vec![argv.len() as u64];
+        words.extend(argv);
+        words.push(0);
+        words.extend(envp);
+        words.push(0);
+        for (type_, value) in &self.auxv {
+            words.push(*type_);
+            words.push(*value);
+        }
+        words.push(AT_NULL);
+        words.push(0);
+
+        let stack_pointer = (address + offset as u64)
+            .checked_sub((words.len() * word_bytes) as u64)
+            .map(|stack_pointer| stack_pointer & !(STACK_ALIGNMENT - 1))
+            .filter(|stack_pointer| *stack_pointer >= address)
+            .ok_or("Arguments and environment do not fit on the stack")?;
+
+        for (i, word) in words.into_iter().enumerate() {
+            let offset = (stack_pointer - address) as usize + i * word_bytes;
+            put_word(&mut stack, offset, word, word_bytes, endian);
+        }
+
+        Ok((stack, stack_pointer))
+    }
+
+    /// Create the process for the program of the given loader, returning a
+    /// `Driver` at the program entry.
+    pub fn build(&self, loader: &dyn Loader) -> Result<Driver> {
+        let architecture = loader.architecture();
+        let word_size = architecture.word_size();
+        let word_bytes = word_size / 8;
+        let endian = architecture.endian();
+
+        let (stack_address, stack_size) = self.stack.unwrap_or_else(|| {
+            let end = default_stack_end(word_size);
+            (end - DEFAULT_STACK_SIZE, DEFAULT_STACK_SIZE)
+        });
+        let stack_end = stack_address
+            .checked_add(stack_size)
+            .ok_or("Stack extends past the end of memory")?;
+
+        let mut backing = loader.memory()?;
+        if backing.sections().iter().any(|(address, section)| {
+            *address < stack_end && address + section.len() as u64 > stack_address
+        }) {
+            bail!("Stack overlaps the memory of the program");
+        }
+
+        let (stack, stack_pointer) =
+            self.stack_image(stack_address, stack_size, word_bytes, &endian)?;
+        backing.set_memory(
+            stack_address,
+            stack,
+            MemoryPermissions::READ | MemoryPermissions::WRITE,
+        );
+
+        let memory = Memory::new_with_backing(endian, RC::new(backing));
+        let mut state = State::new(memory);
+        state.set_scalar(
+            architecture.stack_pointer().name(),
+            il::const_(stack_pointer, word_size),
+        );
+
+        let entry = loader.program_entry();
+        let word = |value: u64| il::const_(value, word_size);
+        match architecture.name() {
+            "amd64" => state.set_scalar("rdx", word(0)),
+            "x86" => state.set_scalar("edx", word(0)),
+            "mips" | "mipsel" | "mips64" | "mips64el" | "mipsn32" | "mipsn32el" => {
+                state.set_scalar("$t9", word(entry));
+                state.set_scalar("$v0", word(0));
+                state.set_scalar("$ra", word(0));
+            }
+            "ppc" | "ppc64" | "ppc64elfv2" | "ppc64le" => {
+                let argv = stack_pointer + word_bytes as u64;
+                let envp = argv + ((self.argv.len() + 1) * word_bytes) as u64;
+                let auxv = envp + ((self.envp.len() + 1) * word_bytes) as u64;
+                state.set_scalar("r3", word(self.argv.len() as u64));
+                state.set_scalar("r4", word(argv));
+                state.set_scalar("r5", word(envp));
+                state.set_scalar("r6", word(auxv));
+                state.set_scalar("r7", word(0));
+            }
+            _ => {}
+        }
+
+        let mut program = il::Program::new();
+        program.add_function(loader.function(entry)?);
+        let location: il::ProgramLocation = il::RefProgramLocation::from_address(&program, entry)
+            .ok_or("Failed to get location for the program entry")?
+            .into();
+
+        Ok(Driver::new(
+            RC::new(program),
+            location,
+            state,
+            RC::from(architecture.box_clone()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::architecture::*;
+    use crate::loader::Raw;
+
+    fn build(raw: &Raw) -> Driver {
+        ProcessBuilder::new()
+            .argv(vec!["prog", "-v"])
+            .envp(vec!["HOME=/"])
+            .auxv(vec![(6, 0x1000)])
+            .build(raw)
+            .unwrap()
+    }
+
+    fn word(driver: &Driver, address: u64, bits: usize) -> u64 {
+        driver
+            .state()
+            .memory()
+            .load(address, bits)
+            .unwrap()
+            .unwrap()
+            .value_u64()
+            .unwrap()
+    }
+
+    fn string(driver: &Driver, address: u64) -> String {
+        let mut bytes = Vec::new();
+        for address in address.. {
+            let byte = driver
+                .state()
+                .memory()
+                .load(address, 8)
+                .unwrap()
+                .unwrap()
+                .value_u64()
+                .unwrap() as u8;
+            if byte == 0 {
+                break;
+            }
+            bytes.push(byte);
+        }
+        String::from_utf8(bytes).unwrap()
+    }
+
+    fn scalar(driver: &Driver, name: &str) -> u64 {
+        driver
+            .state()
+            .get_scalar(name)
+            .unwrap()
+            .value_u64()
+            .unwrap()
+    }
+
+    /// Check the stack is laid out for `argv`, `envp` and `auxv` given by
+    /// `build`, returning the stack pointer.
+    fn check_stack(driver: &Driver, architecture: &dyn Architecture) -> u64 {
+        let bits = architecture.word_size();
+        let word_bytes = bits as u64 / 8;
+        let sp = scalar(driver, architecture.stack_pointer().name());
+        assert_eq!(sp % 16, 0);
+
+        let words: Vec<u64> = (0..9)
+            .map(|i| word(driver, sp + i * word_bytes, bits))
+            .collect();
+        assert_eq!(words[0], 2);
+        assert_eq!(string(driver, words[1]), "prog");
+        assert_eq!(string(driver, words[2]), "-v");
+        assert_eq!(words[3], 0);
+        assert_eq!(string(driver, words[4]), "HOME=/");
+        assert_eq!(&words[5..], &[0, 6, 0x1000, AT_NULL]);
+        assert_eq!(word(driver, sp + 9 * word_bytes, bits), 0);
+
+        sp
+    }
+
+    #[test]
+    fn x86() {
+        let raw = Raw::new(vec![0xc3], 0x1000, Box::new(X86::new()));
+        let driver = build(&raw);
+
+        assert_eq!(driver.address(), Some(0x1000));
+        let sp = check_stack(&driver, &X86::new());
+        assert!((0x7ff0_0000..0x8000_0000).contains(&sp));
+        assert_eq!(scalar(&driver, "edx"), 0);
+    }
+
+    #[test]
+    fn amd64() {
+        let raw = Raw::new(vec![0xc3], 0x40_0000, Box::new(Amd64::new()));
+        let driver = build(&raw);
+
+        assert_eq!(driver.address(), Some(0x40_0000));
+        let sp = check_stack(&driver, &Amd64::new());
+        assert!(sp > 0x7fff_0000_0000);
+        assert_eq!(scalar(&driver, "rdx"), 0);
+    }
+
+    #[test]
+    fn mips() {
+        // jr $ra; nop
+        let bytes = vec![0x03, 0xe0, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00];
+        let raw = Raw::new(bytes, 0x40_0000, Box::new(Mips::new()));
+        let driver = build(&raw);
+
+        assert_eq!(driver.address(), Some(0x40_0000));
+        check_stack(&driver, &Mips::new());
+        assert_eq!(scalar(&driver, "$t9"), 0x40_0000);
+        assert_eq!(scalar(&driver, "$ra"), 0);
+    }
+
+    #[test]
+    fn ppc() {
+        // blr
+        let raw = Raw::new(
+            vec![0x4e, 0x80, 0x00, 0x20],
+            0x1000_0000,
+            Box::new(Ppc::new()),
+        );
+        let driver = ProcessBuilder::new()
+            .argv(vec!["prog", "-v"])
+            .envp(vec!["HOME=/"])
+            .auxv(vec![(6, 0x1000)])
+            .stack(0x2000_0000, 0x1000)
+            .build(&raw)
+            .unwrap();
+
+        assert_eq!(driver.address(), Some(0x1000_0000));
+        let sp = check_stack(&driver, &Ppc::new());
+        assert!((0x2000_0000..0x2000_1000).contains(&sp));
+        assert_eq!(scalar(&driver, "r3"), 2);
+        assert_eq!(scalar(&driver, "r4"), sp + 4);
+        assert_eq!(scalar(&driver, "r5"), sp + 16);
+        assert_eq!(scalar(&driver, "r6"), sp + 24);
+    }
+
+    #[test]
+    fn stack_too_small() {
+        let raw = Raw::new(vec![0xc3], 0x1000, Box::new(X86::new()));
+        let result = ProcessBuilder::new()
+            .argv(vec!["prog"])
+            .stack(0x2000_0000, 8)
+            .build(&raw);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn stack_overlaps_program() {
+        let raw = Raw::new(vec![0xc3; 0x100], 0x1000, Box::new(X86::new()));
+        let result = ProcessBuilder::new().stack(0x800, 0x1000).build(&raw);
+        assert!(result.is_err());
+    }
+}
@@ -129,6 +129,68 @@ impl State {
                 self.symbolize_expression(then)?,
                 self.symbolize_expression(else_)?,
             )?,
+            il::Expression::Fadd(_, _)
+            | il::Expression::Fsub(_, _)
+            | il::Expression::Fmul(_, _)
+            | il::Expression::Fdiv(_, _)
+            | il::Expression::Fcmpeq(_, _)
+            | il::Expression::Fcmplt(_, _)
+            | il::Expression::Fcmpuno(_, _)
+            | il::Expression::Itof(_, _)
+            | il::Expression::Ftoi(_, _)
+            | il::Expression::Fconv(_, _)
+            | il::Expression::Fround(_) => self.symbolize_float_expression(expression)?,
+        })
+    }
+
+    /// Symbolize a floating-point expression.
+    ///
+    /// This is kept apart from `symbolize_expression` to keep the stack frame
+    /// of `symbolize_expression`, which recurses through deeply nested
+    /// expressions, small.
+    fn symbolize_float_expression(&self, expression: &il::Expression) -> Result<il::Expression> {
+        Ok(match *expression {
+            il::Expression::Fadd(ref lhs, ref rhs) => il::Expression::fadd(
+                self.symbolize_expression(lhs)?,
+                self.symbolize_expression(rhs)?,
+            )?,
+            il::Expression::Fsub(ref lhs, ref rhs) => il::Expression::fsub(
+                self.symbolize_expression(lhs)?,
+                self.symbolize_expression(rhs)?,
+            )?,
+            il::Expression::Fmul(ref lhs, ref rhs) => il::Expression::fmul(
+                self.symbolize_expression(lhs)?,
+                self.symbolize_expression(rhs)?,
+            )?,
+            il::Expression::Fdiv(ref lhs, ref rhs) => il::Expression::fdiv(
+                self.symbolize_expression(lhs)?,
+                self.symbolize_expression(rhs)?,
+            )?,
+            il::Expression::Fcmpeq(ref lhs, ref rhs) => il::Expression::fcmpeq(
+                self.symbolize_expression(lhs)?,
+                self.symbolize_expression(rhs)?,
+            )?,
+            il::Expression::Fcmplt(ref lhs, ref rhs) => il::Expression::fcmplt(
+                self.symbolize_expression(lhs)?,
+                self.symbolize_expression(rhs)?,
+            )?,
+            il::Expression::Fcmpuno(ref lhs, ref rhs) => il::Expression::fcmpuno(
+                self.symbolize_expression(lhs)?,
+                self.symbolize_expression(rhs)?,
+            )?,
+            il::Expression::Itof(bits, ref src) => {
+                il::Expression::itof(bits, self.symbolize_expression(src)?)?
+            }
+            il::Expression::Ftoi(bits, ref src) => {
+                il::Expression::ftoi(bits, self.symbolize_expression(src)?)?
+            }
+            il::Expression::Fconv(bits, ref src) => {
+                il::Expression::fconv(bits, self.symbolize_expression(src)?)?
+            }
+            il::Expression::Fround(ref src) => {
+                il::Expression::fround(self.symbolize_expression(src)?)?
+            }
+            _ => unreachable!(),
         })
     }
 
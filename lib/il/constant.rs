@@ -350,6 +350,189 @@ impl Constant {
             Ok(Constant::new_big(value, bits))
         }
     }
+
+    /// Interpret this constant as an IEEE 754 float of its bitness.
+    fn to_f64(&self) -> Result<f64> {
+        let value = self.value_u64().ok_or(ErrorKind::Sort)?;
+        match self.bits {
+            32 => Ok(f64::from(f32::from_bits(value as u32))),
+            64 => Ok(f64::from_bits(value)),
+            _ => Err(ErrorKind::Sort.into()),
+        }
+    }
+
+    /// Encode a float as a constant of the given bitness.
+    fn from_f64(value: f64, bits: usize) -> Result<Constant> {
+        match bits {
+            32 => Ok(Constant::new(u64::from((value as f32).to_bits()), 32)),
+            64 => Ok(Constant::new(value.to_bits(), 64)),
+            _ => Err(ErrorKind::Sort.into()),
+        }
+    }
+
+    /// Apply a floating-point operation over two floats of the same bitness,
+    /// performing single-precision operations in single precision.
+    fn float_op<F, D>(&self, rhs: &Constant, f: F, d: D) -> Result<Constant>
+    where
+        F: Fn(f32, f32) -> f32,
+        D: Fn(f64, f64) -> f64,
+    {
+        if self.bits != rhs.bits {
+            return Err(ErrorKind::Sort.into());
+        }
+        let lhs = self.to_f64()?;
+        let rhs = rhs.to_f64()?;
+        match self.bits {
+            32 => Constant::from_f64(f64::from(f(lhs as f32, rhs as f32)), 32),
+            _ => Constant::from_f64(d(lhs, rhs), self.bits),
+        }
+    }
+
+    /// Apply a floating-point comparison over two floats of the same bitness.
+    fn float_cmp<F>(&self, rhs: &Constant, f: F) -> Result<Constant>
+    where
+        F: Fn(f64, f64) -> bool,
+    {
+        if self.bits != rhs.bits {
+            return Err(ErrorKind::Sort.into());
+        }
+        Ok(Constant::new(f(self.to_f64()?, rhs.to_f64()?) as u64, 1))
+    }
+
+    /// Decode an 80-bit x87 extended precision float.
+    fn extended_to_f64(&self) -> f64 {
+        let mantissa = (self.value.clone() & BigUint::from_u64(u64::MAX).unwrap())
+            .to_u64()
+            .unwrap();
+        let sign_exponent = (self.value.clone() >> 64).to_u64().unwrap();
+        let sign = if sign_exponent & 0x8000 != 0 {
+            -1.0
+        } else {
+            1.0
+        };
+        let exponent = (sign_exponent & 0x7fff) as i32;
+
+        if exponent == 0x7fff {
+            if mantissa << 1 == 0 {
+                sign * f64::INFINITY
+            } else {
+                f64::from_bits(
+                    ((sign_exponent & 0x8000) << 48)
+                        | 0x7ff8_0000_0000_0000
+                        | (mantissa << 1 >> 12),
+                )
+            }
+        } else if mantissa == 0 {
+            sign * 0.0
+        } else {
+            // Scale in steps so intermediate values neither overflow nor
+            // underflow before the final result.
+            let mut value = mantissa as f64;
+            let mut exponent = exponent - 16383 - 63;
+            while exponent > 1000 {
+                value *= 2f64.powi(1000);
+                exponent -= 1000;
+            }
+            while exponent < -1000 {
+                value *= 2f64.powi(-1000);
+                exponent += 1000;
+            }
+            sign * value * 2f64.powi(exponent)
+        }
+    }
+
+    /// Encode a float as an 80-bit x87 extended precision float.
+    fn f64_to_extended(value: f64) -> Constant {
+        let bits = value.to_bits();
+        let sign = (bits >> 63) << 15;
+        let exponent = (bits >> 52) & 0x7ff;
+        let fraction = bits & 0x000f_ffff_ffff_ffff;
+
+        let (exponent, mantissa) = if exponent == 0x7ff {
+            (0x7fff, (1 << 63) | (fraction << 11))
+        } else if exponent == 0 && fraction == 0 {
+            (0, 0)
+        } else if exponent == 0 {
+            let shift = u64::from(fraction.leading_zeros());
+            (16383 - 1011 - shift, fraction << shift)
+        } else {
+            (exponent + 16383 - 1023, (1 << 63) | (fraction << 11))
+        };
+
+        let value = (BigUint::from_u64(sign | exponent).unwrap() << 64)
+            | BigUint::from_u64(mantissa).unwrap();
+        Constant::new_big(value, 80)
+    }
+
+    pub fn fadd(&self, rhs: &Constant) -> Result<Constant> {
+        self.float_op(rhs, |l, r| l + r, |l, r| l + r)
+    }
+
+    pub fn fsub(&self, rhs: &Constant) -> Result<Constant> {
+        self.float_op(rhs, |l, r| l - r, |l, r| l - r)
+    }
+
+    pub fn fmul(&self, rhs: &Constant) -> Result<Constant> {
+        self.float_op(rhs, |l, r| l * r, |l, r| l * r)
+    }
+
+    pub fn fdiv(&self, rhs: &Constant) -> Result<Constant> {
+        self.float_op(rhs, |l, r| l / r, |l, r| l / r)
+    }
+
+    pub fn fcmpeq(&self, rhs: &Constant) -> Result<Constant> {
+        self.float_cmp(rhs, |l, r| l == r)
+    }
+
+    pub fn fcmplt(&self, rhs: &Constant) -> Result<Constant> {
+        self.float_cmp(rhs, |l, r| l < r)
+    }
+
+    pub fn fcmpuno(&self, rhs: &Constant) -> Result<Constant> {
+        self.float_cmp(rhs, |l, r| l.is_nan() || r.is_nan())
+    }
+
+    pub fn itof(&self, bits: usize) -> Result<Constant> {
+        let value = self.value_i64().ok_or(ErrorKind::Sort)?;
+        match bits {
+            32 => Constant::from_f64(f64::from(value as f32), 32),
+            _ => Constant::from_f64(value as f64, bits),
+        }
+    }
+
+    pub fn ftoi(&self, bits: usize) -> Result<Constant> {
+        if bits > 64 || bits == 0 {
+            return Err(ErrorKind::Sort.into());
+        }
+        let value = self.to_f64()?.trunc();
+        let limit = 2f64.powi(bits as i32 - 1);
+        if value.is_nan() || value < -limit || value >= limit {
+            Ok(Constant::new(1 << (bits - 1), bits))
+        } else {
+            Ok(Constant::new(value as i64 as u64, bits))
+        }
+    }
+
+    pub fn fconv(&self, bits: usize) -> Result<Constant> {
+        let value = match self.bits {
+            80 => self.extended_to_f64(),
+            _ => self.to_f64()?,
+        };
+        match bits {
+            80 => Ok(Constant::f64_to_extended(value)),
+            _ => Constant::from_f64(value, bits),
+        }
+    }
+
+    pub fn fround(&self) -> Result<Constant> {
+        let value = self.to_f64()?;
+        let rounded = if (value - value.trunc()).abs() == 0.5 {
+            2.0 * (value / 2.0).round()
+        } else {
+            value.round()
+        };
+        Constant::from_f64(rounded, self.bits)
+    }
 }
 
 impl fmt::Display for Constant {
@@ -527,3 +710,88 @@ fn constant_cmplts() {
         Constant::new(1, 1)
     );
 }
+
+#[test]
+fn constant_fadd() {
+    let one = Constant::new(1f64.to_bits(), 64);
+    let two = Constant::new(2f64.to_bits(), 64);
+    assert_eq!(one.fadd(&two).unwrap(), Constant::new(3f64.to_bits(), 64));
+    assert_eq!(
+        Constant::new(u64::from(1.5f32.to_bits()), 32)
+            .fadd(&Constant::new(u64::from(0.25f32.to_bits()), 32))
+            .unwrap(),
+        Constant::new(u64::from(1.75f32.to_bits()), 32)
+    );
+    assert!(one.fadd(&Constant::new(1, 32)).is_err());
+}
+
+#[test]
+fn constant_fcmp() {
+    let one = Constant::new(1f64.to_bits(), 64);
+    let nan = Constant::new(f64::NAN.to_bits(), 64);
+    assert!(one.fcmpeq(&one).unwrap().is_one());
+    assert!(nan.fcmpeq(&nan).unwrap().is_zero());
+    assert!(nan.fcmplt(&one).unwrap().is_zero());
+    assert!(nan.fcmpuno(&one).unwrap().is_one());
+    assert!(one.fcmpuno(&one).unwrap().is_zero());
+}
+
+#[test]
+fn constant_itof_ftoi() {
+    assert_eq!(
+        Constant::new(-3i64 as u64, 32).itof(64).unwrap(),
+        Constant::new((-3f64).to_bits(), 64)
+    );
+    assert_eq!(
+        Constant::new((-3.75f64).to_bits(), 64).ftoi(32).unwrap(),
+        Constant::new(-3i64 as u64, 32)
+    );
+    assert_eq!(
+        Constant::new(f64::NAN.to_bits(), 64).ftoi(32).unwrap(),
+        Constant::new(0x8000_0000, 32)
+    );
+    assert_eq!(
+        Constant::new(u64::from(3e9f32.to_bits()), 32)
+            .ftoi(32)
+            .unwrap(),
+        Constant::new(0x8000_0000, 32)
+    );
+}
+
+#[test]
+fn constant_fconv() {
+    let value = Constant::new((-1.5f64).to_bits(), 64);
+    let extended = value.fconv(80).unwrap();
+    assert_eq!(
+        extended,
+        Constant::new_big(
+            (BigUint::from_u64(0xbfff).unwrap() << 64)
+                | BigUint::from_u64(0xc000_0000_0000_0000).unwrap(),
+            80
+        )
+    );
+    assert_eq!(extended.fconv(64).unwrap(), value);
+    assert_eq!(
+        value.fconv(32).unwrap(),
+        Constant::new(u64::from((-1.5f32).to_bits()), 32)
+    );
+
+    let tiny = Constant::new(1, 64);
+    assert_eq!(tiny.fconv(80).unwrap().fconv(64).unwrap(), tiny);
+}
+
+#[test]
+fn constant_fround() {
+    let round = |v: f64| {
+        Constant::new(v.to_bits(), 64)
+            .fround()
+            .unwrap()
+            .value_u64()
+            .map(f64::from_bits)
+            .unwrap()
+    };
+    assert_eq!(round(2.5), 2.0);
+    assert_eq!(round(3.5), 4.0);
+    assert_eq!(round(-2.5), -2.0);
+    assert_eq!(round(2.6), 3.0);
+}
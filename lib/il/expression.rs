@@ -20,6 +20,14 @@
 //!
 //! ## Extension/Truncation
 //! `zext`, `sext`, `trun`
+//!
+//! ## Floating Point
+//! `fadd`, `fsub`, `fmul`, `fdiv`, `fcmpeq`, `fcmplt`, `fcmpuno`, `itof`, `ftoi`, `fconv`,
+//! `fround`
+//!
+//! Floating-point expressions operate over the IEEE 754 encoding of their operands. Arithmetic,
+//! comparison and rounding is performed over 32-bit and 64-bit floats, and `fconv` additionally
+//! converts to and from the 80-bit x87 extended format.
 
 use std::fmt;
 
@@ -54,6 +62,20 @@ pub enum Expression {
     Trun(usize, Box<Expression>),
 
     Ite(Box<Expression>, Box<Expression>, Box<Expression>),
+
+    Fadd(Box<Expression>, Box<Expression>),
+    Fsub(Box<Expression>, Box<Expression>),
+    Fmul(Box<Expression>, Box<Expression>),
+    Fdiv(Box<Expression>, Box<Expression>),
+
+    Fcmpeq(Box<Expression>, Box<Expression>),
+    Fcmplt(Box<Expression>, Box<Expression>),
+    Fcmpuno(Box<Expression>, Box<Expression>),
+
+    Itof(usize, Box<Expression>),
+    Ftoi(usize, Box<Expression>),
+    Fconv(usize, Box<Expression>),
+    Fround(Box<Expression>),
 }
 
 impl Expression {
@@ -73,15 +95,26 @@ impl Expression {
             | Expression::Or(ref lhs, _)
             | Expression::Xor(ref lhs, _)
             | Expression::Shl(ref lhs, _)
-            | Expression::Shr(ref lhs, _) => lhs.bits(),
+            | Expression::Shr(ref lhs, _)
+            | Expression::Fadd(ref lhs, _)
+            | Expression::Fsub(ref lhs, _)
+            | Expression::Fmul(ref lhs, _)
+            | Expression::Fdiv(ref lhs, _) => lhs.bits(),
             Expression::Cmpeq(_, _)
             | Expression::Cmpneq(_, _)
             | Expression::Cmplts(_, _)
-            | Expression::Cmpltu(_, _) => 1,
-            Expression::Zext(bits, _) | Expression::Sext(bits, _) | Expression::Trun(bits, _) => {
-                bits
-            }
+            | Expression::Cmpltu(_, _)
+            | Expression::Fcmpeq(_, _)
+            | Expression::Fcmplt(_, _)
+            | Expression::Fcmpuno(_, _) => 1,
+            Expression::Zext(bits, _)
+            | Expression::Sext(bits, _)
+            | Expression::Trun(bits, _)
+            | Expression::Itof(bits, _)
+            | Expression::Ftoi(bits, _)
+            | Expression::Fconv(bits, _) => bits,
             Expression::Ite(_, ref lhs, _) => lhs.bits(),
+            Expression::Fround(ref src) => src.bits(),
         }
     }
 
@@ -94,6 +127,21 @@ impl Expression {
         }
     }
 
+    /// Ensures the bits of the expression are those of a supported float.
+    fn ensure_float(expression: &Expression) -> Result<()> {
+        match expression.bits() {
+            32 | 64 => Ok(()),
+            _ => Err(ErrorKind::Sort.into()),
+        }
+    }
+
+    /// Ensures the bits of both lhs and rhs are the same, and a supported
+    /// float.
+    fn ensure_float_sort(lhs: &Expression, rhs: &Expression) -> Result<()> {
+        Expression::ensure_sort(lhs, rhs)?;
+        Expression::ensure_float(lhs)
+    }
+
     /// Takes a closure which modifies an existing `Expression`
     ///
     /// The closure takes an expression, and returns an Option<Expression>. If
@@ -173,6 +221,33 @@ impl Expression {
                         Expression::Ite(ref cond, ref then, ref else_) => {
                             Expression::ite(self.map(cond)?, self.map(then)?, self.map(else_)?)?
                         }
+                        Expression::Fadd(ref lhs, ref rhs) => {
+                            Expression::fadd(self.map(lhs)?, self.map(rhs)?)?
+                        }
+                        Expression::Fsub(ref lhs, ref rhs) => {
+                            Expression::fsub(self.map(lhs)?, self.map(rhs)?)?
+                        }
+                        Expression::Fmul(ref lhs, ref rhs) => {
+                            Expression::fmul(self.map(lhs)?, self.map(rhs)?)?
+                        }
+                        Expression::Fdiv(ref lhs, ref rhs) => {
+                            Expression::fdiv(self.map(lhs)?, self.map(rhs)?)?
+                        }
+                        Expression::Fcmpeq(ref lhs, ref rhs) => {
+                            Expression::fcmpeq(self.map(lhs)?, self.map(rhs)?)?
+                        }
+                        Expression::Fcmplt(ref lhs, ref rhs) => {
+                            Expression::fcmplt(self.map(lhs)?, self.map(rhs)?)?
+                        }
+                        Expression::Fcmpuno(ref lhs, ref rhs) => {
+                            Expression::fcmpuno(self.map(lhs)?, self.map(rhs)?)?
+                        }
+                        Expression::Itof(bits, ref src) => Expression::itof(bits, self.map(src)?)?,
+                        Expression::Ftoi(bits, ref src) => Expression::ftoi(bits, self.map(src)?)?,
+                        Expression::Fconv(bits, ref src) => {
+                            Expression::fconv(bits, self.map(src)?)?
+                        }
+                        Expression::Fround(ref src) => Expression::fround(self.map(src)?)?,
                     }
                 })
             }
@@ -219,10 +294,21 @@ impl Expression {
             | Expression::Cmpeq(ref lhs, ref rhs)
             | Expression::Cmpneq(ref lhs, ref rhs)
             | Expression::Cmplts(ref lhs, ref rhs)
-            | Expression::Cmpltu(ref lhs, ref rhs) => lhs.all_constants() && rhs.all_constants(),
+            | Expression::Cmpltu(ref lhs, ref rhs)
+            | Expression::Fadd(ref lhs, ref rhs)
+            | Expression::Fsub(ref lhs, ref rhs)
+            | Expression::Fmul(ref lhs, ref rhs)
+            | Expression::Fdiv(ref lhs, ref rhs)
+            | Expression::Fcmpeq(ref lhs, ref rhs)
+            | Expression::Fcmplt(ref lhs, ref rhs)
+            | Expression::Fcmpuno(ref lhs, ref rhs) => lhs.all_constants() && rhs.all_constants(),
             Expression::Zext(_, ref rhs)
             | Expression::Sext(_, ref rhs)
-            | Expression::Trun(_, ref rhs) => rhs.all_constants(),
+            | Expression::Trun(_, ref rhs)
+            | Expression::Itof(_, ref rhs)
+            | Expression::Ftoi(_, ref rhs)
+            | Expression::Fconv(_, ref rhs)
+            | Expression::Fround(ref rhs) => rhs.all_constants(),
             Expression::Ite(ref cond, ref then, ref else_) => {
                 cond.all_constants() && then.all_constants() && else_.all_constants()
             }
@@ -250,13 +336,24 @@ impl Expression {
             | Expression::Cmpeq(ref lhs, ref rhs)
             | Expression::Cmpneq(ref lhs, ref rhs)
             | Expression::Cmplts(ref lhs, ref rhs)
-            | Expression::Cmpltu(ref lhs, ref rhs) => {
+            | Expression::Cmpltu(ref lhs, ref rhs)
+            | Expression::Fadd(ref lhs, ref rhs)
+            | Expression::Fsub(ref lhs, ref rhs)
+            | Expression::Fmul(ref lhs, ref rhs)
+            | Expression::Fdiv(ref lhs, ref rhs)
+            | Expression::Fcmpeq(ref lhs, ref rhs)
+            | Expression::Fcmplt(ref lhs, ref rhs)
+            | Expression::Fcmpuno(ref lhs, ref rhs) => {
                 scalars.append(&mut lhs.scalars());
                 scalars.append(&mut rhs.scalars());
             }
             Expression::Zext(_, ref rhs)
             | Expression::Sext(_, ref rhs)
-            | Expression::Trun(_, ref rhs) => {
+            | Expression::Trun(_, ref rhs)
+            | Expression::Itof(_, ref rhs)
+            | Expression::Ftoi(_, ref rhs)
+            | Expression::Fconv(_, ref rhs)
+            | Expression::Fround(ref rhs) => {
                 scalars.append(&mut rhs.scalars());
             }
             Expression::Ite(ref cond, ref then, ref else_) => {
@@ -289,13 +386,24 @@ impl Expression {
             | Expression::Cmpeq(ref mut lhs, ref mut rhs)
             | Expression::Cmpneq(ref mut lhs, ref mut rhs)
             | Expression::Cmplts(ref mut lhs, ref mut rhs)
-            | Expression::Cmpltu(ref mut lhs, ref mut rhs) => {
+            | Expression::Cmpltu(ref mut lhs, ref mut rhs)
+            | Expression::Fadd(ref mut lhs, ref mut rhs)
+            | Expression::Fsub(ref mut lhs, ref mut rhs)
+            | Expression::Fmul(ref mut lhs, ref mut rhs)
+            | Expression::Fdiv(ref mut lhs, ref mut rhs)
+            | Expression::Fcmpeq(ref mut lhs, ref mut rhs)
+            | Expression::Fcmplt(ref mut lhs, ref mut rhs)
+            | Expression::Fcmpuno(ref mut lhs, ref mut rhs) => {
                 scalars.append(&mut lhs.scalars_mut());
                 scalars.append(&mut rhs.scalars_mut());
             }
             Expression::Zext(_, ref mut rhs)
             | Expression::Sext(_, ref mut rhs)
-            | Expression::Trun(_, ref mut rhs) => {
+            | Expression::Trun(_, ref mut rhs)
+            | Expression::Itof(_, ref mut rhs)
+            | Expression::Ftoi(_, ref mut rhs)
+            | Expression::Fconv(_, ref mut rhs)
+            | Expression::Fround(ref mut rhs) => {
                 scalars.append(&mut rhs.scalars_mut());
             }
             Expression::Ite(ref mut cond, ref mut then, ref mut else_) => {
@@ -512,6 +620,120 @@ impl Expression {
         ))
     }
 
+    /// Create a floating-point addition `Expression`.
+    /// # Error
+    /// The sort of the lhs and the rhs are not the same, or are not 32 or 64
+    /// bits.
+    pub fn fadd(lhs: Expression, rhs: Expression) -> Result<Expression> {
+        Expression::ensure_float_sort(&lhs, &rhs)?;
+        Ok(Expression::Fadd(Box::new(lhs), Box::new(rhs)))
+    }
+
+    /// Create a floating-point subtraction `Expression`.
+    /// # Error
+    /// The sort of the lhs and the rhs are not the same, or are not 32 or 64
+    /// bits.
+    pub fn fsub(lhs: Expression, rhs: Expression) -> Result<Expression> {
+        Expression::ensure_float_sort(&lhs, &rhs)?;
+        Ok(Expression::Fsub(Box::new(lhs), Box::new(rhs)))
+    }
+
+    /// Create a floating-point multiplication `Expression`.
+    /// # Error
+    /// The sort of the lhs and the rhs are not the same, or are not 32 or 64
+    /// bits.
+    pub fn fmul(lhs: Expression, rhs: Expression) -> Result<Expression> {
+        Expression::ensure_float_sort(&lhs, &rhs)?;
+        Ok(Expression::Fmul(Box::new(lhs), Box::new(rhs)))
+    }
+
+    /// Create a floating-point division `Expression`.
+    /// # Error
+    /// The sort of the lhs and the rhs are not the same, or are not 32 or 64
+    /// bits.
+    pub fn fdiv(lhs: Expression, rhs: Expression) -> Result<Expression> {
+        Expression::ensure_float_sort(&lhs, &rhs)?;
+        Ok(Expression::Fdiv(Box::new(lhs), Box::new(rhs)))
+    }
+
+    /// Create a floating-point equals comparison `Expression`.
+    ///
+    /// The comparison is false if either operand is NaN.
+    /// # Error
+    /// The sort of the lhs and the rhs are not the same, or are not 32 or 64
+    /// bits.
+    pub fn fcmpeq(lhs: Expression, rhs: Expression) -> Result<Expression> {
+        Expression::ensure_float_sort(&lhs, &rhs)?;
+        Ok(Expression::Fcmpeq(Box::new(lhs), Box::new(rhs)))
+    }
+
+    /// Create a floating-point less-than comparison `Expression`.
+    ///
+    /// The comparison is false if either operand is NaN.
+    /// # Error
+    /// The sort of the lhs and the rhs are not the same, or are not 32 or 64
+    /// bits.
+    pub fn fcmplt(lhs: Expression, rhs: Expression) -> Result<Expression> {
+        Expression::ensure_float_sort(&lhs, &rhs)?;
+        Ok(Expression::Fcmplt(Box::new(lhs), Box::new(rhs)))
+    }
+
+    /// Create an unordered comparison `Expression`, which is true if either
+    /// operand is NaN.
+    /// # Error
+    /// The sort of the lhs and the rhs are not the same, or are not 32 or 64
+    /// bits.
+    pub fn fcmpuno(lhs: Expression, rhs: Expression) -> Result<Expression> {
+        Expression::ensure_float_sort(&lhs, &rhs)?;
+        Ok(Expression::Fcmpuno(Box::new(lhs), Box::new(rhs)))
+    }
+
+    /// Create an expression to convert the signed integer src to a float of
+    /// the number of bits given.
+    /// # Error
+    /// bits is not 32 or 64, or src has more than 64 bits
+    pub fn itof(bits: usize, src: Expression) -> Result<Expression> {
+        if (bits != 32 && bits != 64) || src.bits() > 64 || src.bits() == 0 {
+            return Err(ErrorKind::Sort.into());
+        }
+        Ok(Expression::Itof(bits, Box::new(src)))
+    }
+
+    /// Create an expression to convert the float src to a signed integer of
+    /// the number of bits given, rounding towards zero.
+    ///
+    /// NaN and out-of-range values convert to the minimum signed integer.
+    /// # Error
+    /// src is not 32 or 64 bits, or bits is greater than 64
+    pub fn ftoi(bits: usize, src: Expression) -> Result<Expression> {
+        Expression::ensure_float(&src)?;
+        if bits > 64 || bits == 0 {
+            return Err(ErrorKind::Sort.into());
+        }
+        Ok(Expression::Ftoi(bits, Box::new(src)))
+    }
+
+    /// Create an expression to convert the float src to a float of the number
+    /// of bits given.
+    /// # Error
+    /// src and bits are the same, or are not 32, 64 or 80
+    pub fn fconv(bits: usize, src: Expression) -> Result<Expression> {
+        let valid = |bits| bits == 32 || bits == 64 || bits == 80;
+        if !valid(bits) || !valid(src.bits()) || src.bits() == bits {
+            return Err(ErrorKind::Sort.into());
+        }
+        Ok(Expression::Fconv(bits, Box::new(src)))
+    }
+
+    /// Create an expression to round the float src to an integral value,
+    /// rounding to nearest even.
+    /// # Error
+    /// src is not 32 or 64 bits
+    pub fn fround(src: Expression) -> Result<Expression> {
+        Expression::ensure_float(&src)?;
+        Ok(Expression::Fround(Box::new(src)))
+    }
+
     /// Perform a shift-right arithmetic
     ///
     /// This is a pseudo-expression, and emits an expression with
@@ -587,6 +809,17 @@ impl fmt::Display for Expression {
             Expression::Ite(ref cond, ref then, ref else_) => {
                 write!(f, "ite({}, {}, {})", cond, then, else_)
             }
+            Expression::Fadd(ref lhs, ref rhs) => write!(f, "({} +f {})", lhs, rhs),
+            Expression::Fsub(ref lhs, ref rhs) => write!(f, "({} -f {})", lhs, rhs),
+            Expression::Fmul(ref lhs, ref rhs) => write!(f, "({} *f {})", lhs, rhs),
+            Expression::Fdiv(ref lhs, ref rhs) => write!(f, "({} /f {})", lhs, rhs),
+            Expression::Fcmpeq(ref lhs, ref rhs) => write!(f, "({} ==f {})", lhs, rhs),
+            Expression::Fcmplt(ref lhs, ref rhs) => write!(f, "({} <f {})", lhs, rhs),
+            Expression::Fcmpuno(ref lhs, ref rhs) => write!(f, "uno({}, {})", lhs, rhs),
+            Expression::Itof(ref bits, ref src) => write!(f, "itof.{}({})", bits, src),
+            Expression::Ftoi(ref bits, ref src) => write!(f, "ftoi.{}({})", bits, src),
+            Expression::Fconv(ref bits, ref src) => write!(f, "fconv.{}({})", bits, src),
+            Expression::Fround(ref src) => write!(f, "fround({})", src),
         }
     }
 }
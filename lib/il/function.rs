@@ -2,6 +2,7 @@
 //!
 //! We can think of a `Function` as providing _location_ to a `ControlFlowGraph`.
 
+use crate::demangle::demangle;
 use crate::il::*;
 
 /// A function for Falcon IL. Provides location and context in a `Program` to a
@@ -16,6 +17,12 @@ pub struct Function {
     name: Option<String>,
     // Functions which belong to Programs have indices
     index: Option<usize>,
+    // Where the variables of this function are kept relative to
+    #[serde(default)]
+    frame_base: Option<FrameBase>,
+    // Parameters and local variables, from debugging information
+    #[serde(default)]
+    variables: Vec<Variable>,
 }
 
 impl Function {
@@ -30,6 +37,8 @@ impl Function {
             control_flow_graph,
             name: None,
             index: None,
+            frame_base: None,
+            variables: Vec::new(),
         }
     }
 
@@ -114,6 +123,15 @@ impl Function {
         }
     }
 
+    /// Return the demangled name of this `Function`, or its name if it is not
+    /// mangled.
+    pub fn demangled_name(&self) -> String {
+        match self.name {
+            Some(ref name) => demangle(name).unwrap_or_else(|| name.to_string()),
+            None => self.name(),
+        }
+    }
+
     /// Set this `Function`'s name.
     pub fn set_name(&mut self, name: Option<String>) {
         self.name = name;
@@ -128,4 +146,26 @@ impl Function {
     pub fn set_index(&mut self, index: Option<usize>) {
         self.index = index;
     }
+
+    /// Return the `FrameBase` which `VariableLocation::FrameBase` offsets of
+    /// this `Function`'s variables are relative to, if known.
+    pub fn frame_base(&self) -> Option<&FrameBase> {
+        self.frame_base.as_ref()
+    }
+
+    /// Set this `Function`'s `FrameBase`.
+    pub fn set_frame_base(&mut self, frame_base: Option<FrameBase>) {
+        self.frame_base = frame_base;
+    }
+
+    /// Return the parameters and local variables of this `Function`, which are
+    /// known when a `Loader` has debugging information for it.
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    /// Set the parameters and local variables of this `Function`.
+    pub fn set_variables(&mut self, variables: Vec<Variable>) {
+        self.variables = variables;
+    }
 }
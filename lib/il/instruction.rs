@@ -19,6 +19,8 @@ pub struct Instruction {
     index: usize,
     comment: Option<String>,
     address: Option<u64>,
+    #[serde(default)]
+    source_location: Option<SourceLocation>,
 }
 
 impl Instruction {
@@ -34,6 +36,7 @@ impl Instruction {
             index,
             comment: None,
             address: None,
+            source_location: None,
         }
     }
 
@@ -171,6 +174,19 @@ impl Instruction {
         self.address = address;
     }
 
+    /// Get the optional `SourceLocation` this `Instruction` was compiled from
+    ///
+    /// A `Loader` sets this from debugging information, by the address of the
+    /// `Instruction`.
+    pub fn source_location(&self) -> Option<&SourceLocation> {
+        self.source_location.as_ref()
+    }
+
+    /// Set the optional `SourceLocation` for this `Instruction`
+    pub fn set_source_location(&mut self, source_location: Option<SourceLocation>) {
+        self.source_location = source_location;
+    }
+
     /// Clone this instruction with a new index.
     pub(crate) fn clone_new_index(&self, index: usize) -> Instruction {
         Instruction {
@@ -178,6 +194,7 @@ impl Instruction {
             index,
             comment: self.comment.clone(),
             address: self.address,
+            source_location: self.source_location.clone(),
         }
     }
 
@@ -20,7 +20,10 @@
 //!
 //! ## Limitations
 //!
-//! * Falcon IL does not support floating point operations.
+//! * Floating point operations are limited to IEEE 754 single and double
+//!   precision. Values in wider formats, such as the x87 80-bit registers, are
+//!   held as doubles and converted at memory boundaries. Floating point
+//!   exceptions and non-default rounding modes are not modelled.
 //!
 //! ## Position and Semantics
 //!
@@ -161,6 +164,10 @@
 //! be filled in by a `Loader` when a corresponding symbol is available, as well
 //! as an optional `index` for when this function belongs to an `il::Program`.
 //!
+//! When a `Loader` has debugging information, a function also holds its
+//! parameters and local variables as `Variable`s, and instructions hold the
+//! `SourceLocation` they were compiled from.
+//!
 //! ## `Program`
 //!
 //! A program holds multiple instances of `Function`.
@@ -193,6 +200,8 @@ mod operation;
 mod phi_node;
 mod program;
 mod scalar;
+mod source_location;
+mod variable;
 
 pub use self::block::*;
 pub use self::constant::*;
@@ -207,6 +216,8 @@ pub use self::operation::*;
 pub use self::phi_node::*;
 pub use self::program::*;
 pub use self::scalar::*;
+pub use self::source_location::*;
+pub use self::variable::*;
 
 /// A convenience function to create a new constant.
 ///
@@ -77,6 +77,11 @@ impl Program {
         self.functions
             .iter()
             .find(|(_, function)| function.name() == name)
+            .or_else(|| {
+                self.functions
+                    .iter()
+                    .find(|(_, function)| function.demangled_name() == name)
+            })
             .map(|(_, function)| function.as_ref())
     }
 }
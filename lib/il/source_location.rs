@@ -0,0 +1,51 @@
+//! A `SourceLocation` is a position in the source code a binary was compiled
+//! from.
+
+use std::fmt;
+
+/// A file, line and column in the source code of a program, as given by
+/// debugging information.
+///
+/// Lines are numbered from 1. A column of 0 means the column is not known.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct SourceLocation {
+    file: String,
+    line: u64,
+    column: u64,
+}
+
+impl SourceLocation {
+    /// Create a new `SourceLocation`.
+    pub fn new<S: Into<String>>(file: S, line: u64, column: u64) -> SourceLocation {
+        SourceLocation {
+            file: file.into(),
+            line,
+            column,
+        }
+    }
+
+    /// Get the path of the source file.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Get the line number in the source file.
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Get the column in the line, or 0 if it is not known.
+    pub fn column(&self) -> u64 {
+        self.column
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.column == 0 {
+            write!(f, "{}:{}", self.file, self.line)
+        } else {
+            write!(f, "{}:{}:{}", self.file, self.line, self.column)
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! A `Variable` is a parameter or local variable of a `Function`, as given by
+//! debugging information.
+
+use crate::il::*;
+use std::fmt;
+
+/// Whether a `Variable` is a parameter or a local.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum VariableKind {
+    Parameter,
+    Local,
+}
+
+/// Where a `Variable` is kept.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum VariableLocation {
+    /// In memory, at an offset from the `FrameBase` of its `Function`.
+    FrameBase(i64),
+    /// In a register.
+    Scalar(Scalar),
+    /// In memory, at a fixed address, as static locals are.
+    Address(u64),
+}
+
+/// The address variables of a `Function` are kept relative to.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum FrameBase {
+    /// The canonical frame address: the value of the stack pointer at the call
+    /// site, before the call.
+    CallFrameAddress,
+    /// The value of a register.
+    Scalar(Scalar),
+    /// The value of a register plus an offset.
+    ScalarOffset(Scalar, i64),
+}
+
+/// A named parameter or local variable of a `Function`.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct Variable {
+    name: String,
+    kind: VariableKind,
+    location: Option<VariableLocation>,
+}
+
+impl Variable {
+    /// Create a new `Variable`. `location` is `None` when the variable does
+    /// not have a single location for the whole of its function.
+    pub fn new<S: Into<String>>(
+        name: S,
+        kind: VariableKind,
+        location: Option<VariableLocation>,
+    ) -> Variable {
+        Variable {
+            name: name.into(),
+            kind,
+            location,
+        }
+    }
+
+    /// Get the name of this `Variable`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get whether this `Variable` is a parameter or a local.
+    pub fn kind(&self) -> VariableKind {
+        self.kind
+    }
+
+    /// Returns `true` if this `Variable` is a parameter.
+    pub fn is_parameter(&self) -> bool {
+        self.kind == VariableKind::Parameter
+    }
+
+    /// Get where this `Variable` is kept, if it has a single location.
+    pub fn location(&self) -> Option<&VariableLocation> {
+        self.location.as_ref()
+    }
+}
+
+impl fmt::Display for VariableLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VariableLocation::FrameBase(offset) if offset < 0 => {
+                write!(f, "[frame_base - 0x{:x}]", offset.wrapping_neg() as u64)
+            }
+            VariableLocation::FrameBase(offset) => write!(f, "[frame_base + 0x{:x}]", offset),
+            VariableLocation::Scalar(ref scalar) => write!(f, "{}", scalar),
+            VariableLocation::Address(address) => write!(f, "[0x{:x}]", address),
+        }
+    }
+}
+
+impl fmt::Display for Variable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.location {
+            Some(ref location) => write!(f, "{} @ {}", self.name, location),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
@@ -8,6 +8,7 @@
 //! * **analysis** - A fixed-point engine and methods for abstract interpretation
 //! over Falcon IL. Example, usable analyses are given.
 //! * **architecture** - Information on Falcon's supported architectures.
+//! * **demangle** - Demangling of C++ and Rust symbol names.
 //! * **executor** - A concrete execution engine over Falcon IL.
 //! * **graph** - A simple directed graph library.
 //! * **il** - Falcon's Intermediate Language.
@@ -44,6 +45,7 @@ extern crate serde_derive;
 
 pub mod analysis;
 pub mod architecture;
+pub mod demangle;
 pub mod executor;
 pub mod graph;
 pub mod il;
@@ -72,6 +74,7 @@ pub mod error {
         foreign_links {
             Base64(::base64::DecodeError);
             Capstone(::falcon_capstone::capstone::CsErr);
+            Gimli(::gimli::Error) #[cfg(feature = "dwarf")];
             Goblin(::goblin::error::Error);
             Io(::std::io::Error);
             Json(::serde_json::Error);
@@ -0,0 +1,266 @@
+//! Debugging information, such as DWARF, which a `Loader` may provide.
+//!
+//! `DebugInfo` holds what Falcon uses from debugging information: the source
+//! location of each address, and the bounds, frame base and variables of each
+//! function. The `Loader` trait applies it to every `il::Function` it lifts.
+
+use crate::il;
+use crate::loader::{FunctionEntry, FunctionEntrySource};
+
+/// A function described by debugging information.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DebugFunction {
+    name: Option<String>,
+    low_pc: u64,
+    high_pc: u64,
+    frame_base: Option<il::FrameBase>,
+    variables: Vec<il::Variable>,
+}
+
+impl DebugFunction {
+    /// Create a new `DebugFunction` covering the addresses from `low_pc` up
+    /// to, but not including, `high_pc`.
+    pub fn new(
+        name: Option<String>,
+        low_pc: u64,
+        high_pc: u64,
+        frame_base: Option<il::FrameBase>,
+        variables: Vec<il::Variable>,
+    ) -> DebugFunction {
+        DebugFunction {
+            name,
+            low_pc,
+            high_pc,
+            frame_base,
+            variables,
+        }
+    }
+
+    /// Get the name of this function.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get the address of the first instruction of this function.
+    pub fn low_pc(&self) -> u64 {
+        self.low_pc
+    }
+
+    /// Get the address one past the end of this function.
+    pub fn high_pc(&self) -> u64 {
+        self.high_pc
+    }
+
+    /// Returns `true` if `address` is within this function.
+    pub fn contains(&self, address: u64) -> bool {
+        address >= self.low_pc && address < self.high_pc
+    }
+
+    /// Get the frame base the variables of this function are relative to.
+    pub fn frame_base(&self) -> Option<&il::FrameBase> {
+        self.frame_base.as_ref()
+    }
+
+    /// Get the parameters and local variables of this function.
+    pub fn variables(&self) -> &[il::Variable] {
+        &self.variables
+    }
+}
+
+/// Debugging information for a binary.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DebugInfo {
+    // Rows of the line table, sorted by address. A row without a source
+    // location ends a sequence of rows.
+    lines: Vec<(u64, Option<il::SourceLocation>)>,
+    // Functions, sorted by low_pc
+    functions: Vec<DebugFunction>,
+}
+
+impl DebugInfo {
+    /// Create a new `DebugInfo`.
+    ///
+    /// Each line is the address of the first instruction compiled from a
+    /// source location, which covers every address up to the next line. A
+    /// line with no source location marks the end of the line before it.
+    pub fn new(
+        lines: Vec<(u64, Option<il::SourceLocation>)>,
+        functions: Vec<DebugFunction>,
+    ) -> DebugInfo {
+        let mut debug_info = DebugInfo { lines, functions };
+        debug_info.sort();
+        debug_info
+    }
+
+    fn sort(&mut self) {
+        // Where one sequence ends at the address another begins, the end comes
+        // first.
+        self.lines
+            .sort_by_key(|(address, source_location)| (*address, source_location.is_some()));
+        self.functions
+            .sort_by_key(|function| (function.low_pc, function.high_pc));
+    }
+
+    /// Add the debugging information in `other` to this `DebugInfo`.
+    pub fn extend(&mut self, other: DebugInfo) {
+        self.lines.extend(other.lines);
+        self.functions.extend(other.functions);
+        self.sort();
+    }
+
+    /// Get the rows of the line table, sorted by address.
+    pub fn lines(&self) -> &[(u64, Option<il::SourceLocation>)] {
+        &self.lines
+    }
+
+    /// Get the functions, sorted by address.
+    pub fn functions(&self) -> &[DebugFunction] {
+        &self.functions
+    }
+
+    /// Get the source location the instruction at `address` was compiled
+    /// from.
+    pub fn source_location(&self, address: u64) -> Option<&il::SourceLocation> {
+        let index = match self
+            .lines
+            .binary_search_by(|(line_address, _)| line_address.cmp(&address))
+        {
+            // Take the last of several lines at this address
+            Ok(index) => {
+                index
+                    + self.lines[index..]
+                        .iter()
+                        .take_while(|(line_address, _)| *line_address == address)
+                        .count()
+                    - 1
+            }
+            Err(0) => return None,
+            Err(index) => index - 1,
+        };
+        self.lines[index].1.as_ref()
+    }
+
+    /// Get the function which begins at `address`.
+    pub fn function(&self, address: u64) -> Option<&DebugFunction> {
+        self.functions
+            .binary_search_by_key(&address, |function| function.low_pc)
+            .ok()
+            .map(|index| &self.functions[index])
+    }
+
+    /// Get a `FunctionEntry` for the start of every function.
+    pub fn function_entries(&self) -> Vec<FunctionEntry> {
+        self.functions
+            .iter()
+            .map(|function| {
+                FunctionEntry::new(function.low_pc, function.name.clone())
+                    .with_source(FunctionEntrySource::DebugInfo)
+            })
+            .collect()
+    }
+
+    /// Apply this `DebugInfo` to an `il::Function`, setting the source
+    /// location of its instructions, and its frame base and variables.
+    pub fn annotate(&self, function: &mut il::Function) {
+        for block in function.blocks_mut() {
+            for instruction in block.instructions_mut() {
+                let source_location = instruction
+                    .address()
+                    .and_then(|address| self.source_location(address))
+                    .cloned();
+                if source_location.is_some() {
+                    instruction.set_source_location(source_location);
+                }
+            }
+        }
+
+        if let Some(debug_function) = self.function(function.address()) {
+            function.set_frame_base(debug_function.frame_base.clone());
+            function.set_variables(debug_function.variables.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_locations() {
+        let location = |line| Some(il::SourceLocation::new("a.c", line, 0));
+        let debug_info = DebugInfo::new(
+            vec![
+                (0x2000, location(20)),
+                (0x1000, location(1)),
+                (0x1010, location(2)),
+                (0x1010, location(3)),
+                (0x1020, None),
+                (0x2000, None),
+                (0x2010, None),
+            ],
+            Vec::new(),
+        );
+
+        assert_eq!(debug_info.source_location(0xfff), None);
+        assert_eq!(debug_info.source_location(0x1000), location(1).as_ref());
+        assert_eq!(debug_info.source_location(0x100f), location(1).as_ref());
+        assert_eq!(debug_info.source_location(0x1010), location(3).as_ref());
+        assert_eq!(debug_info.source_location(0x1020), None);
+        assert_eq!(debug_info.source_location(0x1fff), None);
+        assert_eq!(debug_info.source_location(0x2008), location(20).as_ref());
+        assert_eq!(debug_info.source_location(0x2010), None);
+    }
+
+    #[test]
+    fn annotate() {
+        let variables = vec![il::Variable::new(
+            "x",
+            il::VariableKind::Parameter,
+            Some(il::VariableLocation::FrameBase(-8)),
+        )];
+        let debug_info = DebugInfo::new(
+            vec![
+                (0x1000, Some(il::SourceLocation::new("a.c", 1, 0))),
+                (0x1004, None),
+            ],
+            vec![DebugFunction::new(
+                Some("f".to_string()),
+                0x1000,
+                0x1004,
+                Some(il::FrameBase::CallFrameAddress),
+                variables.clone(),
+            )],
+        );
+
+        let mut control_flow_graph = il::ControlFlowGraph::new();
+        let block_index = {
+            let block = control_flow_graph.new_block().unwrap();
+            block.nop();
+            block.instructions_mut()[0].set_address(Some(0x1000));
+            block.index()
+        };
+        control_flow_graph.set_entry(block_index).unwrap();
+        let mut function = il::Function::new(0x1000, control_flow_graph);
+
+        debug_info.annotate(&mut function);
+
+        let instruction = &function.blocks()[0].instructions()[0];
+        assert_eq!(
+            instruction
+                .source_location()
+                .map(|location| location.line()),
+            Some(1)
+        );
+        assert_eq!(
+            function.frame_base(),
+            Some(&il::FrameBase::CallFrameAddress)
+        );
+        assert_eq!(function.variables(), variables.as_slice());
+
+        assert_eq!(
+            debug_info.function_entries(),
+            vec![FunctionEntry::new(0x1000, Some("f".to_string()))
+                .with_source(FunctionEntrySource::DebugInfo)]
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! DWARF debugging information, read with gimli.
+//!
+//! This takes just enough from the DWARF of a binary to fill in a
+//! `DebugInfo`: the line table of each compilation unit, and the bounds, frame
+//! base and variables of each subprogram. Anything we can not read is skipped
+//! over, rather than treated as an error, as the information we can read is
+//! still good.
+
+use crate::architecture::{general_purpose_registers, Architecture, Endian, AMD64_REGISTERS};
+use crate::error::*;
+use crate::il;
+use crate::loader::{DebugFunction, DebugInfo};
+use gimli::{AttributeValue, EndianSlice, Operation, RunTimeEndian};
+use std::collections::HashMap;
+
+type Reader<'a> = EndianSlice<'a, RunTimeEndian>;
+type Dwarf<'a> = gimli::Dwarf<Reader<'a>>;
+type Unit<'a> = gimli::Unit<Reader<'a>>;
+type Entry<'u, 'a> = gimli::DebuggingInformationEntry<'u, 'u, Reader<'a>>;
+
+/// How many `DW_AT_specification` or `DW_AT_abstract_origin` references we
+/// will follow to find a name.
+const MAX_REFERENCES: usize = 4;
+
+/// The amd64 registers, by DWARF register number, as indices into
+/// `AMD64_REGISTERS`.
+const AMD64_DWARF_REGISTERS: [usize; 16] = [0, 2, 1, 3, 6, 7, 5, 4, 8, 9, 10, 11, 12, 13, 14, 15];
+
+/// Get the name of the scalar for a DWARF register number.
+fn register_name(architecture: &str, number: u16) -> Option<&'static str> {
+    let number = number as usize;
+    match architecture {
+        "amd64" => AMD64_DWARF_REGISTERS
+            .get(number)
+            .map(|index| AMD64_REGISTERS[*index]),
+        // The other architectures number their general purpose registers as
+        // DWARF does
+        _ => general_purpose_registers(architecture)?
+            .get(number)
+            .cloned(),
+    }
+}
+
+/// The architecture a binary's DWARF describes, and where it was loaded.
+struct Target<'t> {
+    architecture: &'t dyn Architecture,
+    base_address: u64,
+}
+
+impl<'t> Target<'t> {
+    /// Get the scalar for a DWARF register.
+    fn register(&self, register: gimli::Register) -> Option<il::Scalar> {
+        register_name(self.architecture.name(), register.0)
+            .map(|name| il::scalar(name, self.architecture.word_size()))
+    }
+
+    /// Rebase an address from the debugging information.
+    fn address(&self, address: u64) -> u64 {
+        let address = address.wrapping_add(self.base_address);
+        match self.architecture.word_size() {
+            32 => address & 0xffff_ffff,
+            _ => address,
+        }
+    }
+}
+
+/// Join a path onto a directory, unless it is already absolute.
+fn join(directory: Option<&str>, path: &str) -> String {
+    match directory {
+        Some(directory) if !directory.is_empty() && !path.starts_with('/') => {
+            format!("{}/{}", directory.trim_end_matches('/'), path)
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// A subprogram and the variables found in it so far.
+struct Subprogram {
+    name: Option<String>,
+    low_pc: u64,
+    high_pc: u64,
+    frame_base: Option<il::FrameBase>,
+    variables: Vec<il::Variable>,
+}
+
+/// A compilation unit being read.
+struct UnitReader<'a, 'u, 't> {
+    dwarf: &'u Dwarf<'a>,
+    unit: &'u Unit<'a>,
+    target: &'t Target<'t>,
+}
+
+impl<'a, 'u, 't> UnitReader<'a, 'u, 't> {
+    fn attribute(
+        &self,
+        entry: &Entry<'_, 'a>,
+        name: gimli::DwAt,
+    ) -> Option<AttributeValue<Reader<'a>>> {
+        entry.attr_value(name).ok()?
+    }
+
+    fn flag(&self, entry: &Entry<'_, 'a>, name: gimli::DwAt) -> bool {
+        match self.attribute(entry, name) {
+            Some(AttributeValue::Flag(flag)) => flag,
+            _ => false,
+        }
+    }
+
+    fn string(&self, value: AttributeValue<Reader<'a>>) -> Option<String> {
+        let string = self.dwarf.attr_string(self.unit, value).ok()?;
+        Some(string.to_string_lossy().into_owned())
+    }
+
+    /// Get the name of an entry, following references to the declaration or
+    /// abstract instance it completes. Linkage names are preferred, as they
+    /// match the names in symbol tables.
+    fn name(&self, entry: &Entry<'_, 'a>, references: usize) -> Option<String> {
+        let name = self
+            .attribute(entry, gimli::DW_AT_linkage_name)
+            .or_else(|| self.attribute(entry, gimli::DW_AT_MIPS_linkage_name))
+            .or_else(|| self.attribute(entry, gimli::DW_AT_name))
+            .and_then(|name| self.string(name));
+        if name.is_some() || references >= MAX_REFERENCES {
+            return name;
+        }
+        match self
+            .attribute(entry, gimli::DW_AT_specification)
+            .or_else(|| self.attribute(entry, gimli::DW_AT_abstract_origin))
+        {
+            Some(AttributeValue::UnitRef(offset)) => {
+                self.name(&self.unit.entry(offset).ok()?, references + 1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Parse a frame base expression.
+    fn frame_base(&self, expression: gimli::Expression<Reader<'a>>) -> Option<il::FrameBase> {
+        let mut operations = expression.operations(self.unit.encoding());
+        let frame_base = match operations.next().ok()?? {
+            Operation::CallFrameCFA => il::FrameBase::CallFrameAddress,
+            Operation::Register { register } => {
+                il::FrameBase::Scalar(self.target.register(register)?)
+            }
+            Operation::RegisterOffset {
+                register, offset, ..
+            } => il::FrameBase::ScalarOffset(self.target.register(register)?, offset),
+            _ => return None,
+        };
+        // Anything more is a computation we do not follow
+        match operations.next() {
+            Ok(None) => Some(frame_base),
+            _ => None,
+        }
+    }
+
+    /// Parse a location expression.
+    fn location(&self, expression: gimli::Expression<Reader<'a>>) -> Option<il::VariableLocation> {
+        let mut operations = expression.operations(self.unit.encoding());
+        let location = match operations.next().ok()?? {
+            Operation::FrameOffset { offset } => il::VariableLocation::FrameBase(offset),
+            Operation::Address { address } => {
+                il::VariableLocation::Address(self.target.address(address))
+            }
+            Operation::AddressIndex { index } => il::VariableLocation::Address(
+                self.target
+                    .address(self.dwarf.address(self.unit, index).ok()?),
+            ),
+            Operation::Register { register } => {
+                il::VariableLocation::Scalar(self.target.register(register)?)
+            }
+            _ => return None,
+        };
+        match operations.next() {
+            Ok(None) => Some(location),
+            _ => None,
+        }
+    }
+
+    /// Get a subprogram with code from its entry.
+    fn subprogram(&self, entry: &Entry<'_, 'a>) -> Option<Subprogram> {
+        if self.flag(entry, gimli::DW_AT_declaration) {
+            return None;
+        }
+        let low_pc = self
+            .dwarf
+            .attr_address(self.unit, self.attribute(entry, gimli::DW_AT_low_pc)?)
+            .ok()??;
+        let high_pc = match self.attribute(entry, gimli::DW_AT_high_pc)? {
+            value @ AttributeValue::Addr(_) | value @ AttributeValue::DebugAddrIndex(_) => {
+                self.dwarf.attr_address(self.unit, value).ok()??
+            }
+            value => low_pc.wrapping_add(value.udata_value()?),
+        };
+        // Functions removed by the linker are left with a low_pc of 0, or all
+        // ones.
+        if low_pc == 0 || low_pc >= high_pc {
+            return None;
+        }
+
+        let frame_base = match self.attribute(entry, gimli::DW_AT_frame_base) {
+            Some(AttributeValue::Exprloc(expression)) => self.frame_base(expression),
+            _ => None,
+        };
+
+        Some(Subprogram {
+            name: self.name(entry, 0),
+            low_pc: self.target.address(low_pc),
+            high_pc: self.target.address(high_pc),
+            frame_base,
+            variables: Vec::new(),
+        })
+    }
+
+    /// Get a parameter or local variable from its entry.
+    fn variable(&self, entry: &Entry<'_, 'a>) -> Option<il::Variable> {
+        if self.flag(entry, gimli::DW_AT_declaration) {
+            return None;
+        }
+        let kind = if entry.tag() == gimli::DW_TAG_formal_parameter {
+            il::VariableKind::Parameter
+        } else {
+            il::VariableKind::Local
+        };
+        let location = match self.attribute(entry, gimli::DW_AT_location) {
+            Some(AttributeValue::Exprloc(expression)) => self.location(expression),
+            _ => None,
+        };
+        Some(il::Variable::new(self.name(entry, 0)?, kind, location))
+    }
+
+    /// Get the subprograms with code in this unit.
+    fn functions(&self) -> gimli::Result<Vec<DebugFunction>> {
+        let mut subprograms: Vec<Subprogram> = Vec::new();
+
+        // For each entry we are in, the subprogram its children belong to.
+        // Variables in lexical blocks belong to the subprogram around them,
+        // but those of nested and inlined subprograms do not.
+        let mut scopes: Vec<Option<usize>> = Vec::new();
+        let mut depth: isize = 0;
+        let mut entries = self.unit.entries();
+        while let Some((delta_depth, entry)) = entries.next_dfs()? {
+            depth += delta_depth;
+            let scope = match depth {
+                0 => None,
+                _ => scopes.get(depth as usize - 1).cloned().flatten(),
+            };
+
+            let child_scope = match entry.tag() {
+                gimli::DW_TAG_subprogram => self.subprogram(entry).map(|subprogram| {
+                    subprograms.push(subprogram);
+                    subprograms.len() - 1
+                }),
+                gimli::DW_TAG_lexical_block => scope,
+                gimli::DW_TAG_formal_parameter | gimli::DW_TAG_variable => {
+                    if let Some(index) = scope {
+                        if let Some(variable) = self.variable(entry) {
+                            subprograms[index].variables.push(variable);
+                        }
+                    }
+                    None
+                }
+                _ => None,
+            };
+            scopes.truncate(depth as usize);
+            scopes.push(child_scope);
+        }
+
+        Ok(subprograms
+            .into_iter()
+            .map(|subprogram| {
+                DebugFunction::new(
+                    subprogram.name,
+                    subprogram.low_pc,
+                    subprogram.high_pc,
+                    subprogram.frame_base,
+                    subprogram.variables,
+                )
+            })
+            .collect())
+    }
+
+    /// Get the path of a file in this unit's line program.
+    fn file_path(
+        &self,
+        header: &gimli::LineProgramHeader<Reader<'a>>,
+        file: &gimli::FileEntry<Reader<'a>>,
+    ) -> Option<String> {
+        let comp_dir = self
+            .unit
+            .comp_dir
+            .map(|comp_dir| comp_dir.to_string_lossy().into_owned());
+        let directory = file
+            .directory(header)
+            .and_then(|directory| self.string(directory))
+            .map(|directory| join(comp_dir.as_deref(), &directory));
+        let path = self.string(file.path_name())?;
+        Some(join(directory.as_deref(), &path))
+    }
+
+    /// Run this unit's line program, appending a line for each row of the
+    /// line table it produces.
+    fn lines(&self, lines: &mut Vec<(u64, Option<il::SourceLocation>)>) -> gimli::Result<()> {
+        let program = match self.unit.line_program.clone() {
+            Some(program) => program,
+            None => return Ok(()),
+        };
+
+        // The paths of the files, by file index
+        let mut files: HashMap<u64, Option<String>> = HashMap::new();
+
+        let mut rows = program.rows();
+        while let Some((header, row)) = rows.next_row()? {
+            let source_location = if row.end_sequence() {
+                None
+            } else {
+                let path = files
+                    .entry(row.file_index())
+                    .or_insert_with(|| {
+                        row.file(header)
+                            .and_then(|file| self.file_path(header, file))
+                    })
+                    .as_deref();
+                let line = row.line().map(|line| line.get()).unwrap_or(0);
+                let column = match row.column() {
+                    gimli::ColumnType::LeftEdge => 0,
+                    gimli::ColumnType::Column(column) => column.get(),
+                };
+                path.map(|path| il::SourceLocation::new(path, line, column))
+            };
+            lines.push((self.target.address(row.address()), source_location));
+        }
+
+        Ok(())
+    }
+}
+
+/// Read the DWARF of a binary for `architecture` loaded at `base_address`,
+/// where `section` gives the contents of a section by name, or nothing if the
+/// section is not present.
+pub(crate) fn debug_info<'a, F>(
+    section: F,
+    architecture: &dyn Architecture,
+    base_address: u64,
+) -> Result<DebugInfo>
+where
+    F: Fn(&str) -> &'a [u8],
+{
+    let endian = match architecture.endian() {
+        Endian::Big => RunTimeEndian::Big,
+        Endian::Little => RunTimeEndian::Little,
+    };
+    let dwarf = gimli::Dwarf::load(|id| -> gimli::Result<Reader<'a>> {
+        Ok(EndianSlice::new(section(id.name()), endian))
+    })?;
+
+    let target = Target {
+        architecture,
+        base_address,
+    };
+
+    let mut lines = Vec::new();
+    let mut functions = Vec::new();
+
+    // Units we can not read are skipped, and reading stops at the first unit
+    // header we can not read.
+    let mut headers = dwarf.units();
+    while let Ok(Some(header)) = headers.next() {
+        // Type units, and split DWARF skeletons, have no code for us
+        match header.type_() {
+            gimli::UnitType::Compilation | gimli::UnitType::Partial => {}
+            _ => continue,
+        }
+        let unit = match dwarf.unit(header) {
+            Ok(unit) => unit,
+            Err(_) => continue,
+        };
+        let unit_reader = UnitReader {
+            dwarf: &dwarf,
+            unit: &unit,
+            target: &target,
+        };
+
+        // A unit with a broken line table still has good functions
+        unit_reader.lines(&mut lines).ok();
+        if let Ok(unit_functions) = unit_reader.functions() {
+            functions.extend(unit_functions);
+        }
+    }
+
+    Ok(DebugInfo::new(lines, functions))
+}
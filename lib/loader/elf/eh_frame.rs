@@ -0,0 +1,281 @@
+//! Function starts from the call frame information in `.eh_frame` and
+//! `.eh_frame_hdr`.
+//!
+//! Every function which may be unwound through, which with modern toolchains
+//! is nearly every function, has a Frame Description Entry (FDE) giving the
+//! address of its first instruction. This information survives stripping.
+
+use crate::architecture::Endian;
+use crate::error::*;
+use std::collections::HashMap;
+
+const DW_EH_PE_ABSPTR: u8 = 0x00;
+const DW_EH_PE_OMIT: u8 = 0xff;
+
+/// A cursor over the bytes of a section loaded at `address`.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    address: u64,
+    offset: usize,
+    endian: Endian,
+    word_bytes: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8], address: u64, endian: Endian, word_bytes: usize) -> Reader<'a> {
+        Reader {
+            bytes,
+            address,
+            offset: 0,
+            endian,
+            word_bytes,
+        }
+    }
+
+    /// The address of the next byte to be read.
+    fn position(&self) -> u64 {
+        self.address.wrapping_add(self.offset as u64)
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(length)
+            .ok_or("Unexpected end of call frame information")?;
+        let bytes = self
+            .bytes
+            .get(self.offset..end)
+            .ok_or("Unexpected end of call frame information")?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn unsigned(&mut self, length: usize) -> Result<u64> {
+        let bytes = self.bytes(length)?;
+        Ok(match self.endian {
+            Endian::Big => bytes
+                .iter()
+                .fold(0, |value, byte| (value << 8) | u64::from(*byte)),
+            Endian::Little => bytes
+                .iter()
+                .rev()
+                .fold(0, |value, byte| (value << 8) | u64::from(*byte)),
+        })
+    }
+
+    fn signed(&mut self, length: usize) -> Result<u64> {
+        let shift = 64 - length * 8;
+        Ok((((self.unsigned(length)? << shift) as i64) >> shift) as u64)
+    }
+
+    fn uleb128(&mut self) -> Result<u64> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            if shift < 64 {
+                value |= u64::from(byte & 0x7f) << shift;
+            }
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    fn sleb128(&mut self) -> Result<u64> {
+        let mut value = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            if shift < 64 {
+                value |= u64::from(byte & 0x7f) << shift;
+            }
+            shift += 7;
+            if byte & 0x80 == 0 {
+                if shift < 64 && byte & 0x40 != 0 {
+                    value |= !0 << shift;
+                }
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Read a NUL-terminated string.
+    fn cstring(&mut self) -> Result<&'a [u8]> {
+        let length = self
+            .bytes
+            .get(self.offset..)
+            .and_then(|bytes| bytes.iter().position(|byte| *byte == 0))
+            .ok_or("Unterminated string in call frame information")?;
+        let string = self.bytes(length)?;
+        self.offset += 1;
+        Ok(string)
+    }
+
+    /// Read a pointer with the given `DW_EH_PE_*` encoding. `data_base` is the
+    /// base of `DW_EH_PE_datarel` pointers.
+    fn pointer(&mut self, encoding: u8, data_base: u64) -> Result<Option<u64>> {
+        if encoding == DW_EH_PE_OMIT {
+            return Ok(None);
+        }
+
+        let position = self.position();
+        let value = match encoding & 0x0f {
+            0x00 => self.unsigned(self.word_bytes)?,
+            0x01 => self.uleb128()?,
+            0x02 => self.unsigned(2)?,
+            0x03 => self.unsigned(4)?,
+            0x04 => self.unsigned(8)?,
+            0x09 => self.sleb128()?,
+            0x0a => self.signed(2)?,
+            0x0b => self.signed(4)?,
+            0x0c => self.signed(8)?,
+            _ => bail!("Unsupported pointer encoding 0x{:x}", encoding),
+        };
+        let base = match encoding & 0x70 {
+            0x00 => 0,
+            0x10 => position,
+            0x30 => data_base,
+            _ => bail!("Unsupported pointer encoding 0x{:x}", encoding),
+        };
+
+        let value = base.wrapping_add(value);
+        Ok(Some(match self.word_bytes {
+            4 => value & 0xffff_ffff,
+            _ => value,
+        }))
+    }
+}
+
+/// Parse a CIE, returning the encoding of the pointers in its FDEs.
+fn cie_pointer_encoding(reader: &mut Reader) -> Result<u8> {
+    let version = reader.u8()?;
+    let augmentation = reader.cstring()?;
+    if augmentation.starts_with(b"eh") {
+        reader.bytes(reader.word_bytes)?;
+    }
+    // code alignment factor, data alignment factor, return address register
+    reader.uleb128()?;
+    reader.sleb128()?;
+    if version == 1 {
+        reader.u8()?;
+    } else {
+        reader.uleb128()?;
+    }
+
+    let mut encoding = DW_EH_PE_ABSPTR;
+    if augmentation.first() == Some(&b'z') {
+        reader.uleb128()?;
+        for augmentation in &augmentation[1..] {
+            match augmentation {
+                b'R' => encoding = reader.u8()?,
+                b'P' => {
+                    let personality_encoding = reader.u8()?;
+                    reader.pointer(personality_encoding, 0)?;
+                }
+                b'L' => {
+                    reader.u8()?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(encoding)
+}
+
+/// Read the initial location of every FDE in `.eh_frame`, stopping at the
+/// terminator or at the first malformed entry.
+fn fde_initial_locations(reader: &mut Reader, locations: &mut Vec<u64>) -> Result<()> {
+    let mut cie_encodings: HashMap<usize, u8> = HashMap::new();
+
+    while reader.offset < reader.bytes.len() {
+        let start = reader.offset;
+        let (length, id_bytes) = match reader.unsigned(4)? {
+            0 => break,
+            0xffff_ffff => (reader.unsigned(8)? as usize, 8),
+            length => (length as usize, 4),
+        };
+        let id_offset = reader.offset;
+        let end = id_offset.saturating_add(length);
+
+        let id = reader.unsigned(id_bytes)? as usize;
+        if id == 0 {
+            let encoding = cie_pointer_encoding(reader)?;
+            cie_encodings.insert(start, encoding);
+        } else if let Some(encoding) = id_offset
+            .checked_sub(id)
+            .and_then(|cie| cie_encodings.get(&cie))
+        {
+            if let Some(location) = reader.pointer(*encoding, 0)? {
+                if location != 0 {
+                    locations.push(location);
+                }
+            }
+        }
+
+        reader.offset = end;
+    }
+
+    Ok(())
+}
+
+/// Get the address of the first instruction of every function described in
+/// an `.eh_frame` section, loaded at `address`.
+pub(crate) fn eh_frame_functions(
+    bytes: &[u8],
+    address: u64,
+    endian: Endian,
+    word_bytes: usize,
+) -> Vec<u64> {
+    let mut reader = Reader::new(bytes, address, endian, word_bytes);
+    let mut locations = Vec::new();
+    // Whatever was found before a malformed entry is still good
+    fde_initial_locations(&mut reader, &mut locations).ok();
+    locations
+}
+
+/// Parse an `.eh_frame_hdr` section, loaded at `address`.
+///
+/// Returns the address of `.eh_frame`, and the address of the first
+/// instruction of every function in the binary search table.
+pub(crate) fn eh_frame_hdr(
+    bytes: &[u8],
+    address: u64,
+    endian: Endian,
+    word_bytes: usize,
+) -> Result<(Option<u64>, Vec<u64>)> {
+    let mut reader = Reader::new(bytes, address, endian, word_bytes);
+
+    if reader.u8()? != 1 {
+        bail!("Unsupported .eh_frame_hdr version");
+    }
+    let eh_frame_ptr_encoding = reader.u8()?;
+    let fde_count_encoding = reader.u8()?;
+    let table_encoding = reader.u8()?;
+
+    let eh_frame = reader.pointer(eh_frame_ptr_encoding, address)?;
+    let fde_count = match reader.pointer(fde_count_encoding, address)? {
+        Some(fde_count) => fde_count,
+        None => return Ok((eh_frame, Vec::new())),
+    };
+
+    let mut locations = Vec::new();
+    for _ in 0..fde_count {
+        let location = match reader.pointer(table_encoding, address) {
+            Ok(Some(location)) => location,
+            _ => break,
+        };
+        if reader.pointer(table_encoding, address).is_err() {
+            break;
+        }
+        locations.push(location);
+    }
+
+    Ok((eh_frame, locations))
+}
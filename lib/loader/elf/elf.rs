@@ -1,4 +1,9 @@
+use super::eh_frame;
+use super::libc_start_main::libc_start_main_arguments;
+use super::plt;
 use crate::architecture::*;
+#[cfg(feature = "dwarf")]
+use crate::loader::dwarf;
 use crate::loader::*;
 use crate::memory::backing::Memory;
 use crate::memory::MemoryPermissions;
@@ -6,6 +11,16 @@ use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
+#[cfg(feature = "dwarf")]
+use std::sync::OnceLock;
+
+// The PPC64 ABI version flags, which Goblin does not yet have. 0 is
+// unspecified.
+const EF_PPC64_ABI: u32 = 3;
+const EF_PPC64_ABI_V1: u32 = 1;
+const EF_PPC64_ABI_V2: u32 = 2;
+// Set for binaries built for the MIPS n32 ABI.
+const EF_MIPS_ABI2: u32 = 0x20;
 
 /// Loader for a single ELf file.
 #[derive(Debug)]
@@ -14,6 +29,8 @@ pub struct Elf {
     bytes: Vec<u8>,
     user_function_entries: Vec<u64>,
     architecture: Box<dyn Architecture>,
+    #[cfg(feature = "dwarf")]
+    debug_info: OnceLock<Option<DebugInfo>>,
 }
 
 impl Elf {
@@ -26,21 +43,67 @@ impl Elf {
             if elf.header.e_machine == goblin::elf::header::EM_386 {
                 Box::new(X86::new())
             } else if elf.header.e_machine == goblin::elf::header::EM_MIPS {
-                match elf.header.endianness()? {
-                    goblin::container::Endian::Big => {
+                // n32 binaries are 32-bit Elfs of 64-bit code
+                let n32 = elf.header.e_flags & EF_MIPS_ABI2 != 0;
+                match (elf.is_64 || n32, elf.header.endianness()?) {
+                    (false, goblin::container::Endian::Big) => {
                         Box::new(Mips::new()) as Box<dyn Architecture>
                     }
-                    goblin::container::Endian::Little => {
+                    (false, goblin::container::Endian::Little) => {
                         Box::new(Mipsel::new()) as Box<dyn Architecture>
                     }
+                    (true, goblin::container::Endian::Big) if n32 => {
+                        Box::new(MipsN32::new()) as Box<dyn Architecture>
+                    }
+                    (true, goblin::container::Endian::Little) if n32 => {
+                        Box::new(MipselN32::new()) as Box<dyn Architecture>
+                    }
+                    (true, goblin::container::Endian::Big) => {
+                        Box::new(Mips64::new()) as Box<dyn Architecture>
+                    }
+                    (true, goblin::container::Endian::Little) => {
+                        Box::new(Mips64el::new()) as Box<dyn Architecture>
+                    }
                 }
             } else if elf.header.e_machine == goblin::elf::header::EM_PPC {
                 match elf.header.endianness()? {
                     goblin::container::Endian::Big => Box::new(Ppc::new()) as Box<dyn Architecture>,
                     goblin::container::Endian::Little => bail!("PPC Little-Endian not supported"),
                 }
+            } else if elf.header.e_machine == goblin::elf::header::EM_PPC64 {
+                // Big-endian binaries which do not give their ABI are ELFv1,
+                // and little-endian binaries ELFv2.
+                match (elf.header.endianness()?, elf.header.e_flags & EF_PPC64_ABI) {
+                    (goblin::container::Endian::Big, EF_PPC64_ABI_V2) => {
+                        Box::new(Ppc64ElfV2::new()) as Box<dyn Architecture>
+                    }
+                    (goblin::container::Endian::Big, _) => {
+                        Box::new(Ppc64::new()) as Box<dyn Architecture>
+                    }
+                    (goblin::container::Endian::Little, EF_PPC64_ABI_V1) => {
+                        bail!("PPC64 Little-Endian ELFv1 not supported")
+                    }
+                    (goblin::container::Endian::Little, _) => {
+                        Box::new(Ppc64le::new()) as Box<dyn Architecture>
+                    }
+                }
             } else if elf.header.e_machine == goblin::elf::header::EM_X86_64 {
                 Box::new(Amd64::new())
+            } else if elf.header.e_machine == goblin::elf::header::EM_AARCH64 {
+                Box::new(AArch64::new()) as Box<dyn Architecture>
+            } else if elf.header.e_machine == goblin::elf::header::EM_ARM {
+                match elf.header.endianness()? {
+                    goblin::container::Endian::Big => bail!("ARM Big-Endian not supported"),
+                    goblin::container::Endian::Little => {
+                        Box::new(Arm::new()) as Box<dyn Architecture>
+                    }
+                }
+            } else if elf.header.e_machine == goblin::elf::header::EM_RISCV {
+                if elf.is_64 {
+                    Box::new(Riscv64::new()) as Box<dyn Architecture>
+                } else {
+                    Box::new(Riscv32::new()) as Box<dyn Architecture>
+                }
             } else {
                 bail!("Unsupported Architecture");
             }
@@ -51,6 +114,8 @@ impl Elf {
             bytes,
             user_function_entries: Vec::new(),
             architecture,
+            #[cfg(feature = "dwarf")]
+            debug_info: OnceLock::new(),
         })
     }
 
@@ -60,6 +125,11 @@ impl Elf {
         self.base_address
     }
 
+    /// Get the raw bytes of this Elf.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
     /// Load an Elf from a file and use the given base address.
     pub fn from_file_with_base_address<P: AsRef<Path>>(
         filename: P,
@@ -196,10 +266,229 @@ impl Elf {
             symbols.push(Symbol::new(name, rel.r_offset));
         }
 
+        symbols.append(&mut self.plt_symbols());
+
         symbols.sort();
         symbols.dedup();
         symbols
     }
+
+    /// Return a `name@plt` symbol for each stub through which this Elf calls
+    /// an imported function
+    pub fn plt_symbols(&self) -> Vec<Symbol> {
+        plt::plt_symbols(self)
+    }
+
+    /// Read a pointer from memory, applying the relative relocation, if there
+    /// is one, which fills it in at load time.
+    fn pointer(&self, elf: &goblin::elf::Elf, memory: &Memory, address: u64) -> Option<u64> {
+        let bits = pointer_bits(elf);
+        memory.get8(address + (bits / 8) as u64 - 1)?;
+        let value = memory.get(address, bits)?.value_u64()?;
+
+        let relative = |relocation: &goblin::elf::Reloc| {
+            relocation.r_sym == 0 && relocation.r_offset + self.base_address == address
+        };
+        if let Some(addend) = elf
+            .dynrelas
+            .iter()
+            .find(relative)
+            .and_then(|relocation| relocation.r_addend)
+        {
+            return Some((addend as u64).wrapping_add(self.base_address));
+        }
+        if value != 0 && elf.dynrels.iter().any(|relocation| relative(&relocation)) {
+            return Some(value.wrapping_add(self.base_address));
+        }
+
+        Some(value)
+    }
+
+    /// Get the address of the code of the function at `address`.
+    ///
+    /// PPC64 ELFv1 function symbols, entry points and function pointers are
+    /// the addresses of function descriptors in `.opd`, whose first
+    /// doubleword is the address of the function's code.
+    fn function_address(&self, elf: &goblin::elf::Elf, memory: &Memory, address: u64) -> u64 {
+        let descriptor = section_header(elf, ".opd").map_or(false, |section| {
+            let start = section.sh_addr + self.base_address;
+            address >= start && address < start + section.sh_size
+        });
+        if descriptor {
+            self.pointer(elf, memory, address).unwrap_or(address)
+        } else {
+            address
+        }
+    }
+
+    /// Get the contents of the section with the given name, or nothing if
+    /// there is no such section or it is compressed.
+    #[cfg(feature = "dwarf")]
+    fn section_bytes(&self, elf: &goblin::elf::Elf, name: &str) -> &[u8] {
+        section_header(elf, name)
+            .filter(|section| {
+                section.sh_type != goblin::elf::section_header::SHT_NOBITS
+                    && section.sh_flags & u64::from(goblin::elf::section_header::SHF_COMPRESSED)
+                        == 0
+            })
+            .and_then(|section| {
+                let offset = section.sh_offset as usize;
+                self.bytes.get(offset..(offset + section.sh_size as usize))
+            })
+            .unwrap_or(&[])
+    }
+
+    /// Get the constructors and destructors from `DT_INIT`, `DT_FINI`,
+    /// `.preinit_array`, `.init_array`, `.fini_array`, `.ctors` and `.dtors`.
+    fn constructors(&self, elf: &goblin::elf::Elf, memory: &Memory) -> Vec<u64> {
+        use goblin::elf::dynamic::*;
+
+        let word_bytes = (pointer_bits(elf) / 8) as u64;
+
+        let mut functions = Vec::new();
+        // Arrays of pointers, as (address, size)
+        let mut arrays = Vec::new();
+
+        if let Some(ref dynamic) = elf.dynamic {
+            let tag = |tag| {
+                dynamic
+                    .dyns
+                    .iter()
+                    .find(|dyn_| dyn_.d_tag == tag)
+                    .map(|dyn_| dyn_.d_val)
+            };
+            for (array, size) in [
+                (DT_PREINIT_ARRAY, DT_PREINIT_ARRAYSZ),
+                (DT_INIT_ARRAY, DT_INIT_ARRAYSZ),
+                (DT_FINI_ARRAY, DT_FINI_ARRAYSZ),
+            ]
+            .iter()
+            {
+                if let (Some(array), Some(size)) = (tag(*array), tag(*size)) {
+                    arrays.push((array + self.base_address, size));
+                }
+            }
+            for function in [DT_INIT, DT_FINI].iter() {
+                if let Some(function) = tag(*function) {
+                    functions.push(function + self.base_address);
+                }
+            }
+        }
+
+        // Static binaries have no dynamic section to tell us where the arrays
+        // are
+        if arrays.is_empty() {
+            for name in [".preinit_array", ".init_array", ".fini_array"].iter() {
+                if let Some(section) = section_header(elf, name) {
+                    arrays.push((section.sh_addr + self.base_address, section.sh_size));
+                }
+            }
+        }
+
+        // .ctors and .dtors are bracketed by -1 and 0
+        for name in [".ctors", ".dtors"].iter() {
+            if let Some(section) = section_header(elf, name) {
+                arrays.push((section.sh_addr + self.base_address, section.sh_size));
+            }
+        }
+
+        for (address, size) in arrays {
+            for i in 0..(size / word_bytes) {
+                if let Some(function) = self.pointer(elf, memory, address + i * word_bytes) {
+                    functions.push(function);
+                }
+            }
+        }
+
+        let all_ones = match word_bytes {
+            4 => 0xffff_ffff,
+            _ => 0xffff_ffff_ffff_ffff,
+        };
+        functions.retain(|function| *function != 0 && *function != all_ones);
+        functions
+    }
+
+    /// Get the first instruction of every function described by
+    /// `.eh_frame_hdr` and `.eh_frame`.
+    fn eh_frame_functions(&self, elf: &goblin::elf::Elf, memory: &Memory) -> Vec<u64> {
+        let endian = self.architecture.endian();
+        let word_bytes = pointer_bits(elf) / 8;
+
+        let mut functions = Vec::new();
+
+        let mut eh_frame = section_header(elf, ".eh_frame")
+            .map(|section| (section.sh_addr + self.base_address, Some(section.sh_size)));
+
+        // The .eh_frame_hdr segment is there even when section headers are
+        // not
+        let eh_frame_hdr = elf
+            .program_headers
+            .iter()
+            .find(|ph| ph.p_type == goblin::elf::program_header::PT_GNU_EH_FRAME)
+            .map(|ph| (ph.p_vaddr + self.base_address, ph.p_memsz))
+            .or_else(|| {
+                section_header(elf, ".eh_frame_hdr")
+                    .map(|section| (section.sh_addr + self.base_address, section.sh_size))
+            });
+
+        if let Some((address, size)) = eh_frame_hdr {
+            let hdr = memory_bytes(memory, address, Some(size)).and_then(|bytes| {
+                eh_frame::eh_frame_hdr(bytes, address, endian.clone(), word_bytes).ok()
+            });
+            if let Some((eh_frame_address, locations)) = hdr {
+                functions.extend(locations);
+                if eh_frame.is_none() {
+                    eh_frame = eh_frame_address.map(|address| (address, None));
+                }
+            }
+        }
+
+        if let Some((address, size)) = eh_frame {
+            if let Some(bytes) = memory_bytes(memory, address, size) {
+                functions.extend(eh_frame::eh_frame_functions(
+                    bytes, address, endian, word_bytes,
+                ));
+            }
+        }
+
+        functions.sort();
+        functions.dedup();
+        functions
+    }
+}
+
+/// Get the size of a pointer in bits, as given by the class of the Elf. For
+/// n32 this is narrower than the architecture's word.
+fn pointer_bits(elf: &goblin::elf::Elf) -> usize {
+    if elf.is_64 {
+        64
+    } else {
+        32
+    }
+}
+
+/// Find the section header with the given name.
+pub(super) fn section_header<'e>(
+    elf: &'e goblin::elf::Elf,
+    name: &str,
+) -> Option<&'e goblin::elf::SectionHeader> {
+    elf.section_headers.iter().find(|section_header| {
+        elf.shdr_strtab
+            .get(section_header.sh_name)
+            .and_then(|section_name| section_name.ok())
+            == Some(name)
+    })
+}
+
+/// Get the bytes in memory from `address`, up to `size` bytes or the end of
+/// the memory section holding them.
+fn memory_bytes(memory: &Memory, address: u64, size: Option<u64>) -> Option<&[u8]> {
+    let (section_address, section) = memory.sections().range(..=address).next_back()?;
+    let bytes = section.data().get((address - section_address) as usize..)?;
+    Some(match size {
+        Some(size) => &bytes[..bytes.len().min(size as usize)],
+        None => bytes,
+    })
 }
 
 impl Loader for Elf {
@@ -236,6 +525,7 @@ impl Loader for Elf {
 
     fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
         let elf = self.elf();
+        let memory = self.memory()?;
 
         let mut function_entries = Vec::new();
 
@@ -245,11 +535,13 @@ impl Loader for Elf {
         for sym in &elf.dynsyms {
             if sym.is_function() && sym.st_value != 0 && sym.st_shndx > 0 {
                 let name = &elf.dynstrtab[sym.st_name];
-                function_entries.push(FunctionEntry::new(
-                    sym.st_value + self.base_address,
-                    Some(name.to_string()),
-                ));
-                functions_added.insert(sym.st_value);
+                let address =
+                    self.function_address(&elf, &memory, sym.st_value + self.base_address);
+                function_entries.push(
+                    FunctionEntry::new(address, Some(name.to_string()))
+                        .with_source(FunctionEntrySource::Symbol),
+                );
+                functions_added.insert(address);
             }
         }
 
@@ -257,40 +549,108 @@ impl Loader for Elf {
         for sym in &elf.syms {
             if sym.is_function() && sym.st_value != 0 && sym.st_shndx > 0 {
                 let name = &elf.strtab[sym.st_name];
-                function_entries.push(FunctionEntry::new(
-                    sym.st_value + self.base_address,
-                    Some(name.to_string()),
-                ));
-                functions_added.insert(sym.st_value);
+                let address =
+                    self.function_address(&elf, &memory, sym.st_value + self.base_address);
+                function_entries.push(
+                    FunctionEntry::new(address, Some(name.to_string()))
+                        .with_source(FunctionEntrySource::Symbol),
+                );
+                functions_added.insert(address);
             }
         }
 
-        if !functions_added.contains(&elf.header.e_entry) {
-            function_entries.push(FunctionEntry::new(
-                elf.header.e_entry + self.base_address,
-                None,
-            ));
+        let entry = self.function_address(&elf, &memory, elf.header.e_entry + self.base_address);
+        if functions_added.insert(entry) {
+            function_entries.push(
+                FunctionEntry::new(entry, None).with_source(FunctionEntrySource::ProgramEntry),
+            );
         }
 
         for user_function_entry in &self.user_function_entries {
-            if functions_added
-                .get(&(user_function_entry + self.base_address))
-                .is_some()
-            {
+            if !functions_added.insert(user_function_entry + self.base_address) {
                 continue;
             }
 
-            function_entries.push(FunctionEntry::new(
-                user_function_entry + self.base_address,
-                Some(format!("user_function_{:x}", user_function_entry)),
+            function_entries.push(
+                FunctionEntry::new(
+                    user_function_entry + self.base_address,
+                    Some(format!("user_function_{:x}", user_function_entry)),
+                )
+                .with_source(FunctionEntrySource::User),
+            );
+        }
+
+        for symbol in self.plt_symbols() {
+            if functions_added.insert(symbol.address()) {
+                function_entries.push(
+                    FunctionEntry::new(symbol.address(), Some(symbol.name().to_string()))
+                        .with_source(FunctionEntrySource::Plt),
+                );
+            }
+        }
+
+        if let Some(debug_info) = self.debug_info()? {
+            for function_entry in debug_info.function_entries() {
+                if functions_added.insert(function_entry.address()) {
+                    function_entries.push(function_entry);
+                }
+            }
+        }
+
+        // Functions stripped binaries still tell us about, in order of how
+        // much we trust them.
+        let mut discovered: Vec<(u64, Option<String>, FunctionEntrySource)> = Vec::new();
+
+        let pointer = |address| self.pointer(&elf, &memory, address);
+        let [main, init, fini] = libc_start_main_arguments(
+            self.architecture(),
+            &memory,
+            &pointer,
+            pointer_bits(&elf),
+            entry,
+        );
+        if let Some(main) = main {
+            discovered.push((
+                main,
+                Some("main".to_string()),
+                FunctionEntrySource::LibcStartMain,
             ));
         }
+        for address in init.iter().chain(fini.iter()) {
+            discovered.push((*address, None, FunctionEntrySource::LibcStartMain));
+        }
+
+        for address in self.constructors(&elf, &memory) {
+            discovered.push((address, None, FunctionEntrySource::Constructor));
+        }
+
+        for address in self.eh_frame_functions(&elf, &memory) {
+            discovered.push((address, None, FunctionEntrySource::UnwindInfo));
+        }
+
+        for (address, name, source) in discovered {
+            let address = self.function_address(&elf, &memory, address);
+            let executable = memory
+                .permissions(address)
+                .map_or(false, |p| p.contains(MemoryPermissions::EXECUTE));
+            if executable && functions_added.insert(address) {
+                function_entries.push(FunctionEntry::new(address, name).with_source(source));
+            }
+        }
 
         Ok(function_entries)
     }
 
     fn program_entry(&self) -> u64 {
-        self.elf().header.e_entry
+        let elf = self.elf();
+        // A PPC64 ELFv1 entry point is a function descriptor
+        if section_header(&elf, ".opd").is_some() {
+            if let Ok(memory) = self.memory() {
+                let entry = elf.header.e_entry + self.base_address;
+                return self.function_address(&elf, &memory, entry) - self.base_address;
+            }
+        }
+        elf.header.e_entry
     }
 
     fn architecture(&self) -> &dyn Architecture {
@@ -304,4 +664,23 @@ impl Loader for Elf {
     fn symbols(&self) -> Vec<Symbol> {
         self.symbols()
     }
+
+    #[cfg(feature = "dwarf")]
+    fn debug_info(&self) -> Result<Option<&DebugInfo>> {
+        if let Some(debug_info) = self.debug_info.get() {
+            return Ok(debug_info.as_ref());
+        }
+
+        let elf = self.elf();
+        let debug_info = if self.section_bytes(&elf, ".debug_info").is_empty() {
+            None
+        } else {
+            Some(dwarf::debug_info(
+                |name| self.section_bytes(&elf, name),
+                self.architecture(),
+                self.base_address,
+            )?)
+        };
+        Ok(self.debug_info.get_or_init(|| debug_info).as_ref())
+    }
 }
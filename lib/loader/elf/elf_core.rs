@@ -0,0 +1,374 @@
+use crate::architecture::*;
+use crate::executor;
+use crate::il;
+use crate::loader::*;
+use crate::memory::backing::Memory;
+use crate::RC;
+use std::path::Path;
+
+/// The offset of `pr_pid` in a 32-bit `elf_prstatus`.
+const PR_PID_OFFSET_32: usize = 24;
+/// The offset of `pr_pid` in a 64-bit `elf_prstatus`.
+const PR_PID_OFFSET_64: usize = 32;
+/// The offset of `pr_cursig` in an `elf_prstatus`.
+const PR_CURSIG_OFFSET: usize = 12;
+/// The offset of `pr_reg` in a 32-bit `elf_prstatus`.
+const PR_REG_OFFSET_32: usize = 72;
+/// The offset of `pr_reg` in a 64-bit `elf_prstatus`.
+const PR_REG_OFFSET_64: usize = 112;
+
+/// The amd64 `user_regs_struct`, as the translator names its registers.
+const AMD64_USER_REGS: [Option<&str>; 27] = [
+    Some("r15"),
+    Some("r14"),
+    Some("r13"),
+    Some("r12"),
+    Some("rbp"),
+    Some("rbx"),
+    Some("r11"),
+    Some("r10"),
+    Some("r9"),
+    Some("r8"),
+    Some("rax"),
+    Some("rcx"),
+    Some("rdx"),
+    Some("rsi"),
+    Some("rdi"),
+    None, // orig_rax
+    None, // rip
+    None, // cs
+    None, // eflags
+    Some("rsp"),
+    None, // ss
+    Some("fs_base"),
+    Some("gs_base"),
+    None, // ds
+    None, // es
+    None, // fs
+    None, // gs
+];
+const AMD64_PC: usize = 16;
+const AMD64_FLAGS: usize = 18;
+
+/// The i386 `user_regs_struct`.
+const X86_USER_REGS: [Option<&str>; 17] = [
+    Some("ebx"),
+    Some("ecx"),
+    Some("edx"),
+    Some("esi"),
+    Some("edi"),
+    Some("ebp"),
+    Some("eax"),
+    None, // ds
+    None, // es
+    None, // fs
+    None, // gs
+    None, // orig_eax
+    None, // eip
+    None, // cs
+    None, // eflags
+    Some("esp"),
+    None, // ss
+];
+const X86_PC: usize = 12;
+const X86_FLAGS: usize = 14;
+
+/// The bits of eflags, and the scalars the x86 translator keeps them in.
+const X86_FLAG_BITS: [(&str, u64); 7] = [
+    ("CF", 0),
+    ("PF", 2),
+    ("AF", 4),
+    ("ZF", 6),
+    ("SF", 7),
+    ("DF", 10),
+    ("OF", 11),
+];
+
+/// The state of one thread, as captured in an `NT_PRSTATUS` note.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoreThread {
+    pid: u32,
+    signal: u16,
+    program_counter: u64,
+    registers: Vec<(String, il::Constant)>,
+}
+
+impl CoreThread {
+    /// The id of this thread.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// The signal this thread was stopped by, or 0.
+    pub fn signal(&self) -> u16 {
+        self.signal
+    }
+
+    /// The address this thread was executing when the core was dumped.
+    pub fn program_counter(&self) -> u64 {
+        self.program_counter
+    }
+
+    /// The registers of this thread, named as they are by the architecture's
+    /// translator.
+    pub fn registers(&self) -> &[(String, il::Constant)] {
+        &self.registers
+    }
+
+    /// Get the value of a register by its name.
+    pub fn register(&self, name: &str) -> Option<&il::Constant> {
+        self.registers
+            .iter()
+            .find(|(register, _)| register == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// Loader for an ELF core dump.
+///
+/// The memory of the core is the contents of its `PT_LOAD` segments, and each
+/// `NT_PRSTATUS` note gives the registers of one thread. A core can be turned
+/// into an `executor::State` to resume concrete execution of a thread from
+/// where it stopped.
+#[derive(Debug)]
+pub struct ElfCore {
+    elf: Elf,
+    threads: Vec<CoreThread>,
+}
+
+impl ElfCore {
+    /// Create a new `ElfCore` from the bytes of a core file.
+    pub fn new(bytes: Vec<u8>) -> Result<ElfCore> {
+        ElfCore::from_elf(Elf::new(bytes, 0)?)
+    }
+
+    /// Load an `ElfCore` from a file.
+    pub fn from_file<P: AsRef<Path>>(filename: P) -> Result<ElfCore> {
+        ElfCore::from_elf(Elf::from_file(filename)?)
+    }
+
+    fn from_elf(elf: Elf) -> Result<ElfCore> {
+        let threads = {
+            let goblin_elf = elf.elf();
+            if goblin_elf.header.e_type != goblin::elf::header::ET_CORE {
+                bail!("Not an ELF core file");
+            }
+
+            let mut threads = Vec::new();
+            if let Some(notes) = goblin_elf.iter_note_headers(elf.bytes()) {
+                for note in notes {
+                    let note = note.map_err(|_| "Malformed ELF core note")?;
+                    if note.name == "CORE" && note.n_type == goblin::elf::note::NT_PRSTATUS {
+                        threads.push(prstatus(elf.architecture(), note.desc)?);
+                    }
+                }
+            }
+            threads
+        };
+
+        Ok(ElfCore { elf, threads })
+    }
+
+    /// The threads captured in this core, in the order of their notes. The
+    /// thread which caused the core to be dumped comes first.
+    pub fn threads(&self) -> &[CoreThread] {
+        &self.threads
+    }
+
+    /// Create an `executor::State` for the thread at the given index, with
+    /// every captured register set and this core's memory as the backing.
+    pub fn state(&self, thread: usize) -> Result<executor::State> {
+        let thread = self
+            .threads
+            .get(thread)
+            .ok_or_else(|| format!("No thread {} in core", thread))?;
+
+        let memory = executor::Memory::new_with_backing(
+            self.architecture().endian(),
+            RC::new(self.memory()?),
+        );
+        let mut state = executor::State::new(memory);
+        for (name, value) in thread.registers() {
+            state.set_scalar(name.clone(), value.clone());
+        }
+
+        Ok(state)
+    }
+}
+
+/// Read the word at the given index of `pr_reg`.
+fn register(desc: &[u8], architecture: &dyn Architecture, index: usize) -> Result<u64> {
+    let word_bytes = architecture.word_size() / 8;
+    let offset = match word_bytes {
+        4 => PR_REG_OFFSET_32,
+        _ => PR_REG_OFFSET_64,
+    } + index * word_bytes;
+    word(desc, architecture, offset, word_bytes)
+}
+
+/// Read an unsigned value of `size` bytes from the given offset.
+fn word(desc: &[u8], architecture: &dyn Architecture, offset: usize, size: usize) -> Result<u64> {
+    let bytes = desc
+        .get(offset..(offset + size))
+        .ok_or("NT_PRSTATUS note too short")?;
+    Ok(match architecture.endian() {
+        Endian::Big => bytes
+            .iter()
+            .fold(0, |value, byte| (value << 8) | u64::from(*byte)),
+        Endian::Little => bytes
+            .iter()
+            .rev()
+            .fold(0, |value, byte| (value << 8) | u64::from(*byte)),
+    })
+}
+
+/// Parse the descriptor of an `NT_PRSTATUS` note.
+fn prstatus(architecture: &dyn Architecture, desc: &[u8]) -> Result<CoreThread> {
+    let bits = architecture.word_size();
+    let pid_offset = match bits {
+        32 => PR_PID_OFFSET_32,
+        _ => PR_PID_OFFSET_64,
+    };
+    let pid = word(desc, architecture, pid_offset, 4)? as u32;
+    let signal = word(desc, architecture, PR_CURSIG_OFFSET, 2)? as u16;
+
+    let reg = |index: usize| register(desc, architecture, index);
+    let mut registers: Vec<(String, il::Constant)> = Vec::new();
+    let mut set = |name: &str, value: u64, bits: usize| {
+        registers.push((name.to_string(), il::const_(value, bits)));
+    };
+
+    let program_counter = match architecture.name() {
+        "amd64" | "x86" => {
+            let (names, pc, flags): (&[Option<&str>], usize, usize) = match bits {
+                64 => (&AMD64_USER_REGS, AMD64_PC, AMD64_FLAGS),
+                _ => (&X86_USER_REGS, X86_PC, X86_FLAGS),
+            };
+            for (index, name) in names.iter().enumerate() {
+                if let Some(name) = name {
+                    set(name, reg(index)?, bits);
+                }
+            }
+            let eflags = reg(flags)?;
+            for (name, bit) in X86_FLAG_BITS.iter() {
+                set(name, (eflags >> bit) & 1, 1);
+            }
+            reg(pc)?
+        }
+        "mips" | "mipsel" | "mips64" | "mips64el" | "mipsn32" | "mipsn32el" => {
+            // The 32-bit layout begins with six words of padding.
+            let first = match bits {
+                32 => 6,
+                _ => 0,
+            };
+            for (index, name) in MIPS_REGISTERS.iter().enumerate().skip(1) {
+                set(name, reg(first + index)?, bits);
+            }
+            set("$lo", reg(first + 32)?, bits);
+            set("$hi", reg(first + 33)?, bits);
+            reg(first + 34)?
+        }
+        "ppc" | "ppc64" | "ppc64elfv2" | "ppc64le" => {
+            for (index, name) in PPC_REGISTERS.iter().enumerate() {
+                set(name, reg(index)?, bits);
+            }
+            set("ctr", reg(35)?, bits);
+            set("lr", reg(36)?, bits);
+
+            let xer = reg(37)?;
+            set("xer-so", (xer >> 31) & 1, 1);
+            set("xer-ov", (xer >> 30) & 1, 1);
+            set("xer-ca", (xer >> 29) & 1, 1);
+
+            // cr0 is held in the most significant nibble of the condition
+            // register.
+            let ccr = reg(38)?;
+            for field in 0..8 {
+                let nibble = (ccr >> (28 - field * 4)) & 0xf;
+                set(&format!("cr{}-lt", field), (nibble >> 3) & 1, 1);
+                set(&format!("cr{}-gt", field), (nibble >> 2) & 1, 1);
+                set(&format!("cr{}-eq", field), (nibble >> 1) & 1, 1);
+                set(&format!("cr{}-so", field), nibble & 1, 1);
+            }
+            reg(32)?
+        }
+        "aarch64" => {
+            for (index, name) in AARCH64_REGISTERS.iter().enumerate() {
+                set(name, reg(index)?, 64);
+            }
+            let pstate = reg(33)?;
+            set("n", (pstate >> 31) & 1, 1);
+            set("z", (pstate >> 30) & 1, 1);
+            set("c", (pstate >> 29) & 1, 1);
+            set("v", (pstate >> 28) & 1, 1);
+            reg(32)?
+        }
+        "arm" => {
+            for (index, name) in ARM_REGISTERS.iter().enumerate() {
+                set(name, reg(index)?, 32);
+            }
+            let cpsr = reg(16)?;
+            set("n", (cpsr >> 31) & 1, 1);
+            set("z", (cpsr >> 30) & 1, 1);
+            set("c", (cpsr >> 29) & 1, 1);
+            set("v", (cpsr >> 28) & 1, 1);
+            // Thumb code is addressed with the Thumb bit set.
+            reg(15)? | ((cpsr >> 5) & 1)
+        }
+        "riscv32" | "riscv64" => {
+            for (index, name) in RISCV_REGISTERS.iter().enumerate().skip(1) {
+                set(name, reg(index)?, bits);
+            }
+            reg(0)?
+        }
+        name => bail!("ELF cores are not supported for {}", name),
+    };
+
+    Ok(CoreThread {
+        pid,
+        signal,
+        program_counter,
+        registers,
+    })
+}
+
+impl Loader for ElfCore {
+    fn memory(&self) -> Result<Memory> {
+        self.elf.memory()
+    }
+
+    fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
+        // There are no symbols in a core, but we know the code every thread
+        // was executing.
+        let mut function_entries: Vec<FunctionEntry> = Vec::new();
+        for thread in &self.threads {
+            let address = thread.program_counter();
+            if function_entries
+                .iter()
+                .all(|entry| entry.address() != address)
+            {
+                function_entries.push(FunctionEntry::new(address, None));
+            }
+        }
+        Ok(function_entries)
+    }
+
+    fn program_entry(&self) -> u64 {
+        self.threads
+            .first()
+            .map(|thread| thread.program_counter())
+            .unwrap_or(0)
+    }
+
+    fn architecture(&self) -> &dyn Architecture {
+        self.elf.architecture()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        Vec::new()
+    }
+}
@@ -6,6 +6,7 @@ use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 /// The address where the first library will be loaded
 const DEFAULT_LIB_BASE: u64 = 0x4000_0000;
@@ -17,6 +18,28 @@ const DT_MIPS_LOCAL_GOTNO: u64 = 0x7000_000a;
 const DT_MIPS_GOTSYM: u64 = 0x7000_0013;
 const DT_MIPS_SYMTABNO: u64 = 0x7000_0011;
 
+// Some PPC-specific DT entries and relocation types, which Goblin does not
+// yet have.
+const DT_PPC_GOT: u64 = 0x7000_0000;
+const R_PPC_NONE: u32 = 0;
+const R_PPC_ADDR32: u32 = 1;
+const R_PPC_ADDR16: u32 = 3;
+const R_PPC_ADDR16_LO: u32 = 4;
+const R_PPC_ADDR16_HI: u32 = 5;
+const R_PPC_ADDR16_HA: u32 = 6;
+const R_PPC_COPY: u32 = 19;
+const R_PPC_GLOB_DAT: u32 = 20;
+const R_PPC_JMP_SLOT: u32 = 21;
+const R_PPC_RELATIVE: u32 = 22;
+const R_PPC_UADDR32: u32 = 24;
+const R_PPC_DTPMOD32: u32 = 68;
+const R_PPC_TPREL32: u32 = 73;
+const R_PPC_DTPREL32: u32 = 78;
+
+/// The bias PPC adds to offsets in a TLS block, so the 16-bit signed offsets
+/// of the code reach all of the first 64K of the block.
+const PPC_DTP_OFFSET: u64 = 0x8000;
+
 /// A helper to build an ElfLinker using the builder pattern.
 #[derive(Clone, Debug)]
 pub struct ElfLinkerBuilder {
@@ -92,6 +115,13 @@ pub struct ElfLinker {
     just_interpreter: bool,
     /// The paths where ElfLinker will look for dependencies
     ld_paths: Option<Vec<PathBuf>>,
+    /// The TLS module id, and offset of the static TLS block below the thread
+    /// pointer, of each loaded Elf with a PT_TLS segment
+    tls_modules: BTreeMap<String, (u64, u64)>,
+    /// The size of the static TLS area below the thread pointer
+    tls_size: u64,
+    /// The debugging information of every loaded Elf, once it is read
+    debug_info: OnceLock<Option<DebugInfo>>,
 }
 
 impl ElfLinker {
@@ -128,6 +158,9 @@ impl ElfLinker {
             do_relocations,
             just_interpreter,
             ld_paths,
+            tls_modules: BTreeMap::new(),
+            tls_size: 0,
+            debug_info: OnceLock::new(),
         };
 
         elf_linker.load_elf(&filename, 0)?;
@@ -180,6 +213,23 @@ impl ElfLinker {
         // Add this Elf to the loaded Elfs
         let filename = filename.file_name().unwrap().to_str().unwrap().to_string();
         self.loaded.insert(filename.clone(), elf);
+        self.debug_info = OnceLock::new();
+
+        // Give it a place in the static TLS area
+        let tls_segment = self.loaded[&filename]
+            .elf()
+            .program_headers
+            .iter()
+            .find(|ph| ph.p_type == goblin::elf::program_header::PT_TLS)
+            .map(|ph| (ph.p_memsz, ph.p_align.max(1)));
+        if let Some((size, align)) = tls_segment {
+            let end = self.tls_size + size;
+            let offset = end + (align - end % align) % align;
+            let module_id = self.tls_modules.len() as u64 + 1;
+            self.tls_modules
+                .insert(filename.clone(), (module_id, offset));
+            self.tls_size = offset;
+        }
 
         {
             let elf = &self.loaded[&filename];
@@ -189,10 +239,8 @@ impl ElfLinker {
                 if self.symbols.get(symbol.name()).is_some() {
                     continue;
                 }
-                self.symbols.insert(
-                    symbol.name().to_string(),
-                    elf.base_address() + symbol.address(),
-                );
+                self.symbols
+                    .insert(symbol.name().to_string(), symbol.address());
             }
         }
 
@@ -219,6 +267,8 @@ impl ElfLinker {
             match self.loaded[&filename].elf().header.e_machine {
                 goblin::elf::header::EM_386 => self.relocations_x86(&filename)?,
                 goblin::elf::header::EM_MIPS => self.relocations_mips(&filename)?,
+                goblin::elf::header::EM_X86_64 => self.relocations_amd64(&filename)?,
+                goblin::elf::header::EM_PPC => self.relocations_ppc(&filename)?,
                 _ => bail!("relocations unsupported for target architecture"),
             }
         }
@@ -369,6 +419,343 @@ impl ElfLinker {
         Ok(())
     }
 
+    /// Find the TLS module id and the offset of the TLS block of the Elf which
+    /// defines the TLS symbol `name`, and the symbol's offset in that block.
+    fn tls_symbol(&self, name: &str) -> Option<(u64, u64, u64)> {
+        self.loaded.iter().find_map(|(filename, elf)| {
+            let (module_id, offset) = self.tls_modules.get(filename)?;
+            let elf = elf.elf();
+            let sym = elf.dynsyms.iter().find(|sym| {
+                sym.st_shndx != 0
+                    && sym.st_type() == goblin::elf::sym::STT_TLS
+                    && elf.dynstrtab.get(sym.st_name).and_then(|name| name.ok()) == Some(name)
+            })?;
+            Some((*module_id, *offset, sym.st_value))
+        })
+    }
+
+    /// Find the TLS module id, TLS block offset, and offset in the block of
+    /// the symbol of a TLS relocation in the Elf `filename`.
+    fn tls_relocation_symbol(
+        &self,
+        filename: &str,
+        reloc: &goblin::elf::Reloc,
+        sym: &goblin::elf::Sym,
+        sym_name: &str,
+    ) -> Result<(u64, u64, u64)> {
+        if reloc.r_sym == 0 || sym.st_shndx != 0 {
+            let (module_id, offset) = self
+                .tls_modules
+                .get(filename)
+                .ok_or(format!("{} has no TLS segment", filename))?;
+            let value = if reloc.r_sym == 0 { 0 } else { sym.st_value };
+            Ok((*module_id, *offset, value))
+        } else {
+            Ok(self
+                .tls_symbol(sym_name)
+                .ok_or(format!("Could not resolve TLS symbol {}", sym_name))?)
+        }
+    }
+
+    /// Copy `size` bytes of the symbol `name`, as defined by a loaded Elf other
+    /// than `filename`, to `address`. This is the work of a COPY relocation.
+    fn copy_symbol(
+        loaded: &BTreeMap<String, Elf>,
+        memory: &mut Memory,
+        filename: &str,
+        name: &str,
+        address: u64,
+        size: u64,
+    ) -> Result<()> {
+        let source = loaded
+            .iter()
+            .filter(|(loaded, _)| loaded.as_str() != filename)
+            .find_map(|(_, elf)| {
+                elf.exported_symbols()
+                    .into_iter()
+                    .find(|symbol| symbol.name() == name)
+            })
+            .ok_or(format!("Could not resolve symbol {}", name))?;
+        for i in 0..size {
+            let byte = memory.get8(source.address() + i).ok_or(format!(
+                "Could not copy {} from 0x{:x}",
+                name,
+                source.address()
+            ))?;
+            memory.set8(address + i, byte)?;
+        }
+        Ok(())
+    }
+
+    /// Perform amd64-specific relocations
+    fn relocations_amd64(&mut self, filename: &str) -> Result<()> {
+        let elf = &self.loaded[filename];
+        let dynsyms = elf.elf().dynsyms;
+        let dynstrtab = elf.elf().dynstrtab;
+        let base_address = elf.base_address();
+
+        for reloc in elf.elf().dynrelas.iter().chain(elf.elf().pltrelocs.iter()) {
+            let address = reloc.r_offset + base_address;
+            let addend = reloc.r_addend.unwrap_or(0) as u64;
+
+            let sym = dynsyms
+                .get(reloc.r_sym)
+                .ok_or(format!("Could not get symbol {}", reloc.r_sym))?;
+            let sym_name = &dynstrtab[sym.st_name];
+
+            // The value of the relocation's symbol. Unresolved weak symbols
+            // take the value 0.
+            let symbol_value = || -> Option<u64> {
+                if reloc.r_sym == 0 {
+                    Some(0)
+                } else if let Some(value) = self.symbols.get(sym_name) {
+                    Some(*value)
+                } else if sym.st_bind() == goblin::elf::sym::STB_WEAK {
+                    Some(0)
+                } else {
+                    None
+                }
+            };
+
+            let tls_symbol = || self.tls_relocation_symbol(filename, &reloc, &sym, sym_name);
+
+            match reloc.r_type {
+                goblin::elf::reloc::R_X86_64_NONE => {}
+                goblin::elf::reloc::R_X86_64_64 => {
+                    let value = match symbol_value() {
+                        Some(value) => value,
+                        None => {
+                            warn!("Could not resolve symbol {}", sym_name);
+                            continue;
+                        }
+                    };
+                    self.memory.set64(address, value.wrapping_add(addend))?;
+                }
+                goblin::elf::reloc::R_X86_64_GLOB_DAT => {
+                    let value = match symbol_value() {
+                        Some(value) => value,
+                        None => {
+                            warn!("Could not resolve symbol {}", sym_name);
+                            continue;
+                        }
+                    };
+                    self.memory.set64(address, value)?;
+                }
+                goblin::elf::reloc::R_X86_64_JUMP_SLOT => {
+                    let value = match symbol_value() {
+                        Some(value) => value,
+                        None => bail!("Could not resolve symbol {}", sym_name),
+                    };
+                    self.memory.set64(address, value)?;
+                }
+                goblin::elf::reloc::R_X86_64_RELATIVE => {
+                    self.memory
+                        .set64(address, base_address.wrapping_add(addend))?;
+                }
+                goblin::elf::reloc::R_X86_64_COPY => {
+                    Self::copy_symbol(
+                        &self.loaded,
+                        &mut self.memory,
+                        filename,
+                        sym_name,
+                        address,
+                        sym.st_size,
+                    )?;
+                }
+                goblin::elf::reloc::R_X86_64_IRELATIVE => {
+                    // The value is the result of calling the resolver function
+                    // at this address, which we cannot do while linking. We
+                    // point at the resolver instead.
+                    warn!(
+                        "R_X86_64_IRELATIVE {:?}:0x{:x} set to its resolver",
+                        self.filename, reloc.r_offset
+                    );
+                    self.memory
+                        .set64(address, base_address.wrapping_add(addend))?;
+                }
+                goblin::elf::reloc::R_X86_64_DTPMOD64 => {
+                    let (module_id, _, _) = tls_symbol()?;
+                    self.memory.set64(address, module_id)?;
+                }
+                goblin::elf::reloc::R_X86_64_DTPOFF64 => {
+                    let (_, _, value) = tls_symbol()?;
+                    self.memory.set64(address, value.wrapping_add(addend))?;
+                }
+                goblin::elf::reloc::R_X86_64_TPOFF64 => {
+                    let (_, offset, value) = tls_symbol()?;
+                    self.memory
+                        .set64(address, value.wrapping_add(addend).wrapping_sub(offset))?;
+                }
+                goblin::elf::reloc::R_X86_64_TPOFF32 => {
+                    let (_, offset, value) = tls_symbol()?;
+                    self.memory.set32(
+                        address,
+                        value.wrapping_add(addend).wrapping_sub(offset) as u32,
+                    )?;
+                }
+                goblin::elf::reloc::R_X86_64_DTPOFF32 => {
+                    let (_, _, value) = tls_symbol()?;
+                    self.memory
+                        .set32(address, value.wrapping_add(addend) as u32)?;
+                }
+                goblin::elf::reloc::R_X86_64_TLSDESC => {
+                    // A TLS descriptor is a resolver function, and an argument
+                    // for it. With static TLS the argument is the offset from
+                    // the thread pointer, but there is no resolver to point at.
+                    warn!(
+                        "R_X86_64_TLSDESC {:?}:0x{:x} has no resolver",
+                        self.filename, reloc.r_offset
+                    );
+                    let (_, offset, value) = tls_symbol()?;
+                    self.memory
+                        .set64(address + 8, value.wrapping_add(addend).wrapping_sub(offset))?;
+                }
+                goblin::elf::reloc::R_X86_64_SIZE32 => {
+                    self.memory
+                        .set32(address, sym.st_size.wrapping_add(addend) as u32)?;
+                }
+                goblin::elf::reloc::R_X86_64_SIZE64 => {
+                    self.memory
+                        .set64(address, sym.st_size.wrapping_add(addend))?;
+                }
+                _ => warn!(
+                    "Ignoring unhandled relocation type {} at {:?}:0x{:x}",
+                    reloc.r_type, self.filename, reloc.r_offset
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    /// Perform PPC-specific relocations
+    fn relocations_ppc(&mut self, filename: &str) -> Result<()> {
+        let elf = &self.loaded[filename];
+        let dynsyms = elf.elf().dynsyms;
+        let dynstrtab = elf.elf().dynstrtab;
+        let base_address = elf.base_address();
+
+        // Binaries with a secure PLT have a DT_PPC_GOT entry, and their PLT is
+        // a table of addresses. Older binaries have an executable BSS-PLT of
+        // branch stubs, which the dynamic linker would patch.
+        let secure_plt = elf
+            .elf()
+            .dynamic
+            .map(|dynamic| dynamic.dyns.iter().any(|dyn_| dyn_.d_tag == DT_PPC_GOT))
+            .unwrap_or(false);
+
+        for reloc in elf.elf().dynrelas.iter().chain(elf.elf().pltrelocs.iter()) {
+            let address = reloc.r_offset + base_address;
+            let addend = reloc.r_addend.unwrap_or(0) as u64;
+
+            let sym = dynsyms
+                .get(reloc.r_sym)
+                .ok_or(format!("Could not get symbol {}", reloc.r_sym))?;
+            let sym_name = &dynstrtab[sym.st_name];
+
+            // The value of the relocation's symbol. Unresolved weak symbols
+            // take the value 0.
+            let symbol_value = || -> Option<u64> {
+                if reloc.r_sym == 0 {
+                    Some(0)
+                } else if let Some(value) = self.symbols.get(sym_name) {
+                    Some(*value)
+                } else if sym.st_bind() == goblin::elf::sym::STB_WEAK {
+                    Some(0)
+                } else {
+                    None
+                }
+            };
+
+            let tls_symbol = || self.tls_relocation_symbol(filename, &reloc, &sym, sym_name);
+
+            match reloc.r_type {
+                R_PPC_NONE => {}
+                R_PPC_ADDR32 | R_PPC_UADDR32 | R_PPC_GLOB_DAT => {
+                    let value = match symbol_value() {
+                        Some(value) => value,
+                        None => {
+                            warn!("Could not resolve symbol {}", sym_name);
+                            continue;
+                        }
+                    };
+                    self.memory
+                        .set32(address, value.wrapping_add(addend) as u32)?;
+                }
+                R_PPC_JMP_SLOT => {
+                    let value = match symbol_value() {
+                        Some(value) => value,
+                        None => bail!("Could not resolve symbol {}", sym_name),
+                    };
+                    if !secure_plt {
+                        warn!(
+                            "R_PPC_JMP_SLOT {:?}:0x{:x} is in a BSS-PLT, which is unsupported",
+                            self.filename, reloc.r_offset
+                        );
+                        continue;
+                    }
+                    self.memory
+                        .set32(address, value.wrapping_add(addend) as u32)?;
+                }
+                R_PPC_RELATIVE => {
+                    self.memory
+                        .set32(address, base_address.wrapping_add(addend) as u32)?;
+                }
+                R_PPC_COPY => {
+                    Self::copy_symbol(
+                        &self.loaded,
+                        &mut self.memory,
+                        filename,
+                        sym_name,
+                        address,
+                        sym.st_size,
+                    )?;
+                }
+                R_PPC_ADDR16 | R_PPC_ADDR16_LO | R_PPC_ADDR16_HI | R_PPC_ADDR16_HA => {
+                    let value = match symbol_value() {
+                        Some(value) => value.wrapping_add(addend),
+                        None => {
+                            warn!("Could not resolve symbol {}", sym_name);
+                            continue;
+                        }
+                    };
+                    let half = match reloc.r_type {
+                        R_PPC_ADDR16_HI => value >> 16,
+                        R_PPC_ADDR16_HA => value.wrapping_add(0x8000) >> 16,
+                        _ => value,
+                    };
+                    // PPC is big-endian
+                    self.memory.set8(address, (half >> 8) as u8)?;
+                    self.memory.set8(address + 1, half as u8)?;
+                }
+                R_PPC_DTPMOD32 => {
+                    let (module_id, _, _) = tls_symbol()?;
+                    self.memory.set32(address, module_id as u32)?;
+                }
+                R_PPC_DTPREL32 => {
+                    let (_, _, value) = tls_symbol()?;
+                    self.memory.set32(
+                        address,
+                        value.wrapping_add(addend).wrapping_sub(PPC_DTP_OFFSET) as u32,
+                    )?;
+                }
+                R_PPC_TPREL32 => {
+                    // The static TLS area is laid out below the thread
+                    // pointer, as it is for every architecture
+                    let (_, offset, value) = tls_symbol()?;
+                    self.memory.set32(
+                        address,
+                        value.wrapping_add(addend).wrapping_sub(offset) as u32,
+                    )?;
+                }
+                _ => warn!(
+                    "Ignoring unhandled relocation type {} at {:?}:0x{:x}",
+                    reloc.r_type, self.filename, reloc.r_offset
+                ),
+            }
+        }
+        Ok(())
+    }
+
     /// Perform MIPS-specific relocations
     fn relocations_mips(&mut self, filename: &str) -> Result<()> {
         let elf = &self.loaded[filename];
@@ -515,4 +902,21 @@ impl Loader for ElfLinker {
             .flat_map(|(_, elf)| elf.symbols())
             .collect()
     }
+
+    fn debug_info(&self) -> Result<Option<&DebugInfo>> {
+        if let Some(debug_info) = self.debug_info.get() {
+            return Ok(debug_info.as_ref());
+        }
+
+        let mut debug_info: Option<DebugInfo> = None;
+        for elf in self.loaded.values() {
+            if let Some(elf_debug_info) = elf.debug_info()? {
+                match debug_info {
+                    Some(ref mut debug_info) => debug_info.extend(elf_debug_info.clone()),
+                    None => debug_info = Some(elf_debug_info.clone()),
+                }
+            }
+        }
+        Ok(self.debug_info.get_or_init(|| debug_info).as_ref())
+    }
 }
@@ -0,0 +1,17 @@
+	.text
+	.globl	_start
+	.type	_start, @function
+_start:
+	call	lib_function@PLT
+	movq	lib_value(%rip), %rax
+	movq	lib_pointer@GOTPCREL(%rip), %rax
+	movq	lib_tls@gottpoff(%rip), %rax
+	movq	%fs:main_tls@tpoff, %rax
+	ret
+	.size	_start, .-_start
+
+	.section	.tdata,"awT",@progbits
+	.type	main_tls, @object
+	.size	main_tls, 4
+main_tls:
+	.long	5
@@ -0,0 +1,42 @@
+#!/bin/sh
+# Rebuilds the ELF fixtures used by the loader tests.
+set -e
+cd "$(dirname "$0")"
+
+as -o libamd64.o libamd64.s
+ld -shared -soname libamd64.so.1 -z max-page-size=0x1000 -z noseparate-code \
+    -o libamd64.so.1 libamd64.o
+
+as -o libtlsdesc.o libtlsdesc.s
+ld -shared -soname libtlsdesc.so.1 -z max-page-size=0x1000 -z noseparate-code \
+    -o libtlsdesc.so.1 libtlsdesc.o
+
+as -o amd64.o amd64.s
+ld -z max-page-size=0x1000 -z noseparate-code -o amd64 amd64.o libamd64.so.1 \
+    -dynamic-linker /lib64/ld-linux-x86-64.so.2
+
+as --32 -o libx86.o libx86.s
+ld -m elf_i386 -shared -soname libx86.so.1 -z max-page-size=0x1000 \
+    -z noseparate-code -o libx86.so.1 libx86.o
+
+as --32 -o x86.o x86.s
+ld -m elf_i386 -z max-page-size=0x1000 -z noseparate-code -o x86 x86.o \
+    libx86.so.1 -dynamic-linker /lib/ld-linux.so.2
+
+gcc -O1 -no-pie -fno-pie -fasynchronous-unwind-tables -s \
+    -Wl,-z,max-page-size=0x1000 -o stripped-amd64 stripped.c
+
+gcc -O0 -g -no-pie -fno-pie -fdebug-prefix-map="$(pwd)"=/fixtures \
+    -Wl,-z,max-page-size=0x1000 -o debug-amd64 debug.c
+
+rm -f *.o
+
+# There is no PPC or MIPS toolchain to hand, so these fixtures are described
+# in YAML
+yaml2obj libppc.yaml -o libppc.so.1
+yaml2obj ppc.yaml -o ppc
+yaml2obj ppc64.yaml -o ppc64
+yaml2obj mips.yaml -o mips
+
+# Cores are laid out by hand
+python3 build_core.py
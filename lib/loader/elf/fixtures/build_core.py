@@ -0,0 +1,133 @@
+#!/usr/bin/env python3
+"""Builds the ELF core fixtures used by the loader tests.
+
+Cores are awkward to capture on demand, so these are laid out by hand. Each
+core has a PT_NOTE segment holding NT_PRSTATUS notes, and two PT_LOAD
+segments: code at CODE and a stack at STACK.
+
+core-amd64 holds two threads. The first stopped with SIGSEGV at CODE with
+rax = 0x1234, rsp = STACK + 0xf00, ZF and CF set and fs_base = 0x7000_0740.
+The second stopped at CODE + 4.
+
+core-mips (32-bit, big-endian) holds one thread, stopped with SIGSEGV at CODE
+with $a0 = 0x1234, $sp = STACK + 0xf00, $lo = 7 and $hi = 9.
+"""
+import os
+import struct
+
+ET_CORE = 4
+EM_MIPS = 8
+EM_X86_64 = 62
+PT_LOAD = 1
+PT_NOTE = 4
+NT_PRSTATUS = 1
+SIGSEGV = 11
+
+CODE = 0x401000
+STACK = 0x7fff0000
+
+PF_X = 1
+PF_W = 2
+PF_R = 4
+
+
+def note(endian, name, n_type, desc):
+    name = name.encode() + b"\0"
+    out = struct.pack(endian + "III", len(name), len(desc), n_type)
+    out += name + b"\0" * (-len(name) % 4)
+    out += desc + b"\0" * (-len(desc) % 4)
+    return out
+
+
+def prstatus(endian, is_64, pid, registers):
+    word = "Q" if is_64 else "I"
+    head = struct.pack(endian + "iiiH", SIGSEGV, 0, 0, SIGSEGV)
+    head += b"\0" * 2
+    head += b"\0" * (16 if is_64 else 8)  # pr_sigpend, pr_sighold
+    head += struct.pack(endian + "iiii", pid, 1, pid, pid)
+    head += b"\0" * (64 if is_64 else 32)  # pr_utime ... pr_cstime
+    desc = head + struct.pack(endian + word * len(registers), *registers)
+    return desc + struct.pack(endian + "i", 0) + b"\0" * (4 if is_64 else 0)
+
+
+def core(endian, is_64, machine, notes, loads):
+    word = "Q" if is_64 else "I"
+    ehsize = 64 if is_64 else 52
+    phentsize = 56 if is_64 else 32
+    phnum = 1 + len(loads)
+
+    offset = ehsize + phentsize * phnum
+    notes_offset = offset
+    offset += len(notes)
+    segments = []
+    for address, flags, data in loads:
+        offset += -offset % 0x1000
+        segments.append((offset, address, flags, data))
+        offset += len(data)
+
+    ident = b"\x7fELF" + bytes([2 if is_64 else 1, 1 if endian == "<" else 2, 1])
+    ident += b"\0" * (16 - len(ident))
+    out = ident + struct.pack(
+        endian + "HHI" + word * 3 + "IHHHHHH",
+        ET_CORE, machine, 1, 0, ehsize, 0, 0, ehsize, phentsize, phnum, 0, 0, 0,
+    )
+
+    def phdr(p_type, p_flags, p_offset, p_vaddr, size, align):
+        if is_64:
+            return struct.pack(endian + "IIQQQQQQ", p_type, p_flags, p_offset,
+                               p_vaddr, 0, size, size, align)
+        return struct.pack(endian + "IIIIIIII", p_type, p_offset, p_vaddr, 0,
+                           size, size, p_flags, align)
+
+    out += phdr(PT_NOTE, 0, notes_offset, 0, len(notes), 4)
+    for offset, address, flags, data in segments:
+        out += phdr(PT_LOAD, flags, offset, address, len(data), 0x1000)
+
+    out += notes
+    for offset, _, _, data in segments:
+        out += b"\0" * (offset - len(out)) + data
+    return out
+
+
+def amd64():
+    def registers(rip, rax):
+        # r15 r14 r13 r12 rbp rbx r11 r10 r9 r8 rax rcx rdx rsi rdi orig_rax
+        # rip cs eflags rsp ss fs_base gs_base ds es fs gs
+        return [0] * 10 + [rax] + [0] * 5 + [rip, 0x33, 0x241, STACK + 0xf00,
+                                             0x2b, 0x7000_0740, 0, 0, 0, 0, 0]
+
+    notes = note("<", "CORE", NT_PRSTATUS, prstatus("<", True, 100, registers(CODE, 0x1234)))
+    notes += note("<", "CORE", NT_PRSTATUS, prstatus("<", True, 101, registers(CODE + 4, 0)))
+    # mov eax, 1; ret; nop; ret
+    code = bytes([0xb8, 1, 0, 0, 0, 0xc3, 0x90, 0xc3])
+    stack = struct.pack("<Q", 0xdeadbeef) + b"\0" * 0xff8
+    return core("<", True, EM_X86_64, notes, [
+        (CODE, PF_R | PF_X, code),
+        (STACK, PF_R | PF_W, stack),
+    ])
+
+
+def mips():
+    # six words of padding, r0 ... r31, lo, hi, epc, badvaddr, status, cause
+    registers = [0] * 6 + [0] * 32 + [7, 9, CODE, 0, 0, 0, 0]
+    registers[6 + 4] = 0x1234
+    registers[6 + 29] = STACK + 0xf00
+    notes = note(">", "CORE", NT_PRSTATUS, prstatus(">", False, 200, registers))
+    # jr $ra; nop
+    code = struct.pack(">II", 0x03e00008, 0)
+    return core(">", False, EM_MIPS, notes, [
+        (CODE, PF_R | PF_X, code),
+        (STACK, PF_R | PF_W, b"\0" * 0x1000),
+    ])
+
+
+def main():
+    os.chdir(os.path.dirname(os.path.abspath(__file__)))
+    with open("core-amd64", "wb") as f:
+        f.write(amd64())
+    with open("core-mips", "wb") as f:
+        f.write(mips())
+
+
+if __name__ == "__main__":
+    main()
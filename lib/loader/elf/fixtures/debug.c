@@ -0,0 +1,14 @@
+/* A binary with DWARF debugging information. */
+int scale(int value, int factor) {
+    static int calls;
+    int product = value * factor;
+    calls++;
+    return product + calls;
+}
+
+int main(int argc, char **argv) {
+    int result;
+    (void) argv;
+    result = scale(argc, 3);
+    return result;
+}
@@ -0,0 +1,77 @@
+	.text
+	.globl	lib_function
+	.type	lib_function, @function
+lib_function:
+	movl	$1, %eax
+	ret
+	.size	lib_function, .-lib_function
+
+	.type	ifunc_impl, @function
+ifunc_impl:
+	movl	$2, %eax
+	ret
+	.size	ifunc_impl, .-ifunc_impl
+
+	.type	ifunc_resolver, @function
+ifunc_resolver:
+	leaq	ifunc_impl(%rip), %rax
+	ret
+	.size	ifunc_resolver, .-ifunc_resolver
+
+	.hidden	ifunc_function
+	.type	ifunc_function, @gnu_indirect_function
+	.set	ifunc_function, ifunc_resolver
+
+	.globl	call_ifunc
+	.type	call_ifunc, @function
+call_ifunc:
+	call	ifunc_function@PLT
+	ret
+	.size	call_ifunc, .-call_ifunc
+
+	.globl	tls_address
+	.type	tls_address, @function
+tls_address:
+	.byte	0x66
+	leaq	lib_tls@tlsgd(%rip), %rdi
+	.word	0x6666
+	rex64
+	call	__tls_get_addr@PLT
+	ret
+	.size	tls_address, .-tls_address
+
+	.globl	__tls_get_addr
+	.type	__tls_get_addr, @function
+__tls_get_addr:
+	ret
+	.size	__tls_get_addr, .-__tls_get_addr
+
+	.data
+	.globl	lib_value
+	.type	lib_value, @object
+	.size	lib_value, 8
+lib_value:
+	.quad	0x1122334455667788
+
+	.globl	lib_pointer
+	.type	lib_pointer, @object
+	.size	lib_pointer, 8
+lib_pointer:
+	.quad	lib_value
+
+	.globl	local_pointer
+	.type	local_pointer, @object
+	.size	local_pointer, 8
+local_pointer:
+	.quad	local_data
+
+local_data:
+	.quad	0
+
+	.section	.tdata,"awT",@progbits
+	.globl	lib_tls
+	.type	lib_tls, @object
+	.size	lib_tls, 8
+	.quad	0
+lib_tls:
+	.quad	42
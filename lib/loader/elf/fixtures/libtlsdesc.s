@@ -0,0 +1,29 @@
+	.text
+	.globl	tls_address
+	.type	tls_address, @function
+tls_address:
+	leaq	lib_tls@tlsdesc(%rip), %rax
+	call	*lib_tls@tlscall(%rax)
+	ret
+	.size	tls_address, .-tls_address
+
+	.data
+	.globl	lib_value
+	.type	lib_value, @object
+	.size	lib_value, 24
+lib_value:
+	.zero	24
+
+	.globl	lib_value_size
+	.type	lib_value_size, @object
+	.size	lib_value_size, 8
+lib_value_size:
+	.quad	lib_value@SIZE
+
+	.section	.tdata,"awT",@progbits
+	.globl	lib_tls
+	.type	lib_tls, @object
+	.size	lib_tls, 8
+	.quad	0
+lib_tls:
+	.quad	42
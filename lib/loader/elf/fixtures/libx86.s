@@ -0,0 +1,20 @@
+	.text
+	.globl	lib_function
+	.type	lib_function, @function
+lib_function:
+	call	exe_function@PLT
+	ret
+	.size	lib_function, .-lib_function
+
+	.data
+	.globl	lib_value
+	.type	lib_value, @object
+	.size	lib_value, 4
+lib_value:
+	.long	0x11223344
+
+	.globl	lib_pointer
+	.type	lib_pointer, @object
+	.size	lib_pointer, 4
+lib_pointer:
+	.long	lib_value
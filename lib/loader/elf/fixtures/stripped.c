@@ -0,0 +1,15 @@
+/* A stripped binary, for function discovery without symbols. */
+static int counter;
+
+__attribute__((noinline)) static int helper(int x) {
+    return x * 3 + counter;
+}
+
+__attribute__((constructor)) static void setup(void) {
+    counter = 7;
+}
+
+int main(int argc, char **argv) {
+    (void) argv;
+    return helper(argc);
+}
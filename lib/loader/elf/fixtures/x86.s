@@ -0,0 +1,13 @@
+	.text
+	.globl	_start
+	.type	_start, @function
+_start:
+	call	lib_function
+	ret
+	.size	_start, .-_start
+
+	.globl	exe_function
+	.type	exe_function, @function
+exe_function:
+	ret
+	.size	exe_function, .-exe_function
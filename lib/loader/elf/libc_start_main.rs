@@ -0,0 +1,288 @@
+//! Recover `main`, `init` and `fini` from the arguments the entry stub passes
+//! to `__libc_start_main`.
+//!
+//! The stub at `e_entry` of a glibc or uClibc binary is a short, straight-line
+//! sequence which loads constants into the argument registers and stack slots,
+//! then calls `__libc_start_main`. We lift the stub's first block and
+//! propagate constants through it until that call.
+
+use crate::architecture::Architecture;
+use crate::executor::eval;
+use crate::il;
+use crate::memory::backing::Memory;
+use crate::memory::MemoryPermissions;
+use crate::translator::TranslationMemory;
+use std::collections::HashMap;
+
+/// The most bytes we will lift from the entry stub.
+const STUB_BYTES: usize = 256;
+
+/// A placeholder value for the stack pointer, so that stores to the stack have
+/// a known address.
+const STACK_POINTER: u64 = 0x7fff_0000;
+
+/// Where an argument to `__libc_start_main` is found at the call.
+enum Argument {
+    /// In a register.
+    Scalar(&'static str),
+    /// On the stack, at an offset from the stack pointer.
+    Stack(u64),
+    /// In the PowerPC `startup_info` structure pointed to by r8, at the given
+    /// word index.
+    StartupInfo(u64),
+}
+
+/// The locations of `main`, `init` and `fini` for each architecture.
+fn arguments(architecture: &str) -> Option<[Argument; 3]> {
+    use self::Argument::*;
+    Some(match architecture {
+        // The return address was pushed by the call.
+        "x86" => [Stack(4), Stack(16), Stack(20)],
+        "amd64" => [Scalar("rdi"), Scalar("rcx"), Scalar("r8")],
+        "mips" | "mipsel" => [Scalar("$a0"), Scalar("$a3"), Stack(16)],
+        // n32 and n64's fifth argument register, $a4, is $t0 by its o32 name.
+        "mips64" | "mips64el" | "mipsn32" | "mipsn32el" => {
+            [Scalar("$a0"), Scalar("$a3"), Scalar("$t0")]
+        }
+        "ppc" | "ppc64" | "ppc64elfv2" | "ppc64le" => {
+            [StartupInfo(1), StartupInfo(2), StartupInfo(3)]
+        }
+        "aarch64" => [Scalar("x0"), Scalar("x3"), Scalar("x4")],
+        "arm" => [Scalar("r0"), Scalar("r3"), Stack(0)],
+        "riscv32" | "riscv64" => [Scalar("a0"), Scalar("a3"), Scalar("a4")],
+        _ => return None,
+    })
+}
+
+/// Evaluate an expression over the scalars known so far.
+fn evaluate(
+    scalars: &HashMap<String, il::Constant>,
+    expression: &il::Expression,
+) -> Option<il::Constant> {
+    let mut expression = expression.clone();
+    let unknown = expression
+        .scalars()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    for scalar in unknown {
+        let value = scalars.get(scalar.name())?.clone();
+        expression = expression.replace_scalar(&scalar, &value.into()).ok()?;
+    }
+    eval(&expression).ok()
+}
+
+/// Constant propagation over the entry stub.
+struct Stub<'m> {
+    memory: &'m Memory,
+    pointer: &'m dyn Fn(u64) -> Option<u64>,
+    bits: usize,
+    scalars: HashMap<String, il::Constant>,
+    stores: HashMap<u64, il::Constant>,
+}
+
+impl<'m> Stub<'m> {
+    fn load(&self, address: u64, bits: usize) -> Option<il::Constant> {
+        if let Some(value) = self.stores.get(&address) {
+            return if value.bits() == bits {
+                Some(value.clone())
+            } else {
+                None
+            };
+        }
+        if bits == self.bits {
+            return (self.pointer)(address).map(|value| il::const_(value, bits));
+        }
+        if bits % 8 != 0 || bits == 0 {
+            return None;
+        }
+        self.memory
+            .get8(address.wrapping_add((bits / 8 - 1) as u64))?;
+        self.memory.get(address, bits)
+    }
+
+    fn execute(&mut self, operation: &il::Operation) {
+        match *operation {
+            il::Operation::Assign { ref dst, ref src } => {
+                match evaluate(&self.scalars, src) {
+                    Some(value) => self.scalars.insert(dst.name().to_string(), value),
+                    None => self.scalars.remove(dst.name()),
+                };
+            }
+            il::Operation::Store { ref index, ref src } => {
+                match evaluate(&self.scalars, index).and_then(|index| index.value_u64()) {
+                    Some(index) => match evaluate(&self.scalars, src) {
+                        Some(value) => self.stores.insert(index, value),
+                        None => self.stores.remove(&index),
+                    },
+                    // A store we can't place may have clobbered anything
+                    None => {
+                        self.stores.clear();
+                        None
+                    }
+                };
+            }
+            il::Operation::Load { ref dst, ref index } => {
+                match evaluate(&self.scalars, index)
+                    .and_then(|index| index.value_u64())
+                    .and_then(|index| self.load(index, dst.bits()))
+                {
+                    Some(value) => self.scalars.insert(dst.name().to_string(), value),
+                    None => self.scalars.remove(dst.name()),
+                };
+            }
+            il::Operation::Branch { .. } | il::Operation::Intrinsic { .. } | il::Operation::Nop => {
+            }
+        }
+    }
+
+    fn scalar(&self, name: &str) -> Option<u64> {
+        self.scalars.get(name).and_then(|value| value.value_u64())
+    }
+
+    fn argument(&self, argument: &Argument, stack_pointer: &str) -> Option<u64> {
+        let word_bytes = (self.bits / 8) as u64;
+        match *argument {
+            Argument::Scalar(name) => self.scalar(name),
+            Argument::Stack(offset) => self
+                .load(self.scalar(stack_pointer)?.wrapping_add(offset), self.bits)?
+                .value_u64(),
+            Argument::StartupInfo(index) => self
+                .load(
+                    self.scalar("r8")?.wrapping_add(index * word_bytes),
+                    self.bits,
+                )?
+                .value_u64(),
+        }
+    }
+
+    /// If `address` is an x86 `__x86.get_pc_thunk.*`, which moves its return
+    /// address into a register, get that register.
+    fn get_pc_thunk(&self, address: u64) -> Option<&'static str> {
+        const REGISTERS: [&str; 8] = ["eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi"];
+        // mov reg, [esp]; ret
+        let bytes = self.memory.get_bytes(address, 4);
+        if bytes.len() == 4
+            && bytes[0] == 0x8b
+            && bytes[1] & 0xc7 == 0x04
+            && bytes[2] == 0x24
+            && bytes[3] == 0xc3
+        {
+            Some(REGISTERS[((bytes[1] >> 3) & 7) as usize])
+        } else {
+            None
+        }
+    }
+}
+
+/// Follow a function descriptor, as used by 64-bit PowerPC ELFv1, to the code
+/// it describes.
+fn code_address(memory: &Memory, pointer: &dyn Fn(u64) -> Option<u64>, address: u64) -> u64 {
+    let executable = |address| {
+        memory
+            .permissions(address)
+            .map_or(false, |p| p.contains(MemoryPermissions::EXECUTE))
+    };
+    if executable(address) {
+        return address;
+    }
+    match pointer(address) {
+        Some(code) if executable(code) => code,
+        _ => address,
+    }
+}
+
+/// Find the addresses of `main`, `init` and `fini` given to
+/// `__libc_start_main` by the entry stub at `entry`. Arguments which are not
+/// found, or are null, are `None`.
+///
+/// `pointer` reads a pointer of `pointer_bits` bits from memory, applying any
+/// relocation which fills it in at load time.
+pub(crate) fn libc_start_main_arguments(
+    architecture: &dyn Architecture,
+    memory: &Memory,
+    pointer: &dyn Fn(u64) -> Option<u64>,
+    pointer_bits: usize,
+    entry: u64,
+) -> [Option<u64>; 3] {
+    let mut found = [None, None, None];
+
+    let arguments = match arguments(architecture.name()) {
+        Some(arguments) => arguments,
+        None => return found,
+    };
+
+    let translator = architecture.translator();
+    let bytes = memory.get_bytes(translator.block_bytes_address(entry), STUB_BYTES);
+    let block = match translator.translate_block(&bytes, entry) {
+        Ok(block) => block,
+        Err(_) => return found,
+    };
+
+    let stack_pointer = architecture.stack_pointer();
+    let mut stub = Stub {
+        memory,
+        pointer,
+        bits: pointer_bits,
+        scalars: HashMap::new(),
+        stores: HashMap::new(),
+    };
+    stub.scalars.insert(
+        stack_pointer.name().to_string(),
+        il::const_(STACK_POINTER, stack_pointer.bits()),
+    );
+
+    for (_, control_flow_graph) in block.instructions() {
+        let blocks = control_flow_graph.blocks();
+        // The stub is straight-line code. Anything else, we can't follow.
+        if blocks.len() != 1 {
+            break;
+        }
+        for instruction in blocks[0].instructions() {
+            stub.execute(instruction.operation());
+
+            let target = match *instruction.operation() {
+                il::Operation::Branch { ref target } => target,
+                _ => continue,
+            };
+
+            // Calls to get_pc_thunk come before the call we are looking for.
+            if architecture.name() == "x86" {
+                let thunk = evaluate(&stub.scalars, target)
+                    .and_then(|target| target.value_u64())
+                    .and_then(|target| stub.get_pc_thunk(target));
+                if let Some(register) = thunk {
+                    let esp = stub.scalar("esp").unwrap_or(0);
+                    let return_address = stub.load(esp, 32);
+                    match return_address {
+                        Some(value) => stub.scalars.insert(register.to_string(), value),
+                        None => stub.scalars.remove(register),
+                    };
+                    stub.scalars
+                        .insert("esp".to_string(), il::const_(esp.wrapping_add(4), 32));
+                    continue;
+                }
+            }
+
+            // The last call which is given a pointer to code for main is the
+            // call to __libc_start_main.
+            let main = stub.argument(&arguments[0], stack_pointer.name());
+            if let Some(main) = main.map(|main| code_address(memory, pointer, main)) {
+                if memory
+                    .permissions(main)
+                    .map_or(false, |p| p.contains(MemoryPermissions::EXECUTE))
+                {
+                    found[0] = Some(main);
+                    for (found, argument) in found.iter_mut().zip(arguments.iter()).skip(1) {
+                        *found = stub
+                            .argument(argument, stack_pointer.name())
+                            .filter(|address| *address != 0)
+                            .map(|address| code_address(memory, pointer, address));
+                    }
+                }
+            }
+        }
+    }
+
+    found
+}
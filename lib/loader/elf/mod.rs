@@ -1,7 +1,15 @@
 //! ELF Linker/Loader
 #[allow(clippy::module_inception)]
+mod eh_frame;
 mod elf;
+mod elf_core;
 mod elf_linker;
+mod libc_start_main;
+mod plt;
+
+#[cfg(test)]
+mod test;
 
 pub use self::elf::Elf;
+pub use self::elf_core::{CoreThread, ElfCore};
 pub use self::elf_linker::{ElfLinker, ElfLinkerBuilder};
@@ -0,0 +1,188 @@
+//! Names for the stubs through which an ELF calls imported functions.
+//!
+//! Calls to imported functions land on a stub, usually in `.plt`, which jumps
+//! through a GOT slot filled in by a dynamic relocation. The relocation names
+//! the imported symbol, so finding the GOT slot each stub jumps through gives
+//! the stub a name, such as `printf@plt`.
+
+use super::elf::section_header;
+use super::Elf;
+use crate::loader::{Loader, Symbol};
+use std::collections::HashMap;
+
+// PowerPC call stub instructions
+const PPC_LIS_R11: u32 = 0x3d60_0000;
+const PPC_LWZ_R11_R11: u32 = 0x816b_0000;
+const PPC_MTCTR_R11: u32 = 0x7d69_03a6;
+const PPC_BCTR: u32 = 0x4e80_0420;
+
+/// The names of imported symbols, by the address of the GOT slot a dynamic
+/// relocation writes their address to.
+fn got_symbol_names(elf: &Elf, goblin_elf: &goblin::elf::Elf) -> HashMap<u64, String> {
+    let mut names = HashMap::new();
+    for relocation in goblin_elf
+        .pltrelocs
+        .iter()
+        .chain(goblin_elf.dynrelas.iter())
+        .chain(goblin_elf.dynrels.iter())
+    {
+        if relocation.r_sym == 0 {
+            continue;
+        }
+        let name = goblin_elf
+            .dynsyms
+            .get(relocation.r_sym)
+            .and_then(|sym| goblin_elf.dynstrtab.get(sym.st_name))
+            .and_then(|name| name.ok());
+        if let Some(name) = name {
+            if !name.is_empty() {
+                names
+                    .entry(relocation.r_offset + elf.base_address())
+                    .or_insert_with(|| name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Get the address of the GOT slot an x86 or amd64 PLT entry jumps through.
+fn x86_got_slot(entry: &[u8], address: u64, is_64: bool, got_plt: Option<u64>) -> Option<u64> {
+    for offset in 0..entry.len().saturating_sub(5) {
+        let displacement = i32::from_le_bytes([
+            entry[offset + 2],
+            entry[offset + 3],
+            entry[offset + 4],
+            entry[offset + 5],
+        ]) as i64 as u64;
+        match (entry[offset], entry[offset + 1]) {
+            // jmp [rip + displacement]
+            (0xff, 0x25) if is_64 => {
+                return Some((address + offset as u64 + 6).wrapping_add(displacement))
+            }
+            // jmp [displacement]
+            (0xff, 0x25) => return Some(displacement & 0xffff_ffff),
+            // jmp [ebx + displacement], where ebx holds the address of
+            // .got.plt
+            (0xff, 0xa3) if !is_64 => {
+                return got_plt.map(|got_plt| got_plt.wrapping_add(displacement) & 0xffff_ffff)
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Name the entries of `.plt`, `.plt.sec` and `.plt.got`.
+fn x86_plt_symbols(elf: &Elf, goblin_elf: &goblin::elf::Elf) -> Vec<Symbol> {
+    let names = got_symbol_names(elf, goblin_elf);
+    let is_64 = elf.architecture().name() == "amd64";
+    let got_plt = goblin_elf.dynamic.as_ref().and_then(|dynamic| {
+        dynamic
+            .dyns
+            .iter()
+            .find(|dyn_| dyn_.d_tag == goblin::elf::dynamic::DT_PLTGOT)
+            .map(|dyn_| dyn_.d_val + elf.base_address())
+    });
+
+    let mut symbols = Vec::new();
+    for section_name in [".plt", ".plt.sec", ".plt.got"].iter() {
+        let section = match section_header(goblin_elf, section_name) {
+            Some(section) => section,
+            None => continue,
+        };
+        let offset = section.sh_offset as usize;
+        let bytes = match elf.bytes().get(offset..(offset + section.sh_size as usize)) {
+            Some(bytes) => bytes,
+            None => continue,
+        };
+        // i386 gives .plt an entry size of 4, which it is not
+        let entry_size = match section.sh_entsize {
+            8 | 16 => section.sh_entsize as usize,
+            _ => 16,
+        };
+
+        for (i, entry) in bytes.chunks(entry_size).enumerate() {
+            let address = section.sh_addr + elf.base_address() + (i * entry_size) as u64;
+            let name = x86_got_slot(entry, address, is_64, got_plt).and_then(|got| names.get(&got));
+            if let Some(name) = name {
+                symbols.push(Symbol::new(format!("{}@plt", name), address));
+            }
+        }
+    }
+    symbols
+}
+
+/// Name the PowerPC call stubs, `lis r11, hi; lwz r11, lo(r11); mtctr r11;
+/// bctr`, which jump through the secure PLT.
+///
+/// The `.glink` stubs of ppc64 are not handled.
+fn ppc_plt_symbols(elf: &Elf, goblin_elf: &goblin::elf::Elf) -> Vec<Symbol> {
+    let names = got_symbol_names(elf, goblin_elf);
+
+    let mut symbols = Vec::new();
+    for ph in &goblin_elf.program_headers {
+        if ph.p_type != goblin::elf::program_header::PT_LOAD
+            || ph.p_flags & goblin::elf::program_header::PF_X == 0
+        {
+            continue;
+        }
+        let offset = ph.p_offset as usize;
+        let bytes = match elf.bytes().get(offset..(offset + ph.p_filesz as usize)) {
+            Some(bytes) => bytes,
+            None => continue,
+        };
+        let words = bytes
+            .chunks_exact(4)
+            .map(|word| u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
+            .collect::<Vec<u32>>();
+
+        for (i, stub) in words.windows(4).enumerate() {
+            if stub[0] & 0xffff_0000 != PPC_LIS_R11
+                || stub[1] & 0xffff_0000 != PPC_LWZ_R11_R11
+                || stub[2] != PPC_MTCTR_R11
+                || stub[3] != PPC_BCTR
+            {
+                continue;
+            }
+            let low = (stub[1] & 0xffff) as u16 as i16 as i32 as u32;
+            let got = ((stub[0] & 0xffff) << 16).wrapping_add(low);
+            if let Some(name) = names.get(&(u64::from(got) + elf.base_address())) {
+                let address = ph.p_vaddr + elf.base_address() + (i * 4) as u64;
+                symbols.push(Symbol::new(format!("{}@plt", name), address));
+            }
+        }
+    }
+    symbols
+}
+
+/// Get a `name@plt` symbol for every stub through which the given Elf calls an
+/// imported function.
+pub(crate) fn plt_symbols(elf: &Elf) -> Vec<Symbol> {
+    let goblin_elf = elf.elf();
+
+    // Undefined functions with a value are called through the stub at that
+    // value. This is how MIPS finds its lazy binding stubs, and how
+    // executables give imported functions a canonical address.
+    let mut symbols = goblin_elf
+        .dynsyms
+        .iter()
+        .filter(|sym| sym.is_function() && sym.st_shndx == 0 && sym.st_value != 0)
+        .filter_map(|sym| {
+            let name = goblin_elf.dynstrtab.get(sym.st_name)?.ok()?;
+            Some(Symbol::new(
+                format!("{}@plt", name),
+                sym.st_value + elf.base_address(),
+            ))
+        })
+        .collect::<Vec<Symbol>>();
+
+    symbols.append(&mut match elf.architecture().name() {
+        "x86" | "amd64" => x86_plt_symbols(elf, &goblin_elf),
+        "ppc" => ppc_plt_symbols(elf, &goblin_elf),
+        _ => Vec::new(),
+    });
+
+    symbols.sort();
+    symbols.dedup();
+    symbols
+}
@@ -0,0 +1,484 @@
+#[cfg(feature = "dwarf")]
+use crate::il;
+use crate::loader::{Elf, ElfCore, ElfLinker, ElfLinkerBuilder, FunctionEntrySource, Loader};
+use crate::memory::backing::Memory;
+use std::path::PathBuf;
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib/loader/elf/fixtures")
+}
+
+fn link(filename: &str) -> ElfLinker {
+    ElfLinkerBuilder::new(fixtures().join(filename))
+        .ld_paths(Some(vec![fixtures()]))
+        .link()
+        .unwrap()
+}
+
+fn symbol_address(linker: &ElfLinker, elf: &str, name: &str) -> u64 {
+    linker.loaded()[elf]
+        .exported_symbols()
+        .into_iter()
+        .find(|symbol| symbol.name() == name)
+        .unwrap()
+        .address()
+}
+
+fn memory(linker: &ElfLinker) -> Memory {
+    linker.memory().unwrap()
+}
+
+#[test]
+fn amd64_loads_dependencies() {
+    let linker = link("amd64");
+
+    assert!(linker.loaded().contains_key("amd64"));
+    assert_eq!(linker.loaded()["libamd64.so.1"].base_address(), 0x4200_0000);
+}
+
+#[test]
+fn amd64_executable_relocations() {
+    let linker = link("amd64");
+    let memory = memory(&linker);
+
+    // R_X86_64_JUMP_SLOT lib_function
+    assert_eq!(
+        memory.get64(0x402000).unwrap(),
+        symbol_address(&linker, "libamd64.so.1", "lib_function")
+    );
+    // R_X86_64_COPY lib_value
+    assert_eq!(memory.get64(0x402008).unwrap(), 0x1122_3344_5566_7788);
+    // R_X86_64_GLOB_DAT lib_pointer
+    assert_eq!(memory.get64(0x401fd8).unwrap(), 0x4200_2018);
+    // R_X86_64_TPOFF64 lib_tls, 8 bytes into the library's TLS block, which
+    // sits below the executable's 4 byte block
+    assert_eq!(memory.get64(0x401fe0).unwrap(), 8u64.wrapping_sub(20));
+}
+
+#[test]
+fn amd64_library_relocations() {
+    let linker = link("amd64");
+    let memory = memory(&linker);
+
+    // R_X86_64_64 lib_value, which resolves to the executable's copy
+    assert_eq!(memory.get64(0x4200_2018).unwrap(), 0x402008);
+    // R_X86_64_RELATIVE
+    assert_eq!(memory.get64(0x4200_2020).unwrap(), 0x4200_2028);
+    // R_X86_64_IRELATIVE, which points at the resolver
+    assert_eq!(memory.get64(0x4200_2008).unwrap(), 0x4200_03ec);
+    // R_X86_64_DTPMOD64 and R_X86_64_DTPOFF64 lib_tls
+    assert_eq!(memory.get64(0x4200_1fd8).unwrap(), 2);
+    assert_eq!(memory.get64(0x4200_1fe0).unwrap(), 8);
+}
+
+#[test]
+fn amd64_without_relocations() {
+    let linker = ElfLinkerBuilder::new(fixtures().join("amd64"))
+        .ld_paths(Some(vec![fixtures()]))
+        .do_relocations(false)
+        .link()
+        .unwrap();
+
+    assert_eq!(memory(&linker).get64(0x402008).unwrap(), 0);
+}
+
+#[test]
+fn amd64_tls_descriptor_relocations() {
+    let linker = link("libtlsdesc.so.1");
+    let memory = memory(&linker);
+
+    // R_X86_64_TLSDESC lib_tls, whose argument is its offset from the thread
+    // pointer, 8 bytes into the 16 byte TLS block
+    assert_eq!(memory.get64(0x2008).unwrap(), 8u64.wrapping_sub(16));
+    // R_X86_64_SIZE64 lib_value
+    assert_eq!(memory.get64(0x2028).unwrap(), 24);
+}
+
+#[test]
+fn x86_relocations() {
+    let linker = link("x86");
+    let memory = memory(&linker);
+
+    // Symbols of the library are rebased once, to where it was loaded
+    assert_eq!(linker.loaded()["libx86.so.1"].base_address(), 0x4200_0000);
+    assert_eq!(
+        symbol_address(&linker, "libx86.so.1", "lib_function"),
+        0x4200_01d0
+    );
+
+    // R_386_JUMP_SLOT lib_function
+    assert_eq!(memory.get32(0x0804_a000).unwrap(), 0x4200_01d0);
+    // R_386_32 lib_value
+    assert_eq!(memory.get32(0x4200_2008).unwrap(), 0x4200_2004);
+    // R_386_JUMP_SLOT exe_function, which the executable defines
+    assert_eq!(memory.get32(0x4200_2000).unwrap(), 0x0804_81c6);
+}
+
+#[test]
+fn ppc_executable_relocations() {
+    let linker = link("ppc");
+    let memory = memory(&linker);
+
+    assert_eq!(linker.loaded()["libppc.so.1"].base_address(), 0x4200_0000);
+
+    // R_PPC_JMP_SLOT lib_function, in the secure PLT
+    assert_eq!(memory.get32(0x1000_2200).unwrap(), 0x4200_1000);
+    // R_PPC_GLOB_DAT lib_pointer
+    assert_eq!(memory.get32(0x1000_210c).unwrap(), 0x4200_2104);
+    // R_PPC_ADDR32 lib_value + 4
+    assert_eq!(memory.get32(0x1000_2304).unwrap(), 0x4200_2104);
+    // R_PPC_COPY lib_copy
+    assert_eq!(memory.get32(0x1000_2300).unwrap(), 0x5566_7788);
+}
+
+#[test]
+fn ppc_library_relocations() {
+    let linker = link("ppc");
+    let memory = memory(&linker);
+
+    // R_PPC_ADDR32 lib_value
+    assert_eq!(memory.get32(0x4200_2104).unwrap(), 0x4200_2100);
+    // R_PPC_RELATIVE
+    assert_eq!(memory.get32(0x4200_2108).unwrap(), 0x4200_2100);
+    // R_PPC_DTPMOD32, R_PPC_DTPREL32 and R_PPC_TPREL32 lib_tls, 4 bytes into
+    // the library's 8 byte TLS block
+    assert_eq!(memory.get32(0x4200_2110).unwrap(), 1);
+    assert_eq!(
+        memory.get32(0x4200_2114).unwrap(),
+        4u32.wrapping_sub(0x8000)
+    );
+    assert_eq!(memory.get32(0x4200_2118).unwrap(), 4u32.wrapping_sub(8));
+    // R_PPC_ADDR16_HA and R_PPC_ADDR16_LO lib_value + 0x8000
+    assert_eq!(memory.get32(0x4200_211c).unwrap(), 0x4201_a100);
+    // R_PPC_ADDR24 is unhandled, and skipped
+    assert_eq!(memory.get32(0x4200_2120).unwrap(), 0);
+}
+
+fn core(filename: &str) -> ElfCore {
+    ElfCore::from_file(fixtures().join(filename)).unwrap()
+}
+
+#[test]
+fn amd64_core_threads() {
+    let core = core("core-amd64");
+
+    assert_eq!(core.architecture().name(), "amd64");
+    assert_eq!(core.threads().len(), 2);
+    assert_eq!(core.program_entry(), 0x401000);
+
+    let thread = &core.threads()[0];
+    assert_eq!(thread.pid(), 100);
+    assert_eq!(thread.signal(), 11);
+    assert_eq!(thread.register("rax").unwrap().value_u64(), Some(0x1234));
+    assert_eq!(
+        thread.register("rsp").unwrap().value_u64(),
+        Some(0x7fff_0f00)
+    );
+    assert_eq!(
+        thread.register("fs_base").unwrap().value_u64(),
+        Some(0x7000_0740)
+    );
+    assert_eq!(thread.register("ZF").unwrap().value_u64(), Some(1));
+    assert_eq!(thread.register("SF").unwrap().value_u64(), Some(0));
+
+    assert_eq!(core.threads()[1].program_counter(), 0x401004);
+    assert_eq!(core.function_entries().unwrap().len(), 2);
+}
+
+#[test]
+fn amd64_core_state() {
+    let core = core("core-amd64");
+    let state = core.state(0).unwrap();
+
+    assert_eq!(state.get_scalar("rax").unwrap().value_u64(), Some(0x1234));
+    assert_eq!(
+        state
+            .memory()
+            .load(0x7fff_0000, 64)
+            .unwrap()
+            .unwrap()
+            .value_u64(),
+        Some(0xdeadbeef)
+    );
+    assert!(core.state(2).is_err());
+}
+
+#[test]
+fn mips_core_threads() {
+    let core = core("core-mips");
+
+    assert_eq!(core.architecture().name(), "mips");
+    assert_eq!(core.program_entry(), 0x401000);
+
+    let thread = &core.threads()[0];
+    assert_eq!(thread.pid(), 200);
+    assert_eq!(thread.register("$a0").unwrap().value_u64(), Some(0x1234));
+    assert_eq!(
+        thread.register("$sp").unwrap().value_u64(),
+        Some(0x7fff_0f00)
+    );
+    assert_eq!(thread.register("$lo").unwrap().value_u64(), Some(7));
+    assert_eq!(thread.register("$hi").unwrap().value_u64(), Some(9));
+    assert!(thread.register("$zero").is_none());
+}
+
+#[test]
+fn elf_is_not_a_core() {
+    assert!(ElfCore::from_file(fixtures().join("amd64")).is_err());
+}
+
+#[test]
+fn stripped_amd64_function_entries() {
+    let elf = Elf::from_file(fixtures().join("stripped-amd64")).unwrap();
+    let function_entries = elf
+        .function_entries()
+        .unwrap()
+        .into_iter()
+        .map(|function_entry| {
+            (
+                function_entry.address(),
+                function_entry.name().map(|name| name.to_string()),
+                function_entry.source(),
+            )
+        })
+        .collect::<Vec<(u64, Option<String>, FunctionEntrySource)>>();
+
+    assert_eq!(
+        function_entries,
+        vec![
+            (0x401020, None, FunctionEntrySource::ProgramEntry),
+            (
+                0x40111b,
+                Some("main".to_string()),
+                FunctionEntrySource::LibcStartMain
+            ),
+            // DT_INIT and DT_FINI
+            (0x401000, None, FunctionEntrySource::Constructor),
+            (0x401124, None, FunctionEntrySource::Constructor),
+            // frame_dummy and setup in .init_array
+            (0x401100, None, FunctionEntrySource::Constructor),
+            (0x401110, None, FunctionEntrySource::Constructor),
+            // __do_global_dtors_aux in .fini_array
+            (0x4010d0, None, FunctionEntrySource::Constructor),
+            // _dl_relocate_static_pie and helper
+            (0x401050, None, FunctionEntrySource::UnwindInfo),
+            (0x401106, None, FunctionEntrySource::UnwindInfo),
+        ]
+    );
+}
+
+#[test]
+fn amd64_plt_symbols() {
+    let elf = Elf::from_file(fixtures().join("amd64")).unwrap();
+
+    let plt_symbols = elf.plt_symbols();
+    assert_eq!(plt_symbols.len(), 1);
+    assert_eq!(plt_symbols[0].name(), "lib_function@plt");
+    assert_eq!(plt_symbols[0].address(), 0x400360);
+
+    assert!(elf
+        .symbols()
+        .iter()
+        .any(|symbol| symbol.name() == "lib_function@plt" && symbol.address() == 0x400360));
+
+    let function_entry = elf
+        .function_entries()
+        .unwrap()
+        .into_iter()
+        .find(|function_entry| function_entry.address() == 0x400360)
+        .unwrap();
+    assert_eq!(function_entry.name(), Some("lib_function@plt"));
+    assert_eq!(function_entry.source(), FunctionEntrySource::Plt);
+}
+
+#[test]
+fn ppc_plt_symbols() {
+    let elf = Elf::from_file(fixtures().join("ppc")).unwrap();
+
+    let plt_symbols = elf.plt_symbols();
+    assert_eq!(plt_symbols.len(), 1);
+    assert_eq!(plt_symbols[0].name(), "lib_function@plt");
+    assert_eq!(plt_symbols[0].address(), 0x10001010);
+
+    let elf = Elf::from_file_with_base_address(fixtures().join("ppc"), 0x1000).unwrap();
+
+    let plt_symbols = elf.plt_symbols();
+    assert_eq!(plt_symbols.len(), 1);
+    assert_eq!(plt_symbols[0].name(), "lib_function@plt");
+    assert_eq!(plt_symbols[0].address(), 0x10002010);
+}
+
+#[test]
+fn x86_plt_symbols() {
+    // jmp [0x804a000]
+    let elf = Elf::from_file(fixtures().join("x86")).unwrap();
+
+    let plt_symbols = elf.plt_symbols();
+    assert_eq!(plt_symbols.len(), 1);
+    assert_eq!(plt_symbols[0].name(), "lib_function@plt");
+    assert_eq!(plt_symbols[0].address(), 0x080481b0);
+
+    // jmp [ebx + 0xc], where ebx holds the address of .got.plt
+    let elf =
+        Elf::from_file_with_base_address(fixtures().join("libx86.so.1"), 0x4000_0000).unwrap();
+
+    let plt_symbols = elf.plt_symbols();
+    assert_eq!(plt_symbols.len(), 1);
+    assert_eq!(plt_symbols[0].name(), "exe_function@plt");
+    assert_eq!(plt_symbols[0].address(), 0x4000_01c0);
+}
+
+#[test]
+fn mips_plt_symbols() {
+    // The undefined lib_function has the address of its lazy binding stub
+    let elf = Elf::from_file(fixtures().join("mips")).unwrap();
+
+    let plt_symbols = elf.plt_symbols();
+    assert_eq!(plt_symbols.len(), 1);
+    assert_eq!(plt_symbols[0].name(), "lib_function@plt");
+    assert_eq!(plt_symbols[0].address(), 0x400210);
+}
+
+#[test]
+fn mips_n32_abi() {
+    // n32 binaries are 32-bit Elfs with EF_MIPS_ABI2 set, of 64-bit code
+    let mut bytes = std::fs::read(fixtures().join("mips")).unwrap();
+    bytes[0x27] |= 0x20;
+    let elf = Elf::new(bytes, 0).unwrap();
+
+    assert_eq!(elf.architecture().name(), "mipsn32");
+    assert_eq!(elf.architecture().word_size(), 64);
+    assert_eq!(elf.plt_symbols()[0].address(), 0x400210);
+}
+
+#[test]
+fn ppc64_function_descriptors() {
+    let elf = Elf::from_file(fixtures().join("ppc64")).unwrap();
+    assert_eq!(elf.architecture().name(), "ppc64");
+
+    // The entry point and function symbols are descriptors in .opd, which
+    // give the address of the code
+    assert_eq!(elf.program_entry(), 0x1000_0200);
+    let function_entries = elf
+        .function_entries()
+        .unwrap()
+        .into_iter()
+        .map(|function_entry| {
+            (
+                function_entry.address(),
+                function_entry.name().map(|name| name.to_string()),
+            )
+        })
+        .collect::<Vec<(u64, Option<String>)>>();
+    assert_eq!(
+        function_entries,
+        vec![
+            (0x1000_0200, Some("_start".to_string())),
+            (0x1000_0208, Some("f".to_string()))
+        ]
+    );
+}
+
+#[test]
+fn ppc64_abi() {
+    // e_flags gives the ABI of big-endian binaries
+    let mut bytes = std::fs::read(fixtures().join("ppc64")).unwrap();
+    bytes[0x33] = 2;
+    let elf = Elf::new(bytes, 0).unwrap();
+    assert_eq!(elf.architecture().name(), "ppc64elfv2");
+}
+
+#[test]
+fn amd64_without_debug_info() {
+    let elf = Elf::from_file(fixtures().join("amd64")).unwrap();
+    assert!(elf.debug_info().unwrap().is_none());
+}
+
+#[cfg(feature = "dwarf")]
+#[test]
+fn amd64_debug_info() {
+    let elf = Elf::from_file(fixtures().join("debug-amd64")).unwrap();
+    let debug_info = elf.debug_info().unwrap().unwrap();
+
+    assert_eq!(
+        debug_info.source_location(0x401110),
+        Some(&il::SourceLocation::new("/fixtures/debug.c", 4, 9))
+    );
+    assert_eq!(
+        debug_info
+            .source_location(0x401113)
+            .map(|source_location| source_location.line()),
+        Some(4)
+    );
+    // The end of the line table
+    assert_eq!(debug_info.source_location(0x40115c), None);
+
+    let function_entries = debug_info
+        .function_entries()
+        .into_iter()
+        .map(|function_entry| {
+            (
+                function_entry.address(),
+                function_entry.name().map(|name| name.to_string()),
+                function_entry.source(),
+            )
+        })
+        .collect::<Vec<(u64, Option<String>, FunctionEntrySource)>>();
+    assert_eq!(
+        function_entries,
+        vec![
+            (
+                0x401106,
+                Some("scale".to_string()),
+                FunctionEntrySource::DebugInfo
+            ),
+            (
+                0x401136,
+                Some("main".to_string()),
+                FunctionEntrySource::DebugInfo
+            ),
+        ]
+    );
+    assert_eq!(debug_info.functions()[0].high_pc(), 0x401136);
+
+    let function = elf.function(0x401106).unwrap();
+    assert_eq!(
+        function.frame_base(),
+        Some(&il::FrameBase::CallFrameAddress)
+    );
+    assert_eq!(
+        function.variables(),
+        &[
+            il::Variable::new(
+                "value",
+                il::VariableKind::Parameter,
+                Some(il::VariableLocation::FrameBase(-36))
+            ),
+            il::Variable::new(
+                "factor",
+                il::VariableKind::Parameter,
+                Some(il::VariableLocation::FrameBase(-40))
+            ),
+            il::Variable::new(
+                "calls",
+                il::VariableKind::Local,
+                Some(il::VariableLocation::Address(0x404014))
+            ),
+            il::Variable::new(
+                "product",
+                il::VariableKind::Local,
+                Some(il::VariableLocation::FrameBase(-20))
+            ),
+        ]
+    );
+
+    let lines = function
+        .blocks()
+        .into_iter()
+        .flat_map(|block| block.instructions())
+        .filter(|instruction| instruction.address() == Some(0x401110))
+        .map(|instruction| instruction.source_location().unwrap().line())
+        .collect::<Vec<u64>>();
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| *line == 4));
+}
@@ -0,0 +1,158 @@
+//! Intel HEX Loader
+
+use crate::loader::hex::{decode, read_file, Image};
+use crate::loader::*;
+use crate::memory::backing::Memory;
+use std::path::Path;
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+/// Loader for firmware in Intel HEX format.
+#[derive(Debug)]
+pub struct IntelHex {
+    memory: Memory,
+    architecture: Box<dyn Architecture>,
+    entry: Option<u64>,
+}
+
+impl IntelHex {
+    /// Parse the given Intel HEX text, for the given architecture.
+    pub fn new(text: &str, architecture: Box<dyn Architecture>) -> Result<IntelHex> {
+        let mut image = Image::default();
+        let mut base = 0;
+        let mut segmented = false;
+        let mut entry = None;
+        let mut end_of_file = false;
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if end_of_file {
+                bail!("Line {}: record after end of file record", line_number);
+            }
+            if !line.starts_with(':') {
+                bail!("Line {}: record does not begin with ':'", line_number);
+            }
+
+            let record = decode(&line[1..], line_number)?;
+            if record.len() < 5 || record.len() != record[0] as usize + 5 {
+                bail!("Line {}: record length is incorrect", line_number);
+            }
+
+            let checksum = record.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+            if checksum != 0 {
+                let expected = record[..record.len() - 1]
+                    .iter()
+                    .fold(0u8, |sum, byte| sum.wrapping_add(*byte))
+                    .wrapping_neg();
+                bail!(
+                    "Line {}: checksum mismatch, expected 0x{:02x} but found 0x{:02x}",
+                    line_number,
+                    expected,
+                    record[record.len() - 1]
+                );
+            }
+
+            let offset = u16::from_be_bytes([record[1], record[2]]) as u64;
+            let data = &record[4..record.len() - 1];
+
+            let value = || -> Result<u64> {
+                Ok(match data.len() {
+                    2 => u16::from_be_bytes([data[0], data[1]]) as u64,
+                    4 => u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as u64,
+                    _ => bail!("Line {}: record length is incorrect", line_number),
+                })
+            };
+
+            match record[3] {
+                DATA => {
+                    // Offsets from an extended segment address wrap around
+                    // within the 64K segment
+                    let split = if segmented {
+                        data.len().min((0x1_0000 - offset) as usize)
+                    } else {
+                        data.len()
+                    };
+                    image.write(base + offset, &data[..split], line_number)?;
+                    image.write(base, &data[split..], line_number)?;
+                }
+                END_OF_FILE => end_of_file = true,
+                EXTENDED_SEGMENT_ADDRESS => {
+                    base = value()? << 4;
+                    segmented = true;
+                }
+                START_SEGMENT_ADDRESS => {
+                    let value = value()?;
+                    entry = Some(((value >> 16) << 4) + (value & 0xffff));
+                }
+                EXTENDED_LINEAR_ADDRESS => {
+                    base = value()? << 16;
+                    segmented = false;
+                }
+                START_LINEAR_ADDRESS => entry = Some(value()?),
+                record_type => bail!(
+                    "Line {}: unknown record type 0x{:02x}",
+                    line_number,
+                    record_type
+                ),
+            }
+        }
+
+        if !end_of_file {
+            bail!("Missing end of file record");
+        }
+
+        let memory = image.memory(architecture.endian());
+        let entry = entry.or_else(|| image.lowest_address());
+
+        Ok(IntelHex {
+            memory,
+            architecture,
+            entry,
+        })
+    }
+
+    /// Load an Intel HEX file, for the given architecture.
+    pub fn from_file(filename: &Path, architecture: Box<dyn Architecture>) -> Result<IntelHex> {
+        IntelHex::new(&read_file(filename)?, architecture)
+    }
+}
+
+impl Loader for IntelHex {
+    fn memory(&self) -> Result<Memory> {
+        Ok(self.memory.clone())
+    }
+
+    fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
+        Ok(self
+            .entry
+            .map(|entry| vec![FunctionEntry::new(entry, None)])
+            .unwrap_or_default())
+    }
+
+    /// The start address record, or the lowest address in the file if there
+    /// is none.
+    fn program_entry(&self) -> u64 {
+        self.entry.unwrap_or(0)
+    }
+
+    fn architecture(&self) -> &dyn Architecture {
+        self.architecture.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,137 @@
+//! Loaders for firmware in hex formats, Intel HEX and Motorola S-record.
+//!
+//! These formats carry no architecture, so the user supplies one. Data
+//! records are gathered into contiguous runs, and each run is mapped into
+//! memory as its own section, with all permissions.
+
+use crate::architecture::Endian;
+use crate::error::*;
+use crate::memory::backing::Memory;
+use crate::memory::MemoryPermissions;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+mod ihex;
+mod srec;
+
+#[cfg(test)]
+mod test;
+
+pub use self::ihex::IntelHex;
+pub use self::srec::SRecord;
+
+/// The bytes written by the data records of a hex file, as contiguous runs
+/// keyed by their start address.
+#[derive(Debug, Default)]
+struct Image {
+    runs: BTreeMap<u64, Vec<u8>>,
+}
+
+impl Image {
+    /// Write the data of a record at the given address, merging it with the
+    /// runs it overlaps or touches.
+    fn write(&mut self, address: u64, data: &[u8], line_number: usize) -> Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let end = address
+            .checked_add(data.len() as u64)
+            .ok_or_else(|| format!("Line {}: record overflows the address space", line_number))?;
+
+        // Runs do not overlap, so runs which end later also start later
+        let neighbours = self
+            .runs
+            .range(..=end)
+            .rev()
+            .take_while(|(start, bytes)| *start + bytes.len() as u64 >= address)
+            .map(|(start, _)| *start)
+            .collect::<Vec<u64>>();
+
+        // Bytes written by both must agree
+        for start in &neighbours {
+            let bytes = &self.runs[start];
+            let start = *start;
+            for overlap in address.max(start)..end.min(start + bytes.len() as u64) {
+                let previous = bytes[(overlap - start) as usize];
+                if previous != data[(overlap - address) as usize] {
+                    bail!(
+                        "Line {}: 0x{:x} was already written with 0x{:02x}",
+                        line_number,
+                        overlap,
+                        previous
+                    );
+                }
+            }
+        }
+
+        // Extend the run this record starts in, if any, in place. Records
+        // usually follow on from the last, so this keeps loading linear.
+        let mut neighbours = neighbours.into_iter().rev().peekable();
+        let (run_start, mut run) = match neighbours.peek() {
+            Some(start) if *start <= address => {
+                let start = *start;
+                neighbours.next();
+                (start, self.runs.remove(&start).unwrap())
+            }
+            _ => (address, Vec::new()),
+        };
+        splice(&mut run, (address - run_start) as usize, data);
+        for start in neighbours {
+            let bytes = self.runs.remove(&start).unwrap();
+            splice(&mut run, (start - run_start) as usize, &bytes);
+        }
+        self.runs.insert(run_start, run);
+
+        Ok(())
+    }
+
+    /// The lowest address written, if any.
+    fn lowest_address(&self) -> Option<u64> {
+        self.runs.keys().next().cloned()
+    }
+
+    /// Map each contiguous run of bytes into memory.
+    fn memory(&self, endian: Endian) -> Memory {
+        let mut memory = Memory::new(endian);
+        for (address, bytes) in &self.runs {
+            memory.set_memory(*address, bytes.clone(), MemoryPermissions::ALL);
+        }
+        memory
+    }
+}
+
+/// Write `bytes` into `run` at `offset`, which is at most the length of the
+/// run, extending the run as needed.
+fn splice(run: &mut Vec<u8>, offset: usize, bytes: &[u8]) {
+    let overlap = bytes.len().min(run.len() - offset);
+    run[offset..offset + overlap].copy_from_slice(&bytes[..overlap]);
+    run.extend_from_slice(&bytes[overlap..]);
+}
+
+/// Decode a string of hex digit pairs.
+fn decode(hex: &str, line_number: usize) -> Result<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .filter(|pair| pair.len() == 2 && pair.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or_else(|| format!("Line {}: invalid hex digits", line_number).into())
+        })
+        .collect()
+}
+
+fn read_file(filename: &Path) -> Result<String> {
+    let mut file = match File::open(filename) {
+        Ok(file) => file,
+        Err(e) => {
+            return Err(format!("Error opening {}: {}", filename.to_str().unwrap(), e).into())
+        }
+    };
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    Ok(String::from_utf8(buf)?)
+}
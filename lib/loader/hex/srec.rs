@@ -0,0 +1,140 @@
+//! Motorola S-record Loader
+
+use crate::loader::hex::{decode, read_file, Image};
+use crate::loader::*;
+use crate::memory::backing::Memory;
+use std::path::Path;
+
+/// Loader for firmware in Motorola S-record format.
+#[derive(Debug)]
+pub struct SRecord {
+    memory: Memory,
+    architecture: Box<dyn Architecture>,
+    entry: Option<u64>,
+    header: Vec<u8>,
+}
+
+impl SRecord {
+    /// Parse the given S-record text, for the given architecture.
+    pub fn new(text: &str, architecture: Box<dyn Architecture>) -> Result<SRecord> {
+        let mut image = Image::default();
+        let mut entry = None;
+        let mut header = Vec::new();
+        let mut data_records = 0;
+
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.len() < 2 || !line.is_ascii() || !line.starts_with('S') {
+                bail!("Line {}: record does not begin with 'S'", line_number);
+            }
+
+            let record_type = &line[1..2];
+            let record = decode(&line[2..], line_number)?;
+            if record.is_empty() || record.len() != record[0] as usize + 1 {
+                bail!("Line {}: record length is incorrect", line_number);
+            }
+
+            let expected = !record[..record.len() - 1]
+                .iter()
+                .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+            let checksum = record[record.len() - 1];
+            if checksum != expected {
+                bail!(
+                    "Line {}: checksum mismatch, expected 0x{:02x} but found 0x{:02x}",
+                    line_number,
+                    expected,
+                    checksum
+                );
+            }
+
+            let address_size = match record_type {
+                "0" | "1" | "5" | "9" => 2,
+                "2" | "6" | "8" => 3,
+                "3" | "7" => 4,
+                _ => bail!("Line {}: unknown record type S{}", line_number, record_type),
+            };
+            if record.len() < address_size + 2 {
+                bail!("Line {}: record length is incorrect", line_number);
+            }
+
+            let address = record[1..=address_size]
+                .iter()
+                .fold(0u64, |address, byte| (address << 8) | *byte as u64);
+            let data = &record[address_size + 1..record.len() - 1];
+
+            match record_type {
+                "0" => header = data.to_vec(),
+                "1" | "2" | "3" => {
+                    image.write(address, data, line_number)?;
+                    data_records += 1;
+                }
+                "5" | "6" => {
+                    if address != data_records {
+                        bail!(
+                            "Line {}: record count is {} but there are {} data records",
+                            line_number,
+                            address,
+                            data_records
+                        );
+                    }
+                }
+                _ => entry = Some(address),
+            }
+        }
+
+        let memory = image.memory(architecture.endian());
+        let entry = entry.or_else(|| image.lowest_address());
+
+        Ok(SRecord {
+            memory,
+            architecture,
+            entry,
+            header,
+        })
+    }
+
+    /// Load an S-record file, for the given architecture.
+    pub fn from_file(filename: &Path, architecture: Box<dyn Architecture>) -> Result<SRecord> {
+        SRecord::new(&read_file(filename)?, architecture)
+    }
+
+    /// Get the data of the S0 header record.
+    pub fn header(&self) -> &[u8] {
+        &self.header
+    }
+}
+
+impl Loader for SRecord {
+    fn memory(&self) -> Result<Memory> {
+        Ok(self.memory.clone())
+    }
+
+    fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
+        Ok(self
+            .entry
+            .map(|entry| vec![FunctionEntry::new(entry, None)])
+            .unwrap_or_default())
+    }
+
+    /// The address in the termination record, or the lowest address in the
+    /// file if there is none.
+    fn program_entry(&self) -> u64 {
+        self.entry.unwrap_or(0)
+    }
+
+    fn architecture(&self) -> &dyn Architecture {
+        self.architecture.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        Vec::new()
+    }
+}
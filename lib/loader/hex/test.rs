@@ -0,0 +1,151 @@
+use crate::architecture::{Arm, X86};
+use crate::loader::{FunctionEntry, IntelHex, Loader, SRecord};
+
+const INTEL_HEX: &str = "\
+:020000040800F2
+:10000000000102030405060708090A0B0C0D0E0F78
+:020010001011CD
+:04010000DEADBEEFC3
+:0400000508000100EE
+:00000001FF
+";
+
+const SRECORD: &str = "\
+S009000066616C636F6E83
+S30D200000000001020304050607B6
+S307200000080809BF
+S205000100C336
+S5030003F9
+S70520000004D6
+";
+
+#[test]
+fn intel_hex() {
+    let ihex = IntelHex::new(INTEL_HEX, Box::new(Arm::new())).unwrap();
+    let memory = ihex.memory().unwrap();
+
+    assert_eq!(
+        memory
+            .sections()
+            .iter()
+            .map(|(address, section)| (*address, section.data().len()))
+            .collect::<Vec<(u64, usize)>>(),
+        vec![(0x0800_0000, 0x12), (0x0800_0100, 4)]
+    );
+    assert_eq!(memory.get8(0x0800_0011), Some(0x11));
+    assert_eq!(memory.get8(0x0800_0012), None);
+    assert_eq!(memory.get32(0x0800_0100), Some(0xefbe_adde));
+
+    assert_eq!(ihex.program_entry(), 0x0800_0100);
+    assert_eq!(
+        ihex.function_entries().unwrap(),
+        vec![FunctionEntry::new(0x0800_0100, None)]
+    );
+}
+
+#[test]
+fn intel_hex_segment_address() {
+    let text = ":020000021000EC\n:01002000AA35\n:0400000310000020C9\n:00000001FF\n";
+    let ihex = IntelHex::new(text, Box::new(X86::new())).unwrap();
+
+    assert_eq!(ihex.memory().unwrap().get8(0x10020), Some(0xaa));
+    assert_eq!(ihex.program_entry(), 0x10020);
+}
+
+#[test]
+fn intel_hex_segment_wraps() {
+    // The last two bytes wrap around to the start of the segment
+    let text = ":020000021000EC\n:04FFFE0001020304F5\n:00000001FF\n";
+    let ihex = IntelHex::new(text, Box::new(X86::new())).unwrap();
+    let memory = ihex.memory().unwrap();
+
+    assert_eq!(
+        memory
+            .sections()
+            .iter()
+            .map(|(address, section)| (*address, section.data().len()))
+            .collect::<Vec<(u64, usize)>>(),
+        vec![(0x10000, 2), (0x1fffe, 2)]
+    );
+    assert_eq!(memory.get8(0x1ffff), Some(0x02));
+    assert_eq!(memory.get8(0x10000), Some(0x03));
+    assert_eq!(memory.get8(0x20000), None);
+}
+
+#[test]
+fn intel_hex_merged_records() {
+    // Records out of order, where the third overlaps the second and touches
+    // the first
+    let text = "\
+:020010001011CD
+:0400000000010203F6
+:0E00020002030405060708090A0B0C0D0E0F79
+:0100200020BF
+:00000001FF
+";
+    let ihex = IntelHex::new(text, Box::new(Arm::new())).unwrap();
+    let memory = ihex.memory().unwrap();
+
+    assert_eq!(
+        memory
+            .sections()
+            .iter()
+            .map(|(address, section)| (*address, section.data().len()))
+            .collect::<Vec<(u64, usize)>>(),
+        vec![(0, 0x12), (0x20, 1)]
+    );
+    assert_eq!(memory.get8(0x3), Some(0x03));
+    assert_eq!(memory.get8(0x10), Some(0x10));
+    assert_eq!(ihex.program_entry(), 0);
+}
+
+#[test]
+fn intel_hex_errors() {
+    let bad_checksum = INTEL_HEX.replace(":020010001011CD", ":020010001011CE");
+    let error = IntelHex::new(&bad_checksum, Box::new(Arm::new())).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Line 3: checksum mismatch, expected 0xcd but found 0xce"
+    );
+
+    let missing_eof = INTEL_HEX.replace(":00000001FF\n", "");
+    assert!(IntelHex::new(&missing_eof, Box::new(Arm::new())).is_err());
+
+    let bad_length = INTEL_HEX.replace(":020010001011CD", ":030010001011CC");
+    assert!(IntelHex::new(&bad_length, Box::new(Arm::new())).is_err());
+}
+
+#[test]
+fn srecord() {
+    let srec = SRecord::new(SRECORD, Box::new(Arm::new())).unwrap();
+    let memory = srec.memory().unwrap();
+
+    assert_eq!(srec.header(), b"falcon");
+    assert_eq!(
+        memory
+            .sections()
+            .iter()
+            .map(|(address, section)| (*address, section.data().len()))
+            .collect::<Vec<(u64, usize)>>(),
+        vec![(0x100, 1), (0x2000_0000, 10)]
+    );
+    assert_eq!(memory.get8(0x100), Some(0xc3));
+    assert_eq!(memory.get8(0x2000_0009), Some(0x09));
+    assert_eq!(srec.program_entry(), 0x2000_0004);
+}
+
+#[test]
+fn srecord_errors() {
+    let bad_checksum = SRECORD.replace("S307200000080809BF", "S307200000080809B0");
+    let error = SRecord::new(&bad_checksum, Box::new(Arm::new())).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Line 3: checksum mismatch, expected 0xbf but found 0xb0"
+    );
+
+    let bad_count = SRECORD.replace("S5030003F9\n", "S5030004F8\n");
+    assert!(SRecord::new(&bad_count, Box::new(Arm::new())).is_err());
+
+    let overlap = format!("{}S30620000000FFDA\n", SRECORD);
+    assert!(SRecord::new(&overlap, Box::new(Arm::new())).is_err());
+}
@@ -1,101 +1,311 @@
-//! Experimental loader which takes a program specification in Json form.
+//! Loader which takes a program specification in Json form.
+//!
+//! The specification is an object with the following fields:
+//!
+//! * `arch` - The name of the architecture, as given by `Architecture::name`.
+//! * `entry` - The address program execution begins at.
+//! * `functions` - An array of function entries, each with an `address`, an
+//! optional `name`, an optional `source` as named by `FunctionEntrySource`,
+//! and optional manual `edges` with a `head`, `tail`, and optional
+//! `condition`.
+//! * `segments` - An array of segments, each with an `address`, base64
+//! encoded `bytes`, and optional `permissions`, such as `"r-x"`. Segments
+//! without permissions have all permissions.
+//! * `symbols` - An optional array of symbols, each with a `name` and
+//! `address`.
+//!
+//! A `Json` can be created from any other `Loader` with `Json::from_loader`,
+//! and written out with `Json::to_file`, to snapshot a loaded and linked
+//! image.
 
 use crate::architecture::*;
+use crate::il;
 use crate::loader::*;
 use crate::memory::backing::*;
 use crate::memory::MemoryPermissions;
-use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 
-/// Experimental loader which takes a program specification in Json form.
+#[derive(Deserialize, Serialize)]
+struct JsonSpec {
+    arch: String,
+    entry: u64,
+    functions: Vec<JsonFunction>,
+    segments: Vec<JsonSegment>,
+    #[serde(default)]
+    symbols: Vec<JsonSymbol>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct JsonFunction {
+    address: u64,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    source: FunctionEntrySource,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    edges: Vec<JsonEdge>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct JsonEdge {
+    head: u64,
+    tail: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    condition: Option<il::Expression>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct JsonSegment {
+    address: u64,
+    bytes: String,
+    #[serde(default)]
+    permissions: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct JsonSymbol {
+    name: String,
+    address: u64,
+}
+
+/// Get the architecture with the given name.
+fn architecture(name: &str) -> Result<Box<dyn Architecture>> {
+    Ok(match name {
+        "aarch64" => Box::new(AArch64::new()),
+        "amd64" => Box::new(Amd64::new()),
+        "arm" => Box::new(Arm::new()),
+        "mips" => Box::new(Mips::new()),
+        "mipsel" => Box::new(Mipsel::new()),
+        "mips64" => Box::new(Mips64::new()),
+        "mips64el" => Box::new(Mips64el::new()),
+        "mipsn32" => Box::new(MipsN32::new()),
+        "mipsn32el" => Box::new(MipselN32::new()),
+        "ppc" => Box::new(Ppc::new()),
+        "ppc64" => Box::new(Ppc64::new()),
+        "ppc64elfv2" => Box::new(Ppc64ElfV2::new()),
+        "ppc64le" => Box::new(Ppc64le::new()),
+        "riscv32" => Box::new(Riscv32::new()),
+        "riscv64" => Box::new(Riscv64::new()),
+        "x86" => Box::new(X86::new()),
+        _ => bail!("unsupported architecture {}", name),
+    })
+}
+
+fn permissions_to_string(permissions: MemoryPermissions) -> String {
+    [
+        (MemoryPermissions::READ, 'r'),
+        (MemoryPermissions::WRITE, 'w'),
+        (MemoryPermissions::EXECUTE, 'x'),
+    ]
+    .iter()
+    .map(|(permission, c)| {
+        if permissions.contains(*permission) {
+            *c
+        } else {
+            '-'
+        }
+    })
+    .collect()
+}
+
+fn permissions_from_str(permissions: &str) -> Result<MemoryPermissions> {
+    let mut result = MemoryPermissions::NONE;
+    for c in permissions.chars() {
+        match c {
+            'r' => result |= MemoryPermissions::READ,
+            'w' => result |= MemoryPermissions::WRITE,
+            'x' => result |= MemoryPermissions::EXECUTE,
+            '-' => {}
+            _ => bail!("invalid segment permissions {}", permissions),
+        }
+    }
+    Ok(result)
+}
+
+/// Loader which takes a program specification in Json form.
 ///
 /// See the binary ninja script for an example use.
 #[derive(Debug)]
 pub struct Json {
     function_entries: Vec<FunctionEntry>,
+    manual_edges: BTreeMap<u64, Vec<(u64, u64, Option<il::Expression>)>>,
     memory: Memory,
     architecture: Box<dyn Architecture>,
     entry: u64,
+    symbols: Vec<Symbol>,
 }
 
 impl Json {
-    /// Create a new `Json` loader from the given file.
-    pub fn from_file(filename: &Path) -> Result<Json> {
-        let mut file = File::open(filename)?;
-        let mut buf = Vec::new();
-        file.read_to_end(&mut buf)?;
+    /// Create a new `Json` loader from a specification in Json form.
+    pub fn new(json: &str) -> Result<Json> {
+        let spec: JsonSpec = serde_json::from_str(json)?;
 
-        let root: Value = serde_json::from_str(&String::from_utf8(buf)?)?;
-
-        let architecture = match root["arch"] {
-            Value::String(ref architecture) => {
-                if architecture == "x86" {
-                    Box::new(X86::new())
-                } else {
-                    bail!("unsupported architecture {}", root["arch"])
-                }
-            }
-            _ => bail!("architecture missing"),
-        };
-
-        let entry = match root["entry"] {
-            Value::Number(ref number) => number.as_u64().unwrap(),
-            _ => bail!("entry missing"),
-        };
+        let architecture = architecture(&spec.arch)?;
 
         let mut function_entries = Vec::new();
-        if let Value::Array(ref functions) = root["functions"] {
-            for function in functions {
-                let address = match function["address"] {
-                    Value::Number(ref address) => match address.as_u64() {
-                        Some(address) => address,
-                        None => bail!("function address not u64"),
-                    },
-                    _ => bail!("address missing for function"),
-                };
-
-                let name = match function["name"] {
-                    Value::String(ref name) => name.to_string(),
-                    _ => bail!("name missing for function"),
-                };
-
-                function_entries.push(FunctionEntry::new(address, Some(name)));
+        let mut manual_edges = BTreeMap::new();
+        for function in spec.functions {
+            function_entries.push(
+                FunctionEntry::new(function.address, function.name).with_source(function.source),
+            );
+            if !function.edges.is_empty() {
+                manual_edges.insert(
+                    function.address,
+                    function
+                        .edges
+                        .into_iter()
+                        .map(|edge| (edge.head, edge.tail, edge.condition))
+                        .collect(),
+                );
             }
-        } else {
-            bail!("functions missing");
         }
 
         let mut memory = Memory::new(architecture.endian());
-        if let Value::Array(ref segments) = root["segments"] {
-            for segment in segments {
-                let address = match segment["address"] {
-                    Value::Number(ref address) => match address.as_u64() {
-                        Some(address) => address,
-                        None => bail!("segment address not u64"),
-                    },
-                    _ => bail!("address missing for segment"),
-                };
-
-                let bytes = match segment["bytes"] {
-                    Value::String(ref bytes) => base64::decode(&bytes)?,
-                    _ => bail!("bytes missing for segment"),
-                };
-
-                memory.set_memory(address, bytes, MemoryPermissions::ALL);
-            }
-        } else {
-            bail!("segments missing");
+        for segment in spec.segments {
+            let permissions = match segment.permissions {
+                Some(ref permissions) => permissions_from_str(permissions)?,
+                None => MemoryPermissions::ALL,
+            };
+            memory.set_memory(
+                segment.address,
+                base64::decode(&segment.bytes)?,
+                permissions,
+            );
         }
 
+        let symbols = spec
+            .symbols
+            .into_iter()
+            .map(|symbol| Symbol::new(symbol.name, symbol.address))
+            .collect();
+
         Ok(Json {
             function_entries,
+            manual_edges,
             memory,
             architecture,
-            entry,
+            entry: spec.entry,
+            symbols,
         })
     }
+
+    /// Create a new `Json` loader from the given file.
+    pub fn from_file(filename: &Path) -> Result<Json> {
+        let mut file = File::open(filename)?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+
+        Json::new(&String::from_utf8(buf)?)
+    }
+
+    /// Create a new `Json` loader holding a snapshot of the memory, function
+    /// entries, manual edges and symbols of another loader.
+    pub fn from_loader(loader: &dyn Loader) -> Result<Json> {
+        let function_entries = loader.function_entries()?;
+
+        let manual_edges = function_entries
+            .iter()
+            .map(|function_entry| {
+                (
+                    function_entry.address(),
+                    loader.manual_edges(function_entry.address()),
+                )
+            })
+            .filter(|(_, edges)| !edges.is_empty())
+            .collect();
+
+        Ok(Json {
+            function_entries,
+            manual_edges,
+            memory: loader.memory()?,
+            architecture: loader.architecture().box_clone(),
+            entry: loader.program_entry(),
+            symbols: loader.symbols(),
+        })
+    }
+
+    /// Add a function entry.
+    pub fn add_function_entry(&mut self, function_entry: FunctionEntry) {
+        self.function_entries.push(function_entry);
+    }
+
+    /// Add a manual edge to the function at `function_address`, which will be
+    /// used when lifting that function.
+    pub fn add_manual_edge(
+        &mut self,
+        function_address: u64,
+        head: u64,
+        tail: u64,
+        condition: Option<il::Expression>,
+    ) {
+        self.manual_edges
+            .entry(function_address)
+            .or_default()
+            .push((head, tail, condition));
+    }
+
+    /// Get the specification for this loader in Json form.
+    pub fn to_json(&self) -> Result<String> {
+        let functions = self
+            .function_entries
+            .iter()
+            .map(|function_entry| JsonFunction {
+                address: function_entry.address(),
+                name: function_entry.name().map(|name| name.to_string()),
+                source: function_entry.source(),
+                edges: self
+                    .manual_edges(function_entry.address())
+                    .into_iter()
+                    .map(|(head, tail, condition)| JsonEdge {
+                        head,
+                        tail,
+                        condition,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let segments = self
+            .memory
+            .sections()
+            .iter()
+            .map(|(address, section)| JsonSegment {
+                address: *address,
+                bytes: base64::encode(section.data()),
+                permissions: Some(permissions_to_string(section.permissions())),
+            })
+            .collect();
+
+        let symbols = self
+            .symbols
+            .iter()
+            .map(|symbol| JsonSymbol {
+                name: symbol.name().to_string(),
+                address: symbol.address(),
+            })
+            .collect();
+
+        let spec = JsonSpec {
+            arch: self.architecture.name().to_string(),
+            entry: self.entry,
+            functions,
+            segments,
+            symbols,
+        };
+
+        Ok(serde_json::to_string(&spec)?)
+    }
+
+    /// Write the specification for this loader in Json form to the given
+    /// file.
+    pub fn to_file(&self, filename: &Path) -> Result<()> {
+        let mut file = File::create(filename)?;
+        file.write_all(self.to_json()?.as_bytes())?;
+        Ok(())
+    }
 }
 
 impl Loader for Json {
@@ -115,11 +325,127 @@ impl Loader for Json {
         self.architecture.as_ref()
     }
 
+    fn manual_edges(&self, address: u64) -> Vec<(u64, u64, Option<il::Expression>)> {
+        self.manual_edges.get(&address).cloned().unwrap_or_default()
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
 
     fn symbols(&self) -> Vec<Symbol> {
-        Vec::new()
+        self.symbols.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn legacy_spec() {
+        // ret
+        let json = r#"{
+            "arch": "x86",
+            "entry": 4096,
+            "functions": [{"address": 4096, "name": "main"}],
+            "segments": [{"address": 4096, "bytes": "ww=="}]
+        }"#;
+        let loader = Json::new(json).unwrap();
+
+        assert_eq!(loader.architecture().name(), "x86");
+        assert_eq!(
+            loader.memory().unwrap().permissions(0x1000),
+            Some(MemoryPermissions::ALL)
+        );
+        assert_eq!(
+            loader.function_entries().unwrap(),
+            vec![FunctionEntry::new(0x1000, Some("main".to_string()))]
+        );
+        assert!(loader.symbols().is_empty());
+        assert_eq!(loader.program().unwrap().functions().len(), 1);
+    }
+
+    #[test]
+    fn permissions() {
+        for permissions in &["---", "r--", "rw-", "r-x", "rwx"] {
+            let parsed = permissions_from_str(permissions).unwrap();
+            assert_eq!(&permissions_to_string(parsed), permissions);
+        }
+        assert!(permissions_from_str("rwz").is_err());
+    }
+
+    #[test]
+    fn manual_edges() {
+        // mov eax, 0x1007; jmp eax; ret
+        let json = r#"{
+            "arch": "x86",
+            "entry": 4096,
+            "functions": [{"address": 4096, "edges": [{"head": 4096, "tail": 4103}]}],
+            "segments": [{"address": 4096, "bytes": "uAcQAAD/4MM=", "permissions": "r-x"}]
+        }"#;
+        let mut loader = Json::new(json).unwrap();
+
+        assert_eq!(loader.manual_edges(0x1000), vec![(0x1000, 0x1007, None)]);
+        let function = loader.function(0x1000).unwrap();
+        let lifted = |function: &il::Function, address: u64| {
+            function.blocks().iter().any(|block| {
+                block
+                    .instructions()
+                    .iter()
+                    .any(|instruction| instruction.address() == Some(address))
+            })
+        };
+        assert!(lifted(&function, 0x1007));
+
+        let translator = loader.architecture().translator();
+        let function = translator
+            .translate_function(&loader.memory().unwrap(), 0x1000)
+            .unwrap();
+        assert!(!lifted(&function, 0x1007));
+
+        // Manual edges survive a round trip
+        loader.add_function_entry(FunctionEntry::new(0x1007, None));
+        loader.add_manual_edge(0x1007, 0x1007, 0x1007, Some(il::expr_const(1, 1)));
+        let loader = Json::new(&loader.to_json().unwrap()).unwrap();
+
+        assert_eq!(loader.manual_edges(0x1000), vec![(0x1000, 0x1007, None)]);
+        assert_eq!(
+            loader.manual_edges(0x1007),
+            vec![(0x1007, 0x1007, Some(il::expr_const(1, 1)))]
+        );
+        assert_eq!(
+            loader.memory().unwrap().permissions(0x1000),
+            Some(MemoryPermissions::READ | MemoryPermissions::EXECUTE)
+        );
+    }
+
+    #[test]
+    fn elf_round_trip() {
+        let elf = Elf::from_file(
+            &PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib/loader/elf/fixtures/amd64"),
+        )
+        .unwrap();
+        let loader = Json::new(&Json::from_loader(&elf).unwrap().to_json().unwrap()).unwrap();
+
+        assert_eq!(loader.architecture().name(), elf.architecture().name());
+        assert_eq!(loader.program_entry(), elf.program_entry());
+        assert_eq!(
+            loader.function_entries().unwrap(),
+            elf.function_entries().unwrap()
+        );
+        assert_eq!(loader.symbols(), elf.symbols());
+
+        let memory = loader.memory().unwrap();
+        let elf_memory = elf.memory().unwrap();
+        assert_eq!(memory.sections().len(), elf_memory.sections().len());
+        for ((address, section), (elf_address, elf_section)) in
+            memory.sections().iter().zip(elf_memory.sections())
+        {
+            assert_eq!(address, elf_address);
+            assert_eq!(section.data(), elf_section.data());
+            assert_eq!(section.permissions(), elf_section.permissions());
+        }
     }
 }
@@ -0,0 +1,141 @@
+#!/usr/bin/env python3
+"""Builds the Mach-O fixtures used by the loader tests.
+
+There is no Mach-O toolchain to hand, so the files are laid out by hand. Each
+thin file has __PAGEZERO, __TEXT (with __text), __DATA (with __data) and
+__LINKEDIT segments, and LC_MAIN, LC_SYMTAB and LC_FUNCTION_STARTS.
+
+__text holds, at 0x800 into __TEXT:
+    0x800 _main:     push bp; mov bp, sp; call 0x810; pop bp; ret
+    0x810 (stripped) mov eax, 1; ret
+    0x820 _exported: xor eax, eax; ret
+"""
+import os
+import struct
+
+LC_SEGMENT = 0x1
+LC_SYMTAB = 0x2
+LC_SEGMENT_64 = 0x19
+LC_FUNCTION_STARTS = 0x26
+LC_MAIN = 0x80000028
+
+CPU_TYPE_I386 = 7
+CPU_TYPE_X86_64 = 0x01000007
+
+
+def uleb128(value):
+    out = bytearray()
+    while True:
+        byte = value & 0x7f
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def name16(name):
+    return name.encode().ljust(16, b"\0")
+
+
+def thin(is_64):
+    text_base = 0x100000000 if is_64 else 0x1000
+    data_base = text_base + 0x1000
+    linkedit_base = text_base + 0x2000
+
+    if is_64:
+        code = bytes.fromhex("554889e5e8070000005dc3")
+    else:
+        code = bytes.fromhex("5589e5e8080000005dc3")
+    code = code.ljust(0x10, b"\x90") + bytes.fromhex("b801000000c3").ljust(0x10, b"\x90")
+    code += bytes.fromhex("31c0c3")
+
+    # __LINKEDIT: function starts, then the symbol table, then strings
+    starts = uleb128(0x800) + uleb128(0x10) + uleb128(0x10) + b"\0"
+    starts = starts.ljust(8, b"\0")
+    strings = b"\0"
+    symbols = []
+    for name, n_type, n_sect, address in [
+        ("_main", 0x0f, 1, text_base + 0x800),
+        ("_exported", 0x0f, 1, text_base + 0x820),
+        ("_data_value", 0x0f, 2, data_base),
+        ("_printf", 0x01, 0, 0),
+    ]:
+        symbols.append((len(strings), n_type, n_sect, address))
+        strings += name.encode() + b"\0"
+    if is_64:
+        symtab = b"".join(struct.pack("<IBBHQ", *symbol[:3], 0, symbol[3]) for symbol in symbols)
+    else:
+        symtab = b"".join(struct.pack("<IBBHI", *symbol[:3], 0, symbol[3]) for symbol in symbols)
+    linkedit = starts + symtab + strings
+
+    if is_64:
+        def segment(name, vmaddr, vmsize, fileoff, filesize, prot, sections):
+            return struct.pack("<II16sQQQQIIII", LC_SEGMENT_64, 72 + 80 * len(sections),
+                               name16(name), vmaddr, vmsize, fileoff, filesize, prot, prot,
+                               len(sections), 0) + b"".join(sections)
+
+        def section(sectname, segname, addr, size, offset, flags):
+            return struct.pack("<16s16sQQIIIIIIII", name16(sectname), name16(segname), addr,
+                               size, offset, 0, 0, 0, flags, 0, 0, 0)
+    else:
+        def segment(name, vmaddr, vmsize, fileoff, filesize, prot, sections):
+            return struct.pack("<II16sIIIIIIII", LC_SEGMENT, 56 + 68 * len(sections),
+                               name16(name), vmaddr, vmsize, fileoff, filesize, prot, prot,
+                               len(sections), 0) + b"".join(sections)
+
+        def section(sectname, segname, addr, size, offset, flags):
+            return struct.pack("<16s16sIIIIIIIII", name16(sectname), name16(segname), addr,
+                               size, offset, 0, 0, 0, flags, 0, 0)
+
+    commands = []
+    if is_64:
+        commands.append(segment("__PAGEZERO", 0, text_base, 0, 0, 0, []))
+    else:
+        commands.append(segment("__PAGEZERO", 0, text_base, 0, 0, 0, []))
+    commands.append(segment("__TEXT", text_base, 0x1000, 0, 0x1000, 5, [
+        section("__text", "__TEXT", text_base + 0x800, len(code), 0x800, 0x80000400)]))
+    commands.append(segment("__DATA", data_base, 0x1000, 0x1000, 0x1000, 3, [
+        section("__data", "__DATA", data_base, 8, 0x1000, 0)]))
+    commands.append(segment("__LINKEDIT", linkedit_base, 0x1000, 0x2000, len(linkedit), 1, []))
+    commands.append(struct.pack("<IIQQ", LC_MAIN, 24, 0x800, 0))
+    commands.append(struct.pack("<IIIIII", LC_SYMTAB, 24, 0x2000 + len(starts), len(symbols),
+                                0x2000 + len(starts) + len(symtab), len(strings)))
+    commands.append(struct.pack("<IIII", LC_FUNCTION_STARTS, 16, 0x2000, len(starts)))
+    commands = b"".join(commands)
+
+    if is_64:
+        header = struct.pack("<IIIIIIII", 0xfeedfacf, CPU_TYPE_X86_64, 3, 2, 7, len(commands),
+                             0x00200085, 0)
+    else:
+        header = struct.pack("<IIIIIII", 0xfeedface, CPU_TYPE_I386, 3, 2, 7, len(commands),
+                             0x00200085)
+
+    image = bytearray(0x2000 + len(linkedit))
+    image[0:len(header) + len(commands)] = header + commands
+    image[0x800:0x800 + len(code)] = code
+    image[0x1000:0x1008] = struct.pack("<Q", 0x1122334455667788)
+    image[0x2000:] = linkedit
+    return bytes(image)
+
+
+def fat(slices):
+    header = struct.pack(">II", 0xcafebabe, len(slices))
+    offset = 0x1000
+    arches = b""
+    body = b""
+    for cputype, image in slices:
+        arches += struct.pack(">IIIII", cputype, 3, offset, len(image), 12)
+        padding = offset - 0x1000 - len(body)
+        body += b"\0" * padding + image
+        offset += (len(image) + 0xfff) & ~0xfff
+    return (header + arches).ljust(0x1000, b"\0") + body
+
+
+os.chdir(os.path.dirname(os.path.abspath(__file__)))
+x86_64 = thin(True)
+i386 = thin(False)
+open("x86_64", "wb").write(x86_64)
+open("i386", "wb").write(i386)
+open("fat", "wb").write(fat([(CPU_TYPE_I386, i386), (CPU_TYPE_X86_64, x86_64)]))
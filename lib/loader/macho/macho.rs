@@ -0,0 +1,311 @@
+//! Mach-O Loader
+
+use crate::architecture::{Amd64, X86};
+use crate::loader::*;
+use crate::memory::backing::Memory;
+use crate::memory::MemoryPermissions;
+use goblin::mach::constants::cputype::{CPU_TYPE_I386, CPU_TYPE_X86_64};
+use goblin::mach::constants::{
+    S_ATTR_PURE_INSTRUCTIONS, S_ATTR_SOME_INSTRUCTIONS, VM_PROT_EXECUTE, VM_PROT_READ,
+    VM_PROT_WRITE,
+};
+use goblin::mach::load_command::CommandVariant;
+use goblin::mach::symbols::N_SECT;
+use goblin::mach::Mach;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// The most zero-filled memory past the end of its file data a segment may
+/// ask for. Anything larger is taken to be a malformed vmsize.
+const MAX_ZERO_FILL: u64 = 0x1000_0000;
+
+/// Loader for a single Mach-O file, or one architecture of a fat Mach-O file.
+#[derive(Debug)]
+pub struct MachO {
+    bytes: Vec<u8>,
+    /// The offset of the Mach-O we are loading in `bytes`. This is non-zero
+    /// when loading from a fat file.
+    offset: usize,
+    /// The size of the Mach-O we are loading in `bytes`.
+    size: usize,
+    architecture: Box<dyn Architecture>,
+}
+
+impl MachO {
+    /// Create a new MachO from the given bytes.
+    ///
+    /// If the bytes are a fat Mach-O, the x86_64 slice is loaded if there is
+    /// one, and the i386 slice otherwise.
+    pub fn new(bytes: Vec<u8>) -> Result<MachO> {
+        let cputype = match Mach::parse(&bytes).map_err(|_| "Not a valid Mach-O")? {
+            Mach::Binary(macho) => macho.header.cputype,
+            Mach::Fat(multi_arch) => {
+                let arches = multi_arch.arches().map_err(|_| "Not a valid Mach-O")?;
+                if arches.iter().any(|arch| arch.cputype == CPU_TYPE_X86_64) {
+                    CPU_TYPE_X86_64
+                } else {
+                    CPU_TYPE_I386
+                }
+            }
+        };
+        MachO::new_with_cputype(bytes, cputype)
+    }
+
+    /// Create a new MachO from the given bytes, loading the slice with the
+    /// given `CPU_TYPE_*` if the bytes are a fat Mach-O.
+    pub fn new_with_cputype(bytes: Vec<u8>, cputype: u32) -> Result<MachO> {
+        let (offset, size) = match Mach::parse(&bytes).map_err(|_| "Not a valid Mach-O")? {
+            Mach::Binary(macho) => {
+                if macho.header.cputype != cputype {
+                    bail!("Mach-O is not of cputype 0x{:x}", cputype);
+                }
+                (0, bytes.len())
+            }
+            Mach::Fat(multi_arch) => {
+                let arch = multi_arch
+                    .find_cputype(cputype)
+                    .map_err(|_| "Not a valid Mach-O")?
+                    .ok_or(format!(
+                        "Fat Mach-O has no slice of cputype 0x{:x}",
+                        cputype
+                    ))?;
+                (arch.offset as usize, arch.size as usize)
+            }
+        };
+
+        let architecture = match cputype {
+            CPU_TYPE_I386 => Box::new(X86::new()) as Box<dyn Architecture>,
+            CPU_TYPE_X86_64 => Box::new(Amd64::new()) as Box<dyn Architecture>,
+            _ => bail!("Unsupported Architecture"),
+        };
+
+        // Make sure the slice we will be loading parses
+        let slice = bytes
+            .get(offset..offset + size)
+            .ok_or("Fat Mach-O slice is out of bounds")?;
+        goblin::mach::MachO::parse(slice, 0).map_err(|_| "Not a valid Mach-O")?;
+
+        Ok(MachO {
+            bytes,
+            offset,
+            size,
+            architecture,
+        })
+    }
+
+    /// Load a MachO from a file.
+    pub fn from_file(filename: &Path) -> Result<MachO> {
+        MachO::new(MachO::read_file(filename)?)
+    }
+
+    /// Load a MachO from a file, loading the slice with the given
+    /// `CPU_TYPE_*` if the file is a fat Mach-O.
+    pub fn from_file_with_cputype(filename: &Path, cputype: u32) -> Result<MachO> {
+        MachO::new_with_cputype(MachO::read_file(filename)?, cputype)
+    }
+
+    fn read_file(filename: &Path) -> Result<Vec<u8>> {
+        let mut file = match File::open(filename) {
+            Ok(file) => file,
+            Err(e) => {
+                return Err(format!("Error opening {}: {}", filename.to_str().unwrap(), e).into())
+            }
+        };
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Get the bytes of the Mach-O we are loading. File offsets in the Mach-O
+    /// are relative to the start of these bytes.
+    fn slice(&self) -> &[u8] {
+        &self.bytes[self.offset..self.offset + self.size]
+    }
+
+    /// Return the goblin::mach::MachO for this MachO.
+    pub fn macho(&self) -> goblin::mach::MachO<'_> {
+        goblin::mach::MachO::parse(self.slice(), 0).unwrap()
+    }
+
+    /// Get the addresses of the functions listed in `LC_FUNCTION_STARTS`.
+    pub fn function_starts(&self) -> Result<Vec<u64>> {
+        let macho = self.macho();
+
+        let command =
+            macho
+                .load_commands
+                .iter()
+                .find_map(|load_command| match load_command.command {
+                    CommandVariant::FunctionStarts(command) => Some(command),
+                    _ => None,
+                });
+        let command = match command {
+            Some(command) => command,
+            None => return Ok(Vec::new()),
+        };
+
+        let offset = command.dataoff as usize;
+        let data = offset
+            .checked_add(command.datasize as usize)
+            .and_then(|end| self.slice().get(offset..end))
+            .ok_or("Could not read LC_FUNCTION_STARTS data")?;
+
+        // The addresses are encoded as ULEB128 deltas, beginning from the
+        // start of the __TEXT segment, and terminated by a zero delta.
+        let address = macho
+            .segments
+            .iter()
+            .find(|segment| segment.name().ok() == Some("__TEXT"))
+            .ok_or("Mach-O has LC_FUNCTION_STARTS but no __TEXT segment")?
+            .vmaddr;
+
+        Ok(decode_function_starts(data, address))
+    }
+
+    /// Get the symbols defined in a section, with the section flags of each.
+    fn section_symbols(&self) -> Vec<(Symbol, u32)> {
+        let macho = self.macho();
+
+        let section_flags = macho
+            .segments
+            .sections()
+            .flatten()
+            .map(|section| section.map(|(section, _)| section.flags).unwrap_or(0))
+            .collect::<Vec<u32>>();
+
+        macho
+            .symbols()
+            .filter_map(|symbol| symbol.ok())
+            .filter(|(_, nlist)| !nlist.is_stab() && nlist.get_type() == N_SECT)
+            .map(|(name, nlist)| {
+                // Sections are numbered from 1
+                let flags = section_flags
+                    .get(nlist.n_sect.wrapping_sub(1))
+                    .cloned()
+                    .unwrap_or(0);
+                (Symbol::new(name, nlist.n_value), flags)
+            })
+            .collect()
+    }
+}
+
+/// Decode the ULEB128 deltas of LC_FUNCTION_STARTS, beginning from `address`.
+///
+/// Decoding stops at a zero delta, or at a delta which does not fit in 64
+/// bits or overflows the address, returning the addresses decoded so far.
+pub(super) fn decode_function_starts(data: &[u8], mut address: u64) -> Vec<u64> {
+    let mut function_starts = Vec::new();
+    let mut delta = 0;
+    let mut shift = 0;
+    for byte in data {
+        if shift >= 64 {
+            break;
+        }
+        delta |= u64::from(byte & 0x7f) << shift;
+        shift += 7;
+        if byte & 0x80 != 0 {
+            continue;
+        }
+        if delta == 0 {
+            break;
+        }
+        address = match address.checked_add(delta) {
+            Some(address) => address,
+            None => break,
+        };
+        function_starts.push(address);
+        delta = 0;
+        shift = 0;
+    }
+    function_starts
+}
+
+impl Loader for MachO {
+    fn memory(&self) -> Result<Memory> {
+        let mut memory = Memory::new(self.architecture().endian());
+
+        for segment in self.macho().segments.iter() {
+            // __PAGEZERO, and anything else the process cannot access, is not
+            // mapped
+            if segment.initprot == 0 || segment.vmsize == 0 {
+                continue;
+            }
+
+            let mut bytes = segment.data.to_vec();
+            if segment.vmsize > bytes.len() as u64 + MAX_ZERO_FILL {
+                bail!(
+                    "Segment {} has a vmsize of 0x{:x}, but only 0x{:x} bytes in the file",
+                    segment.name().unwrap_or("?"),
+                    segment.vmsize,
+                    bytes.len()
+                );
+            }
+            bytes.resize(segment.vmsize as usize, 0);
+
+            let mut permissions = MemoryPermissions::NONE;
+            if segment.initprot & VM_PROT_READ != 0 {
+                permissions |= MemoryPermissions::READ;
+            }
+            if segment.initprot & VM_PROT_WRITE != 0 {
+                permissions |= MemoryPermissions::WRITE;
+            }
+            if segment.initprot & VM_PROT_EXECUTE != 0 {
+                permissions |= MemoryPermissions::EXECUTE;
+            }
+
+            memory.set_memory(segment.vmaddr, bytes, permissions);
+        }
+
+        Ok(memory)
+    }
+
+    fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
+        let mut function_entries = Vec::new();
+
+        for (symbol, flags) in self.section_symbols() {
+            if flags & (S_ATTR_PURE_INSTRUCTIONS | S_ATTR_SOME_INSTRUCTIONS) != 0
+                && !function_entries
+                    .iter()
+                    .any(|fe: &FunctionEntry| fe.address() == symbol.address())
+            {
+                function_entries.push(FunctionEntry::new(
+                    symbol.address(),
+                    Some(symbol.name().to_string()),
+                ));
+            }
+        }
+
+        for address in self.function_starts()? {
+            if !function_entries.iter().any(|fe| fe.address() == address) {
+                function_entries.push(FunctionEntry::new(address, None));
+            }
+        }
+
+        let entry = self.program_entry();
+        if entry != 0 && !function_entries.iter().any(|fe| fe.address() == entry) {
+            function_entries.push(FunctionEntry::new(entry, None));
+        }
+
+        Ok(function_entries)
+    }
+
+    fn program_entry(&self) -> u64 {
+        self.macho().entry
+    }
+
+    fn architecture(&self) -> &dyn Architecture {
+        self.architecture.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        self.section_symbols()
+            .into_iter()
+            .map(|(symbol, _)| symbol)
+            .collect()
+    }
+}
@@ -0,0 +1,8 @@
+//! Mach-O Loader
+#[allow(clippy::module_inception)]
+mod macho;
+
+#[cfg(test)]
+mod test;
+
+pub use self::macho::MachO;
@@ -0,0 +1,158 @@
+use super::macho::decode_function_starts;
+use crate::loader::{Loader, MachO, Symbol};
+use crate::memory::MemoryPermissions;
+use goblin::mach::constants::cputype::CPU_TYPE_I386;
+use std::path::PathBuf;
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib/loader/macho/fixtures")
+}
+
+fn fixture(filename: &str) -> MachO {
+    MachO::from_file(&fixtures().join(filename)).unwrap()
+}
+
+fn function_entries(macho: &MachO) -> Vec<(u64, Option<String>)> {
+    macho
+        .function_entries()
+        .unwrap()
+        .into_iter()
+        .map(|function_entry| {
+            (
+                function_entry.address(),
+                function_entry.name().map(|name| name.to_string()),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn x86_64_memory() {
+    let macho = fixture("x86_64");
+    let memory = macho.memory().unwrap();
+
+    assert_eq!(memory.permissions(0x1000), None);
+    assert_eq!(
+        memory.permissions(0x1_0000_0800),
+        Some(MemoryPermissions::READ | MemoryPermissions::EXECUTE)
+    );
+    assert_eq!(
+        memory.permissions(0x1_0000_1000),
+        Some(MemoryPermissions::READ | MemoryPermissions::WRITE)
+    );
+    assert_eq!(memory.get64(0x1_0000_1000), Some(0x1122_3344_5566_7788));
+    assert_eq!(memory.get8(0x1_0000_0800), Some(0x55));
+}
+
+#[test]
+fn x86_64_function_entries() {
+    let macho = fixture("x86_64");
+
+    assert_eq!(macho.architecture().name(), "amd64");
+    assert_eq!(macho.program_entry(), 0x1_0000_0800);
+    assert_eq!(
+        macho.function_starts().unwrap(),
+        vec![0x1_0000_0800, 0x1_0000_0810, 0x1_0000_0820]
+    );
+    assert_eq!(
+        function_entries(&macho),
+        vec![
+            (0x1_0000_0800, Some("_main".to_string())),
+            (0x1_0000_0820, Some("_exported".to_string())),
+            (0x1_0000_0810, None),
+        ]
+    );
+}
+
+#[test]
+fn x86_64_symbols() {
+    let macho = fixture("x86_64");
+
+    assert_eq!(
+        macho.symbols(),
+        vec![
+            Symbol::new("_main", 0x1_0000_0800),
+            Symbol::new("_exported", 0x1_0000_0820),
+            Symbol::new("_data_value", 0x1_0000_1000),
+        ]
+    );
+}
+
+#[test]
+fn x86_64_program() {
+    let macho = fixture("x86_64");
+    let program = macho.program().unwrap();
+
+    assert_eq!(program.functions().len(), 3);
+    assert!(program.function_by_name("_main").is_some());
+}
+
+#[test]
+fn i386() {
+    let macho = fixture("i386");
+
+    assert_eq!(macho.architecture().name(), "x86");
+    assert_eq!(macho.program_entry(), 0x1800);
+    assert_eq!(
+        function_entries(&macho),
+        vec![
+            (0x1800, Some("_main".to_string())),
+            (0x1820, Some("_exported".to_string())),
+            (0x1810, None),
+        ]
+    );
+    assert_eq!(macho.memory().unwrap().get32(0x2000), Some(0x5566_7788));
+}
+
+#[test]
+fn fat() {
+    let macho = fixture("fat");
+
+    assert_eq!(macho.architecture().name(), "amd64");
+    assert_eq!(macho.program_entry(), 0x1_0000_0800);
+    assert_eq!(macho.function_starts().unwrap().len(), 3);
+
+    let macho = MachO::from_file_with_cputype(&fixtures().join("fat"), CPU_TYPE_I386).unwrap();
+
+    assert_eq!(macho.architecture().name(), "x86");
+    assert_eq!(macho.program_entry(), 0x1800);
+    assert_eq!(macho.symbols()[0], Symbol::new("_main", 0x1800));
+    assert_eq!(macho.memory().unwrap().get8(0x1800), Some(0x55));
+}
+
+#[test]
+fn wrong_cputype() {
+    let bytes = std::fs::read(fixtures().join("x86_64")).unwrap();
+    assert!(MachO::new_with_cputype(bytes, CPU_TYPE_I386).is_err());
+}
+
+#[test]
+fn huge_vmsize() {
+    // The vmsize of __LINKEDIT, which has 0x6d bytes in the file
+    let mut bytes = std::fs::read(fixtures().join("x86_64")).unwrap();
+    bytes[0x1b8..0x1c0].copy_from_slice(&0xffff_ffff_0000u64.to_le_bytes());
+    assert!(MachO::new(bytes).unwrap().memory().is_err());
+}
+
+#[test]
+fn function_starts_out_of_bounds() {
+    // The datasize of LC_FUNCTION_STARTS
+    let mut bytes = std::fs::read(fixtures().join("x86_64")).unwrap();
+    bytes[0x21c..0x220].copy_from_slice(&0xffff_ffffu32.to_le_bytes());
+    assert!(MachO::new(bytes).unwrap().function_starts().is_err());
+}
+
+#[test]
+fn function_starts_overflow() {
+    // A delta which does not fit in 64 bits
+    let data = [
+        0x10, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+    ];
+    assert_eq!(decode_function_starts(&data, 0x1000), vec![0x1010]);
+
+    // A delta which overflows the address
+    let data = [
+        0x10, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x10,
+    ];
+    assert_eq!(decode_function_starts(&data, 0x1000), vec![0x1010]);
+}
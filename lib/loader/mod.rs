@@ -27,21 +27,62 @@ use std::any::Any;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+mod debug_info;
+#[cfg(feature = "dwarf")]
+mod dwarf;
 mod elf;
+mod hex;
 mod json;
+mod macho;
 mod pe;
+mod raw;
 mod symbol;
 
+pub use self::debug_info::{DebugFunction, DebugInfo};
 pub use self::elf::*;
+pub use self::hex::*;
 pub use self::json::*;
+pub use self::macho::*;
 pub use self::pe::*;
+pub use self::raw::*;
 pub use self::symbol::Symbol;
 
+/// Where a loader found a `FunctionEntry`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum FunctionEntrySource {
+    /// The loader does not say.
+    Unknown,
+    /// A symbol table.
+    Symbol,
+    /// The address program execution begins at.
+    ProgramEntry,
+    /// Added by the user.
+    User,
+    /// Call frame or unwind information, such as `.eh_frame`.
+    UnwindInfo,
+    /// A table of constructors or destructors, such as `.init_array`,
+    /// `.fini_array` or `.ctors`.
+    Constructor,
+    /// An argument to `__libc_start_main` in the program's entry stub.
+    LibcStartMain,
+    /// A stub which calls an imported function, such as a PLT entry.
+    Plt,
+    /// Debugging information, such as DWARF.
+    DebugInfo,
+}
+
+impl Default for FunctionEntrySource {
+    fn default() -> FunctionEntrySource {
+        FunctionEntrySource::Unknown
+    }
+}
+
 /// A declared entry point for a function.
 #[derive(Clone, Debug, PartialEq)]
 pub struct FunctionEntry {
     address: u64,
     name: Option<String>,
+    source: FunctionEntrySource,
 }
 
 impl FunctionEntry {
@@ -49,7 +90,17 @@ impl FunctionEntry {
     ///
     /// If no name is provided: `sup_{:X}` will be used to name the function.
     pub fn new(address: u64, name: Option<String>) -> FunctionEntry {
-        FunctionEntry { address, name }
+        FunctionEntry {
+            address,
+            name,
+            source: FunctionEntrySource::Unknown,
+        }
+    }
+
+    /// Set where this `FunctionEntry` was found.
+    pub fn with_source(mut self, source: FunctionEntrySource) -> FunctionEntry {
+        self.source = source;
+        self
     }
 
     /// Get the address for this `FunctionEntry`.
@@ -61,6 +112,11 @@ impl FunctionEntry {
     pub fn name(&self) -> Option<&str> {
         self.name.as_deref()
     }
+
+    /// Get where this `FunctionEntry` was found.
+    pub fn source(&self) -> FunctionEntrySource {
+        self.source
+    }
 }
 
 impl fmt::Display for FunctionEntry {
@@ -86,11 +142,34 @@ pub trait Loader: fmt::Debug + Send + Sync {
     /// Get the architecture of the binary
     fn architecture(&self) -> &dyn Architecture;
 
+    /// Get the manual edges for the function at the given address, as
+    /// `(head address, tail address, condition)`.
+    ///
+    /// Manual edges give control flow the translator cannot find by itself,
+    /// such as the targets of jump tables. By default there are none.
+    fn manual_edges(&self, _address: u64) -> Vec<(u64, u64, Option<il::Expression>)> {
+        Vec::new()
+    }
+
+    /// Get the debugging information for the binary, if it has any.
+    ///
+    /// Lifted functions are annotated with the source locations and variables
+    /// this gives. It is read once, the first time it is asked for. By default
+    /// there is none.
+    fn debug_info(&self) -> Result<Option<&DebugInfo>> {
+        Ok(None)
+    }
+
     /// Lift just one function from the executable
     fn function(&self, address: u64) -> Result<il::Function> {
         let translator = self.architecture().translator();
         let memory = self.memory()?;
-        Ok(translator.translate_function(&memory, address)?)
+        let mut function =
+            translator.translate_function_extended(&memory, address, self.manual_edges(address))?;
+        if let Some(debug_info) = self.debug_info()? {
+            debug_info.annotate(&mut function);
+        }
+        Ok(function)
     }
 
     /// Cast loader to `Any`
@@ -128,6 +207,8 @@ pub trait Loader: fmt::Debug + Send + Sync {
         // Create a mapping of the file memory
         let memory = self.memory()?;
 
+        let debug_info = self.debug_info()?;
+
         let mut program = il::Program::new();
 
         let mut translation_errors: Vec<(FunctionEntry, Error)> = Vec::new();
@@ -139,9 +220,16 @@ pub trait Loader: fmt::Debug + Send + Sync {
                 .permissions(address)
                 .map_or(false, |p| p.contains(memory::MemoryPermissions::EXECUTE))
             {
-                match translator.translate_function(&memory, address) {
+                match translator.translate_function_extended(
+                    &memory,
+                    address,
+                    self.manual_edges(address),
+                ) {
                     Ok(mut function) => {
                         function.set_name(function_entry.name().map(|n| n.to_string()));
+                        if let Some(ref debug_info) = debug_info {
+                            debug_info.annotate(&mut function);
+                        }
                         program.add_function(function);
                     }
                     Err(e) => translation_errors.push((function_entry.clone(), e)),
@@ -189,6 +277,10 @@ pub trait Loader: fmt::Debug + Send + Sync {
         let (mut program, mut translation_errors) = self.program_verbose()?;
         let mut processed = HashSet::new();
 
+        let translator = self.architecture().translator();
+        let memory = self.memory()?;
+        let debug_info = self.debug_info()?;
+
         loop {
             // Get the address of every function currently in the program
             let function_addresses = program
@@ -238,8 +330,17 @@ pub trait Loader: fmt::Debug + Send + Sync {
 
             // For each address, attempt to lift a function
             for address in addresses {
-                match self.function(address) {
-                    Ok(function) => program.add_function(function),
+                match translator.translate_function_extended(
+                    &memory,
+                    address,
+                    self.manual_edges(address),
+                ) {
+                    Ok(mut function) => {
+                        if let Some(ref debug_info) = debug_info {
+                            debug_info.annotate(&mut function);
+                        }
+                        program.add_function(function);
+                    }
                     Err(e) => {
                         let function_entry = FunctionEntry::new(address, None);
                         translation_errors.push((function_entry, e));
@@ -0,0 +1,13 @@
+#!/bin/sh
+# Rebuilds the PE fixtures used by the loader tests.
+set -e
+cd "$(dirname "$0")"
+
+# There is no PE toolchain to hand, so the PE fixtures are described in YAML
+yaml2obj pe32.yaml -o pe32.exe
+yaml2obj fixture.yaml -o fixture.dll
+yaml2obj fixture64.yaml -o fixture64.dll
+yaml2obj linked.yaml -o linked.exe
+mkdir -p amd64
+yaml2obj fixture64.yaml -o amd64/fixture.dll
+yaml2obj unwind.yaml -o unwind.exe
@@ -0,0 +1,12 @@
+//! PE Loader
+#[allow(clippy::module_inception)]
+mod pe;
+mod pe_linker;
+mod unwind;
+
+#[cfg(test)]
+mod test;
+
+pub use self::pe::Pe;
+pub use self::pe_linker::{PeLinker, PeLinkerBuilder};
+pub use self::unwind::{RuntimeFunction, UnwindCode, UnwindInfo, UnwindOperation};
@@ -0,0 +1,411 @@
+//! PE Loader
+
+use crate::architecture::{Amd64, X86};
+use crate::executor::eval;
+use crate::il;
+use crate::loader::pe::unwind;
+use crate::loader::*;
+use crate::memory::backing::Memory;
+use crate::memory::MemoryPermissions;
+use log::warn;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+// Base relocation types we handle. These are not in Goblin.
+const IMAGE_REL_BASED_ABSOLUTE: u16 = 0;
+const IMAGE_REL_BASED_HIGHLOW: u16 = 3;
+const IMAGE_REL_BASED_DIR64: u16 = 10;
+
+/// Loader for a single PE file.
+#[derive(Debug)]
+pub struct Pe {
+    bytes: Vec<u8>,
+    base_address: u64,
+    architecture: Box<dyn Architecture>,
+    imports: Vec<Symbol>,
+}
+
+impl Pe {
+    /// Create a new Pe from the given bytes, loaded at its preferred image
+    /// base.
+    pub fn new(bytes: Vec<u8>) -> Result<Pe> {
+        let image_base = goblin::pe::PE::parse(&bytes)
+            .map_err(|_| "Not a valid PE")?
+            .image_base as u64;
+        Pe::new_with_base_address(bytes, image_base)
+    }
+
+    /// Create a new Pe from the given bytes. This Pe will be rebased to the
+    /// given base address, applying its base relocations.
+    pub fn new_with_base_address(bytes: Vec<u8>, base_address: u64) -> Result<Pe> {
+        let (architecture, imports) = {
+            let pe = goblin::pe::PE::parse(&bytes).map_err(|_| "Not a valid PE")?;
+
+            let architecture = match pe.header.coff_header.machine {
+                goblin::pe::header::COFF_MACHINE_X86 => {
+                    Box::new(X86::new()) as Box<dyn Architecture>
+                }
+                goblin::pe::header::COFF_MACHINE_X86_64 => {
+                    Box::new(Amd64::new()) as Box<dyn Architecture>
+                }
+                _ => bail!("Unsupported Architecture"),
+            };
+
+            (architecture, Pe::import_symbols(&pe, base_address))
+        };
+
+        Ok(Pe {
+            bytes,
+            base_address,
+            architecture,
+            imports,
+        })
+    }
+
+    /// Get a symbol for each slot in the import address table of `pe`, loaded
+    /// at `base_address`.
+    fn import_symbols(pe: &goblin::pe::PE, base_address: u64) -> Vec<Symbol> {
+        pe.imports
+            .iter()
+            .map(|import| {
+                let dll = import.dll.to_lowercase();
+                // goblin stores the address of the IAT slot in offset, and
+                // gives imports by ordinal no hint/name entry
+                let name = if import.rva == 0 {
+                    format!("{}!#{}", dll, import.ordinal)
+                } else {
+                    format!("{}!{}", dll, import.name)
+                };
+                Symbol::new(name, base_address + import.offset as u64)
+            })
+            .collect()
+    }
+
+    /// Load a Pe from a file and use the given base address.
+    pub fn from_file_with_base_address(filename: &Path, base_address: u64) -> Result<Pe> {
+        Pe::new_with_base_address(Pe::read_file(filename)?, base_address)
+    }
+
+    /// Load a Pe from a file at its preferred image base.
+    pub fn from_file(filename: &Path) -> Result<Pe> {
+        Pe::new(Pe::read_file(filename)?)
+    }
+
+    fn read_file(filename: &Path) -> Result<Vec<u8>> {
+        let mut file = match File::open(filename) {
+            Ok(file) => file,
+            Err(e) => {
+                return Err(format!("Error opening {}: {}", filename.to_str().unwrap(), e).into())
+            }
+        };
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Get the base address of this Pe where it has been loaded into loader
+    /// memory.
+    pub fn base_address(&self) -> u64 {
+        self.base_address
+    }
+
+    /// Get the size of this Pe's image once loaded into memory.
+    pub fn size_of_image(&self) -> u64 {
+        self.pe()
+            .header
+            .optional_header
+            .map(|optional_header| optional_header.windows_fields.size_of_image as u64)
+            .unwrap_or(0)
+    }
+
+    /// Return the goblin::pe::PE for this PE.
+    pub fn pe(&self) -> goblin::pe::PE<'_> {
+        goblin::pe::PE::parse(&self.bytes).unwrap()
+    }
+
+    /// Get the bytes of the file from the given RVA to the end of the section
+    /// which holds it.
+    fn read_rva(&self, rva: u64) -> Option<&[u8]> {
+        let section = self.pe().sections.into_iter().find(|section| {
+            let virtual_address = section.virtual_address as u64;
+            rva >= virtual_address && rva < virtual_address + section.size_of_raw_data as u64
+        })?;
+        let offset =
+            (section.pointer_to_raw_data as u64 + rva - section.virtual_address as u64) as usize;
+        let end = (section.pointer_to_raw_data + section.size_of_raw_data) as usize;
+        self.bytes.get(offset..end)
+    }
+
+    /// Get the entries of the x64 function table in `.pdata`, with their
+    /// unwind information. Other PEs have no function table, and return no
+    /// entries.
+    pub fn runtime_functions(&self) -> Result<Vec<RuntimeFunction>> {
+        let pe = self.pe();
+        if pe.header.coff_header.machine != goblin::pe::header::COFF_MACHINE_X86_64 {
+            return Ok(Vec::new());
+        }
+
+        let table = match pe
+            .header
+            .optional_header
+            .and_then(|optional_header| *optional_header.data_directories.get_exception_table())
+        {
+            Some(table) => table,
+            None => return Ok(Vec::new()),
+        };
+
+        let pdata = self
+            .read_rva(table.virtual_address as u64)
+            .and_then(|pdata| pdata.get(0..table.size as usize))
+            .ok_or("Could not read .pdata")?;
+
+        Ok(unwind::parse_runtime_functions(
+            pdata,
+            |rva| self.read_rva(rva),
+            self.base_address,
+        ))
+    }
+
+    /// Get the entry of the x64 function table for the function containing
+    /// `address`, if there is one.
+    pub fn runtime_function(&self, address: u64) -> Result<Option<RuntimeFunction>> {
+        Ok(self
+            .runtime_functions()?
+            .into_iter()
+            .find(|runtime_function| {
+                address >= runtime_function.begin_address()
+                    && address < runtime_function.end_address()
+            }))
+    }
+
+    /// Apply the fixups in the base relocation table to `memory`, which holds
+    /// this Pe's sections at its base address.
+    fn apply_base_relocations(&self, memory: &mut Memory) -> Result<()> {
+        let pe = self.pe();
+        let delta = self.base_address.wrapping_sub(pe.image_base as u64);
+        if delta == 0 {
+            return Ok(());
+        }
+
+        let table = match pe.header.optional_header.and_then(|optional_header| {
+            *optional_header.data_directories.get_base_relocation_table()
+        }) {
+            Some(table) => table,
+            None => return Ok(()),
+        };
+
+        let read16 = |memory: &Memory, address: u64| -> Result<u16> {
+            let lo = memory
+                .get8(address)
+                .ok_or("Malformed base relocation table")?;
+            let hi = memory
+                .get8(address + 1)
+                .ok_or("Malformed base relocation table")?;
+            Ok(u16::from_le_bytes([lo, hi]))
+        };
+
+        // The table is a series of blocks, each a page RVA and block size
+        // followed by 16-bit entries for that page
+        let mut block = self.base_address + table.virtual_address as u64;
+        let end = block + table.size as u64;
+        while block < end {
+            let page = memory
+                .get32(block)
+                .ok_or("Malformed base relocation table")? as u64;
+            let block_size = memory
+                .get32(block + 4)
+                .ok_or("Malformed base relocation table")? as u64;
+            if block_size < 8 {
+                bail!("Malformed base relocation block at 0x{:x}", block);
+            }
+
+            for entry in (8..block_size).step_by(2) {
+                let entry = read16(memory, block + entry)?;
+                let address = self.base_address + page + (entry & 0xfff) as u64;
+                match entry >> 12 {
+                    IMAGE_REL_BASED_ABSOLUTE => {}
+                    IMAGE_REL_BASED_HIGHLOW => {
+                        let value = memory
+                            .get32(address)
+                            .ok_or(format!("Could not get fixup at 0x{:x}", address))?;
+                        memory.set32(address, value.wrapping_add(delta as u32))?;
+                    }
+                    IMAGE_REL_BASED_DIR64 => {
+                        let value = memory
+                            .get64(address)
+                            .ok_or(format!("Could not get fixup at 0x{:x}", address))?;
+                        memory.set64(address, value.wrapping_add(delta))?;
+                    }
+                    relocation_type => {
+                        bail!("unhandled base relocation type {}", relocation_type)
+                    }
+                }
+            }
+
+            block += block_size;
+        }
+
+        Ok(())
+    }
+
+    /// Get a symbol for each slot in the import address table, at the address
+    /// of that slot.
+    ///
+    /// Symbols are named `dll!function`, or `dll!#ordinal` for functions
+    /// imported by ordinal. DLL names are in lower case.
+    pub fn imports(&self) -> &[Symbol] {
+        &self.imports
+    }
+
+    /// If the branch instruction at `index` in `block` branches through a slot
+    /// in the import address table, as `call [iat_slot]` lifts to, get the
+    /// import for that slot.
+    pub fn import_branch_target(&self, block: &il::Block, index: usize) -> Option<Symbol> {
+        let instructions = block.instructions();
+        let position = instructions
+            .iter()
+            .position(|instruction| instruction.index() == index)?;
+
+        let target = match instructions[position].operation() {
+            il::Operation::Branch { target } => target.get_scalar()?,
+            _ => return None,
+        };
+
+        // Find the load of the branch target, and the constant address it
+        // loads from
+        let address = instructions[0..position]
+            .iter()
+            .rev()
+            .find_map(|instruction| match instruction.operation() {
+                il::Operation::Load { dst, index } if dst == target => Some(index),
+                _ => None,
+            })
+            .and_then(|index| eval(index).ok())
+            .and_then(|address| address.value_u64())?;
+
+        self.imports
+            .iter()
+            .find(|import| import.address() == address)
+            .cloned()
+    }
+
+    /// Get the import called by each branch through the import address table
+    /// in `function`, by the address of the branching instruction.
+    pub fn import_calls(&self, function: &il::Function) -> BTreeMap<u64, Symbol> {
+        let mut import_calls = BTreeMap::new();
+        for block in function.blocks() {
+            for instruction in block.instructions() {
+                if !instruction.is_branch() {
+                    continue;
+                }
+                if let (Some(address), Some(import)) = (
+                    instruction.address(),
+                    self.import_branch_target(block, instruction.index()),
+                ) {
+                    import_calls.insert(address, import);
+                }
+            }
+        }
+        import_calls
+    }
+}
+
+impl Loader for Pe {
+    fn memory(&self) -> Result<Memory> {
+        let mut memory = Memory::new(self.architecture().endian());
+
+        let pe = self.pe();
+        for section in pe.sections {
+            let file_offset = section.pointer_to_raw_data as usize;
+            let file_size = section.size_of_raw_data as usize;
+            let file_bytes = self
+                .bytes
+                .get(file_offset..(file_offset + file_size))
+                .expect("Malformed PE")
+                .to_vec();
+
+            let address = section.virtual_address as u64 + self.base_address;
+
+            let mut permissions = memory::MemoryPermissions::NONE;
+            if section.characteristics & goblin::pe::section_table::IMAGE_SCN_MEM_READ != 0 {
+                permissions |= MemoryPermissions::READ;
+            }
+            if section.characteristics & goblin::pe::section_table::IMAGE_SCN_MEM_WRITE != 0 {
+                permissions |= MemoryPermissions::WRITE;
+            }
+            if section.characteristics & goblin::pe::section_table::IMAGE_SCN_MEM_EXECUTE != 0 {
+                permissions |= MemoryPermissions::EXECUTE;
+            }
+
+            memory.set_memory(address, file_bytes, permissions);
+        }
+
+        self.apply_base_relocations(&mut memory)?;
+
+        Ok(memory)
+    }
+
+    fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
+        let pe = self.pe();
+
+        let mut function_entries = Vec::new();
+
+        for symbol in pe.exports {
+            let function_entry = FunctionEntry::new(
+                self.base_address + symbol.rva as u64,
+                symbol.name.map(|s| s.to_string()),
+            );
+            function_entries.push(function_entry);
+        }
+
+        let entry = self.base_address + pe.entry as u64;
+
+        if !function_entries.iter().any(|fe| fe.address() == entry) {
+            function_entries.push(FunctionEntry::new(entry, None));
+        }
+
+        let runtime_functions = self.runtime_functions().unwrap_or_else(|e| {
+            warn!("Skipping the function table: {}", e);
+            Vec::new()
+        });
+        for runtime_function in runtime_functions {
+            let address = runtime_function.begin_address();
+            if runtime_function.is_function_entry()
+                && !function_entries.iter().any(|fe| fe.address() == address)
+            {
+                function_entries.push(FunctionEntry::new(address, None));
+            }
+        }
+
+        Ok(function_entries)
+    }
+
+    fn program_entry(&self) -> u64 {
+        self.base_address + self.pe().entry as u64
+    }
+
+    fn architecture(&self) -> &dyn Architecture {
+        self.architecture.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        let pe = self.pe();
+        let mut symbols = Vec::new();
+        for export in pe.exports {
+            if let Some(name) = export.name {
+                symbols.push(Symbol::new(
+                    name.to_string(),
+                    self.base_address + export.rva as u64,
+                ));
+            }
+        }
+        symbols.extend_from_slice(&self.imports);
+        symbols
+    }
+}
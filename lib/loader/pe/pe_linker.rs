@@ -0,0 +1,345 @@
+use crate::architecture::*;
+use crate::loader::*;
+use crate::memory::backing::Memory;
+use log::warn;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The address where the first DLL will be loaded, if it cannot be loaded at
+/// its preferred image base.
+const DEFAULT_DLL_BASE: u64 = 0x1000_0000;
+/// The step in address between where we will load rebased DLLs.
+const DLL_BASE_STEP: u64 = 0x0100_0000;
+/// How many forwarded exports we will follow before giving up.
+const MAX_FORWARDS: usize = 8;
+
+/// A helper to build a PeLinker using the builder pattern.
+#[derive(Clone, Debug)]
+pub struct PeLinkerBuilder {
+    filename: PathBuf,
+    search_paths: Vec<PathBuf>,
+}
+
+impl PeLinkerBuilder {
+    /// Create a new PeLinker
+    pub fn new(filename: PathBuf) -> PeLinkerBuilder {
+        PeLinkerBuilder {
+            filename,
+            search_paths: Vec::new(),
+        }
+    }
+
+    /// Set the paths the PeLinker will search for DLLs
+    pub fn search_paths<P: Into<PathBuf>>(mut self, search_paths: Vec<P>) -> Self {
+        self.search_paths = search_paths.into_iter().map(|p| p.into()).collect();
+        self
+    }
+
+    /// Get the PeLinker for this PeLinkerBuilder
+    pub fn link(self) -> Result<PeLinker> {
+        PeLinker::new(self.filename, self.search_paths)
+    }
+}
+
+/// An import of a function by name, or by ordinal.
+enum ImportRef<'a> {
+    Name(&'a str),
+    Ordinal(usize),
+}
+
+/// Loader which links together multiple PE files.
+///
+/// Dependent DLLs which cannot be found in the search paths are skipped, and
+/// their imports are left unresolved.
+#[derive(Debug)]
+pub struct PeLinker {
+    /// The filename (path included) of the PE we are loading.
+    filename: PathBuf,
+    /// A mapping from the lower-cased filename of each loaded PE to its Pe.
+    loaded: BTreeMap<String, Pe>,
+    /// The current memory mapping.
+    memory: Memory,
+    /// The address we will place the next rebased DLL at.
+    next_dll_address: u64,
+    /// The paths where PeLinker will look for DLLs
+    search_paths: Vec<PathBuf>,
+}
+
+impl PeLinker {
+    /// Takes a path to a PE and links it with the DLLs it depends on.
+    ///
+    /// It is recommended you use PeLinkerBuilder to build a PeLinker.
+    pub fn new(filename: PathBuf, search_paths: Vec<PathBuf>) -> Result<PeLinker> {
+        let mut pe_linker = PeLinker {
+            filename: filename.clone(),
+            loaded: BTreeMap::new(),
+            memory: Memory::new(Endian::Little),
+            next_dll_address: DEFAULT_DLL_BASE,
+            search_paths,
+        };
+
+        pe_linker.load_pe(&filename)?;
+        pe_linker.resolve_imports()?;
+
+        Ok(pe_linker)
+    }
+
+    /// Get the PEs loaded and linked in this loader, by lower-cased filename
+    pub fn loaded(&self) -> &BTreeMap<String, Pe> {
+        &self.loaded
+    }
+
+    /// Get the filename of the PE we're loading
+    pub fn filename(&self) -> &Path {
+        &self.filename
+    }
+
+    /// Get the main Pe we are loading
+    pub fn get_pe(&self) -> Result<&Pe> {
+        let filename = PeLinker::key(&self.filename)?;
+        self.loaded
+            .get(&filename)
+            .ok_or(format!("Could not get {} from PeLinker", filename).into())
+    }
+
+    /// The key a PE is known by in `loaded`. Windows filenames are not case
+    /// sensitive.
+    fn key(filename: &Path) -> Result<String> {
+        Ok(filename
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or(format!("Invalid filename {:?}", filename))?
+            .to_lowercase())
+    }
+
+    /// Find a DLL for the given architecture in the search paths, ignoring
+    /// case. DLLs of the same name for other architectures are passed over.
+    fn find_dll(&self, name: &str, architecture: &str) -> Option<PathBuf> {
+        self.search_paths
+            .iter()
+            .filter_map(|search_path| {
+                search_path
+                    .read_dir()
+                    .ok()?
+                    .filter_map(|entry| entry.ok())
+                    .find(|entry| {
+                        entry
+                            .file_name()
+                            .to_str()
+                            .map(|file_name| file_name.eq_ignore_ascii_case(name))
+                            .unwrap_or(false)
+                    })
+                    .map(|entry| entry.path())
+            })
+            .find(|path| match Pe::from_file(path) {
+                Ok(pe) if pe.architecture().name() == architecture => true,
+                Ok(pe) => {
+                    warn!(
+                        "Skipping {:?}, which is for {}",
+                        path,
+                        pe.architecture().name()
+                    );
+                    false
+                }
+                Err(e) => {
+                    warn!("Skipping {:?}: {}", path, e);
+                    false
+                }
+            })
+    }
+
+    /// Find a base address for an image of the given size, preferring
+    /// `image_base`.
+    fn base_address(&mut self, image_base: u64, size: u64) -> u64 {
+        let overlaps = |loaded: &BTreeMap<String, Pe>, base: u64| {
+            loaded.values().any(|pe| {
+                base < pe.base_address() + pe.size_of_image() && pe.base_address() < base + size
+            })
+        };
+
+        if !overlaps(&self.loaded, image_base) {
+            return image_base;
+        }
+
+        while overlaps(&self.loaded, self.next_dll_address) {
+            self.next_dll_address += DLL_BASE_STEP;
+        }
+        let base_address = self.next_dll_address;
+        self.next_dll_address += DLL_BASE_STEP;
+        base_address
+    }
+
+    /// Takes the path to a PE, loads it at its image base (or rebases it if
+    /// that address is taken), and then loads all the DLLs it imports from.
+    ///
+    /// DLLs must be for the same architecture as the PE we are loading. Those
+    /// we cannot find for this architecture are left unresolved.
+    pub fn load_pe(&mut self, filename: &Path) -> Result<()> {
+        let pe = Pe::from_file(filename)?;
+        if let Ok(main) = self.get_pe() {
+            if main.architecture().name() != pe.architecture().name() {
+                bail!(
+                    "{:?} is for {}, but {:?} is for {}",
+                    filename,
+                    pe.architecture().name(),
+                    self.filename,
+                    main.architecture().name()
+                );
+            }
+        }
+        let base_address = self.base_address(pe.base_address(), pe.size_of_image());
+        let pe = if base_address == pe.base_address() {
+            pe
+        } else {
+            Pe::from_file_with_base_address(filename, base_address)?
+        };
+
+        // Update our memory map based on what's in the Pe
+        for (address, section) in pe.memory()?.sections() {
+            self.memory
+                .set_memory(*address, section.data().to_owned(), section.permissions());
+        }
+
+        let libraries = pe
+            .pe()
+            .libraries
+            .iter()
+            .map(|library| library.to_string())
+            .collect::<Vec<String>>();
+        let architecture = pe.architecture().name().to_string();
+
+        self.loaded.insert(PeLinker::key(filename)?, pe);
+
+        // Ensure all DLLs we rely on are loaded
+        for library in libraries {
+            if self.loaded.contains_key(&library.to_lowercase()) {
+                continue;
+            }
+            match self.find_dll(&library, &architecture) {
+                Some(path) => self.load_pe(&path)?,
+                None => warn!(
+                    "Could not find {} for {} in the search paths",
+                    library, architecture
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the address of an export of a loaded DLL, following forwarded
+    /// exports.
+    fn export_address(&self, dll: &str, import: ImportRef, forwards: usize) -> Option<u64> {
+        if forwards > MAX_FORWARDS {
+            return None;
+        }
+
+        let pe = self.loaded.get(&dll.to_lowercase())?;
+        let goblin_pe = pe.pe();
+
+        let rva = match import {
+            ImportRef::Name(name) => {
+                goblin_pe
+                    .exports
+                    .iter()
+                    .find(|export| export.name == Some(name))?
+                    .rva
+            }
+            ImportRef::Ordinal(ordinal) => {
+                let export_data = goblin_pe.export_data.as_ref()?;
+                let index = ordinal
+                    .checked_sub(export_data.export_directory_table.ordinal_base as usize)?;
+                match export_data.export_address_table.get(index)? {
+                    goblin::pe::export::ExportAddressTableEntry::ExportRVA(rva)
+                    | goblin::pe::export::ExportAddressTableEntry::ForwarderRVA(rva) => {
+                        *rva as usize
+                    }
+                }
+            }
+        };
+
+        let reexport = goblin_pe
+            .exports
+            .iter()
+            .find(|export| export.rva == rva)
+            .and_then(|export| export.reexport.as_ref());
+
+        match reexport {
+            Some(goblin::pe::export::Reexport::DLLName { export, lib }) => {
+                let dll = format!("{}.dll", lib);
+                self.export_address(&dll, ImportRef::Name(export), forwards + 1)
+            }
+            Some(goblin::pe::export::Reexport::DLLOrdinal { ordinal, lib }) => {
+                let dll = format!("{}.dll", lib);
+                self.export_address(&dll, ImportRef::Ordinal(*ordinal), forwards + 1)
+            }
+            None => Some(pe.base_address() + rva as u64),
+        }
+    }
+
+    /// Write the address of each imported function into its slot in the
+    /// import address table.
+    fn resolve_imports(&mut self) -> Result<()> {
+        let mut bindings = Vec::new();
+
+        for pe in self.loaded.values() {
+            let goblin_pe = pe.pe();
+            for import in &goblin_pe.imports {
+                // goblin gives imports by ordinal no hint/name entry
+                let import_ref = if import.rva == 0 {
+                    ImportRef::Ordinal(import.ordinal as usize)
+                } else {
+                    ImportRef::Name(&import.name)
+                };
+                match self.export_address(import.dll, import_ref, 0) {
+                    Some(address) => bindings.push((
+                        pe.base_address() + import.offset as u64,
+                        address,
+                        goblin_pe.is_64,
+                    )),
+                    None => warn!("Could not resolve import {}!{}", import.dll, import.name),
+                }
+            }
+        }
+
+        for (slot, address, is_64) in bindings {
+            if is_64 {
+                self.memory.set64(slot, address)?;
+            } else {
+                self.memory.set32(slot, address as u32)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Loader for PeLinker {
+    fn memory(&self) -> Result<Memory> {
+        Ok(self.memory.clone())
+    }
+
+    fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
+        let mut function_entries = Vec::new();
+        for pe in self.loaded.values() {
+            function_entries.append(&mut pe.function_entries()?);
+        }
+        Ok(function_entries)
+    }
+
+    fn program_entry(&self) -> u64 {
+        self.get_pe().unwrap().program_entry()
+    }
+
+    fn architecture(&self) -> &dyn Architecture {
+        self.get_pe().unwrap().architecture()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        self.loaded.values().flat_map(|pe| pe.symbols()).collect()
+    }
+}
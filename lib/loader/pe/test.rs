@@ -0,0 +1,254 @@
+use crate::analysis;
+use crate::loader::pe::unwind;
+use crate::loader::{Loader, Pe, PeLinkerBuilder, Symbol, UnwindOperation};
+use std::path::PathBuf;
+
+fn fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib/loader/pe/fixtures")
+}
+
+fn fixture(filename: &str) -> Pe {
+    Pe::from_file(&fixtures().join(filename)).unwrap()
+}
+
+#[test]
+fn pe32_imports() {
+    let pe = fixture("pe32.exe");
+
+    assert_eq!(
+        pe.imports(),
+        vec![
+            Symbol::new("kernel32.dll!CreateFileA", 0x402060),
+            Symbol::new("kernel32.dll!ExitProcess", 0x402064),
+            Symbol::new("user32.dll!#17", 0x40206c),
+        ]
+    );
+}
+
+#[test]
+fn pe32_symbols() {
+    let pe = fixture("pe32.exe");
+    let symbols = pe.symbols();
+
+    assert!(symbols.contains(&Symbol::new("exported", 0x401020)));
+    assert!(symbols.contains(&Symbol::new("kernel32.dll!CreateFileA", 0x402060)));
+}
+
+#[test]
+fn pe32_function_entries() {
+    let pe = fixture("pe32.exe");
+    let addresses = pe
+        .function_entries()
+        .unwrap()
+        .into_iter()
+        .map(|function_entry| function_entry.address())
+        .collect::<Vec<u64>>();
+
+    assert_eq!(addresses, vec![0x401020, 0x401000]);
+}
+
+#[test]
+fn pe32_import_calls() {
+    let pe = fixture("pe32.exe");
+    let function = pe.function(pe.program_entry()).unwrap();
+
+    let import_calls = pe
+        .import_calls(&function)
+        .into_iter()
+        .map(|(address, import)| (address, import.name().to_string()))
+        .collect::<Vec<(u64, String)>>();
+
+    assert_eq!(
+        import_calls,
+        vec![
+            (0x401000, "kernel32.dll!CreateFileA".to_string()),
+            (0x401006, "user32.dll!#17".to_string()),
+            (0x40100c, "kernel32.dll!ExitProcess".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn pe32_base_relocations() {
+    let path = fixtures().join("fixture.dll");
+
+    let memory = Pe::from_file(&path).unwrap().memory().unwrap();
+    assert_eq!(memory.get32(0x401001).unwrap(), 0x402004);
+    assert_eq!(memory.get32(0x402000).unwrap(), 0x402004);
+
+    let pe = Pe::from_file_with_base_address(&path, 0x1000_0000).unwrap();
+    let memory = pe.memory().unwrap();
+    assert_eq!(memory.get32(0x1000_1001).unwrap(), 0x1000_2004);
+    assert_eq!(memory.get32(0x1000_2000).unwrap(), 0x1000_2004);
+    assert!(pe
+        .symbols()
+        .contains(&Symbol::new("dll_function", 0x1000_1000)));
+}
+
+#[test]
+fn pe64_base_relocations() {
+    let pe =
+        Pe::from_file_with_base_address(&fixtures().join("fixture64.dll"), 0x7000_0000).unwrap();
+
+    assert_eq!(pe.architecture().name(), "amd64");
+    assert_eq!(
+        pe.memory().unwrap().get64(0x7000_2000).unwrap(),
+        0x7000_2008
+    );
+}
+
+#[test]
+fn pe_linker() {
+    let linker = PeLinkerBuilder::new(fixtures().join("linked.exe"))
+        .search_paths(vec![fixtures()])
+        .link()
+        .unwrap();
+    let memory = linker.memory().unwrap();
+
+    // fixture.dll prefers the executable's image base, so is rebased
+    assert_eq!(linker.loaded()["linked.exe"].base_address(), 0x400000);
+    assert_eq!(linker.loaded()["fixture.dll"].base_address(), 0x1000_0000);
+    assert_eq!(memory.get32(0x1000_1001).unwrap(), 0x1000_2004);
+
+    // dll_function, forwarded to dll_function, and ordinal 2, dll_data
+    assert_eq!(memory.get32(0x402060).unwrap(), 0x1000_1000);
+    assert_eq!(memory.get32(0x402064).unwrap(), 0x1000_1000);
+    assert_eq!(memory.get32(0x402068).unwrap(), 0x1000_2004);
+    // KERNEL32.dll is not in the search paths, so CreateFileA is unresolved
+    assert_eq!(memory.get32(0x402070).unwrap(), 0x20a0);
+
+    assert_eq!(linker.program_entry(), 0x401000);
+}
+
+#[test]
+fn pe_linker_skips_other_architectures() {
+    // The fixture.dll here is built for amd64, and linked.exe for x86, so the
+    // search continues on to the x86 fixture.dll
+    let linker = PeLinkerBuilder::new(fixtures().join("linked.exe"))
+        .search_paths(vec![fixtures().join("amd64"), fixtures()])
+        .link()
+        .unwrap();
+
+    assert_eq!(linker.loaded()["fixture.dll"].architecture().name(), "x86");
+    assert_eq!(
+        linker.memory().unwrap().get32(0x402060).unwrap(),
+        0x1000_1000
+    );
+
+    // With no x86 fixture.dll to be found, its imports are left unresolved
+    let linker = PeLinkerBuilder::new(fixtures().join("linked.exe"))
+        .search_paths(vec![fixtures().join("amd64")])
+        .link()
+        .unwrap();
+
+    assert!(!linker.loaded().contains_key("fixture.dll"));
+    assert_eq!(
+        linker.memory().unwrap().get32(0x402060).unwrap(),
+        fixture("linked.exe")
+            .memory()
+            .unwrap()
+            .get32(0x402060)
+            .unwrap()
+    );
+}
+
+#[test]
+fn pe64_runtime_functions() {
+    let pe = fixture("unwind.exe");
+    let runtime_functions = pe.runtime_functions().unwrap();
+
+    assert_eq!(runtime_functions.len(), 3);
+
+    let function = &runtime_functions[0];
+    assert_eq!(function.begin_address(), 0x1_4000_1000);
+    assert_eq!(function.end_address(), 0x1_4000_100b);
+    assert_eq!(function.unwind_info().size_of_prolog(), 5);
+    assert_eq!(function.unwind_info().frame_register(), None);
+    assert_eq!(function.unwind_info().pushed_registers(), vec!["rbx"]);
+    assert_eq!(function.unwind_info().stack_size(), 0x28);
+
+    let function = &runtime_functions[1];
+    assert_eq!(
+        function.unwind_info().unwind_codes()[1].operation(),
+        &UnwindOperation::Alloc { size: 0x100 }
+    );
+    assert_eq!(function.unwind_info().frame_register(), Some("rbp"));
+    assert_eq!(function.unwind_info().frame_offset(), 0x20);
+    assert_eq!(function.unwind_info().stack_size(), 0x108);
+
+    // The fragment at 0x1030 is chained to the function at 0x1010
+    let fragment = &runtime_functions[2];
+    assert!(!fragment.is_function_entry());
+    assert_eq!(fragment.unwind_info().chained(), Some(function));
+    assert_eq!(
+        pe.runtime_function(0x1_4000_1020).unwrap().as_ref(),
+        Some(function)
+    );
+}
+
+#[test]
+fn malformed_runtime_functions_are_skipped() {
+    // sub rsp, 40
+    let unwind_info: &[u8] = &[0x01, 0x04, 0x01, 0x00, 0x04, 0x42, 0x00, 0x00];
+    // An unknown unwind operation, 7
+    let unknown_operation: &[u8] = &[0x01, 0x04, 0x01, 0x00, 0x04, 0x07, 0x00, 0x00];
+    let read = |rva: u64| match rva {
+        0x100 => Some(unwind_info),
+        0x200 => Some(unknown_operation),
+        _ => None,
+    };
+
+    // The last entry's UNWIND_INFO cannot be read
+    let pdata = [
+        0x1000u32, 0x1010, 0x100, 0x1010, 0x1020, 0x200, 0x1020, 0x1030, 0x300,
+    ]
+    .iter()
+    .flat_map(|word| word.to_le_bytes().to_vec())
+    .collect::<Vec<u8>>();
+
+    let runtime_functions = unwind::parse_runtime_functions(&pdata, read, 0x4000_0000);
+    assert_eq!(runtime_functions.len(), 1);
+    assert_eq!(runtime_functions[0].begin_address(), 0x4000_1000);
+    assert_eq!(runtime_functions[0].unwind_info().stack_size(), 40);
+}
+
+#[test]
+fn pe64_function_entries() {
+    let pe = fixture("unwind.exe");
+    let addresses = pe
+        .function_entries()
+        .unwrap()
+        .into_iter()
+        .map(|function_entry| function_entry.address())
+        .collect::<Vec<u64>>();
+
+    assert_eq!(addresses, vec![0x1_4000_1000, 0x1_4000_1010]);
+}
+
+#[test]
+fn pe64_unwind_info_bounds_stack_pointer_offsets() {
+    let pe = fixture("unwind.exe");
+
+    for runtime_function in pe.runtime_functions().unwrap() {
+        if !runtime_function.is_function_entry() {
+            continue;
+        }
+
+        let function = pe.function(runtime_function.begin_address()).unwrap();
+        let stack_pointer_offsets =
+            analysis::stack_pointer_offsets::stack_pointer_offsets(&function, pe.architecture())
+                .unwrap();
+
+        // Every offset we know of lies within the frame the unwind info
+        // describes. The push of the first saved register is always known.
+        let stack_size = runtime_function.unwind_info().stack_size() as isize;
+        let offsets = stack_pointer_offsets
+            .values()
+            .filter_map(|offset| offset.value())
+            .collect::<Vec<isize>>();
+        assert!(offsets.contains(&-8));
+        assert!(offsets
+            .into_iter()
+            .all(|offset| offset <= 0 && offset >= -stack_size));
+    }
+}
@@ -0,0 +1,360 @@
+//! x64 exception handling data, from a PE's `.pdata` section.
+//!
+//! Every x64 function which allocates stack space or saves non-volatile
+//! registers has a `RUNTIME_FUNCTION` entry in `.pdata`, giving its bounds and
+//! the `UNWIND_INFO` which describes its prolog.
+
+use crate::architecture::AMD64_REGISTERS;
+use crate::error::*;
+use log::warn;
+
+const UNW_FLAG_CHAININFO: u8 = 0x4;
+
+/// How many chained unwind infos we will follow before giving up.
+const MAX_CHAIN: usize = 32;
+
+/// An entry in the `.pdata` function table, and its unwind information.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RuntimeFunction {
+    begin_address: u64,
+    end_address: u64,
+    unwind_info: UnwindInfo,
+}
+
+impl RuntimeFunction {
+    /// The address of the first byte of this function.
+    pub fn begin_address(&self) -> u64 {
+        self.begin_address
+    }
+
+    /// The address of the byte after the last byte of this function.
+    pub fn end_address(&self) -> u64 {
+        self.end_address
+    }
+
+    /// The unwind information describing this function's prolog.
+    pub fn unwind_info(&self) -> &UnwindInfo {
+        &self.unwind_info
+    }
+
+    /// Returns true if this is the primary entry for a function, and not a
+    /// fragment chained to another entry.
+    pub fn is_function_entry(&self) -> bool {
+        self.unwind_info.chained().is_none()
+    }
+}
+
+/// An operation performed by a function's prolog.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnwindOperation {
+    /// Push a non-volatile register.
+    PushNonvol { register: u8 },
+    /// Allocate space on the stack.
+    Alloc { size: u64 },
+    /// Set the frame register to rsp plus the frame offset.
+    SetFpReg,
+    /// Save a non-volatile register at an offset from rsp.
+    SaveNonvol { register: u8, offset: u64 },
+    /// Save an xmm register at an offset from rsp.
+    SaveXmm128 { register: u8, offset: u64 },
+    /// Push a machine frame, with an error code if `error_code` is set.
+    PushMachframe { error_code: bool },
+    /// Describes an epilog, in version 2 unwind info.
+    Epilog,
+}
+
+/// An entry in the unwind code array of an `UNWIND_INFO`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnwindCode {
+    code_offset: u8,
+    operation: UnwindOperation,
+}
+
+impl UnwindCode {
+    /// The offset from the beginning of the prolog of the end of the
+    /// instruction performing this operation.
+    pub fn code_offset(&self) -> u8 {
+        self.code_offset
+    }
+
+    /// The operation performed.
+    pub fn operation(&self) -> &UnwindOperation {
+        &self.operation
+    }
+}
+
+/// The frame information in an `UNWIND_INFO` structure.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnwindInfo {
+    version: u8,
+    flags: u8,
+    size_of_prolog: u8,
+    frame_register: u8,
+    frame_offset: u8,
+    unwind_codes: Vec<UnwindCode>,
+    chained: Option<Box<RuntimeFunction>>,
+}
+
+impl UnwindInfo {
+    /// The version of this unwind info, 1 or 2.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// The `UNW_FLAG_*` flags.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// The length of the function's prolog in bytes.
+    pub fn size_of_prolog(&self) -> u8 {
+        self.size_of_prolog
+    }
+
+    /// The name of the frame register, if the function uses one.
+    pub fn frame_register(&self) -> Option<&'static str> {
+        if self.frame_register == 0 {
+            None
+        } else {
+            Some(AMD64_REGISTERS[self.frame_register as usize])
+        }
+    }
+
+    /// The offset from rsp the frame register is set to, once the frame
+    /// register is established.
+    pub fn frame_offset(&self) -> u64 {
+        self.frame_offset as u64 * 16
+    }
+
+    /// The unwind codes, in the reverse order of the prolog's operations.
+    pub fn unwind_codes(&self) -> &[UnwindCode] {
+        &self.unwind_codes
+    }
+
+    /// The entry this unwind info is chained to, if any.
+    pub fn chained(&self) -> Option<&RuntimeFunction> {
+        self.chained.as_deref()
+    }
+
+    /// The names of the registers pushed by the prolog, in the order they are
+    /// pushed.
+    pub fn pushed_registers(&self) -> Vec<&'static str> {
+        self.unwind_codes
+            .iter()
+            .rev()
+            .filter_map(|unwind_code| match unwind_code.operation {
+                UnwindOperation::PushNonvol { register } => {
+                    Some(AMD64_REGISTERS[register as usize])
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The number of bytes the prolog subtracts from rsp, not including the
+    /// return address or any chained unwind info.
+    ///
+    /// At the end of the prolog, the offset of the stack pointer from its
+    /// value at function entry is the negation of this value.
+    pub fn stack_size(&self) -> u64 {
+        self.unwind_codes
+            .iter()
+            .map(|unwind_code| match unwind_code.operation {
+                UnwindOperation::PushNonvol { .. } => 8,
+                UnwindOperation::Alloc { size } => size,
+                UnwindOperation::PushMachframe { error_code: false } => 40,
+                UnwindOperation::PushMachframe { error_code: true } => 48,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+/// Parse the `RUNTIME_FUNCTION` entries in `pdata`. `read` returns the bytes
+/// at an RVA, and `base_address` is added to every address.
+///
+/// Malformed entries, and entries with unwind codes we do not know, are
+/// skipped.
+pub(crate) fn parse_runtime_functions<'a, F>(
+    pdata: &[u8],
+    read: F,
+    base_address: u64,
+) -> Vec<RuntimeFunction>
+where
+    F: Fn(u64) -> Option<&'a [u8]>,
+{
+    pdata
+        .chunks_exact(12)
+        .filter(|entry| entry.iter().any(|byte| *byte != 0))
+        .filter_map(
+            |entry| match parse_runtime_function(entry, &read, base_address, 0) {
+                Ok(runtime_function) => Some(runtime_function),
+                Err(e) => {
+                    warn!("Skipping RUNTIME_FUNCTION: {}", e);
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
+fn parse_runtime_function<'a, F>(
+    entry: &[u8],
+    read: &F,
+    base_address: u64,
+    depth: usize,
+) -> Result<RuntimeFunction>
+where
+    F: Fn(u64) -> Option<&'a [u8]>,
+{
+    if depth > MAX_CHAIN {
+        bail!("Too many chained unwind infos");
+    }
+
+    let begin_address = read_u32(entry, 0)? as u64;
+    let end_address = read_u32(entry, 4)? as u64;
+    let unwind_info_address = read_u32(entry, 8)? as u64;
+
+    let unwind_info = parse_unwind_info(unwind_info_address, read, base_address, depth)?;
+
+    Ok(RuntimeFunction {
+        begin_address: base_address + begin_address,
+        end_address: base_address + end_address,
+        unwind_info,
+    })
+}
+
+fn parse_unwind_info<'a, F>(
+    rva: u64,
+    read: &F,
+    base_address: u64,
+    depth: usize,
+) -> Result<UnwindInfo>
+where
+    F: Fn(u64) -> Option<&'a [u8]>,
+{
+    let bytes = read(rva).ok_or(format!("Could not read UNWIND_INFO at 0x{:x}", rva))?;
+    if bytes.len() < 4 {
+        bail!("Truncated UNWIND_INFO at 0x{:x}", rva);
+    }
+
+    let version = bytes[0] & 0x7;
+    let flags = bytes[0] >> 3;
+    let size_of_prolog = bytes[1];
+    let count_of_codes = bytes[2] as usize;
+    let frame_register = bytes[3] & 0xf;
+    let frame_offset = bytes[3] >> 4;
+
+    let slot = |index: usize| -> Result<u16> {
+        let offset = 4 + index * 2;
+        bytes
+            .get(offset..offset + 2)
+            .map(|slot| u16::from_le_bytes([slot[0], slot[1]]))
+            .ok_or_else(|| format!("Truncated UNWIND_INFO at 0x{:x}", rva).into())
+    };
+
+    let mut unwind_codes = Vec::new();
+    let mut index = 0;
+    while index < count_of_codes {
+        let code = slot(index)?;
+        let code_offset = (code & 0xff) as u8;
+        let op_info = (code >> 12) as u8;
+        let (operation, slots) = match (code >> 8) & 0xf {
+            0 => (UnwindOperation::PushNonvol { register: op_info }, 1),
+            1 if op_info == 0 => (
+                UnwindOperation::Alloc {
+                    size: slot(index + 1)? as u64 * 8,
+                },
+                2,
+            ),
+            1 => (
+                UnwindOperation::Alloc {
+                    size: slot(index + 1)? as u64 | (slot(index + 2)? as u64) << 16,
+                },
+                3,
+            ),
+            2 => (
+                UnwindOperation::Alloc {
+                    size: op_info as u64 * 8 + 8,
+                },
+                1,
+            ),
+            3 => (UnwindOperation::SetFpReg, 1),
+            4 => (
+                UnwindOperation::SaveNonvol {
+                    register: op_info,
+                    offset: slot(index + 1)? as u64 * 8,
+                },
+                2,
+            ),
+            5 => (
+                UnwindOperation::SaveNonvol {
+                    register: op_info,
+                    offset: slot(index + 1)? as u64 | (slot(index + 2)? as u64) << 16,
+                },
+                3,
+            ),
+            6 => (UnwindOperation::Epilog, 2),
+            8 => (
+                UnwindOperation::SaveXmm128 {
+                    register: op_info,
+                    offset: slot(index + 1)? as u64 * 16,
+                },
+                2,
+            ),
+            9 => (
+                UnwindOperation::SaveXmm128 {
+                    register: op_info,
+                    offset: slot(index + 1)? as u64 | (slot(index + 2)? as u64) << 16,
+                },
+                3,
+            ),
+            10 => (
+                UnwindOperation::PushMachframe {
+                    error_code: op_info != 0,
+                },
+                1,
+            ),
+            op => bail!("Unknown unwind operation {} at 0x{:x}", op, rva),
+        };
+        unwind_codes.push(UnwindCode {
+            code_offset,
+            operation,
+        });
+        index += slots;
+    }
+
+    // The chained RUNTIME_FUNCTION follows the unwind codes, which are padded
+    // to an even number of slots
+    let chained = if flags & UNW_FLAG_CHAININFO != 0 {
+        let offset = 4 + ((count_of_codes + 1) & !1) * 2;
+        let entry = bytes
+            .get(offset..offset + 12)
+            .ok_or(format!("Truncated UNWIND_INFO at 0x{:x}", rva))?;
+        Some(Box::new(parse_runtime_function(
+            entry,
+            read,
+            base_address,
+            depth + 1,
+        )?))
+    } else {
+        None
+    };
+
+    Ok(UnwindInfo {
+        version,
+        flags,
+        size_of_prolog,
+        frame_register,
+        frame_offset,
+        unwind_codes,
+        chained,
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| "Truncated RUNTIME_FUNCTION".into())
+}
@@ -0,0 +1,442 @@
+//! Loader for raw binaries, such as headerless firmware dumps.
+
+use crate::architecture::*;
+use crate::loader::*;
+use crate::memory::backing::*;
+use crate::memory::MemoryPermissions;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// A pattern matching the start of a common function prologue.
+enum Prologue {
+    /// Any of these byte sequences, at any offset.
+    Bytes(&'static [&'static [u8]]),
+    /// A 32-bit instruction where `instruction & mask == value`, at offsets
+    /// which are a multiple of `alignment`.
+    Instruction {
+        mask: u32,
+        value: u32,
+        alignment: usize,
+    },
+    /// A 16-bit instruction where `instruction & mask == value`, at offsets
+    /// which are a multiple of 2.
+    Halfword { mask: u16, value: u16 },
+    /// Any of these prologues.
+    Any(&'static [Prologue]),
+    /// Any of these Thumb prologues. Matches are returned with the Thumb bit
+    /// set, so they are translated as Thumb code.
+    Thumb(&'static [Prologue]),
+}
+
+impl Prologue {
+    /// Get the prologue pattern for the architecture with the given name.
+    fn for_architecture(name: &str) -> Option<Prologue> {
+        Some(match name {
+            // push ebp; mov ebp, esp
+            "x86" => Prologue::Bytes(&[&[0x55, 0x89, 0xe5], &[0x55, 0x8b, 0xec]]),
+            // push rbp; mov rbp, rsp
+            "amd64" => Prologue::Bytes(&[&[0x55, 0x48, 0x89, 0xe5], &[0x55, 0x48, 0x8b, 0xec]]),
+            // stp x29, x30, [sp, #-n]!
+            "aarch64" => Prologue::Instruction {
+                mask: 0xffe0_7fff,
+                value: 0xa9a0_7bfd,
+                alignment: 4,
+            },
+            "arm" => Prologue::Any(&[
+                // push {..., lr}
+                Prologue::Instruction {
+                    mask: 0xffff_4000,
+                    value: 0xe92d_4000,
+                    alignment: 4,
+                },
+                Prologue::Thumb(&[
+                    // push {..., lr}
+                    Prologue::Halfword {
+                        mask: 0xff00,
+                        value: 0xb500,
+                    },
+                    // Thumb-2 stmdb sp!, {..., lr}. The first halfword is
+                    // stored first, so it is the low half of the little-endian
+                    // word.
+                    Prologue::Instruction {
+                        mask: 0xc000_ffff,
+                        value: 0x4000_e92d,
+                        alignment: 2,
+                    },
+                ]),
+            ]),
+            // addiu $sp, $sp, -n, which n32 uses too, as its pointers are
+            // 32 bits
+            "mips" | "mipsel" | "mipsn32" | "mipsn32el" => Prologue::Instruction {
+                mask: 0xffff_8000,
+                value: 0x27bd_8000,
+                alignment: 4,
+            },
+            // daddiu $sp, $sp, -n
+            "mips64" | "mips64el" => Prologue::Instruction {
+                mask: 0xffff_8000,
+                value: 0x67bd_8000,
+                alignment: 4,
+            },
+            // stwu r1, -n(r1)
+            "ppc" => Prologue::Instruction {
+                mask: 0xffff_8000,
+                value: 0x9421_8000,
+                alignment: 4,
+            },
+            // stdu r1, -n(r1)
+            "ppc64" | "ppc64elfv2" | "ppc64le" => Prologue::Instruction {
+                mask: 0xffff_8003,
+                value: 0xf821_8001,
+                alignment: 4,
+            },
+            "riscv32" | "riscv64" => Prologue::Any(&[
+                // addi sp, sp, -n
+                Prologue::Instruction {
+                    mask: 0x800f_ffff,
+                    value: 0x8001_0113,
+                    alignment: 2,
+                },
+                // c.addi16sp sp, -n
+                Prologue::Halfword {
+                    mask: 0xff83,
+                    value: 0x7101,
+                },
+                // c.addi sp, -n
+                Prologue::Halfword {
+                    mask: 0xff83,
+                    value: 0x1101,
+                },
+            ]),
+            _ => return None,
+        })
+    }
+
+    /// Get the offsets in `bytes` where this prologue begins.
+    fn scan(&self, bytes: &[u8], endian: Endian) -> Vec<usize> {
+        match *self {
+            Prologue::Bytes(patterns) => (0..bytes.len())
+                .filter(|offset| {
+                    patterns
+                        .iter()
+                        .any(|pattern| bytes[*offset..].starts_with(pattern))
+                })
+                .collect(),
+            Prologue::Instruction {
+                mask,
+                value,
+                alignment,
+            } => (0..bytes.len().saturating_sub(3))
+                .step_by(alignment)
+                .filter(|offset| {
+                    let word = [
+                        bytes[*offset],
+                        bytes[offset + 1],
+                        bytes[offset + 2],
+                        bytes[offset + 3],
+                    ];
+                    let instruction = match endian {
+                        Endian::Big => u32::from_be_bytes(word),
+                        Endian::Little => u32::from_le_bytes(word),
+                    };
+                    instruction & mask == value
+                })
+                .collect(),
+            Prologue::Halfword { mask, value } => (0..bytes.len().saturating_sub(1))
+                .step_by(2)
+                .filter(|offset| {
+                    let halfword = [bytes[*offset], bytes[offset + 1]];
+                    let instruction = match endian {
+                        Endian::Big => u16::from_be_bytes(halfword),
+                        Endian::Little => u16::from_le_bytes(halfword),
+                    };
+                    instruction & mask == value
+                })
+                .collect(),
+            Prologue::Any(prologues) => {
+                let mut offsets = prologues
+                    .iter()
+                    .flat_map(|prologue| prologue.scan(bytes, endian.clone()))
+                    .collect::<Vec<usize>>();
+                offsets.sort();
+                offsets.dedup();
+                offsets
+            }
+            Prologue::Thumb(prologues) => Prologue::Any(prologues)
+                .scan(bytes, endian)
+                .into_iter()
+                .map(|offset| offset | 1)
+                .collect(),
+        }
+    }
+}
+
+/// Loader for raw binaries, such as headerless firmware dumps.
+///
+/// The bytes are mapped at the base address with all permissions. Entry
+/// points, additional segments, and scanning for function prologues are set
+/// with the builder-style methods.
+///
+/// ```
+/// # use falcon::error::*;
+/// use falcon::architecture::Mips;
+/// use falcon::loader::{Loader, Raw};
+/// use falcon::memory::MemoryPermissions;
+///
+/// # fn example () -> Result<()> {
+/// let firmware = vec![0; 0x100];
+/// let ram = vec![0; 0x1000];
+/// let raw = Raw::new(firmware, 0xbfc0_0000, Box::new(Mips::new()))
+///     .entry_points(vec![0xbfc0_0000])
+///     .segment(0xa000_0000, ram, MemoryPermissions::READ | MemoryPermissions::WRITE)
+///     .scan_prologues(true);
+/// let program = raw.program_recursive()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Raw {
+    base_address: u64,
+    memory: Memory,
+    architecture: Box<dyn Architecture>,
+    entry_points: Vec<u64>,
+    scan_prologues: bool,
+}
+
+impl Raw {
+    /// Create a new `Raw` loader, mapping `bytes` at `base_address`.
+    pub fn new(bytes: Vec<u8>, base_address: u64, architecture: Box<dyn Architecture>) -> Raw {
+        let mut memory = Memory::new(architecture.endian());
+        memory.set_memory(base_address, bytes, MemoryPermissions::ALL);
+
+        Raw {
+            base_address,
+            memory,
+            architecture,
+            entry_points: Vec::new(),
+            scan_prologues: false,
+        }
+    }
+
+    /// Create a new `Raw` loader, mapping the contents of the given file at
+    /// `base_address`.
+    pub fn from_file(
+        filename: &Path,
+        base_address: u64,
+        architecture: Box<dyn Architecture>,
+    ) -> Result<Raw> {
+        let mut file = match File::open(filename) {
+            Ok(file) => file,
+            Err(e) => {
+                return Err(format!("Error opening {}: {}", filename.to_str().unwrap(), e).into())
+            }
+        };
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        Ok(Raw::new(buf, base_address, architecture))
+    }
+
+    /// Set the entry points of this binary. The first entry point is the
+    /// program entry.
+    pub fn entry_points(mut self, entry_points: Vec<u64>) -> Self {
+        self.entry_points = entry_points;
+        self
+    }
+
+    /// Map an additional segment, with the given permissions.
+    pub fn segment(mut self, address: u64, bytes: Vec<u8>, permissions: MemoryPermissions) -> Self {
+        self.memory.set_memory(address, bytes, permissions);
+        self
+    }
+
+    /// Set whether executable memory is scanned for function prologues, to
+    /// find more function entries.
+    pub fn scan_prologues(mut self, scan_prologues: bool) -> Self {
+        self.scan_prologues = scan_prologues;
+        self
+    }
+
+    /// Get the address this binary is mapped at.
+    pub fn base_address(&self) -> u64 {
+        self.base_address
+    }
+
+    /// Get the addresses of function prologues in executable memory.
+    ///
+    /// Prologues are found by matching the first instruction of the usual
+    /// prologue for this architecture, and will not find every function.
+    pub fn prologues(&self) -> Vec<u64> {
+        let prologue = match Prologue::for_architecture(self.architecture.name()) {
+            Some(prologue) => prologue,
+            None => return Vec::new(),
+        };
+
+        self.memory
+            .sections()
+            .iter()
+            .filter(|(_, section)| section.permissions().contains(MemoryPermissions::EXECUTE))
+            .flat_map(|(address, section)| {
+                prologue
+                    .scan(section.data(), self.architecture.endian())
+                    .into_iter()
+                    .map(move |offset| address + offset as u64)
+            })
+            .collect()
+    }
+}
+
+impl Loader for Raw {
+    fn memory(&self) -> Result<Memory> {
+        Ok(self.memory.clone())
+    }
+
+    fn function_entries(&self) -> Result<Vec<FunctionEntry>> {
+        let mut function_entries: Vec<FunctionEntry> = Vec::new();
+
+        let prologues = if self.scan_prologues {
+            self.prologues()
+        } else {
+            Vec::new()
+        };
+
+        for address in self.entry_points.iter().chain(prologues.iter()) {
+            if !function_entries.iter().any(|fe| fe.address() == *address) {
+                function_entries.push(FunctionEntry::new(*address, None));
+            }
+        }
+
+        Ok(function_entries)
+    }
+
+    fn program_entry(&self) -> u64 {
+        self.entry_points
+            .first()
+            .cloned()
+            .unwrap_or(self.base_address)
+    }
+
+    fn architecture(&self) -> &dyn Architecture {
+        self.architecture.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn symbols(&self) -> Vec<Symbol> {
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_points() {
+        let raw = Raw::new(vec![0xc3; 0x10], 0x1000, Box::new(X86::new()));
+        assert_eq!(raw.program_entry(), 0x1000);
+        assert!(raw.function_entries().unwrap().is_empty());
+
+        let raw = raw.entry_points(vec![0x1008, 0x1000, 0x1008]);
+        assert_eq!(raw.program_entry(), 0x1008);
+        assert_eq!(
+            raw.function_entries().unwrap(),
+            vec![
+                FunctionEntry::new(0x1008, None),
+                FunctionEntry::new(0x1000, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn segments() {
+        let raw = Raw::new(vec![0xc3; 0x10], 0x1000, Box::new(X86::new())).segment(
+            0x2000,
+            vec![1, 2, 3, 4],
+            MemoryPermissions::READ,
+        );
+        let memory = raw.memory().unwrap();
+
+        assert_eq!(memory.permissions(0x1000), Some(MemoryPermissions::ALL));
+        assert_eq!(memory.permissions(0x2000), Some(MemoryPermissions::READ));
+        assert_eq!(memory.get32(0x2000), Some(0x0403_0201));
+    }
+
+    #[test]
+    fn x86_prologues() {
+        // f: push ebp; mov ebp, esp; pop ebp; ret
+        // g: push ebp; mov ebp, esp; call f; pop ebp; ret
+        let bytes = vec![
+            0x55, 0x89, 0xe5, 0x5d, 0xc3, 0x55, 0x89, 0xe5, 0xe8, 0xf3, 0xff, 0xff, 0xff, 0x5d,
+            0xc3,
+        ];
+        let raw = Raw::new(bytes.clone(), 0x1000, Box::new(X86::new()))
+            .entry_points(vec![0x1005])
+            .segment(0x2000, bytes, MemoryPermissions::READ);
+
+        assert_eq!(raw.prologues(), vec![0x1000, 0x1005]);
+        assert_eq!(raw.function_entries().unwrap().len(), 1);
+
+        let raw = raw.scan_prologues(true);
+        assert_eq!(
+            raw.function_entries().unwrap(),
+            vec![
+                FunctionEntry::new(0x1005, None),
+                FunctionEntry::new(0x1000, None)
+            ]
+        );
+        assert_eq!(raw.program_recursive().unwrap().functions().len(), 2);
+    }
+
+    #[test]
+    fn mips_prologues() {
+        // addiu $sp, $sp, -32; jr $ra; addiu $sp, $sp, 32; addiu $sp, $sp, -8
+        let bytes = vec![
+            0x27, 0xbd, 0xff, 0xe0, 0x03, 0xe0, 0x00, 0x08, 0x27, 0xbd, 0x00, 0x20, 0x27, 0xbd,
+            0xff, 0xf8,
+        ];
+
+        let raw = Raw::new(bytes, 0xbfc0_0000, Box::new(Mips::new()));
+        assert_eq!(raw.prologues(), vec![0xbfc0_0000, 0xbfc0_000c]);
+    }
+
+    #[test]
+    fn ppc_prologues() {
+        // stwu r1, -16(r1); addi r1, r1, 16; blr; stwu r1, -32(r1)
+        let bytes = vec![
+            0x94, 0x21, 0xff, 0xf0, 0x38, 0x21, 0x00, 0x10, 0x4e, 0x80, 0x00, 0x20, 0x94, 0x21,
+            0xff, 0xe0,
+        ];
+
+        let raw = Raw::new(bytes, 0x100, Box::new(Ppc::new()));
+        assert_eq!(raw.prologues(), vec![0x100, 0x10c]);
+    }
+
+    #[test]
+    fn arm_prologues() {
+        // push {r4, lr}; bx lr
+        // Thumb: push {r4, r7, lr}; pop {r4, r7, pc}
+        // Thumb-2: push.w {r4-r11, lr}
+        // Thumb matches have the Thumb bit set.
+        let bytes = vec![
+            0x10, 0x40, 0x2d, 0xe9, 0x1e, 0xff, 0x2f, 0xe1, 0x90, 0xb5, 0x90, 0xbd, 0x2d, 0xe9,
+            0xf0, 0x4f,
+        ];
+
+        let raw = Raw::new(bytes, 0x1000, Box::new(Arm::new()));
+        assert_eq!(raw.prologues(), vec![0x1000, 0x1009, 0x100d]);
+    }
+
+    #[test]
+    fn riscv_prologues() {
+        // c.addi16sp sp, -32; c.addi sp, -16; c.addi sp, 16; addi sp, sp, -16; c.jr ra
+        let bytes = vec![
+            0x3d, 0x71, 0x41, 0x11, 0x41, 0x01, 0x13, 0x01, 0x01, 0xff, 0x82, 0x80,
+        ];
+
+        let raw = Raw::new(bytes, 0x100, Box::new(Riscv64::new()));
+        assert_eq!(raw.prologues(), vec![0x100, 0x102, 0x106]);
+    }
+}
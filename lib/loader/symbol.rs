@@ -1,3 +1,5 @@
+use crate::demangle::demangle;
+
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Symbol {
     address: u64,
@@ -16,6 +18,12 @@ impl Symbol {
         &self.name
     }
 
+    /// Get the demangled name of this symbol, or its name if it is not
+    /// mangled.
+    pub fn demangled_name(&self) -> String {
+        demangle(&self.name).unwrap_or_else(|| self.name.clone())
+    }
+
     pub fn address(&self) -> u64 {
         self.address
     }
@@ -121,6 +121,19 @@ impl Memory {
         }
     }
 
+    /// Set the `u8` value at the given address.
+    pub fn set8(&mut self, address: u64, value: u8) -> Result<()> {
+        let (section_address, offset) = self
+            .section_address_offset(address)
+            .ok_or_else(|| format!("Address 0x{:x} has no section", address))?;
+
+        let section = self.sections.get_mut(&section_address).unwrap();
+
+        *section.data.get_mut(offset).unwrap() = value;
+
+        Ok(())
+    }
+
     /// Set the 32-bit value at the given address, allowing the memory model
     /// to account for the underlying endianness.
     pub fn set32(&mut self, address: u64, value: u32) -> Result<()> {
@@ -187,6 +200,33 @@ impl Memory {
         })
     }
 
+    /// Set the 64-bit value at the given address, allowing the memory model
+    /// to account for the underlying endianness.
+    pub fn set64(&mut self, address: u64, value: u64) -> Result<()> {
+        match self.endian {
+            Endian::Big => {
+                self.set32(address, (value >> 32) as u32)?;
+                self.set32(address + 4, value as u32)
+            }
+            Endian::Little => {
+                self.set32(address, value as u32)?;
+                self.set32(address + 4, (value >> 32) as u32)
+            }
+        }
+    }
+
+    /// Get the 64-bit value at the given address, allowing the memory model to
+    /// account for the underlying endianness.
+    pub fn get64(&self, address: u64) -> Option<u64> {
+        let first = self.get32(address)? as u64;
+        let second = self.get32(address + 4)? as u64;
+
+        Some(match self.endian {
+            Endian::Big => (first << 32) | second,
+            Endian::Little => (second << 32) | first,
+        })
+    }
+
     /// Get a constant value up to a certain number of bits
     pub fn get(&self, address: u64, bits: usize) -> Option<il::Constant> {
         if bits % 8 > 0 || bits == 0 {
@@ -0,0 +1,563 @@
+//! Capstone-based translator for AArch64.
+
+use crate::error::*;
+use crate::il::*;
+use crate::translator::{BlockTranslationResult, Translator};
+use falcon_capstone::{capstone, capstone_sys};
+
+pub mod semantics;
+#[cfg(test)]
+mod test;
+
+/// The AArch64 translator.
+#[derive(Clone, Debug, Default)]
+pub struct AArch64;
+
+impl AArch64 {
+    pub fn new() -> AArch64 {
+        AArch64
+    }
+}
+
+impl Translator for AArch64 {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(bytes, address)
+    }
+}
+
+fn unhandled_intrinsic(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.intrinsic(Intrinsic::new(
+            instruction.mnemonic.clone(),
+            format!("{} {}", instruction.mnemonic, instruction.op_str),
+            Vec::new(),
+            None,
+            None,
+            instruction.bytes.get(0..4).unwrap().to_vec(),
+        ));
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+fn ensure_block_instruction(control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    let head_block_num_instructions = control_flow_graph
+        .block(control_flow_graph.entry().unwrap())?
+        .instructions()
+        .len();
+
+    if head_block_num_instructions == 0 {
+        let head_index = control_flow_graph.entry().unwrap();
+        control_flow_graph.block_mut(head_index)?.nop();
+    }
+
+    Ok(())
+}
+
+fn translate_block(bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+    let cs = match capstone::Capstone::new(capstone::cs_arch::CS_ARCH_ARM64, capstone::CS_MODE_ARM)
+    {
+        Ok(cs) => cs,
+        Err(_) => return Err(ErrorKind::CapstoneError.into()),
+    };
+
+    cs.option(
+        capstone::cs_opt_type::CS_OPT_DETAIL,
+        capstone::cs_opt_value::CS_OPT_ON,
+    )
+    .unwrap();
+
+    // A vec which holds each lifted instruction in this block.
+    let mut block_graphs: Vec<(u64, ControlFlowGraph)> = Vec::new();
+
+    // the length of this block in bytes.
+    let mut length: usize = 0;
+
+    // The successors which exit this block.
+    let mut successors: Vec<(u64, Option<Expression>)> = Vec::new();
+
+    // Offset in bytes to the next instruction from the address given at entry.
+    let mut offset: usize = 0;
+
+    loop {
+        if offset == bytes.len() {
+            successors.push((address + offset as u64, None));
+            break;
+        }
+
+        let disassembly_range = (offset)..bytes.len();
+        let disassembly_bytes = bytes.get(disassembly_range).unwrap();
+        let instructions = match cs.disasm(disassembly_bytes, address + offset as u64, 1) {
+            Ok(instructions) => instructions,
+            Err(e) => match e.code() {
+                // Not enough bytes left for another instruction. Let the
+                // translator give us more.
+                capstone_sys::cs_err::CS_ERR_OK => {
+                    if offset == 0 {
+                        return Err(ErrorKind::DisassemblyFailure.into());
+                    }
+                    successors.push((address + offset as u64, None));
+                    break;
+                }
+                _ => return Err(ErrorKind::CapstoneError.into()),
+            },
+        };
+
+        if instructions.count() == 0 {
+            return Err(ErrorKind::CapstoneError.into());
+        }
+
+        let instruction = instructions.get(0).unwrap();
+
+        if let capstone::InstrIdArch::ARM64(instruction_id) = instruction.id {
+            let mut instruction_graph = ControlFlowGraph::new();
+
+            match instruction_id {
+                capstone::arm64_insn::ARM64_INS_ADC => {
+                    semantics::adc(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_ADD => {
+                    semantics::add(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_ADR | capstone::arm64_insn::ARM64_INS_ADRP => {
+                    semantics::adr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_AND => {
+                    semantics::and(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_ASR => {
+                    semantics::asr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_B
+                | capstone::arm64_insn::ARM64_INS_CBNZ
+                | capstone::arm64_insn::ARM64_INS_CBZ
+                | capstone::arm64_insn::ARM64_INS_TBNZ
+                | capstone::arm64_insn::ARM64_INS_TBZ => {
+                    semantics::b(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_BFI => {
+                    semantics::bfi(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_BFXIL => {
+                    semantics::bfxil(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_BIC => {
+                    semantics::bic(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_BL => {
+                    semantics::bl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_BLR => {
+                    semantics::blr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_BR => {
+                    semantics::br(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_BRK | capstone::arm64_insn::ARM64_INS_HLT => {
+                    semantics::brk(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CCMN => {
+                    semantics::ccmn(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CCMP => {
+                    semantics::ccmp(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CINC => {
+                    semantics::cinc(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CINV => {
+                    semantics::cinv(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CLS => {
+                    semantics::cls(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CLZ => {
+                    semantics::clz(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CMN => {
+                    semantics::cmn(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CMP => {
+                    semantics::cmp(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CNEG => {
+                    semantics::cneg(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CSEL => {
+                    semantics::csel(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CSET => {
+                    semantics::cset(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CSETM => {
+                    semantics::csetm(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CSINC => {
+                    semantics::csinc(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CSINV => {
+                    semantics::csinv(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CSNEG => {
+                    semantics::csneg(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_CLREX
+                | capstone::arm64_insn::ARM64_INS_DMB
+                | capstone::arm64_insn::ARM64_INS_DSB
+                | capstone::arm64_insn::ARM64_INS_HINT
+                | capstone::arm64_insn::ARM64_INS_ISB
+                | capstone::arm64_insn::ARM64_INS_NOP
+                | capstone::arm64_insn::ARM64_INS_PRFM
+                | capstone::arm64_insn::ARM64_INS_PRFUM
+                | capstone::arm64_insn::ARM64_INS_SEV
+                | capstone::arm64_insn::ARM64_INS_SEVL
+                | capstone::arm64_insn::ARM64_INS_WFE
+                | capstone::arm64_insn::ARM64_INS_WFI
+                | capstone::arm64_insn::ARM64_INS_YIELD => {
+                    semantics::nop(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_EON => {
+                    semantics::eon(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_EOR => {
+                    semantics::eor(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_EXTR => {
+                    semantics::extr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDAXP
+                | capstone::arm64_insn::ARM64_INS_LDNP
+                | capstone::arm64_insn::ARM64_INS_LDP
+                | capstone::arm64_insn::ARM64_INS_LDXP => {
+                    semantics::ldp(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDPSW => {
+                    semantics::ldpsw(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDAR
+                | capstone::arm64_insn::ARM64_INS_LDAXR
+                | capstone::arm64_insn::ARM64_INS_LDR
+                | capstone::arm64_insn::ARM64_INS_LDUR
+                | capstone::arm64_insn::ARM64_INS_LDXR => {
+                    semantics::ldr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDARB
+                | capstone::arm64_insn::ARM64_INS_LDAXRB
+                | capstone::arm64_insn::ARM64_INS_LDRB
+                | capstone::arm64_insn::ARM64_INS_LDURB
+                | capstone::arm64_insn::ARM64_INS_LDXRB => {
+                    semantics::ldrb(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDARH
+                | capstone::arm64_insn::ARM64_INS_LDAXRH
+                | capstone::arm64_insn::ARM64_INS_LDRH
+                | capstone::arm64_insn::ARM64_INS_LDURH
+                | capstone::arm64_insn::ARM64_INS_LDXRH => {
+                    semantics::ldrh(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDRSB | capstone::arm64_insn::ARM64_INS_LDURSB => {
+                    semantics::ldrsb(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDRSH | capstone::arm64_insn::ARM64_INS_LDURSH => {
+                    semantics::ldrsh(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LDRSW | capstone::arm64_insn::ARM64_INS_LDURSW => {
+                    semantics::ldrsw(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LSL => {
+                    semantics::lsl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_LSR => {
+                    semantics::lsr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MADD => {
+                    semantics::madd(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MNEG => {
+                    semantics::mneg(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MOV | capstone::arm64_insn::ARM64_INS_MOVZ => {
+                    semantics::mov(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MOVK => {
+                    semantics::movk(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MOVN => {
+                    semantics::movn(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MRS | capstone::arm64_insn::ARM64_INS_MSR => {
+                    unhandled_intrinsic(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MSUB => {
+                    semantics::msub(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MUL => {
+                    semantics::mul(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_MVN => {
+                    semantics::mvn(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_NEG => {
+                    semantics::neg(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_NGC => {
+                    semantics::ngc(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_ORN => {
+                    semantics::orn(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_ORR => {
+                    semantics::orr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_RBIT => {
+                    semantics::rbit(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_RET => {
+                    semantics::ret(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_REV => {
+                    semantics::rev(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_REV16 => {
+                    semantics::rev16(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_REV32 => {
+                    semantics::rev32(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_ROR => {
+                    semantics::ror(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SBC => {
+                    semantics::sbc(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SBFIZ => {
+                    semantics::sbfiz(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SBFX => {
+                    semantics::sbfx(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SDIV => {
+                    semantics::sdiv(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SMADDL => {
+                    semantics::smaddl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SMNEGL => {
+                    semantics::smnegl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SMSUBL => {
+                    semantics::smsubl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SMULH => {
+                    semantics::smulh(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SMULL => {
+                    semantics::smull(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_STNP | capstone::arm64_insn::ARM64_INS_STP => {
+                    semantics::stp(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_STLR
+                | capstone::arm64_insn::ARM64_INS_STR
+                | capstone::arm64_insn::ARM64_INS_STUR => {
+                    semantics::str(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_STLRB
+                | capstone::arm64_insn::ARM64_INS_STRB
+                | capstone::arm64_insn::ARM64_INS_STURB => {
+                    semantics::strb(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_STLRH
+                | capstone::arm64_insn::ARM64_INS_STRH
+                | capstone::arm64_insn::ARM64_INS_STURH => {
+                    semantics::strh(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_STLXP
+                | capstone::arm64_insn::ARM64_INS_STLXR
+                | capstone::arm64_insn::ARM64_INS_STXP
+                | capstone::arm64_insn::ARM64_INS_STXR => {
+                    semantics::stxr(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_STLXRB | capstone::arm64_insn::ARM64_INS_STXRB => {
+                    semantics::stxrb(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_STLXRH | capstone::arm64_insn::ARM64_INS_STXRH => {
+                    semantics::stxrh(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SUB => {
+                    semantics::sub(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SVC => {
+                    semantics::svc(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SXTB => {
+                    semantics::sxtb(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SXTH => {
+                    semantics::sxth(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_SXTW => {
+                    semantics::sxtw(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_TST => {
+                    semantics::tst(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UBFIZ => {
+                    semantics::ubfiz(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UBFX => {
+                    semantics::ubfx(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UDIV => {
+                    semantics::udiv(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UMADDL => {
+                    semantics::umaddl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UMNEGL => {
+                    semantics::umnegl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UMSUBL => {
+                    semantics::umsubl(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UMULH => {
+                    semantics::umulh(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UMULL => {
+                    semantics::umull(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UXTB => {
+                    semantics::uxtb(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UXTH => {
+                    semantics::uxth(&mut instruction_graph, &instruction)
+                }
+                capstone::arm64_insn::ARM64_INS_UXTW => {
+                    semantics::uxtw(&mut instruction_graph, &instruction)
+                }
+                _ => {
+                    let bytes = (0..4)
+                        .map(|i| disassembly_bytes[i])
+                        .map(|byte| format!("{:02x}", byte))
+                        .collect::<Vec<String>>()
+                        .join("");
+                    return Err(format!(
+                        "Unhandled instruction {} {} {} at 0x{:x}",
+                        bytes, instruction.mnemonic, instruction.op_str, instruction.address
+                    )
+                    .into());
+                }
+            }?;
+
+            length += instruction.size as usize;
+
+            // instructions that terminate blocks
+            match instruction_id {
+                capstone::arm64_insn::ARM64_INS_B => {
+                    ensure_block_instruction(&mut instruction_graph)?;
+                    instruction_graph.set_address(Some(instruction.address));
+                    block_graphs.push((instruction.address, instruction_graph));
+
+                    let detail = semantics::details(&instruction)?;
+                    let target = detail.operands[0].imm() as u64;
+                    match detail.cc {
+                        capstone_sys::arm64_cc::ARM64_CC_INVALID
+                        | capstone_sys::arm64_cc::ARM64_CC_AL => {
+                            successors.push((target, None));
+                        }
+                        cc => {
+                            let condition = semantics::condition_code(cc)?;
+                            successors.push((
+                                address + length as u64,
+                                Some(Expression::cmpeq(condition.clone(), expr_const(0, 1))?),
+                            ));
+                            successors.push((target, Some(condition)));
+                        }
+                    }
+                    break;
+                }
+                capstone::arm64_insn::ARM64_INS_CBNZ | capstone::arm64_insn::ARM64_INS_CBZ => {
+                    ensure_block_instruction(&mut instruction_graph)?;
+                    instruction_graph.set_address(Some(instruction.address));
+                    block_graphs.push((instruction.address, instruction_graph));
+
+                    let detail = semantics::details(&instruction)?;
+                    let register = semantics::get_register(detail.operands[0].reg())?;
+                    let target = detail.operands[1].imm() as u64;
+
+                    let zero = Expression::cmpeq(register.get()?, expr_const(0, register.bits()))?;
+                    let condition = if instruction_id == capstone::arm64_insn::ARM64_INS_CBZ {
+                        zero
+                    } else {
+                        Expression::cmpeq(zero, expr_const(0, 1))?
+                    };
+                    successors.push((
+                        address + length as u64,
+                        Some(Expression::cmpeq(condition.clone(), expr_const(0, 1))?),
+                    ));
+                    successors.push((target, Some(condition)));
+                    break;
+                }
+                capstone::arm64_insn::ARM64_INS_TBNZ | capstone::arm64_insn::ARM64_INS_TBZ => {
+                    ensure_block_instruction(&mut instruction_graph)?;
+                    instruction_graph.set_address(Some(instruction.address));
+                    block_graphs.push((instruction.address, instruction_graph));
+
+                    let detail = semantics::details(&instruction)?;
+                    let register = semantics::get_register(detail.operands[0].reg())?;
+                    let bit = detail.operands[1].imm() as u64;
+                    let target = detail.operands[2].imm() as u64;
+
+                    let set = Expression::trun(
+                        1,
+                        Expression::shr(register.get()?, expr_const(bit, register.bits()))?,
+                    )?;
+                    let condition = if instruction_id == capstone::arm64_insn::ARM64_INS_TBNZ {
+                        set
+                    } else {
+                        Expression::cmpeq(set, expr_const(0, 1))?
+                    };
+                    successors.push((
+                        address + length as u64,
+                        Some(Expression::cmpeq(condition.clone(), expr_const(0, 1))?),
+                    ));
+                    successors.push((target, Some(condition)));
+                    break;
+                }
+                // instructions without successors
+                capstone::arm64_insn::ARM64_INS_BR | capstone::arm64_insn::ARM64_INS_RET => {
+                    ensure_block_instruction(&mut instruction_graph)?;
+                    instruction_graph.set_address(Some(instruction.address));
+                    block_graphs.push((instruction.address, instruction_graph));
+
+                    break;
+                }
+                _ => {
+                    instruction_graph.set_address(Some(instruction.address));
+                    block_graphs.push((instruction.address, instruction_graph));
+                }
+            }
+        } else {
+            bail!("not an AArch64 instruction")
+        }
+
+        offset += instruction.size as usize;
+    }
+
+    Ok(BlockTranslationResult::new(
+        block_graphs,
+        address,
+        length,
+        successors,
+    ))
+}
@@ -0,0 +1,2347 @@
+use crate::error::*;
+use crate::il::Expression as Expr;
+use crate::il::*;
+use falcon_capstone::capstone;
+use falcon_capstone::capstone::cs_arm64_op;
+use falcon_capstone::capstone_sys::{
+    arm64_cc, arm64_extender, arm64_op_type, arm64_reg, arm64_shifter,
+};
+use std::cmp::Ordering;
+
+/// Struct for dealing with AArch64 registers
+pub struct AArch64Register {
+    name: &'static str,
+    // The capstone enum value for this register.
+    capstone_reg: arm64_reg,
+    /// The 64-bit register this register is a view of
+    full_reg: &'static str,
+    /// The size of this register in bits
+    bits: usize,
+}
+
+impl AArch64Register {
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Returns true if this is `xzr` or `wzr`.
+    pub fn is_zero(&self) -> bool {
+        self.full_reg == "xzr"
+    }
+
+    /// Returns the scalar for the full 64-bit register.
+    pub fn full_scalar(&self) -> Scalar {
+        scalar(self.full_reg, 64)
+    }
+
+    /// Returns an expression which evaluates to the value of this register.
+    pub fn get(&self) -> Result<Expr> {
+        if self.is_zero() {
+            Ok(expr_const(0, self.bits))
+        } else if self.bits == 64 {
+            Ok(expr_scalar(self.full_reg, 64))
+        } else {
+            Expr::trun(self.bits, expr_scalar(self.full_reg, 64))
+        }
+    }
+
+    /// Sets the value of this register.
+    ///
+    /// Writes to a 32-bit register zero the upper half of the full register,
+    /// and writes to the zero register are discarded.
+    pub fn set(&self, block: &mut Block, value: Expr) -> Result<()> {
+        if self.is_zero() {
+            return Ok(());
+        }
+        let value = if self.bits == 64 {
+            value
+        } else {
+            Expr::zext(64, value)?
+        };
+        block.assign(self.full_scalar(), value);
+        Ok(())
+    }
+}
+
+const AARCH64_REGISTERS: &[AArch64Register] = &[
+    AArch64Register {
+        name: "x0",
+        capstone_reg: arm64_reg::ARM64_REG_X0,
+        full_reg: "x0",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x1",
+        capstone_reg: arm64_reg::ARM64_REG_X1,
+        full_reg: "x1",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x2",
+        capstone_reg: arm64_reg::ARM64_REG_X2,
+        full_reg: "x2",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x3",
+        capstone_reg: arm64_reg::ARM64_REG_X3,
+        full_reg: "x3",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x4",
+        capstone_reg: arm64_reg::ARM64_REG_X4,
+        full_reg: "x4",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x5",
+        capstone_reg: arm64_reg::ARM64_REG_X5,
+        full_reg: "x5",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x6",
+        capstone_reg: arm64_reg::ARM64_REG_X6,
+        full_reg: "x6",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x7",
+        capstone_reg: arm64_reg::ARM64_REG_X7,
+        full_reg: "x7",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x8",
+        capstone_reg: arm64_reg::ARM64_REG_X8,
+        full_reg: "x8",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x9",
+        capstone_reg: arm64_reg::ARM64_REG_X9,
+        full_reg: "x9",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x10",
+        capstone_reg: arm64_reg::ARM64_REG_X10,
+        full_reg: "x10",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x11",
+        capstone_reg: arm64_reg::ARM64_REG_X11,
+        full_reg: "x11",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x12",
+        capstone_reg: arm64_reg::ARM64_REG_X12,
+        full_reg: "x12",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x13",
+        capstone_reg: arm64_reg::ARM64_REG_X13,
+        full_reg: "x13",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x14",
+        capstone_reg: arm64_reg::ARM64_REG_X14,
+        full_reg: "x14",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x15",
+        capstone_reg: arm64_reg::ARM64_REG_X15,
+        full_reg: "x15",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x16",
+        capstone_reg: arm64_reg::ARM64_REG_X16,
+        full_reg: "x16",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x17",
+        capstone_reg: arm64_reg::ARM64_REG_X17,
+        full_reg: "x17",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x18",
+        capstone_reg: arm64_reg::ARM64_REG_X18,
+        full_reg: "x18",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x19",
+        capstone_reg: arm64_reg::ARM64_REG_X19,
+        full_reg: "x19",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x20",
+        capstone_reg: arm64_reg::ARM64_REG_X20,
+        full_reg: "x20",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x21",
+        capstone_reg: arm64_reg::ARM64_REG_X21,
+        full_reg: "x21",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x22",
+        capstone_reg: arm64_reg::ARM64_REG_X22,
+        full_reg: "x22",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x23",
+        capstone_reg: arm64_reg::ARM64_REG_X23,
+        full_reg: "x23",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x24",
+        capstone_reg: arm64_reg::ARM64_REG_X24,
+        full_reg: "x24",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x25",
+        capstone_reg: arm64_reg::ARM64_REG_X25,
+        full_reg: "x25",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x26",
+        capstone_reg: arm64_reg::ARM64_REG_X26,
+        full_reg: "x26",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x27",
+        capstone_reg: arm64_reg::ARM64_REG_X27,
+        full_reg: "x27",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x28",
+        capstone_reg: arm64_reg::ARM64_REG_X28,
+        full_reg: "x28",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x29",
+        capstone_reg: arm64_reg::ARM64_REG_X29,
+        full_reg: "x29",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "x30",
+        capstone_reg: arm64_reg::ARM64_REG_X30,
+        full_reg: "x30",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "w0",
+        capstone_reg: arm64_reg::ARM64_REG_W0,
+        full_reg: "x0",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w1",
+        capstone_reg: arm64_reg::ARM64_REG_W1,
+        full_reg: "x1",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w2",
+        capstone_reg: arm64_reg::ARM64_REG_W2,
+        full_reg: "x2",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w3",
+        capstone_reg: arm64_reg::ARM64_REG_W3,
+        full_reg: "x3",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w4",
+        capstone_reg: arm64_reg::ARM64_REG_W4,
+        full_reg: "x4",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w5",
+        capstone_reg: arm64_reg::ARM64_REG_W5,
+        full_reg: "x5",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w6",
+        capstone_reg: arm64_reg::ARM64_REG_W6,
+        full_reg: "x6",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w7",
+        capstone_reg: arm64_reg::ARM64_REG_W7,
+        full_reg: "x7",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w8",
+        capstone_reg: arm64_reg::ARM64_REG_W8,
+        full_reg: "x8",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w9",
+        capstone_reg: arm64_reg::ARM64_REG_W9,
+        full_reg: "x9",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w10",
+        capstone_reg: arm64_reg::ARM64_REG_W10,
+        full_reg: "x10",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w11",
+        capstone_reg: arm64_reg::ARM64_REG_W11,
+        full_reg: "x11",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w12",
+        capstone_reg: arm64_reg::ARM64_REG_W12,
+        full_reg: "x12",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w13",
+        capstone_reg: arm64_reg::ARM64_REG_W13,
+        full_reg: "x13",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w14",
+        capstone_reg: arm64_reg::ARM64_REG_W14,
+        full_reg: "x14",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w15",
+        capstone_reg: arm64_reg::ARM64_REG_W15,
+        full_reg: "x15",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w16",
+        capstone_reg: arm64_reg::ARM64_REG_W16,
+        full_reg: "x16",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w17",
+        capstone_reg: arm64_reg::ARM64_REG_W17,
+        full_reg: "x17",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w18",
+        capstone_reg: arm64_reg::ARM64_REG_W18,
+        full_reg: "x18",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w19",
+        capstone_reg: arm64_reg::ARM64_REG_W19,
+        full_reg: "x19",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w20",
+        capstone_reg: arm64_reg::ARM64_REG_W20,
+        full_reg: "x20",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w21",
+        capstone_reg: arm64_reg::ARM64_REG_W21,
+        full_reg: "x21",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w22",
+        capstone_reg: arm64_reg::ARM64_REG_W22,
+        full_reg: "x22",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w23",
+        capstone_reg: arm64_reg::ARM64_REG_W23,
+        full_reg: "x23",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w24",
+        capstone_reg: arm64_reg::ARM64_REG_W24,
+        full_reg: "x24",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w25",
+        capstone_reg: arm64_reg::ARM64_REG_W25,
+        full_reg: "x25",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w26",
+        capstone_reg: arm64_reg::ARM64_REG_W26,
+        full_reg: "x26",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w27",
+        capstone_reg: arm64_reg::ARM64_REG_W27,
+        full_reg: "x27",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w28",
+        capstone_reg: arm64_reg::ARM64_REG_W28,
+        full_reg: "x28",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w29",
+        capstone_reg: arm64_reg::ARM64_REG_W29,
+        full_reg: "x29",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "w30",
+        capstone_reg: arm64_reg::ARM64_REG_W30,
+        full_reg: "x30",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "sp",
+        capstone_reg: arm64_reg::ARM64_REG_SP,
+        full_reg: "sp",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "wsp",
+        capstone_reg: arm64_reg::ARM64_REG_WSP,
+        full_reg: "sp",
+        bits: 32,
+    },
+    AArch64Register {
+        name: "xzr",
+        capstone_reg: arm64_reg::ARM64_REG_XZR,
+        full_reg: "xzr",
+        bits: 64,
+    },
+    AArch64Register {
+        name: "wzr",
+        capstone_reg: arm64_reg::ARM64_REG_WZR,
+        full_reg: "xzr",
+        bits: 32,
+    },
+];
+
+/// Takes a capstone register enum and returns an `AArch64Register`
+pub fn get_register(capstone_id: arm64_reg) -> Result<&'static AArch64Register> {
+    for register in AARCH64_REGISTERS.iter() {
+        if register.capstone_reg == capstone_id {
+            return Ok(register);
+        }
+    }
+    Err("Could not find register".into())
+}
+
+/// Converts the register of a memory operand to an `arm64_reg`.
+///
+/// Capstone 3 gives these as raw integers, and capstone 4 as `arm64_reg`.
+fn memory_register<R: Into<arm64_reg>>(capstone_id: R) -> arm64_reg {
+    capstone_id.into()
+}
+
+/// Returns the details section of an aarch64 capstone instruction.
+pub fn details(instruction: &capstone::Instr) -> Result<capstone::cs_arm64> {
+    let detail = instruction.detail.as_ref().unwrap();
+    match detail.arch {
+        capstone::DetailsArch::ARM64(x) => Ok(x),
+        _ => Err("Could not get instruction details".into()),
+    }
+}
+
+/// Generates a temporary scalar unique to this instruction.
+fn temp(instruction: &capstone::Instr, subindex: usize, bits: usize) -> Scalar {
+    Scalar::new(
+        format!("temp_0x{:X}_{}", instruction.address, subindex),
+        bits,
+    )
+}
+
+/// Returns a mask of the lowest `width` bits.
+fn mask(width: u64) -> u64 {
+    if width >= 64 {
+        0xffff_ffff_ffff_ffff
+    } else {
+        (1 << width) - 1
+    }
+}
+
+fn not(expr: Expr) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::xor(expr, expr_const(0xffff_ffff_ffff_ffff, bits))
+}
+
+fn sign_bit(expr: Expr) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::trun(1, Expr::shr(expr, expr_const(bits as u64 - 1, bits))?)
+}
+
+fn rotate_right(expr: Expr, amount: Expr) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::or(
+        Expr::shr(expr.clone(), amount.clone())?,
+        Expr::shl(expr, Expr::sub(expr_const(bits as u64, bits), amount)?)?,
+    )
+}
+
+/// Truncates or extends an expression to the given number of bits.
+fn resize(expr: Expr, bits: usize, signed: bool) -> Result<Expr> {
+    match expr.bits().cmp(&bits) {
+        Ordering::Less => {
+            if signed {
+                Expr::sext(bits, expr)
+            } else {
+                Expr::zext(bits, expr)
+            }
+        }
+        Ordering::Greater => Expr::trun(bits, expr),
+        Ordering::Equal => Ok(expr),
+    }
+}
+
+/// Applies an operand's extender to a register value, producing a value of
+/// `bits` bits.
+fn extend(value: Expr, extender: arm64_extender, bits: usize) -> Result<Expr> {
+    let (width, signed) = match extender {
+        arm64_extender::ARM64_EXT_UXTB => (8, false),
+        arm64_extender::ARM64_EXT_UXTH => (16, false),
+        arm64_extender::ARM64_EXT_UXTW => (32, false),
+        arm64_extender::ARM64_EXT_UXTX => (64, false),
+        arm64_extender::ARM64_EXT_SXTB => (8, true),
+        arm64_extender::ARM64_EXT_SXTH => (16, true),
+        arm64_extender::ARM64_EXT_SXTW => (32, true),
+        arm64_extender::ARM64_EXT_SXTX => (64, true),
+        arm64_extender::ARM64_EXT_INVALID => return resize(value, bits, false),
+    };
+    let value = if value.bits() > width {
+        Expr::trun(width, value)?
+    } else {
+        value
+    };
+    resize(value, bits, signed)
+}
+
+/// Applies an operand's shift to a value.
+fn shift(value: Expr, operand: &cs_arm64_op) -> Result<Expr> {
+    let amount = u64::from(operand.shift.value);
+    if amount == 0 {
+        return Ok(value);
+    }
+    let amount = expr_const(amount, value.bits());
+    match operand.shift.type_ {
+        arm64_shifter::ARM64_SFT_INVALID => Ok(value),
+        arm64_shifter::ARM64_SFT_LSL => Expr::shl(value, amount),
+        arm64_shifter::ARM64_SFT_LSR => Expr::shr(value, amount),
+        arm64_shifter::ARM64_SFT_ASR => Expr::sra(value, amount),
+        arm64_shifter::ARM64_SFT_ROR => rotate_right(value, amount),
+        arm64_shifter::ARM64_SFT_MSL => bail!("Unhandled MSL shift"),
+    }
+}
+
+/// Returns the value of a register or immediate operand as an expression of
+/// `bits` bits, with any extend and shift applied.
+pub fn operand_value(operand: &cs_arm64_op, bits: usize) -> Result<Expr> {
+    match operand.type_ {
+        arm64_op_type::ARM64_OP_REG => {
+            let value = get_register(operand.reg())?.get()?;
+            shift(extend(value, operand.ext, bits)?, operand)
+        }
+        arm64_op_type::ARM64_OP_IMM | arm64_op_type::ARM64_OP_CIMM => {
+            shift(expr_const(operand.imm() as u64, bits), operand)
+        }
+        _ => bail!("Invalid operand type"),
+    }
+}
+
+/// Returns the address of a memory operand, before any post-index.
+pub fn memory_address(operand: &cs_arm64_op) -> Result<Expr> {
+    let mem = operand.mem();
+    let base = get_register(memory_register(mem.base))?.get()?;
+    let index = memory_register(mem.index);
+    let offset = if index == arm64_reg::ARM64_REG_INVALID {
+        expr_const(mem.disp as i64 as u64, 64)
+    } else {
+        let index = get_register(index)?.get()?;
+        shift(extend(index, operand.ext, 64)?, operand)?
+    };
+    Expr::add(base, offset)
+}
+
+/// Returns the address accessed by a load or store, and the register and
+/// value to write back to it for pre and post-indexed addressing.
+fn load_store_address(
+    instruction: &capstone::Instr,
+) -> Result<(Expr, Option<(&'static AArch64Register, Expr)>)> {
+    let detail = details(instruction)?;
+    let operands = &detail.operands[0..detail.op_count as usize];
+
+    let index = match operands
+        .iter()
+        .position(|operand| operand.type_ == arm64_op_type::ARM64_OP_MEM)
+    {
+        Some(index) => index,
+        // PC-relative literal, `ldr x0, label`
+        None => match operands.last() {
+            Some(operand) if operand.type_ == arm64_op_type::ARM64_OP_IMM => {
+                return Ok((expr_const(operand.imm() as u64, 64), None));
+            }
+            _ => bail!("Could not find memory operand"),
+        },
+    };
+
+    let operand = &operands[index];
+    let address = memory_address(operand)?;
+
+    if !detail.writeback {
+        return Ok((address, None));
+    }
+
+    let base = get_register(memory_register(operand.mem().base))?;
+    match operands.get(index + 1) {
+        // post-index, `ldr x0, [x1], #8`
+        Some(post_index) => {
+            let value = Expr::add(base.get()?, operand_value(post_index, 64)?)?;
+            Ok((address, Some((base, value))))
+        }
+        // pre-index, `ldr x0, [x1, #8]!`
+        None => Ok((address.clone(), Some((base, address)))),
+    }
+}
+
+/// Returns an expression which evaluates to true when the condition code
+/// holds.
+pub fn condition_code(cc: arm64_cc) -> Result<Expr> {
+    let n = expr_scalar("n", 1);
+    let z = expr_scalar("z", 1);
+    let c = expr_scalar("c", 1);
+    let v = expr_scalar("v", 1);
+
+    Ok(match cc {
+        arm64_cc::ARM64_CC_EQ => z,
+        arm64_cc::ARM64_CC_NE => not(z)?,
+        arm64_cc::ARM64_CC_HS => c,
+        arm64_cc::ARM64_CC_LO => not(c)?,
+        arm64_cc::ARM64_CC_MI => n,
+        arm64_cc::ARM64_CC_PL => not(n)?,
+        arm64_cc::ARM64_CC_VS => v,
+        arm64_cc::ARM64_CC_VC => not(v)?,
+        arm64_cc::ARM64_CC_HI => Expr::and(c, not(z)?)?,
+        arm64_cc::ARM64_CC_LS => Expr::or(not(c)?, z)?,
+        arm64_cc::ARM64_CC_GE => Expr::cmpeq(n, v)?,
+        arm64_cc::ARM64_CC_LT => Expr::cmpneq(n, v)?,
+        arm64_cc::ARM64_CC_GT => Expr::and(not(z)?, Expr::cmpeq(n, v)?)?,
+        arm64_cc::ARM64_CC_LE => Expr::or(z, Expr::cmpneq(n, v)?)?,
+        arm64_cc::ARM64_CC_AL | arm64_cc::ARM64_CC_NV | arm64_cc::ARM64_CC_INVALID => {
+            expr_const(1, 1)
+        }
+    })
+}
+
+/// Computes `lhs + rhs + carry` into a temporary, and returns that temporary
+/// along with the n, z, c and v flags the addition produces.
+fn add_with_carry(
+    block: &mut Block,
+    instruction: &capstone::Instr,
+    lhs: Expr,
+    rhs: Expr,
+    carry: Expr,
+) -> Result<(Expr, [Expr; 4])> {
+    let bits = lhs.bits();
+    let result = temp(instruction, 0, bits);
+
+    let sum = Expr::add(lhs.clone(), rhs.clone())?;
+    let sum = if carry == expr_const(0, 1) {
+        sum
+    } else {
+        Expr::add(sum, Expr::zext(bits, carry.clone())?)?
+    };
+    block.assign(result.clone(), sum);
+
+    let result: Expr = result.into();
+
+    let n = sign_bit(result.clone())?;
+    let z = Expr::cmpeq(result.clone(), expr_const(0, bits))?;
+    let c = Expr::or(
+        Expr::cmpltu(result.clone(), lhs.clone())?,
+        Expr::and(Expr::cmpeq(result.clone(), lhs.clone())?, carry)?,
+    )?;
+    let v = sign_bit(Expr::and(
+        Expr::xor(lhs, result.clone())?,
+        Expr::xor(rhs, result.clone())?,
+    )?)?;
+
+    Ok((result, [n, z, c, v]))
+}
+
+/// Returns the flags set by a logical operation.
+fn logical_flags(result: Expr) -> Result<[Expr; 4]> {
+    let bits = result.bits();
+    Ok([
+        sign_bit(result.clone())?,
+        Expr::cmpeq(result, expr_const(0, bits))?,
+        expr_const(0, 1),
+        expr_const(0, 1),
+    ])
+}
+
+fn set_flags(block: &mut Block, flags: [Expr; 4]) {
+    let [n, z, c, v] = flags;
+    block.assign(scalar("n", 1), n);
+    block.assign(scalar("z", 1), z);
+    block.assign(scalar("c", 1), c);
+    block.assign(scalar("v", 1), v);
+}
+
+/// Returns the number of leading zero bits in `value`.
+fn count_leading_zeros(value: Expr) -> Result<Expr> {
+    let bits = value.bits();
+    let mut result = expr_const(bits as u64, bits);
+    for i in 0..bits {
+        let bit = Expr::trun(1, Expr::shr(value.clone(), expr_const(i as u64, bits))?)?;
+        result = Expr::ite(bit, expr_const((bits - 1 - i) as u64, bits), result)?;
+    }
+    Ok(result)
+}
+
+/// Reverses the order of the bytes in each `container` bits of `value`.
+fn reverse_bytes(value: Expr, container: usize) -> Result<Expr> {
+    let bits = value.bits();
+    let container_bytes = container / 8;
+    let mut result = expr_const(0, bits);
+    for i in 0..(bits / 8) {
+        let byte = Expr::and(
+            Expr::shr(value.clone(), expr_const(i as u64 * 8, bits))?,
+            expr_const(0xff, bits),
+        )?;
+        let position =
+            (i / container_bytes) * container_bytes + container_bytes - 1 - (i % container_bytes);
+        result = Expr::or(
+            result,
+            Expr::shl(byte, expr_const(position as u64 * 8, bits))?,
+        )?;
+    }
+    Ok(result)
+}
+
+/// Shared semantics for add, adds, adc and adcs.
+fn add_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    with_carry: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = operand_value(&detail.operands[1], dst.bits())?;
+    let rhs = operand_value(&detail.operands[2], dst.bits())?;
+    let carry = if with_carry {
+        expr_scalar("c", 1)
+    } else {
+        expr_const(0, 1)
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let (result, flags) = add_with_carry(block, instruction, lhs, rhs, carry)?;
+        if detail.update_flags {
+            set_flags(block, flags);
+        }
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for sub, subs, sbc and sbcs.
+fn sub_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    with_carry: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = operand_value(&detail.operands[1], dst.bits())?;
+    let rhs = operand_value(&detail.operands[2], dst.bits())?;
+    let carry = if with_carry {
+        expr_scalar("c", 1)
+    } else {
+        expr_const(1, 1)
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let (result, flags) = add_with_carry(block, instruction, lhs, not(rhs)?, carry)?;
+        if detail.update_flags {
+            set_flags(block, flags);
+        }
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for neg, negs, ngc and ngcs.
+fn neg_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    with_carry: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+    let carry = if with_carry {
+        expr_scalar("c", 1)
+    } else {
+        expr_const(1, 1)
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let zero = expr_const(0, dst.bits());
+        let (result, flags) = add_with_carry(block, instruction, zero, not(src)?, carry)?;
+        if detail.update_flags {
+            set_flags(block, flags);
+        }
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for cmp and cmn.
+fn compare_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    negative: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let bits = get_register(detail.operands[0].reg())?.bits();
+    let lhs = operand_value(&detail.operands[0], bits)?;
+    let rhs = operand_value(&detail.operands[1], bits)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let (_, flags) = if negative {
+            add_with_carry(block, instruction, lhs, rhs, expr_const(0, 1))?
+        } else {
+            add_with_carry(block, instruction, lhs, not(rhs)?, expr_const(1, 1))?
+        };
+        set_flags(block, flags);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for ccmp and ccmn.
+fn conditional_compare_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    negative: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let bits = get_register(detail.operands[0].reg())?.bits();
+    let lhs = operand_value(&detail.operands[0], bits)?;
+    let rhs = operand_value(&detail.operands[1], bits)?;
+    let nzcv = detail.operands[2].imm() as u64;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let condition = temp(instruction, 1, 1);
+        block.assign(condition.clone(), condition_code(detail.cc)?);
+
+        let (_, flags) = if negative {
+            add_with_carry(block, instruction, lhs, rhs, expr_const(0, 1))?
+        } else {
+            add_with_carry(block, instruction, lhs, not(rhs)?, expr_const(1, 1))?
+        };
+
+        let [n, z, c, v] = flags;
+        set_flags(
+            block,
+            [
+                Expr::ite(condition.clone().into(), n, expr_const(nzcv >> 3, 1))?,
+                Expr::ite(condition.clone().into(), z, expr_const(nzcv >> 2, 1))?,
+                Expr::ite(condition.clone().into(), c, expr_const(nzcv >> 1, 1))?,
+                Expr::ite(condition.into(), v, expr_const(nzcv, 1))?,
+            ],
+        );
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the logical instructions with a register or
+/// immediate second operand.
+fn logical_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr, Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = operand_value(&detail.operands[1], dst.bits())?;
+    let rhs = operand_value(&detail.operands[2], dst.bits())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = op(lhs, rhs)?;
+        if detail.update_flags {
+            let temp = temp(instruction, 0, dst.bits());
+            block.assign(temp.clone(), result);
+            set_flags(block, logical_flags(temp.clone().into())?);
+            dst.set(block, temp.into())?;
+        } else {
+            dst.set(block, result)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the shift instructions.
+fn shift_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr, Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+    let amount = operand_value(&detail.operands[2], dst.bits())?;
+    // register shift amounts are taken modulo the register size
+    let amount = Expr::and(amount, expr_const(dst.bits() as u64 - 1, dst.bits()))?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        dst.set(block, op(src, amount)?)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for mul, madd, msub and mneg.
+fn multiply_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    subtract: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = operand_value(&detail.operands[1], dst.bits())?;
+    let rhs = operand_value(&detail.operands[2], dst.bits())?;
+    let accumulator = if detail.op_count == 4 {
+        operand_value(&detail.operands[3], dst.bits())?
+    } else {
+        expr_const(0, dst.bits())
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let product = Expr::mul(lhs, rhs)?;
+        let result = if subtract {
+            Expr::sub(accumulator, product)?
+        } else if detail.op_count == 4 {
+            Expr::add(accumulator, product)?
+        } else {
+            product
+        };
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the 32x32->64 multiplies, smull, umaddl and
+/// friends.
+fn multiply_long_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    signed: bool,
+    subtract: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = get_register(detail.operands[1].reg())?.get()?;
+    let rhs = get_register(detail.operands[2].reg())?.get()?;
+    let accumulator = if detail.op_count == 4 {
+        operand_value(&detail.operands[3], 64)?
+    } else {
+        expr_const(0, 64)
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let product = Expr::mul(resize(lhs, 64, signed)?, resize(rhs, 64, signed)?)?;
+        let result = if subtract {
+            Expr::sub(accumulator, product)?
+        } else if detail.op_count == 4 {
+            Expr::add(accumulator, product)?
+        } else {
+            product
+        };
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for smulh and umulh.
+fn multiply_high_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = get_register(detail.operands[1].reg())?.get()?;
+    let rhs = get_register(detail.operands[2].reg())?.get()?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let product = Expr::mul(resize(lhs, 128, signed)?, resize(rhs, 128, signed)?)?;
+        let result = Expr::trun(64, Expr::shr(product, expr_const(64, 128))?)?;
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for sdiv and udiv.
+fn divide_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = operand_value(&detail.operands[1], dst.bits())?;
+    let rhs = operand_value(&detail.operands[2], dst.bits())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        // division by zero gives zero
+        let quotient = if signed {
+            Expr::divs(lhs, rhs.clone())?
+        } else {
+            Expr::divu(lhs, rhs.clone())?
+        };
+        let result = Expr::ite(
+            Expr::cmpeq(rhs, expr_const(0, dst.bits()))?,
+            expr_const(0, dst.bits()),
+            quotient,
+        )?;
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for csel, csinc, csinv and csneg. `op` is applied to the
+/// second source operand when the condition does not hold.
+fn conditional_select_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let lhs = operand_value(&detail.operands[1], dst.bits())?;
+    let rhs = operand_value(&detail.operands[2], dst.bits())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = Expr::ite(condition_code(detail.cc)?, lhs, op(rhs)?)?;
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for cinc, cinv and cneg. `op` is applied to the source
+/// operand when the condition holds.
+fn conditional_op_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = Expr::ite(condition_code(detail.cc)?, op(src.clone())?, src)?;
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for cset and csetm.
+fn conditional_set_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    value: u64,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = Expr::ite(
+            condition_code(detail.cc)?,
+            expr_const(value, dst.bits()),
+            expr_const(0, dst.bits()),
+        )?;
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the bitfield instructions. `op` receives the
+/// destination, the source, the lsb and the width, and returns the result.
+fn bitfield_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr, Expr, u64, u64) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+    let lsb = detail.operands[2].imm() as u64;
+    let width = detail.operands[3].imm() as u64;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        dst.set(block, op(dst.get()?, src, lsb, width)?)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for sxtb, uxtb and friends.
+fn extend_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    bits: usize,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = get_register(detail.operands[1].reg())?.get()?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = resize(resize(src, bits, false)?, dst.bits(), signed)?;
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the single source data processing instructions,
+/// clz, rev and friends.
+fn unary_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        dst.set(block, op(src)?)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for loads. Each register operand is loaded from
+/// consecutive addresses. `bits` is the size of each load, or the size of
+/// the destination register when `None`.
+fn load_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    bits: Option<usize>,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dsts = detail.operands[0..detail.op_count as usize]
+        .iter()
+        .take_while(|operand| operand.type_ == arm64_op_type::ARM64_OP_REG)
+        .map(|operand| get_register(operand.reg()))
+        .collect::<Result<Vec<&AArch64Register>>>()?;
+    let (address, writeback) = load_store_address(instruction)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let address_temp = temp(instruction, 0, 64);
+        block.assign(address_temp.clone(), address);
+
+        let mut offset = 0;
+        for (i, dst) in dsts.iter().enumerate() {
+            let bits = bits.unwrap_or_else(|| dst.bits());
+            let address = if offset == 0 {
+                address_temp.clone().into()
+            } else {
+                Expr::add(address_temp.clone().into(), expr_const(offset, 64))?
+            };
+            let value = temp(instruction, i + 1, bits);
+            block.load(value.clone(), address);
+            dst.set(block, resize(value.into(), dst.bits(), signed)?)?;
+            offset += bits as u64 / 8;
+        }
+
+        if let Some((base, value)) = writeback {
+            base.set(block, value)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for stores. Each register operand is stored to
+/// consecutive addresses. Exclusive stores have a leading status register,
+/// which we set to 0 for success.
+fn store_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    bits: Option<usize>,
+    exclusive: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let registers = detail.operands[0..detail.op_count as usize]
+        .iter()
+        .take_while(|operand| operand.type_ == arm64_op_type::ARM64_OP_REG)
+        .map(|operand| get_register(operand.reg()))
+        .collect::<Result<Vec<&AArch64Register>>>()?;
+    let (status, srcs) = if exclusive {
+        (Some(registers[0]), &registers[1..])
+    } else {
+        (None, &registers[..])
+    };
+    let (address, writeback) = load_store_address(instruction)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let address_temp = temp(instruction, 0, 64);
+        block.assign(address_temp.clone(), address);
+
+        let mut offset = 0;
+        for src in srcs {
+            let bits = bits.unwrap_or_else(|| src.bits());
+            let address = if offset == 0 {
+                address_temp.clone().into()
+            } else {
+                Expr::add(address_temp.clone().into(), expr_const(offset, 64))?
+            };
+            block.store(address, resize(src.get()?, bits, false)?);
+            offset += bits as u64 / 8;
+        }
+
+        if let Some(status) = status {
+            status.set(block, expr_const(0, status.bits()))?;
+        }
+
+        if let Some((base, value)) = writeback {
+            base.set(block, value)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn adc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    add_(control_flow_graph, instruction, true)
+}
+
+pub fn add(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    add_(control_flow_graph, instruction, false)
+}
+
+pub fn adr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = expr_const(detail.operands[1].imm() as u64, 64);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        dst.set(block, src)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn and(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    logical_(control_flow_graph, instruction, Expr::and)
+}
+
+pub fn asr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    shift_(control_flow_graph, instruction, Expr::sra)
+}
+
+pub fn b(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Result<()> {
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.nop();
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn bfi(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    bitfield_(control_flow_graph, instruction, |dst, src, lsb, width| {
+        let bits = dst.bits();
+        let field = mask(width) << lsb;
+        Expr::or(
+            Expr::and(dst, expr_const(!field, bits))?,
+            Expr::shl(
+                Expr::and(src, expr_const(mask(width), bits))?,
+                expr_const(lsb, bits),
+            )?,
+        )
+    })
+}
+
+pub fn bfxil(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    bitfield_(control_flow_graph, instruction, |dst, src, lsb, width| {
+        let bits = dst.bits();
+        Expr::or(
+            Expr::and(dst, expr_const(!mask(width), bits))?,
+            Expr::and(
+                Expr::shr(src, expr_const(lsb, bits))?,
+                expr_const(mask(width), bits),
+            )?,
+        )
+    })
+}
+
+pub fn bic(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    logical_(control_flow_graph, instruction, |lhs, rhs| {
+        Expr::and(lhs, not(rhs)?)
+    })
+}
+
+pub fn bl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = expr_const(detail.operands[0].imm() as u64, 64);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(
+            scalar("x30", 64),
+            expr_const(instruction.address + instruction.size as u64, 64),
+        );
+        block.branch(dst);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn blr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.get()?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        // the target may be x30, so read it before setting the link register
+        let target = temp(instruction, 0, 64);
+        block.assign(target.clone(), dst);
+        block.assign(
+            scalar("x30", 64),
+            expr_const(instruction.address + instruction.size as u64, 64),
+        );
+        block.branch(target.into());
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn br(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.get()?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.branch(dst);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn brk(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let intrinsic = Intrinsic::new(
+            "brk",
+            format!("brk {}", instruction.op_str),
+            Vec::new(),
+            Some(Vec::new()),
+            Some(Vec::new()),
+            instruction.bytes.get(0..4).unwrap().to_vec(),
+        );
+
+        block.intrinsic(intrinsic);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn ccmn(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_compare_(control_flow_graph, instruction, true)
+}
+
+pub fn ccmp(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_compare_(control_flow_graph, instruction, false)
+}
+
+pub fn cinc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_op_(control_flow_graph, instruction, |src| {
+        let bits = src.bits();
+        Expr::add(src, expr_const(1, bits))
+    })
+}
+
+pub fn cinv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_op_(control_flow_graph, instruction, not)
+}
+
+pub fn cls(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    unary_(control_flow_graph, instruction, |src| {
+        // count the leading bits of src ^ (src << 1), which are set where
+        // a bit differs from the one below it
+        let bits = src.bits();
+        let differences = Expr::xor(src.clone(), Expr::shl(src, expr_const(1, bits))?)?;
+        count_leading_zeros(Expr::or(differences, expr_const(1, bits))?)
+    })
+}
+
+pub fn clz(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    unary_(control_flow_graph, instruction, count_leading_zeros)
+}
+
+pub fn cmn(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    compare_(control_flow_graph, instruction, true)
+}
+
+pub fn cmp(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    compare_(control_flow_graph, instruction, false)
+}
+
+pub fn cneg(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_op_(control_flow_graph, instruction, |src| {
+        let bits = src.bits();
+        Expr::sub(expr_const(0, bits), src)
+    })
+}
+
+pub fn csel(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_select_(control_flow_graph, instruction, Ok)
+}
+
+pub fn cset(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_set_(control_flow_graph, instruction, 1)
+}
+
+pub fn csetm(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_set_(control_flow_graph, instruction, 0xffff_ffff_ffff_ffff)
+}
+
+pub fn csinc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_select_(control_flow_graph, instruction, |src| {
+        let bits = src.bits();
+        Expr::add(src, expr_const(1, bits))
+    })
+}
+
+pub fn csinv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_select_(control_flow_graph, instruction, not)
+}
+
+pub fn csneg(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    conditional_select_(control_flow_graph, instruction, |src| {
+        let bits = src.bits();
+        Expr::sub(expr_const(0, bits), src)
+    })
+}
+
+pub fn eon(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    logical_(control_flow_graph, instruction, |lhs, rhs| {
+        Expr::xor(lhs, not(rhs)?)
+    })
+}
+
+pub fn eor(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    logical_(control_flow_graph, instruction, Expr::xor)
+}
+
+pub fn extr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let high = get_register(detail.operands[1].reg())?.get()?;
+    let low = get_register(detail.operands[2].reg())?.get()?;
+    let lsb = detail.operands[3].imm() as u64;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let bits = dst.bits();
+        let result = if lsb == 0 {
+            low
+        } else {
+            Expr::or(
+                Expr::shr(low, expr_const(lsb, bits))?,
+                Expr::shl(high, expr_const(bits as u64 - lsb, bits))?,
+            )?
+        };
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn ldp(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    load_(control_flow_graph, instruction, None, false)
+}
+
+pub fn ldpsw(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, Some(32), true)
+}
+
+pub fn ldr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    load_(control_flow_graph, instruction, None, false)
+}
+
+pub fn ldrb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, Some(8), false)
+}
+
+pub fn ldrh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, Some(16), false)
+}
+
+pub fn ldrsb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, Some(8), true)
+}
+
+pub fn ldrsh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, Some(16), true)
+}
+
+pub fn ldrsw(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, Some(32), true)
+}
+
+pub fn lsl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    shift_(control_flow_graph, instruction, Expr::shl)
+}
+
+pub fn lsr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    shift_(control_flow_graph, instruction, Expr::shr)
+}
+
+pub fn madd(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_(control_flow_graph, instruction, false)
+}
+
+pub fn mov(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        dst.set(block, src)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn movk(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+    let field: u64 = 0xffff << detail.operands[1].shift.value;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = Expr::or(Expr::and(dst.get()?, expr_const(!field, dst.bits()))?, src)?;
+        dst.set(block, result)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn movn(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?;
+    let src = operand_value(&detail.operands[1], dst.bits())?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        dst.set(block, not(src)?)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn mneg(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_(control_flow_graph, instruction, true)
+}
+
+pub fn msub(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_(control_flow_graph, instruction, true)
+}
+
+pub fn mul(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    multiply_(control_flow_graph, instruction, false)
+}
+
+pub fn mvn(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    unary_(control_flow_graph, instruction, not)
+}
+
+pub fn neg(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    neg_(control_flow_graph, instruction, false)
+}
+
+pub fn ngc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    neg_(control_flow_graph, instruction, true)
+}
+
+pub fn nop(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Result<()> {
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.nop();
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn orn(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    logical_(control_flow_graph, instruction, |lhs, rhs| {
+        Expr::or(lhs, not(rhs)?)
+    })
+}
+
+pub fn orr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    logical_(control_flow_graph, instruction, Expr::or)
+}
+
+pub fn rbit(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, |src| {
+        let bits = src.bits();
+        let mut result = expr_const(0, bits);
+        for i in 0..bits {
+            let bit = Expr::and(
+                Expr::shr(src.clone(), expr_const(i as u64, bits))?,
+                expr_const(1, bits),
+            )?;
+            result = Expr::or(
+                result,
+                Expr::shl(bit, expr_const((bits - 1 - i) as u64, bits))?,
+            )?;
+        }
+        Ok(result)
+    })
+}
+
+pub fn ret(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = if detail.op_count == 1 {
+        get_register(detail.operands[0].reg())?.get()?
+    } else {
+        expr_scalar("x30", 64)
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.branch(dst);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn rev(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    unary_(control_flow_graph, instruction, |src| {
+        let bits = src.bits();
+        reverse_bytes(src, bits)
+    })
+}
+
+pub fn rev16(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, |src| {
+        reverse_bytes(src, 16)
+    })
+}
+
+pub fn rev32(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, |src| {
+        reverse_bytes(src, 32)
+    })
+}
+
+pub fn ror(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    shift_(control_flow_graph, instruction, rotate_right)
+}
+
+pub fn sbc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    sub_(control_flow_graph, instruction, true)
+}
+
+pub fn sbfiz(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    bitfield_(control_flow_graph, instruction, |dst, src, lsb, width| {
+        let bits = dst.bits();
+        let top = expr_const(bits as u64 - width, bits);
+        Expr::shl(
+            Expr::sra(Expr::shl(src, top.clone())?, top)?,
+            expr_const(lsb, bits),
+        )
+    })
+}
+
+pub fn sbfx(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    bitfield_(control_flow_graph, instruction, |dst, src, lsb, width| {
+        let bits = dst.bits();
+        Expr::sra(
+            Expr::shl(src, expr_const(bits as u64 - lsb - width, bits))?,
+            expr_const(bits as u64 - width, bits),
+        )
+    })
+}
+
+pub fn sdiv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    divide_(control_flow_graph, instruction, true)
+}
+
+pub fn smaddl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, true, false)
+}
+
+pub fn smnegl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, true, true)
+}
+
+pub fn smsubl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, true, true)
+}
+
+pub fn smulh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_high_(control_flow_graph, instruction, true)
+}
+
+pub fn smull(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, true, false)
+}
+
+pub fn stp(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    store_(control_flow_graph, instruction, None, false)
+}
+
+pub fn str(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    store_(control_flow_graph, instruction, None, false)
+}
+
+pub fn strb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, Some(8), false)
+}
+
+pub fn strh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, Some(16), false)
+}
+
+pub fn stxr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, None, true)
+}
+
+pub fn stxrb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, Some(8), true)
+}
+
+pub fn stxrh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, Some(16), true)
+}
+
+pub fn sub(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    sub_(control_flow_graph, instruction, false)
+}
+
+pub fn svc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let intrinsic = Intrinsic::new(
+            "svc",
+            format!("svc {}", instruction.op_str),
+            Vec::new(),
+            Some(Vec::new()),
+            Some(Vec::new()),
+            instruction.bytes.get(0..4).unwrap().to_vec(),
+        );
+
+        block.intrinsic(intrinsic);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn sxtb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, 8, true)
+}
+
+pub fn sxth(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, 16, true)
+}
+
+pub fn sxtw(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, 32, true)
+}
+
+pub fn tst(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let bits = get_register(detail.operands[0].reg())?.bits();
+    let lhs = operand_value(&detail.operands[0], bits)?;
+    let rhs = operand_value(&detail.operands[1], bits)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_flags(block, logical_flags(Expr::and(lhs, rhs)?)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn ubfiz(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    bitfield_(control_flow_graph, instruction, |dst, src, lsb, width| {
+        let bits = dst.bits();
+        Expr::shl(
+            Expr::and(src, expr_const(mask(width), bits))?,
+            expr_const(lsb, bits),
+        )
+    })
+}
+
+pub fn ubfx(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    bitfield_(control_flow_graph, instruction, |dst, src, lsb, width| {
+        let bits = dst.bits();
+        Expr::and(
+            Expr::shr(src, expr_const(lsb, bits))?,
+            expr_const(mask(width), bits),
+        )
+    })
+}
+
+pub fn udiv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    divide_(control_flow_graph, instruction, false)
+}
+
+pub fn umaddl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, false, false)
+}
+
+pub fn umnegl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, false, true)
+}
+
+pub fn umsubl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, false, true)
+}
+
+pub fn umulh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_high_(control_flow_graph, instruction, false)
+}
+
+pub fn umull(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, false, false)
+}
+
+pub fn uxtb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, 8, false)
+}
+
+pub fn uxth(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, 16, false)
+}
+
+pub fn uxtw(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, 32, false)
+}
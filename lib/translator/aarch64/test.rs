@@ -0,0 +1,1053 @@
+use crate::architecture;
+use crate::architecture::Endian;
+use crate::executor::*;
+use crate::il::*;
+use crate::memory;
+use crate::translator::aarch64::*;
+use crate::RC;
+
+macro_rules! backing {
+    ($e: expr) => {{
+        let v: Vec<u8> = $e.to_vec();
+        let mut b = memory::backing::Memory::new(Endian::Little);
+        b.set_memory(0, v, memory::MemoryPermissions::EXECUTE);
+        b
+    }};
+}
+
+fn init_driver_block(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory_: Memory,
+) -> Driver {
+    let mut bytes = instruction_bytes.to_vec();
+    // nop
+    bytes.append(&mut vec![0x1f, 0x20, 0x03, 0xd5]);
+
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        bytes.to_vec(),
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let function = AArch64::new().translate_function(&backing, 0).unwrap();
+
+    let location = if function
+        .control_flow_graph()
+        .block(0)
+        .unwrap()
+        .instructions()
+        .is_empty()
+    {
+        ProgramLocation::new(Some(0), FunctionLocation::EmptyBlock(0))
+    } else {
+        ProgramLocation::new(Some(0), FunctionLocation::Instruction(0, 0))
+    };
+
+    let mut program = Program::new();
+    program.add_function(function);
+
+    let mut state = State::new(memory_);
+    for scalar in scalars {
+        state.set_scalar(scalar.0, scalar.1);
+    }
+
+    Driver::new(
+        RC::new(program),
+        location,
+        state,
+        RC::new(architecture::AArch64::new()),
+    )
+}
+
+fn init_driver_function(
+    backing: memory::backing::Memory,
+    scalars: Vec<(&str, Constant)>,
+) -> Driver {
+    let memory = Memory::new_with_backing(Endian::Little, RC::new(backing));
+
+    let function = AArch64::new().translate_function(&memory, 0).unwrap();
+    let mut program = Program::new();
+
+    program.add_function(function);
+
+    let location = ProgramLocation::new(Some(0), FunctionLocation::Instruction(0, 0));
+
+    let mut state = State::new(memory);
+    for scalar in scalars {
+        state.set_scalar(scalar.0, scalar.1);
+    }
+
+    Driver::new(
+        RC::new(program),
+        location,
+        state,
+        RC::new(architecture::AArch64::new()),
+    )
+}
+
+fn get_scalar(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+    result_scalar: &str,
+) -> Constant {
+    let mut driver = init_driver_block(instruction_bytes, scalars, memory);
+
+    while !driver
+        .location()
+        .apply(driver.program())
+        .unwrap()
+        .forward()
+        .unwrap()
+        .is_empty()
+    {
+        driver = driver.step().unwrap();
+    }
+
+    driver.state().get_scalar(result_scalar).unwrap().clone()
+}
+
+fn get_intrinsic(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+) -> Intrinsic {
+    let mut driver = init_driver_block(instruction_bytes, scalars, memory);
+
+    loop {
+        {
+            let location = driver.location().apply(driver.program()).unwrap();
+            if let Some(instruction) = location.instruction() {
+                if let Operation::Intrinsic { ref intrinsic } = *instruction.operation() {
+                    return intrinsic.clone();
+                }
+            }
+        }
+        driver = driver.step().unwrap();
+    }
+}
+
+fn step_to(mut driver: Driver, target_address: u64) -> Driver {
+    loop {
+        driver = driver.step().unwrap();
+        if let Some(address) = driver.location().apply(driver.program()).unwrap().address() {
+            if address == target_address {
+                return driver;
+            }
+        }
+    }
+}
+
+#[test]
+fn add() {
+    // add x0, x1, x2
+    let instruction_bytes = &[0x20, 0x00, 0x02, 0x8b];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(1, 64)), ("x2", const_(2, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 3);
+}
+
+#[test]
+fn add_extended_register() {
+    // add x0, x1, w2, sxtw #2
+    let instruction_bytes = &[0x20, 0xc8, 0x22, 0x8b];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(0x100, 64)),
+            ("x2", const_(0x1234_5678_ffff_fffe, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf8);
+}
+
+#[test]
+fn adds() {
+    // adds x0, x1, x2
+    let instruction_bytes = &[0x20, 0x00, 0x02, 0xab];
+
+    let scalars = vec![
+        ("x1", const_(0xffff_ffff_ffff_ffff, 64)),
+        ("x2", const_(1, 64)),
+    ];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "x0");
+    assert_eq!(result.value_u64().unwrap(), 0);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "z");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "c");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars, memory.clone(), "v");
+    assert_eq!(result.value_u64().unwrap(), 0);
+
+    let scalars = vec![
+        ("x1", const_(0x7fff_ffff_ffff_ffff, 64)),
+        ("x2", const_(1, 64)),
+    ];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "n");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "c");
+    assert_eq!(result.value_u64().unwrap(), 0);
+    let result = get_scalar(instruction_bytes, scalars, memory, "v");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn adc() {
+    // adc x0, x1, x2
+    let instruction_bytes = &[0x20, 0x00, 0x02, 0x9a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(1, 64)),
+            ("x2", const_(2, 64)),
+            ("c", const_(1, 1)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 4);
+}
+
+#[test]
+fn sub() {
+    // sub w0, w1, w2
+    let instruction_bytes = &[0x20, 0x00, 0x02, 0x4b];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x0", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("x1", const_(1, 64)),
+            ("x2", const_(2, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+}
+
+#[test]
+fn subs() {
+    // subs x0, x1, #1
+    let instruction_bytes = &[0x20, 0x04, 0x00, 0xf1];
+
+    let memory = Memory::new(Endian::Little);
+    let scalars = vec![("x1", const_(0, 64))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "x0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_ffff);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "n");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars, memory.clone(), "c");
+    assert_eq!(result.value_u64().unwrap(), 0);
+
+    let scalars = vec![("x1", const_(1, 64))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "z");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars, memory, "c");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn sbcs() {
+    // sbcs x0, x1, x2
+    let instruction_bytes = &[0x20, 0x00, 0x02, 0xfa];
+
+    let memory = Memory::new(Endian::Little);
+    let scalars = vec![
+        ("x1", const_(5, 64)),
+        ("x2", const_(2, 64)),
+        ("c", const_(0, 1)),
+    ];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "x0");
+    assert_eq!(result.value_u64().unwrap(), 2);
+    let result = get_scalar(instruction_bytes, scalars, memory, "c");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn neg() {
+    // neg x0, x1
+    let instruction_bytes = &[0xe0, 0x03, 0x01, 0xcb];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(1, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_ffff);
+}
+
+#[test]
+fn mov_movk() {
+    // mov x0, #0x1234
+    // movk x0, #0x5678, lsl #16
+    let instruction_bytes = &[0x80, 0x46, 0x82, 0xd2, 0x00, 0xcf, 0xaa, 0xf2];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x0", const_(0xffff_ffff_ffff_ffff, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x5678_1234);
+}
+
+#[test]
+fn movn() {
+    // movn w0, #0
+    let instruction_bytes = &[0x00, 0x00, 0x80, 0x12];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x0", const_(0, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+}
+
+#[test]
+fn mvn() {
+    // mvn x0, x1
+    let instruction_bytes = &[0xe0, 0x03, 0x21, 0xaa];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0xffff_0000_ffff_0000, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x0000_ffff_0000_ffff);
+}
+
+#[test]
+fn eor() {
+    // eor x0, x1, x2, lsr #4
+    let instruction_bytes = &[0x20, 0x10, 0x42, 0xca];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0xff, 64)), ("x2", const_(0xf00, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf);
+}
+
+#[test]
+fn ands() {
+    // ands x0, x1, #0xff
+    let instruction_bytes = &[0x20, 0x1c, 0x40, 0xf2];
+
+    let memory = Memory::new(Endian::Little);
+    let scalars = vec![("x1", const_(0x1200, 64))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "x0");
+    assert_eq!(result.value_u64().unwrap(), 0);
+    let result = get_scalar(instruction_bytes, scalars, memory, "z");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn lsl() {
+    // lsl x0, x1, #4
+    let instruction_bytes = &[0x20, 0xec, 0x7c, 0xd3];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x1234, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x12340);
+}
+
+#[test]
+fn asr() {
+    // asr x0, x1, x2
+    let instruction_bytes = &[0x20, 0x28, 0xc2, 0x9a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(0x8000_0000_0000_0000, 64)),
+            ("x2", const_(68, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf800_0000_0000_0000);
+}
+
+#[test]
+fn ror() {
+    // ror w0, w1, #8
+    let instruction_bytes = &[0x20, 0x20, 0x81, 0x13];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x1234_5678, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x7812_3456);
+}
+
+#[test]
+fn ubfx() {
+    // ubfx x0, x1, #4, #8
+    let instruction_bytes = &[0x20, 0x2c, 0x44, 0xd3];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0xabcd, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xbc);
+}
+
+#[test]
+fn sbfx() {
+    // sbfx x0, x1, #4, #8
+    let instruction_bytes = &[0x20, 0x2c, 0x44, 0x93];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x0f80, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_fff8);
+}
+
+#[test]
+fn bfi() {
+    // bfi x0, x1, #8, #4
+    let instruction_bytes = &[0x20, 0x0c, 0x78, 0xb3];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x0", const_(0xffff, 64)), ("x1", const_(0x35, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf5ff);
+}
+
+#[test]
+fn extr() {
+    // extr x0, x1, x2, #16
+    let instruction_bytes = &[0x20, 0x40, 0xc2, 0x93];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(0x1111_2222_3333_4444, 64)),
+            ("x2", const_(0x5555_6666_7777_8888, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x4444_5555_6666_7777);
+}
+
+#[test]
+fn sxtb() {
+    // sxtb x0, w1
+    let instruction_bytes = &[0x20, 0x1c, 0x40, 0x93];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x180, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_ff80);
+}
+
+#[test]
+fn csel() {
+    // csel x0, x1, x2, lt
+    let instruction_bytes = &[0x20, 0xb0, 0x82, 0x9a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(1, 64)),
+            ("x2", const_(2, 64)),
+            ("n", const_(1, 1)),
+            ("v", const_(0, 1)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(1, 64)),
+            ("x2", const_(2, 64)),
+            ("n", const_(1, 1)),
+            ("v", const_(1, 1)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 2);
+}
+
+#[test]
+fn cset() {
+    // cset w0, ne
+    let instruction_bytes = &[0xe0, 0x07, 0x9f, 0x1a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("z", const_(0, 1))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("z", const_(1, 1))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn ccmp() {
+    // ccmp x0, x1, #0, eq
+    let instruction_bytes = &[0x00, 0x00, 0x41, 0xfa];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x0", const_(5, 64)),
+            ("x1", const_(5, 64)),
+            ("z", const_(1, 1)),
+        ],
+        Memory::new(Endian::Little),
+        "z",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x0", const_(5, 64)),
+            ("x1", const_(5, 64)),
+            ("z", const_(0, 1)),
+        ],
+        Memory::new(Endian::Little),
+        "z",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn madd() {
+    // madd x0, x1, x2, x3
+    let instruction_bytes = &[0x20, 0x0c, 0x02, 0x9b];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(2, 64)),
+            ("x2", const_(3, 64)),
+            ("x3", const_(4, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 10);
+}
+
+#[test]
+fn umulh() {
+    // umulh x0, x1, x2
+    let instruction_bytes = &[0x20, 0x7c, 0xc2, 0x9b];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("x2", const_(2, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn smulh() {
+    // smulh x0, x1, x2
+    let instruction_bytes = &[0x20, 0x7c, 0x42, 0x9b];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x1", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("x2", const_(2, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_ffff);
+}
+
+#[test]
+fn udiv() {
+    // udiv x0, x1, x2
+    let instruction_bytes = &[0x20, 0x08, 0xc2, 0x9a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(7, 64)), ("x2", const_(2, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 3);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(7, 64)), ("x2", const_(0, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn sdiv() {
+    // sdiv w0, w1, w2
+    let instruction_bytes = &[0x20, 0x0c, 0xc2, 0x1a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0xffff_fff9, 64)), ("x2", const_(2, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffd);
+}
+
+#[test]
+fn clz() {
+    // clz x0, x1
+    let instruction_bytes = &[0x20, 0x10, 0xc0, 0xda];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x0000_1000_0000_0000, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 19);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 64);
+}
+
+#[test]
+fn cls() {
+    // cls x0, x1
+    let instruction_bytes = &[0x20, 0x14, 0xc0, 0xda];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0xffff_0000_0000_0000, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 15);
+}
+
+#[test]
+fn rev() {
+    // rev w0, w1
+    let instruction_bytes = &[0x20, 0x08, 0xc0, 0x5a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x1122_3344, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x4433_2211);
+}
+
+#[test]
+fn rbit() {
+    // rbit w0, w1
+    let instruction_bytes = &[0x20, 0x00, 0xc0, 0x5a];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x3, 64))],
+        Memory::new(Endian::Little),
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xc000_0000);
+}
+
+#[test]
+fn ldr_pre_index() {
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1008, const_(0x1122_3344_5566_7788, 64))
+        .unwrap();
+
+    // ldr x0, [x1, #8]!
+    let instruction_bytes = &[0x20, 0x8c, 0x40, 0xf8];
+
+    let scalars = vec![("x1", const_(0x1000, 64))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "x0");
+    assert_eq!(result.value_u64().unwrap(), 0x1122_3344_5566_7788);
+    let result = get_scalar(instruction_bytes, scalars, memory, "x1");
+    assert_eq!(result.value_u64().unwrap(), 0x1008);
+}
+
+#[test]
+fn ldr_post_index() {
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1000, const_(0x1122_3344_5566_7788, 64))
+        .unwrap();
+
+    // ldr x0, [x1], #8
+    let instruction_bytes = &[0x20, 0x84, 0x40, 0xf8];
+
+    let scalars = vec![("x1", const_(0x1000, 64))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "x0");
+    assert_eq!(result.value_u64().unwrap(), 0x1122_3344_5566_7788);
+    let result = get_scalar(instruction_bytes, scalars, memory, "x1");
+    assert_eq!(result.value_u64().unwrap(), 0x1008);
+}
+
+#[test]
+fn ldrb() {
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, const_(0x1122_3344, 32)).unwrap();
+
+    // ldrb w0, [x1, #1]
+    let instruction_bytes = &[0x20, 0x04, 0x40, 0x39];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("x0", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("x1", const_(0x1000, 64)),
+        ],
+        memory,
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x33);
+}
+
+#[test]
+fn ldrsw() {
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, const_(0x8000_0000, 32)).unwrap();
+
+    // ldrsw x0, [x1]
+    let instruction_bytes = &[0x20, 0x00, 0x80, 0xb9];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("x1", const_(0x1000, 64))],
+        memory,
+        "x0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0000);
+}
+
+#[test]
+fn str() {
+    /*
+    str w0, [x1, x2, lsl #2]
+    ret
+    */
+    let instruction_bytes = backing!([0x20, 0x78, 0x22, 0xb8, 0xc0, 0x03, 0x5f, 0xd6]);
+
+    let driver = init_driver_function(
+        instruction_bytes,
+        vec![
+            ("x0", const_(0xdead_beef_1234_5678, 64)),
+            ("x1", const_(0x1000, 64)),
+            ("x2", const_(3, 64)),
+        ],
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver
+            .state()
+            .memory()
+            .load(0x100c, 32)
+            .unwrap()
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x1234_5678
+    );
+}
+
+#[test]
+fn stp_ldp() {
+    /*
+    stp x29, x30, [sp, #-16]!
+    ldp x0, x1, [sp], #16
+    ret
+    */
+    let instruction_bytes =
+        backing!([0xfd, 0x7b, 0xbf, 0xa9, 0xe0, 0x07, 0xc1, 0xa8, 0xc0, 0x03, 0x5f, 0xd6]);
+
+    let driver = init_driver_function(
+        instruction_bytes,
+        vec![
+            ("x29", const_(0x1111, 64)),
+            ("x30", const_(0x2222, 64)),
+            ("sp", const_(0x8000, 64)),
+        ],
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("sp")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x7ff0
+    );
+    assert_eq!(
+        driver
+            .state()
+            .memory()
+            .load(0x7ff8, 64)
+            .unwrap()
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x2222
+    );
+
+    let driver = step_to(driver, 0x8);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x0")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x1111
+    );
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x1")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x2222
+    );
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("sp")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x8000
+    );
+}
+
+#[test]
+fn b_eq() {
+    /*
+    cmp x0, x1
+    b.eq 0xc
+    mov x0, #1
+    ret
+    */
+    let instruction_bytes = [
+        0x1f, 0x00, 0x01, 0xeb, 0x40, 0x00, 0x00, 0x54, 0x20, 0x00, 0x80, 0xd2, 0xc0, 0x03, 0x5f,
+        0xd6,
+    ];
+
+    let driver = init_driver_function(
+        backing!(instruction_bytes),
+        vec![("x0", const_(5, 64)), ("x1", const_(5, 64))],
+    );
+
+    let driver = step_to(driver, 0xc);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x0")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        5
+    );
+
+    let driver = init_driver_function(
+        backing!(instruction_bytes),
+        vec![("x0", const_(5, 64)), ("x1", const_(6, 64))],
+    );
+
+    let driver = step_to(driver, 0xc);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x0")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn cbz() {
+    /*
+    cbz x0, 0x8
+    mov x0, #1
+    ret
+    */
+    let instruction_bytes = [
+        0x40, 0x00, 0x00, 0xb4, 0x20, 0x00, 0x80, 0xd2, 0xc0, 0x03, 0x5f, 0xd6,
+    ];
+
+    let driver = init_driver_function(backing!(instruction_bytes), vec![("x0", const_(0, 64))]);
+
+    let driver = step_to(driver, 0x8);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x0")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0
+    );
+
+    let driver = init_driver_function(backing!(instruction_bytes), vec![("x0", const_(2, 64))]);
+
+    let driver = step_to(driver, 0x8);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x0")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn tbnz() {
+    /*
+    tbnz w0, #3, 0x8
+    mov x0, #1
+    ret
+    */
+    let instruction_bytes = [
+        0x40, 0x00, 0x18, 0x37, 0x20, 0x00, 0x80, 0xd2, 0xc0, 0x03, 0x5f, 0xd6,
+    ];
+
+    let driver = init_driver_function(backing!(instruction_bytes), vec![("x0", const_(8, 64))]);
+
+    let driver = step_to(driver, 0x8);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x0")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        8
+    );
+
+    let driver = init_driver_function(backing!(instruction_bytes), vec![("x0", const_(7, 64))]);
+
+    let driver = step_to(driver, 0x8);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x0")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn bl() {
+    /*
+    bl 0x8
+    ret
+    ret
+    */
+    let instruction_bytes =
+        backing!([0x02, 0x00, 0x00, 0x94, 0xc0, 0x03, 0x5f, 0xd6, 0xc0, 0x03, 0x5f, 0xd6]);
+
+    let driver = init_driver_function(instruction_bytes, vec![]);
+
+    let driver = step_to(driver, 0x8);
+
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("x30")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x4
+    );
+}
+
+#[test]
+fn svc() {
+    // svc #0
+    let instruction_bytes = &[0x01, 0x00, 0x00, 0xd4];
+
+    let intrinsic = get_intrinsic(instruction_bytes, vec![], Memory::new(Endian::Little));
+    assert_eq!(intrinsic.mnemonic(), "svc");
+}
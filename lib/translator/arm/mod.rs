@@ -0,0 +1,656 @@
+//! Capstone-based translator for 32-bit ARM and Thumb.
+//!
+//! Blocks are decoded as Thumb when the lowest bit of their address is set,
+//! and as ARM otherwise. Instructions lifted from Thumb code carry this bit in
+//! their addresses, as do the successors of Thumb blocks, so functions which
+//! interwork are followed into the correct state.
+
+use crate::error::*;
+use crate::il::*;
+use crate::translator::{BlockTranslationResult, Translator};
+use falcon_capstone::capstone;
+use falcon_capstone::capstone_sys::{arm_cc, arm_op_type, arm_reg};
+
+mod mode;
+mod semantics;
+#[cfg(test)]
+mod test;
+
+use self::mode::Mode;
+
+/// The ARM translator.
+#[derive(Clone, Debug, Default)]
+pub struct Arm;
+
+impl Arm {
+    pub fn new() -> Arm {
+        Arm
+    }
+}
+
+impl Translator for Arm {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(bytes, address)
+    }
+
+    fn block_bytes_address(&self, address: u64) -> u64 {
+        address & !1
+    }
+}
+
+fn unhandled_intrinsic(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.intrinsic(Intrinsic::new(
+            instruction.mnemonic.clone(),
+            format!("{} {}", instruction.mnemonic, instruction.op_str),
+            Vec::new(),
+            None,
+            None,
+            instruction
+                .bytes
+                .get(0..instruction.size as usize)
+                .unwrap()
+                .to_vec(),
+        ));
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+fn ensure_block_instruction(control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    let head_block_num_instructions = control_flow_graph
+        .block(control_flow_graph.entry().unwrap())?
+        .instructions()
+        .len();
+
+    if head_block_num_instructions == 0 {
+        let head_index = control_flow_graph.entry().unwrap();
+        control_flow_graph.block_mut(head_index)?.nop();
+    }
+
+    Ok(())
+}
+
+/// Wraps the semantics of an instruction so they only execute when
+/// `condition` holds.
+fn conditional(
+    instruction_graph: &ControlFlowGraph,
+    condition: Expression,
+) -> Result<ControlFlowGraph> {
+    let mut control_flow_graph = ControlFlowGraph::new();
+
+    let head_index = {
+        let block = control_flow_graph.new_block()?;
+        block.nop();
+        block.index()
+    };
+    let tail_index = control_flow_graph.new_block()?.index();
+
+    let (entry, exit) = control_flow_graph.insert(instruction_graph)?;
+
+    control_flow_graph.conditional_edge(
+        head_index,
+        tail_index,
+        Expression::cmpeq(condition.clone(), expr_const(0, 1))?,
+    )?;
+    control_flow_graph.conditional_edge(head_index, entry, condition)?;
+    control_flow_graph.unconditional_edge(exit, tail_index)?;
+
+    control_flow_graph.set_entry(head_index)?;
+    control_flow_graph.set_exit(tail_index)?;
+
+    Ok(control_flow_graph)
+}
+
+/// If this instruction sets a register to a constant, returns that register
+/// and constant.
+///
+/// This is used to resolve the targets of `bx` in veneers such as
+/// `add ip, pc, #1; bx ip`.
+fn constant_register(
+    instruction_id: capstone::arm_insn,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<Option<(arm_reg, u64)>> {
+    let detail = semantics::details(instruction)?;
+    match detail.cc {
+        arm_cc::ARM_CC_AL | arm_cc::ARM_CC_INVALID => {}
+        _ => return Ok(None),
+    }
+
+    let operands = &detail.operands[0..detail.op_count as usize];
+    let is_reg = |operand: &capstone::cs_arm_op, reg| {
+        operand.type_ == arm_op_type::ARM_OP_REG && operand.reg() == reg
+    };
+    let is_imm = |operand: &capstone::cs_arm_op| operand.type_ == arm_op_type::ARM_OP_IMM;
+    let aligned_pc = mode.pc(instruction) & !3;
+
+    Ok(match instruction_id {
+        capstone::arm_insn::ARM_INS_ADR => {
+            let offset = u64::from(operands[1].imm() as u32);
+            Some((operands[0].reg(), aligned_pc.wrapping_add(offset)))
+        }
+        capstone::arm_insn::ARM_INS_ADD | capstone::arm_insn::ARM_INS_ADDW
+            if operands.len() == 3
+                && is_reg(&operands[1], arm_reg::ARM_REG_PC)
+                && is_imm(&operands[2]) =>
+        {
+            let offset = u64::from(operands[2].imm() as u32);
+            Some((operands[0].reg(), aligned_pc.wrapping_add(offset)))
+        }
+        capstone::arm_insn::ARM_INS_SUB | capstone::arm_insn::ARM_INS_SUBW
+            if operands.len() == 3
+                && is_reg(&operands[1], arm_reg::ARM_REG_PC)
+                && is_imm(&operands[2]) =>
+        {
+            let offset = u64::from(operands[2].imm() as u32);
+            Some((operands[0].reg(), aligned_pc.wrapping_sub(offset)))
+        }
+        capstone::arm_insn::ARM_INS_MOV if operands.len() == 2 && is_imm(&operands[1]) => {
+            Some((operands[0].reg(), u64::from(operands[1].imm() as u32)))
+        }
+        _ => None,
+    }
+    .map(|(register, value)| (register, value & 0xffff_ffff)))
+}
+
+/// Returns true if this instruction writes pc, and is not a branch
+/// instruction we handle separately.
+fn writes_pc(instruction_id: capstone::arm_insn, instruction: &capstone::Instr) -> Result<bool> {
+    let detail = semantics::details(instruction)?;
+    let operands = &detail.operands[0..detail.op_count as usize];
+    let is_pc = |operand: &capstone::cs_arm_op| {
+        operand.type_ == arm_op_type::ARM_OP_REG && operand.reg() == arm_reg::ARM_REG_PC
+    };
+
+    Ok(match instruction_id {
+        capstone::arm_insn::ARM_INS_LDM
+        | capstone::arm_insn::ARM_INS_LDMDA
+        | capstone::arm_insn::ARM_INS_LDMDB
+        | capstone::arm_insn::ARM_INS_LDMIB
+        | capstone::arm_insn::ARM_INS_POP => operands.iter().any(is_pc),
+        capstone::arm_insn::ARM_INS_B
+        | capstone::arm_insn::ARM_INS_BL
+        | capstone::arm_insn::ARM_INS_BLX
+        | capstone::arm_insn::ARM_INS_BX
+        | capstone::arm_insn::ARM_INS_CBNZ
+        | capstone::arm_insn::ARM_INS_CBZ
+        | capstone::arm_insn::ARM_INS_CMN
+        | capstone::arm_insn::ARM_INS_CMP
+        | capstone::arm_insn::ARM_INS_PUSH
+        | capstone::arm_insn::ARM_INS_STM
+        | capstone::arm_insn::ARM_INS_STMDA
+        | capstone::arm_insn::ARM_INS_STMDB
+        | capstone::arm_insn::ARM_INS_STMIB
+        | capstone::arm_insn::ARM_INS_STR
+        | capstone::arm_insn::ARM_INS_STRB
+        | capstone::arm_insn::ARM_INS_STRD
+        | capstone::arm_insn::ARM_INS_STREX
+        | capstone::arm_insn::ARM_INS_STREXB
+        | capstone::arm_insn::ARM_INS_STREXH
+        | capstone::arm_insn::ARM_INS_STRH
+        | capstone::arm_insn::ARM_INS_TEQ
+        | capstone::arm_insn::ARM_INS_TST => false,
+        _ => operands.first().map(is_pc).unwrap_or(false),
+    })
+}
+
+fn translate_block(bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+    let mode = Mode::from_address(address);
+
+    let cs = match capstone::Capstone::new(capstone::cs_arch::CS_ARCH_ARM, mode.capstone_mode()) {
+        Ok(cs) => cs,
+        Err(_) => return Err(ErrorKind::CapstoneError.into()),
+    };
+
+    cs.option(
+        capstone::cs_opt_type::CS_OPT_DETAIL,
+        capstone::cs_opt_value::CS_OPT_ON,
+    )
+    .unwrap();
+
+    // Capstone tracks IT blocks between the instructions of a single call to
+    // disasm, so we disassemble everything we were given at once.
+    let instructions = match cs.disasm(bytes, address & !1, 0) {
+        Ok(instructions) => instructions,
+        Err(e) => match e.code() {
+            capstone::cs_err::CS_ERR_OK => return Err(ErrorKind::DisassemblyFailure.into()),
+            _ => return Err(ErrorKind::CapstoneError.into()),
+        },
+    };
+
+    if instructions.count() == 0 {
+        return Err(ErrorKind::DisassemblyFailure.into());
+    }
+
+    // A vec which holds each lifted instruction in this block.
+    let mut block_graphs: Vec<(u64, ControlFlowGraph)> = Vec::new();
+
+    // the length of this block in bytes.
+    let mut length: usize = 0;
+
+    // The successors which exit this block.
+    let mut successors: Vec<(u64, Option<Expression>)> = Vec::new();
+
+    // A register the previous instruction set to a constant.
+    let mut constant: Option<(arm_reg, u64)> = None;
+
+    let mut terminated = false;
+
+    for instruction in instructions.iter() {
+        let instruction_id = match instruction.id {
+            capstone::InstrIdArch::ARM(instruction_id) => instruction_id,
+            _ => bail!("not an ARM instruction"),
+        };
+
+        let detail = semantics::details(&instruction)?;
+        let unconditional = matches!(detail.cc, arm_cc::ARM_CC_AL | arm_cc::ARM_CC_INVALID);
+
+        // The target of a bx we can resolve statically.
+        let bx_target = if instruction_id == capstone::arm_insn::ARM_INS_BX && unconditional {
+            let register = detail.operands[0].reg();
+            if register == arm_reg::ARM_REG_PC {
+                Some(Mode::Arm.tag(mode.pc(&instruction) & !3))
+            } else {
+                match constant {
+                    Some((constant_register, value)) if constant_register == register => {
+                        Some(value)
+                    }
+                    _ => None,
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut instruction_graph = ControlFlowGraph::new();
+
+        match instruction_id {
+            capstone::arm_insn::ARM_INS_ADC => {
+                semantics::adc(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_ADD | capstone::arm_insn::ARM_INS_ADDW => {
+                semantics::add(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_ADR => {
+                semantics::adr(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_AND => {
+                semantics::and(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_ASR => {
+                semantics::asr(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_B
+            | capstone::arm_insn::ARM_INS_CBNZ
+            | capstone::arm_insn::ARM_INS_CBZ
+            | capstone::arm_insn::ARM_INS_CLREX
+            | capstone::arm_insn::ARM_INS_DMB
+            | capstone::arm_insn::ARM_INS_DSB
+            | capstone::arm_insn::ARM_INS_ISB
+            | capstone::arm_insn::ARM_INS_IT
+            | capstone::arm_insn::ARM_INS_NOP
+            | capstone::arm_insn::ARM_INS_PLD
+            | capstone::arm_insn::ARM_INS_PLDW
+            | capstone::arm_insn::ARM_INS_PLI
+            | capstone::arm_insn::ARM_INS_SEV
+            | capstone::arm_insn::ARM_INS_SEVL
+            | capstone::arm_insn::ARM_INS_WFE
+            | capstone::arm_insn::ARM_INS_WFI
+            | capstone::arm_insn::ARM_INS_YIELD => {
+                semantics::nop(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_BFC => {
+                semantics::bfc(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_BFI => {
+                semantics::bfi(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_BIC => {
+                semantics::bic(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_BKPT => {
+                semantics::bkpt(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_BL => {
+                semantics::bl(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_BLX => {
+                semantics::blx(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_BX => match bx_target {
+                Some(_) => semantics::nop(&mut instruction_graph, &instruction, mode),
+                None => semantics::bx(&mut instruction_graph, &instruction, mode),
+            },
+            capstone::arm_insn::ARM_INS_CLZ => {
+                semantics::clz(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_CMN => {
+                semantics::cmn(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_CMP => {
+                semantics::cmp(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_EOR => {
+                semantics::eor(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDM => {
+                semantics::ldm(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDMDA => {
+                semantics::ldmda(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDMDB => {
+                semantics::ldmdb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDMIB => {
+                semantics::ldmib(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDR
+            | capstone::arm_insn::ARM_INS_LDRD
+            | capstone::arm_insn::ARM_INS_LDREX
+            | capstone::arm_insn::ARM_INS_LDRT => {
+                semantics::ldr(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDRB
+            | capstone::arm_insn::ARM_INS_LDRBT
+            | capstone::arm_insn::ARM_INS_LDREXB => {
+                semantics::ldrb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDREXH
+            | capstone::arm_insn::ARM_INS_LDRH
+            | capstone::arm_insn::ARM_INS_LDRHT => {
+                semantics::ldrh(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDRSB | capstone::arm_insn::ARM_INS_LDRSBT => {
+                semantics::ldrsb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LDRSH | capstone::arm_insn::ARM_INS_LDRSHT => {
+                semantics::ldrsh(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LSL => {
+                semantics::lsl(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_LSR => {
+                semantics::lsr(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_MLA => {
+                semantics::mla(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_MLS => {
+                semantics::mls(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_MOV | capstone::arm_insn::ARM_INS_MOVW => {
+                semantics::mov(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_MOVT => {
+                semantics::movt(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_MRS | capstone::arm_insn::ARM_INS_MSR => {
+                unhandled_intrinsic(&mut instruction_graph, &instruction)
+            }
+            capstone::arm_insn::ARM_INS_MUL => {
+                semantics::mul(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_MVN => {
+                semantics::mvn(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_ORN => {
+                semantics::orn(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_ORR => {
+                semantics::orr(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_POP => {
+                semantics::pop(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_PUSH => {
+                semantics::push(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_RBIT => {
+                semantics::rbit(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_REV => {
+                semantics::rev(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_REV16 => {
+                semantics::rev16(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_REVSH => {
+                semantics::revsh(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_ROR => {
+                semantics::ror(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_RRX => {
+                semantics::rrx(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_RSB => {
+                semantics::rsb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_RSC => {
+                semantics::rsc(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SBC => {
+                semantics::sbc(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SBFX => {
+                semantics::sbfx(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SDIV => {
+                semantics::sdiv(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SMLAL => {
+                semantics::smlal(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SMULL => {
+                semantics::smull(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STM => {
+                semantics::stm(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STMDA => {
+                semantics::stmda(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STMDB => {
+                semantics::stmdb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STMIB => {
+                semantics::stmib(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STR
+            | capstone::arm_insn::ARM_INS_STRD
+            | capstone::arm_insn::ARM_INS_STRT => {
+                semantics::str(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STRB | capstone::arm_insn::ARM_INS_STRBT => {
+                semantics::strb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STREX => {
+                semantics::strex(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STREXB => {
+                semantics::strexb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STREXH => {
+                semantics::strexh(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_STRH | capstone::arm_insn::ARM_INS_STRHT => {
+                semantics::strh(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SUB | capstone::arm_insn::ARM_INS_SUBW => {
+                semantics::sub(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SVC => {
+                semantics::svc(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SXTAB => {
+                semantics::sxtab(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SXTAH => {
+                semantics::sxtah(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SXTB => {
+                semantics::sxtb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_SXTH => {
+                semantics::sxth(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_TBB => {
+                semantics::tbb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_TBH => {
+                semantics::tbh(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_TEQ => {
+                semantics::teq(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_TST => {
+                semantics::tst(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UBFX => {
+                semantics::ubfx(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UDF => {
+                semantics::udf(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UDIV => {
+                semantics::udiv(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UMLAL => {
+                semantics::umlal(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UMULL => {
+                semantics::umull(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UXTAB => {
+                semantics::uxtab(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UXTAH => {
+                semantics::uxtah(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UXTB => {
+                semantics::uxtb(&mut instruction_graph, &instruction, mode)
+            }
+            capstone::arm_insn::ARM_INS_UXTH => {
+                semantics::uxth(&mut instruction_graph, &instruction, mode)
+            }
+            _ => {
+                let bytes = instruction
+                    .bytes
+                    .get(0..instruction.size as usize)
+                    .unwrap()
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect::<Vec<String>>()
+                    .join("");
+                return Err(format!(
+                    "Unhandled instruction {} {} {} at 0x{:x}",
+                    bytes, instruction.mnemonic, instruction.op_str, instruction.address
+                )
+                .into());
+            }
+        }?;
+
+        ensure_block_instruction(&mut instruction_graph)?;
+
+        // Conditional branches are handled by the successors of this block,
+        // and the condition on `it` belongs to the instructions which follow
+        // it. Everything else we lift to a conditional graph.
+        let instruction_graph = match instruction_id {
+            capstone::arm_insn::ARM_INS_B
+            | capstone::arm_insn::ARM_INS_CBNZ
+            | capstone::arm_insn::ARM_INS_CBZ
+            | capstone::arm_insn::ARM_INS_IT => instruction_graph,
+            _ if unconditional => instruction_graph,
+            _ => conditional(&instruction_graph, semantics::condition_code(detail.cc)?)?,
+        };
+
+        let mut instruction_graph = instruction_graph;
+        let instruction_address = mode.tag(instruction.address);
+        instruction_graph.set_address(Some(instruction_address));
+        block_graphs.push((instruction_address, instruction_graph));
+
+        length += instruction.size as usize;
+        let next_address = mode.tag((address & !1) + length as u64);
+
+        // instructions that terminate blocks
+        match instruction_id {
+            capstone::arm_insn::ARM_INS_B => {
+                let target = mode.tag(u64::from(detail.operands[0].imm() as u32));
+                if unconditional {
+                    successors.push((target, None));
+                } else {
+                    let condition = semantics::condition_code(detail.cc)?;
+                    successors.push((
+                        next_address,
+                        Some(Expression::cmpeq(condition.clone(), expr_const(0, 1))?),
+                    ));
+                    successors.push((target, Some(condition)));
+                }
+                terminated = true;
+            }
+            capstone::arm_insn::ARM_INS_CBNZ | capstone::arm_insn::ARM_INS_CBZ => {
+                let register = semantics::get_register(detail.operands[0].reg())?;
+                let target = mode.tag(u64::from(detail.operands[1].imm() as u32));
+
+                let zero = Expression::cmpeq(register.expression(), expr_const(0, 32))?;
+                let condition = if instruction_id == capstone::arm_insn::ARM_INS_CBZ {
+                    zero
+                } else {
+                    Expression::cmpeq(zero, expr_const(0, 1))?
+                };
+                successors.push((
+                    next_address,
+                    Some(Expression::cmpeq(condition.clone(), expr_const(0, 1))?),
+                ));
+                successors.push((target, Some(condition)));
+                terminated = true;
+            }
+            capstone::arm_insn::ARM_INS_BX if bx_target.is_some() => {
+                successors.push((bx_target.unwrap(), None));
+                terminated = true;
+            }
+            // instructions without successors
+            capstone::arm_insn::ARM_INS_BX
+            | capstone::arm_insn::ARM_INS_TBB
+            | capstone::arm_insn::ARM_INS_TBH
+                if unconditional =>
+            {
+                terminated = true;
+            }
+            _ => {
+                if unconditional && writes_pc(instruction_id, &instruction)? {
+                    terminated = true;
+                }
+            }
+        }
+
+        if terminated {
+            break;
+        }
+
+        constant = constant_register(instruction_id, &instruction, mode)?;
+    }
+
+    if !terminated {
+        successors.push((mode.tag((address & !1) + length as u64), None));
+    }
+
+    Ok(BlockTranslationResult::new(
+        block_graphs,
+        address,
+        length,
+        successors,
+    ))
+}
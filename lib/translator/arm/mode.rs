@@ -0,0 +1,45 @@
+use falcon_capstone::capstone;
+
+/// The instruction set state an ARM block is decoded in.
+///
+/// Thumb blocks are addressed with the Thumb bit, the lowest bit of the
+/// address, set.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Mode {
+    Arm,
+    Thumb,
+}
+
+impl Mode {
+    /// Returns the mode selected by the lowest bit of a (branch target) address.
+    pub(crate) fn from_address(address: u64) -> Mode {
+        if address & 1 == 1 {
+            Mode::Thumb
+        } else {
+            Mode::Arm
+        }
+    }
+
+    /// Returns `address` tagged with this mode.
+    pub(crate) fn tag(self, address: u64) -> u64 {
+        match self {
+            Mode::Arm => address & !1,
+            Mode::Thumb => address | 1,
+        }
+    }
+
+    /// Returns the value read from pc by an instruction.
+    pub(crate) fn pc(self, instruction: &capstone::Instr) -> u64 {
+        match self {
+            Mode::Arm => instruction.address + 8,
+            Mode::Thumb => instruction.address + 4,
+        }
+    }
+
+    pub(crate) fn capstone_mode(self) -> capstone::cs_mode {
+        match self {
+            Mode::Arm => capstone::CS_MODE_ARM,
+            Mode::Thumb => capstone::CS_MODE_THUMB,
+        }
+    }
+}
@@ -0,0 +1,2365 @@
+use crate::error::*;
+use crate::il::Expression as Expr;
+use crate::il::*;
+use crate::translator::arm::mode::Mode;
+use falcon_capstone::capstone;
+use falcon_capstone::capstone::cs_arm_op;
+use falcon_capstone::capstone_sys::{arm_cc, arm_op_type, arm_reg, arm_shifter};
+
+/// Struct for dealing with ARM registers
+pub struct ArmRegister {
+    name: &'static str,
+    // The capstone enum value for this register.
+    capstone_reg: arm_reg,
+}
+
+impl ArmRegister {
+    /// Returns the scalar for this register.
+    pub fn scalar(&self) -> Scalar {
+        scalar(self.name, 32)
+    }
+
+    /// Returns an expression which evaluates to the value of this register.
+    pub fn expression(&self) -> Expr {
+        expr_scalar(self.name, 32)
+    }
+}
+
+const ARM_REGISTERS: &[ArmRegister] = &[
+    ArmRegister {
+        name: "r0",
+        capstone_reg: arm_reg::ARM_REG_R0,
+    },
+    ArmRegister {
+        name: "r1",
+        capstone_reg: arm_reg::ARM_REG_R1,
+    },
+    ArmRegister {
+        name: "r2",
+        capstone_reg: arm_reg::ARM_REG_R2,
+    },
+    ArmRegister {
+        name: "r3",
+        capstone_reg: arm_reg::ARM_REG_R3,
+    },
+    ArmRegister {
+        name: "r4",
+        capstone_reg: arm_reg::ARM_REG_R4,
+    },
+    ArmRegister {
+        name: "r5",
+        capstone_reg: arm_reg::ARM_REG_R5,
+    },
+    ArmRegister {
+        name: "r6",
+        capstone_reg: arm_reg::ARM_REG_R6,
+    },
+    ArmRegister {
+        name: "r7",
+        capstone_reg: arm_reg::ARM_REG_R7,
+    },
+    ArmRegister {
+        name: "r8",
+        capstone_reg: arm_reg::ARM_REG_R8,
+    },
+    ArmRegister {
+        name: "r9",
+        capstone_reg: arm_reg::ARM_REG_R9,
+    },
+    ArmRegister {
+        name: "r10",
+        capstone_reg: arm_reg::ARM_REG_R10,
+    },
+    ArmRegister {
+        name: "r11",
+        capstone_reg: arm_reg::ARM_REG_R11,
+    },
+    ArmRegister {
+        name: "r12",
+        capstone_reg: arm_reg::ARM_REG_R12,
+    },
+    ArmRegister {
+        name: "sp",
+        capstone_reg: arm_reg::ARM_REG_SP,
+    },
+    ArmRegister {
+        name: "lr",
+        capstone_reg: arm_reg::ARM_REG_LR,
+    },
+];
+
+/// Takes a capstone register enum and returns an `ArmRegister`
+pub fn get_register(capstone_id: arm_reg) -> Result<&'static ArmRegister> {
+    for register in ARM_REGISTERS.iter() {
+        if register.capstone_reg == capstone_id {
+            return Ok(register);
+        }
+    }
+    Err("Could not find register".into())
+}
+
+/// Converts the register of a memory operand to an `arm_reg`.
+///
+/// Capstone 3 gives these as raw integers, and capstone 4 as `arm_reg`.
+fn memory_register<R: Into<arm_reg>>(capstone_id: R) -> arm_reg {
+    capstone_id.into()
+}
+
+/// Returns the details section of an ARM capstone instruction.
+pub fn details(instruction: &capstone::Instr) -> Result<capstone::cs_arm> {
+    let detail = instruction.detail.as_ref().unwrap();
+    match detail.arch {
+        capstone::DetailsArch::ARM(x) => Ok(x),
+        _ => Err("Could not get instruction details".into()),
+    }
+}
+
+/// Generates a temporary scalar unique to this instruction.
+fn temp(instruction: &capstone::Instr, subindex: usize, bits: usize) -> Scalar {
+    Scalar::new(
+        format!("temp_0x{:X}_{}", instruction.address, subindex),
+        bits,
+    )
+}
+
+/// Returns true if this instruction updates the flags.
+///
+/// Capstone sets `update_flags` for adc, sbc and rsc whether or not they
+/// set the flags, so we look for the `s` suffix in the mnemonic instead. No
+/// condition code begins with an `s`.
+fn sets_flags(instruction: &capstone::Instr, detail: &capstone::cs_arm) -> bool {
+    let mnemonic = instruction.mnemonic.split('.').next().unwrap_or("");
+    let mnemonic = match detail.cc {
+        arm_cc::ARM_CC_AL | arm_cc::ARM_CC_INVALID => mnemonic,
+        _ => &mnemonic[0..mnemonic.len().saturating_sub(2)],
+    };
+    mnemonic.ends_with('s')
+}
+
+fn not(expr: Expr) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::xor(expr, expr_const(0xffff_ffff, bits))
+}
+
+/// Returns bit `index` of `expr`.
+fn bit(expr: Expr, index: u64) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::trun(1, Expr::shr(expr, expr_const(index, bits))?)
+}
+
+fn sign_bit(expr: Expr) -> Result<Expr> {
+    let bits = expr.bits() as u64;
+    bit(expr, bits - 1)
+}
+
+fn rotate_right(expr: Expr, amount: Expr) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::or(
+        Expr::shr(expr.clone(), amount.clone())?,
+        Expr::shl(expr, Expr::sub(expr_const(bits as u64, bits), amount)?)?,
+    )
+}
+
+/// Returns the value read from a register. Reads of pc give the address of
+/// the instruction plus 8 in ARM state, and plus 4 in Thumb state.
+fn register_value(capstone_id: arm_reg, instruction: &capstone::Instr, mode: Mode) -> Result<Expr> {
+    if capstone_id == arm_reg::ARM_REG_PC {
+        Ok(expr_const(mode.pc(instruction), 32))
+    } else {
+        Ok(get_register(capstone_id)?.expression())
+    }
+}
+
+/// Writes the result of a data-processing instruction to a register.
+///
+/// Writes to pc become branches. In ARM state these interwork, and in Thumb
+/// state they stay in Thumb state.
+fn set_register(block: &mut Block, capstone_id: arm_reg, value: Expr, mode: Mode) -> Result<()> {
+    if capstone_id == arm_reg::ARM_REG_PC {
+        match mode {
+            Mode::Arm => block.branch(value),
+            Mode::Thumb => block.branch(Expr::or(value, expr_const(1, 32))?),
+        };
+    } else {
+        block.assign(get_register(capstone_id)?.scalar(), value);
+    }
+    Ok(())
+}
+
+/// Writes a loaded value to a register. Loads to pc become branches which
+/// interwork, so the lowest bit of the value selects the new state.
+fn set_register_interworking(block: &mut Block, capstone_id: arm_reg, value: Expr) -> Result<()> {
+    if capstone_id == arm_reg::ARM_REG_PC {
+        block.branch(value);
+    } else {
+        block.assign(get_register(capstone_id)?.scalar(), value);
+    }
+    Ok(())
+}
+
+/// Shifts `value` by an immediate amount, returning the result and the
+/// shifter carry out.
+fn shift_immediate(
+    value: Expr,
+    shift_type: arm_shifter,
+    amount: u64,
+    carry: Expr,
+) -> Result<(Expr, Expr)> {
+    if amount == 0 && shift_type != arm_shifter::ARM_SFT_RRX {
+        return Ok((value, carry));
+    }
+    Ok(match shift_type {
+        arm_shifter::ARM_SFT_LSL => (
+            Expr::shl(value.clone(), expr_const(amount, 32))?,
+            bit(value, 32 - amount)?,
+        ),
+        arm_shifter::ARM_SFT_LSR => {
+            if amount >= 32 {
+                (expr_const(0, 32), sign_bit(value)?)
+            } else {
+                (
+                    Expr::shr(value.clone(), expr_const(amount, 32))?,
+                    bit(value, amount - 1)?,
+                )
+            }
+        }
+        arm_shifter::ARM_SFT_ASR => {
+            if amount >= 32 {
+                (
+                    Expr::sra(value.clone(), expr_const(31, 32))?,
+                    sign_bit(value)?,
+                )
+            } else {
+                (
+                    Expr::sra(value.clone(), expr_const(amount, 32))?,
+                    bit(value, amount - 1)?,
+                )
+            }
+        }
+        arm_shifter::ARM_SFT_ROR => {
+            let amount = amount % 32;
+            if amount == 0 {
+                (value.clone(), sign_bit(value)?)
+            } else {
+                (
+                    rotate_right(value.clone(), expr_const(amount, 32))?,
+                    bit(value, amount - 1)?,
+                )
+            }
+        }
+        arm_shifter::ARM_SFT_RRX => (
+            Expr::or(
+                Expr::shr(value.clone(), expr_const(1, 32))?,
+                Expr::shl(Expr::zext(32, carry)?, expr_const(31, 32))?,
+            )?,
+            bit(value, 0)?,
+        ),
+        arm_shifter::ARM_SFT_INVALID => (value, carry),
+        _ => bail!("Unhandled register shift"),
+    })
+}
+
+/// Shifts `value` by the bottom byte of a register, returning the result and
+/// the shifter carry out.
+fn shift_register(
+    value: Expr,
+    shift_type: arm_shifter,
+    amount: Expr,
+    carry: Expr,
+) -> Result<(Expr, Expr)> {
+    let amount = Expr::and(amount, expr_const(0xff, 32))?;
+    let zero_amount = Expr::cmpeq(amount.clone(), expr_const(0, 32))?;
+    let in_range = Expr::cmpltu(amount.clone(), expr_const(32, 32))?;
+    let dynamic_bit = |index: Expr| Expr::trun(1, Expr::shr(value.clone(), index)?);
+
+    let (result, carry_out) = match shift_type {
+        arm_shifter::ARM_SFT_LSL_REG => (
+            Expr::ite(
+                in_range,
+                Expr::shl(value.clone(), amount.clone())?,
+                expr_const(0, 32),
+            )?,
+            Expr::ite(
+                Expr::cmpltu(amount.clone(), expr_const(33, 32))?,
+                dynamic_bit(Expr::sub(expr_const(32, 32), amount)?)?,
+                expr_const(0, 1),
+            )?,
+        ),
+        arm_shifter::ARM_SFT_LSR_REG => (
+            Expr::ite(
+                in_range,
+                Expr::shr(value.clone(), amount.clone())?,
+                expr_const(0, 32),
+            )?,
+            Expr::ite(
+                Expr::cmpltu(amount.clone(), expr_const(33, 32))?,
+                dynamic_bit(Expr::sub(amount, expr_const(1, 32))?)?,
+                expr_const(0, 1),
+            )?,
+        ),
+        arm_shifter::ARM_SFT_ASR_REG => (
+            Expr::ite(
+                in_range.clone(),
+                Expr::sra(value.clone(), amount.clone())?,
+                Expr::sra(value.clone(), expr_const(31, 32))?,
+            )?,
+            Expr::ite(
+                in_range,
+                dynamic_bit(Expr::sub(amount, expr_const(1, 32))?)?,
+                sign_bit(value.clone())?,
+            )?,
+        ),
+        arm_shifter::ARM_SFT_ROR_REG => {
+            let amount = Expr::and(amount, expr_const(31, 32))?;
+            let zero_rotation = Expr::cmpeq(amount.clone(), expr_const(0, 32))?;
+            (
+                Expr::ite(
+                    zero_rotation.clone(),
+                    value.clone(),
+                    rotate_right(value.clone(), amount.clone())?,
+                )?,
+                Expr::ite(
+                    zero_rotation,
+                    sign_bit(value.clone())?,
+                    dynamic_bit(Expr::sub(amount, expr_const(1, 32))?)?,
+                )?,
+            )
+        }
+        _ => bail!("Unhandled register shift"),
+    };
+
+    Ok((
+        Expr::ite(zero_amount.clone(), value.clone(), result)?,
+        Expr::ite(zero_amount, carry, carry_out)?,
+    ))
+}
+
+/// Returns the carry out of an expanded modified immediate.
+///
+/// Immediates which required a rotation set the carry to their top bit.
+/// Thumb's replicated byte patterns leave it unchanged.
+fn immediate_carry(imm: u32, mode: Mode, carry: Expr) -> Expr {
+    let low = imm & 0xff;
+    let high = imm & 0xff00;
+    let replicated = imm == low | (low << 16)
+        || imm == high | (high << 16)
+        || imm == low.wrapping_mul(0x0101_0101);
+    if imm <= 0xff || (mode == Mode::Thumb && replicated) {
+        carry
+    } else {
+        expr_const(u64::from(imm >> 31), 1)
+    }
+}
+
+/// Returns the value of a register or immediate operand with any shift
+/// applied, along with the shifter carry out.
+fn operand_value_carry(
+    operand: &cs_arm_op,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<(Expr, Expr)> {
+    let carry = expr_scalar("c", 1);
+    match operand.type_ {
+        arm_op_type::ARM_OP_REG => {
+            let value = register_value(operand.reg(), instruction, mode)?;
+            match operand.shift.type_ {
+                arm_shifter::ARM_SFT_ASR_REG
+                | arm_shifter::ARM_SFT_LSL_REG
+                | arm_shifter::ARM_SFT_LSR_REG
+                | arm_shifter::ARM_SFT_ROR_REG => {
+                    let amount =
+                        register_value(arm_reg::from(operand.shift.value), instruction, mode)?;
+                    shift_register(value, operand.shift.type_, amount, carry)
+                }
+                shift_type => {
+                    shift_immediate(value, shift_type, u64::from(operand.shift.value), carry)
+                }
+            }
+        }
+        arm_op_type::ARM_OP_IMM | arm_op_type::ARM_OP_CIMM | arm_op_type::ARM_OP_PIMM => {
+            let imm = operand.imm() as u32;
+            Ok((
+                expr_const(u64::from(imm), 32),
+                immediate_carry(imm, mode, carry),
+            ))
+        }
+        _ => bail!("Invalid operand type"),
+    }
+}
+
+/// Returns the value of a register or immediate operand with any shift
+/// applied.
+fn operand_value(operand: &cs_arm_op, instruction: &capstone::Instr, mode: Mode) -> Result<Expr> {
+    Ok(operand_value_carry(operand, instruction, mode)?.0)
+}
+
+/// Returns the source operands of a data-processing instruction. Thumb's
+/// two operand forms use the destination as the first source.
+fn source_operands(detail: &capstone::cs_arm) -> (&cs_arm_op, &cs_arm_op) {
+    if detail.op_count == 2 {
+        (&detail.operands[0], &detail.operands[1])
+    } else {
+        (&detail.operands[1], &detail.operands[2])
+    }
+}
+
+/// Returns the value of the first source operand of an add or subtract.
+///
+/// When this is pc and the second source is an immediate, the instruction is
+/// a form of adr, which reads pc aligned to 4 bytes.
+fn first_source_value(
+    lhs: &cs_arm_op,
+    rhs: &cs_arm_op,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<Expr> {
+    if lhs.type_ == arm_op_type::ARM_OP_REG
+        && lhs.reg() == arm_reg::ARM_REG_PC
+        && rhs.type_ == arm_op_type::ARM_OP_IMM
+    {
+        Ok(expr_const(mode.pc(instruction) & !3, 32))
+    } else {
+        operand_value(lhs, instruction, mode)
+    }
+}
+
+/// Returns the address of a memory operand, before any post-index.
+///
+/// Loads relative to pc use pc aligned to 4 bytes.
+fn memory_address(operand: &cs_arm_op, instruction: &capstone::Instr, mode: Mode) -> Result<Expr> {
+    let mem = operand.mem();
+    let base = memory_register(mem.base);
+    let base = if base == arm_reg::ARM_REG_PC {
+        expr_const(mode.pc(instruction) & !3, 32)
+    } else {
+        get_register(base)?.expression()
+    };
+    let index = memory_register(mem.index);
+    if index == arm_reg::ARM_REG_INVALID {
+        if mem.disp == 0 {
+            Ok(base)
+        } else {
+            Expr::add(base, expr_const(mem.disp as u32 as u64, 32))
+        }
+    } else {
+        let index = register_value(index, instruction, mode)?;
+        let (index, _) = shift_immediate(
+            index,
+            operand.shift.type_,
+            u64::from(operand.shift.value),
+            expr_const(0, 1),
+        )?;
+        if operand.subtracted {
+            Expr::sub(base, index)
+        } else {
+            Expr::add(base, index)
+        }
+    }
+}
+
+/// Returns the address accessed by a load or store, and the base register
+/// and value to write back to it for pre and post-indexed addressing.
+fn load_store_address(
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<(Expr, Option<(arm_reg, Expr)>)> {
+    let detail = details(instruction)?;
+    let operands = &detail.operands[0..detail.op_count as usize];
+
+    let index = match operands
+        .iter()
+        .position(|operand| operand.type_ == arm_op_type::ARM_OP_MEM)
+    {
+        Some(index) => index,
+        None => bail!("Could not find memory operand"),
+    };
+
+    let operand = &operands[index];
+    let address = memory_address(operand, instruction, mode)?;
+
+    if !detail.writeback {
+        return Ok((address, None));
+    }
+
+    let base = memory_register(operand.mem().base);
+    match operands.get(index + 1) {
+        // post-index, `ldr r0, [r1], #4`
+        Some(post_index) => {
+            let offset = operand_value(post_index, instruction, mode)?;
+            let value = if post_index.subtracted {
+                Expr::sub(get_register(base)?.expression(), offset)?
+            } else {
+                Expr::add(get_register(base)?.expression(), offset)?
+            };
+            Ok((address, Some((base, value))))
+        }
+        // pre-index, `ldr r0, [r1, #4]!`
+        None => Ok((address.clone(), Some((base, address)))),
+    }
+}
+
+/// Returns an expression which evaluates to true when the condition code
+/// holds.
+pub fn condition_code(cc: arm_cc) -> Result<Expr> {
+    let n = expr_scalar("n", 1);
+    let z = expr_scalar("z", 1);
+    let c = expr_scalar("c", 1);
+    let v = expr_scalar("v", 1);
+
+    Ok(match cc {
+        arm_cc::ARM_CC_EQ => z,
+        arm_cc::ARM_CC_NE => not(z)?,
+        arm_cc::ARM_CC_HS => c,
+        arm_cc::ARM_CC_LO => not(c)?,
+        arm_cc::ARM_CC_MI => n,
+        arm_cc::ARM_CC_PL => not(n)?,
+        arm_cc::ARM_CC_VS => v,
+        arm_cc::ARM_CC_VC => not(v)?,
+        arm_cc::ARM_CC_HI => Expr::and(c, not(z)?)?,
+        arm_cc::ARM_CC_LS => Expr::or(not(c)?, z)?,
+        arm_cc::ARM_CC_GE => Expr::cmpeq(n, v)?,
+        arm_cc::ARM_CC_LT => Expr::cmpneq(n, v)?,
+        arm_cc::ARM_CC_GT => Expr::and(not(z)?, Expr::cmpeq(n, v)?)?,
+        arm_cc::ARM_CC_LE => Expr::or(z, Expr::cmpneq(n, v)?)?,
+        arm_cc::ARM_CC_AL | arm_cc::ARM_CC_INVALID => expr_const(1, 1),
+    })
+}
+
+/// Computes `lhs + rhs + carry` into a temporary, and returns that temporary
+/// along with the n, z, c and v flags the addition produces.
+fn add_with_carry(
+    block: &mut Block,
+    instruction: &capstone::Instr,
+    lhs: Expr,
+    rhs: Expr,
+    carry: Expr,
+) -> Result<(Expr, [Expr; 4])> {
+    let result = temp(instruction, 0, 32);
+
+    let sum = Expr::add(lhs.clone(), rhs.clone())?;
+    let sum = if carry == expr_const(0, 1) {
+        sum
+    } else {
+        Expr::add(sum, Expr::zext(32, carry.clone())?)?
+    };
+    block.assign(result.clone(), sum);
+
+    let result: Expr = result.into();
+
+    let n = sign_bit(result.clone())?;
+    let z = Expr::cmpeq(result.clone(), expr_const(0, 32))?;
+    let c = Expr::or(
+        Expr::cmpltu(result.clone(), lhs.clone())?,
+        Expr::and(Expr::cmpeq(result.clone(), lhs.clone())?, carry)?,
+    )?;
+    let v = sign_bit(Expr::and(
+        Expr::xor(lhs, result.clone())?,
+        Expr::xor(rhs, result.clone())?,
+    )?)?;
+
+    Ok((result, [n, z, c, v]))
+}
+
+fn set_flags(block: &mut Block, flags: [Expr; 4]) {
+    let [n, z, c, v] = flags;
+    block.assign(scalar("n", 1), n);
+    block.assign(scalar("z", 1), z);
+    block.assign(scalar("c", 1), c);
+    block.assign(scalar("v", 1), v);
+}
+
+/// Sets the n, z and c flags for a logical operation. v is unchanged.
+fn set_logical_flags(block: &mut Block, result: Expr, carry: Expr) -> Result<()> {
+    block.assign(scalar("n", 1), sign_bit(result.clone())?);
+    block.assign(scalar("z", 1), Expr::cmpeq(result, expr_const(0, 32))?);
+    block.assign(scalar("c", 1), carry);
+    Ok(())
+}
+
+/// Returns the number of leading zero bits in `value`.
+fn count_leading_zeros(value: Expr) -> Result<Expr> {
+    let bits = value.bits();
+    let mut result = expr_const(bits as u64, bits);
+    for i in 0..bits {
+        let bit = bit(value.clone(), i as u64)?;
+        result = Expr::ite(bit, expr_const((bits - 1 - i) as u64, bits), result)?;
+    }
+    Ok(result)
+}
+
+/// Reverses the order of the bytes in each `container` bits of `value`.
+fn reverse_bytes(value: Expr, container: usize) -> Result<Expr> {
+    let bits = value.bits();
+    let container_bytes = container / 8;
+    let mut result = expr_const(0, bits);
+    for i in 0..(bits / 8) {
+        let byte = Expr::and(
+            Expr::shr(value.clone(), expr_const(i as u64 * 8, bits))?,
+            expr_const(0xff, bits),
+        )?;
+        let position =
+            (i / container_bytes) * container_bytes + container_bytes - 1 - (i % container_bytes);
+        result = Expr::or(
+            result,
+            Expr::shl(byte, expr_const(position as u64 * 8, bits))?,
+        )?;
+    }
+    Ok(result)
+}
+
+/// Shared semantics for add, adds, adc, adcs and addw.
+fn add_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    with_carry: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (lhs, rhs) = source_operands(&detail);
+    let lhs = first_source_value(lhs, rhs, instruction, mode)?;
+    let rhs = operand_value(rhs, instruction, mode)?;
+    let carry = if with_carry {
+        expr_scalar("c", 1)
+    } else {
+        expr_const(0, 1)
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let (result, flags) = add_with_carry(block, instruction, lhs, rhs, carry)?;
+        if sets_flags(instruction, &detail) {
+            set_flags(block, flags);
+        }
+        set_register(block, dst, result, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for sub, subs, sbc, sbcs, subw, rsb, rsbs, rsc and rscs.
+fn sub_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    with_carry: bool,
+    reverse: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (lhs, rhs) = source_operands(&detail);
+    let lhs = first_source_value(lhs, rhs, instruction, mode)?;
+    let rhs = operand_value(rhs, instruction, mode)?;
+    let (lhs, rhs) = if reverse { (rhs, lhs) } else { (lhs, rhs) };
+    let carry = if with_carry {
+        expr_scalar("c", 1)
+    } else {
+        expr_const(1, 1)
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let (result, flags) = add_with_carry(block, instruction, lhs, not(rhs)?, carry)?;
+        if sets_flags(instruction, &detail) {
+            set_flags(block, flags);
+        }
+        set_register(block, dst, result, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for cmp and cmn.
+fn compare_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    negative: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let lhs = operand_value(&detail.operands[0], instruction, mode)?;
+    let rhs = operand_value(&detail.operands[1], instruction, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let (_, flags) = if negative {
+            add_with_carry(block, instruction, lhs, rhs, expr_const(0, 1))?
+        } else {
+            add_with_carry(block, instruction, lhs, not(rhs)?, expr_const(1, 1))?
+        };
+        set_flags(block, flags);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for tst and teq.
+fn test_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr, Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let lhs = operand_value(&detail.operands[0], instruction, mode)?;
+    let (rhs, carry) = operand_value_carry(&detail.operands[1], instruction, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = temp(instruction, 0, 32);
+        block.assign(result.clone(), op(lhs, rhs)?);
+        set_logical_flags(block, result.into(), carry)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the logical instructions.
+fn logical_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr, Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (lhs, rhs) = source_operands(&detail);
+    let lhs = operand_value(lhs, instruction, mode)?;
+    let (rhs, carry) = operand_value_carry(rhs, instruction, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let result = op(lhs, rhs)?;
+        if sets_flags(instruction, &detail) {
+            let temp = temp(instruction, 0, 32);
+            block.assign(temp.clone(), result);
+            set_logical_flags(block, temp.clone().into(), carry)?;
+            set_register(block, dst, temp.into(), mode)?;
+        } else {
+            set_register(block, dst, result, mode)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for mov and mvn, which may carry a shift on their source.
+fn move_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    invert: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (src, carry) = operand_value_carry(&detail.operands[1], instruction, mode)?;
+    let src = if invert { not(src)? } else { src };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        if sets_flags(instruction, &detail) {
+            let temp = temp(instruction, 0, 32);
+            block.assign(temp.clone(), src);
+            set_logical_flags(block, temp.clone().into(), carry)?;
+            set_register(block, dst, temp.into(), mode)?;
+        } else {
+            set_register(block, dst, src, mode)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the shift instructions.
+///
+/// Capstone gives the immediate forms with the shift on the source operand,
+/// `lsl r0, r1, #2`, or as a third immediate operand in Thumb-2. The register
+/// forms are `lsl r0, r1, r2`, or `lsls r0, r1` in Thumb.
+fn shift_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    immediate_type: arm_shifter,
+    register_type: arm_shifter,
+) -> Result<()> {
+    let detail = details(instruction)?;
+    let carry = expr_scalar("c", 1);
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (result, carry) =
+        if detail.op_count == 2 && detail.operands[1].shift.type_ != arm_shifter::ARM_SFT_INVALID {
+            operand_value_carry(&detail.operands[1], instruction, mode)?
+        } else {
+            let (src, amount) = source_operands(&detail);
+            let src = operand_value(src, instruction, mode)?;
+            match amount.type_ {
+                arm_op_type::ARM_OP_IMM => {
+                    shift_immediate(src, immediate_type, amount.imm() as u64, carry)?
+                }
+                _ => {
+                    let amount = operand_value(amount, instruction, mode)?;
+                    shift_register(src, register_type, amount, carry)?
+                }
+            }
+        };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        if sets_flags(instruction, &detail) {
+            let temp = temp(instruction, 0, 32);
+            block.assign(temp.clone(), result);
+            set_logical_flags(block, temp.clone().into(), carry)?;
+            set_register(block, dst, temp.into(), mode)?;
+        } else {
+            set_register(block, dst, result, mode)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for mul, mla and mls.
+fn multiply_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    accumulate: Option<bool>,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (lhs, rhs) = source_operands(&detail);
+    let product = Expr::mul(
+        operand_value(lhs, instruction, mode)?,
+        operand_value(rhs, instruction, mode)?,
+    )?;
+    let result = match accumulate {
+        Some(subtract) => {
+            let accumulator = operand_value(&detail.operands[3], instruction, mode)?;
+            if subtract {
+                Expr::sub(accumulator, product)?
+            } else {
+                Expr::add(accumulator, product)?
+            }
+        }
+        None => product,
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let temp = temp(instruction, 0, 32);
+        block.assign(temp.clone(), result);
+        if sets_flags(instruction, &detail) {
+            let temp: Expr = temp.clone().into();
+            block.assign(scalar("n", 1), sign_bit(temp.clone())?);
+            block.assign(scalar("z", 1), Expr::cmpeq(temp, expr_const(0, 32))?);
+        }
+        set_register(block, dst, temp.into(), mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for umull, smull, umlal and smlal.
+fn multiply_long_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    signed: bool,
+    accumulate: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst_lo = get_register(detail.operands[0].reg())?;
+    let dst_hi = get_register(detail.operands[1].reg())?;
+    let lhs = operand_value(&detail.operands[2], instruction, mode)?;
+    let rhs = operand_value(&detail.operands[3], instruction, mode)?;
+    let (lhs, rhs) = if signed {
+        (Expr::sext(64, lhs)?, Expr::sext(64, rhs)?)
+    } else {
+        (Expr::zext(64, lhs)?, Expr::zext(64, rhs)?)
+    };
+    let product = Expr::mul(lhs, rhs)?;
+    let result = if accumulate {
+        let accumulator = Expr::or(
+            Expr::shl(Expr::zext(64, dst_hi.expression())?, expr_const(32, 64))?,
+            Expr::zext(64, dst_lo.expression())?,
+        )?;
+        Expr::add(accumulator, product)?
+    } else {
+        product
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let temp = temp(instruction, 0, 64);
+        block.assign(temp.clone(), result);
+        let temp: Expr = temp.into();
+        if sets_flags(instruction, &detail) {
+            block.assign(scalar("n", 1), sign_bit(temp.clone())?);
+            block.assign(
+                scalar("z", 1),
+                Expr::cmpeq(temp.clone(), expr_const(0, 64))?,
+            );
+        }
+        block.assign(dst_lo.scalar(), Expr::trun(32, temp.clone())?);
+        block.assign(
+            dst_hi.scalar(),
+            Expr::trun(32, Expr::shr(temp, expr_const(32, 64))?)?,
+        );
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for sdiv and udiv. Division by zero gives zero.
+fn divide_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (lhs, rhs) = source_operands(&detail);
+    let lhs = operand_value(lhs, instruction, mode)?;
+    let rhs = operand_value(rhs, instruction, mode)?;
+
+    let quotient = if signed {
+        Expr::divs(lhs, rhs.clone())?
+    } else {
+        Expr::divu(lhs, rhs.clone())?
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_register(
+            block,
+            dst,
+            Expr::ite(
+                Expr::cmpeq(rhs, expr_const(0, 32))?,
+                expr_const(0, 32),
+                quotient,
+            )?,
+            mode,
+        )?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for instructions of the form `op rd, rm`.
+fn unary_<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let src = operand_value(&detail.operands[1], instruction, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_register(block, dst, op(src)?, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the extend instructions, `sxtb rd, rm{, ror #n}`,
+/// and the extend and add instructions, `sxtab rd, rn, rm{, ror #n}`.
+fn extend_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    bits: usize,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let (accumulator, src) = if detail.op_count == 3 {
+        (
+            Some(operand_value(&detail.operands[1], instruction, mode)?),
+            &detail.operands[2],
+        )
+    } else {
+        (None, &detail.operands[1])
+    };
+    let src = Expr::trun(bits, operand_value(src, instruction, mode)?)?;
+    let src = if signed {
+        Expr::sext(32, src)?
+    } else {
+        Expr::zext(32, src)?
+    };
+    let result = match accumulator {
+        Some(accumulator) => Expr::add(accumulator, src)?,
+        None => src,
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_register(block, dst, result, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for ubfx and sbfx.
+fn bitfield_extract_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let src = operand_value(&detail.operands[1], instruction, mode)?;
+    let lsb = detail.operands[2].imm() as u64;
+    let width = detail.operands[3].imm() as usize;
+
+    let field = Expr::trun(width, Expr::shr(src, expr_const(lsb, 32))?)?;
+    let result = if width == 32 {
+        field
+    } else if signed {
+        Expr::sext(32, field)?
+    } else {
+        Expr::zext(32, field)?
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_register(block, dst, result, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the loads of one or two registers.
+///
+/// Loads to pc branch, interworking, once every other register is written.
+fn load_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    bits: usize,
+    signed: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dsts = detail.operands[0..detail.op_count as usize]
+        .iter()
+        .take_while(|operand| operand.type_ == arm_op_type::ARM_OP_REG)
+        .map(|operand| operand.reg())
+        .collect::<Vec<arm_reg>>();
+    let (address, writeback) = load_store_address(instruction, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let address_temp = temp(instruction, 0, 32);
+        block.assign(address_temp.clone(), address);
+
+        let mut values = Vec::new();
+        for (i, dst) in dsts.into_iter().enumerate() {
+            let address = if i == 0 {
+                address_temp.clone().into()
+            } else {
+                Expr::add(address_temp.clone().into(), expr_const(i as u64 * 4, 32))?
+            };
+            let value = temp(instruction, i + 1, bits);
+            block.load(value.clone(), address);
+            let value = if bits == 32 {
+                value.into()
+            } else if signed {
+                Expr::sext(32, value.into())?
+            } else {
+                Expr::zext(32, value.into())?
+            };
+            values.push((dst, value));
+        }
+
+        if let Some((base, value)) = writeback {
+            set_register(block, base, value, mode)?;
+        }
+
+        values.sort_by_key(|(dst, _)| *dst == arm_reg::ARM_REG_PC);
+        for (dst, value) in values {
+            set_register_interworking(block, dst, value)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the stores of one or two registers. Exclusive stores
+/// have a leading status register, which we set to 0 for success.
+fn store_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    bits: usize,
+    exclusive: bool,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let registers = detail.operands[0..detail.op_count as usize]
+        .iter()
+        .take_while(|operand| operand.type_ == arm_op_type::ARM_OP_REG)
+        .map(|operand| operand.reg())
+        .collect::<Vec<arm_reg>>();
+    let (status, srcs) = if exclusive {
+        (Some(registers[0]), &registers[1..])
+    } else {
+        (None, &registers[..])
+    };
+    let (address, writeback) = load_store_address(instruction, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let address_temp = temp(instruction, 0, 32);
+        block.assign(address_temp.clone(), address);
+
+        for (i, src) in srcs.iter().enumerate() {
+            let address = if i == 0 {
+                address_temp.clone().into()
+            } else {
+                Expr::add(address_temp.clone().into(), expr_const(i as u64 * 4, 32))?
+            };
+            let value = register_value(*src, instruction, mode)?;
+            let value = if bits == 32 {
+                value
+            } else {
+                Expr::trun(bits, value)?
+            };
+            block.store(address, value);
+        }
+
+        if let Some(status) = status {
+            set_register(block, status, expr_const(0, 32), mode)?;
+        }
+
+        if let Some((base, value)) = writeback {
+            set_register(block, base, value, mode)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Returns the base register, register list and whether the base is written
+/// back for a load or store multiple.
+fn multiple_operands(
+    instruction: &capstone::Instr,
+    stack: bool,
+) -> Result<(arm_reg, Vec<arm_reg>, bool)> {
+    let detail = details(instruction)?;
+    let operands = detail.operands[0..detail.op_count as usize]
+        .iter()
+        .map(|operand| operand.reg());
+    if stack {
+        Ok((arm_reg::ARM_REG_SP, operands.collect(), true))
+    } else {
+        let mut operands = operands;
+        let base = operands.next().ok_or("Missing base register")?;
+        Ok((base, operands.collect(), detail.writeback))
+    }
+}
+
+/// Returns the lowest address accessed by a load or store multiple of
+/// `count` registers, and the value written back to the base register.
+fn multiple_addresses(
+    base: Expr,
+    count: u64,
+    increment: bool,
+    before: bool,
+) -> Result<(Expr, Expr)> {
+    let size = expr_const(count * 4, 32);
+    Ok(match (increment, before) {
+        (true, false) => (base.clone(), Expr::add(base, size)?),
+        (true, true) => (
+            Expr::add(base.clone(), expr_const(4, 32))?,
+            Expr::add(base, size)?,
+        ),
+        (false, false) => (
+            Expr::add(Expr::sub(base.clone(), size.clone())?, expr_const(4, 32))?,
+            Expr::sub(base, size)?,
+        ),
+        (false, true) => (
+            Expr::sub(base.clone(), size.clone())?,
+            Expr::sub(base, size)?,
+        ),
+    })
+}
+
+/// Shared semantics for ldm, ldmib, ldmda, ldmdb and pop.
+fn load_multiple_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    stack: bool,
+    increment: bool,
+    before: bool,
+) -> Result<()> {
+    let (base, registers, writeback) = multiple_operands(instruction, stack)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let base_temp = temp(instruction, 0, 32);
+        block.assign(base_temp.clone(), get_register(base)?.expression());
+        let (start, end) =
+            multiple_addresses(base_temp.into(), registers.len() as u64, increment, before)?;
+
+        let mut values = Vec::new();
+        for (i, register) in registers.into_iter().enumerate() {
+            let value = temp(instruction, i + 1, 32);
+            block.load(
+                value.clone(),
+                Expr::add(start.clone(), expr_const(i as u64 * 4, 32))?,
+            );
+            values.push((register, value));
+        }
+
+        if writeback {
+            set_register(block, base, end, mode)?;
+        }
+
+        values.sort_by_key(|(register, _)| *register == arm_reg::ARM_REG_PC);
+        for (register, value) in values {
+            set_register_interworking(block, register, value.into())?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for stm, stmib, stmda, stmdb and push.
+fn store_multiple_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    stack: bool,
+    increment: bool,
+    before: bool,
+) -> Result<()> {
+    let (base, registers, writeback) = multiple_operands(instruction, stack)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let base_temp = temp(instruction, 0, 32);
+        block.assign(base_temp.clone(), get_register(base)?.expression());
+        let (start, end) =
+            multiple_addresses(base_temp.into(), registers.len() as u64, increment, before)?;
+
+        for (i, register) in registers.into_iter().enumerate() {
+            block.store(
+                Expr::add(start.clone(), expr_const(i as u64 * 4, 32))?,
+                register_value(register, instruction, mode)?,
+            );
+        }
+
+        if writeback {
+            set_register(block, base, end, mode)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for the instructions which raise an exception, which we
+/// lift to intrinsics.
+fn exception_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    let mnemonic = instruction.mnemonic.split('.').next().unwrap_or("");
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let intrinsic = Intrinsic::new(
+            mnemonic,
+            format!("{} {}", mnemonic, instruction.op_str),
+            Vec::new(),
+            Some(Vec::new()),
+            Some(Vec::new()),
+            instruction
+                .bytes
+                .get(0..instruction.size as usize)
+                .unwrap()
+                .to_vec(),
+        );
+
+        block.intrinsic(intrinsic);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Shared semantics for tbb and tbh.
+fn table_branch_(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    bits: usize,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let mem = detail.operands[0].mem();
+    let base = register_value(memory_register(mem.base), instruction, mode)?;
+    let index = register_value(memory_register(mem.index), instruction, mode)?;
+    let index = if bits == 16 {
+        Expr::shl(index, expr_const(1, 32))?
+    } else {
+        index
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let offset = temp(instruction, 0, bits);
+        block.load(offset.clone(), Expr::add(base, index)?);
+        let target = Expr::add(
+            expr_const(mode.pc(instruction), 32),
+            Expr::shl(Expr::zext(32, offset.into())?, expr_const(1, 32))?,
+        )?;
+        block.branch(Expr::or(target, expr_const(1, 32))?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn adc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    add_(control_flow_graph, instruction, mode, true)
+}
+
+pub fn add(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    add_(control_flow_graph, instruction, mode, false)
+}
+
+pub fn adr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let offset = detail.operands[1].imm() as u32 as u64;
+    let address = (mode.pc(instruction) & !3).wrapping_add(offset);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_register(block, dst, expr_const(address, 32), mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn and(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    logical_(control_flow_graph, instruction, mode, Expr::and)
+}
+
+pub fn asr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    shift_(
+        control_flow_graph,
+        instruction,
+        mode,
+        arm_shifter::ARM_SFT_ASR,
+        arm_shifter::ARM_SFT_ASR_REG,
+    )
+}
+
+pub fn bfc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let lsb = detail.operands[1].imm() as u64;
+    let width = detail.operands[2].imm() as u64;
+    let mask = (((1u64 << width) - 1) << lsb) as u32;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_register(
+            block,
+            dst,
+            Expr::and(
+                get_register(dst)?.expression(),
+                expr_const(u64::from(!mask), 32),
+            )?,
+            mode,
+        )?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn bfi(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let src = operand_value(&detail.operands[1], instruction, mode)?;
+    let lsb = detail.operands[2].imm() as u64;
+    let width = detail.operands[3].imm() as u64;
+    let mask = (((1u64 << width) - 1) << lsb) as u32;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let field = Expr::and(
+            Expr::shl(src, expr_const(lsb, 32))?,
+            expr_const(u64::from(mask), 32),
+        )?;
+        let kept = Expr::and(
+            get_register(dst)?.expression(),
+            expr_const(u64::from(!mask), 32),
+        )?;
+        set_register(block, dst, Expr::or(kept, field)?, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn bic(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    logical_(control_flow_graph, instruction, mode, |lhs, rhs| {
+        Expr::and(lhs, not(rhs)?)
+    })
+}
+
+pub fn bkpt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    _: Mode,
+) -> Result<()> {
+    exception_(control_flow_graph, instruction)
+}
+
+/// Branch with link. The target stays in the current state.
+pub fn bl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let target = mode.tag(detail.operands[0].imm() as u32 as u64);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(
+            scalar("lr", 32),
+            expr_const(mode.tag(instruction.address + instruction.size as u64), 32),
+        );
+        block.branch(expr_const(target, 32));
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Branch with link and exchange. Immediate targets are in the other state,
+/// and the lowest bit of register targets selects the state.
+pub fn blx(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let target = match detail.operands[0].type_ {
+        arm_op_type::ARM_OP_REG => register_value(detail.operands[0].reg(), instruction, mode)?,
+        _ => {
+            let target = detail.operands[0].imm() as u32 as u64;
+            match mode {
+                Mode::Arm => expr_const(Mode::Thumb.tag(target), 32),
+                Mode::Thumb => expr_const(Mode::Arm.tag(target), 32),
+            }
+        }
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        // the target may be lr, so read it before setting the link register
+        let target_temp = temp(instruction, 0, 32);
+        block.assign(target_temp.clone(), target);
+        block.assign(
+            scalar("lr", 32),
+            expr_const(mode.tag(instruction.address + instruction.size as u64), 32),
+        );
+        block.branch(target_temp.into());
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Branch and exchange to a register. The lowest bit of the target selects
+/// the state.
+pub fn bx(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let target = register_value(detail.operands[0].reg(), instruction, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.branch(target);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn clz(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, mode, count_leading_zeros)
+}
+
+pub fn cmn(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    compare_(control_flow_graph, instruction, mode, true)
+}
+
+pub fn cmp(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    compare_(control_flow_graph, instruction, mode, false)
+}
+
+pub fn eor(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    logical_(control_flow_graph, instruction, mode, Expr::xor)
+}
+
+pub fn ldm(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_multiple_(control_flow_graph, instruction, mode, false, true, false)
+}
+
+pub fn ldmda(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_multiple_(control_flow_graph, instruction, mode, false, false, false)
+}
+
+pub fn ldmdb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_multiple_(control_flow_graph, instruction, mode, false, false, true)
+}
+
+pub fn ldmib(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_multiple_(control_flow_graph, instruction, mode, false, true, true)
+}
+
+pub fn ldr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, mode, 32, false)
+}
+
+pub fn ldrb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, mode, 8, false)
+}
+
+pub fn ldrh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, mode, 16, false)
+}
+
+pub fn ldrsb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, mode, 8, true)
+}
+
+pub fn ldrsh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_(control_flow_graph, instruction, mode, 16, true)
+}
+
+pub fn lsl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    shift_(
+        control_flow_graph,
+        instruction,
+        mode,
+        arm_shifter::ARM_SFT_LSL,
+        arm_shifter::ARM_SFT_LSL_REG,
+    )
+}
+
+pub fn lsr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    shift_(
+        control_flow_graph,
+        instruction,
+        mode,
+        arm_shifter::ARM_SFT_LSR,
+        arm_shifter::ARM_SFT_LSR_REG,
+    )
+}
+
+pub fn mla(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    multiply_(control_flow_graph, instruction, mode, Some(false))
+}
+
+pub fn mls(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    multiply_(control_flow_graph, instruction, mode, Some(true))
+}
+
+pub fn mov(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    move_(control_flow_graph, instruction, mode, false)
+}
+
+pub fn movt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let imm = u64::from(detail.operands[1].imm() as u32 & 0xffff);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        set_register(
+            block,
+            dst,
+            Expr::or(
+                Expr::and(get_register(dst)?.expression(), expr_const(0xffff, 32))?,
+                expr_const(imm << 16, 32),
+            )?,
+            mode,
+        )?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn mul(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    multiply_(control_flow_graph, instruction, mode, None)
+}
+
+pub fn mvn(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    move_(control_flow_graph, instruction, mode, true)
+}
+
+pub fn nop(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr, _: Mode) -> Result<()> {
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.nop();
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn orn(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    logical_(control_flow_graph, instruction, mode, |lhs, rhs| {
+        Expr::or(lhs, not(rhs)?)
+    })
+}
+
+pub fn orr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    logical_(control_flow_graph, instruction, mode, Expr::or)
+}
+
+pub fn pop(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    load_multiple_(control_flow_graph, instruction, mode, true, true, false)
+}
+
+pub fn push(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_multiple_(control_flow_graph, instruction, mode, true, false, true)
+}
+
+pub fn rbit(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, mode, |src| {
+        let mut result = expr_const(0, 32);
+        for i in 0..32 {
+            result = Expr::or(
+                result,
+                Expr::shl(
+                    Expr::zext(32, bit(src.clone(), i)?)?,
+                    expr_const(31 - i, 32),
+                )?,
+            )?;
+        }
+        Ok(result)
+    })
+}
+
+pub fn rev(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, mode, |src| {
+        reverse_bytes(src, 32)
+    })
+}
+
+pub fn rev16(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, mode, |src| {
+        reverse_bytes(src, 16)
+    })
+}
+
+pub fn revsh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    unary_(control_flow_graph, instruction, mode, |src| {
+        Expr::sext(32, reverse_bytes(Expr::trun(16, src)?, 16)?)
+    })
+}
+
+pub fn ror(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    shift_(
+        control_flow_graph,
+        instruction,
+        mode,
+        arm_shifter::ARM_SFT_ROR,
+        arm_shifter::ARM_SFT_ROR_REG,
+    )
+}
+
+pub fn rrx(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = detail.operands[0].reg();
+    let src = operand_value(&detail.operands[1], instruction, mode)?;
+    let (result, carry) = shift_immediate(src, arm_shifter::ARM_SFT_RRX, 1, expr_scalar("c", 1))?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        if sets_flags(instruction, &detail) {
+            let temp = temp(instruction, 0, 32);
+            block.assign(temp.clone(), result);
+            set_logical_flags(block, temp.clone().into(), carry)?;
+            set_register(block, dst, temp.into(), mode)?;
+        } else {
+            set_register(block, dst, result, mode)?;
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn rsb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    sub_(control_flow_graph, instruction, mode, false, true)
+}
+
+pub fn rsc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    sub_(control_flow_graph, instruction, mode, true, true)
+}
+
+pub fn sbc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    sub_(control_flow_graph, instruction, mode, true, false)
+}
+
+pub fn sbfx(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    bitfield_extract_(control_flow_graph, instruction, mode, true)
+}
+
+pub fn sdiv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    divide_(control_flow_graph, instruction, mode, true)
+}
+
+pub fn smlal(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, mode, true, true)
+}
+
+pub fn smull(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, mode, true, false)
+}
+
+pub fn stm(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_multiple_(control_flow_graph, instruction, mode, false, true, false)
+}
+
+pub fn stmda(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_multiple_(control_flow_graph, instruction, mode, false, false, false)
+}
+
+pub fn stmdb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_multiple_(control_flow_graph, instruction, mode, false, false, true)
+}
+
+pub fn stmib(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_multiple_(control_flow_graph, instruction, mode, false, true, true)
+}
+
+pub fn str(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, mode, 32, false)
+}
+
+pub fn strb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, mode, 8, false)
+}
+
+pub fn strex(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, mode, 32, true)
+}
+
+pub fn strexb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, mode, 8, true)
+}
+
+pub fn strexh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, mode, 16, true)
+}
+
+pub fn strh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    store_(control_flow_graph, instruction, mode, 16, false)
+}
+
+pub fn sub(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    sub_(control_flow_graph, instruction, mode, false, false)
+}
+
+pub fn svc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    _: Mode,
+) -> Result<()> {
+    exception_(control_flow_graph, instruction)
+}
+
+pub fn sxtab(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 8, true)
+}
+
+pub fn sxtah(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 16, true)
+}
+
+pub fn sxtb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 8, true)
+}
+
+pub fn sxth(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 16, true)
+}
+
+pub fn tbb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    table_branch_(control_flow_graph, instruction, mode, 8)
+}
+
+pub fn tbh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    table_branch_(control_flow_graph, instruction, mode, 16)
+}
+
+pub fn teq(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    test_(control_flow_graph, instruction, mode, Expr::xor)
+}
+
+pub fn tst(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    test_(control_flow_graph, instruction, mode, Expr::and)
+}
+
+pub fn ubfx(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    bitfield_extract_(control_flow_graph, instruction, mode, false)
+}
+
+pub fn udf(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    _: Mode,
+) -> Result<()> {
+    exception_(control_flow_graph, instruction)
+}
+
+pub fn udiv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    divide_(control_flow_graph, instruction, mode, false)
+}
+
+pub fn umlal(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, mode, false, true)
+}
+
+pub fn umull(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    multiply_long_(control_flow_graph, instruction, mode, false, false)
+}
+
+pub fn uxtab(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 8, false)
+}
+
+pub fn uxtah(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 16, false)
+}
+
+pub fn uxtb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 8, false)
+}
+
+pub fn uxth(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    extend_(control_flow_graph, instruction, mode, 16, false)
+}
@@ -0,0 +1,1010 @@
+use crate::architecture;
+use crate::architecture::Endian;
+use crate::executor::*;
+use crate::il::*;
+use crate::memory;
+use crate::translator::arm::*;
+use crate::RC;
+
+fn init_driver_block(
+    instruction_bytes: &[u8],
+    thumb: bool,
+    scalars: Vec<(&str, Constant)>,
+    memory_: Memory,
+) -> Driver {
+    let mut bytes = instruction_bytes.to_vec();
+    // nop
+    let address = if thumb {
+        bytes.append(&mut vec![0x00, 0xbf]);
+        1
+    } else {
+        bytes.append(&mut vec![0x00, 0xf0, 0x20, 0xe3]);
+        0
+    };
+
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        bytes.to_vec(),
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let function = Arm::new().translate_function(&backing, address).unwrap();
+
+    let location = if function
+        .control_flow_graph()
+        .block(0)
+        .unwrap()
+        .instructions()
+        .is_empty()
+    {
+        ProgramLocation::new(Some(0), FunctionLocation::EmptyBlock(0))
+    } else {
+        ProgramLocation::new(Some(0), FunctionLocation::Instruction(0, 0))
+    };
+
+    let mut program = Program::new();
+    program.add_function(function);
+
+    let mut state = State::new(memory_);
+    for scalar in scalars {
+        state.set_scalar(scalar.0, scalar.1);
+    }
+
+    Driver::new(
+        RC::new(program),
+        location,
+        state,
+        RC::new(architecture::Arm::new()),
+    )
+}
+
+fn init_driver_function(
+    backing: memory::backing::Memory,
+    address: u64,
+    scalars: Vec<(&str, Constant)>,
+) -> Driver {
+    let memory = Memory::new_with_backing(Endian::Little, RC::new(backing));
+
+    let function = Arm::new().translate_function(&memory, address).unwrap();
+    let mut program = Program::new();
+
+    program.add_function(function);
+
+    let location = ProgramLocation::new(Some(0), FunctionLocation::Instruction(0, 0));
+
+    let mut state = State::new(memory);
+    for scalar in scalars {
+        state.set_scalar(scalar.0, scalar.1);
+    }
+
+    Driver::new(
+        RC::new(program),
+        location,
+        state,
+        RC::new(architecture::Arm::new()),
+    )
+}
+
+fn run(mut driver: Driver) -> Driver {
+    while !driver
+        .location()
+        .apply(driver.program())
+        .unwrap()
+        .forward()
+        .unwrap()
+        .is_empty()
+    {
+        driver = driver.step().unwrap();
+    }
+    driver
+}
+
+fn get_scalar(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+    result_scalar: &str,
+) -> Constant {
+    let driver = run(init_driver_block(instruction_bytes, false, scalars, memory));
+    driver.state().get_scalar(result_scalar).unwrap().clone()
+}
+
+fn get_scalar_thumb(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+    result_scalar: &str,
+) -> Constant {
+    let driver = run(init_driver_block(instruction_bytes, true, scalars, memory));
+    driver.state().get_scalar(result_scalar).unwrap().clone()
+}
+
+fn get_state(instruction_bytes: &[u8], scalars: Vec<(&str, Constant)>, memory: Memory) -> State {
+    let driver = run(init_driver_block(instruction_bytes, false, scalars, memory));
+    driver.state().clone()
+}
+
+fn get_intrinsic(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+) -> Intrinsic {
+    let mut driver = init_driver_block(instruction_bytes, false, scalars, memory);
+
+    loop {
+        {
+            let location = driver.location().apply(driver.program()).unwrap();
+            if let Some(instruction) = location.instruction() {
+                if let Operation::Intrinsic { ref intrinsic } = *instruction.operation() {
+                    return intrinsic.clone();
+                }
+            }
+        }
+        driver = driver.step().unwrap();
+    }
+}
+
+fn step_to(mut driver: Driver, target_address: u64) -> Driver {
+    loop {
+        driver = driver.step().unwrap();
+        if let Some(address) = driver.location().apply(driver.program()).unwrap().address() {
+            if address == target_address {
+                return driver;
+            }
+        }
+    }
+}
+
+#[test]
+fn add() {
+    // add r0, r1, r2
+    let instruction_bytes = &[0x02, 0x00, 0x81, 0xe0];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(1, 32)), ("r2", const_(2, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 3);
+}
+
+#[test]
+fn adds() {
+    // adds r0, r1, r2
+    let instruction_bytes = &[0x02, 0x00, 0x91, 0xe0];
+
+    let scalars = vec![("r1", const_(0xffff_ffff, 32)), ("r2", const_(1, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "z");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "c");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars, memory.clone(), "v");
+    assert_eq!(result.value_u64().unwrap(), 0);
+
+    let scalars = vec![("r1", const_(0x7fff_ffff, 32)), ("r2", const_(1, 32))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "n");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "c");
+    assert_eq!(result.value_u64().unwrap(), 0);
+    let result = get_scalar(instruction_bytes, scalars, memory, "v");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn adc() {
+    // adc r0, r1, r2
+    let instruction_bytes = &[0x02, 0x00, 0xa1, 0xe0];
+
+    let scalars = vec![
+        ("r1", const_(1, 32)),
+        ("r2", const_(2, 32)),
+        ("c", const_(1, 1)),
+        ("z", const_(0, 1)),
+    ];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 4);
+    // adc does not set the flags
+    let result = get_scalar(instruction_bytes, scalars, memory, "z");
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn subs() {
+    // subs r0, r1, r2
+    let instruction_bytes = &[0x02, 0x00, 0x51, 0xe0];
+
+    let scalars = vec![("r1", const_(1, 32)), ("r2", const_(2, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "n");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars, memory.clone(), "c");
+    assert_eq!(result.value_u64().unwrap(), 0);
+
+    let scalars = vec![("r1", const_(2, 32)), ("r2", const_(1, 32))];
+    let result = get_scalar(instruction_bytes, scalars, memory, "c");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn rsb() {
+    // rsb r0, r1, #0
+    let instruction_bytes = &[0x00, 0x00, 0x61, 0xe2];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(5, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffb);
+}
+
+#[test]
+fn cmp() {
+    // cmp r0, r1
+    let instruction_bytes = &[0x01, 0x00, 0x50, 0xe1];
+
+    let scalars = vec![("r0", const_(7, 32)), ("r1", const_(7, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "z");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(instruction_bytes, scalars, memory, "c");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn tst() {
+    // tst r0, #1
+    let instruction_bytes = &[0x01, 0x00, 0x10, 0xe3];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r0", const_(2, 32)), ("c", const_(0, 1))],
+        Memory::new(Endian::Little),
+        "z",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn and_shifted_register() {
+    // and r0, r1, r2, lsl #4
+    let instruction_bytes = &[0x02, 0x02, 0x01, 0xe0];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0xff0, 32)), ("r2", const_(0x0f, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf0);
+}
+
+#[test]
+fn orr_register_shifted_register() {
+    // orr r0, r1, r2, lsr r3
+    let instruction_bytes = &[0x32, 0x03, 0x81, 0xe1];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("r1", const_(1, 32)),
+            ("r2", const_(0x100, 32)),
+            ("r3", const_(4, 32)),
+        ],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x11);
+}
+
+#[test]
+fn bic() {
+    // bic r0, r1, #255
+    let instruction_bytes = &[0xff, 0x00, 0xc1, 0xe3];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x1234, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1200);
+}
+
+#[test]
+fn mvn() {
+    // mvn r0, r1
+    let instruction_bytes = &[0x01, 0x00, 0xe0, 0xe1];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+}
+
+#[test]
+fn mov() {
+    // mov r0, #4096
+    let instruction_bytes = &[0x01, 0x0a, 0xa0, 0xe3];
+
+    let result = get_scalar(instruction_bytes, vec![], Memory::new(Endian::Little), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0x1000);
+}
+
+#[test]
+fn movw_movt() {
+    // movw r0, #0x1234
+    // movt r0, #0x5678
+    let instruction_bytes = &[0x34, 0x02, 0x01, 0xe3, 0x78, 0x06, 0x45, 0xe3];
+
+    let result = get_scalar(instruction_bytes, vec![], Memory::new(Endian::Little), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0x5678_1234);
+}
+
+#[test]
+fn lsl() {
+    // lsl r0, r1, #3
+    let instruction_bytes = &[0x81, 0x01, 0xa0, 0xe1];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x11, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x88);
+}
+
+#[test]
+fn asr() {
+    // asr r0, r1, r2
+    let instruction_bytes = &[0x51, 0x02, 0xa0, 0xe1];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x8000_0000, 32)), ("r2", const_(4, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf800_0000);
+}
+
+#[test]
+fn mla() {
+    // mla r0, r1, r2, r3
+    let instruction_bytes = &[0x91, 0x32, 0x20, 0xe0];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![
+            ("r1", const_(3, 32)),
+            ("r2", const_(4, 32)),
+            ("r3", const_(5, 32)),
+        ],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 17);
+}
+
+#[test]
+fn umull() {
+    // umull r0, r1, r2, r3
+    let instruction_bytes = &[0x92, 0x03, 0x81, 0xe0];
+
+    let scalars = vec![("r2", const_(0xffff_ffff, 32)), ("r3", const_(2, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffe);
+    let result = get_scalar(instruction_bytes, scalars, memory, "r1");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn smull() {
+    // smull r0, r1, r2, r3
+    let instruction_bytes = &[0x92, 0x03, 0xc1, 0xe0];
+
+    let scalars = vec![("r2", const_(0xffff_ffff, 32)), ("r3", const_(2, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffe);
+    let result = get_scalar(instruction_bytes, scalars, memory, "r1");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+}
+
+#[test]
+fn clz() {
+    // clz r0, r1
+    let instruction_bytes = &[0x11, 0x0f, 0x6f, 0xe1];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x00f0_0000, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 8);
+}
+
+#[test]
+fn rev() {
+    // rev r0, r1
+    let instruction_bytes = &[0x31, 0x0f, 0xbf, 0xe6];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x1234_5678, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x7856_3412);
+}
+
+#[test]
+fn uxtb() {
+    // uxtb r0, r1
+    let instruction_bytes = &[0x71, 0x00, 0xef, 0xe6];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x1234, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x34);
+}
+
+#[test]
+fn sxth() {
+    // sxth r0, r1
+    let instruction_bytes = &[0x71, 0x00, 0xbf, 0xe6];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x8000, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_8000);
+}
+
+#[test]
+fn ubfx() {
+    // ubfx r0, r1, #4, #8
+    let instruction_bytes = &[0x51, 0x02, 0xe7, 0xe7];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x1234, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x23);
+}
+
+#[test]
+fn sbfx() {
+    // sbfx r0, r1, #4, #8
+    let instruction_bytes = &[0x51, 0x02, 0xa7, 0xe7];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0xf00, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fff0);
+}
+
+#[test]
+fn bfi() {
+    // bfi r0, r1, #8, #4
+    let instruction_bytes = &[0x11, 0x04, 0xcb, 0xe7];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r0", const_(0xffff_ffff, 32)), ("r1", const_(0x5, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_f5ff);
+}
+
+#[test]
+fn bfc() {
+    // bfc r0, #8, #4
+    let instruction_bytes = &[0x1f, 0x04, 0xcb, 0xe7];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r0", const_(0xffff_ffff, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_f0ff);
+}
+
+#[test]
+fn ldr() {
+    // ldr r0, [r1, #4]
+    let instruction_bytes = &[0x04, 0x00, 0x91, 0xe5];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1004, const_(0xdead_beef, 32)).unwrap();
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x1000, 32))],
+        memory,
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xdead_beef);
+}
+
+#[test]
+fn ldr_post_index() {
+    // ldr r0, [r1], #4
+    let instruction_bytes = &[0x04, 0x00, 0x91, 0xe4];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, const_(0xdead_beef, 32)).unwrap();
+
+    let scalars = vec![("r1", const_(0x1000, 32))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0xdead_beef);
+    let result = get_scalar(instruction_bytes, scalars, memory, "r1");
+    assert_eq!(result.value_u64().unwrap(), 0x1004);
+}
+
+#[test]
+fn ldr_pre_index() {
+    // ldr r0, [r1, #4]!
+    let instruction_bytes = &[0x04, 0x00, 0xb1, 0xe5];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1004, const_(0xdead_beef, 32)).unwrap();
+
+    let scalars = vec![("r1", const_(0x1000, 32))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0xdead_beef);
+    let result = get_scalar(instruction_bytes, scalars, memory, "r1");
+    assert_eq!(result.value_u64().unwrap(), 0x1004);
+}
+
+#[test]
+fn ldr_literal() {
+    // ldr r0, [pc, #4]
+    let instruction_bytes = &[0x04, 0x00, 0x9f, 0xe5];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0xc, const_(0x1234_5678, 32)).unwrap();
+
+    let result = get_scalar(instruction_bytes, vec![], memory, "r0");
+    assert_eq!(result.value_u64().unwrap(), 0x1234_5678);
+}
+
+#[test]
+fn ldrb_register_offset() {
+    // ldrb r0, [r1, r2]
+    let instruction_bytes = &[0x02, 0x00, 0xd1, 0xe7];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1003, const_(0xaa, 8)).unwrap();
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x1000, 32)), ("r2", const_(3, 32))],
+        memory,
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xaa);
+}
+
+#[test]
+fn ldrsh() {
+    // ldrsh r0, [r1]
+    let instruction_bytes = &[0xf0, 0x00, 0xd1, 0xe1];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, const_(0x8001, 16)).unwrap();
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r1", const_(0x1000, 32))],
+        memory,
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_8001);
+}
+
+#[test]
+fn ldrd() {
+    // ldrd r2, r3, [r1]
+    let instruction_bytes = &[0xd0, 0x20, 0xc1, 0xe1];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, const_(0x1111_1111, 32)).unwrap();
+    memory.store(0x1004, const_(0x2222_2222, 32)).unwrap();
+
+    let scalars = vec![("r1", const_(0x1000, 32))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r2");
+    assert_eq!(result.value_u64().unwrap(), 0x1111_1111);
+    let result = get_scalar(instruction_bytes, scalars, memory, "r3");
+    assert_eq!(result.value_u64().unwrap(), 0x2222_2222);
+}
+
+#[test]
+fn str() {
+    // str r0, [r1, #-4]
+    let instruction_bytes = &[0x04, 0x00, 0x01, 0xe5];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r0", const_(0xdead_beef, 32)), ("r1", const_(0x1004, 32))],
+        Memory::new(Endian::Little),
+    );
+    let result = state.memory().load(0x1000, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0xdead_beef);
+}
+
+#[test]
+fn strh() {
+    // strh r0, [r1, #2]
+    let instruction_bytes = &[0xb2, 0x00, 0xc1, 0xe1];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r0", const_(0xdead_beef, 32)), ("r1", const_(0x1000, 32))],
+        Memory::new(Endian::Little),
+    );
+    let result = state.memory().load(0x1002, 16).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0xbeef);
+}
+
+#[test]
+fn push() {
+    // push {r4, lr}
+    let instruction_bytes = &[0x10, 0x40, 0x2d, 0xe9];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![
+            ("sp", const_(0x2000, 32)),
+            ("r4", const_(1, 32)),
+            ("lr", const_(2, 32)),
+        ],
+        Memory::new(Endian::Little),
+    );
+    assert_eq!(state.get_scalar("sp").unwrap().value_u64().unwrap(), 0x1ff8);
+    let result = state.memory().load(0x1ff8, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = state.memory().load(0x1ffc, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 2);
+}
+
+#[test]
+fn ldm_writeback() {
+    // ldm r0!, {r1, r2}
+    let instruction_bytes = &[0x06, 0x00, 0xb0, 0xe8];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, const_(0x1111_1111, 32)).unwrap();
+    memory.store(0x1004, const_(0x2222_2222, 32)).unwrap();
+
+    let scalars = vec![("r0", const_(0x1000, 32))];
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0x1008);
+    let result = get_scalar(instruction_bytes, scalars.clone(), memory.clone(), "r1");
+    assert_eq!(result.value_u64().unwrap(), 0x1111_1111);
+    let result = get_scalar(instruction_bytes, scalars, memory, "r2");
+    assert_eq!(result.value_u64().unwrap(), 0x2222_2222);
+}
+
+#[test]
+fn stmdb_writeback() {
+    // stmdb r0!, {r1, r2}
+    let instruction_bytes = &[0x06, 0x00, 0x20, 0xe9];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![
+            ("r0", const_(0x1008, 32)),
+            ("r1", const_(0x1111_1111, 32)),
+            ("r2", const_(0x2222_2222, 32)),
+        ],
+        Memory::new(Endian::Little),
+    );
+    assert_eq!(state.get_scalar("r0").unwrap().value_u64().unwrap(), 0x1000);
+    let result = state.memory().load(0x1000, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0x1111_1111);
+    let result = state.memory().load(0x1004, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0x2222_2222);
+}
+
+#[test]
+fn conditional_execution() {
+    // cmp r0, r1
+    // moveq r0, #1
+    // movne r0, #2
+    let instruction_bytes = &[
+        0x01, 0x00, 0x50, 0xe1, 0x01, 0x00, 0xa0, 0x03, 0x02, 0x00, 0xa0, 0x13,
+    ];
+
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r0", const_(5, 32)), ("r1", const_(5, 32))],
+        memory.clone(),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r0", const_(4, 32)), ("r1", const_(5, 32))],
+        memory,
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 2);
+}
+
+#[test]
+fn svc() {
+    // svc #0
+    let instruction_bytes = &[0x00, 0x00, 0x00, 0xef];
+
+    let intrinsic = get_intrinsic(instruction_bytes, vec![], Memory::new(Endian::Little));
+    assert_eq!(intrinsic.mnemonic(), "svc");
+}
+
+#[test]
+fn thumb_adds() {
+    // adds r0, r1, r2
+    let instruction_bytes = &[0x88, 0x18];
+
+    let scalars = vec![("r1", const_(0xffff_ffff, 32)), ("r2", const_(1, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar_thumb(instruction_bytes, scalars.clone(), memory.clone(), "r0");
+    assert_eq!(result.value_u64().unwrap(), 0);
+    let result = get_scalar_thumb(instruction_bytes, scalars, memory, "c");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn thumb_two_operand() {
+    // add r0, r1
+    let instruction_bytes = &[0x08, 0x44];
+
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![("r0", const_(3, 32)), ("r1", const_(4, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 7);
+
+    // bics r0, r1
+    let instruction_bytes = &[0x88, 0x43];
+
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![
+            ("r0", const_(0xff, 32)),
+            ("r1", const_(0x0f, 32)),
+            ("c", const_(0, 1)),
+        ],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf0);
+}
+
+#[test]
+fn thumb_shifts() {
+    // lsl.w r0, r1, #2
+    let instruction_bytes = &[0x4f, 0xea, 0x81, 0x00];
+
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![("r1", const_(3, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 12);
+
+    // lsls r0, r1
+    let instruction_bytes = &[0x88, 0x40];
+
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![("r0", const_(3, 32)), ("r1", const_(4, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 48);
+}
+
+#[test]
+fn thumb_udiv() {
+    // udiv r0, r1, r2
+    let instruction_bytes = &[0xb1, 0xfb, 0xf2, 0xf0];
+
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![("r1", const_(100, 32)), ("r2", const_(7, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 14);
+
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![("r1", const_(100, 32)), ("r2", const_(0, 32))],
+        Memory::new(Endian::Little),
+        "r0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn thumb_adr() {
+    // adr r0, #8
+    let instruction_bytes = &[0x02, 0xa0];
+
+    let result = get_scalar_thumb(instruction_bytes, vec![], Memory::new(Endian::Little), "r0");
+    assert_eq!(result.value_u64().unwrap(), 12);
+}
+
+#[test]
+fn thumb_it_block() {
+    // cmp r0, #0
+    // ite ne
+    // movne r1, #2
+    // moveq r1, #3
+    let instruction_bytes = &[0x00, 0x28, 0x14, 0xbf, 0x02, 0x21, 0x03, 0x21];
+
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![("r0", const_(0, 32)), ("r1", const_(0, 32))],
+        memory.clone(),
+        "r1",
+    );
+    assert_eq!(result.value_u64().unwrap(), 3);
+
+    let result = get_scalar_thumb(
+        instruction_bytes,
+        vec![("r0", const_(1, 32)), ("r1", const_(0, 32))],
+        memory,
+        "r1",
+    );
+    assert_eq!(result.value_u64().unwrap(), 2);
+}
+
+#[test]
+fn thumb_addresses() {
+    // movs r0, #5
+    let result = Arm::new().translate_block(&[0x05, 0x20], 1).unwrap();
+    assert_eq!(result.instructions()[0].0, 1);
+    assert_eq!(result.successors().len(), 1);
+    assert_eq!(result.successors()[0].0, 3);
+
+    // cbz r0, #0x14
+    let result = Arm::new().translate_block(&[0x40, 0xb1], 1).unwrap();
+    let mut successors = result
+        .successors()
+        .iter()
+        .map(|successor| successor.0)
+        .collect::<Vec<u64>>();
+    successors.sort();
+    assert_eq!(successors, vec![3, 0x15]);
+}
+
+#[test]
+fn arm_to_thumb_veneer() {
+    // 0x0: add ip, pc, #1
+    // 0x4: bx ip
+    // 0x8: movs r0, #5 (thumb)
+    // 0xa: nop (thumb)
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        vec![
+            0x01, 0xc0, 0x8f, 0xe2, 0x1c, 0xff, 0x2f, 0xe1, 0x05, 0x20, 0x00, 0xbf,
+        ],
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let function = Arm::new().translate_function(&backing, 0).unwrap();
+    assert!(function.blocks().iter().any(|block| block
+        .instructions()
+        .iter()
+        .any(|instruction| instruction.address() == Some(9))));
+
+    let driver = run(init_driver_function(backing, 0, vec![("c", const_(0, 1))]));
+    let result = driver.state().get_scalar("r0").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 5);
+}
+
+#[test]
+fn thumb_to_arm_bx_pc() {
+    // 0x0: bx pc (thumb)
+    // 0x2: nop (thumb)
+    // 0x4: mov r0, #4096
+    // 0x8: nop
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        vec![
+            0x78, 0x47, 0x00, 0xbf, 0x01, 0x0a, 0xa0, 0xe3, 0x00, 0xf0, 0x20, 0xe3,
+        ],
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let driver = run(init_driver_function(backing, 1, vec![]));
+    let result = driver.state().get_scalar("r0").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0x1000);
+}
+
+#[test]
+fn blx_immediate() {
+    // 0x00: blx #0x10
+    // 0x04: nop
+    // 0x08: bx lr
+    // 0x0c: nop
+    // 0x10: movs r0, #7 (thumb)
+    // 0x12: bx lr (thumb)
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        vec![
+            0x02, 0x00, 0x00, 0xfa, 0x00, 0xf0, 0x20, 0xe3, 0x1e, 0xff, 0x2f, 0xe1, 0x00, 0xf0,
+            0x20, 0xe3, 0x07, 0x20, 0x70, 0x47,
+        ],
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let driver = init_driver_function(backing, 0, vec![("c", const_(0, 1))]);
+    let driver = step_to(driver, 4);
+    let result = driver.state().get_scalar("r0").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 7);
+    let result = driver.state().get_scalar("lr").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 4);
+}
+
+#[test]
+fn thumb_bl() {
+    // 0x0: bl #8 (thumb)
+    // 0x4: nop (thumb)
+    // 0x6: nop (thumb)
+    // 0x8: movs r0, #9 (thumb)
+    // 0xa: bx lr (thumb)
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        vec![
+            0x00, 0xf0, 0x02, 0xf8, 0x00, 0xbf, 0x00, 0xbf, 0x09, 0x20, 0x70, 0x47,
+        ],
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let driver = init_driver_function(backing, 1, vec![("c", const_(0, 1))]);
+    let driver = step_to(driver, 5);
+    let result = driver.state().get_scalar("r0").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 9);
+    let result = driver.state().get_scalar("lr").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 5);
+}
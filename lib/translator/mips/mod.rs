@@ -6,10 +6,13 @@ use crate::il::*;
 use crate::translator::{BlockTranslationResult, Translator, DEFAULT_TRANSLATION_BLOCK_BYTES};
 use falcon_capstone::capstone;
 
+mod mode;
 mod semantics;
 #[cfg(test)]
 mod test;
 
+pub use self::mode::Mode;
+
 /// The MIPS translator.
 #[derive(Clone, Debug, Default)]
 pub struct Mips;
@@ -22,11 +25,11 @@ impl Mips {
 
 impl Translator for Mips {
     fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
-        translate_block(bytes, address, Endian::Big)
+        translate_block(Mode::Mips32, bytes, address, Endian::Big)
     }
 }
 
-/// This MIPSel translator.
+/// The MIPSel translator.
 #[derive(Clone, Debug, Default)]
 pub struct Mipsel;
 
@@ -38,7 +41,39 @@ impl Mipsel {
 
 impl Translator for Mipsel {
     fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
-        translate_block(bytes, address, Endian::Little)
+        translate_block(Mode::Mips32, bytes, address, Endian::Little)
+    }
+}
+
+/// The MIPS64 translator.
+#[derive(Clone, Debug, Default)]
+pub struct Mips64;
+
+impl Mips64 {
+    pub fn new() -> Mips64 {
+        Mips64
+    }
+}
+
+impl Translator for Mips64 {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(Mode::Mips64, bytes, address, Endian::Big)
+    }
+}
+
+/// The little-endian MIPS64 translator.
+#[derive(Clone, Debug, Default)]
+pub struct Mips64el;
+
+impl Mips64el {
+    pub fn new() -> Mips64el {
+        Mips64el
+    }
+}
+
+impl Translator for Mips64el {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(Mode::Mips64, bytes, address, Endian::Little)
     }
 }
 
@@ -91,12 +126,21 @@ fn conditional_graph(address: u64, branching_condition: Expression) -> Result<Co
     Ok(cfg)
 }
 
-fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTranslationResult> {
-    let mode = match endian {
-        Endian::Big => capstone::CS_MODE_32 | capstone::CS_MODE_BIG_ENDIAN,
-        Endian::Little => capstone::CS_MODE_32 | capstone::CS_MODE_LITTLE_ENDIAN,
+fn translate_block(
+    mode: Mode,
+    bytes: &[u8],
+    address: u64,
+    endian: Endian,
+) -> Result<BlockTranslationResult> {
+    let cs_mode = match mode {
+        Mode::Mips32 => capstone::CS_MODE_32,
+        Mode::Mips64 => capstone::CS_MODE_64,
+    };
+    let cs_mode = match endian {
+        Endian::Big => cs_mode | capstone::CS_MODE_BIG_ENDIAN,
+        Endian::Little => cs_mode | capstone::CS_MODE_LITTLE_ENDIAN,
     };
-    let cs = match capstone::Capstone::new(capstone::cs_arch::CS_ARCH_MIPS, mode) {
+    let cs = match capstone::Capstone::new(capstone::cs_arch::CS_ARCH_MIPS, cs_mode) {
         Ok(cs) => cs,
         Err(_) => return Err(ErrorKind::CapstoneError.into()),
     };
@@ -148,29 +192,42 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
             let mut instruction_graph = ControlFlowGraph::new();
 
             match instruction_id {
+                capstone::mips_insn::MIPS_INS_ABS => {
+                    semantics::abs_fmt(&mut instruction_graph, &instruction, mode)
+                }
                 capstone::mips_insn::MIPS_INS_ADD => {
-                    semantics::add(&mut instruction_graph, &instruction)
+                    if semantics::is_fpu_instruction(&instruction)? {
+                        semantics::add_fmt(&mut instruction_graph, &instruction, mode)
+                    } else {
+                        semantics::add(&mut instruction_graph, &instruction, mode)
+                    }
                 }
                 capstone::mips_insn::MIPS_INS_ADDI => {
-                    semantics::addi(&mut instruction_graph, &instruction)
+                    semantics::addi(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_ADDIU => {
-                    semantics::addiu(&mut instruction_graph, &instruction)
+                    semantics::addiu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_ADDU => {
-                    semantics::addu(&mut instruction_graph, &instruction)
+                    semantics::addu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_AND => {
-                    semantics::and(&mut instruction_graph, &instruction)
+                    semantics::and(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_ANDI => {
-                    semantics::andi(&mut instruction_graph, &instruction)
+                    semantics::andi(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_B => {
                     semantics::b(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_BAL => {
-                    semantics::bal(&mut instruction_graph, &instruction)
+                    semantics::bal(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_BC1F => {
+                    semantics::b(&mut instruction_graph, &instruction)
+                }
+                capstone::mips_insn::MIPS_INS_BC1T => {
+                    semantics::b(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_BEQ => {
                     semantics::b(&mut instruction_graph, &instruction)
@@ -182,7 +239,7 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                     semantics::b(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_BGEZAL => {
-                    semantics::bgezal(&mut instruction_graph, &instruction)
+                    semantics::bgezal(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_BGTZ => {
                     semantics::b(&mut instruction_graph, &instruction)
@@ -194,7 +251,7 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                     semantics::b(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_BLTZAL => {
-                    semantics::bltzal(&mut instruction_graph, &instruction)
+                    semantics::bltzal(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_BNE => {
                     semantics::b(&mut instruction_graph, &instruction)
@@ -205,173 +262,305 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 capstone::mips_insn::MIPS_INS_BREAK => {
                     semantics::break_(&mut instruction_graph, &instruction)
                 }
+                capstone::mips_insn::MIPS_INS_C => {
+                    semantics::c_cond_fmt(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_CFC1 => {
+                    semantics::cfc1(&mut instruction_graph, &instruction, mode)
+                }
                 capstone::mips_insn::MIPS_INS_CLO => {
-                    semantics::clo(&mut instruction_graph, &instruction)
+                    semantics::clo(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_CLZ => {
-                    semantics::clz(&mut instruction_graph, &instruction)
+                    semantics::clz(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_CTC1 => {
+                    semantics::ctc1(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_CVT => {
+                    semantics::cvt(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DADD => {
+                    semantics::dadd(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DADDI => {
+                    semantics::daddi(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DADDIU => {
+                    semantics::daddiu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DADDU => {
+                    semantics::daddu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DCLO => {
+                    semantics::dclo(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DCLZ => {
+                    semantics::dclz(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DDIV => {
+                    semantics::ddiv(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DDIVU => {
+                    semantics::ddivu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DMFC1 => {
+                    semantics::dmfc1(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DMTC1 => {
+                    semantics::dmtc1(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DMULT => {
+                    semantics::dmult(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DMULTU => {
+                    semantics::dmultu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSLL => {
+                    semantics::dsll(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSLL32 => {
+                    semantics::dsll32(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSLLV => {
+                    semantics::dsllv(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSRA => {
+                    semantics::dsra(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSRA32 => {
+                    semantics::dsra32(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSRAV => {
+                    semantics::dsrav(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSRL => {
+                    semantics::dsrl(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSRL32 => {
+                    semantics::dsrl32(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSRLV => {
+                    semantics::dsrlv(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSUB => {
+                    semantics::dsub(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_DSUBU => {
+                    semantics::dsubu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_DIV => {
-                    semantics::div(&mut instruction_graph, &instruction)
+                    if semantics::is_fpu_instruction(&instruction)? {
+                        semantics::div_fmt(&mut instruction_graph, &instruction, mode)
+                    } else {
+                        semantics::div(&mut instruction_graph, &instruction, mode)
+                    }
                 }
                 capstone::mips_insn::MIPS_INS_DIVU => {
-                    semantics::divu(&mut instruction_graph, &instruction)
+                    semantics::divu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_J => {
                     semantics::j(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_JR => {
-                    semantics::jr(&mut instruction_graph, &instruction)
+                    semantics::jr(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_JAL => {
-                    semantics::jal(&mut instruction_graph, &instruction)
+                    semantics::jal(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_JALR => {
-                    semantics::jalr(&mut instruction_graph, &instruction)
+                    semantics::jalr(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LB => {
-                    semantics::lb(&mut instruction_graph, &instruction)
+                    semantics::lb(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LBU => {
-                    semantics::lbu(&mut instruction_graph, &instruction)
+                    semantics::lbu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_LD => {
+                    semantics::ld(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_LDC1 => {
+                    semantics::ldc1(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LH => {
-                    semantics::lh(&mut instruction_graph, &instruction)
+                    semantics::lh(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LHU => {
-                    semantics::lhu(&mut instruction_graph, &instruction)
+                    semantics::lhu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LL => {
-                    semantics::ll(&mut instruction_graph, &instruction)
+                    semantics::ll(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_LLD => {
+                    semantics::lld(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LUI => {
-                    semantics::lui(&mut instruction_graph, &instruction)
+                    semantics::lui(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LW => {
-                    semantics::lw(&mut instruction_graph, &instruction)
+                    semantics::lw(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_LWC1 => {
+                    semantics::lwc1(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LWL => {
-                    semantics::lwl(&mut instruction_graph, &instruction)
+                    semantics::lwl(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_LWR => {
-                    semantics::lwr(&mut instruction_graph, &instruction)
+                    semantics::lwr(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_LWU => {
+                    semantics::lwu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MADD => {
-                    semantics::madd(&mut instruction_graph, &instruction)
+                    semantics::madd(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MADDU => {
-                    semantics::maddu(&mut instruction_graph, &instruction)
+                    semantics::maddu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_MFC1 => {
+                    semantics::mfc1(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MFHI => {
-                    semantics::mfhi(&mut instruction_graph, &instruction)
+                    semantics::mfhi(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MFLO => {
-                    semantics::mflo(&mut instruction_graph, &instruction)
+                    semantics::mflo(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_MOV => {
+                    semantics::mov_fmt(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MOVE => {
-                    semantics::move_(&mut instruction_graph, &instruction)
+                    semantics::move_(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MOVN => {
-                    semantics::movn(&mut instruction_graph, &instruction)
+                    semantics::movn(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MOVZ => {
-                    semantics::movz(&mut instruction_graph, &instruction)
+                    semantics::movz(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MSUB => {
-                    semantics::msub(&mut instruction_graph, &instruction)
+                    semantics::msub(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MSUBU => {
-                    semantics::msubu(&mut instruction_graph, &instruction)
+                    semantics::msubu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_MTC1 => {
+                    semantics::mtc1(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MTHI => {
-                    semantics::mthi(&mut instruction_graph, &instruction)
+                    semantics::mthi(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MTLO => {
-                    semantics::mtlo(&mut instruction_graph, &instruction)
+                    semantics::mtlo(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MUL => {
-                    semantics::mul(&mut instruction_graph, &instruction)
+                    if semantics::is_fpu_instruction(&instruction)? {
+                        semantics::mul_fmt(&mut instruction_graph, &instruction, mode)
+                    } else {
+                        semantics::mul(&mut instruction_graph, &instruction, mode)
+                    }
                 }
                 capstone::mips_insn::MIPS_INS_MULT => {
-                    semantics::mult(&mut instruction_graph, &instruction)
+                    semantics::mult(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_MULTU => {
-                    semantics::multu(&mut instruction_graph, &instruction)
+                    semantics::multu(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_NEG => {
+                    semantics::neg_fmt(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_NEGU => {
-                    semantics::negu(&mut instruction_graph, &instruction)
+                    semantics::negu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_NOP => {
                     semantics::nop(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_NOR => {
-                    semantics::nor(&mut instruction_graph, &instruction)
+                    semantics::nor(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_OR => {
-                    semantics::or(&mut instruction_graph, &instruction)
+                    semantics::or(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_ORI => {
-                    semantics::ori(&mut instruction_graph, &instruction)
+                    semantics::ori(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_RDHWR => {
-                    semantics::rdhwr(&mut instruction_graph, &instruction)
+                    semantics::rdhwr(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_PREF => {
                     semantics::nop(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_SB => {
-                    semantics::sb(&mut instruction_graph, &instruction)
+                    semantics::sb(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SC => {
-                    semantics::sc(&mut instruction_graph, &instruction)
+                    semantics::sc(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_SCD => {
+                    semantics::scd(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_SD => {
+                    semantics::sd(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_SDC1 => {
+                    semantics::sdc1(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SH => {
-                    semantics::sh(&mut instruction_graph, &instruction)
+                    semantics::sh(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SLL => {
-                    semantics::sll(&mut instruction_graph, &instruction)
+                    semantics::sll(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SLLV => {
-                    semantics::sllv(&mut instruction_graph, &instruction)
+                    semantics::sllv(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SLT => {
-                    semantics::slt(&mut instruction_graph, &instruction)
+                    semantics::slt(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SLTI => {
-                    semantics::slti(&mut instruction_graph, &instruction)
+                    semantics::slti(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SLTIU => {
-                    semantics::sltiu(&mut instruction_graph, &instruction)
+                    semantics::sltiu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SLTU => {
-                    semantics::sltu(&mut instruction_graph, &instruction)
+                    semantics::sltu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SRA => {
-                    semantics::sra(&mut instruction_graph, &instruction)
+                    semantics::sra(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SRAV => {
-                    semantics::srav(&mut instruction_graph, &instruction)
+                    semantics::srav(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SRL => {
-                    semantics::srl(&mut instruction_graph, &instruction)
+                    semantics::srl(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SRLV => {
-                    semantics::srlv(&mut instruction_graph, &instruction)
+                    semantics::srlv(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SUB => {
-                    semantics::sub(&mut instruction_graph, &instruction)
+                    if semantics::is_fpu_instruction(&instruction)? {
+                        semantics::sub_fmt(&mut instruction_graph, &instruction, mode)
+                    } else {
+                        semantics::sub(&mut instruction_graph, &instruction, mode)
+                    }
                 }
                 capstone::mips_insn::MIPS_INS_SUBU => {
-                    semantics::subu(&mut instruction_graph, &instruction)
+                    semantics::subu(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SW => {
-                    semantics::sw(&mut instruction_graph, &instruction)
+                    semantics::sw(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_SWC1 => {
+                    semantics::swc1(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SWL => {
-                    semantics::swl(&mut instruction_graph, &instruction)
+                    semantics::swl(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SWR => {
-                    semantics::swr(&mut instruction_graph, &instruction)
+                    semantics::swr(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_SYNC => {
                     semantics::nop(&mut instruction_graph, &instruction)
@@ -380,13 +569,16 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                     semantics::syscall(&mut instruction_graph, &instruction)
                 }
                 capstone::mips_insn::MIPS_INS_TEQ => {
-                    semantics::teq(&mut instruction_graph, &instruction)
+                    semantics::teq(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::mips_insn::MIPS_INS_TRUNC => {
+                    semantics::trunc(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_XOR => {
-                    semantics::xor(&mut instruction_graph, &instruction)
+                    semantics::xor(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::mips_insn::MIPS_INS_XORI => {
-                    semantics::xori(&mut instruction_graph, &instruction)
+                    semantics::xori(&mut instruction_graph, &instruction, mode)
                 }
                 _ => {
                     let bytes = (0..4)
@@ -428,6 +620,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
             // delay slot
             match instruction_id {
                 capstone::mips_insn::MIPS_INS_B
+                | capstone::mips_insn::MIPS_INS_BC1F
+                | capstone::mips_insn::MIPS_INS_BC1T
                 | capstone::mips_insn::MIPS_INS_BEQ
                 | capstone::mips_insn::MIPS_INS_BEQZ
                 | capstone::mips_insn::MIPS_INS_BGEZ
@@ -461,10 +655,43 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                     successors.push((operand.imm() as u64, None));
                     branch_delay = TranslateBranchDelay::Branch;
                 }
+                capstone::mips_insn::MIPS_INS_BC1F => {
+                    let detail = semantics::details(&instruction)?;
+                    let fcc = semantics::branch_fcc(&instruction)?;
+                    let target = detail.operands[detail.op_count as usize - 1].imm() as u64;
+                    let condition = Expression::cmpeq(fcc, expr_const(0, 1))?;
+
+                    conditional_direct_branch(
+                        &mut block_graphs,
+                        &mut successors,
+                        instruction.address,
+                        target,
+                        instruction.address + 8,
+                        condition,
+                    )?;
+
+                    branch_delay = TranslateBranchDelay::Branch;
+                }
+                capstone::mips_insn::MIPS_INS_BC1T => {
+                    let detail = semantics::details(&instruction)?;
+                    let condition = semantics::branch_fcc(&instruction)?;
+                    let target = detail.operands[detail.op_count as usize - 1].imm() as u64;
+
+                    conditional_direct_branch(
+                        &mut block_graphs,
+                        &mut successors,
+                        instruction.address,
+                        target,
+                        instruction.address + 8,
+                        condition,
+                    )?;
+
+                    branch_delay = TranslateBranchDelay::Branch;
+                }
                 capstone::mips_insn::MIPS_INS_BEQ => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let rhs = semantics::get_register(detail.operands[1].reg())?.expression();
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let rhs = semantics::get_register(detail.operands[1].reg())?.expression(mode);
                     let target = detail.operands[2].imm() as u64;
                     let condition = Expression::cmpeq(lhs, rhs)?;
 
@@ -481,8 +708,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 }
                 capstone::mips_insn::MIPS_INS_BEQZ => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let rhs = expr_const(0, 32);
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let rhs = expr_const(0, mode.bits());
                     let target = detail.operands[1].imm() as u64;
                     let condition = Expression::cmpeq(lhs, rhs)?;
 
@@ -499,8 +726,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 }
                 capstone::mips_insn::MIPS_INS_BGEZ => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let zero = expr_const(0, 32);
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let zero = expr_const(0, mode.bits());
                     let target = detail.operands[1].imm() as u64;
                     let condition =
                         Expression::cmpeq(Expression::cmplts(lhs, zero)?, expr_const(0, 1))?;
@@ -518,8 +745,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 }
                 capstone::mips_insn::MIPS_INS_BGTZ => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let zero = expr_const(0, 32);
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let zero = expr_const(0, mode.bits());
                     let target = detail.operands[1].imm() as u64;
                     let condition = Expression::cmplts(zero, lhs)?;
 
@@ -536,8 +763,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 }
                 capstone::mips_insn::MIPS_INS_BLEZ => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let zero = expr_const(0, 32);
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let zero = expr_const(0, mode.bits());
                     let target = detail.operands[1].imm() as u64;
                     let condition = Expression::or(
                         Expression::cmplts(lhs.clone(), zero.clone())?,
@@ -557,8 +784,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 }
                 capstone::mips_insn::MIPS_INS_BLTZ => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let zero = expr_const(0, 32);
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let zero = expr_const(0, mode.bits());
                     let target = detail.operands[1].imm() as u64;
                     let condition = Expression::cmplts(lhs, zero)?;
 
@@ -575,8 +802,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 }
                 capstone::mips_insn::MIPS_INS_BNE => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let rhs = semantics::get_register(detail.operands[1].reg())?.expression();
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let rhs = semantics::get_register(detail.operands[1].reg())?.expression(mode);
                     let target = detail.operands[2].imm() as u64;
                     let condition = Expression::cmpneq(lhs.clone(), rhs.clone())?;
 
@@ -593,8 +820,8 @@ fn translate_block(bytes: &[u8], address: u64, endian: Endian) -> Result<BlockTr
                 }
                 capstone::mips_insn::MIPS_INS_BNEZ => {
                     let detail = semantics::details(&instruction)?;
-                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression();
-                    let rhs = expr_const(0, 32);
+                    let lhs = semantics::get_register(detail.operands[0].reg())?.expression(mode);
+                    let rhs = expr_const(0, mode.bits());
                     let target = detail.operands[1].imm() as u64;
                     let condition = Expression::cmpneq(lhs.clone(), rhs.clone())?;
 
@@ -0,0 +1,24 @@
+/// The width of the registers a MIPS block is translated with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    Mips32,
+    Mips64,
+}
+
+impl Mode {
+    /// Returns the width of the general purpose registers, in bits.
+    pub fn bits(self) -> usize {
+        match self {
+            Mode::Mips32 => 32,
+            Mode::Mips64 => 64,
+        }
+    }
+
+    /// Returns a mask of the bits of a register.
+    pub fn mask(self) -> u64 {
+        match self {
+            Mode::Mips32 => 0xffff_ffff,
+            Mode::Mips64 => 0xffff_ffff_ffff_ffff,
+        }
+    }
+}
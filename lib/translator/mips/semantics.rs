@@ -1,3 +1,4 @@
+use super::Mode;
 use crate::error::*;
 use crate::il::Expression as Expr;
 use crate::il::*;
@@ -9,28 +10,22 @@ pub struct MIPSRegister {
     name: &'static str,
     // The capstone enum value for this register.
     capstone_reg: mips_reg,
-    /// The size of this register in bits
-    bits: usize,
 }
 
 impl MIPSRegister {
-    // pub fn bits(&self) -> usize {
-    //     self.bits
-    // }
-
-    pub fn name(&self) -> &str {
-        self.name
-    }
-
-    pub fn scalar(&self) -> Scalar {
-        scalar(self.name, self.bits)
+    /// Returns this register as a scalar, with the width of the registers in
+    /// `mode`.
+    pub fn scalar(&self, mode: Mode) -> Scalar {
+        scalar(self.name, mode.bits())
     }
 
-    pub fn expression(&self) -> Expr {
+    /// Returns this register as an expression, with the width of the
+    /// registers in `mode`.
+    pub fn expression(&self, mode: Mode) -> Expr {
         if self.name == "$zero" {
-            expr_const(0, 32)
+            expr_const(0, mode.bits())
         } else {
-            expr_scalar(self.name, self.bits)
+            expr_scalar(self.name, mode.bits())
         }
     }
 }
@@ -39,162 +34,130 @@ const MIPSREGISTERS: &[MIPSRegister] = &[
     MIPSRegister {
         name: "$zero",
         capstone_reg: mips_reg::MIPS_REG_0,
-        bits: 32,
     },
     MIPSRegister {
         name: "$at",
         capstone_reg: mips_reg::MIPS_REG_1,
-        bits: 32,
     },
     MIPSRegister {
         name: "$v0",
         capstone_reg: mips_reg::MIPS_REG_2,
-        bits: 32,
     },
     MIPSRegister {
         name: "$v1",
         capstone_reg: mips_reg::MIPS_REG_3,
-        bits: 32,
     },
     MIPSRegister {
         name: "$a0",
         capstone_reg: mips_reg::MIPS_REG_4,
-        bits: 32,
     },
     MIPSRegister {
         name: "$a1",
         capstone_reg: mips_reg::MIPS_REG_5,
-        bits: 32,
     },
     MIPSRegister {
         name: "$a2",
         capstone_reg: mips_reg::MIPS_REG_6,
-        bits: 32,
     },
     MIPSRegister {
         name: "$a3",
         capstone_reg: mips_reg::MIPS_REG_7,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t0",
         capstone_reg: mips_reg::MIPS_REG_8,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t1",
         capstone_reg: mips_reg::MIPS_REG_9,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t2",
         capstone_reg: mips_reg::MIPS_REG_10,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t3",
         capstone_reg: mips_reg::MIPS_REG_11,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t4",
         capstone_reg: mips_reg::MIPS_REG_12,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t5",
         capstone_reg: mips_reg::MIPS_REG_13,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t6",
         capstone_reg: mips_reg::MIPS_REG_14,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t7",
         capstone_reg: mips_reg::MIPS_REG_15,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s0",
         capstone_reg: mips_reg::MIPS_REG_16,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s1",
         capstone_reg: mips_reg::MIPS_REG_17,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s2",
         capstone_reg: mips_reg::MIPS_REG_18,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s3",
         capstone_reg: mips_reg::MIPS_REG_19,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s4",
         capstone_reg: mips_reg::MIPS_REG_20,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s5",
         capstone_reg: mips_reg::MIPS_REG_21,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s6",
         capstone_reg: mips_reg::MIPS_REG_22,
-        bits: 32,
     },
     MIPSRegister {
         name: "$s7",
         capstone_reg: mips_reg::MIPS_REG_23,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t8",
         capstone_reg: mips_reg::MIPS_REG_24,
-        bits: 32,
     },
     MIPSRegister {
         name: "$t9",
         capstone_reg: mips_reg::MIPS_REG_25,
-        bits: 32,
     },
     MIPSRegister {
         name: "$k0",
         capstone_reg: mips_reg::MIPS_REG_26,
-        bits: 32,
     },
     MIPSRegister {
         name: "$k1",
         capstone_reg: mips_reg::MIPS_REG_27,
-        bits: 32,
     },
     MIPSRegister {
         name: "$gp",
         capstone_reg: mips_reg::MIPS_REG_28,
-        bits: 32,
     },
     MIPSRegister {
         name: "$sp",
         capstone_reg: mips_reg::MIPS_REG_29,
-        bits: 32,
     },
     MIPSRegister {
         name: "$fp",
         capstone_reg: mips_reg::MIPS_REG_30,
-        bits: 32,
     },
     MIPSRegister {
         name: "$ra",
         capstone_reg: mips_reg::MIPS_REG_31,
-        bits: 32,
     },
 ];
 
@@ -217,13 +180,374 @@ pub fn details(instruction: &capstone::Instr) -> Result<capstone::cs_mips> {
     }
 }
 
-pub fn add(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+/// Truncates or extends `expr` to `bits`.
+fn resize(expr: Expr, bits: usize, signed: bool) -> Result<Expr> {
+    if expr.bits() > bits {
+        Expr::trun(bits, expr)
+    } else if expr.bits() == bits {
+        Ok(expr)
+    } else if signed {
+        Expr::sext(bits, expr)
+    } else {
+        Expr::zext(bits, expr)
+    }
+}
+
+/// Returns the low 32 bits of a register value, the operand of the 32-bit
+/// arithmetic instructions.
+fn word(expr: Expr) -> Result<Expr> {
+    resize(expr, 32, false)
+}
+
+/// Sign-extends the 32-bit result of a word instruction to the width of the
+/// registers in `mode`.
+fn sign_extend_word(expr: Expr, mode: Mode) -> Result<Expr> {
+    resize(expr, mode.bits(), true)
+}
+
+/// Loads a sign-extended word from `address` into `dst`.
+fn load_word(
+    block: &mut Block,
+    instruction: &capstone::Instr,
+    dst: Scalar,
+    address: Expr,
+    mode: Mode,
+) -> Result<()> {
+    match mode {
+        Mode::Mips32 => block.load(dst, address),
+        Mode::Mips64 => {
+            let temp = Scalar::temp(instruction.address, 32);
+            block.load(temp.clone(), address);
+            block.assign(dst, Expr::sext(64, temp.into())?);
+        }
+    }
+    Ok(())
+}
+
+/// Assigns `value` to `dst`, unless `overflow` is set, in which case an
+/// `IntegerOverflow` intrinsic is raised and `dst` is left unmodified.
+fn checked_assign(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    dst: Scalar,
+    value: Expr,
+    overflow: Expr,
+) -> Result<()> {
+    let head_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.nop();
+
+        block.index()
+    };
+
+    let raise_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.intrinsic(Intrinsic::new(
+            "IntegerOverflow",
+            "IntegerOverflow",
+            Vec::new(),
+            None,
+            None,
+            instruction.bytes.get(0..4).unwrap().to_vec(),
+        ));
+
+        block.index()
+    };
+
+    let operation_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(dst, value);
+
+        block.index()
+    };
+
+    let terminating_index = { control_flow_graph.new_block()?.index() };
+
+    control_flow_graph.conditional_edge(head_index, raise_index, overflow.clone())?;
+    control_flow_graph.conditional_edge(
+        head_index,
+        operation_index,
+        Expr::cmpeq(overflow, expr_const(0, 1))?,
+    )?;
+
+    control_flow_graph.unconditional_edge(raise_index, terminating_index)?;
+    control_flow_graph.unconditional_edge(operation_index, terminating_index)?;
+
+    control_flow_graph.set_entry(head_index)?;
+    control_flow_graph.set_exit(terminating_index)?;
+
+    Ok(())
+}
+
+/// Returns the sign bit of `expr`.
+fn sign_bit(expr: Expr) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::trun(1, Expr::shr(expr, expr_const(bits as u64 - 1, bits))?)
+}
+
+/// Returns a 1-bit expression set when `lhs + rhs` overflows as a signed
+/// addition.
+fn add_overflow(lhs: Expr, rhs: Expr) -> Result<Expr> {
+    let result = Expr::add(lhs.clone(), rhs.clone())?;
+    sign_bit(Expr::and(
+        Expr::xor(lhs, result.clone())?,
+        Expr::xor(rhs, result)?,
+    )?)
+}
+
+/// Returns a 1-bit expression set when `lhs - rhs` overflows as a signed
+/// subtraction.
+fn sub_overflow(lhs: Expr, rhs: Expr) -> Result<Expr> {
+    let result = Expr::sub(lhs.clone(), rhs.clone())?;
+    sign_bit(Expr::and(
+        Expr::xor(lhs.clone(), rhs)?,
+        Expr::xor(lhs, result)?,
+    )?)
+}
+
+/// Returns true if the first operand of `instruction` is a floating-point
+/// register, which is how the floating-point forms of instructions sharing an
+/// id with their integer forms, such as `add.s`, are told apart.
+pub fn is_fpu_instruction(instruction: &capstone::Instr) -> Result<bool> {
+    let detail = details(instruction)?;
+    Ok(detail.op_count > 0
+        && detail.operands[0].type_ == capstone::mips_op_type::MIPS_OP_REG
+        && fpu_register_index(detail.operands[0].reg()).is_ok())
+}
+
+/// Returns the index of the floating-point register `capstone_id`.
+fn fpu_register_index(capstone_id: mips_reg) -> Result<u32> {
+    let index = (capstone_id as u32).wrapping_sub(mips_reg::MIPS_REG_F0 as u32);
+    if index < 32 {
+        Ok(index)
+    } else {
+        Err("Could not find floating-point register".into())
+    }
+}
+
+/// Returns the floating-point register `$f<index>` as a scalar, with the
+/// width of the registers in `mode`.
+fn fpu_scalar(index: u32, mode: Mode) -> Scalar {
+    scalar(format!("$f{}", index), mode.bits())
+}
+
+/// Returns the width in bits of a floating-point format from the format
+/// suffix of a mnemonic, `s` and `w` being 32 bits wide and `d` and `l` 64.
+fn format_bits(format: &str) -> Result<usize> {
+    match format {
+        "s" | "w" => Ok(32),
+        "d" | "l" => Ok(64),
+        _ => bail!("Unknown floating-point format {}", format),
+    }
+}
+
+/// Returns the formats of a floating-point instruction, with the format of
+/// the source operands last, so `["d", "s"]` for `cvt.d.s`.
+fn formats(instruction: &capstone::Instr) -> Vec<&str> {
+    instruction.mnemonic.split('.').skip(1).collect()
+}
+
+/// Reads a `bits` wide value out of the floating-point register
+/// `capstone_id`.
+///
+/// In `Mips32` the floating-point registers are 32 bits wide, and 64-bit
+/// values are held in an even/odd pair of registers, with the low word in the
+/// even register. In `Mips64` each register holds a 64-bit value, and 32-bit
+/// values are held in the low word.
+fn fpu_read(capstone_id: mips_reg, bits: usize, mode: Mode) -> Result<Expr> {
+    let index = fpu_register_index(capstone_id)?;
+    match (mode, bits) {
+        (Mode::Mips32, 64) => {
+            if index % 2 == 1 {
+                bail!("64-bit value in odd floating-point register $f{}", index);
+            }
+            Expr::or(
+                Expr::shl(
+                    Expr::zext(64, fpu_scalar(index + 1, mode).into())?,
+                    expr_const(32, 64),
+                )?,
+                Expr::zext(64, fpu_scalar(index, mode).into())?,
+            )
+        }
+        _ => resize(fpu_scalar(index, mode).into(), bits, false),
+    }
+}
+
+/// Writes `value` to the floating-point register `capstone_id`, following
+/// the register layout described in `fpu_read`.
+fn fpu_write(block: &mut Block, capstone_id: mips_reg, value: Expr, mode: Mode) -> Result<()> {
+    let index = fpu_register_index(capstone_id)?;
+    match (mode, value.bits()) {
+        (Mode::Mips32, 64) => {
+            if index % 2 == 1 {
+                bail!("64-bit value in odd floating-point register $f{}", index);
+            }
+            block.assign(fpu_scalar(index, mode), Expr::trun(32, value.clone())?);
+            block.assign(
+                fpu_scalar(index + 1, mode),
+                Expr::trun(32, Expr::shr(value, expr_const(32, 64))?)?,
+            );
+        }
+        _ => block.assign(fpu_scalar(index, mode), resize(value, mode.bits(), false)?),
+    }
+    Ok(())
+}
+
+/// Returns the floating-point condition code `$fcc<index>` as a scalar.
+fn fcc(index: u32) -> Scalar {
+    scalar(format!("$fcc{}", index), 1)
+}
+
+/// Returns the floating-point condition code a `bc1t` or `bc1f` branch
+/// tests.
+pub fn branch_fcc(instruction: &capstone::Instr) -> Result<Expr> {
+    let detail = details(instruction)?;
+    let index = if detail.op_count > 1 {
+        (detail.operands[0].reg() as u32).wrapping_sub(mips_reg::MIPS_REG_FCC0 as u32)
+    } else {
+        0
+    };
+    if index >= 8 {
+        bail!("Could not find floating-point condition code");
+    }
+    Ok(fcc(index).into())
+}
+
+/// Returns the bit of the FCSR holding the floating-point condition code
+/// `$fcc<index>`.
+fn fcsr_fcc_bit(index: u32) -> u64 {
+    if index == 0 {
+        23
+    } else {
+        24 + u64::from(index)
+    }
+}
+
+/// Lifts a floating-point instruction of the form `op.fmt fd, fs, ft`.
+fn fpu_binary<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr, Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+    let bits = format_bits(formats(instruction)[0])?;
+
+    let lhs = fpu_read(detail.operands[1].reg(), bits, mode)?;
+    let rhs = fpu_read(detail.operands[2].reg(), bits, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        fpu_write(block, detail.operands[0].reg(), op(lhs, rhs)?, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Lifts a floating-point instruction of the form `op.fmt fd, fs`.
+fn fpu_unary<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    op: F,
+) -> Result<()>
+where
+    F: Fn(Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+    let bits = format_bits(formats(instruction)[0])?;
+
+    let src = fpu_read(detail.operands[1].reg(), bits, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        fpu_write(block, detail.operands[0].reg(), op(src)?, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Lifts a conversion, where the formats of the destination and source are
+/// both given in the mnemonic, as in `cvt.d.w`, and `float_to_int` converts a
+/// floating-point value to an integer.
+fn fpu_convert<F>(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+    float_to_int: F,
+) -> Result<()>
+where
+    F: Fn(usize, Expr) -> Result<Expr>,
+{
+    let detail = details(instruction)?;
+    let (dst_format, src_format) = match formats(instruction).as_slice() {
+        [dst_format, src_format] => (*dst_format, *src_format),
+        _ => bail!("Unknown conversion {}", instruction.mnemonic),
+    };
+    let dst_bits = format_bits(dst_format)?;
+
+    let src = fpu_read(detail.operands[1].reg(), format_bits(src_format)?, mode)?;
+
+    let value = match (dst_format, src_format) {
+        ("s", "w") | ("s", "l") | ("d", "w") | ("d", "l") => Expr::itof(dst_bits, src)?,
+        ("w", _) | ("l", _) => float_to_int(dst_bits, src)?,
+        _ => Expr::fconv(dst_bits, src)?,
+    };
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        fpu_write(block, detail.operands[0].reg(), value, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn abs_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_unary(control_flow_graph, instruction, mode, |src| {
+        let bits = src.bits();
+        Expr::and(src, expr_const((1 << (bits - 1)) - 1, bits))
+    })
+}
+
+pub fn add(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = get_register(detail.operands[2].reg())?.expression();
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rhs = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -251,7 +575,10 @@ pub fn add(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     let operation_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(dst, Expr::add(lhs.clone(), rhs.clone())?);
+        block.assign(
+            dst,
+            sign_extend_word(Expr::add(lhs.clone(), rhs.clone())?, mode)?,
+        );
 
         block.index()
     };
@@ -283,23 +610,26 @@ pub fn add(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
+pub fn add_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_binary(control_flow_graph, instruction, mode, Expr::fadd)
+}
+
 pub fn addi(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?;
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
     let rhs = expr_const(detail.operands[2].imm() as u64, 32);
 
-    let lhs = if lhs.name() == "$zero" {
-        expr_const(0, 32)
-    } else {
-        lhs.expression()
-    };
-
     let head_index = {
         let block = control_flow_graph.new_block()?;
 
@@ -326,7 +656,10 @@ pub fn addi(
     let operation_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(dst, Expr::add(lhs.clone(), rhs.clone())?);
+        block.assign(
+            dst,
+            sign_extend_word(Expr::add(lhs.clone(), rhs.clone())?, mode)?,
+        );
 
         block.index()
     };
@@ -361,24 +694,19 @@ pub fn addi(
 pub fn addiu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?;
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
     let rhs = expr_const(detail.operands[2].imm() as u64, 32);
 
-    let lhs = if lhs.name() == "$zero" {
-        expr_const(0, 32)
-    } else {
-        lhs.expression()
-    };
-
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(dst, Expr::add(lhs, rhs)?);
+        block.assign(dst, sign_extend_word(Expr::add(lhs, rhs)?, mode)?);
 
         block.index()
     };
@@ -392,18 +720,19 @@ pub fn addiu(
 pub fn addu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = get_register(detail.operands[2].reg())?.expression();
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rhs = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(dst, Expr::add(lhs, rhs)?);
+        block.assign(dst, sign_extend_word(Expr::add(lhs, rhs)?, mode)?);
 
         block.index()
     };
@@ -414,13 +743,17 @@ pub fn addu(
     Ok(())
 }
 
-pub fn and(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn and(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = get_register(detail.operands[2].reg())?.expression();
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rhs = get_register(detail.operands[2].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -439,13 +772,14 @@ pub fn and(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn andi(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = expr_const(detail.operands[2].imm() as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rhs = expr_const(detail.operands[2].imm() as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -470,14 +804,21 @@ pub fn b(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Resu
     Ok(())
 }
 
-pub fn bal(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn bal(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let operand = details(&instruction)?.operands[0];
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(scalar("$ra", 32), expr_const(instruction.address + 8, 32));
-        block.branch(expr_const(operand.imm() as u64, 32));
+        block.assign(
+            scalar("$ra", mode.bits()),
+            expr_const(instruction.address + 8, mode.bits()),
+        );
+        block.branch(expr_const(operand.imm() as u64, mode.bits()));
 
         block.index()
     };
@@ -491,16 +832,20 @@ pub fn bal(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn bgezal(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
-    let lhs = get_register(detail.operands[0].reg())?.expression();
-    let zero = expr_const(0, 32);
-    let target = expr_const(detail.operands[1].imm() as u64, 32);
+    let lhs = get_register(detail.operands[0].reg())?.expression(mode);
+    let zero = expr_const(0, mode.bits());
+    let target = expr_const(detail.operands[1].imm() as u64, mode.bits());
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
-        block.assign(scalar("$ra", 32), expr_const(instruction.address + 8, 32));
+        block.assign(
+            scalar("$ra", mode.bits()),
+            expr_const(instruction.address + 8, mode.bits()),
+        );
         block.index()
     };
 
@@ -535,16 +880,20 @@ pub fn bgezal(
 pub fn bltzal(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
-    let lhs = get_register(detail.operands[0].reg())?.expression();
-    let zero = expr_const(0, 32);
-    let target = expr_const(detail.operands[1].imm() as u64, 32);
+    let lhs = get_register(detail.operands[0].reg())?.expression(mode);
+    let zero = expr_const(0, mode.bits());
+    let target = expr_const(detail.operands[1].imm() as u64, mode.bits());
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
-        block.assign(scalar("$ra", 32), expr_const(instruction.address + 8, 32));
+        block.assign(
+            scalar("$ra", mode.bits()),
+            expr_const(instruction.address + 8, mode.bits()),
+        );
         block.index()
     };
 
@@ -598,17 +947,20 @@ pub fn break_(
     Ok(())
 }
 
-pub fn clo(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
-    let detail = details(instruction)?;
-
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
+/// Counts the leading ones, or zeros, of `rs` into `rd`.
+fn count_leading(
+    control_flow_graph: &mut ControlFlowGraph,
+    rd: Scalar,
+    rs: Expr,
+    ones: bool,
+) -> Result<()> {
+    let bits = rs.bits();
 
     let (head_index, count) = {
-        let count = control_flow_graph.temp(32);
+        let count = control_flow_graph.temp(bits);
         let block = control_flow_graph.new_block()?;
 
-        block.assign(count.clone(), expr_const(0, 32));
+        block.assign(count.clone(), expr_const(0, bits));
 
         (block.index(), count)
     };
@@ -620,7 +972,7 @@ pub fn clo(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 
         block.assign(
             count.clone(),
-            Expr::add(count.clone().into(), expr_const(1, 32))?,
+            Expr::add(count.clone().into(), expr_const(1, bits))?,
         );
 
         block.index()
@@ -629,31 +981,37 @@ pub fn clo(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     let terminating_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, count.clone().into());
+        let rd_bits = rd.bits();
+        block.assign(rd, resize(count.clone().into(), rd_bits, false)?);
 
         block.index()
     };
 
     control_flow_graph.unconditional_edge(head_index, check_index)?;
-    let condition = Expr::trun(
+    let bit_set = Expr::trun(
         1,
-        Expr::shr(rs, Expr::sub(expr_const(31, 32), count.clone().into())?)?,
-    )?;
-    control_flow_graph.conditional_edge(check_index, inc_index, condition.clone())?;
-    control_flow_graph.conditional_edge(
-        check_index,
-        terminating_index,
-        Expr::cmpeq(condition, expr_const(0, 1))?,
+        Expr::shr(
+            rs,
+            Expr::sub(expr_const(bits as u64 - 1, bits), count.clone().into())?,
+        )?,
     )?;
+    let bit_clear = Expr::cmpeq(bit_set.clone(), expr_const(0, 1))?;
+    let (counted, uncounted) = if ones {
+        (bit_set, bit_clear)
+    } else {
+        (bit_clear, bit_set)
+    };
+    control_flow_graph.conditional_edge(check_index, inc_index, counted)?;
+    control_flow_graph.conditional_edge(check_index, terminating_index, uncounted)?;
     control_flow_graph.conditional_edge(
         inc_index,
         terminating_index,
-        Expr::cmpeq(count.clone().into(), expr_const(32, 32))?,
+        Expr::cmpeq(count.clone().into(), expr_const(bits as u64, bits))?,
     )?;
     control_flow_graph.conditional_edge(
         inc_index,
         check_index,
-        Expr::cmpneq(count.into(), expr_const(32, 32))?,
+        Expr::cmpneq(count.into(), expr_const(bits as u64, bits))?,
     )?;
 
     control_flow_graph.set_entry(head_index)?;
@@ -662,82 +1020,773 @@ pub fn clo(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
-pub fn clz(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+/// Lifts `c.cond.fmt`, which sets the floating-point condition code `$fcc0`.
+pub fn c_cond_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
+    // get operands
+    let formats = formats(instruction);
+    let bits = format_bits(formats.get(1).ok_or("Unknown floating-point format")?)?;
+    let lhs = fpu_read(detail.operands[0].reg(), bits, mode)?;
+    let rhs = fpu_read(detail.operands[1].reg(), bits, mode)?;
+
+    // The low three bits of the condition field select whether the condition
+    // holds when the operands are unordered, equal and less than. The
+    // signalling conditions only differ in the exceptions they raise.
+    let condition = match formats[0] {
+        "f" | "sf" => 0,
+        "un" | "ngle" => 1,
+        "eq" | "seq" => 2,
+        "ueq" | "ngl" => 3,
+        "olt" | "lt" => 4,
+        "ult" | "nge" => 5,
+        "ole" | "le" => 6,
+        "ule" | "ngt" => 7,
+        _ => bail!("Unknown floating-point condition {}", formats[0]),
+    };
 
-    let (head_index, count) = {
-        let count = control_flow_graph.temp(32);
+    let mut value = expr_const(0, 1);
+    if condition & 1 != 0 {
+        value = Expr::or(value, Expr::fcmpuno(lhs.clone(), rhs.clone())?)?;
+    }
+    if condition & 2 != 0 {
+        value = Expr::or(value, Expr::fcmpeq(lhs.clone(), rhs.clone())?)?;
+    }
+    if condition & 4 != 0 {
+        value = Expr::or(value, Expr::fcmplt(lhs, rhs)?)?;
+    }
+
+    let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(count.clone(), expr_const(0, 32));
+        block.assign(fcc(0), value);
 
-        (block.index(), count)
+        block.index()
     };
 
-    let check_index = { control_flow_graph.new_block()?.index() };
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
 
-    let inc_index = {
-        let block = control_flow_graph.new_block()?;
+    Ok(())
+}
 
-        block.assign(
-            count.clone(),
-            Expr::add(count.clone().into(), expr_const(1, 32))?,
-        );
+/// Lifts `cfc1`. The condition codes of the FCSR, control register 31, are
+/// read from the `$fcc` scalars, and the other control registers are read
+/// from scalars named `$fcr<n>`.
+pub fn cfc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
 
-        block.index()
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let fcr = (detail.operands[1].reg() as u32).wrapping_sub(mips_reg::MIPS_REG_0 as u32);
+
+    let value = if fcr == 31 {
+        let mut value = Expr::and(expr_scalar("$fcsr", 32), expr_const(0x017f_ffff, 32))?;
+        for index in 0..8 {
+            value = Expr::or(
+                value,
+                Expr::shl(
+                    Expr::zext(32, fcc(index).into())?,
+                    expr_const(fcsr_fcc_bit(index), 32),
+                )?,
+            )?;
+        }
+        value
+    } else {
+        expr_scalar(format!("$fcr{}", fcr), 32)
     };
 
-    let terminating_index = {
+    let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, count.clone().into());
+        block.assign(dst, sign_extend_word(value, mode)?);
 
         block.index()
     };
 
-    control_flow_graph.unconditional_edge(head_index, check_index)?;
-    let condition = Expr::trun(
-        1,
-        Expr::shr(rs, Expr::sub(expr_const(31, 32), count.clone().into())?)?,
-    )?;
-    control_flow_graph.conditional_edge(
-        check_index,
-        inc_index,
-        Expr::cmpeq(condition.clone(), expr_const(0, 1))?,
-    )?;
-    control_flow_graph.conditional_edge(check_index, terminating_index, condition)?;
-    control_flow_graph.conditional_edge(
-        inc_index,
-        terminating_index,
-        Expr::cmpeq(count.clone().into(), expr_const(32, 32))?,
-    )?;
-    control_flow_graph.conditional_edge(
-        inc_index,
-        check_index,
-        Expr::cmpneq(count.into(), expr_const(32, 32))?,
-    )?;
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn clo(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+
+    count_leading(control_flow_graph, rd, rs, true)
+}
+
+pub fn clz(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+
+    count_leading(control_flow_graph, rd, rs, false)
+}
+
+/// Lifts `ctc1`, the inverse of `cfc1`.
+pub fn ctc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let src = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let fcr = (detail.operands[1].reg() as u32).wrapping_sub(mips_reg::MIPS_REG_0 as u32);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        if fcr == 31 {
+            block.assign(scalar("$fcsr", 32), src.clone());
+            for index in 0..8 {
+                block.assign(
+                    fcc(index),
+                    Expr::trun(
+                        1,
+                        Expr::shr(src.clone(), expr_const(fcsr_fcc_bit(index), 32))?,
+                    )?,
+                );
+            }
+        } else {
+            block.assign(scalar(format!("$fcr{}", fcr), 32), src);
+        }
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Lifts `cvt.fmt.fmt`. Conversions to integers round to nearest, the default
+/// rounding mode.
+pub fn cvt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_convert(control_flow_graph, instruction, mode, |bits, src| {
+        Expr::ftoi(bits, Expr::fround(src)?)
+    })
+}
+
+pub fn dadd(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
+
+    let result = Expr::add(rs.clone(), rt.clone())?;
+    let overflow = add_overflow(rs, rt)?;
+
+    checked_assign(control_flow_graph, instruction, rd, result, overflow)
+}
+
+pub fn daddi(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let imm = expr_const(detail.operands[2].imm() as u64, mode.bits());
+
+    let result = Expr::add(rs.clone(), imm.clone())?;
+    let overflow = add_overflow(rs, imm)?;
+
+    checked_assign(control_flow_graph, instruction, rt, result, overflow)
+}
+
+pub fn daddiu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let imm = expr_const(detail.operands[2].imm() as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rt, Expr::add(rs, imm)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn daddu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::add(rs, rt)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dclo(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+
+    count_leading(control_flow_graph, rd, rs, true)
+}
+
+pub fn dclz(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+
+    count_leading(control_flow_graph, rd, rs, false)
+}
+
+pub fn ddiv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rs = get_register(detail.operands[0].reg())?.expression(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(
+            scalar("$lo", mode.bits()),
+            Expr::divs(rs.clone(), rt.clone())?,
+        );
+        block.assign(scalar("$hi", mode.bits()), Expr::mods(rs, rt)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn ddivu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rs = get_register(detail.operands[0].reg())?.expression(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(
+            scalar("$lo", mode.bits()),
+            Expr::divu(rs.clone(), rt.clone())?,
+        );
+        block.assign(scalar("$hi", mode.bits()), Expr::modu(rs, rt)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dmfc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let src = fpu_read(detail.operands[1].reg(), 64, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(dst, src);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dmtc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let src = get_register(detail.operands[0].reg())?.expression(mode);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        fpu_write(block, detail.operands[1].reg(), src, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dmult(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rs = get_register(detail.operands[0].reg())?.expression(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let tmp = Scalar::temp(instruction.address, 128);
+        block.assign(
+            tmp.clone(),
+            Expr::mul(Expr::sext(128, rs)?, Expr::sext(128, rt)?)?,
+        );
+        block.assign(
+            scalar("$hi", mode.bits()),
+            Expr::trun(64, Expr::shr(tmp.clone().into(), expr_const(64, 128))?)?,
+        );
+        block.assign(scalar("$lo", mode.bits()), Expr::trun(64, tmp.into())?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dmultu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rs = get_register(detail.operands[0].reg())?.expression(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        let tmp = Scalar::temp(instruction.address, 128);
+        block.assign(
+            tmp.clone(),
+            Expr::mul(Expr::zext(128, rs)?, Expr::zext(128, rt)?)?,
+        );
+        block.assign(
+            scalar("$hi", mode.bits()),
+            Expr::trun(64, Expr::shr(tmp.clone().into(), expr_const(64, 128))?)?,
+        );
+        block.assign(scalar("$lo", mode.bits()), Expr::trun(64, tmp.into())?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsll(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let sa = expr_const(detail.operands[2].imm() as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::shl(rt, sa)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsll32(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let sa = expr_const(detail.operands[2].imm() as u64 + 32, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::shl(rt, sa)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsllv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let rs = get_register(detail.operands[2].reg())?.expression(mode);
+    let rs = Expr::and(rs, expr_const(0x3f, mode.bits()))?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::shl(rt, rs)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsra(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let sa = expr_const(detail.operands[2].imm() as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::sra(rt, sa)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsra32(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let sa = expr_const(detail.operands[2].imm() as u64 + 32, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::sra(rt, sa)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsrav(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let rs = get_register(detail.operands[2].reg())?.expression(mode);
+    let rs = Expr::and(rs, expr_const(0x3f, mode.bits()))?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::sra(rt, rs)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
 
-    control_flow_graph.set_entry(head_index)?;
-    control_flow_graph.set_exit(terminating_index)?;
+    Ok(())
+}
+
+pub fn dsrl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let sa = expr_const(detail.operands[2].imm() as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::shr(rt, sa)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsrl32(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let sa = expr_const(detail.operands[2].imm() as u64 + 32, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::shr(rt, sa)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsrlv(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
+    let rs = get_register(detail.operands[2].reg())?.expression(mode);
+    let rs = Expr::and(rs, expr_const(0x3f, mode.bits()))?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::shr(rt, rs)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn dsub(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
+
+    let result = Expr::sub(rs.clone(), rt.clone())?;
+    let overflow = sub_overflow(rs, rt)?;
+
+    checked_assign(control_flow_graph, instruction, rd, result, overflow)
+}
+
+pub fn dsubu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(rd, Expr::sub(rs, rt)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
 
     Ok(())
 }
 
-pub fn div(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn div(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let lhs = get_register(detail.operands[0].reg())?.expression();
-    let rhs = get_register(detail.operands[1].reg())?.expression();
+    let lhs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rhs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(scalar("$lo", 32), Expr::divs(lhs.clone(), rhs.clone())?);
-        block.assign(scalar("$hi", 32), Expr::mods(lhs, rhs)?);
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::divs(lhs.clone(), rhs.clone())?, mode)?,
+        );
+        block.assign(
+            scalar("$hi", mode.bits()),
+            sign_extend_word(Expr::mods(lhs, rhs)?, mode)?,
+        );
 
         block.index()
     };
@@ -748,21 +1797,36 @@ pub fn div(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
+pub fn div_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_binary(control_flow_graph, instruction, mode, Expr::fdiv)
+}
+
 pub fn divu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let lhs = get_register(detail.operands[0].reg())?.expression();
-    let rhs = get_register(detail.operands[1].reg())?.expression();
+    let lhs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rhs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(scalar("$lo", 32), Expr::divu(lhs.clone(), rhs.clone())?);
-        block.assign(scalar("$hi", 32), Expr::modu(lhs, rhs)?);
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::divu(lhs.clone(), rhs.clone())?, mode)?,
+        );
+        block.assign(
+            scalar("$hi", mode.bits()),
+            sign_extend_word(Expr::modu(lhs, rhs)?, mode)?,
+        );
 
         block.index()
     };
@@ -782,10 +1846,14 @@ pub fn j(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Resu
     Ok(())
 }
 
-pub fn jr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn jr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
-    let target = get_register(detail.operands[0].reg())?.expression();
+    let target = get_register(detail.operands[0].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -801,14 +1869,21 @@ pub fn jr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn jal(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn jal(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(scalar("$ra", 32), expr_const(instruction.address + 8, 32));
-        block.branch(expr_const(detail.operands[0].imm() as u64, 32));
+        block.assign(
+            scalar("$ra", mode.bits()),
+            expr_const(instruction.address + 8, mode.bits()),
+        );
+        block.branch(expr_const(detail.operands[0].imm() as u64, mode.bits()));
 
         block.index()
     };
@@ -822,15 +1897,19 @@ pub fn jal(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn jalr(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
-    let target = get_register(detail.operands[0].reg())?.expression();
+    let target = get_register(detail.operands[0].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(scalar("$ra", 32), expr_const(instruction.address + 8, 32));
+        block.assign(
+            scalar("$ra", mode.bits()),
+            expr_const(instruction.address + 8, mode.bits()),
+        );
         block.branch(target);
 
         block.index()
@@ -842,20 +1921,24 @@ pub fn jalr(
     Ok(())
 }
 
-pub fn lb(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
         let temp = Scalar::temp(instruction.address, 8);
         block.load(temp.clone(), Expr::add(base, offset)?);
-        block.assign(dst, Expr::sext(32, temp.into())?);
+        block.assign(dst, Expr::sext(mode.bits(), temp.into())?);
 
         block.index()
     };
@@ -866,20 +1949,78 @@ pub fn lb(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn lbu(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lbu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
         let temp = Scalar::temp(instruction.address, 8);
         block.load(temp.clone(), Expr::add(base, offset)?);
-        block.assign(dst, Expr::zext(32, temp.into())?);
+        block.assign(dst, Expr::zext(mode.bits(), temp.into())?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn ld(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.load(rt, Expr::add(base, offset)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn ldc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let temp = Scalar::temp(instruction.address, 64);
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.load(temp.clone(), Expr::add(base, offset)?);
+        fpu_write(block, detail.operands[0].reg(), temp.into(), mode)?;
 
         block.index()
     };
@@ -890,20 +2031,24 @@ pub fn lbu(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
-pub fn lh(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
         let temp = Scalar::temp(instruction.address, 16);
         block.load(temp.clone(), Expr::add(base, offset)?);
-        block.assign(dst, Expr::sext(32, temp.into())?);
+        block.assign(dst, Expr::sext(mode.bits(), temp.into())?);
 
         block.index()
     };
@@ -914,20 +2059,24 @@ pub fn lh(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn lhu(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lhu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
         let temp = Scalar::temp(instruction.address, 16);
         block.load(temp.clone(), Expr::add(base, offset)?);
-        block.assign(dst, Expr::zext(32, temp.into())?);
+        block.assign(dst, Expr::zext(mode.bits(), temp.into())?);
 
         block.index()
     };
@@ -939,18 +2088,49 @@ pub fn lhu(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 }
 
 // This is identical to lw
-pub fn ll(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn ll(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        load_word(block, instruction, dst, Expr::add(base, offset)?, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+// This is identical to ld
+pub fn lld(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.load(dst, Expr::add(base, offset)?);
+        block.load(rt, Expr::add(base, offset)?);
 
         block.index()
     };
@@ -961,12 +2141,17 @@ pub fn ll(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn lui(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lui(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.scalar();
-    let imm = expr_const((detail.operands[1].imm() as u64) << 16, 32);
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let imm = ((detail.operands[1].imm() as u32) << 16) as i32 as u64;
+    let imm = expr_const(imm, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -982,18 +2167,50 @@ pub fn lui(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
-pub fn lw(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lw(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        load_word(block, instruction, dst, Expr::add(base, offset)?, mode)?;
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn lwc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let temp = Scalar::temp(instruction.address, 32);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.load(dst, Expr::add(base, offset)?);
+        block.load(temp.clone(), Expr::add(base, offset)?);
+        fpu_write(block, detail.operands[0].reg(), temp.into(), mode)?;
 
         block.index()
     };
@@ -1004,13 +2221,17 @@ pub fn lw(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn lwl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lwl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1020,19 +2241,19 @@ pub fn lwl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
         // get the number of bits to clear
         let bytes_to_clear = Expr::sub(
             expr_const(4, 32),
-            Expr::and(expr_const(3, 32), address.clone())?,
+            Expr::and(expr_const(3, 32), word(address.clone())?)?,
         )?;
         let bits_to_clear = Expr::shl(bytes_to_clear, expr_const(3, 32))?;
 
         // get the number of bytes to shift the result
-        let bytes_to_shift = Expr::and(expr_const(3, 32), address.clone())?;
+        let bytes_to_shift = Expr::and(expr_const(3, 32), word(address.clone())?)?;
         let bits_to_shift = Expr::shl(bytes_to_shift, expr_const(3, 32))?;
 
         let tmp = Scalar::temp(instruction.address, 32);
         block.load(tmp.clone(), address);
 
         // clear the dst register by shifting left then right
-        let dst_expr = Expr::shl(dst.clone().into(), bits_to_clear.clone())?;
+        let dst_expr = Expr::shl(word(dst.clone().into())?, bits_to_clear.clone())?;
         let dst_expr = Expr::shr(dst_expr, bits_to_clear)?;
 
         // zero out the right bits in the loaded word
@@ -1041,7 +2262,7 @@ pub fn lwl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
         // or together
         let dst_expr = Expr::or(dst_expr, tmp)?;
 
-        block.assign(dst, dst_expr);
+        block.assign(dst, sign_extend_word(dst_expr, mode)?);
 
         block.index()
     };
@@ -1052,21 +2273,25 @@ pub fn lwl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
-pub fn lwr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn lwr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        let address = Expr::sub(Expr::add(base, offset)?, expr_const(3, 32))?;
+        let address = Expr::sub(Expr::add(base, offset)?, expr_const(3, mode.bits()))?;
 
         // create a bit mask for dst and the loaded result
-        let mask_bytes = Expr::and(address.clone(), expr_const(3, 32))?;
+        let mask_bytes = Expr::and(word(address.clone())?, expr_const(3, 32))?;
         let mask_bits = Expr::shl(mask_bytes, expr_const(3, 32))?;
         let mask_bit = Expr::shl(expr_const(1, 32), mask_bits)?;
         let mask = Expr::sub(mask_bit, expr_const(1, 32))?;
@@ -1078,15 +2303,43 @@ pub fn lwr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
         // we want to and this word with our mask to remove the high bits
         let temp = Expr::and(tmp.into(), mask.clone())?;
 
-        // and out the bits we're about to set in dst
-        let dst_expr = Expr::and(
-            dst.clone().into(),
-            Expr::sub(expr_const(0xffff_ffff, 32), mask)?,
-        )?;
+        // and out the bits we're about to set in dst
+        let dst_expr = Expr::and(
+            word(dst.clone().into())?,
+            Expr::sub(expr_const(0xffff_ffff, 32), mask)?,
+        )?;
+
+        let dst_expr = Expr::or(dst_expr, temp)?;
+
+        block.assign(dst, sign_extend_word(dst_expr, mode)?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn lwu(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
-        let dst_expr = Expr::or(dst_expr, temp)?;
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
 
-        block.assign(dst, dst_expr);
+        let temp = Scalar::temp(instruction.address, 32);
+        block.load(temp.clone(), Expr::add(base, offset)?);
+        block.assign(rt, Expr::zext(mode.bits(), temp.into())?);
 
         block.index()
     };
@@ -1100,12 +2353,13 @@ pub fn lwr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn madd(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1118,18 +2372,30 @@ pub fn madd(
         );
         block.assign(
             tmp1.clone(),
-            Expr::shl(Expr::zext(64, expr_scalar("$hi", 32))?, expr_const(32, 64))?,
+            Expr::shl(
+                Expr::zext(64, word(expr_scalar("$hi", mode.bits()))?)?,
+                expr_const(32, 64),
+            )?,
         );
         block.assign(
             tmp1.clone(),
-            Expr::or(tmp1.clone().into(), Expr::zext(64, expr_scalar("$lo", 32))?)?,
+            Expr::or(
+                tmp1.clone().into(),
+                Expr::zext(64, word(expr_scalar("$lo", mode.bits()))?)?,
+            )?,
         );
         block.assign(tmp0.clone(), Expr::add(tmp0.clone().into(), tmp1.into())?);
         block.assign(
-            scalar("$hi", 32),
-            Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+            scalar("$hi", mode.bits()),
+            sign_extend_word(
+                Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+                mode,
+            )?,
+        );
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::trun(32, tmp0.into())?, mode)?,
         );
-        block.assign(scalar("$lo", 32), Expr::trun(32, tmp0.into())?);
 
         block.index()
     };
@@ -1143,12 +2409,13 @@ pub fn madd(
 pub fn maddu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1161,18 +2428,55 @@ pub fn maddu(
         );
         block.assign(
             tmp1.clone(),
-            Expr::shl(Expr::zext(64, expr_scalar("$hi", 32))?, expr_const(32, 64))?,
+            Expr::shl(
+                Expr::zext(64, word(expr_scalar("$hi", mode.bits()))?)?,
+                expr_const(32, 64),
+            )?,
         );
         block.assign(
             tmp1.clone(),
-            Expr::or(tmp1.clone().into(), Expr::zext(64, expr_scalar("$lo", 32))?)?,
+            Expr::or(
+                tmp1.clone().into(),
+                Expr::zext(64, word(expr_scalar("$lo", mode.bits()))?)?,
+            )?,
         );
         block.assign(tmp0.clone(), Expr::add(tmp0.clone().into(), tmp1.into())?);
         block.assign(
-            scalar("$hi", 32),
-            Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+            scalar("$hi", mode.bits()),
+            sign_extend_word(
+                Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+                mode,
+            )?,
+        );
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::trun(32, tmp0.into())?, mode)?,
         );
-        block.assign(scalar("$lo", 32), Expr::trun(32, tmp0.into())?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn mfc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let src = fpu_read(detail.operands[1].reg(), 32, mode)?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.assign(dst, sign_extend_word(src, mode)?);
 
         block.index()
     };
@@ -1186,16 +2490,17 @@ pub fn maddu(
 pub fn mfhi(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, expr_scalar("$hi", 32));
+        block.assign(rd, expr_scalar("$hi", mode.bits()));
 
         block.index()
     };
@@ -1209,16 +2514,17 @@ pub fn mfhi(
 pub fn mflo(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, expr_scalar("$lo", 32));
+        block.assign(rd, expr_scalar("$lo", mode.bits()));
 
         block.index()
     };
@@ -1229,15 +2535,24 @@ pub fn mflo(
     Ok(())
 }
 
+pub fn mov_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_unary(control_flow_graph, instruction, mode, Ok)
+}
+
 pub fn move_(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let src = get_register(detail.operands[1].reg())?.expression();
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let src = get_register(detail.operands[1].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1256,13 +2571,14 @@ pub fn move_(
 pub fn movn(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -1285,13 +2601,13 @@ pub fn movn(
     control_flow_graph.conditional_edge(
         head_index,
         op_index,
-        Expr::cmpneq(rt.clone(), expr_const(0, 32))?,
+        Expr::cmpneq(rt.clone(), expr_const(0, mode.bits()))?,
     )?;
 
     control_flow_graph.conditional_edge(
         head_index,
         terminating_index,
-        Expr::cmpeq(rt, expr_const(0, 32))?,
+        Expr::cmpeq(rt, expr_const(0, mode.bits()))?,
     )?;
 
     control_flow_graph.unconditional_edge(op_index, terminating_index)?;
@@ -1305,13 +2621,14 @@ pub fn movn(
 pub fn movz(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -1334,13 +2651,13 @@ pub fn movz(
     control_flow_graph.conditional_edge(
         head_index,
         op_index,
-        Expr::cmpeq(rt.clone(), expr_const(0, 32))?,
+        Expr::cmpeq(rt.clone(), expr_const(0, mode.bits()))?,
     )?;
 
     control_flow_graph.conditional_edge(
         head_index,
         terminating_index,
-        Expr::cmpneq(rt, expr_const(0, 32))?,
+        Expr::cmpneq(rt, expr_const(0, mode.bits()))?,
     )?;
 
     control_flow_graph.unconditional_edge(op_index, terminating_index)?;
@@ -1354,12 +2671,13 @@ pub fn movz(
 pub fn msub(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1372,18 +2690,30 @@ pub fn msub(
         );
         block.assign(
             tmp1.clone(),
-            Expr::shl(Expr::zext(64, expr_scalar("$hi", 32))?, expr_const(32, 64))?,
+            Expr::shl(
+                Expr::zext(64, word(expr_scalar("$hi", mode.bits()))?)?,
+                expr_const(32, 64),
+            )?,
         );
         block.assign(
             tmp1.clone(),
-            Expr::or(tmp1.clone().into(), Expr::zext(64, expr_scalar("$lo", 32))?)?,
+            Expr::or(
+                tmp1.clone().into(),
+                Expr::zext(64, word(expr_scalar("$lo", mode.bits()))?)?,
+            )?,
         );
         block.assign(tmp0.clone(), Expr::sub(tmp0.clone().into(), tmp1.into())?);
         block.assign(
-            scalar("$hi", 32),
-            Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+            scalar("$hi", mode.bits()),
+            sign_extend_word(
+                Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+                mode,
+            )?,
+        );
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::trun(32, tmp0.into())?, mode)?,
         );
-        block.assign(scalar("$lo", 32), Expr::trun(32, tmp0.into())?);
 
         block.index()
     };
@@ -1397,12 +2727,13 @@ pub fn msub(
 pub fn msubu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1415,18 +2746,54 @@ pub fn msubu(
         );
         block.assign(
             tmp1.clone(),
-            Expr::shl(Expr::zext(64, expr_scalar("$hi", 32))?, expr_const(32, 64))?,
+            Expr::shl(
+                Expr::zext(64, word(expr_scalar("$hi", mode.bits()))?)?,
+                expr_const(32, 64),
+            )?,
         );
         block.assign(
             tmp1.clone(),
-            Expr::or(tmp1.clone().into(), Expr::zext(64, expr_scalar("$lo", 32))?)?,
+            Expr::or(
+                tmp1.clone().into(),
+                Expr::zext(64, word(expr_scalar("$lo", mode.bits()))?)?,
+            )?,
         );
         block.assign(tmp0.clone(), Expr::sub(tmp0.clone().into(), tmp1.into())?);
         block.assign(
-            scalar("$hi", 32),
-            Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+            scalar("$hi", mode.bits()),
+            sign_extend_word(
+                Expr::trun(32, Expr::shr(tmp0.clone().into(), expr_const(32, 64))?)?,
+                mode,
+            )?,
+        );
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::trun(32, tmp0.into())?, mode)?,
         );
-        block.assign(scalar("$lo", 32), Expr::trun(32, tmp0.into())?);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn mtc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let src = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        fpu_write(block, detail.operands[1].reg(), src, mode)?;
 
         block.index()
     };
@@ -1440,16 +2807,17 @@ pub fn msubu(
 pub fn mthi(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
+    let rs = get_register(detail.operands[0].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(scalar("$hi", 32), rs);
+        block.assign(scalar("$hi", mode.bits()), rs);
 
         block.index()
     };
@@ -1463,16 +2831,17 @@ pub fn mthi(
 pub fn mtlo(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
+    let rs = get_register(detail.operands[0].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(scalar("$lo", 32), rs);
+        block.assign(scalar("$lo", mode.bits()), rs);
 
         block.index()
     };
@@ -1483,21 +2852,23 @@ pub fn mtlo(
     Ok(())
 }
 
-pub fn mul(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn mul(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(
-            rd,
-            Expr::trun(32, Expr::mul(Expr::sext(64, rs)?, Expr::sext(64, rt)?)?)?,
-        );
+        let product = Expr::mul(Expr::sext(64, rs)?, Expr::sext(64, rt)?)?;
+        block.assign(rd, sign_extend_word(Expr::trun(32, product)?, mode)?);
 
         block.index()
     };
@@ -1508,15 +2879,24 @@ pub fn mul(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
+pub fn mul_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_binary(control_flow_graph, instruction, mode, Expr::fmul)
+}
+
 pub fn mult(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1527,10 +2907,16 @@ pub fn mult(
             Expr::mul(Expr::sext(64, rs)?, Expr::sext(64, rt)?)?,
         );
         block.assign(
-            scalar("$hi", 32),
-            Expr::trun(32, Expr::shr(tmp.clone().into(), expr_const(32, 64))?)?,
+            scalar("$hi", mode.bits()),
+            sign_extend_word(
+                Expr::trun(32, Expr::shr(tmp.clone().into(), expr_const(32, 64))?)?,
+                mode,
+            )?,
+        );
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::trun(32, tmp.into())?, mode)?,
         );
-        block.assign(scalar("$lo", 32), Expr::trun(32, tmp.into())?);
 
         block.index()
     };
@@ -1544,12 +2930,13 @@ pub fn mult(
 pub fn multu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rs = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1560,10 +2947,16 @@ pub fn multu(
             Expr::mul(Expr::zext(64, rs)?, Expr::zext(64, rt)?)?,
         );
         block.assign(
-            scalar("$hi", 32),
-            Expr::trun(32, Expr::shr(tmp.clone().into(), expr_const(32, 64))?)?,
+            scalar("$hi", mode.bits()),
+            sign_extend_word(
+                Expr::trun(32, Expr::shr(tmp.clone().into(), expr_const(32, 64))?)?,
+                mode,
+            )?,
+        );
+        block.assign(
+            scalar("$lo", mode.bits()),
+            sign_extend_word(Expr::trun(32, tmp.into())?, mode)?,
         );
-        block.assign(scalar("$lo", 32), Expr::trun(32, tmp.into())?);
 
         block.index()
     };
@@ -1574,20 +2967,35 @@ pub fn multu(
     Ok(())
 }
 
+pub fn neg_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_unary(control_flow_graph, instruction, mode, |src| {
+        let bits = src.bits();
+        Expr::xor(src, expr_const(1 << (bits - 1), bits))
+    })
+}
+
 pub fn negu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, Expr::sub(expr_const(0, 32), rs)?);
+        block.assign(
+            rd,
+            sign_extend_word(Expr::sub(expr_const(0, 32), rs)?, mode)?,
+        );
 
         block.index()
     };
@@ -1613,20 +3021,24 @@ pub fn nop(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Re
     Ok(())
 }
 
-pub fn nor(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn nor(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
         block.assign(
             rd,
-            Expr::xor(Expr::or(rs, rt)?, expr_const(0xffff_ffff, 32))?,
+            Expr::xor(Expr::or(rs, rt)?, expr_const(mode.mask(), mode.bits()))?,
         );
 
         block.index()
@@ -1638,13 +3050,17 @@ pub fn nor(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
-pub fn or(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn or(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1660,13 +3076,17 @@ pub fn or(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn ori(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn ori(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let imm = expr_const(detail.operands[2].imm() as u64, 32);
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let imm = expr_const(detail.operands[2].imm() as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1685,11 +3105,12 @@ pub fn ori(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn rdhwr(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
-    let rt = get_register(detail.operands[0].reg())?.expression();
-    let rd = get_register(detail.operands[1].reg())?.expression();
+    let rt = get_register(detail.operands[0].reg())?.expression(mode);
+    let rd = get_register(detail.operands[1].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1711,13 +3132,17 @@ pub fn rdhwr(
     Ok(())
 }
 
-pub fn sb(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn sb(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let rt = get_register(detail.operands[0].reg())?.expression(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1734,13 +3159,17 @@ pub fn sb(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
 }
 
 // This is identical to sw
-pub fn sc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn sc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let rt = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let addr_expr = Expr::add(base, offset)?;
 
@@ -1750,8 +3179,40 @@ pub fn sc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
         block.store(addr_expr, rt);
         // a 1 is written to rt on success
         block.assign(
-            get_register(detail.operands[0].reg())?.scalar(),
-            expr_const(1, 32),
+            get_register(detail.operands[0].reg())?.scalar(mode),
+            expr_const(1, mode.bits()),
+        );
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+// This is identical to sd
+pub fn scd(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rt = get_register(detail.operands[0].reg())?.expression(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.store(Expr::add(base, offset)?, rt);
+        // a 1 is written to rt on success
+        block.assign(
+            get_register(detail.operands[0].reg())?.scalar(mode),
+            expr_const(1, mode.bits()),
         );
 
         block.index()
@@ -1763,13 +3224,69 @@ pub fn sc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn sh(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn sd(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let rt = get_register(detail.operands[0].reg())?.expression(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.store(Expr::add(base, offset)?, rt);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn sdc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let src = fpu_read(detail.operands[0].reg(), 64, mode)?;
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.store(Expr::add(base, offset)?, src);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn sh(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let rt = get_register(detail.operands[0].reg())?.expression(mode);
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -1785,18 +3302,22 @@ pub fn sh(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn sll(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn sll(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
     let sa = expr_const(detail.operands[2].imm() as u64, 32);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, Expr::shl(rt, sa)?);
+        block.assign(rd, sign_extend_word(Expr::shl(rt, sa)?, mode)?);
 
         block.index()
     };
@@ -1810,18 +3331,19 @@ pub fn sll(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn sllv(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rt = get_register(detail.operands[1].reg())?.expression();
-    let rs = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rs = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, Expr::shl(rt, rs)?);
+        block.assign(rd, sign_extend_word(Expr::shl(rt, rs)?, mode)?);
 
         block.index()
     };
@@ -1832,13 +3354,17 @@ pub fn sllv(
     Ok(())
 }
 
-pub fn slt(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn slt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -1851,7 +3377,7 @@ pub fn slt(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     let true_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd.clone(), expr_const(1, 32));
+        block.assign(rd.clone(), expr_const(1, mode.bits()));
 
         block.index()
     };
@@ -1859,7 +3385,7 @@ pub fn slt(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     let false_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, expr_const(0, 32));
+        block.assign(rd, expr_const(0, mode.bits()));
 
         block.index()
     };
@@ -1888,13 +3414,14 @@ pub fn slt(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn slti(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let imm = expr_const(detail.operands[2].imm() as u64, 32);
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let imm = expr_const(detail.operands[2].imm() as u64, mode.bits());
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -1907,7 +3434,7 @@ pub fn slti(
     let true_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rt.clone(), expr_const(1, 32));
+        block.assign(rt.clone(), expr_const(1, mode.bits()));
 
         block.index()
     };
@@ -1915,7 +3442,7 @@ pub fn slti(
     let false_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rt, expr_const(0, 32));
+        block.assign(rt, expr_const(0, mode.bits()));
 
         block.index()
     };
@@ -1944,13 +3471,14 @@ pub fn slti(
 pub fn sltiu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let imm = expr_const(detail.operands[2].imm() as u64, 32);
+    let rt = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let imm = expr_const(detail.operands[2].imm() as u64, mode.bits());
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -1963,7 +3491,7 @@ pub fn sltiu(
     let true_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rt.clone(), expr_const(1, 32));
+        block.assign(rt.clone(), expr_const(1, mode.bits()));
 
         block.index()
     };
@@ -1971,7 +3499,7 @@ pub fn sltiu(
     let false_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rt, expr_const(0, 32));
+        block.assign(rt, expr_const(0, mode.bits()));
 
         block.index()
     };
@@ -2000,13 +3528,14 @@ pub fn sltiu(
 pub fn sltu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -2019,7 +3548,7 @@ pub fn sltu(
     let true_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd.clone(), expr_const(1, 32));
+        block.assign(rd.clone(), expr_const(1, mode.bits()));
 
         block.index()
     };
@@ -2027,7 +3556,7 @@ pub fn sltu(
     let false_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, expr_const(0, 32));
+        block.assign(rd, expr_const(0, mode.bits()));
 
         block.index()
     };
@@ -2053,12 +3582,16 @@ pub fn sltu(
     Ok(())
 }
 
-pub fn sra(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn sra(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
     let sa = expr_const(detail.operands[2].imm() as u64, 32);
 
     let block_index = {
@@ -2074,7 +3607,10 @@ pub fn sra(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
             Expr::sub(expr_const(32, 32), sa.clone())?,
         )?;
         block.assign(temp.clone(), expr);
-        block.assign(rd, Expr::or(Expr::shr(rt, sa)?, temp.into())?);
+        block.assign(
+            rd,
+            sign_extend_word(Expr::or(Expr::shr(rt, sa)?, temp.into())?, mode)?,
+        );
 
         block.index()
     };
@@ -2088,13 +3624,14 @@ pub fn sra(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn srav(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rt = get_register(detail.operands[1].reg())?.expression();
-    let rs = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rs = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -2109,7 +3646,10 @@ pub fn srav(
             Expr::sub(expr_const(32, 32), rs.clone())?,
         )?;
         block.assign(temp.clone(), expr);
-        block.assign(rd, Expr::or(Expr::shr(rt, rs)?, temp.into())?);
+        block.assign(
+            rd,
+            sign_extend_word(Expr::or(Expr::shr(rt, rs)?, temp.into())?, mode)?,
+        );
 
         block.index()
     };
@@ -2120,18 +3660,22 @@ pub fn srav(
     Ok(())
 }
 
-pub fn srl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn srl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
     let sa = expr_const(detail.operands[2].imm() as u64, 32);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, Expr::shr(rt, sa)?);
+        block.assign(rd, sign_extend_word(Expr::shr(rt, sa)?, mode)?);
 
         block.index()
     };
@@ -2145,18 +3689,19 @@ pub fn srl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn srlv(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rt = get_register(detail.operands[1].reg())?.expression();
-    let rs = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rt = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rs = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, Expr::shr(rt, rs)?);
+        block.assign(rd, sign_extend_word(Expr::shr(rt, rs)?, mode)?);
 
         block.index()
     };
@@ -2167,13 +3712,17 @@ pub fn srlv(
     Ok(())
 }
 
-pub fn sub(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn sub(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -2201,7 +3750,10 @@ pub fn sub(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     let operation_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, Expr::sub(rs.clone(), rt.clone())?);
+        block.assign(
+            rd,
+            sign_extend_word(Expr::sub(rs.clone(), rt.clone())?, mode)?,
+        );
 
         block.index()
     };
@@ -2232,21 +3784,30 @@ pub fn sub(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
+pub fn sub_fmt(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_binary(control_flow_graph, instruction, mode, Expr::fsub)
+}
+
 pub fn subu(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = word(get_register(detail.operands[1].reg())?.expression(mode))?;
+    let rt = word(get_register(detail.operands[2].reg())?.expression(mode))?;
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(rd, Expr::sub(rs, rt)?);
+        block.assign(rd, sign_extend_word(Expr::sub(rs, rt)?, mode)?);
 
         block.index()
     };
@@ -2257,13 +3818,17 @@ pub fn subu(
     Ok(())
 }
 
-pub fn sw(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn sw(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let rt = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let addr_expr = Expr::add(base, offset)?;
 
@@ -2329,13 +3894,43 @@ pub fn sw(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn swl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn swc1(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+
+    // get operands
+    let src = fpu_read(detail.operands[0].reg(), 32, mode)?;
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
+
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+
+        block.store(Expr::add(base, offset)?, src);
+
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+pub fn swl(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let rt = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -2346,11 +3941,11 @@ pub fn swl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
         let tmp = Scalar::temp(instruction.address, 32);
         block.load(
             tmp.clone(),
-            Expr::and(expr_const(0xffff_fffc, 32), address.clone())?,
+            Expr::and(expr_const(mode.mask() & !3, mode.bits()), address.clone())?,
         );
 
         // create a mask for our value
-        let mask_bytes = Expr::and(address.clone(), expr_const(3, 32))?;
+        let mask_bytes = Expr::and(word(address.clone())?, expr_const(3, 32))?;
         // we want the opposite of the number of bytes we are storing
         let mask_bytes = Expr::sub(expr_const(4, 32), mask_bytes)?;
         let mask_bits = Expr::shl(mask_bytes, expr_const(3, 32))?;
@@ -2362,7 +3957,7 @@ pub fn swl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
         let tmp = Expr::and(Expr::sub(expr_const(0xffff_ffff, 32), mask)?, tmp.into())?;
 
         // figure out how many bits we should shift our value right
-        let shift_bytes = Expr::and(address.clone(), expr_const(3, 32))?;
+        let shift_bytes = Expr::and(word(address.clone())?, expr_const(3, 32))?;
         let shift_bits = Expr::shl(shift_bytes, expr_const(3, 32))?;
 
         // shift the value right
@@ -2372,7 +3967,10 @@ pub fn swl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
         let expr = Expr::or(tmp, rt)?;
 
         // store it back in memory
-        block.store(Expr::and(expr_const(0xffff_fffc, 32), address)?, expr);
+        block.store(
+            Expr::and(expr_const(mode.mask() & !3, mode.bits()), address)?,
+            expr,
+        );
 
         block.index()
     };
@@ -2383,21 +3981,25 @@ pub fn swl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
-pub fn swr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn swr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rt = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = expr_const(detail.operands[1].mem().disp as u64, 32);
+    let rt = word(get_register(detail.operands[0].reg())?.expression(mode))?;
+    let base = get_register(detail.operands[1].mem().base)?.expression(mode);
+    let offset = expr_const(detail.operands[1].mem().disp as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        let address = Expr::sub(Expr::add(base, offset)?, expr_const(3, 32))?;
+        let address = Expr::sub(Expr::add(base, offset)?, expr_const(3, mode.bits()))?;
 
         // create a bit mask for dst and the loaded result
-        let mask_bytes = Expr::and(address.clone(), expr_const(3, 32))?;
+        let mask_bytes = Expr::and(word(address.clone())?, expr_const(3, 32))?;
         let mask_bits = Expr::shl(mask_bytes, expr_const(3, 32))?;
         let mask_bit = Expr::shl(expr_const(1, 32), mask_bits)?;
         let mask = Expr::sub(mask_bit, expr_const(1, 32))?;
@@ -2457,12 +4059,16 @@ pub fn syscall(
     Ok(())
 }
 
-pub fn teq(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn teq(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rs = get_register(detail.operands[0].reg())?.expression();
-    let rt = get_register(detail.operands[1].reg())?.expression();
+    let rs = get_register(detail.operands[0].reg())?.expression(mode);
+    let rt = get_register(detail.operands[1].reg())?.expression(mode);
 
     let head_index = {
         let block = control_flow_graph.new_block()?;
@@ -2506,13 +4112,25 @@ pub fn teq(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
     Ok(())
 }
 
-pub fn xor(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn trunc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    fpu_convert(control_flow_graph, instruction, mode, Expr::ftoi)
+}
+
+pub fn xor(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let rt = get_register(detail.operands[2].reg())?.expression();
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rt = get_register(detail.operands[2].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -2531,13 +4149,14 @@ pub fn xor(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::In
 pub fn xori(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let rd = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let imm = expr_const(detail.operands[2].imm() as u64, 32);
+    let rd = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let imm = expr_const(detail.operands[2].imm() as u64, mode.bits());
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -16,23 +16,30 @@ macro_rules! backing {
     }};
 }
 
-fn init_driver_block<'d>(
+fn init_driver_block(
+    architecture: RC<dyn architecture::Architecture>,
     instruction_bytes: &[u8],
     scalars: Vec<(&str, Constant)>,
     memory_: Memory,
 ) -> Driver {
     let mut bytes = instruction_bytes.to_vec();
     // ori $a0, $a0, $a0
-    bytes.append(&mut vec![0x00, 0x84, 0x20, 0x25]);
+    match architecture.endian() {
+        Endian::Big => bytes.append(&mut vec![0x00, 0x84, 0x20, 0x25]),
+        Endian::Little => bytes.append(&mut vec![0x25, 0x20, 0x84, 0x00]),
+    }
 
-    let mut backing = memory::backing::Memory::new(Endian::Big);
+    let mut backing = memory::backing::Memory::new(architecture.endian());
     backing.set_memory(
         0,
         bytes.to_vec(),
         memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
     );
 
-    let function = Mips::new().translate_function(&backing, 0).unwrap();
+    let function = architecture
+        .translator()
+        .translate_function(&backing, 0)
+        .unwrap();
 
     let location = if function
         .control_flow_graph()
@@ -55,12 +62,7 @@ fn init_driver_block<'d>(
         state.set_scalar(scalar.0, scalar.1);
     }
 
-    Driver::new(
-        RC::new(program),
-        location,
-        state,
-        RC::new(architecture::Mips::new()),
-    )
+    Driver::new(RC::new(program), location, state, architecture)
 }
 
 fn init_driver_function(
@@ -89,13 +91,62 @@ fn init_driver_function(
     )
 }
 
+fn get_state_architecture(
+    architecture: RC<dyn architecture::Architecture>,
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+) -> State {
+    let mut driver = init_driver_block(architecture, instruction_bytes, scalars, memory);
+
+    while !driver
+        .location()
+        .apply(driver.program())
+        .unwrap()
+        .forward()
+        .unwrap()
+        .is_empty()
+    {
+        driver = driver.step().unwrap();
+    }
+
+    driver.state().clone()
+}
+
+/// Executes `instruction_bytes` with the big-endian MIPS64 translator.
+fn get_state_64(instruction_bytes: &[u8], scalars: Vec<(&str, Constant)>, memory: Memory) -> State {
+    get_state_architecture(
+        RC::new(architecture::Mips64::new()),
+        instruction_bytes,
+        scalars,
+        memory,
+    )
+}
+
+fn get_scalar_64(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+    result_scalar: &str,
+) -> Constant {
+    get_state_64(instruction_bytes, scalars, memory)
+        .get_scalar(result_scalar)
+        .unwrap()
+        .clone()
+}
+
 fn get_scalar(
     instruction_bytes: &[u8],
     scalars: Vec<(&str, Constant)>,
     memory: Memory,
     result_scalar: &str,
 ) -> Constant {
-    let mut driver = init_driver_block(instruction_bytes, scalars, memory);
+    let mut driver = init_driver_block(
+        RC::new(architecture::Mips::new()),
+        instruction_bytes,
+        scalars,
+        memory,
+    );
 
     while driver
         .location()
@@ -119,7 +170,21 @@ fn get_intrinsic(
     scalars: Vec<(&str, Constant)>,
     memory: Memory,
 ) -> Intrinsic {
-    let mut driver = init_driver_block(instruction_bytes, scalars, memory);
+    get_intrinsic_architecture(
+        RC::new(architecture::Mips::new()),
+        instruction_bytes,
+        scalars,
+        memory,
+    )
+}
+
+fn get_intrinsic_architecture(
+    architecture: RC<dyn architecture::Architecture>,
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+) -> Intrinsic {
+    let mut driver = init_driver_block(architecture, instruction_bytes, scalars, memory);
 
     loop {
         {
@@ -1215,6 +1280,95 @@ fn break_() {
     }
 }
 
+#[test]
+fn c_cond_fmt() {
+    /*
+    c.lt.d $f2, $f0
+    bc1t 0x10
+    nop
+    addiu $a1, $zero, 1
+    jr $ra
+    nop
+    */
+    let instruction_bytes = [
+        0x46, 0x20, 0x10, 0x3c, 0x45, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x24, 0x05, 0x00,
+        0x01, 0x03, 0xe0, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    let one = 1_f64.to_bits();
+    let two = 2_f64.to_bits();
+
+    let driver = init_driver_function(
+        backing!(instruction_bytes),
+        vec![
+            ("$a1", const_(0, 32)),
+            ("$f0", const_(two & 0xffff_ffff, 32)),
+            ("$f1", const_(two >> 32, 32)),
+            ("$f2", const_(one & 0xffff_ffff, 32)),
+            ("$f3", const_(one >> 32, 32)),
+        ],
+    );
+    let driver = step_to(driver, 0x10);
+    assert!(driver.state().get_scalar("$fcc0").unwrap().is_one());
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("$a1")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0
+    );
+
+    let driver = init_driver_function(
+        backing!(instruction_bytes),
+        vec![
+            ("$a1", const_(0, 32)),
+            ("$f0", const_(one & 0xffff_ffff, 32)),
+            ("$f1", const_(one >> 32, 32)),
+            ("$f2", const_(two & 0xffff_ffff, 32)),
+            ("$f3", const_(two >> 32, 32)),
+        ],
+    );
+    let driver = step_to(driver, 0x10);
+    assert!(driver.state().get_scalar("$fcc0").unwrap().is_zero());
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("$a1")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        1
+    );
+
+    // bc1f 0x10
+    let mut instruction_bytes = instruction_bytes;
+    instruction_bytes[7] = 0x02;
+    instruction_bytes[5] = 0x00;
+
+    let driver = init_driver_function(
+        backing!(instruction_bytes),
+        vec![
+            ("$a1", const_(0, 32)),
+            ("$f0", const_(one & 0xffff_ffff, 32)),
+            ("$f1", const_(one >> 32, 32)),
+            ("$f2", const_(two & 0xffff_ffff, 32)),
+            ("$f3", const_(two >> 32, 32)),
+        ],
+    );
+    let driver = step_to(driver, 0x10);
+    assert_eq!(
+        driver
+            .state()
+            .get_scalar("$a1")
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0
+    );
+}
+
 #[test]
 fn clo() {
     /*
@@ -1251,6 +1405,25 @@ fn clz() {
     assert_eq!(result.value_u64().unwrap(), 12);
 }
 
+#[test]
+fn ctc1() {
+    // ctc1 $a3, $31
+    // cfc1 $a1, $31
+    let state = get_state_architecture(
+        RC::new(architecture::Mips::new()),
+        &[0x44, 0xc7, 0xf8, 0x00, 0x44, 0x45, 0xf8, 0x00],
+        vec![("$a3", const_(0x0280_0003, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert!(state.get_scalar("$fcc0").unwrap().is_one());
+    assert!(state.get_scalar("$fcc1").unwrap().is_one());
+    assert!(state.get_scalar("$fcc2").unwrap().is_zero());
+    assert_eq!(
+        state.get_scalar("$a1").unwrap().value_u64().unwrap(),
+        0x0280_0003
+    );
+}
+
 #[test]
 fn div() {
     /*
@@ -1323,6 +1496,51 @@ fn divu() {
     assert_eq!(result.value_u64().unwrap(), 0x3ffffffb);
 }
 
+#[test]
+fn div_fmt() {
+    let mut memory = Memory::new(Endian::Big);
+    memory.store(0x1000, const_(7_f64.to_bits(), 64)).unwrap();
+    memory.store(0x1008, const_(2_f64.to_bits(), 64)).unwrap();
+
+    /*
+    ldc1 $f0, 0($a0)
+    ldc1 $f2, 8($a0)
+    div.d $f4, $f0, $f2
+    sdc1 $f4, 16($a0)
+    cvt.w.d $f6, $f4
+    trunc.w.d $f8, $f4
+    mfc1 $a1, $f6
+    mfc1 $a2, $f8
+    */
+    let state = get_state_architecture(
+        RC::new(architecture::Mips::new()),
+        &[
+            0xd4, 0x80, 0x00, 0x00, 0xd4, 0x82, 0x00, 0x08, 0x46, 0x22, 0x01, 0x03, 0xf4, 0x84,
+            0x00, 0x10, 0x46, 0x20, 0x21, 0xa4, 0x46, 0x20, 0x22, 0x0d, 0x44, 0x05, 0x30, 0x00,
+            0x44, 0x06, 0x40, 0x00,
+        ],
+        vec![("$a0", const_(0x1000, 32))],
+        memory,
+    );
+
+    assert_eq!(
+        state
+            .memory()
+            .load(0x1010, 64)
+            .unwrap()
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        3.5_f64.to_bits()
+    );
+    assert_eq!(
+        state.get_scalar("$f5").unwrap().value_u64().unwrap(),
+        3.5_f64.to_bits() >> 32
+    );
+    assert_eq!(state.get_scalar("$a1").unwrap().value_u64().unwrap(), 4);
+    assert_eq!(state.get_scalar("$a2").unwrap().value_u64().unwrap(), 3);
+}
+
 #[test]
 fn j() {
     /*
@@ -2036,6 +2254,42 @@ fn multu() {
     assert_eq!(result.value_u64().unwrap(), 1);
 }
 
+#[test]
+fn neg_fmt() {
+    /*
+    mtc1 $a1, $f0
+    cvt.s.w $f0, $f0
+    cvt.d.s $f2, $f0
+    neg.d $f2, $f2
+    mov.d $f4, $f2
+    abs.s $f0, $f0
+    c.ult.d $f2, $f4
+    */
+    let state = get_state_architecture(
+        RC::new(architecture::Mips::new()),
+        &[
+            0x44, 0x85, 0x00, 0x00, 0x46, 0x80, 0x00, 0x20, 0x46, 0x00, 0x00, 0xa1, 0x46, 0x20,
+            0x10, 0x87, 0x46, 0x20, 0x11, 0x06, 0x46, 0x00, 0x00, 0x05, 0x46, 0x24, 0x10, 0x35,
+        ],
+        vec![("$a1", const_(0xffff_fffd, 32))],
+        Memory::new(Endian::Big),
+    );
+
+    assert_eq!(
+        state.get_scalar("$f0").unwrap().value_u64().unwrap(),
+        u64::from(3_f32.to_bits())
+    );
+    assert_eq!(
+        state.get_scalar("$f4").unwrap().value_u64().unwrap(),
+        3_f64.to_bits() & 0xffff_ffff
+    );
+    assert_eq!(
+        state.get_scalar("$f5").unwrap().value_u64().unwrap(),
+        3_f64.to_bits() >> 32
+    );
+    assert!(state.get_scalar("$fcc0").unwrap().is_zero());
+}
+
 #[test]
 fn negu() {
     /* negu $a0, $a1 */
@@ -2690,3 +2944,338 @@ fn xori() {
     );
     assert_eq!(result.value_u64().unwrap(), 0xff00f00f);
 }
+
+#[test]
+fn addu_64() {
+    // addu $a0, $a1, $a2
+    let instruction_bytes = &[0x00, 0xa6, 0x20, 0x21];
+
+    let result = get_scalar_64(
+        instruction_bytes,
+        vec![
+            ("$a1", const_(0xffff_ffff_7fff_ffff, 64)),
+            ("$a2", const_(1, 64)),
+        ],
+        Memory::new(Endian::Big),
+        "$a0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0000);
+}
+
+#[test]
+fn lui_64() {
+    // lui $v0, 0x8000
+    let result = get_scalar_64(
+        &[0x3c, 0x02, 0x80, 0x00],
+        vec![],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0000);
+
+    // lui $v0, 0x1234
+    let result = get_scalar_64(
+        &[0x3c, 0x02, 0x12, 0x34],
+        vec![],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1234_0000);
+}
+
+#[test]
+fn dadd() {
+    // dadd $v0, $a0, $a1
+    let result = get_scalar_64(
+        &[0x00, 0x85, 0x10, 0x2c],
+        vec![("$a0", const_(0xffff_ffff, 64)), ("$a1", const_(1, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1_0000_0000);
+
+    // dadd $v0, $a0, $a1
+    let intrinsic = get_intrinsic_architecture(
+        RC::new(architecture::Mips64::new()),
+        &[0x00, 0x85, 0x10, 0x2c],
+        vec![
+            ("$a0", const_(0x7fff_ffff_ffff_ffff, 64)),
+            ("$a1", const_(1, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(intrinsic.mnemonic(), "IntegerOverflow");
+}
+
+#[test]
+fn daddiu() {
+    // daddiu $v0, $a0, -8
+    let instruction_bytes = &[0x64, 0x82, 0xff, 0xf8];
+
+    let result = get_scalar_64(
+        instruction_bytes,
+        vec![("$a0", const_(0x1_0000_0010, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1_0000_0008);
+
+    let result = get_scalar_64(
+        instruction_bytes,
+        vec![("$a0", const_(0, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_fff8);
+}
+
+#[test]
+fn daddu() {
+    // daddu $v0, $a0, $a1
+    let result = get_scalar_64(
+        &[0x00, 0x85, 0x10, 0x2d],
+        vec![
+            ("$a0", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("$a1", const_(2, 64)),
+        ],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn dsubu() {
+    // dsubu $v0, $a0, $a1
+    let result = get_scalar_64(
+        &[0x00, 0x85, 0x10, 0x2f],
+        vec![("$a0", const_(0x1_0000_0000, 64)), ("$a1", const_(1, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+}
+
+#[test]
+fn dsll() {
+    // dsll $v0, $a0, 3
+    let result = get_scalar_64(
+        &[0x00, 0x04, 0x10, 0xf8],
+        vec![("$a0", const_(0x2000_0000_0000_0001, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x8);
+
+    // dsll32 $v0, $a0, 3
+    let result = get_scalar_64(
+        &[0x00, 0x04, 0x10, 0xfc],
+        vec![("$a0", const_(0x1, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x8_0000_0000);
+}
+
+#[test]
+fn dsra32() {
+    // dsra32 $v0, $a0, 3
+    let result = get_scalar_64(
+        &[0x00, 0x04, 0x10, 0xff],
+        vec![("$a0", const_(0x8000_0000_0000_0000, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_f000_0000);
+}
+
+#[test]
+fn dsrlv() {
+    // dsrlv $v0, $a0, $a1
+    let result = get_scalar_64(
+        &[0x00, 0xa4, 0x10, 0x16],
+        vec![
+            ("$a0", const_(0x8000_0000_0000_0000, 64)),
+            ("$a1", const_(0x40 + 60, 64)),
+        ],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x8);
+}
+
+#[test]
+fn dmult() {
+    // dmult $a0, $a1
+    let state = get_state_64(
+        &[0x00, 0x85, 0x00, 0x1c],
+        vec![
+            ("$a0", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("$a1", const_(2, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(
+        state.get_scalar("$hi").unwrap().value_u64().unwrap(),
+        0xffff_ffff_ffff_ffff
+    );
+    assert_eq!(
+        state.get_scalar("$lo").unwrap().value_u64().unwrap(),
+        0xffff_ffff_ffff_fffe
+    );
+
+    // dmultu $a0, $a1
+    let state = get_state_64(
+        &[0x00, 0x85, 0x00, 0x1d],
+        vec![
+            ("$a0", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("$a1", const_(2, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(state.get_scalar("$hi").unwrap().value_u64().unwrap(), 1);
+    assert_eq!(
+        state.get_scalar("$lo").unwrap().value_u64().unwrap(),
+        0xffff_ffff_ffff_fffe
+    );
+}
+
+#[test]
+fn ddiv() {
+    // ddiv $a0, $a1
+    let state = get_state_64(
+        &[0x00, 0x85, 0x00, 0x1e],
+        vec![
+            ("$a0", const_(0xffff_ffff_ffff_fff9, 64)),
+            ("$a1", const_(2, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(
+        state.get_scalar("$lo").unwrap().value_u64().unwrap(),
+        0xffff_ffff_ffff_fffd
+    );
+    assert_eq!(
+        state.get_scalar("$hi").unwrap().value_u64().unwrap(),
+        0xffff_ffff_ffff_ffff
+    );
+}
+
+#[test]
+fn dclz() {
+    // dclz $v0, $a0
+    let result = get_scalar_64(
+        &[0x70, 0x82, 0x10, 0x24],
+        vec![("$a0", const_(0x1_0000_0000, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 31);
+
+    // dclo $v0, $a0
+    let result = get_scalar_64(
+        &[0x70, 0x82, 0x10, 0x25],
+        vec![("$a0", const_(0xffff_0000_0000_0000, 64))],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 16);
+}
+
+#[test]
+fn ld() {
+    let mut memory = Memory::new(Endian::Big);
+    memory
+        .store(0x1008, const_(0x1122_3344_5566_7788, 64))
+        .unwrap();
+
+    // ld $v0, 8($a0)
+    let result = get_scalar_64(
+        &[0xdc, 0x82, 0x00, 0x08],
+        vec![("$a0", const_(0x1000, 64))],
+        memory,
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1122_3344_5566_7788);
+}
+
+#[test]
+fn sd() {
+    // sd $v0, -8($sp)
+    let state = get_state_64(
+        &[0xff, 0xa2, 0xff, 0xf8],
+        vec![
+            ("$v0", const_(0x1122_3344_5566_7788, 64)),
+            ("$sp", const_(0x1008, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(
+        state
+            .memory()
+            .load(0x1000, 64)
+            .unwrap()
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x1122_3344_5566_7788
+    );
+}
+
+#[test]
+fn lw_64() {
+    let mut memory = Memory::new(Endian::Big);
+    memory.store(0x1004, const_(0x8000_0001, 32)).unwrap();
+
+    // lw $v0, 4($a0)
+    let result = get_scalar_64(
+        &[0x8c, 0x82, 0x00, 0x04],
+        vec![("$a0", const_(0x1000, 64))],
+        memory.clone(),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0001);
+
+    // lwu $v0, 4($a0)
+    let result = get_scalar_64(
+        &[0x9c, 0x82, 0x00, 0x04],
+        vec![("$a0", const_(0x1000, 64))],
+        memory,
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x8000_0001);
+}
+
+#[test]
+fn add_fmt_64() {
+    // add.s $f0, $f2, $f4
+    // dmfc1 $v0, $f0
+    let result = get_scalar_64(
+        &[0x46, 0x04, 0x10, 0x00, 0x44, 0x22, 0x00, 0x00],
+        vec![
+            ("$f2", const_(0xffff_ffff_3fc0_0000, 64)),
+            ("$f4", const_(0x4010_0000, 64)),
+        ],
+        Memory::new(Endian::Big),
+        "$v0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x4070_0000);
+}
+
+#[test]
+fn mips64el() {
+    // daddu $v0, $a0, $a1
+    let state = get_state_architecture(
+        RC::new(architecture::Mips64el::new()),
+        &[0x2d, 0x10, 0x85, 0x00],
+        vec![
+            ("$a0", const_(0x1_0000_0000, 64)),
+            ("$a1", const_(0x2_0000_0000, 64)),
+        ],
+        Memory::new(Endian::Little),
+    );
+    assert_eq!(
+        state.get_scalar("$v0").unwrap().value_u64().unwrap(),
+        0x3_0000_0000
+    );
+}
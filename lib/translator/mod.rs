@@ -23,8 +23,11 @@ use crate::il::*;
 use crate::memory::MemoryPermissions;
 use std::collections::{BTreeMap, VecDeque};
 
+pub mod aarch64;
+pub mod arm;
 pub mod mips;
 pub mod ppc;
+pub mod riscv;
 pub mod x86;
 
 const DEFAULT_TRANSLATION_BLOCK_BYTES: usize = 64;
@@ -149,6 +152,14 @@ pub trait Translator {
     /// Translates a basic block
     fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult>;
 
+    /// Returns the address in memory of the bytes for the block at `address`.
+    ///
+    /// Translators which encode a decoding mode in block addresses, such as
+    /// the Thumb bit on ARM, strip it here.
+    fn block_bytes_address(&self, address: u64) -> u64 {
+        address
+    }
+
     /// Translates a function
     fn translate_function(
         &self,
@@ -190,7 +201,10 @@ pub trait Translator {
                 continue;
             }
 
-            let block_bytes = memory.get_bytes(block_address, DEFAULT_TRANSLATION_BLOCK_BYTES);
+            let block_bytes = memory.get_bytes(
+                self.block_bytes_address(block_address),
+                DEFAULT_TRANSLATION_BLOCK_BYTES,
+            );
             if block_bytes.is_empty() {
                 let mut control_flow_graph = ControlFlowGraph::new();
                 let block_index = control_flow_graph.new_block()?.index();
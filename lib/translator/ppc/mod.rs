@@ -1,15 +1,19 @@
-//! Capstone-based translator for MIPS.
+//! Capstone-based translator for 32-bit and 64-bit PowerPC.
 
+use crate::architecture::Endian;
 use crate::error::*;
 use crate::il::*;
 use crate::translator::{BlockTranslationResult, Translator};
 use falcon_capstone::capstone;
 
+mod mode;
 pub mod semantics;
 #[cfg(test)]
 mod test;
 
-/// The MIPS translator.
+pub use self::mode::Mode;
+
+/// The 32-bit PowerPC translator.
 #[derive(Clone, Debug, Default)]
 pub struct Ppc;
 
@@ -21,7 +25,39 @@ impl Ppc {
 
 impl Translator for Ppc {
     fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
-        translate_block(bytes, address)
+        translate_block(Mode::Ppc32, Endian::Big, bytes, address)
+    }
+}
+
+/// The 64-bit big-endian PowerPC translator.
+#[derive(Clone, Debug, Default)]
+pub struct Ppc64;
+
+impl Ppc64 {
+    pub fn new() -> Ppc64 {
+        Ppc64
+    }
+}
+
+impl Translator for Ppc64 {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(Mode::Ppc64, Endian::Big, bytes, address)
+    }
+}
+
+/// The 64-bit little-endian PowerPC translator.
+#[derive(Clone, Debug, Default)]
+pub struct Ppc64le;
+
+impl Ppc64le {
+    pub fn new() -> Ppc64le {
+        Ppc64le
+    }
+}
+
+impl Translator for Ppc64le {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(Mode::Ppc64, Endian::Little, bytes, address)
     }
 }
 
@@ -38,9 +74,41 @@ pub fn nop(control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
     Ok(())
 }
 
-fn translate_block(bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
-    let mode = capstone::CS_MODE_32 | capstone::CS_MODE_BIG_ENDIAN;
-    let cs = match capstone::Capstone::new(capstone::cs_arch::CS_ARCH_PPC, mode) {
+/// Capstone does not disassemble the XO-form instructions with the OE bit set,
+/// such as addo and divwo. If `bytes` begin with one of these instructions,
+/// returns the instruction with the OE bit cleared.
+fn clear_overflow_enable(bytes: &[u8]) -> Option<Vec<u8>> {
+    if bytes.len() < 4 {
+        return None;
+    }
+
+    let word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let extended_opcode = (word >> 1) & 0x1ff;
+
+    match extended_opcode {
+        8 | 10 | 40 | 104 | 136 | 138 | 200 | 202 | 232 | 233 | 234 | 235 | 266 | 457 | 459
+        | 489 | 491
+            if word >> 26 == 31 && word & 0x400 != 0 =>
+        {
+            Some((word & !0x400).to_be_bytes().to_vec())
+        }
+        _ => None,
+    }
+}
+
+fn translate_block(
+    mode: Mode,
+    endian: Endian,
+    bytes: &[u8],
+    address: u64,
+) -> Result<BlockTranslationResult> {
+    // Little-endian instructions are byte-swapped before disassembly, so
+    // capstone, and the semantics, only see big-endian instruction words.
+    let cs_mode = match mode {
+        Mode::Ppc32 => capstone::CS_MODE_32,
+        Mode::Ppc64 => capstone::CS_MODE_64,
+    } | capstone::CS_MODE_BIG_ENDIAN;
+    let cs = match capstone::Capstone::new(capstone::cs_arch::CS_ARCH_PPC, cs_mode) {
         Ok(cs) => cs,
         Err(_) => return Err(ErrorKind::CapstoneError.into()),
     };
@@ -64,112 +132,271 @@ fn translate_block(bytes: &[u8], address: u64) -> Result<BlockTranslationResult>
     let mut offset: usize = 0;
 
     loop {
-        // println!("offset: 0x{:x}, address + offset: 0x{:x}",
-        //          offset,
-        //          address + offset as u64);
         if offset == bytes.len() {
             successors.push((address + offset as u64, None));
             break;
         }
 
-        let disassembly_range = (offset)..bytes.len();
-        let disassembly_bytes = bytes.get(disassembly_range).unwrap();
-        let instructions = match cs.disasm(disassembly_bytes, address + offset as u64, 1) {
-            Ok(instructions) => instructions,
-            Err(_) => return Err(ErrorKind::CapstoneError.into()),
+        let disassembly_range = (offset)..bytes.len().min(offset + 4);
+        let mut disassembly_bytes = bytes.get(disassembly_range).unwrap().to_vec();
+        if endian == Endian::Little && disassembly_bytes.len() == 4 {
+            disassembly_bytes.reverse();
+        }
+        let disassemble = |bytes: &[u8]| match cs.disasm(bytes, address + offset as u64, 1) {
+            Ok(ref instructions) if instructions.count() == 0 => None,
+            Ok(instructions) => Some(instructions),
+            Err(_) => None,
         };
 
-        if instructions.count() == 0 {
-            return Err(ErrorKind::CapstoneError.into());
-        }
+        // Set when the instruction writes XER[OV].
+        let mut overflow = false;
+
+        let instructions = match disassemble(&disassembly_bytes) {
+            Some(instructions) => instructions,
+            None => match clear_overflow_enable(&disassembly_bytes) {
+                Some(bytes) => {
+                    overflow = true;
+                    disassemble(&bytes).ok_or(ErrorKind::CapstoneError)?
+                }
+                None => return Err(ErrorKind::CapstoneError.into()),
+            },
+        };
 
         let instruction = instructions.get(0).unwrap();
 
-        if let capstone::InstrIdArch::PPC(instruction_id) = instruction.id {
-            let mut instruction_graph = ControlFlowGraph::new();
+        let instruction_id = match instruction.id {
+            capstone::InstrIdArch::PPC(instruction_id) => instruction_id,
+            _ => bail!("not a PPC instruction"),
+        };
+
+        // Branches and traps have many extended mnemonics, each with their
+        // own capstone id, so we identify these by their opcodes.
+        let word = semantics::instruction_word(&instruction);
+        let opcode = word >> 26;
+        let extended_opcode = (word >> 1) & 0x3ff;
+
+        let mut instruction_graph = ControlFlowGraph::new();
 
-            match instruction_id {
-                capstone::ppc_insn::PPC_INS_ADD => {
-                    semantics::add(&mut instruction_graph, &instruction)
+        match (opcode, extended_opcode) {
+            (16, _) | (18, _) => semantics::bc(&mut instruction_graph, &instruction, mode),
+            (19, 16) => semantics::bclr(&mut instruction_graph, &instruction, mode),
+            (19, 528) => semantics::bcctr(&mut instruction_graph, &instruction, mode),
+            (30, _) => semantics::rotate_doubleword(&mut instruction_graph, &instruction, mode),
+            (2, _) | (3, _) | (31, 4) | (31, 68) => {
+                semantics::trap(&mut instruction_graph, &instruction, mode)
+            }
+            _ => match instruction_id {
+                capstone::ppc_insn::PPC_INS_ADD
+                | capstone::ppc_insn::PPC_INS_ADDC
+                | capstone::ppc_insn::PPC_INS_ADDE
+                | capstone::ppc_insn::PPC_INS_ADDIC
+                | capstone::ppc_insn::PPC_INS_ADDME
+                | capstone::ppc_insn::PPC_INS_ADDZE
+                | capstone::ppc_insn::PPC_INS_NEG
+                | capstone::ppc_insn::PPC_INS_SUBF
+                | capstone::ppc_insn::PPC_INS_SUBFC
+                | capstone::ppc_insn::PPC_INS_SUBFE
+                | capstone::ppc_insn::PPC_INS_SUBFIC
+                | capstone::ppc_insn::PPC_INS_SUBFME
+                | capstone::ppc_insn::PPC_INS_SUBFZE => {
+                    semantics::arithmetic(&mut instruction_graph, &instruction, overflow, mode)
                 }
                 capstone::ppc_insn::PPC_INS_ADDI => {
-                    semantics::addi(&mut instruction_graph, &instruction)
+                    semantics::addi(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::ppc_insn::PPC_INS_ADDIS => {
-                    semantics::addis(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_ADDZE => {
-                    semantics::addze(&mut instruction_graph, &instruction)
+                    semantics::addis(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::ppc_insn::PPC_INS_AND
+                | capstone::ppc_insn::PPC_INS_ANDC
+                | capstone::ppc_insn::PPC_INS_ANDI
+                | capstone::ppc_insn::PPC_INS_ANDIS
+                | capstone::ppc_insn::PPC_INS_CNTLZW
+                | capstone::ppc_insn::PPC_INS_CNTLZD
+                | capstone::ppc_insn::PPC_INS_EQV
+                | capstone::ppc_insn::PPC_INS_EXTSB
+                | capstone::ppc_insn::PPC_INS_EXTSH
+                | capstone::ppc_insn::PPC_INS_EXTSW
+                | capstone::ppc_insn::PPC_INS_MR
+                | capstone::ppc_insn::PPC_INS_NAND
+                | capstone::ppc_insn::PPC_INS_NOR
+                | capstone::ppc_insn::PPC_INS_NOT
+                | capstone::ppc_insn::PPC_INS_OR
+                | capstone::ppc_insn::PPC_INS_ORC
+                | capstone::ppc_insn::PPC_INS_ORI
+                | capstone::ppc_insn::PPC_INS_ORIS
+                | capstone::ppc_insn::PPC_INS_XOR
+                | capstone::ppc_insn::PPC_INS_XORI
+                | capstone::ppc_insn::PPC_INS_XORIS => {
+                    semantics::logical(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::ppc_insn::PPC_INS_CMPD
+                | capstone::ppc_insn::PPC_INS_CMPDI
+                | capstone::ppc_insn::PPC_INS_CMPLD
+                | capstone::ppc_insn::PPC_INS_CMPLDI
+                | capstone::ppc_insn::PPC_INS_CMPW
+                | capstone::ppc_insn::PPC_INS_CMPWI
+                | capstone::ppc_insn::PPC_INS_CMPLW
+                | capstone::ppc_insn::PPC_INS_CMPLWI => {
+                    semantics::compare(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::ppc_insn::PPC_INS_CRAND
+                | capstone::ppc_insn::PPC_INS_CRANDC
+                | capstone::ppc_insn::PPC_INS_CRCLR
+                | capstone::ppc_insn::PPC_INS_CREQV
+                | capstone::ppc_insn::PPC_INS_CRMOVE
+                | capstone::ppc_insn::PPC_INS_CRNAND
+                | capstone::ppc_insn::PPC_INS_CRNOR
+                | capstone::ppc_insn::PPC_INS_CRNOT
+                | capstone::ppc_insn::PPC_INS_CROR
+                | capstone::ppc_insn::PPC_INS_CRORC
+                | capstone::ppc_insn::PPC_INS_CRSET
+                | capstone::ppc_insn::PPC_INS_CRXOR => {
+                    semantics::condition_register_logical(&mut instruction_graph, &instruction)
+                }
+                capstone::ppc_insn::PPC_INS_DCBF
+                | capstone::ppc_insn::PPC_INS_DCBST
+                | capstone::ppc_insn::PPC_INS_DCBT
+                | capstone::ppc_insn::PPC_INS_DCBTST
+                | capstone::ppc_insn::PPC_INS_EIEIO
+                | capstone::ppc_insn::PPC_INS_ICBI
+                | capstone::ppc_insn::PPC_INS_ISYNC
+                | capstone::ppc_insn::PPC_INS_LWSYNC
+                | capstone::ppc_insn::PPC_INS_NOP
+                | capstone::ppc_insn::PPC_INS_SYNC => {
+                    semantics::nop(&mut instruction_graph, &instruction)
                 }
-                capstone::ppc_insn::PPC_INS_B => nop(&mut instruction_graph),
-                capstone::ppc_insn::PPC_INS_BL => {
-                    semantics::bl(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_DIVD
+                | capstone::ppc_insn::PPC_INS_DIVDU
+                | capstone::ppc_insn::PPC_INS_DIVW
+                | capstone::ppc_insn::PPC_INS_DIVWU => {
+                    semantics::divide(&mut instruction_graph, &instruction, overflow, mode)
+                }
+                capstone::ppc_insn::PPC_INS_LBZ
+                | capstone::ppc_insn::PPC_INS_LBZU
+                | capstone::ppc_insn::PPC_INS_LBZUX
+                | capstone::ppc_insn::PPC_INS_LBZX
+                | capstone::ppc_insn::PPC_INS_LD
+                | capstone::ppc_insn::PPC_INS_LDARX
+                | capstone::ppc_insn::PPC_INS_LDBRX
+                | capstone::ppc_insn::PPC_INS_LDU
+                | capstone::ppc_insn::PPC_INS_LDUX
+                | capstone::ppc_insn::PPC_INS_LDX
+                | capstone::ppc_insn::PPC_INS_LHA
+                | capstone::ppc_insn::PPC_INS_LHAU
+                | capstone::ppc_insn::PPC_INS_LHAUX
+                | capstone::ppc_insn::PPC_INS_LHAX
+                | capstone::ppc_insn::PPC_INS_LHBRX
+                | capstone::ppc_insn::PPC_INS_LHZ
+                | capstone::ppc_insn::PPC_INS_LHZU
+                | capstone::ppc_insn::PPC_INS_LHZUX
+                | capstone::ppc_insn::PPC_INS_LHZX
+                | capstone::ppc_insn::PPC_INS_LWA
+                | capstone::ppc_insn::PPC_INS_LWARX
+                | capstone::ppc_insn::PPC_INS_LWAUX
+                | capstone::ppc_insn::PPC_INS_LWAX
+                | capstone::ppc_insn::PPC_INS_LWBRX
+                | capstone::ppc_insn::PPC_INS_LWZ
+                | capstone::ppc_insn::PPC_INS_LWZU
+                | capstone::ppc_insn::PPC_INS_LWZUX
+                | capstone::ppc_insn::PPC_INS_LWZX => {
+                    semantics::load(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_BC => nop(&mut instruction_graph),
-                capstone::ppc_insn::PPC_INS_BCLR => {
-                    semantics::bclr(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_LI => {
+                    semantics::li(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_BCTR => {
-                    semantics::bctr(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_LIS => {
+                    semantics::lis(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_BDNZL => nop(&mut instruction_graph),
-                capstone::ppc_insn::PPC_INS_BLR => nop(&mut instruction_graph),
-                capstone::ppc_insn::PPC_INS_CMPWI => {
-                    semantics::cmpwi(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_LMW => {
+                    semantics::lmw(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_CMPLWI => {
-                    semantics::cmplwi(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_MCRF => {
+                    semantics::mcrf(&mut instruction_graph, &instruction)
                 }
-                capstone::ppc_insn::PPC_INS_LBZ => {
-                    semantics::lbz(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_MFCR | capstone::ppc_insn::PPC_INS_MFOCRF => {
+                    semantics::mfcr(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_LWZ => {
-                    semantics::lwz(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_MFCTR => {
+                    semantics::mfctr(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_LWZU => {
-                    semantics::lwzu(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_MFLR => {
+                    semantics::mflr(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_LI => {
-                    semantics::li(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_MFXER => {
+                    semantics::mfxer(&mut instruction_graph, &instruction, mode)
                 }
-                capstone::ppc_insn::PPC_INS_LIS => {
-                    semantics::lis(&mut instruction_graph, &instruction)
+                capstone::ppc_insn::PPC_INS_MTCR
+                | capstone::ppc_insn::PPC_INS_MTCRF
+                | capstone::ppc_insn::PPC_INS_MTOCRF => {
+                    semantics::mtcrf(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::ppc_insn::PPC_INS_MTCTR => {
-                    semantics::mtctr(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_MFLR => {
-                    semantics::mflr(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_MR => {
-                    semantics::mr(&mut instruction_graph, &instruction)
+                    semantics::mtctr(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::ppc_insn::PPC_INS_MTLR => {
-                    semantics::mflr(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_NOP => {
-                    semantics::nop(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_RLWINM => {
-                    semantics::rlwinm(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_SLWI => {
-                    semantics::slwi(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_SRAWI => {
-                    semantics::srawi(&mut instruction_graph, &instruction)
+                    semantics::mtlr(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::ppc_insn::PPC_INS_MTXER => {
+                    semantics::mtxer(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::ppc_insn::PPC_INS_MULHD
+                | capstone::ppc_insn::PPC_INS_MULHDU
+                | capstone::ppc_insn::PPC_INS_MULHW
+                | capstone::ppc_insn::PPC_INS_MULHWU
+                | capstone::ppc_insn::PPC_INS_MULLI
+                | capstone::ppc_insn::PPC_INS_MULLD
+                | capstone::ppc_insn::PPC_INS_MULLW => {
+                    semantics::multiply(&mut instruction_graph, &instruction, overflow, mode)
+                }
+                capstone::ppc_insn::PPC_INS_CLRLWI
+                | capstone::ppc_insn::PPC_INS_RLWIMI
+                | capstone::ppc_insn::PPC_INS_RLWINM
+                | capstone::ppc_insn::PPC_INS_RLWNM
+                | capstone::ppc_insn::PPC_INS_ROTLW
+                | capstone::ppc_insn::PPC_INS_ROTLWI
+                | capstone::ppc_insn::PPC_INS_SLWI
+                | capstone::ppc_insn::PPC_INS_SRWI => {
+                    semantics::rotate(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::ppc_insn::PPC_INS_SC => {
+                    semantics::sc(&mut instruction_graph, &instruction)
+                }
+                capstone::ppc_insn::PPC_INS_SLD
+                | capstone::ppc_insn::PPC_INS_SLW
+                | capstone::ppc_insn::PPC_INS_SRAD
+                | capstone::ppc_insn::PPC_INS_SRADI
+                | capstone::ppc_insn::PPC_INS_SRAW
+                | capstone::ppc_insn::PPC_INS_SRAWI
+                | capstone::ppc_insn::PPC_INS_SRD
+                | capstone::ppc_insn::PPC_INS_SRW => {
+                    semantics::shift(&mut instruction_graph, &instruction, mode)
+                }
+                capstone::ppc_insn::PPC_INS_STB
+                | capstone::ppc_insn::PPC_INS_STBU
+                | capstone::ppc_insn::PPC_INS_STBUX
+                | capstone::ppc_insn::PPC_INS_STBX
+                | capstone::ppc_insn::PPC_INS_STD
+                | capstone::ppc_insn::PPC_INS_STDBRX
+                | capstone::ppc_insn::PPC_INS_STDCX
+                | capstone::ppc_insn::PPC_INS_STDU
+                | capstone::ppc_insn::PPC_INS_STDUX
+                | capstone::ppc_insn::PPC_INS_STDX
+                | capstone::ppc_insn::PPC_INS_STH
+                | capstone::ppc_insn::PPC_INS_STHBRX
+                | capstone::ppc_insn::PPC_INS_STHU
+                | capstone::ppc_insn::PPC_INS_STHUX
+                | capstone::ppc_insn::PPC_INS_STHX
+                | capstone::ppc_insn::PPC_INS_STW
+                | capstone::ppc_insn::PPC_INS_STWBRX
+                | capstone::ppc_insn::PPC_INS_STWCX
+                | capstone::ppc_insn::PPC_INS_STWU
+                | capstone::ppc_insn::PPC_INS_STWUX
+                | capstone::ppc_insn::PPC_INS_STWX => {
+                    semantics::store(&mut instruction_graph, &instruction, mode)
                 }
                 capstone::ppc_insn::PPC_INS_STMW => {
-                    semantics::stmw(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_STW => {
-                    semantics::stw(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_STWU => {
-                    semantics::stwu(&mut instruction_graph, &instruction)
-                }
-                capstone::ppc_insn::PPC_INS_SUBF => {
-                    semantics::subf(&mut instruction_graph, &instruction)
+                    semantics::stmw(&mut instruction_graph, &instruction, mode)
                 }
                 _ => {
                     let bytes = (0..4)
@@ -183,56 +410,41 @@ fn translate_block(bytes: &[u8], address: u64) -> Result<BlockTranslationResult>
                     )
                     .into());
                 }
-            }?;
-
-            match instruction_id {
-                capstone::ppc_insn::PPC_INS_B => {
-                    let detail = semantics::details(&instruction)?;
+            },
+        }?;
 
-                    instruction_graph.set_address(Some(instruction.address));
-                    block_graphs.push((instruction.address, instruction_graph));
+        instruction_graph.set_address(Some(instruction.address));
+        block_graphs.push((instruction.address, instruction_graph));
 
-                    successors.push((detail.operands[0].imm() as u64, None));
-
-                    break;
-                }
-                capstone::ppc_insn::PPC_INS_BCTR => {
-                    instruction_graph.set_address(Some(instruction.address));
-                    block_graphs.push((instruction.address, instruction_graph));
+        length += instruction.size as usize;
+        offset += instruction.size as usize;
 
-                    break;
-                }
-                capstone::ppc_insn::PPC_INS_BC => {
-                    let detail = semantics::details(&instruction)?;
+        // Branches which link, or are lifted to conditional branch operations,
+        // fall through to the next instruction.
+        if semantics::branch_links(&instruction) {
+            continue;
+        }
 
-                    // beq
-                    if detail.operands[0].imm() == 12 && detail.operands[1].imm() == 10 {
-                        let true_condition = expr_scalar("cr0-eq", 1);
-                        let false_condition =
-                            Expression::cmpneq(true_condition.clone(), expr_const(1, 1))?;
-                        successors.push((instruction.address + 4, Some(false_condition)));
-                        successors.push((detail.operands[0].imm() as u64, Some(true_condition)));
-                    } else {
-                        bail!("Unhandled bc instruction");
+        match (opcode, extended_opcode) {
+            (16, _) | (18, _) => {
+                let target = semantics::branch_target(&instruction, mode);
+                match semantics::branch_condition(&instruction, mode)? {
+                    Some(condition) => {
+                        successors.push((
+                            instruction.address + 4,
+                            Some(Expression::cmpeq(condition.clone(), expr_const(0, 1))?),
+                        ));
+                        successors.push((target, Some(condition)));
                     }
-                    break;
-                }
-                capstone::ppc_insn::PPC_INS_BLR | capstone::ppc_insn::PPC_INS_BL => {
-                    instruction_graph.set_address(Some(instruction.address));
-                    block_graphs.push((instruction.address, instruction_graph));
-                }
-                _ => {
-                    instruction_graph.set_address(Some(instruction.address));
-                    block_graphs.push((instruction.address, instruction_graph));
+                    None => successors.push((target, None)),
                 }
+                break;
             }
-
-            length += instruction.size as usize;
-        } else {
-            bail!("not a MIPS instruction")
+            (19, 16) | (19, 528) if semantics::branch_condition(&instruction, mode)?.is_none() => {
+                break
+            }
+            _ => {}
         }
-
-        offset += instruction.size as usize;
     }
 
     Ok(BlockTranslationResult::new(
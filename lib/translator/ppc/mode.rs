@@ -0,0 +1,24 @@
+/// The width of the registers a PowerPC block is translated with.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    Ppc32,
+    Ppc64,
+}
+
+impl Mode {
+    /// Returns the width of the general purpose registers, in bits.
+    pub fn bits(self) -> usize {
+        match self {
+            Mode::Ppc32 => 32,
+            Mode::Ppc64 => 64,
+        }
+    }
+
+    /// Returns a mask of the bits of an address.
+    pub fn mask(self) -> u64 {
+        match self {
+            Mode::Ppc32 => 0xffff_ffff,
+            Mode::Ppc64 => 0xffff_ffff_ffff_ffff,
+        }
+    }
+}
@@ -1,34 +1,32 @@
+use super::Mode;
 use crate::error::*;
 use crate::il::Expression as Expr;
 use crate::il::*;
 use falcon_capstone::capstone;
-use falcon_capstone::capstone_sys::ppc_reg;
-use std::cmp::Ordering;
+use falcon_capstone::capstone::ppc_insn;
+use falcon_capstone::capstone_sys::{ppc_op_type, ppc_reg};
 
 /// Struct for dealing with x86 registers
 pub struct PPCRegister {
     name: &'static str,
     // The capstone enum value for this register.
     capstone_reg: ppc_reg,
-    /// The size of this register in bits
-    bits: usize,
 }
 
 impl PPCRegister {
-    // pub fn bits(&self) -> usize {
-    //     self.bits
-    // }
-
     pub fn name(&self) -> &str {
         self.name
     }
 
-    pub fn scalar(&self) -> Scalar {
-        scalar(self.name, self.bits)
+    /// Returns this register as a scalar as wide as the registers of `mode`.
+    pub fn scalar(&self, mode: Mode) -> Scalar {
+        scalar(self.name, mode.bits())
     }
 
-    pub fn expression(&self) -> Expr {
-        expr_scalar(self.name, self.bits)
+    /// Returns this register as an expression as wide as the registers of
+    /// `mode`.
+    pub fn expression(&self, mode: Mode) -> Expr {
+        expr_scalar(self.name, mode.bits())
     }
 }
 
@@ -36,207 +34,166 @@ const PPC_REGISTERS: &[PPCRegister] = &[
     PPCRegister {
         name: "r0",
         capstone_reg: ppc_reg::PPC_REG_R0,
-        bits: 32,
     },
     PPCRegister {
         name: "r1",
         capstone_reg: ppc_reg::PPC_REG_R1,
-        bits: 32,
     },
     PPCRegister {
         name: "r2",
         capstone_reg: ppc_reg::PPC_REG_R2,
-        bits: 32,
     },
     PPCRegister {
         name: "r3",
         capstone_reg: ppc_reg::PPC_REG_R3,
-        bits: 32,
     },
     PPCRegister {
         name: "r4",
         capstone_reg: ppc_reg::PPC_REG_R4,
-        bits: 32,
     },
     PPCRegister {
         name: "r5",
         capstone_reg: ppc_reg::PPC_REG_R5,
-        bits: 32,
     },
     PPCRegister {
         name: "r6",
         capstone_reg: ppc_reg::PPC_REG_R6,
-        bits: 32,
     },
     PPCRegister {
         name: "r7",
         capstone_reg: ppc_reg::PPC_REG_R7,
-        bits: 32,
     },
     PPCRegister {
         name: "r8",
         capstone_reg: ppc_reg::PPC_REG_R8,
-        bits: 32,
     },
     PPCRegister {
         name: "r9",
         capstone_reg: ppc_reg::PPC_REG_R9,
-        bits: 32,
     },
     PPCRegister {
         name: "r10",
         capstone_reg: ppc_reg::PPC_REG_R10,
-        bits: 32,
     },
     PPCRegister {
         name: "r11",
         capstone_reg: ppc_reg::PPC_REG_R11,
-        bits: 32,
     },
     PPCRegister {
         name: "r12",
         capstone_reg: ppc_reg::PPC_REG_R12,
-        bits: 32,
     },
     PPCRegister {
         name: "r13",
         capstone_reg: ppc_reg::PPC_REG_R13,
-        bits: 32,
     },
     PPCRegister {
         name: "r14",
         capstone_reg: ppc_reg::PPC_REG_R14,
-        bits: 32,
     },
     PPCRegister {
         name: "r15",
         capstone_reg: ppc_reg::PPC_REG_R15,
-        bits: 32,
     },
     PPCRegister {
         name: "r16",
         capstone_reg: ppc_reg::PPC_REG_R16,
-        bits: 32,
     },
     PPCRegister {
         name: "r17",
         capstone_reg: ppc_reg::PPC_REG_R17,
-        bits: 32,
     },
     PPCRegister {
         name: "r18",
         capstone_reg: ppc_reg::PPC_REG_R18,
-        bits: 32,
     },
     PPCRegister {
         name: "r19",
         capstone_reg: ppc_reg::PPC_REG_R19,
-        bits: 32,
     },
     PPCRegister {
         name: "r20",
         capstone_reg: ppc_reg::PPC_REG_R20,
-        bits: 32,
     },
     PPCRegister {
         name: "r21",
         capstone_reg: ppc_reg::PPC_REG_R21,
-        bits: 32,
     },
     PPCRegister {
         name: "r22",
         capstone_reg: ppc_reg::PPC_REG_R22,
-        bits: 32,
     },
     PPCRegister {
         name: "r23",
         capstone_reg: ppc_reg::PPC_REG_R23,
-        bits: 32,
     },
     PPCRegister {
         name: "r24",
         capstone_reg: ppc_reg::PPC_REG_R24,
-        bits: 32,
     },
     PPCRegister {
         name: "r25",
         capstone_reg: ppc_reg::PPC_REG_R25,
-        bits: 32,
     },
     PPCRegister {
         name: "r26",
         capstone_reg: ppc_reg::PPC_REG_R26,
-        bits: 32,
     },
     PPCRegister {
         name: "r27",
         capstone_reg: ppc_reg::PPC_REG_R27,
-        bits: 32,
     },
     PPCRegister {
         name: "r28",
         capstone_reg: ppc_reg::PPC_REG_R28,
-        bits: 32,
     },
     PPCRegister {
         name: "r29",
         capstone_reg: ppc_reg::PPC_REG_R29,
-        bits: 32,
     },
     PPCRegister {
         name: "r30",
         capstone_reg: ppc_reg::PPC_REG_R30,
-        bits: 32,
     },
     PPCRegister {
         name: "r31",
         capstone_reg: ppc_reg::PPC_REG_R31,
-        bits: 32,
     },
     PPCRegister {
         name: "cr0",
         capstone_reg: ppc_reg::PPC_REG_CR0,
-        bits: 32,
     },
     PPCRegister {
         name: "cr1",
         capstone_reg: ppc_reg::PPC_REG_CR1,
-        bits: 32,
     },
     PPCRegister {
         name: "cr2",
         capstone_reg: ppc_reg::PPC_REG_CR2,
-        bits: 32,
     },
     PPCRegister {
         name: "cr3",
         capstone_reg: ppc_reg::PPC_REG_CR3,
-        bits: 32,
     },
     PPCRegister {
         name: "cr4",
         capstone_reg: ppc_reg::PPC_REG_CR4,
-        bits: 32,
     },
     PPCRegister {
         name: "cr5",
         capstone_reg: ppc_reg::PPC_REG_CR5,
-        bits: 32,
     },
     PPCRegister {
         name: "cr6",
         capstone_reg: ppc_reg::PPC_REG_CR6,
-        bits: 32,
     },
     PPCRegister {
         name: "cr7",
         capstone_reg: ppc_reg::PPC_REG_CR7,
-        bits: 32,
     },
     PPCRegister {
         name: "ctr",
         capstone_reg: ppc_reg::PPC_REG_CTR,
-        bits: 32,
     },
 ];
 
@@ -259,30 +216,121 @@ pub fn details(instruction: &capstone::Instr) -> Result<capstone::cs_ppc> {
     }
 }
 
+/// Returns the capstone id of a PPC instruction.
+fn instruction_id(instruction: &capstone::Instr) -> Result<ppc_insn> {
+    match instruction.id {
+        capstone::InstrIdArch::PPC(instruction_id) => Ok(instruction_id),
+        _ => bail!("not a PPC instruction"),
+    }
+}
+
+/// Returns the 32-bit word encoding an instruction.
+///
+/// Capstone reports some fields, such as the BO field of branches and the
+/// masks of the rotate instructions, only through extended mnemonics. We read
+/// those fields from the instruction word instead.
+pub fn instruction_word(instruction: &capstone::Instr) -> u32 {
+    u32::from_be_bytes([
+        instruction.bytes[0],
+        instruction.bytes[1],
+        instruction.bytes[2],
+        instruction.bytes[3],
+    ])
+}
+
+/// Returns the general purpose register with the given number.
+fn general_purpose_register(index: u32) -> &'static PPCRegister {
+    &PPC_REGISTERS[index as usize]
+}
+
+/// Returns the number of a general purpose register.
+///
+/// Capstone also reports condition register bits as general purpose
+/// registers, so this returns the number of those bits as well.
+fn register_index(capstone_id: ppc_reg) -> Result<usize> {
+    match PPC_REGISTERS[0..32]
+        .iter()
+        .position(|register| register.capstone_reg == capstone_id)
+    {
+        Some(index) => Ok(index),
+        None => bail!("Not a general purpose register"),
+    }
+}
+
+/// Returns the value of a register used as a base address. r0 reads as zero
+/// in this position.
+fn base_register(capstone_id: ppc_reg, mode: Mode) -> Result<Expr> {
+    if capstone_id == ppc_reg::PPC_REG_R0 {
+        Ok(expr_const(0, mode.bits()))
+    } else {
+        Ok(get_register(capstone_id)?.expression(mode))
+    }
+}
+
+/// Capstone reports signed 16-bit immediates without sign-extending them.
+fn signed_immediate(immediate: u64) -> u64 {
+    immediate as u16 as i16 as u64
+}
+
+/// Generates a temporary scalar unique to this instruction.
+fn temp(instruction: &capstone::Instr, subindex: usize, bits: usize) -> Scalar {
+    Scalar::new(
+        format!("temp_0x{:X}_{}", instruction.address, subindex),
+        bits,
+    )
+}
+
+/// Truncates or extends `expr` to `bits`.
+fn resize(expr: Expr, bits: usize, signed: bool) -> Result<Expr> {
+    if expr.bits() > bits {
+        Expr::trun(bits, expr)
+    } else if expr.bits() == bits {
+        Ok(expr)
+    } else if signed {
+        Expr::sext(bits, expr)
+    } else {
+        Expr::zext(bits, expr)
+    }
+}
+
+/// Returns bit `bit` of `value`, counting from the least significant bit.
+fn bit(value: Expr, bit: u64) -> Result<Expr> {
+    let bits = value.bits();
+    Expr::trun(1, Expr::shr(value, expr_const(bit, bits))?)
+}
+
+fn not(expr: Expr) -> Result<Expr> {
+    let bits = expr.bits();
+    Expr::xor(expr, expr_const(0xffff_ffff_ffff_ffff, bits))
+}
+
+/// Sets the entry and exit of a graph holding a single block.
+fn single_block<F>(control_flow_graph: &mut ControlFlowGraph, f: F) -> Result<()>
+where
+    F: FnOnce(&mut Block) -> Result<()>,
+{
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+        f(block)?;
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
 pub fn set_condition_register_signed(
     block: &mut Block,
     condition_register: Scalar,
     lhs: Expression,
     rhs: Expression,
 ) -> Result<()> {
-    let lt = Expression::ite(
-        Expression::cmplts(lhs.clone(), rhs.clone())?,
-        expr_const(0b0100, 4),
-        expr_const(0b0000, 4),
-    )?;
-    let gt = Expression::ite(
-        Expression::cmplts(rhs.clone(), lhs.clone())?,
-        expr_const(0b0010, 4),
-        expr_const(0b0000, 4),
-    )?;
-    let eq = Expression::ite(
-        Expression::cmplts(rhs, lhs)?,
-        expr_const(0b0001, 4),
-        expr_const(0b0000, 4),
-    )?;
-    block.assign(scalar(format!("{}-lt", condition_register.name()), 1), lt);
-    block.assign(scalar(format!("{}-gt", condition_register.name()), 1), gt);
-    block.assign(scalar(format!("{}-eq", condition_register.name()), 1), eq);
+    let lt = Expression::cmplts(lhs.clone(), rhs.clone())?;
+    let gt = Expression::cmplts(rhs.clone(), lhs.clone())?;
+    let eq = Expression::cmpeq(lhs, rhs)?;
+    set_condition_register(block, condition_register, lt, gt, eq);
 
     Ok(())
 }
@@ -293,26 +341,27 @@ pub fn set_condition_register_unsigned(
     lhs: Expression,
     rhs: Expression,
 ) -> Result<()> {
-    let lt = Expression::ite(
-        Expression::cmpltu(lhs.clone(), rhs.clone())?,
-        expr_const(0b0100, 4),
-        expr_const(0b0000, 4),
-    )?;
-    let gt = Expression::ite(
-        Expression::cmpltu(rhs.clone(), lhs.clone())?,
-        expr_const(0b0010, 4),
-        expr_const(0b0000, 4),
-    )?;
-    let eq = Expression::ite(
-        Expression::cmpltu(rhs, lhs)?,
-        expr_const(0b0001, 4),
-        expr_const(0b0000, 4),
-    )?;
+    let lt = Expression::cmpltu(lhs.clone(), rhs.clone())?;
+    let gt = Expression::cmpltu(rhs.clone(), lhs.clone())?;
+    let eq = Expression::cmpeq(lhs, rhs)?;
+    set_condition_register(block, condition_register, lt, gt, eq);
+
+    Ok(())
+}
+
+/// Sets the lt, gt and eq bits of a condition register field. The so bit
+/// receives a copy of XER[SO].
+fn set_condition_register(
+    block: &mut Block,
+    condition_register: Scalar,
+    lt: Expression,
+    gt: Expression,
+    eq: Expression,
+) {
     block.assign(scalar(format!("{}-lt", condition_register.name()), 1), lt);
     block.assign(scalar(format!("{}-gt", condition_register.name()), 1), gt);
     block.assign(scalar(format!("{}-eq", condition_register.name()), 1), eq);
-
-    Ok(())
+    set_condition_register_summary_overflow(block, condition_register, expr_scalar("xer-so", 1));
 }
 
 pub fn set_condition_register_summary_overflow(
@@ -364,91 +413,378 @@ pub fn condition_register_bit_to_flag(condition_register_bit: usize) -> Result<S
     })
 }
 
-pub fn rlwinm_(
+/// Sets cr0 from the result of an instruction with the record bit set, such
+/// as `and.`.
+fn record(block: &mut Block, result: Expression) -> Result<()> {
+    let zero = expr_const(0, result.bits());
+    set_condition_register_signed(block, scalar("cr0", 32), result, zero)
+}
+
+/// Sets XER[OV], and accumulates it into XER[SO].
+fn set_overflow(block: &mut Block, overflow: Expression) -> Result<()> {
+    block.assign(scalar("xer-ov", 1), overflow);
+    block.assign(
+        scalar("xer-so", 1),
+        Expr::or(expr_scalar("xer-so", 1), expr_scalar("xer-ov", 1))?,
+    );
+    Ok(())
+}
+
+/// Returns the mask used by the rotate instructions, `bits` wide, with bits
+/// `mb` through `me` set. Bits are numbered from the most significant bit, and
+/// the mask wraps around when `mb` is greater than `me`.
+fn rotate_mask(mb: u32, me: u32, bits: usize) -> u64 {
+    let ones = 0xffff_ffff_ffff_ffff_u64 >> (64 - bits);
+    let begin = ones >> mb;
+    let end = (ones << (bits as u32 - 1 - me)) & ones;
+    if mb <= me {
+        begin & end
+    } else {
+        begin | end
+    }
+}
+
+/// Returns the number of leading zero bits in `value`.
+fn count_leading_zeros(value: Expr) -> Result<Expr> {
+    let bits = value.bits();
+    let mut result = expr_const(bits as u64, bits);
+    for i in 0..bits {
+        result = Expr::ite(
+            bit(value.clone(), i as u64)?,
+            expr_const((bits - i - 1) as u64, bits),
+            result,
+        )?;
+    }
+    Ok(result)
+}
+
+/// Reverses the order of the bytes in `value`.
+fn byte_reverse(value: Expr) -> Result<Expr> {
+    let bits = value.bits();
+    let mut result = expr_const(0, bits);
+    for i in (0..bits).step_by(8) {
+        let byte = Expr::and(
+            Expr::shr(value.clone(), expr_const(i as u64, bits))?,
+            expr_const(0xff, bits),
+        )?;
+        result = Expr::or(
+            result,
+            Expr::shl(byte, expr_const((bits - i - 8) as u64, bits))?,
+        )?;
+    }
+    Ok(result)
+}
+
+/// Returns the register holding the base address of a load or store, and the
+/// effective address of the access. Handles both the `d(rA)` and the indexed
+/// `rA, rB` forms.
+fn effective_address(detail: &capstone::cs_ppc, mode: Mode) -> Result<(ppc_reg, Expr)> {
+    if detail.operands[1].type_ == ppc_op_type::PPC_OP_MEM {
+        let mem = detail.operands[1].mem();
+        let base = base_register(mem.base, mode)?;
+        let ea = if mem.disp == 0 {
+            base
+        } else {
+            Expr::add(base, expr_const(mem.disp as u64, mode.bits()))?
+        };
+        Ok((mem.base, ea))
+    } else {
+        let base = base_register(detail.operands[1].reg(), mode)?;
+        let index = get_register(detail.operands[2].reg())?.expression(mode);
+        Ok((detail.operands[1].reg(), Expr::add(base, index)?))
+    }
+}
+
+/// Returns the width of a load or store in bits, whether a loaded value is
+/// sign-extended, and whether the base register is updated with the effective
+/// address.
+fn memory_access(instruction_id: ppc_insn) -> Result<(usize, bool, bool)> {
+    Ok(match instruction_id {
+        ppc_insn::PPC_INS_LBZ
+        | ppc_insn::PPC_INS_LBZX
+        | ppc_insn::PPC_INS_STB
+        | ppc_insn::PPC_INS_STBX => (8, false, false),
+        ppc_insn::PPC_INS_LBZU
+        | ppc_insn::PPC_INS_LBZUX
+        | ppc_insn::PPC_INS_STBU
+        | ppc_insn::PPC_INS_STBUX => (8, false, true),
+        ppc_insn::PPC_INS_LHZ
+        | ppc_insn::PPC_INS_LHZX
+        | ppc_insn::PPC_INS_LHBRX
+        | ppc_insn::PPC_INS_STH
+        | ppc_insn::PPC_INS_STHX
+        | ppc_insn::PPC_INS_STHBRX => (16, false, false),
+        ppc_insn::PPC_INS_LHZU
+        | ppc_insn::PPC_INS_LHZUX
+        | ppc_insn::PPC_INS_STHU
+        | ppc_insn::PPC_INS_STHUX => (16, false, true),
+        ppc_insn::PPC_INS_LHA | ppc_insn::PPC_INS_LHAX => (16, true, false),
+        ppc_insn::PPC_INS_LHAU | ppc_insn::PPC_INS_LHAUX => (16, true, true),
+        ppc_insn::PPC_INS_LWZ
+        | ppc_insn::PPC_INS_LWZX
+        | ppc_insn::PPC_INS_LWBRX
+        | ppc_insn::PPC_INS_LWARX
+        | ppc_insn::PPC_INS_STW
+        | ppc_insn::PPC_INS_STWX
+        | ppc_insn::PPC_INS_STWBRX
+        | ppc_insn::PPC_INS_STWCX => (32, false, false),
+        ppc_insn::PPC_INS_LWZU
+        | ppc_insn::PPC_INS_LWZUX
+        | ppc_insn::PPC_INS_STWU
+        | ppc_insn::PPC_INS_STWUX => (32, false, true),
+        ppc_insn::PPC_INS_LWA | ppc_insn::PPC_INS_LWAX => (32, true, false),
+        ppc_insn::PPC_INS_LWAUX => (32, true, true),
+        ppc_insn::PPC_INS_LD
+        | ppc_insn::PPC_INS_LDX
+        | ppc_insn::PPC_INS_LDBRX
+        | ppc_insn::PPC_INS_LDARX
+        | ppc_insn::PPC_INS_STD
+        | ppc_insn::PPC_INS_STDX
+        | ppc_insn::PPC_INS_STDBRX
+        | ppc_insn::PPC_INS_STDCX => (64, false, false),
+        ppc_insn::PPC_INS_LDU
+        | ppc_insn::PPC_INS_LDUX
+        | ppc_insn::PPC_INS_STDU
+        | ppc_insn::PPC_INS_STDUX => (64, false, true),
+        _ => bail!("Not a load or store"),
+    })
+}
+
+/// Returns the target of a branch with an immediate target, b or bc.
+pub fn branch_target(instruction: &capstone::Instr, mode: Mode) -> u64 {
+    let word = instruction_word(instruction);
+    let displacement = if word >> 26 == 18 {
+        (((word & 0x03ff_fffc) << 6) as i32 >> 6) as u64
+    } else {
+        (word & 0xfffc) as u16 as i16 as u64
+    };
+    let base = if word & 2 == 0 {
+        instruction.address
+    } else {
+        0
+    };
+    base.wrapping_add(displacement) & mode.mask()
+}
+
+/// Returns the condition under which a conditional branch is taken, given by
+/// its BO and BI fields, or `None` if the branch is always taken.
+///
+/// The condition reads the count register after it is decremented.
+pub fn branch_condition(instruction: &capstone::Instr, mode: Mode) -> Result<Option<Expr>> {
+    let word = instruction_word(instruction);
+    let bo = (word >> 21) & 0x1f;
+    let bi = (word >> 16) & 0x1f;
+
+    // Branches with immediate targets, b, have no condition.
+    if word >> 26 == 18 {
+        return Ok(None);
+    }
+
+    let mut conditions = Vec::new();
+    if bo & 0b00100 == 0 {
+        let ctr = expr_scalar("ctr", mode.bits());
+        conditions.push(if bo & 0b00010 == 0 {
+            Expr::cmpneq(ctr, expr_const(0, mode.bits()))?
+        } else {
+            Expr::cmpeq(ctr, expr_const(0, mode.bits()))?
+        });
+    }
+    if bo & 0b10000 == 0 {
+        let flag: Expr = condition_register_bit_to_flag(bi as usize)?.into();
+        conditions.push(if bo & 0b01000 == 0 {
+            Expr::cmpeq(flag, expr_const(0, 1))?
+        } else {
+            flag
+        });
+    }
+
+    let mut condition: Option<Expr> = None;
+    for c in conditions {
+        condition = Some(match condition {
+            Some(condition) => Expr::and(condition, c)?,
+            None => c,
+        });
+    }
+    Ok(condition)
+}
+
+/// Returns true if this branch links, writing the address of the following
+/// instruction to the link register.
+pub fn branch_links(instruction: &capstone::Instr) -> bool {
+    instruction_word(instruction) & 1 == 1
+}
+
+/// Returns true if this conditional branch decrements the count register.
+fn branch_decrements(instruction: &capstone::Instr) -> bool {
+    let word = instruction_word(instruction);
+    word >> 26 != 18 && (word >> 21) & 0b00100 == 0
+}
+
+/// Decrements the count register.
+fn decrement_count_register(block: &mut Block, mode: Mode) -> Result<()> {
+    block.assign(
+        scalar("ctr", mode.bits()),
+        Expr::sub(expr_scalar("ctr", mode.bits()), expr_const(1, mode.bits()))?,
+    );
+    Ok(())
+}
+
+/// Lifts a branch to a branch operation.
+///
+/// The count register is decremented, and the link register written, before
+/// the condition is evaluated. The target is read before either is written.
+fn branch(
     control_flow_graph: &mut ControlFlowGraph,
-    ra: Scalar,
-    rs: Expression,
-    sh: u64,
-    mb: u64,
-    me: u64,
+    instruction: &capstone::Instr,
+    target: Expr,
+    mode: Mode,
 ) -> Result<()> {
-    /*
-    - If the MB value is less than the ME value + 1, then the mask bits between
-      and including the starting point and the end point are set to ones. All
-      other bits are set to zeros.
-    - If the MB value is the same as the ME value + 1, then all 32 mask bits are
-      set to ones.
-    - If the MB value is greater than the ME value + 1, then all of the mask
-      bits between and including the ME value +1 and the MB value -1 are set to
-      zeros. All other bits are set to ones.
-    */
-
-    let mask = match mb.cmp(&(me + 1)) {
-        Ordering::Less => {
-            let mb = 32 - mb;
-            let me = 32 - me;
-            let mask = (1 << (mb - me)) - 1;
-            mask << me
-        }
-        Ordering::Equal => 0xffff_ffff,
-        Ordering::Greater => {
-            let mb = 32 - mb;
-            let me = 32 - me;
-            let mask = (1 << (me - mb)) - 1;
-            let mask = mask << mb;
-            mask ^ 0xffff_ffff
-        }
-    };
+    let condition = branch_condition(instruction, mode)?;
 
-    let block_index = {
+    let head_index = {
         let block = control_flow_graph.new_block()?;
 
-        let value = Expr::rotl(rs, expr_const(sh, 32))?;
-        let value = Expr::and(value, expr_const(mask, 32))?;
-        block.assign(ra, value);
+        let target_temp = temp(instruction, 0, mode.bits());
+        block.assign(target_temp.clone(), target);
+
+        if branch_decrements(instruction) {
+            decrement_count_register(block, mode)?;
+        }
+        if branch_links(instruction) {
+            block.assign(
+                scalar("lr", mode.bits()),
+                expr_const(instruction.address + 4, mode.bits()),
+            );
+        }
+
+        if condition.is_none() {
+            block.branch(target_temp.into());
+        }
 
         block.index()
     };
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+    match condition {
+        Some(condition) => {
+            let branch_index = {
+                let block = control_flow_graph.new_block()?;
+                block.branch(temp(instruction, 0, mode.bits()).into());
+                block.index()
+            };
+
+            let tail_index = { control_flow_graph.new_block()?.index() };
+
+            control_flow_graph.conditional_edge(
+                head_index,
+                tail_index,
+                Expr::cmpeq(condition.clone(), expr_const(0, 1))?,
+            )?;
+            control_flow_graph.conditional_edge(head_index, branch_index, condition)?;
+            control_flow_graph.unconditional_edge(branch_index, tail_index)?;
+
+            control_flow_graph.set_entry(head_index)?;
+            control_flow_graph.set_exit(tail_index)?;
+        }
+        None => {
+            control_flow_graph.set_entry(head_index)?;
+            control_flow_graph.set_exit(head_index)?;
+        }
+    }
 
     Ok(())
 }
 
-pub fn add(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+/// Semantics for the add and subtract from instructions.
+///
+/// Each of these computes `a + b + c`, where subtracting rA is lifted as
+/// adding its one's complement, and c is either a constant or XER[CA]. XER[OV]
+/// and XER[SO] are written when `overflow` is set.
+pub fn arithmetic(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    overflow: bool,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
+    let bits = mode.bits();
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = get_register(detail.operands[2].reg())?.expression();
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let ra = get_register(detail.operands[1].reg())?.expression(mode);
+    let rb = || -> Result<Expr> { Ok(get_register(detail.operands[2].reg())?.expression(mode)) };
+    let immediate = || expr_const(signed_immediate(detail.operands[2].imm() as u64), bits);
+    let carry = || Expr::zext(bits, expr_scalar("xer-ca", 1));
+    let zero = expr_const(0, bits);
+    let one = expr_const(1, bits);
+    let all_ones = expr_const(mode.mask(), bits);
+
+    let (a, b, c, sets_carry) = match instruction_id(instruction)? {
+        ppc_insn::PPC_INS_ADD => (ra, rb()?, zero, false),
+        ppc_insn::PPC_INS_ADDC => (ra, rb()?, zero, true),
+        ppc_insn::PPC_INS_ADDE => (ra, rb()?, carry()?, true),
+        ppc_insn::PPC_INS_ADDME => (ra, all_ones, carry()?, true),
+        ppc_insn::PPC_INS_ADDZE => (ra, zero, carry()?, true),
+        ppc_insn::PPC_INS_ADDIC => (ra, immediate(), zero, true),
+        ppc_insn::PPC_INS_SUBF => (not(ra)?, rb()?, one, false),
+        ppc_insn::PPC_INS_SUBFC => (not(ra)?, rb()?, one, true),
+        ppc_insn::PPC_INS_SUBFE => (not(ra)?, rb()?, carry()?, true),
+        ppc_insn::PPC_INS_SUBFME => (not(ra)?, all_ones, carry()?, true),
+        ppc_insn::PPC_INS_SUBFZE => (not(ra)?, zero, carry()?, true),
+        ppc_insn::PPC_INS_SUBFIC => (not(ra)?, immediate(), one, true),
+        ppc_insn::PPC_INS_NEG => (not(ra)?, zero, one, false),
+        _ => bail!("Not an arithmetic instruction"),
+    };
 
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
+    single_block(control_flow_graph, |block| {
+        let result = temp(instruction, 0, bits);
+        block.assign(
+            result.clone(),
+            Expr::add(Expr::add(a.clone(), b.clone())?, c.clone())?,
+        );
+
+        if overflow {
+            // The operands have the same sign, and the result has a
+            // different sign.
+            let sign = bits as u64 - 1;
+            let overflow = Expr::and(
+                Expr::cmpeq(bit(a.clone(), sign)?, bit(b.clone(), sign)?)?,
+                Expr::cmpneq(bit(a.clone(), sign)?, bit(result.clone().into(), sign)?)?,
+            )?;
+            set_overflow(block, overflow)?;
+        }
 
-        let src = Expression::add(lhs, rhs)?;
-        block.assign(dst, src);
+        if sets_carry {
+            let sum = Expr::add(
+                Expr::add(Expr::zext(bits + 1, a)?, Expr::zext(bits + 1, b)?)?,
+                Expr::zext(bits + 1, c)?,
+            )?;
+            block.assign(scalar("xer-ca", 1), bit(sum, bits as u64)?);
+        }
 
-        block.index()
-    };
+        block.assign(dst.clone(), result.into());
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+        if detail.update_cr0 {
+            record(block, dst.into())?;
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
 
 pub fn addi(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = expr_const(detail.operands[2].imm() as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = base_register(detail.operands[1].reg(), mode)?;
+    let rhs = expr_const(
+        signed_immediate(detail.operands[2].imm() as u64),
+        mode.bits(),
+    );
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -468,13 +804,17 @@ pub fn addi(
 pub fn addis(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = expr_const((detail.operands[2].imm() as u64) << 16, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = base_register(detail.operands[1].reg(), mode)?;
+    let rhs = expr_const(
+        signed_immediate(detail.operands[2].imm() as u64) << 16,
+        mode.bits(),
+    );
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
@@ -491,253 +831,323 @@ pub fn addis(
     Ok(())
 }
 
-pub fn addze(
+/// Semantics for the branches with immediate targets, b and bc.
+///
+/// Branches which do not link are followed through the successors of the
+/// block, so we only lift the decrement of the count register here.
+pub fn bc(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
-    let detail = details(instruction)?;
-
-    // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
-
-        let src = Expression::add(
-            lhs.clone(),
-            Expression::zext(lhs.bits() as usize, expr_scalar("carry", 1))?,
-        )?;
-        block.assign(dst, src);
+    if branch_links(instruction) {
+        let target = expr_const(branch_target(instruction, mode), mode.bits());
+        return branch(control_flow_graph, instruction, target, mode);
+    }
 
-        block.index()
-    };
+    single_block(control_flow_graph, |block| {
+        if branch_decrements(instruction) {
+            decrement_count_register(block, mode)?;
+        } else {
+            block.nop();
+        }
+        Ok(())
+    })
+}
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+/// Semantics for branches to the link register.
+pub fn bclr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let target = expr_scalar("lr", mode.bits());
+    branch(control_flow_graph, instruction, target, mode)
+}
 
-    Ok(())
+/// Semantics for branches to the count register.
+pub fn bcctr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let target = expr_scalar("ctr", mode.bits());
+    branch(control_flow_graph, instruction, target, mode)
 }
 
-pub fn bl(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+/// Semantics for the word compares cmpw, cmplw, cmpwi and cmplwi, and the
+/// doubleword compares cmpd, cmpld, cmpdi and cmpldi. The condition register
+/// field is optional, and defaults to cr0.
+pub fn compare(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
+    let instruction_id = instruction_id(instruction)?;
 
     // get operands
-    let dst = expr_const(detail.operands[0].imm() as u32 as u64, 32);
+    let (cr, operands) = if detail.op_count == 3 {
+        (
+            get_register(detail.operands[0].reg())?.scalar(mode),
+            &detail.operands[1..3],
+        )
+    } else {
+        (scalar("cr0", 32), &detail.operands[0..2])
+    };
 
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
+    // The word compares read the low words of the registers.
+    let bits = match instruction_id {
+        ppc_insn::PPC_INS_CMPD
+        | ppc_insn::PPC_INS_CMPDI
+        | ppc_insn::PPC_INS_CMPLD
+        | ppc_insn::PPC_INS_CMPLDI => 64,
+        _ => 32,
+    };
+    let register = |operand: &capstone::cs_ppc_op| -> Result<Expr> {
+        resize(get_register(operand.reg())?.expression(mode), bits, false)
+    };
+    let lhs = register(&operands[0])?;
 
-        block.assign(scalar("lr", 32), expr_const(instruction.address + 4, 32));
-        block.branch(dst);
+    single_block(control_flow_graph, |block| match instruction_id {
+        ppc_insn::PPC_INS_CMPW | ppc_insn::PPC_INS_CMPD => {
+            let rhs = register(&operands[1])?;
+            set_condition_register_signed(block, cr, lhs, rhs)
+        }
+        ppc_insn::PPC_INS_CMPWI | ppc_insn::PPC_INS_CMPDI => {
+            let rhs = expr_const(signed_immediate(operands[1].imm() as u64), bits);
+            set_condition_register_signed(block, cr, lhs, rhs)
+        }
+        ppc_insn::PPC_INS_CMPLW | ppc_insn::PPC_INS_CMPLD => {
+            let rhs = register(&operands[1])?;
+            set_condition_register_unsigned(block, cr, lhs, rhs)
+        }
+        ppc_insn::PPC_INS_CMPLWI | ppc_insn::PPC_INS_CMPLDI => {
+            let rhs = expr_const(operands[1].imm() as u64 & 0xffff, bits);
+            set_condition_register_unsigned(block, cr, lhs, rhs)
+        }
+        _ => bail!("Not a compare instruction"),
+    })
+}
 
-        block.index()
+/// Semantics for the condition register logical instructions, such as crand
+/// and crnor, and their extended mnemonics, such as crset.
+pub fn condition_register_logical(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    // The extended mnemonics repeat operands, so read them from the
+    // instruction word.
+    let word = instruction_word(instruction);
+    let dst = condition_register_bit_to_flag(((word >> 21) & 0x1f) as usize)?;
+    let a: Expr = condition_register_bit_to_flag(((word >> 16) & 0x1f) as usize)?.into();
+    let b: Expr = condition_register_bit_to_flag(((word >> 11) & 0x1f) as usize)?.into();
+
+    let src = match (word >> 1) & 0x3ff {
+        257 => Expr::and(a, b)?,
+        449 => Expr::or(a, b)?,
+        193 => Expr::xor(a, b)?,
+        225 => not(Expr::and(a, b)?)?,
+        33 => not(Expr::or(a, b)?)?,
+        289 => not(Expr::xor(a, b)?)?,
+        129 => Expr::and(a, not(b)?)?,
+        417 => Expr::or(a, not(b)?)?,
+        _ => bail!("Not a condition register logical instruction"),
     };
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
-
-    Ok(())
+    single_block(control_flow_graph, |block| {
+        block.assign(dst, src);
+        Ok(())
+    })
 }
 
-pub fn bclr(
+/// Semantics for divw, divwu, divd and divdu. The quotient of a division by
+/// zero is undefined, and we set it to zero.
+///
+/// In 64-bit mode the word divides leave the high word of the result
+/// undefined, and we extend the quotient into it.
+pub fn divide(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    overflow: bool,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
+    let instruction_id = instruction_id(instruction)?;
+
+    let (bits, signed) = match instruction_id {
+        ppc_insn::PPC_INS_DIVW => (32, true),
+        ppc_insn::PPC_INS_DIVWU => (32, false),
+        ppc_insn::PPC_INS_DIVD => (64, true),
+        ppc_insn::PPC_INS_DIVDU => (64, false),
+        _ => bail!("Not a divide instruction"),
+    };
 
     // get operands
-    let bo = detail.operands[0].imm() as usize;
-    let bi = detail.operands[1].imm() as usize;
-
-    let ctr = scalar("ctr", 32);
-    let branch_target = expr_scalar("lr", 32);
-
-    match bo & 0x1f {
-        0b00000 | 0b00001 | 0b00010 | 0b00011 => {
-            // Decrement the CTR, then branch if condition is false
-            let head_index = {
-                let block = control_flow_graph.new_block()?;
-                block.assign(
-                    ctr.clone(),
-                    Expression::sub(ctr.clone().into(), expr_const(1, ctr.bits()))?,
-                );
-                block.index()
-            };
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = resize(
+        get_register(detail.operands[1].reg())?.expression(mode),
+        bits,
+        false,
+    )?;
+    let rhs = resize(
+        get_register(detail.operands[2].reg())?.expression(mode),
+        bits,
+        false,
+    )?;
 
-            let true_index = {
-                let block = control_flow_graph.new_block()?;
-                block.branch(branch_target);
-                block.index()
-            };
+    let divide_by_zero = Expr::cmpeq(rhs.clone(), expr_const(0, bits))?;
+
+    let (quotient, undefined) = if signed {
+        let minimum = 1 << (bits - 1);
+        let minus_one = 0xffff_ffff_ffff_ffff >> (64 - bits);
+        (
+            Expr::divs(lhs.clone(), rhs.clone())?,
+            Expr::or(
+                divide_by_zero.clone(),
+                Expr::and(
+                    Expr::cmpeq(lhs, expr_const(minimum, bits))?,
+                    Expr::cmpeq(rhs, expr_const(minus_one, bits))?,
+                )?,
+            )?,
+        )
+    } else {
+        (Expr::divu(lhs, rhs)?, divide_by_zero.clone())
+    };
 
-            let false_index = { control_flow_graph.new_block()?.index() };
+    single_block(control_flow_graph, |block| {
+        if overflow {
+            set_overflow(block, undefined)?;
+        }
 
-            let false_condition: Expression = condition_register_bit_to_flag(bi)?.into();
-            let true_condition = Expression::cmpeq(false_condition.clone(), expr_const(0, 1))?;
+        let quotient = Expr::ite(divide_by_zero, expr_const(0, bits), quotient)?;
+        block.assign(dst.clone(), resize(quotient, mode.bits(), signed)?);
 
-            control_flow_graph.conditional_edge(head_index, true_index, true_condition)?;
+        if detail.update_cr0 {
+            record(block, dst.into())?;
+        }
 
-            control_flow_graph.conditional_edge(head_index, false_index, false_condition)?;
+        Ok(())
+    })
+}
 
-            control_flow_graph.set_entry(head_index)?;
-            control_flow_graph.set_exit(false_index)?;
-        }
-        0b00100 | 0b00101 | 0b00110 | 0b00111 => {
-            // Branch if the condition is false
-            let head_index = { control_flow_graph.new_block()?.index() };
+/// Semantics for the loads of bytes, halfwords, words and doublewords,
+/// including the algebraic, update, indexed and byte-reversed forms.
+pub fn load(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+    let instruction_id = instruction_id(instruction)?;
 
-            let true_index = {
-                let block = control_flow_graph.new_block()?;
-                block.branch(branch_target);
-                block.index()
-            };
+    let (bits, signed, update) = memory_access(instruction_id)?;
+    let byte_reversed = matches!(
+        instruction_id,
+        ppc_insn::PPC_INS_LHBRX | ppc_insn::PPC_INS_LWBRX | ppc_insn::PPC_INS_LDBRX
+    );
 
-            let false_index = { control_flow_graph.new_block()?.index() };
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let (base, ea) = effective_address(&detail, mode)?;
 
-            let false_condition: Expression = condition_register_bit_to_flag(bi)?.into();
-            let true_condition = Expression::cmpeq(false_condition.clone(), expr_const(0, 1))?;
+    single_block(control_flow_graph, |block| {
+        let address = temp(instruction, 0, mode.bits());
+        block.assign(address.clone(), ea);
 
-            control_flow_graph.conditional_edge(head_index, true_index, true_condition)?;
+        let value = temp(instruction, 1, bits);
+        block.load(value.clone(), address.clone().into());
 
-            control_flow_graph.conditional_edge(head_index, false_index, false_condition)?;
+        let value = if byte_reversed {
+            byte_reverse(value.into())?
+        } else {
+            value.into()
+        };
+        block.assign(dst, resize(value, mode.bits(), signed)?);
 
-            control_flow_graph.set_entry(head_index)?;
-            control_flow_graph.set_exit(false_index)?;
+        if update {
+            block.assign(get_register(base)?.scalar(mode), address.into());
         }
-        0b01000 | 0b01001 | 0b01010 | 0b01011 => {
-            // Decrement the CTR, then branch if condition is true
-            let head_index = {
-                let block = control_flow_graph.new_block()?;
-                block.assign(
-                    ctr.clone(),
-                    Expression::sub(ctr.clone().into(), expr_const(1, ctr.bits()))?,
-                );
-                block.index()
-            };
-
-            let true_index = {
-                let block = control_flow_graph.new_block()?;
-                block.branch(branch_target);
-                block.index()
-            };
 
-            let false_index = { control_flow_graph.new_block()?.index() };
+        Ok(())
+    })
+}
 
-            let true_condition: Expression = condition_register_bit_to_flag(bi)?.into();
-            let false_condition = Expression::cmpeq(true_condition.clone(), expr_const(0, 1))?;
+/// Semantics for the logical instructions, and the sign-extension and count
+/// leading zeros instructions.
+pub fn logical(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
+    let bits = mode.bits();
 
-            control_flow_graph.conditional_edge(head_index, true_index, true_condition)?;
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = get_register(detail.operands[1].reg())?.expression(mode);
+    let rb = || -> Result<Expr> { Ok(get_register(detail.operands[2].reg())?.expression(mode)) };
+    let immediate = || expr_const(detail.operands[2].imm() as u64 & 0xffff, bits);
+    let shifted_immediate = || expr_const((detail.operands[2].imm() as u64 & 0xffff) << 16, bits);
+
+    let src = match instruction_id(instruction)? {
+        ppc_insn::PPC_INS_AND => Expr::and(rs, rb()?)?,
+        ppc_insn::PPC_INS_ANDC => Expr::and(rs, not(rb()?)?)?,
+        ppc_insn::PPC_INS_ANDI => Expr::and(rs, immediate())?,
+        ppc_insn::PPC_INS_ANDIS => Expr::and(rs, shifted_immediate())?,
+        ppc_insn::PPC_INS_OR => Expr::or(rs, rb()?)?,
+        ppc_insn::PPC_INS_ORC => Expr::or(rs, not(rb()?)?)?,
+        ppc_insn::PPC_INS_ORI => Expr::or(rs, immediate())?,
+        ppc_insn::PPC_INS_ORIS => Expr::or(rs, shifted_immediate())?,
+        ppc_insn::PPC_INS_XOR => Expr::xor(rs, rb()?)?,
+        ppc_insn::PPC_INS_XORI => Expr::xor(rs, immediate())?,
+        ppc_insn::PPC_INS_XORIS => Expr::xor(rs, shifted_immediate())?,
+        ppc_insn::PPC_INS_NAND => not(Expr::and(rs, rb()?)?)?,
+        ppc_insn::PPC_INS_NOR => not(Expr::or(rs, rb()?)?)?,
+        ppc_insn::PPC_INS_EQV => not(Expr::xor(rs, rb()?)?)?,
+        ppc_insn::PPC_INS_MR => rs,
+        ppc_insn::PPC_INS_NOT => not(rs)?,
+        ppc_insn::PPC_INS_EXTSB => Expr::sext(bits, Expr::trun(8, rs)?)?,
+        ppc_insn::PPC_INS_EXTSH => Expr::sext(bits, Expr::trun(16, rs)?)?,
+        ppc_insn::PPC_INS_EXTSW => resize(Expr::trun(32, rs)?, bits, true)?,
+        ppc_insn::PPC_INS_CNTLZW => {
+            resize(count_leading_zeros(resize(rs, 32, false)?)?, bits, false)?
+        }
+        ppc_insn::PPC_INS_CNTLZD => {
+            resize(count_leading_zeros(resize(rs, 64, false)?)?, bits, false)?
+        }
+        _ => bail!("Not a logical instruction"),
+    };
 
-            control_flow_graph.conditional_edge(head_index, false_index, false_condition)?;
+    single_block(control_flow_graph, |block| {
+        block.assign(dst.clone(), src);
 
-            control_flow_graph.set_entry(head_index)?;
-            control_flow_graph.set_exit(false_index)?;
+        if detail.update_cr0 {
+            record(block, dst.into())?;
         }
-        0b01100 | 0b01101 | 0b01110 | 0b01111 => {
-            // Branch if the condition is true
-            let head_index = { control_flow_graph.new_block()?.index() };
 
-            let true_index = {
-                let block = control_flow_graph.new_block()?;
-                block.branch(branch_target);
-                block.index()
-            };
+        Ok(())
+    })
+}
 
-            let false_index = { control_flow_graph.new_block()?.index() };
+pub fn li(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let detail = details(instruction)?;
 
-            let true_condition: Expression = condition_register_bit_to_flag(bi)?.into();
-            let false_condition = Expression::cmpeq(true_condition.clone(), expr_const(0, 1))?;
+    // get operands
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let src = expr_const(
+        signed_immediate(detail.operands[1].imm() as u64),
+        mode.bits(),
+    );
 
-            control_flow_graph.conditional_edge(head_index, true_index, true_condition)?;
-
-            control_flow_graph.conditional_edge(head_index, false_index, false_condition)?;
-
-            control_flow_graph.set_entry(head_index)?;
-            control_flow_graph.set_exit(false_index)?;
-        }
-        0b10000 | 0b10001 | 0b11000 | 0b11001 => {
-            // Decrement the CTF, then branch if CTR != 0
-            let head_index = {
-                let block = control_flow_graph.new_block()?;
-                block.assign(
-                    ctr.clone(),
-                    Expression::sub(ctr.clone().into(), expr_const(1, ctr.bits()))?,
-                );
-                block.index()
-            };
-
-            let true_index = {
-                let block = control_flow_graph.new_block()?;
-                block.branch(branch_target);
-                block.index()
-            };
-
-            let false_index = { control_flow_graph.new_block()?.index() };
-
-            let true_condition = Expression::cmpneq(ctr.clone().into(), expr_const(0, ctr.bits()))?;
-            let false_condition = Expression::cmpeq(ctr.clone().into(), expr_const(0, ctr.bits()))?;
-
-            control_flow_graph.conditional_edge(head_index, true_index, true_condition)?;
-
-            control_flow_graph.conditional_edge(head_index, false_index, false_condition)?;
-
-            control_flow_graph.set_entry(head_index)?;
-            control_flow_graph.set_exit(false_index)?;
-        }
-        0b10010 | 0b10011 | 0b11010 | 0b11011 => {
-            // Decrement the CTR, then branch if CTR == 0
-            let head_index = {
-                let block = control_flow_graph.new_block()?;
-                block.assign(
-                    ctr.clone(),
-                    Expression::sub(ctr.clone().into(), expr_const(1, ctr.bits()))?,
-                );
-                block.index()
-            };
-
-            let true_index = {
-                let block = control_flow_graph.new_block()?;
-                block.branch(branch_target);
-                block.index()
-            };
-
-            let false_index = { control_flow_graph.new_block()?.index() };
-
-            let true_condition = Expression::cmpeq(ctr.clone().into(), expr_const(0, ctr.bits()))?;
-            let false_condition =
-                Expression::cmpneq(ctr.clone().into(), expr_const(0, ctr.bits()))?;
-
-            control_flow_graph.conditional_edge(head_index, true_index, true_condition)?;
-
-            control_flow_graph.conditional_edge(head_index, false_index, false_condition)?;
-
-            control_flow_graph.set_entry(head_index)?;
-            control_flow_graph.set_exit(false_index)?;
-        }
-        0b10100 | 0b10101 | 0b10110 | 0b10111 | 0b11100 | 0b11101 | 0b11110 | 0b11111 => {
-            // Always branch
-            let block_index = {
-                let block = control_flow_graph.new_block()?;
-                block.branch(branch_target);
-                block.index()
-            };
-
-            control_flow_graph.set_entry(block_index)?;
-            control_flow_graph.set_exit(block_index)?;
-        }
-        _ => bail!("Invalid bo for bclr: {}", bo),
-    }
-
-    Ok(())
-}
-
-pub fn bctr(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Result<()> {
-    // get operands
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.branch(expr_scalar("ctr", 32));
+        block.assign(dst, src);
 
         block.index()
     };
@@ -748,21 +1158,24 @@ pub fn bctr(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> R
     Ok(())
 }
 
-pub fn cmpwi(
+pub fn lis(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let cr = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = expr_const(detail.operands[2].imm() as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let src = expr_const(
+        signed_immediate(detail.operands[1].imm() as u64) << 16,
+        mode.bits(),
+    );
 
     let block_index = {
-        let mut block = control_flow_graph.new_block()?;
+        let block = control_flow_graph.new_block()?;
 
-        set_condition_register_signed(&mut block, cr, lhs, rhs)?;
+        block.assign(dst, src);
 
         block.index()
     };
@@ -773,123 +1186,122 @@ pub fn cmpwi(
     Ok(())
 }
 
-pub fn cmplwi(
+/// Semantics for lmw, which loads words into the low words of the registers
+/// rD through r31.
+pub fn lmw(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let cr = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = expr_const(detail.operands[2].imm() as u64, 32);
-
-    let block_index = {
-        let mut block = control_flow_graph.new_block()?;
-
-        set_condition_register_unsigned(&mut block, cr, lhs, rhs)?;
-
-        block.index()
-    };
-
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+    let first = register_index(detail.operands[0].reg())?;
+    let (_, ea) = effective_address(&detail, mode)?;
+
+    single_block(control_flow_graph, |block| {
+        let address = temp(instruction, 0, mode.bits());
+        block.assign(address.clone(), ea);
+
+        for (i, register) in PPC_REGISTERS[first..32].iter().enumerate() {
+            let ea = Expr::add(
+                address.clone().into(),
+                expr_const(i as u64 * 4, mode.bits()),
+            )?;
+            if mode.bits() == 32 {
+                block.load(register.scalar(mode), ea);
+            } else {
+                let value = temp(instruction, i + 1, 32);
+                block.load(value.clone(), ea);
+                block.assign(
+                    register.scalar(mode),
+                    Expr::zext(mode.bits(), value.into())?,
+                );
+            }
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
 
-pub fn lbz(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
-    let detail = details(instruction)?;
-
-    // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = detail.operands[1].mem().disp;
-    let offset = expr_const(offset as u64, 32);
-
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
-
-        let ea = Expr::add(offset, base)?;
-
-        let temp = Scalar::temp(instruction.address, 8);
-        block.load(temp.clone(), ea);
-        block.assign(dst.clone(), Expression::zext(dst.bits(), temp.into())?);
-
-        block.index()
-    };
-
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
-
-    Ok(())
+/// Semantics for mcrf, which copies one condition register field to another.
+pub fn mcrf(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+) -> Result<()> {
+    let word = instruction_word(instruction);
+    let dst = ((word >> 23) & 0x7) as usize;
+    let src = ((word >> 18) & 0x7) as usize;
+
+    single_block(control_flow_graph, |block| {
+        for i in 0..4 {
+            block.assign(
+                condition_register_bit_to_flag(dst * 4 + i)?,
+                condition_register_bit_to_flag(src * 4 + i)?.into(),
+            );
+        }
+        Ok(())
+    })
 }
 
-pub fn li(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+/// Semantics for mfcr, and mfocrf, which we lift as reading the entire
+/// condition register.
+pub fn mfcr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
+    let bits = mode.bits();
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let src = expr_const(detail.operands[1].imm() as u64, 32);
-
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+
+    let mut src = expr_const(0, bits);
+    for i in 0..32 {
+        let flag: Expr = condition_register_bit_to_flag(i)?.into();
+        src = Expr::or(
+            src,
+            Expr::shl(Expr::zext(bits, flag)?, expr_const(31 - i as u64, bits))?,
+        )?;
+    }
 
+    single_block(control_flow_graph, |block| {
         block.assign(dst, src);
-
-        block.index()
-    };
-
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
-
-    Ok(())
+        Ok(())
+    })
 }
 
-pub fn lwz(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn mfctr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = detail.operands[1].mem().disp;
-    let offset = expr_const(offset as u64, 32);
-
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
-
-        let ea = Expr::add(offset, base)?;
-
-        block.load(dst, ea);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
 
-        block.index()
-    };
-
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
-
-    Ok(())
+    single_block(control_flow_graph, |block| {
+        block.assign(dst, expr_scalar("ctr", mode.bits()));
+        Ok(())
+    })
 }
 
-pub fn lwzu(
+pub fn mflr(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let base = get_register(detail.operands[1].mem().base)?.scalar();
-    let offset = detail.operands[1].mem().disp;
-    let offset = expr_const(offset as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        let ea = Expr::add(offset, base.clone().into())?;
-
-        block.load(dst, ea.clone());
-        block.assign(base, ea);
+        block.assign(dst, expr_scalar("lr", mode.bits()));
 
         block.index()
     };
@@ -900,43 +1312,78 @@ pub fn lwzu(
     Ok(())
 }
 
-pub fn lis(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+/// Semantics for mfxer. Only the SO, OV and CA bits of XER are modelled, and
+/// the byte count reads as zero.
+pub fn mfxer(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
+    let bits = mode.bits();
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let src = expr_const(detail.operands[1].imm() as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
 
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
-
-        let src = Expression::or(
-            Expression::and(src.clone(), expr_const(0x0000_ffff, 32))?,
-            Expression::shl(src, expr_const(16, 32))?,
-        )?;
-
-        block.assign(dst, src);
-
-        block.index()
+    let flag = |name: &str, bit: u64| -> Result<Expr> {
+        Expr::shl(
+            Expr::zext(bits, expr_scalar(name, 1))?,
+            expr_const(bit, bits),
+        )
     };
+    let src = Expr::or(
+        Expr::or(flag("xer-so", 31)?, flag("xer-ov", 30)?)?,
+        flag("xer-ca", 29)?,
+    )?;
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+    single_block(control_flow_graph, |block| {
+        block.assign(dst, src);
+        Ok(())
+    })
+}
 
-    Ok(())
+/// Semantics for mtcrf, and mtocrf, which write the condition register fields
+/// selected by the FXM mask.
+pub fn mtcrf(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    // Capstone omits the mask of mtocrf, so read it from the instruction word.
+    let word = instruction_word(instruction);
+    let src = general_purpose_register((word >> 21) & 0x1f).expression(mode);
+    let mask = (word >> 12) & 0xff;
+
+    single_block(control_flow_graph, |block| {
+        for field in 0..8_usize {
+            if mask & (0x80 >> field) == 0 {
+                continue;
+            }
+            for i in field * 4..field * 4 + 4 {
+                block.assign(
+                    condition_register_bit_to_flag(i)?,
+                    bit(src.clone(), 31 - i as u64)?,
+                );
+            }
+        }
+        Ok(())
+    })
 }
 
-pub fn mr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+pub fn mtctr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let src = get_register(detail.operands[1].reg())?.expression();
+    let src = get_register(detail.operands[0].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(dst, src);
+        block.assign(scalar("ctr", mode.bits()), src);
 
         block.index()
     };
@@ -947,19 +1394,20 @@ pub fn mr(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Ins
     Ok(())
 }
 
-pub fn mflr(
+pub fn mtlr(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
+    let src = get_register(detail.operands[0].reg())?.expression(mode);
 
     let block_index = {
         let block = control_flow_graph.new_block()?;
 
-        block.assign(dst, expr_scalar("lr", 32));
+        block.assign(scalar("lr", mode.bits()), src);
 
         block.index()
     };
@@ -970,51 +1418,100 @@ pub fn mflr(
     Ok(())
 }
 
-pub fn mtctr(
+pub fn mtxer(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let src = expr_const(detail.operands[1].imm() as u64, 32);
-
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
-
-        block.assign(dst, src);
-
-        block.index()
-    };
-
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+    let src = get_register(detail.operands[0].reg())?.expression(mode);
 
-    Ok(())
+    single_block(control_flow_graph, |block| {
+        block.assign(scalar("xer-so", 1), bit(src.clone(), 31)?);
+        block.assign(scalar("xer-ov", 1), bit(src.clone(), 30)?);
+        block.assign(scalar("xer-ca", 1), bit(src, 29)?);
+        Ok(())
+    })
 }
 
-pub fn mtlr(
+/// Semantics for mulli, and the word and doubleword multiplies mullw, mulhw,
+/// mulhwu, mulld, mulhd and mulhdu.
+///
+/// In 64-bit mode mullw writes the entire doubleword product, and the high
+/// word multiplies leave the high word of the result undefined. We extend the
+/// high word of the product into it.
+pub fn multiply(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    overflow: bool,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
+    let instruction_id = instruction_id(instruction)?;
 
     // get operands
-    let src = get_register(detail.operands[0].reg())?.expression();
-
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let lhs = get_register(detail.operands[1].reg())?.expression(mode);
+
+    // mulli keeps the low bits of the product, which are the same whether or
+    // not the operands are signed.
+    if instruction_id == ppc_insn::PPC_INS_MULLI {
+        let rhs = expr_const(
+            signed_immediate(detail.operands[2].imm() as u64),
+            mode.bits(),
+        );
+        return single_block(control_flow_graph, |block| {
+            block.assign(dst, Expr::mul(lhs, rhs)?);
+            Ok(())
+        });
+    }
 
-        block.assign(scalar("lr", 32), src);
+    let rhs = get_register(detail.operands[2].reg())?.expression(mode);
 
-        block.index()
+    let (bits, signed) = match instruction_id {
+        ppc_insn::PPC_INS_MULLW | ppc_insn::PPC_INS_MULHW => (32, true),
+        ppc_insn::PPC_INS_MULHWU => (32, false),
+        ppc_insn::PPC_INS_MULLD | ppc_insn::PPC_INS_MULHD => (64, true),
+        ppc_insn::PPC_INS_MULHDU => (64, false),
+        _ => bail!("Not a multiply instruction"),
     };
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+    let product = Expr::mul(
+        resize(resize(lhs, bits, false)?, bits * 2, signed)?,
+        resize(resize(rhs, bits, false)?, bits * 2, signed)?,
+    )?;
 
-    Ok(())
+    single_block(control_flow_graph, |block| {
+        let product_temp = temp(instruction, 0, bits * 2);
+        block.assign(product_temp.clone(), product);
+        let product: Expr = product_temp.into();
+
+        let src = match instruction_id {
+            ppc_insn::PPC_INS_MULLW | ppc_insn::PPC_INS_MULLD => {
+                resize(product.clone(), mode.bits(), true)?
+            }
+            _ => {
+                let high = Expr::shr(product.clone(), expr_const(bits as u64, bits * 2))?;
+                resize(Expr::trun(bits, high)?, mode.bits(), signed)?
+            }
+        };
+
+        if overflow {
+            // The product does not fit in the width of the operands.
+            let low = Expr::trun(bits, product.clone())?;
+            set_overflow(block, Expr::cmpneq(Expr::sext(bits * 2, low)?, product)?)?;
+        }
+
+        block.assign(dst.clone(), src);
+
+        if detail.update_cr0 {
+            record(block, dst.into())?;
+        }
+
+        Ok(())
+    })
 }
 
 pub fn nop(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Result<()> {
@@ -1030,182 +1527,420 @@ pub fn nop(control_flow_graph: &mut ControlFlowGraph, _: &capstone::Instr) -> Re
     Ok(())
 }
 
-pub fn rlwinm(
+/// Semantics for rlwinm, rlwimi and rlwnm, and their extended mnemonics, such
+/// as slwi and clrlwi.
+///
+/// In 64-bit mode the low word is rotated as though it were repeated in both
+/// words of the register.
+pub fn rotate(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
-    let detail = details(instruction)?;
-
-    let ra = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let sh = detail.operands[2].imm() as u64;
-    let mb = detail.operands[3].imm() as u64;
-    let me = detail.operands[4].imm() as u64;
+    // The extended mnemonics do not report the mask, so read the operands
+    // from the instruction word.
+    let word = instruction_word(instruction);
+    let bits = mode.bits();
+    let rs = resize(
+        general_purpose_register((word >> 21) & 0x1f).expression(mode),
+        32,
+        false,
+    )?;
+    let ra = general_purpose_register((word >> 16) & 0x1f);
+    let offset = bits as u32 - 32;
+    let mask = rotate_mask(
+        ((word >> 6) & 0x1f) + offset,
+        ((word >> 1) & 0x1f) + offset,
+        bits,
+    );
 
-    rlwinm_(control_flow_graph, ra, rs, sh, mb, me)
-}
+    let shift = match word >> 26 {
+        // rlwnm
+        23 => Expr::and(
+            resize(
+                general_purpose_register((word >> 11) & 0x1f).expression(mode),
+                32,
+                false,
+            )?,
+            expr_const(0x1f, 32),
+        )?,
+        _ => expr_const(u64::from((word >> 11) & 0x1f), 32),
+    };
+    let rotated = Expr::rotl(rs, shift)?;
+    let rotated = if bits == 32 {
+        rotated
+    } else {
+        let rotated = Expr::zext(bits, rotated)?;
+        Expr::or(Expr::shl(rotated.clone(), expr_const(32, bits))?, rotated)?
+    };
+    let rotated = Expr::and(rotated, expr_const(mask, bits))?;
+
+    let src = match word >> 26 {
+        // rlwimi
+        20 => Expr::or(
+            rotated,
+            Expr::and(ra.expression(mode), expr_const(!mask & mode.mask(), bits))?,
+        )?,
+        _ => rotated,
+    };
 
-pub fn slwi(
-    control_flow_graph: &mut ControlFlowGraph,
-    instruction: &capstone::Instr,
-) -> Result<()> {
-    let detail = details(instruction)?;
+    single_block(control_flow_graph, |block| {
+        block.assign(ra.scalar(mode), src);
 
-    let ra = get_register(detail.operands[0].reg())?.scalar();
-    let rs = get_register(detail.operands[1].reg())?.expression();
-    let sh = detail.operands[2].imm() as u64;
+        if word & 1 == 1 {
+            record(block, ra.expression(mode))?;
+        }
 
-    rlwinm_(control_flow_graph, ra, rs, sh, 0, 31 - sh)
+        Ok(())
+    })
 }
 
-pub fn srawi(
+/// Semantics for the doubleword rotates rldicl, rldicr, rldic, rldimi, rldcl
+/// and rldcr, and their extended mnemonics, such as sldi and clrldi.
+pub fn rotate_doubleword(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
-    let detail = details(instruction)?;
+    // The 6-bit shift and mask fields are split across the instruction word.
+    let word = instruction_word(instruction);
+    let rs = resize(
+        general_purpose_register((word >> 21) & 0x1f).expression(mode),
+        64,
+        false,
+    )?;
+    let ra = general_purpose_register((word >> 16) & 0x1f);
+    let sh = ((word >> 11) & 0x1f) | (((word >> 1) & 1) << 5);
+    let mb = ((word >> 6) & 0x1f) | (((word >> 5) & 1) << 5);
+
+    let immediate_shift = || expr_const(u64::from(sh), 64);
+    let register_shift = || -> Result<Expr> {
+        Expr::and(
+            resize(
+                general_purpose_register((word >> 11) & 0x1f).expression(mode),
+                64,
+                false,
+            )?,
+            expr_const(0x3f, 64),
+        )
+    };
 
-    // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = expr_const(detail.operands[2].imm() as u64, 32);
+    // The MD-form instructions have a 3-bit extended opcode, and the MDS-form
+    // instructions a 4-bit extended opcode.
+    let (shift, mask, insert) = match ((word >> 2) & 0x7, (word >> 1) & 0xf) {
+        // rldicl
+        (0, _) => (immediate_shift(), rotate_mask(mb, 63, 64), false),
+        // rldicr
+        (1, _) => (immediate_shift(), rotate_mask(0, mb, 64), false),
+        // rldic
+        (2, _) => (immediate_shift(), rotate_mask(mb, 63 - sh, 64), false),
+        // rldimi
+        (3, _) => (immediate_shift(), rotate_mask(mb, 63 - sh, 64), true),
+        // rldcl
+        (_, 8) => (register_shift()?, rotate_mask(mb, 63, 64), false),
+        // rldcr
+        (_, 9) => (register_shift()?, rotate_mask(0, mb, 64), false),
+        _ => bail!("Not a doubleword rotate instruction"),
+    };
 
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
+    let rotated = Expr::and(Expr::rotl(rs, shift)?, expr_const(mask, 64))?;
+    let src = if insert {
+        Expr::or(
+            rotated,
+            Expr::and(
+                resize(ra.expression(mode), 64, false)?,
+                expr_const(!mask, 64),
+            )?,
+        )?
+    } else {
+        rotated
+    };
+    let src = resize(src, mode.bits(), false)?;
 
-        block.assign(dst, Expression::sra(lhs, rhs)?);
+    single_block(control_flow_graph, |block| {
+        block.assign(ra.scalar(mode), src);
 
-        block.index()
-    };
+        if word & 1 == 1 {
+            record(block, ra.expression(mode))?;
+        }
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+        Ok(())
+    })
+}
 
-    Ok(())
+/// Semantics for sc, which we lift to an intrinsic.
+pub fn sc(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+    single_block(control_flow_graph, |block| {
+        block.intrinsic(Intrinsic::new(
+            "sc",
+            "sc",
+            Vec::new(),
+            Some(Vec::new()),
+            Some(Vec::new()),
+            instruction.bytes.get(0..4).unwrap().to_vec(),
+        ));
+        Ok(())
+    })
 }
 
-pub fn stw(control_flow_graph: &mut ControlFlowGraph, instruction: &capstone::Instr) -> Result<()> {
+/// Semantics for the word shifts slw, srw, sraw and srawi, and the doubleword
+/// shifts sld, srd, srad and sradi. Shift amounts from the width of the
+/// operand up to twice the width shift out every bit.
+pub fn shift(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
     let detail = details(instruction)?;
+    let instruction_id = instruction_id(instruction)?;
+
+    let (bits, algebraic) = match instruction_id {
+        ppc_insn::PPC_INS_SLW | ppc_insn::PPC_INS_SRW => (32, false),
+        ppc_insn::PPC_INS_SRAW | ppc_insn::PPC_INS_SRAWI => (32, true),
+        ppc_insn::PPC_INS_SLD | ppc_insn::PPC_INS_SRD => (64, false),
+        ppc_insn::PPC_INS_SRAD | ppc_insn::PPC_INS_SRADI => (64, true),
+        _ => bail!("Not a shift instruction"),
+    };
+    let all_ones = 0xffff_ffff_ffff_ffff >> (64 - bits);
 
     // get operands
-    let src = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = detail.operands[1].mem().disp;
-    let offset = expr_const(offset as u64, 32);
+    let dst = get_register(detail.operands[0].reg())?.scalar(mode);
+    let rs = resize(
+        get_register(detail.operands[1].reg())?.expression(mode),
+        bits,
+        false,
+    )?;
 
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
+    let (amount, overshift) = match instruction_id {
+        ppc_insn::PPC_INS_SRAWI | ppc_insn::PPC_INS_SRADI => (
+            expr_const(detail.operands[2].imm() as u64 & (bits as u64 - 1), bits),
+            expr_const(0, 1),
+        ),
+        _ => {
+            let rb = resize(
+                get_register(detail.operands[2].reg())?.expression(mode),
+                bits,
+                false,
+            )?;
+            let overshift = bit(rb.clone(), bits.trailing_zeros() as u64)?;
+            (Expr::and(rb, expr_const(bits as u64 - 1, bits))?, overshift)
+        }
+    };
 
-        let ea = Expr::add(offset, base)?;
+    let src = match instruction_id {
+        ppc_insn::PPC_INS_SLW | ppc_insn::PPC_INS_SLD => Expr::ite(
+            overshift.clone(),
+            expr_const(0, bits),
+            Expr::shl(rs.clone(), amount.clone())?,
+        )?,
+        ppc_insn::PPC_INS_SRW | ppc_insn::PPC_INS_SRD => Expr::ite(
+            overshift.clone(),
+            expr_const(0, bits),
+            Expr::shr(rs.clone(), amount.clone())?,
+        )?,
+        _ => Expr::ite(
+            overshift.clone(),
+            Expr::sra(rs.clone(), expr_const(bits as u64 - 1, bits))?,
+            Expr::sra(rs.clone(), amount.clone())?,
+        )?,
+    };
 
-        block.store(ea, src);
+    single_block(control_flow_graph, |block| {
+        // The algebraic shifts set XER[CA] when a negative value has 1 bits
+        // shifted out.
+        if algebraic {
+            let shifted_out = Expr::ite(
+                overshift,
+                rs.clone(),
+                Expr::and(
+                    rs.clone(),
+                    not(Expr::shl(expr_const(all_ones, bits), amount)?)?,
+                )?,
+            )?;
+            block.assign(
+                scalar("xer-ca", 1),
+                Expr::and(
+                    bit(rs, bits as u64 - 1)?,
+                    Expr::cmpneq(shifted_out, expr_const(0, bits))?,
+                )?,
+            );
+        }
 
-        block.index()
-    };
+        block.assign(dst.clone(), resize(src, mode.bits(), algebraic)?);
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+        if detail.update_cr0 {
+            record(block, dst.into())?;
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
 
-pub fn stmw(
+/// Semantics for the stores of bytes, halfwords, words and doublewords,
+/// including the update, indexed and byte-reversed forms.
+///
+/// We do not model reservations, so stwcx. and stdcx. always succeed.
+pub fn store(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
+    let instruction_id = instruction_id(instruction)?;
 
-    // get operands
-    let base = get_register(detail.operands[1].mem().base)?.expression();
-    let offset = detail.operands[1].mem().disp;
-    let mut offset = const_(offset as u64, 32);
+    let (bits, _, update) = memory_access(instruction_id)?;
 
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
-
-        let mut start_register: Option<usize> = None;
-        for (i, reg) in PPC_REGISTERS.iter().enumerate() {
-            if reg.capstone_reg == detail.operands[0].reg() {
-                start_register = Some(i);
-                break;
-            }
+    // get operands
+    let src = resize(
+        get_register(detail.operands[0].reg())?.expression(mode),
+        bits,
+        false,
+    )?;
+    let src = match instruction_id {
+        ppc_insn::PPC_INS_STHBRX | ppc_insn::PPC_INS_STWBRX | ppc_insn::PPC_INS_STDBRX => {
+            byte_reverse(src)?
         }
+        _ => src,
+    };
+    let (base, ea) = effective_address(&detail, mode)?;
 
-        let mut i = match start_register {
-            Some(i) => i,
-            None => bail!("Failed to find start register for stmwu"),
-        };
+    single_block(control_flow_graph, |block| {
+        let address = temp(instruction, 0, mode.bits());
+        block.assign(address.clone(), ea);
 
-        while PPC_REGISTERS[i].capstone_reg != ppc_reg::PPC_REG_CR0 {
-            let register = PPC_REGISTERS[i].expression();
-            let ea = Expr::add(offset.clone().into(), base.clone())?;
-            block.store(ea, register);
-            offset = offset.add(&const_(4, offset.bits()))?;
-            i += 1;
-        }
+        block.store(address.clone().into(), src);
 
-        block.index()
-    };
+        if update {
+            block.assign(get_register(base)?.scalar(mode), address.into());
+        }
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+        if let ppc_insn::PPC_INS_STWCX | ppc_insn::PPC_INS_STDCX = instruction_id {
+            set_condition_register(
+                block,
+                scalar("cr0", 32),
+                expr_const(0, 1),
+                expr_const(0, 1),
+                expr_const(1, 1),
+            );
+        }
 
-    Ok(())
+        Ok(())
+    })
 }
 
-pub fn stwu(
+/// Semantics for stmw, which stores the low words of the registers rS through
+/// r31.
+pub fn stmw(
     control_flow_graph: &mut ControlFlowGraph,
     instruction: &capstone::Instr,
+    mode: Mode,
 ) -> Result<()> {
     let detail = details(instruction)?;
 
     // get operands
-    let src = get_register(detail.operands[0].reg())?.expression();
-    let base = get_register(detail.operands[1].mem().base)?.scalar();
-    let offset = detail.operands[1].mem().disp;
-    let offset = expr_const(offset as u64, 32);
-
-    let block_index = {
-        let block = control_flow_graph.new_block()?;
+    let first = register_index(detail.operands[0].reg())?;
+    let (_, ea) = effective_address(&detail, mode)?;
+
+    single_block(control_flow_graph, |block| {
+        let address = temp(instruction, 0, mode.bits());
+        block.assign(address.clone(), ea);
+
+        for (i, register) in PPC_REGISTERS[first..32].iter().enumerate() {
+            let ea = Expr::add(
+                address.clone().into(),
+                expr_const(i as u64 * 4, mode.bits()),
+            )?;
+            block.store(ea, resize(register.expression(mode), 32, false)?);
+        }
 
-        let ea = Expr::add(offset, base.clone().into())?;
+        Ok(())
+    })
+}
 
-        block.store(ea.clone(), src);
-        block.assign(base, ea);
+/// Semantics for tw, twi, td and tdi, and their extended mnemonics. The TO
+/// field selects the comparisons which cause a trap, which we lift to an
+/// intrinsic.
+pub fn trap(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &capstone::Instr,
+    mode: Mode,
+) -> Result<()> {
+    let word = instruction_word(instruction);
+    let to = (word >> 21) & 0x1f;
 
-        block.index()
+    // tw and twi compare words, and td and tdi doublewords.
+    let bits = match (word >> 26, (word >> 1) & 0x3ff) {
+        (2, _) | (31, 68) => 64,
+        _ => 32,
+    };
+    let register = |index: u32| -> Result<Expr> {
+        resize(
+            general_purpose_register(index & 0x1f).expression(mode),
+            bits,
+            false,
+        )
+    };
+    let a = register(word >> 16)?;
+    let b = match word >> 26 {
+        2 | 3 => expr_const(signed_immediate(u64::from(word)), bits),
+        _ => register(word >> 11)?,
     };
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+    let mut condition = expr_const(0, 1);
+    let comparisons = [
+        (0b10000, Expr::cmplts(a.clone(), b.clone())?),
+        (0b01000, Expr::cmplts(b.clone(), a.clone())?),
+        (0b00100, Expr::cmpeq(a.clone(), b.clone())?),
+        (0b00010, Expr::cmpltu(a.clone(), b.clone())?),
+        (0b00001, Expr::cmpltu(b, a)?),
+    ];
+    for (mask, comparison) in comparisons.iter() {
+        if to & mask != 0 {
+            condition = Expr::or(condition, comparison.clone())?;
+        }
+    }
 
-    Ok(())
-}
+    // lt, gt and eq together always trap, as with `trap` (tw 31,0,0).
+    if to & 0b11100 == 0b11100 {
+        condition = expr_const(1, 1);
+    }
 
-pub fn subf(
-    control_flow_graph: &mut ControlFlowGraph,
-    instruction: &capstone::Instr,
-) -> Result<()> {
-    let detail = details(instruction)?;
+    let head_index = {
+        let block = control_flow_graph.new_block()?;
+        block.nop();
+        block.index()
+    };
 
-    // get operands
-    let dst = get_register(detail.operands[0].reg())?.scalar();
-    let lhs = get_register(detail.operands[1].reg())?.expression();
-    let rhs = get_register(detail.operands[2].reg())?.expression();
+    let tail_index = { control_flow_graph.new_block()?.index() };
 
-    let block_index = {
+    let trap_index = {
         let block = control_flow_graph.new_block()?;
 
-        let src = Expression::add(
-            Expression::add(Expression::xor(lhs, expr_const(0xffff_ffff, 32))?, rhs)?,
-            expr_const(1, 32),
-        )?;
-        block.assign(dst, src);
+        let intrinsic = Intrinsic::new(
+            "trap",
+            "trap",
+            Vec::new(),
+            Some(Vec::new()),
+            Some(Vec::new()),
+            instruction.bytes.get(0..4).unwrap().to_vec(),
+        );
+        block.intrinsic(intrinsic);
 
         block.index()
     };
 
-    control_flow_graph.set_entry(block_index)?;
-    control_flow_graph.set_exit(block_index)?;
+    control_flow_graph.conditional_edge(head_index, trap_index, condition.clone())?;
+
+    control_flow_graph.conditional_edge(
+        head_index,
+        tail_index,
+        Expr::cmpeq(condition, expr_const(0, 1))?,
+    )?;
+
+    control_flow_graph.unconditional_edge(trap_index, tail_index)?;
+
+    control_flow_graph.set_entry(head_index)?;
+    control_flow_graph.set_exit(tail_index)?;
 
     Ok(())
 }
@@ -6,23 +6,39 @@ use crate::memory;
 use crate::translator::ppc::*;
 use crate::RC;
 
-fn init_driver_block<'d>(
+macro_rules! backing {
+    ($e: expr) => {{
+        let v: Vec<u8> = $e.to_vec();
+        let mut b = memory::backing::Memory::new(Endian::Big);
+        b.set_memory(0, v, memory::MemoryPermissions::EXECUTE);
+        b
+    }};
+}
+
+fn init_driver_block(
+    architecture: RC<dyn architecture::Architecture>,
     instruction_bytes: &[u8],
     scalars: Vec<(&str, Constant)>,
     memory_: Memory,
 ) -> Driver {
     let mut bytes = instruction_bytes.to_vec();
     // ori 0,0,0
-    bytes.append(&mut vec![0x60, 0x00, 0x00, 0x00]);
+    match architecture.endian() {
+        Endian::Big => bytes.append(&mut vec![0x60, 0x00, 0x00, 0x00]),
+        Endian::Little => bytes.append(&mut vec![0x00, 0x00, 0x00, 0x60]),
+    }
 
-    let mut backing = memory::backing::Memory::new(Endian::Big);
+    let mut backing = memory::backing::Memory::new(architecture.endian());
     backing.set_memory(
         0,
         bytes.to_vec(),
         memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
     );
 
-    let function = Ppc::new().translate_function(&backing, 0).unwrap();
+    let function = architecture
+        .translator()
+        .translate_function(&backing, 0)
+        .unwrap();
 
     let location = if function
         .control_flow_graph()
@@ -45,6 +61,27 @@ fn init_driver_block<'d>(
         state.set_scalar(scalar.0, scalar.1);
     }
 
+    Driver::new(RC::new(program), location, state, architecture)
+}
+
+fn init_driver_function(
+    backing: memory::backing::Memory,
+    scalars: Vec<(&str, Constant)>,
+) -> Driver {
+    let memory = Memory::new_with_backing(Endian::Big, RC::new(backing));
+
+    let function = Ppc::new().translate_function(&memory, 0).unwrap();
+    let mut program = Program::new();
+
+    program.add_function(function);
+
+    let location = ProgramLocation::new(Some(0), FunctionLocation::Instruction(0, 0));
+
+    let mut state = State::new(memory);
+    for scalar in scalars {
+        state.set_scalar(scalar.0, scalar.1);
+    }
+
     Driver::new(
         RC::new(program),
         location,
@@ -53,29 +90,132 @@ fn init_driver_block<'d>(
     )
 }
 
-fn get_scalar(
+fn get_state(instruction_bytes: &[u8], scalars: Vec<(&str, Constant)>, memory: Memory) -> State {
+    get_state_architecture(
+        RC::new(architecture::Ppc::new()),
+        instruction_bytes,
+        scalars,
+        memory,
+    )
+}
+
+fn get_state_architecture(
+    architecture: RC<dyn architecture::Architecture>,
     instruction_bytes: &[u8],
     scalars: Vec<(&str, Constant)>,
     memory: Memory,
-    result_scalar: &str,
-) -> Constant {
-    let mut driver = init_driver_block(instruction_bytes, scalars, memory);
+) -> State {
+    let mut driver = init_driver_block(architecture, instruction_bytes, scalars, memory);
 
-    while driver
+    while !driver
         .location()
         .apply(driver.program())
         .unwrap()
         .forward()
         .unwrap()
-        .len()
-        > 0
+        .is_empty()
     {
         driver = driver.step().unwrap();
     }
-    // The final step
-    // driver = driver.step().unwrap();
 
-    driver.state().get_scalar(result_scalar).unwrap().clone()
+    driver.state().clone()
+}
+
+/// Executes `instruction_bytes` with the 64-bit big-endian translator.
+fn get_state_64(instruction_bytes: &[u8], scalars: Vec<(&str, Constant)>, memory: Memory) -> State {
+    get_state_architecture(
+        RC::new(architecture::Ppc64::new()),
+        instruction_bytes,
+        scalars,
+        memory,
+    )
+}
+
+fn get_scalar(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+    result_scalar: &str,
+) -> Constant {
+    get_state(instruction_bytes, scalars, memory)
+        .get_scalar(result_scalar)
+        .unwrap()
+        .clone()
+}
+
+fn get_intrinsic(
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+) -> Intrinsic {
+    get_intrinsic_architecture(
+        RC::new(architecture::Ppc::new()),
+        instruction_bytes,
+        scalars,
+        memory,
+    )
+}
+
+fn get_intrinsic_architecture(
+    architecture: RC<dyn architecture::Architecture>,
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+) -> Intrinsic {
+    let mut driver = init_driver_block(architecture, instruction_bytes, scalars, memory);
+
+    loop {
+        {
+            let location = driver.location().apply(driver.program()).unwrap();
+            if let Some(instruction) = location.instruction() {
+                if let Operation::Intrinsic { ref intrinsic } = *instruction.operation() {
+                    return intrinsic.clone();
+                }
+            }
+        }
+        driver = driver.step().unwrap();
+    }
+}
+
+fn step_to(mut driver: Driver, target_address: u64) -> Driver {
+    loop {
+        driver = driver.step().unwrap();
+        if let Some(address) = driver.location().apply(driver.program()).unwrap().address() {
+            if address == target_address {
+                return driver;
+            }
+        }
+    }
+}
+
+fn scalar_value(state: &State, name: &str) -> u64 {
+    state.get_scalar(name).unwrap().value_u64().unwrap()
+}
+
+fn memory_value(state: &State, address: u64, bits: usize) -> u64 {
+    state
+        .memory()
+        .load(address, bits)
+        .unwrap()
+        .unwrap()
+        .value_u64()
+        .unwrap()
+}
+
+const CONDITION_REGISTER_FLAGS: [&str; 32] = [
+    "cr0-lt", "cr0-gt", "cr0-eq", "cr0-so", "cr1-lt", "cr1-gt", "cr1-eq", "cr1-so", "cr2-lt",
+    "cr2-gt", "cr2-eq", "cr2-so", "cr3-lt", "cr3-gt", "cr3-eq", "cr3-so", "cr4-lt", "cr4-gt",
+    "cr4-eq", "cr4-so", "cr5-lt", "cr5-gt", "cr5-eq", "cr5-so", "cr6-lt", "cr6-gt", "cr6-eq",
+    "cr6-so", "cr7-lt", "cr7-gt", "cr7-eq", "cr7-so",
+];
+
+/// Sets the condition register flags from a 32-bit CR value.
+fn condition_register(value: u32) -> Vec<(&'static str, Constant)> {
+    CONDITION_REGISTER_FLAGS
+        .iter()
+        .enumerate()
+        .map(|(i, flag)| (*flag, const_(((value >> (31 - i)) & 1) as u64, 1)))
+        .collect()
 }
 
 #[test]
@@ -105,3 +245,1430 @@ fn rlwinm() {
     );
     assert_eq!(result.value_u64().unwrap(), 0xc010_c000);
 }
+
+#[test]
+fn addi() {
+    // addi 1,1,-16
+    let result = get_scalar(
+        &[0x38, 0x21, 0xff, 0xf0],
+        vec![("r1", const_(0x1000, 32))],
+        Memory::new(Endian::Big),
+        "r1",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xff0);
+}
+
+#[test]
+fn lis() {
+    // lis 3,0x8001
+    let result = get_scalar(
+        &[0x3c, 0x60, 0x80, 0x01],
+        vec![],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x8001_0000);
+}
+
+#[test]
+fn addc() {
+    // addc 3,4,5
+    let instruction_bytes = &[0x7c, 0x64, 0x28, 0x14];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(0xffff_ffff, 32)), ("r5", const_(2, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 1);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(1, 32)), ("r5", const_(2, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 3);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+}
+
+#[test]
+fn adde() {
+    // adde 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x29, 0x14],
+        vec![
+            ("r4", const_(1, 32)),
+            ("r5", const_(2, 32)),
+            ("xer-ca", const_(1, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 4);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+}
+
+#[test]
+fn addic_() {
+    // addic. 3,4,-1
+    let state = get_state(
+        &[0x34, 0x64, 0xff, 0xff],
+        vec![("r4", const_(0, 32)), ("xer-so", const_(0, 1))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+    assert_eq!(scalar_value(&state, "cr0-lt"), 1);
+    assert_eq!(scalar_value(&state, "cr0-gt"), 0);
+    assert_eq!(scalar_value(&state, "cr0-eq"), 0);
+    assert_eq!(scalar_value(&state, "cr0-so"), 0);
+}
+
+#[test]
+fn add_() {
+    // add. 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x2a, 0x15],
+        vec![
+            ("r4", const_(0xffff_ffff, 32)),
+            ("r5", const_(1, 32)),
+            ("xer-so", const_(1, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0);
+    assert_eq!(scalar_value(&state, "cr0-lt"), 0);
+    assert_eq!(scalar_value(&state, "cr0-eq"), 1);
+    assert_eq!(scalar_value(&state, "cr0-so"), 1);
+}
+
+#[test]
+fn addo() {
+    // addo 3,4,5
+    let instruction_bytes = &[0x7c, 0x64, 0x2e, 0x14];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![
+            ("r4", const_(0x7fff_ffff, 32)),
+            ("r5", const_(1, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x8000_0000);
+    assert_eq!(scalar_value(&state, "xer-ov"), 1);
+    assert_eq!(scalar_value(&state, "xer-so"), 1);
+
+    // Summary overflow is sticky
+    let state = get_state(
+        instruction_bytes,
+        vec![
+            ("r4", const_(1, 32)),
+            ("r5", const_(1, 32)),
+            ("xer-so", const_(1, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 2);
+    assert_eq!(scalar_value(&state, "xer-ov"), 0);
+    assert_eq!(scalar_value(&state, "xer-so"), 1);
+}
+
+#[test]
+fn subfc() {
+    // subfc 3,4,5
+    let instruction_bytes = &[0x7c, 0x64, 0x28, 0x10];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(1, 32)), ("r5", const_(3, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 2);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(3, 32)), ("r5", const_(1, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_fffe);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+}
+
+#[test]
+fn subfe() {
+    // subfe 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x29, 0x10],
+        vec![
+            ("r4", const_(1, 32)),
+            ("r5", const_(3, 32)),
+            ("xer-ca", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 1);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+}
+
+#[test]
+fn subfic() {
+    // subfic 3,4,0
+    let instruction_bytes = &[0x20, 0x64, 0x00, 0x00];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(5, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_fffb);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(0, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+}
+
+#[test]
+fn subfo_() {
+    // subfo. 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x2c, 0x51],
+        vec![
+            ("r4", const_(1, 32)),
+            ("r5", const_(0x8000_0000, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x7fff_ffff);
+    assert_eq!(scalar_value(&state, "xer-ov"), 1);
+    assert_eq!(scalar_value(&state, "cr0-gt"), 1);
+    assert_eq!(scalar_value(&state, "cr0-so"), 1);
+}
+
+#[test]
+fn neg() {
+    // neg 3,4
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x00, 0xd0],
+        vec![("r4", const_(1, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+}
+
+#[test]
+fn logical() {
+    // andc 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x83, 0x28, 0x78],
+        vec![
+            ("r4", const_(0xff00_ff00, 32)),
+            ("r5", const_(0x0ff0_0ff0, 32)),
+        ],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf000_f000);
+
+    // nand 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x83, 0x2b, 0xb8],
+        vec![
+            ("r4", const_(0xffff_0000, 32)),
+            ("r5", const_(0xff00_ff00, 32)),
+        ],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x00ff_ffff);
+
+    // eqv 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x83, 0x2a, 0x38],
+        vec![
+            ("r4", const_(0xf0f0_f0f0, 32)),
+            ("r5", const_(0xff00_ff00, 32)),
+        ],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xf00f_f00f);
+
+    // ori 3,4,0xffff
+    let result = get_scalar(
+        &[0x60, 0x83, 0xff, 0xff],
+        vec![("r4", const_(0x1234_0000, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1234_ffff);
+
+    // xoris 3,4,0x8000
+    let result = get_scalar(
+        &[0x6c, 0x83, 0x80, 0x00],
+        vec![("r4", const_(0x8000_0001, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn andi_() {
+    // andi. 3,4,0xff
+    let state = get_state(
+        &[0x70, 0x83, 0x00, 0xff],
+        vec![("r4", const_(0x1234, 32)), ("xer-so", const_(0, 1))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x34);
+    assert_eq!(scalar_value(&state, "cr0-gt"), 1);
+    assert_eq!(scalar_value(&state, "cr0-eq"), 0);
+}
+
+#[test]
+fn extsb() {
+    // extsb 3,4
+    let result = get_scalar(
+        &[0x7c, 0x83, 0x07, 0x74],
+        vec![("r4", const_(0x1280, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ff80);
+}
+
+#[test]
+fn extsh_() {
+    // extsh. 3,4
+    let state = get_state(
+        &[0x7c, 0x83, 0x07, 0x35],
+        vec![("r4", const_(0x8000, 32)), ("xer-so", const_(0, 1))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_8000);
+    assert_eq!(scalar_value(&state, "cr0-lt"), 1);
+}
+
+#[test]
+fn cntlzw() {
+    // cntlzw 3,4
+    let instruction_bytes = &[0x7c, 0x83, 0x00, 0x34];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r4", const_(0x0001_0000, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 15);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r4", const_(0, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 32);
+}
+
+#[test]
+fn slw() {
+    // slw 3,4,5
+    let instruction_bytes = &[0x7c, 0x83, 0x28, 0x30];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r4", const_(1, 32)), ("r5", const_(31, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x8000_0000);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r4", const_(1, 32)), ("r5", const_(32, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn srw() {
+    // srw 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x83, 0x2c, 0x30],
+        vec![("r4", const_(0x8000_0000, 32)), ("r5", const_(4, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x0800_0000);
+}
+
+#[test]
+fn sraw() {
+    // sraw 3,4,5
+    let instruction_bytes = &[0x7c, 0x83, 0x2e, 0x30];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(0x8000_0001, 32)), ("r5", const_(4, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xf800_0000);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(0x8000_0000, 32)), ("r5", const_(32, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(0x4000_0001, 32)), ("r5", const_(4, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x0400_0000);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+}
+
+#[test]
+fn srawi() {
+    // srawi 3,4,4
+    let instruction_bytes = &[0x7c, 0x83, 0x26, 0x70];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(0x8000_0000, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xf800_0000);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+
+    let state = get_state(
+        instruction_bytes,
+        vec![("r4", const_(0xffff_fff1, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+}
+
+#[test]
+fn rlwimi() {
+    // rlwimi 3,4,8,16,23
+    let result = get_scalar(
+        &[0x50, 0x83, 0x44, 0x2e],
+        vec![("r3", const_(0x1111_1111, 32)), ("r4", const_(0xab, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1111_ab11);
+}
+
+#[test]
+fn rlwnm() {
+    // rlwnm 3,4,5,24,31
+    let result = get_scalar(
+        &[0x5c, 0x83, 0x2e, 0x3e],
+        vec![("r4", const_(0x1234_5678, 32)), ("r5", const_(8, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x12);
+
+    // clrlwi 3,4,16
+    let result = get_scalar(
+        &[0x54, 0x83, 0x04, 0x3e],
+        vec![("r4", const_(0xdead_beef, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xbeef);
+}
+
+#[test]
+fn mullw() {
+    // mullw 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x29, 0xd6],
+        vec![("r4", const_(0x1_0000, 32)), ("r5", const_(0x1_0001, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1_0000);
+
+    // mulli 3,4,-3
+    let result = get_scalar(
+        &[0x1c, 0x64, 0xff, 0xfd],
+        vec![("r4", const_(5, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fff1);
+
+    // mullwo 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x2d, 0xd6],
+        vec![
+            ("r4", const_(0x1_0000, 32)),
+            ("r5", const_(0x1_0000, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0);
+    assert_eq!(scalar_value(&state, "xer-ov"), 1);
+    assert_eq!(scalar_value(&state, "xer-so"), 1);
+}
+
+#[test]
+fn mulhw() {
+    // mulhw 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x28, 0x96],
+        vec![("r4", const_(0xffff_fffe, 32)), ("r5", const_(3, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+
+    // mulhwu 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x28, 0x16],
+        vec![
+            ("r4", const_(0xffff_ffff, 32)),
+            ("r5", const_(0xffff_ffff, 32)),
+        ],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffe);
+}
+
+#[test]
+fn divw() {
+    // divw 3,4,5
+    let instruction_bytes = &[0x7c, 0x64, 0x2b, 0xd6];
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r4", const_(0xffff_fff9, 32)), ("r5", const_(2, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffd);
+
+    let result = get_scalar(
+        instruction_bytes,
+        vec![("r4", const_(7, 32)), ("r5", const_(0, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0);
+
+    // divwo 3,4,5
+    let instruction_bytes = &[0x7c, 0x64, 0x2f, 0xd6];
+
+    let state = get_state(
+        instruction_bytes,
+        vec![
+            ("r4", const_(0x8000_0000, 32)),
+            ("r5", const_(0xffff_ffff, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "xer-ov"), 1);
+    assert_eq!(scalar_value(&state, "xer-so"), 1);
+
+    let state = get_state(
+        instruction_bytes,
+        vec![
+            ("r4", const_(7, 32)),
+            ("r5", const_(2, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 3);
+    assert_eq!(scalar_value(&state, "xer-ov"), 0);
+    assert_eq!(scalar_value(&state, "xer-so"), 0);
+}
+
+#[test]
+fn divwu() {
+    // divwu 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x2b, 0x96],
+        vec![("r4", const_(0xffff_fffe, 32)), ("r5", const_(2, 32))],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x7fff_ffff);
+}
+
+#[test]
+fn load() {
+    let mut memory = Memory::new(Endian::Big);
+    memory.store(0x1000, const_(0xdead_beef, 32)).unwrap();
+
+    // lbzu 3,1(4)
+    let state = get_state(
+        &[0x8c, 0x64, 0x00, 0x01],
+        vec![("r4", const_(0x1000, 32))],
+        memory.clone(),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xad);
+    assert_eq!(scalar_value(&state, "r4"), 0x1001);
+
+    // lha 3,2(4)
+    let result = get_scalar(
+        &[0xa8, 0x64, 0x00, 0x02],
+        vec![("r4", const_(0x1000, 32))],
+        memory.clone(),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_beef);
+
+    // lhax 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x2a, 0xae],
+        vec![("r4", const_(0xffe, 32)), ("r5", const_(4, 32))],
+        memory.clone(),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_beef);
+
+    // lwzx 3,0,5
+    let result = get_scalar(
+        &[0x7c, 0x60, 0x28, 0x2e],
+        vec![("r0", const_(0x5000, 32)), ("r5", const_(0x1000, 32))],
+        memory.clone(),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xdead_beef);
+
+    // lhbrx 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x2e, 0x2c],
+        vec![("r4", const_(0x1000, 32)), ("r5", const_(0, 32))],
+        memory.clone(),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xadde);
+
+    // lwbrx 3,4,5
+    let result = get_scalar(
+        &[0x7c, 0x64, 0x2c, 0x2c],
+        vec![("r4", const_(0x1000, 32)), ("r5", const_(0, 32))],
+        memory,
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xefbe_adde);
+}
+
+#[test]
+fn store() {
+    // stbu 3,-1(4)
+    let state = get_state(
+        &[0x9c, 0x64, 0xff, 0xff],
+        vec![("r3", const_(0x4141, 32)), ("r4", const_(0x1001, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0x1000, 8), 0x41);
+    assert_eq!(scalar_value(&state, "r4"), 0x1000);
+
+    // sthx 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x2b, 0x2e],
+        vec![
+            ("r3", const_(0x1234_beef, 32)),
+            ("r4", const_(0x1000, 32)),
+            ("r5", const_(2, 32)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0x1002, 16), 0xbeef);
+
+    // stwbrx 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x2d, 0x2c],
+        vec![
+            ("r3", const_(0x1122_3344, 32)),
+            ("r4", const_(0x1000, 32)),
+            ("r5", const_(0, 32)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0x1000, 32), 0x4433_2211);
+
+    // stwux 3,4,5
+    let state = get_state(
+        &[0x7c, 0x64, 0x29, 0x6e],
+        vec![
+            ("r3", const_(0xdead_beef, 32)),
+            ("r4", const_(0xff0, 32)),
+            ("r5", const_(0x10, 32)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0x1000, 32), 0xdead_beef);
+    assert_eq!(scalar_value(&state, "r4"), 0x1000);
+}
+
+#[test]
+fn stwcx_() {
+    // stwcx. 3,0,4
+    let state = get_state(
+        &[0x7c, 0x60, 0x21, 0x2d],
+        vec![
+            ("r3", const_(0xdead_beef, 32)),
+            ("r4", const_(0x1000, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0x1000, 32), 0xdead_beef);
+    assert_eq!(scalar_value(&state, "cr0-eq"), 1);
+}
+
+#[test]
+fn lmw() {
+    let mut memory = Memory::new(Endian::Big);
+    memory.store(0x1000, const_(1, 32)).unwrap();
+    memory.store(0x1004, const_(2, 32)).unwrap();
+    memory.store(0x1008, const_(3, 32)).unwrap();
+
+    // lmw 29,0(4)
+    let state = get_state(
+        &[0xbb, 0xa4, 0x00, 0x00],
+        vec![("r4", const_(0x1000, 32))],
+        memory,
+    );
+    assert_eq!(scalar_value(&state, "r29"), 1);
+    assert_eq!(scalar_value(&state, "r30"), 2);
+    assert_eq!(scalar_value(&state, "r31"), 3);
+}
+
+#[test]
+fn stmw() {
+    // stmw 30,0(4)
+    let state = get_state(
+        &[0xbf, 0xc4, 0x00, 0x00],
+        vec![
+            ("r4", const_(0x1000, 32)),
+            ("r30", const_(0xaaaa, 32)),
+            ("r31", const_(0xbbbb, 32)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0x1000, 32), 0xaaaa);
+    assert_eq!(memory_value(&state, 0x1004, 32), 0xbbbb);
+}
+
+#[test]
+fn cmpw() {
+    // cmpw 7,3,4
+    let state = get_state(
+        &[0x7f, 0x83, 0x20, 0x00],
+        vec![
+            ("r3", const_(0xffff_ffff, 32)),
+            ("r4", const_(1, 32)),
+            ("xer-so", const_(1, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr7-lt"), 1);
+    assert_eq!(scalar_value(&state, "cr7-gt"), 0);
+    assert_eq!(scalar_value(&state, "cr7-eq"), 0);
+    assert_eq!(scalar_value(&state, "cr7-so"), 1);
+
+    // cmpwi 3,-1
+    let state = get_state(
+        &[0x2c, 0x03, 0xff, 0xff],
+        vec![("r3", const_(0xffff_ffff, 32)), ("xer-so", const_(0, 1))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr0-lt"), 0);
+    assert_eq!(scalar_value(&state, "cr0-gt"), 0);
+    assert_eq!(scalar_value(&state, "cr0-eq"), 1);
+}
+
+#[test]
+fn cmplw() {
+    // cmplw 3,4
+    let state = get_state(
+        &[0x7c, 0x03, 0x20, 0x40],
+        vec![
+            ("r3", const_(0xffff_ffff, 32)),
+            ("r4", const_(1, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr0-lt"), 0);
+    assert_eq!(scalar_value(&state, "cr0-gt"), 1);
+
+    // cmplwi 6,3,0xffff
+    let state = get_state(
+        &[0x2b, 0x03, 0xff, 0xff],
+        vec![("r3", const_(0x1_0000, 32)), ("xer-so", const_(0, 1))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr6-gt"), 1);
+}
+
+#[test]
+fn condition_register_logical() {
+    // crand 1,2,3
+    let mut scalars = condition_register(0x3000_0000);
+    let state = get_state(&[0x4c, 0x22, 0x1a, 0x02], scalars, Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "cr0-gt"), 1);
+
+    // crnor 4,5,6
+    scalars = condition_register(0x0400_0000);
+    let state = get_state(&[0x4c, 0x85, 0x30, 0x42], scalars, Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "cr1-lt"), 0);
+
+    scalars = condition_register(0);
+    let state = get_state(&[0x4c, 0x85, 0x30, 0x42], scalars, Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "cr1-lt"), 1);
+
+    // crset 31
+    scalars = condition_register(0);
+    let state = get_state(&[0x4f, 0xff, 0xfa, 0x42], scalars, Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "cr7-so"), 1);
+
+    // crnot 0,2
+    scalars = condition_register(0x2000_0000);
+    let state = get_state(&[0x4c, 0x02, 0x10, 0x42], scalars, Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "cr0-lt"), 0);
+}
+
+#[test]
+fn mcrf() {
+    // mcrf 7,0
+    let state = get_state(
+        &[0x4f, 0x80, 0x00, 0x00],
+        condition_register(0xa000_0000),
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr7-lt"), 1);
+    assert_eq!(scalar_value(&state, "cr7-gt"), 0);
+    assert_eq!(scalar_value(&state, "cr7-eq"), 1);
+    assert_eq!(scalar_value(&state, "cr7-so"), 0);
+}
+
+#[test]
+fn mfcr() {
+    // mfcr 3
+    let result = get_scalar(
+        &[0x7c, 0x60, 0x00, 0x26],
+        condition_register(0x8421_0013),
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x8421_0013);
+}
+
+#[test]
+fn mtcrf() {
+    // mtcrf 0x81,3
+    let mut scalars = condition_register(0x0f00_0000);
+    scalars.push(("r3", const_(0x8000_0001, 32)));
+    let state = get_state(&[0x7c, 0x68, 0x11, 0x20], scalars, Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "cr0-lt"), 1);
+    assert_eq!(scalar_value(&state, "cr1-lt"), 1);
+    assert_eq!(scalar_value(&state, "cr1-so"), 1);
+    assert_eq!(scalar_value(&state, "cr7-so"), 1);
+
+    // mtocrf 0x40,3
+    let mut scalars = condition_register(0);
+    scalars.push(("r3", const_(0x0400_0000, 32)));
+    let state = get_state(&[0x7c, 0x74, 0x01, 0x20], scalars, Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "cr1-gt"), 1);
+}
+
+#[test]
+fn mfxer() {
+    // mfxer 3
+    let result = get_scalar(
+        &[0x7c, 0x61, 0x02, 0xa6],
+        vec![
+            ("xer-so", const_(1, 1)),
+            ("xer-ov", const_(0, 1)),
+            ("xer-ca", const_(1, 1)),
+        ],
+        Memory::new(Endian::Big),
+        "r3",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xa000_0000);
+}
+
+#[test]
+fn mtxer() {
+    // mtxer 3
+    let state = get_state(
+        &[0x7c, 0x61, 0x03, 0xa6],
+        vec![("r3", const_(0x6000_0000, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "xer-so"), 0);
+    assert_eq!(scalar_value(&state, "xer-ov"), 1);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+}
+
+#[test]
+fn mtctr() {
+    // mtctr 3
+    let result = get_scalar(
+        &[0x7c, 0x69, 0x03, 0xa6],
+        vec![("r3", const_(0x1234, 32))],
+        Memory::new(Endian::Big),
+        "ctr",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1234);
+}
+
+#[test]
+fn sc() {
+    // sc
+    let intrinsic = get_intrinsic(&[0x44, 0x00, 0x00, 0x02], vec![], Memory::new(Endian::Big));
+    assert_eq!(intrinsic.mnemonic(), "sc");
+}
+
+#[test]
+fn trap() {
+    // tweqi 3,0
+    let intrinsic = get_intrinsic(
+        &[0x0c, 0x83, 0x00, 0x00],
+        vec![("r3", const_(0, 32))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(intrinsic.mnemonic(), "trap");
+
+    // trap
+    let intrinsic = get_intrinsic(&[0x7f, 0xe0, 0x00, 0x08], vec![], Memory::new(Endian::Big));
+    assert_eq!(intrinsic.mnemonic(), "trap");
+}
+
+#[test]
+fn bdnz() {
+    /*
+    li 3,0
+    li 4,5
+    mtctr 4
+    addi 3,3,2
+    bdnz -4
+    blr
+    */
+    let instruction_bytes = backing!([
+        0x38, 0x60, 0x00, 0x00, 0x38, 0x80, 0x00, 0x05, 0x7c, 0x89, 0x03, 0xa6, 0x38, 0x63, 0x00,
+        0x02, 0x42, 0x00, 0xff, 0xfc, 0x4e, 0x80, 0x00, 0x20
+    ]);
+
+    let driver = init_driver_function(instruction_bytes, vec![]);
+
+    let driver = step_to(driver, 0x14);
+
+    assert_eq!(scalar_value(driver.state(), "r3"), 10);
+    assert_eq!(scalar_value(driver.state(), "ctr"), 0);
+}
+
+#[test]
+fn beq() {
+    /*
+    cmpwi 3,0
+    beq +8
+    li 4,1
+    li 5,1
+    blr
+    */
+    let instruction_bytes = [
+        0x2c, 0x03, 0x00, 0x00, 0x41, 0x82, 0x00, 0x08, 0x38, 0x80, 0x00, 0x01, 0x38, 0xa0, 0x00,
+        0x01, 0x4e, 0x80, 0x00, 0x20,
+    ];
+
+    let driver = init_driver_function(
+        backing!(instruction_bytes),
+        vec![
+            ("r3", const_(0, 32)),
+            ("r4", const_(0, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+    );
+    let driver = step_to(driver, 0x10);
+    assert_eq!(scalar_value(driver.state(), "r4"), 0);
+    assert_eq!(scalar_value(driver.state(), "r5"), 1);
+
+    let driver = init_driver_function(
+        backing!(instruction_bytes),
+        vec![
+            ("r3", const_(1, 32)),
+            ("r4", const_(0, 32)),
+            ("xer-so", const_(0, 1)),
+        ],
+    );
+    let driver = step_to(driver, 0x10);
+    assert_eq!(scalar_value(driver.state(), "r4"), 1);
+    assert_eq!(scalar_value(driver.state(), "r5"), 1);
+}
+
+#[test]
+fn ld() {
+    let mut memory = Memory::new(Endian::Big);
+    memory
+        .store(0x1000, const_(0x1122_3344_5566_7788, 64))
+        .unwrap();
+    memory.store(0x1008, const_(0x8000_0000, 32)).unwrap();
+
+    // ld 3,8(4)
+    let result = get_state_64(
+        &[0xe8, 0x64, 0x00, 0x08],
+        vec![("r4", const_(0xff8, 64))],
+        memory.clone(),
+    );
+    assert_eq!(scalar_value(&result, "r3"), 0x1122_3344_5566_7788);
+
+    // ldu 3,-8(1)
+    let state = get_state_64(
+        &[0xe8, 0x61, 0xff, 0xf9],
+        vec![("r1", const_(0x1008, 64))],
+        memory.clone(),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x1122_3344_5566_7788);
+    assert_eq!(scalar_value(&state, "r1"), 0x1000);
+
+    // lwa 3,4(4)
+    let state = get_state_64(
+        &[0xe8, 0x64, 0x00, 0x06],
+        vec![("r4", const_(0x1004, 64))],
+        memory.clone(),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff_8000_0000);
+
+    // ldbrx 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x2c, 0x28],
+        vec![("r4", const_(0x800, 64)), ("r5", const_(0x800, 64))],
+        memory,
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x8877_6655_4433_2211);
+}
+
+#[test]
+fn std() {
+    // std 3,-8(1)
+    let state = get_state_64(
+        &[0xf8, 0x61, 0xff, 0xf8],
+        vec![
+            ("r1", const_(0x1000, 64)),
+            ("r3", const_(0x1122_3344_5566_7788, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0xff8, 64), 0x1122_3344_5566_7788);
+    assert_eq!(scalar_value(&state, "r1"), 0x1000);
+
+    // stdu 1,-112(1)
+    let state = get_state_64(
+        &[0xf8, 0x21, 0xff, 0x91],
+        vec![("r1", const_(0x1000, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(memory_value(&state, 0xf90, 64), 0x1000);
+    assert_eq!(scalar_value(&state, "r1"), 0xf90);
+}
+
+#[test]
+fn rotate_doubleword() {
+    let rs = ("r4", const_(0x1122_3344_5566_7788, 64));
+
+    // rldicl 3,4,8,56
+    let state = get_state_64(
+        &[0x78, 0x83, 0x46, 0x20],
+        vec![rs.clone()],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x11);
+
+    // rldicr 3,4,8,55
+    let state = get_state_64(
+        &[0x78, 0x83, 0x45, 0xe4],
+        vec![rs.clone()],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x2233_4455_6677_8800);
+
+    // rldic 3,4,8,8
+    let state = get_state_64(
+        &[0x78, 0x83, 0x42, 0x08],
+        vec![rs.clone()],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x0033_4455_6677_8800);
+
+    // rldimi 3,4,8,16
+    let state = get_state_64(
+        &[0x78, 0x83, 0x44, 0x0c],
+        vec![rs.clone(), ("r3", const_(0xaaaa_aaaa_aaaa_aaaa, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xaaaa_4455_6677_88aa);
+
+    // rldcr 3,4,5,63
+    let state = get_state_64(
+        &[0x78, 0x83, 0x2f, 0xf2],
+        vec![rs.clone(), ("r5", const_(0x44, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x1223_3445_5667_7881);
+
+    // srdi 3,4,3
+    let state = get_state_64(
+        &[0x78, 0x83, 0xe8, 0xc2],
+        vec![rs.clone()],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x0224_4668_8aac_cef1);
+
+    // clrldi 3,4,32
+    let state = get_state_64(
+        &[0x78, 0x83, 0x00, 0x20],
+        vec![rs],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x5566_7788);
+}
+
+#[test]
+fn rlwinm_64() {
+    // rlwinm 6,4,2,0,0x1D
+    let state = get_state_64(
+        &[0x54, 0x86, 0x10, 0x3a],
+        vec![("r4", const_(0xdead_beef_9000_3000, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r6"), 0x4000_c000);
+
+    // rotlwi 3,4,8
+    let state = get_state_64(
+        &[0x54, 0x83, 0x40, 0x3e],
+        vec![("r4", const_(0xdead_beef_1122_3344, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x2233_4411);
+}
+
+#[test]
+fn lis_64() {
+    // lis 3,0x8001
+    let state = get_state_64(&[0x3c, 0x60, 0x80, 0x01], vec![], Memory::new(Endian::Big));
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff_8001_0000);
+}
+
+#[test]
+fn mulld() {
+    // mulld 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x29, 0xd2],
+        vec![
+            ("r4", const_(0xffff_ffff_ffff_fffe, 64)),
+            ("r5", const_(3, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff_ffff_fffa);
+
+    // mulhd 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x28, 0x92],
+        vec![
+            ("r4", const_(0xffff_ffff_ffff_fffe, 64)),
+            ("r5", const_(3, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff_ffff_ffff);
+
+    // mulhdu 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x28, 0x12],
+        vec![
+            ("r4", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("r5", const_(2, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 1);
+
+    // mulldo 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x2d, 0xd2],
+        vec![
+            ("r4", const_(0x4000_0000_0000_0000, 64)),
+            ("r5", const_(4, 64)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0);
+    assert_eq!(scalar_value(&state, "xer-ov"), 1);
+
+    // mullw 3,4,5 writes the entire product
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x29, 0xd6],
+        vec![
+            ("r4", const_(0x1_0000_0000, 64)),
+            ("r5", const_(0xffff_ffff, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0);
+}
+
+#[test]
+fn divd() {
+    // divd 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x2b, 0xd2],
+        vec![
+            ("r4", const_(0xffff_ffff_ffff_fff9, 64)),
+            ("r5", const_(2, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff_ffff_fffd);
+
+    // divdu 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x2b, 0x92],
+        vec![
+            ("r4", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("r5", const_(0x10, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x0fff_ffff_ffff_ffff);
+
+    // divdo 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x64, 0x2f, 0xd2],
+        vec![
+            ("r4", const_(0x8000_0000_0000_0000, 64)),
+            ("r5", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "xer-ov"), 1);
+    assert_eq!(scalar_value(&state, "xer-so"), 1);
+}
+
+#[test]
+fn shift_doubleword() {
+    // sld 3,4,5
+    let instruction_bytes = &[0x7c, 0x83, 0x28, 0x36];
+    let state = get_state_64(
+        instruction_bytes,
+        vec![("r4", const_(1, 64)), ("r5", const_(63, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x8000_0000_0000_0000);
+
+    let state = get_state_64(
+        instruction_bytes,
+        vec![("r4", const_(1, 64)), ("r5", const_(64, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0);
+
+    // srd 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x83, 0x2c, 0x36],
+        vec![
+            ("r4", const_(0x8000_0000_0000_0000, 64)),
+            ("r5", const_(4, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x0800_0000_0000_0000);
+
+    // srad 3,4,5
+    let state = get_state_64(
+        &[0x7c, 0x83, 0x2e, 0x34],
+        vec![
+            ("r4", const_(0x8000_0000_0000_0010, 64)),
+            ("r5", const_(4, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xf800_0000_0000_0001);
+    assert_eq!(scalar_value(&state, "xer-ca"), 0);
+
+    // sradi 3,4,35
+    let state = get_state_64(
+        &[0x7c, 0x83, 0x1e, 0x76],
+        vec![("r4", const_(0x8000_0000_0000_0001, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff_f000_0000);
+    assert_eq!(scalar_value(&state, "xer-ca"), 1);
+
+    // slw 3,4,5 clears the high word
+    let state = get_state_64(
+        &[0x7c, 0x83, 0x28, 0x30],
+        vec![
+            ("r4", const_(0xffff_ffff_8000_0001, 64)),
+            ("r5", const_(1, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 2);
+}
+
+#[test]
+fn extsw() {
+    // extsw 3,4
+    let state = get_state_64(
+        &[0x7c, 0x83, 0x07, 0xb4],
+        vec![("r4", const_(0x1234_5678_8000_0000, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0xffff_ffff_8000_0000);
+}
+
+#[test]
+fn cntlzd() {
+    // cntlzd 3,4
+    let state = get_state_64(
+        &[0x7c, 0x83, 0x00, 0x74],
+        vec![("r4", const_(0x1_0000_0000, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 31);
+
+    // cntlzw 3,4 counts the low word
+    let state = get_state_64(
+        &[0x7c, 0x83, 0x00, 0x34],
+        vec![("r4", const_(0x1_0000_0000, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 32);
+}
+
+#[test]
+fn cmpd() {
+    // cmpd 3,4
+    let state = get_state_64(
+        &[0x7c, 0x23, 0x20, 0x00],
+        vec![
+            ("r3", const_(0xffff_ffff_0000_0000, 64)),
+            ("r4", const_(0, 64)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr0-lt"), 1);
+    assert_eq!(scalar_value(&state, "cr0-eq"), 0);
+
+    // cmpw 3,4 compares the low words
+    let state = get_state_64(
+        &[0x7c, 0x03, 0x20, 0x00],
+        vec![
+            ("r3", const_(0xffff_ffff_0000_0000, 64)),
+            ("r4", const_(0, 64)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr0-lt"), 0);
+    assert_eq!(scalar_value(&state, "cr0-eq"), 1);
+
+    // cmpdi 3,-1
+    let state = get_state_64(
+        &[0x2c, 0x23, 0xff, 0xff],
+        vec![
+            ("r3", const_(0xffff_ffff_ffff_ffff, 64)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr0-eq"), 1);
+
+    // cmpld 7,3,4
+    let state = get_state_64(
+        &[0x7f, 0xa3, 0x20, 0x40],
+        vec![
+            ("r3", const_(0xffff_ffff_0000_0000, 64)),
+            ("r4", const_(1, 64)),
+            ("xer-so", const_(0, 1)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "cr7-gt"), 1);
+    assert_eq!(scalar_value(&state, "cr7-lt"), 0);
+}
+
+#[test]
+fn td() {
+    // td 4,3,4
+    let intrinsic = get_intrinsic_architecture(
+        RC::new(architecture::Ppc64::new()),
+        &[0x7c, 0x83, 0x20, 0x88],
+        vec![
+            ("r3", const_(0x1_0000_0000, 64)),
+            ("r4", const_(0x1_0000_0000, 64)),
+        ],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(intrinsic.mnemonic(), "trap");
+
+    // tdi 4,3,0
+    let state = get_state_64(
+        &[0x08, 0x83, 0x00, 0x00],
+        vec![("r3", const_(0x1_0000_0000, 64))],
+        Memory::new(Endian::Big),
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x1_0000_0000);
+}
+
+#[test]
+fn ppc64le() {
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1000, const_(0x1122_3344_5566_7788, 64))
+        .unwrap();
+
+    // ld 3,8(4)
+    let state = get_state_architecture(
+        RC::new(architecture::Ppc64le::new()),
+        &[0x08, 0x00, 0x64, 0xe8],
+        vec![("r4", const_(0xff8, 64))],
+        memory,
+    );
+    assert_eq!(scalar_value(&state, "r3"), 0x1122_3344_5566_7788);
+
+    // addi 1,1,-16
+    let state = get_state_architecture(
+        RC::new(architecture::Ppc64le::new()),
+        &[0xf0, 0xff, 0x21, 0x38],
+        vec![("r1", const_(0x1000, 64))],
+        Memory::new(Endian::Little),
+    );
+    assert_eq!(scalar_value(&state, "r1"), 0xff0);
+}
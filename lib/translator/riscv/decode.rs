@@ -0,0 +1,590 @@
+//! A decoder for the RISC-V base integer instruction sets, and the M, A and C
+//! extensions.
+//!
+//! Capstone does not support RISC-V, so we decode instructions ourselves.
+//! Compressed instructions are expanded to the base instructions they are
+//! equivalent to.
+
+use crate::error::*;
+use crate::translator::riscv::mode::Mode;
+
+/// The operation an atomic memory operation performs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum AmoOp {
+    Swap,
+    Add,
+    Xor,
+    And,
+    Or,
+    Min,
+    Max,
+    Minu,
+    Maxu,
+}
+
+/// A decoded RISC-V operation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Opcode {
+    Lui,
+    Auipc,
+    Jal,
+    Jalr,
+    Beq,
+    Bne,
+    Blt,
+    Bge,
+    Bltu,
+    Bgeu,
+    Load {
+        bits: usize,
+        signed: bool,
+    },
+    Store {
+        bits: usize,
+    },
+    Addi,
+    Slti,
+    Sltiu,
+    Xori,
+    Ori,
+    Andi,
+    Slli,
+    Srli,
+    Srai,
+    Add,
+    Sub,
+    Sll,
+    Slt,
+    Sltu,
+    Xor,
+    Srl,
+    Sra,
+    Or,
+    And,
+    Addiw,
+    Slliw,
+    Srliw,
+    Sraiw,
+    Addw,
+    Subw,
+    Sllw,
+    Srlw,
+    Sraw,
+    Mul,
+    Mulh,
+    Mulhsu,
+    Mulhu,
+    Div,
+    Divu,
+    Rem,
+    Remu,
+    Mulw,
+    Divw,
+    Divuw,
+    Remw,
+    Remuw,
+    Lr {
+        bits: usize,
+    },
+    Sc {
+        bits: usize,
+    },
+    Amo {
+        op: AmoOp,
+        bits: usize,
+    },
+    Fence,
+    Ecall,
+    Ebreak,
+    /// Reads and writes of control and status registers. The immediate holds
+    /// the number of the csr.
+    Csr,
+    /// Returns from a trap handler, mret, sret and uret.
+    TrapReturn,
+}
+
+/// A decoded RISC-V instruction.
+#[derive(Clone, Debug)]
+pub(crate) struct Instruction {
+    pub address: u64,
+    /// The length of this instruction in bytes, 2 or 4.
+    pub length: usize,
+    pub opcode: Opcode,
+    pub mnemonic: &'static str,
+    pub rd: usize,
+    pub rs1: usize,
+    pub rs2: usize,
+    pub imm: i64,
+    pub bytes: Vec<u8>,
+}
+
+impl Instruction {
+    /// Returns the address of the instruction which follows this one.
+    pub fn next_address(&self) -> u64 {
+        self.address + self.length as u64
+    }
+}
+
+/// Returns bits `hi` through `lo`, inclusive, of `instruction`.
+fn field(instruction: u32, hi: u32, lo: u32) -> u32 {
+    (instruction >> lo) & ((1 << (hi - lo + 1)) - 1)
+}
+
+/// Sign-extends the low `bits` of `value`.
+fn sign_extend(value: u32, bits: u32) -> i64 {
+    i64::from(((value << (32 - bits)) as i32) >> (32 - bits))
+}
+
+fn i_immediate(instruction: u32) -> i64 {
+    i64::from((instruction as i32) >> 20)
+}
+
+fn s_immediate(instruction: u32) -> i64 {
+    sign_extend(
+        (field(instruction, 31, 25) << 5) | field(instruction, 11, 7),
+        12,
+    )
+}
+
+fn b_immediate(instruction: u32) -> i64 {
+    sign_extend(
+        (field(instruction, 31, 31) << 12)
+            | (field(instruction, 7, 7) << 11)
+            | (field(instruction, 30, 25) << 5)
+            | (field(instruction, 11, 8) << 1),
+        13,
+    )
+}
+
+fn u_immediate(instruction: u32) -> i64 {
+    i64::from((instruction & 0xffff_f000) as i32)
+}
+
+fn j_immediate(instruction: u32) -> i64 {
+    sign_extend(
+        (field(instruction, 31, 31) << 20)
+            | (field(instruction, 19, 12) << 12)
+            | (field(instruction, 20, 20) << 11)
+            | (field(instruction, 30, 21) << 1),
+        21,
+    )
+}
+
+/// The fields of an instruction before we know its length and address.
+struct Decoded {
+    opcode: Opcode,
+    mnemonic: &'static str,
+    rd: usize,
+    rs1: usize,
+    rs2: usize,
+    imm: i64,
+}
+
+impl Decoded {
+    fn new(
+        opcode: Opcode,
+        mnemonic: &'static str,
+        rd: u32,
+        rs1: u32,
+        rs2: u32,
+        imm: i64,
+    ) -> Decoded {
+        Decoded {
+            opcode,
+            mnemonic,
+            rd: rd as usize,
+            rs1: rs1 as usize,
+            rs2: rs2 as usize,
+            imm,
+        }
+    }
+}
+
+/// Decodes the instruction at the start of `bytes`.
+///
+/// Returns `None` if there are not enough bytes for the instruction.
+pub(crate) fn decode(mode: Mode, bytes: &[u8], address: u64) -> Result<Option<Instruction>> {
+    if bytes.len() < 2 {
+        return Ok(None);
+    }
+
+    let low = u32::from(bytes[0]) | (u32::from(bytes[1]) << 8);
+
+    let (decoded, length, raw) = if low & 0b11 != 0b11 {
+        (decode_compressed(mode, low), 2, low)
+    } else {
+        if bytes.len() < 4 {
+            return Ok(None);
+        }
+        let raw = low | (u32::from(bytes[2]) << 16) | (u32::from(bytes[3]) << 24);
+        (decode_standard(mode, raw), 4, raw)
+    };
+
+    let decoded = match decoded {
+        Some(decoded) => decoded,
+        None => {
+            let bytes = if length == 2 {
+                format!("{:04x}", raw)
+            } else {
+                format!("{:08x}", raw)
+            };
+            bail!("Unhandled instruction {} at 0x{:x}", bytes, address);
+        }
+    };
+
+    Ok(Some(Instruction {
+        address,
+        length,
+        opcode: decoded.opcode,
+        mnemonic: decoded.mnemonic,
+        rd: decoded.rd,
+        rs1: decoded.rs1,
+        rs2: decoded.rs2,
+        imm: decoded.imm,
+        bytes: bytes[0..length].to_vec(),
+    }))
+}
+
+/// Decodes a 32-bit instruction.
+fn decode_standard(mode: Mode, instruction: u32) -> Option<Decoded> {
+    let rd = field(instruction, 11, 7);
+    let rs1 = field(instruction, 19, 15);
+    let rs2 = field(instruction, 24, 20);
+    let funct3 = field(instruction, 14, 12);
+    let funct7 = field(instruction, 31, 25);
+    let rv64 = mode == Mode::Rv64;
+
+    let d = |opcode, mnemonic, imm| Some(Decoded::new(opcode, mnemonic, rd, rs1, rs2, imm));
+
+    match field(instruction, 6, 0) {
+        0x37 => d(Opcode::Lui, "lui", u_immediate(instruction)),
+        0x17 => d(Opcode::Auipc, "auipc", u_immediate(instruction)),
+        0x6f => d(Opcode::Jal, "jal", j_immediate(instruction)),
+        0x67 if funct3 == 0 => d(Opcode::Jalr, "jalr", i_immediate(instruction)),
+        0x63 => {
+            let imm = b_immediate(instruction);
+            match funct3 {
+                0 => d(Opcode::Beq, "beq", imm),
+                1 => d(Opcode::Bne, "bne", imm),
+                4 => d(Opcode::Blt, "blt", imm),
+                5 => d(Opcode::Bge, "bge", imm),
+                6 => d(Opcode::Bltu, "bltu", imm),
+                7 => d(Opcode::Bgeu, "bgeu", imm),
+                _ => None,
+            }
+        }
+        0x03 => {
+            let imm = i_immediate(instruction);
+            let load = |bits, signed| Opcode::Load { bits, signed };
+            match funct3 {
+                0 => d(load(8, true), "lb", imm),
+                1 => d(load(16, true), "lh", imm),
+                2 => d(load(32, true), "lw", imm),
+                3 if rv64 => d(load(64, true), "ld", imm),
+                4 => d(load(8, false), "lbu", imm),
+                5 => d(load(16, false), "lhu", imm),
+                6 if rv64 => d(load(32, false), "lwu", imm),
+                _ => None,
+            }
+        }
+        0x23 => {
+            let imm = s_immediate(instruction);
+            let store = |bits| Opcode::Store { bits };
+            match funct3 {
+                0 => d(store(8), "sb", imm),
+                1 => d(store(16), "sh", imm),
+                2 => d(store(32), "sw", imm),
+                3 if rv64 => d(store(64), "sd", imm),
+                _ => None,
+            }
+        }
+        0x13 => {
+            let imm = i_immediate(instruction);
+            // RV64 shifts take 6 bits of shift amount, RV32 shifts 5.
+            let shamt = if rv64 {
+                field(instruction, 25, 20)
+            } else {
+                field(instruction, 24, 20)
+            };
+            let shift_funct = if rv64 {
+                field(instruction, 31, 26) << 1
+            } else {
+                funct7
+            };
+            match funct3 {
+                0 => d(Opcode::Addi, "addi", imm),
+                2 => d(Opcode::Slti, "slti", imm),
+                3 => d(Opcode::Sltiu, "sltiu", imm),
+                4 => d(Opcode::Xori, "xori", imm),
+                6 => d(Opcode::Ori, "ori", imm),
+                7 => d(Opcode::Andi, "andi", imm),
+                1 if shift_funct == 0 => d(Opcode::Slli, "slli", i64::from(shamt)),
+                5 if shift_funct == 0 => d(Opcode::Srli, "srli", i64::from(shamt)),
+                5 if shift_funct == 0x20 => d(Opcode::Srai, "srai", i64::from(shamt)),
+                _ => None,
+            }
+        }
+        0x1b if rv64 => {
+            let shamt = i64::from(rs2);
+            match (funct3, funct7) {
+                (0, _) => d(Opcode::Addiw, "addiw", i_immediate(instruction)),
+                (1, 0) => d(Opcode::Slliw, "slliw", shamt),
+                (5, 0) => d(Opcode::Srliw, "srliw", shamt),
+                (5, 0x20) => d(Opcode::Sraiw, "sraiw", shamt),
+                _ => None,
+            }
+        }
+        0x33 => match (funct7, funct3) {
+            (0, 0) => d(Opcode::Add, "add", 0),
+            (0x20, 0) => d(Opcode::Sub, "sub", 0),
+            (0, 1) => d(Opcode::Sll, "sll", 0),
+            (0, 2) => d(Opcode::Slt, "slt", 0),
+            (0, 3) => d(Opcode::Sltu, "sltu", 0),
+            (0, 4) => d(Opcode::Xor, "xor", 0),
+            (0, 5) => d(Opcode::Srl, "srl", 0),
+            (0x20, 5) => d(Opcode::Sra, "sra", 0),
+            (0, 6) => d(Opcode::Or, "or", 0),
+            (0, 7) => d(Opcode::And, "and", 0),
+            (1, 0) => d(Opcode::Mul, "mul", 0),
+            (1, 1) => d(Opcode::Mulh, "mulh", 0),
+            (1, 2) => d(Opcode::Mulhsu, "mulhsu", 0),
+            (1, 3) => d(Opcode::Mulhu, "mulhu", 0),
+            (1, 4) => d(Opcode::Div, "div", 0),
+            (1, 5) => d(Opcode::Divu, "divu", 0),
+            (1, 6) => d(Opcode::Rem, "rem", 0),
+            (1, 7) => d(Opcode::Remu, "remu", 0),
+            _ => None,
+        },
+        0x3b if rv64 => match (funct7, funct3) {
+            (0, 0) => d(Opcode::Addw, "addw", 0),
+            (0x20, 0) => d(Opcode::Subw, "subw", 0),
+            (0, 1) => d(Opcode::Sllw, "sllw", 0),
+            (0, 5) => d(Opcode::Srlw, "srlw", 0),
+            (0x20, 5) => d(Opcode::Sraw, "sraw", 0),
+            (1, 0) => d(Opcode::Mulw, "mulw", 0),
+            (1, 4) => d(Opcode::Divw, "divw", 0),
+            (1, 5) => d(Opcode::Divuw, "divuw", 0),
+            (1, 6) => d(Opcode::Remw, "remw", 0),
+            (1, 7) => d(Opcode::Remuw, "remuw", 0),
+            _ => None,
+        },
+        0x2f => {
+            let (bits, word) = match funct3 {
+                2 => (32, true),
+                3 if rv64 => (64, false),
+                _ => return None,
+            };
+            let amo = |op| Opcode::Amo { op, bits };
+            let mnemonic = |w, d_| if word { w } else { d_ };
+            match field(instruction, 31, 27) {
+                0x02 if rs2 == 0 => d(Opcode::Lr { bits }, mnemonic("lr.w", "lr.d"), 0),
+                0x03 => d(Opcode::Sc { bits }, mnemonic("sc.w", "sc.d"), 0),
+                0x01 => d(amo(AmoOp::Swap), mnemonic("amoswap.w", "amoswap.d"), 0),
+                0x00 => d(amo(AmoOp::Add), mnemonic("amoadd.w", "amoadd.d"), 0),
+                0x04 => d(amo(AmoOp::Xor), mnemonic("amoxor.w", "amoxor.d"), 0),
+                0x0c => d(amo(AmoOp::And), mnemonic("amoand.w", "amoand.d"), 0),
+                0x08 => d(amo(AmoOp::Or), mnemonic("amoor.w", "amoor.d"), 0),
+                0x10 => d(amo(AmoOp::Min), mnemonic("amomin.w", "amomin.d"), 0),
+                0x14 => d(amo(AmoOp::Max), mnemonic("amomax.w", "amomax.d"), 0),
+                0x18 => d(amo(AmoOp::Minu), mnemonic("amominu.w", "amominu.d"), 0),
+                0x1c => d(amo(AmoOp::Maxu), mnemonic("amomaxu.w", "amomaxu.d"), 0),
+                _ => None,
+            }
+        }
+        0x0f => match funct3 {
+            0 => d(Opcode::Fence, "fence", 0),
+            1 => d(Opcode::Fence, "fence.i", 0),
+            _ => None,
+        },
+        0x73 => match funct3 {
+            0 => match instruction {
+                0x0000_0073 => d(Opcode::Ecall, "ecall", 0),
+                0x0010_0073 => d(Opcode::Ebreak, "ebreak", 0),
+                0x0020_0073 => d(Opcode::TrapReturn, "uret", 0),
+                0x1020_0073 => d(Opcode::TrapReturn, "sret", 0),
+                0x3020_0073 => d(Opcode::TrapReturn, "mret", 0),
+                0x1050_0073 => d(Opcode::Fence, "wfi", 0),
+                _ if funct7 == 0x09 && rd == 0 => d(Opcode::Fence, "sfence.vma", 0),
+                _ => None,
+            },
+            4 => None,
+            _ => {
+                let csr = i64::from(field(instruction, 31, 20));
+                let mnemonic = match funct3 {
+                    1 => "csrrw",
+                    2 => "csrrs",
+                    3 => "csrrc",
+                    5 => "csrrwi",
+                    6 => "csrrsi",
+                    _ => "csrrci",
+                };
+                d(Opcode::Csr, mnemonic, csr)
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Decodes a 16-bit compressed instruction, expanding it to the equivalent
+/// base instruction.
+fn decode_compressed(mode: Mode, instruction: u32) -> Option<Decoded> {
+    let rv64 = mode == Mode::Rv64;
+    let funct3 = field(instruction, 15, 13);
+
+    // Full register fields, and the 3-bit fields which address x8-x15.
+    let rd = field(instruction, 11, 7);
+    let rs2 = field(instruction, 6, 2);
+    let rd_ = field(instruction, 4, 2) + 8;
+    let rs1_ = field(instruction, 9, 7) + 8;
+
+    // The 6-bit signed immediate shared by many formats.
+    let imm6 = sign_extend(
+        (field(instruction, 12, 12) << 5) | field(instruction, 6, 2),
+        6,
+    );
+    let shamt = i64::from((field(instruction, 12, 12) << 5) | field(instruction, 6, 2));
+
+    // Offsets for c.lw/c.sw, and c.ld/c.sd.
+    let word_offset = i64::from(
+        (field(instruction, 12, 10) << 3)
+            | (field(instruction, 6, 6) << 2)
+            | (field(instruction, 5, 5) << 6),
+    );
+    let double_offset =
+        i64::from((field(instruction, 12, 10) << 3) | (field(instruction, 6, 5) << 6));
+
+    let load = |bits| Opcode::Load { bits, signed: true };
+    let store = |bits| Opcode::Store { bits };
+    let d = |opcode, mnemonic, rd, rs1, rs2, imm| {
+        Some(Decoded::new(opcode, mnemonic, rd, rs1, rs2, imm))
+    };
+
+    match (field(instruction, 1, 0), funct3) {
+        // c.addi4spn
+        (0, 0) => {
+            let imm = (field(instruction, 12, 11) << 4)
+                | (field(instruction, 10, 7) << 6)
+                | (field(instruction, 6, 6) << 2)
+                | (field(instruction, 5, 5) << 3);
+            if imm == 0 {
+                None
+            } else {
+                d(Opcode::Addi, "c.addi4spn", rd_, 2, 0, i64::from(imm))
+            }
+        }
+        (0, 2) => d(load(32), "c.lw", rd_, rs1_, 0, word_offset),
+        (0, 3) if rv64 => d(load(64), "c.ld", rd_, rs1_, 0, double_offset),
+        (0, 6) => d(store(32), "c.sw", 0, rs1_, rd_, word_offset),
+        (0, 7) if rv64 => d(store(64), "c.sd", 0, rs1_, rd_, double_offset),
+        (1, 0) => d(Opcode::Addi, "c.addi", rd, rd, 0, imm6),
+        (1, 1) => {
+            if rv64 {
+                if rd == 0 {
+                    None
+                } else {
+                    d(Opcode::Addiw, "c.addiw", rd, rd, 0, imm6)
+                }
+            } else {
+                d(Opcode::Jal, "c.jal", 1, 0, 0, cj_immediate(instruction))
+            }
+        }
+        (1, 2) => d(Opcode::Addi, "c.li", rd, 0, 0, imm6),
+        (1, 3) => {
+            if rd == 2 {
+                let imm = sign_extend(
+                    (field(instruction, 12, 12) << 9)
+                        | (field(instruction, 6, 6) << 4)
+                        | (field(instruction, 5, 5) << 6)
+                        | (field(instruction, 4, 3) << 7)
+                        | (field(instruction, 2, 2) << 5),
+                    10,
+                );
+                if imm == 0 {
+                    None
+                } else {
+                    d(Opcode::Addi, "c.addi16sp", 2, 2, 0, imm)
+                }
+            } else if imm6 == 0 {
+                None
+            } else {
+                d(Opcode::Lui, "c.lui", rd, 0, 0, imm6 << 12)
+            }
+        }
+        (1, 4) => match field(instruction, 11, 10) {
+            0 if rv64 || shamt < 32 => d(Opcode::Srli, "c.srli", rs1_, rs1_, 0, shamt),
+            1 if rv64 || shamt < 32 => d(Opcode::Srai, "c.srai", rs1_, rs1_, 0, shamt),
+            2 => d(Opcode::Andi, "c.andi", rs1_, rs1_, 0, imm6),
+            3 => match (field(instruction, 12, 12), field(instruction, 6, 5)) {
+                (0, 0) => d(Opcode::Sub, "c.sub", rs1_, rs1_, rd_, 0),
+                (0, 1) => d(Opcode::Xor, "c.xor", rs1_, rs1_, rd_, 0),
+                (0, 2) => d(Opcode::Or, "c.or", rs1_, rs1_, rd_, 0),
+                (0, 3) => d(Opcode::And, "c.and", rs1_, rs1_, rd_, 0),
+                (1, 0) if rv64 => d(Opcode::Subw, "c.subw", rs1_, rs1_, rd_, 0),
+                (1, 1) if rv64 => d(Opcode::Addw, "c.addw", rs1_, rs1_, rd_, 0),
+                _ => None,
+            },
+            _ => None,
+        },
+        (1, 5) => d(Opcode::Jal, "c.j", 0, 0, 0, cj_immediate(instruction)),
+        (1, 6) | (1, 7) => {
+            let imm = sign_extend(
+                (field(instruction, 12, 12) << 8)
+                    | (field(instruction, 11, 10) << 3)
+                    | (field(instruction, 6, 5) << 6)
+                    | (field(instruction, 4, 3) << 1)
+                    | (field(instruction, 2, 2) << 5),
+                9,
+            );
+            if funct3 == 6 {
+                d(Opcode::Beq, "c.beqz", 0, rs1_, 0, imm)
+            } else {
+                d(Opcode::Bne, "c.bnez", 0, rs1_, 0, imm)
+            }
+        }
+        (2, 0) if rv64 || shamt < 32 => d(Opcode::Slli, "c.slli", rd, rd, 0, shamt),
+        (2, 2) if rd != 0 => {
+            let imm = (field(instruction, 12, 12) << 5)
+                | (field(instruction, 6, 4) << 2)
+                | (field(instruction, 3, 2) << 6);
+            d(load(32), "c.lwsp", rd, 2, 0, i64::from(imm))
+        }
+        (2, 3) if rv64 && rd != 0 => {
+            let imm = (field(instruction, 12, 12) << 5)
+                | (field(instruction, 6, 5) << 3)
+                | (field(instruction, 4, 2) << 6);
+            d(load(64), "c.ldsp", rd, 2, 0, i64::from(imm))
+        }
+        (2, 4) => match (field(instruction, 12, 12), rd, rs2) {
+            (0, 0, 0) => None,
+            (0, _, 0) => d(Opcode::Jalr, "c.jr", 0, rd, 0, 0),
+            (0, _, _) => d(Opcode::Add, "c.mv", rd, 0, rs2, 0),
+            (1, 0, 0) => d(Opcode::Ebreak, "c.ebreak", 0, 0, 0, 0),
+            (1, _, 0) => d(Opcode::Jalr, "c.jalr", 1, rd, 0, 0),
+            _ => d(Opcode::Add, "c.add", rd, rd, rs2, 0),
+        },
+        (2, 6) => {
+            let imm = (field(instruction, 12, 9) << 2) | (field(instruction, 8, 7) << 6);
+            d(store(32), "c.swsp", 0, 2, rs2, i64::from(imm))
+        }
+        (2, 7) if rv64 => {
+            let imm = (field(instruction, 12, 10) << 3) | (field(instruction, 9, 7) << 6);
+            d(store(64), "c.sdsp", 0, 2, rs2, i64::from(imm))
+        }
+        _ => None,
+    }
+}
+
+/// The jump offset of c.j and c.jal.
+fn cj_immediate(instruction: u32) -> i64 {
+    sign_extend(
+        (field(instruction, 12, 12) << 11)
+            | (field(instruction, 11, 11) << 4)
+            | (field(instruction, 10, 9) << 8)
+            | (field(instruction, 8, 8) << 10)
+            | (field(instruction, 7, 7) << 6)
+            | (field(instruction, 6, 6) << 7)
+            | (field(instruction, 5, 3) << 1)
+            | (field(instruction, 2, 2) << 5),
+        12,
+    )
+}
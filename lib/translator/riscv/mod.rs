@@ -0,0 +1,205 @@
+//! Translator for 32 and 64-bit RISC-V.
+//!
+//! We support the RV32I and RV64I base integer instruction sets, with the M,
+//! A and C extensions. Capstone does not support RISC-V, so instructions are
+//! decoded by hand. Compressed instructions are lifted as the base
+//! instructions they expand to.
+
+use crate::error::*;
+use crate::il::*;
+use crate::translator::{BlockTranslationResult, Translator};
+
+mod decode;
+mod mode;
+mod semantics;
+#[cfg(test)]
+mod test;
+
+use self::decode::Opcode;
+use self::mode::Mode;
+
+/// The RV32 translator.
+#[derive(Clone, Debug, Default)]
+pub struct Riscv32;
+
+impl Riscv32 {
+    pub fn new() -> Riscv32 {
+        Riscv32
+    }
+}
+
+impl Translator for Riscv32 {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(Mode::Rv32, bytes, address)
+    }
+}
+
+/// The RV64 translator.
+#[derive(Clone, Debug, Default)]
+pub struct Riscv64;
+
+impl Riscv64 {
+    pub fn new() -> Riscv64 {
+        Riscv64
+    }
+}
+
+impl Translator for Riscv64 {
+    fn translate_block(&self, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+        translate_block(Mode::Rv64, bytes, address)
+    }
+}
+
+fn ensure_block_instruction(control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    let head_block_num_instructions = control_flow_graph
+        .block(control_flow_graph.entry().unwrap())?
+        .instructions()
+        .len();
+
+    if head_block_num_instructions == 0 {
+        let head_index = control_flow_graph.entry().unwrap();
+        control_flow_graph.block_mut(head_index)?.nop();
+    }
+
+    Ok(())
+}
+
+fn translate_block(mode: Mode, bytes: &[u8], address: u64) -> Result<BlockTranslationResult> {
+    // A vec which holds each lifted instruction in this block.
+    let mut block_graphs: Vec<(u64, ControlFlowGraph)> = Vec::new();
+
+    // the length of this block in bytes.
+    let mut length: usize = 0;
+
+    // The successors which exit this block.
+    let mut successors: Vec<(u64, Option<Expression>)> = Vec::new();
+
+    let mut terminated = false;
+
+    while let Some(instruction) = decode::decode(mode, &bytes[length..], address + length as u64)? {
+        let mut instruction_graph = ControlFlowGraph::new();
+
+        match instruction.opcode {
+            Opcode::Lui => semantics::lui(&mut instruction_graph, &instruction, mode),
+            Opcode::Auipc => semantics::auipc(&mut instruction_graph, &instruction, mode),
+            Opcode::Jal => semantics::jal(&mut instruction_graph, &instruction, mode),
+            Opcode::Jalr => semantics::jalr(&mut instruction_graph, &instruction, mode),
+            Opcode::Beq
+            | Opcode::Bne
+            | Opcode::Blt
+            | Opcode::Bge
+            | Opcode::Bltu
+            | Opcode::Bgeu
+            | Opcode::Fence => semantics::nop(&mut instruction_graph, &instruction, mode),
+            Opcode::Load { .. } | Opcode::Lr { .. } => {
+                semantics::load(&mut instruction_graph, &instruction, mode)
+            }
+            Opcode::Store { .. } => semantics::store(&mut instruction_graph, &instruction, mode),
+            Opcode::Sc { .. } => semantics::sc(&mut instruction_graph, &instruction, mode),
+            Opcode::Amo { .. } => semantics::amo(&mut instruction_graph, &instruction, mode),
+            Opcode::Addi
+            | Opcode::Slti
+            | Opcode::Sltiu
+            | Opcode::Xori
+            | Opcode::Ori
+            | Opcode::Andi
+            | Opcode::Slli
+            | Opcode::Srli
+            | Opcode::Srai => semantics::alu_immediate(&mut instruction_graph, &instruction, mode),
+            Opcode::Add
+            | Opcode::Sub
+            | Opcode::Sll
+            | Opcode::Slt
+            | Opcode::Sltu
+            | Opcode::Xor
+            | Opcode::Srl
+            | Opcode::Sra
+            | Opcode::Or
+            | Opcode::And
+            | Opcode::Mul
+            | Opcode::Mulh
+            | Opcode::Mulhsu
+            | Opcode::Mulhu
+            | Opcode::Div
+            | Opcode::Divu
+            | Opcode::Rem
+            | Opcode::Remu => semantics::alu_register(&mut instruction_graph, &instruction, mode),
+            Opcode::Addiw
+            | Opcode::Slliw
+            | Opcode::Srliw
+            | Opcode::Sraiw
+            | Opcode::Addw
+            | Opcode::Subw
+            | Opcode::Sllw
+            | Opcode::Srlw
+            | Opcode::Sraw
+            | Opcode::Mulw
+            | Opcode::Divw
+            | Opcode::Divuw
+            | Opcode::Remw
+            | Opcode::Remuw => semantics::alu_word(&mut instruction_graph, &instruction, mode),
+            Opcode::Ecall | Opcode::Ebreak => {
+                semantics::exception(&mut instruction_graph, &instruction, mode)
+            }
+            Opcode::Csr => semantics::csr(&mut instruction_graph, &instruction, mode),
+            Opcode::TrapReturn => {
+                semantics::trap_return(&mut instruction_graph, &instruction, mode)
+            }
+        }?;
+
+        // Writes to x0 are discarded, which may leave an instruction without
+        // semantics.
+        ensure_block_instruction(&mut instruction_graph)?;
+
+        instruction_graph.set_address(Some(instruction.address));
+        block_graphs.push((instruction.address, instruction_graph));
+
+        length += instruction.length;
+
+        // instructions that terminate blocks
+        match instruction.opcode {
+            Opcode::Jal if instruction.rd == 0 => {
+                let target = instruction.address.wrapping_add(instruction.imm as u64);
+                successors.push((target & mode.mask(), None));
+                terminated = true;
+            }
+            Opcode::Beq | Opcode::Bne | Opcode::Blt | Opcode::Bge | Opcode::Bltu | Opcode::Bgeu => {
+                let target = instruction.address.wrapping_add(instruction.imm as u64);
+                let condition = semantics::branch_condition(&instruction, mode)?;
+                successors.push((
+                    instruction.next_address(),
+                    Some(Expression::cmpeq(condition.clone(), expr_const(0, 1))?),
+                ));
+                successors.push((target & mode.mask(), Some(condition)));
+                terminated = true;
+            }
+            // instructions without successors
+            Opcode::Jalr if instruction.rd == 0 => {
+                terminated = true;
+            }
+            Opcode::TrapReturn => {
+                terminated = true;
+            }
+            _ => {}
+        }
+
+        if terminated {
+            break;
+        }
+    }
+
+    if length == 0 {
+        return Err(ErrorKind::DisassemblyFailure.into());
+    }
+
+    if !terminated {
+        successors.push((address + length as u64, None));
+    }
+
+    Ok(BlockTranslationResult::new(
+        block_graphs,
+        address,
+        length,
+        successors,
+    ))
+}
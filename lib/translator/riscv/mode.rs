@@ -0,0 +1,24 @@
+/// The base integer instruction set a RISC-V block is decoded in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Mode {
+    Rv32,
+    Rv64,
+}
+
+impl Mode {
+    /// Returns XLEN, the width of the integer registers, in bits.
+    pub(crate) fn bits(self) -> usize {
+        match self {
+            Mode::Rv32 => 32,
+            Mode::Rv64 => 64,
+        }
+    }
+
+    /// Returns a mask of the bits of an address.
+    pub(crate) fn mask(self) -> u64 {
+        match self {
+            Mode::Rv32 => 0xffff_ffff,
+            Mode::Rv64 => 0xffff_ffff_ffff_ffff,
+        }
+    }
+}
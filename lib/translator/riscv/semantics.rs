@@ -0,0 +1,508 @@
+use crate::error::*;
+use crate::il::Expression as Expr;
+use crate::il::*;
+use crate::translator::riscv::decode::{AmoOp, Instruction, Opcode};
+use crate::translator::riscv::mode::Mode;
+
+/// The ABI names of the integer registers, x0 through x31.
+const REGISTER_NAMES: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+/// Returns the ABI name of integer register `index`.
+pub fn register_name(index: usize) -> &'static str {
+    REGISTER_NAMES[index]
+}
+
+/// Returns the value of integer register `index`. x0 always reads as zero.
+fn register_value(index: usize, mode: Mode) -> Expr {
+    if index == 0 {
+        expr_const(0, mode.bits())
+    } else {
+        expr_scalar(REGISTER_NAMES[index], mode.bits())
+    }
+}
+
+/// Writes `value` to integer register `index`. Writes to x0 are discarded.
+fn set_register(block: &mut Block, index: usize, value: Expr, mode: Mode) {
+    if index != 0 {
+        block.assign(scalar(REGISTER_NAMES[index], mode.bits()), value);
+    }
+}
+
+/// Generates a temporary scalar unique to this instruction.
+fn temp(instruction: &Instruction, subindex: usize, bits: usize) -> Scalar {
+    Scalar::new(
+        format!("temp_0x{:X}_{}", instruction.address, subindex),
+        bits,
+    )
+}
+
+/// Returns the sign-extended immediate of this instruction at XLEN bits.
+fn immediate(instruction: &Instruction, mode: Mode) -> Expr {
+    expr_const(instruction.imm as u64, mode.bits())
+}
+
+/// Truncates or extends `expr` to `bits`.
+fn resize(expr: Expr, bits: usize, signed: bool) -> Result<Expr> {
+    if expr.bits() > bits {
+        Expr::trun(bits, expr)
+    } else if expr.bits() == bits {
+        Ok(expr)
+    } else if signed {
+        Expr::sext(bits, expr)
+    } else {
+        Expr::zext(bits, expr)
+    }
+}
+
+/// Returns the effective address of a load or store.
+fn memory_address(instruction: &Instruction, mode: Mode) -> Result<Expr> {
+    if instruction.imm == 0 {
+        Ok(register_value(instruction.rs1, mode))
+    } else {
+        Expr::add(
+            register_value(instruction.rs1, mode),
+            immediate(instruction, mode),
+        )
+    }
+}
+
+/// Sets the entry and exit of a graph holding a single block.
+fn single_block<F>(control_flow_graph: &mut ControlFlowGraph, f: F) -> Result<()>
+where
+    F: FnOnce(&mut Block) -> Result<()>,
+{
+    let block_index = {
+        let block = control_flow_graph.new_block()?;
+        f(block)?;
+        block.index()
+    };
+
+    control_flow_graph.set_entry(block_index)?;
+    control_flow_graph.set_exit(block_index)?;
+
+    Ok(())
+}
+
+/// Returns the condition under which a conditional branch is taken.
+pub fn branch_condition(instruction: &Instruction, mode: Mode) -> Result<Expr> {
+    let lhs = register_value(instruction.rs1, mode);
+    let rhs = register_value(instruction.rs2, mode);
+    let not = |expr| Expr::cmpeq(expr, expr_const(0, 1));
+    Ok(match instruction.opcode {
+        Opcode::Beq => Expr::cmpeq(lhs, rhs)?,
+        Opcode::Bne => Expr::cmpneq(lhs, rhs)?,
+        Opcode::Blt => Expr::cmplts(lhs, rhs)?,
+        Opcode::Bge => not(Expr::cmplts(lhs, rhs)?)?,
+        Opcode::Bltu => Expr::cmpltu(lhs, rhs)?,
+        Opcode::Bgeu => not(Expr::cmpltu(lhs, rhs)?)?,
+        _ => bail!("{} is not a conditional branch", instruction.mnemonic),
+    })
+}
+
+/// Applies a binary operation of the base instruction set.
+fn binary_operation(opcode: Opcode, lhs: Expr, rhs: Expr) -> Result<Expr> {
+    let bits = lhs.bits();
+    let shift_mask = || expr_const(bits as u64 - 1, bits);
+    Ok(match opcode {
+        Opcode::Add | Opcode::Addi | Opcode::Addw | Opcode::Addiw => Expr::add(lhs, rhs)?,
+        Opcode::Sub | Opcode::Subw => Expr::sub(lhs, rhs)?,
+        Opcode::Xor | Opcode::Xori => Expr::xor(lhs, rhs)?,
+        Opcode::Or | Opcode::Ori => Expr::or(lhs, rhs)?,
+        Opcode::And | Opcode::Andi => Expr::and(lhs, rhs)?,
+        Opcode::Slt | Opcode::Slti => Expr::zext(bits, Expr::cmplts(lhs, rhs)?)?,
+        Opcode::Sltu | Opcode::Sltiu => Expr::zext(bits, Expr::cmpltu(lhs, rhs)?)?,
+        Opcode::Slli | Opcode::Slliw => Expr::shl(lhs, rhs)?,
+        Opcode::Srli | Opcode::Srliw => Expr::shr(lhs, rhs)?,
+        Opcode::Srai | Opcode::Sraiw => Expr::sra(lhs, rhs)?,
+        Opcode::Sll | Opcode::Sllw => Expr::shl(lhs, Expr::and(rhs, shift_mask())?)?,
+        Opcode::Srl | Opcode::Srlw => Expr::shr(lhs, Expr::and(rhs, shift_mask())?)?,
+        Opcode::Sra | Opcode::Sraw => Expr::sra(lhs, Expr::and(rhs, shift_mask())?)?,
+        Opcode::Mul | Opcode::Mulw => Expr::mul(lhs, rhs)?,
+        Opcode::Mulh => high_multiply(lhs, rhs, true, true)?,
+        Opcode::Mulhsu => high_multiply(lhs, rhs, true, false)?,
+        Opcode::Mulhu => high_multiply(lhs, rhs, false, false)?,
+        // Division by zero does not trap. The quotient has all bits set, and
+        // the remainder is the dividend.
+        Opcode::Div | Opcode::Divw => Expr::ite(
+            Expr::cmpeq(rhs.clone(), expr_const(0, bits))?,
+            expr_const(0xffff_ffff_ffff_ffff, bits),
+            Expr::divs(lhs, rhs)?,
+        )?,
+        Opcode::Divu | Opcode::Divuw => Expr::ite(
+            Expr::cmpeq(rhs.clone(), expr_const(0, bits))?,
+            expr_const(0xffff_ffff_ffff_ffff, bits),
+            Expr::divu(lhs, rhs)?,
+        )?,
+        Opcode::Rem | Opcode::Remw => Expr::ite(
+            Expr::cmpeq(rhs.clone(), expr_const(0, bits))?,
+            lhs.clone(),
+            Expr::mods(lhs, rhs)?,
+        )?,
+        Opcode::Remu | Opcode::Remuw => Expr::ite(
+            Expr::cmpeq(rhs.clone(), expr_const(0, bits))?,
+            lhs.clone(),
+            Expr::modu(lhs, rhs)?,
+        )?,
+        _ => bail!("{:?} is not a binary operation", opcode),
+    })
+}
+
+/// Returns the upper half of the double-width product of `lhs` and `rhs`.
+fn high_multiply(lhs: Expr, rhs: Expr, lhs_signed: bool, rhs_signed: bool) -> Result<Expr> {
+    let bits = lhs.bits();
+    let product = Expr::mul(
+        resize(lhs, bits * 2, lhs_signed)?,
+        resize(rhs, bits * 2, rhs_signed)?,
+    )?;
+    Expr::trun(bits, Expr::shr(product, expr_const(bits as u64, bits * 2))?)
+}
+
+/// Semantics for the register-immediate operations, such as addi and slli.
+pub fn alu_immediate(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let result = binary_operation(
+        instruction.opcode,
+        register_value(instruction.rs1, mode),
+        immediate(instruction, mode),
+    )?;
+
+    single_block(control_flow_graph, |block| {
+        set_register(block, instruction.rd, result, mode);
+        Ok(())
+    })
+}
+
+/// Semantics for the register-register operations, including those of the M
+/// extension.
+pub fn alu_register(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let result = binary_operation(
+        instruction.opcode,
+        register_value(instruction.rs1, mode),
+        register_value(instruction.rs2, mode),
+    )?;
+
+    single_block(control_flow_graph, |block| {
+        set_register(block, instruction.rd, result, mode);
+        Ok(())
+    })
+}
+
+/// Semantics for the RV64 operations on words, such as addw and sraiw. These
+/// operate on the low 32 bits of their operands, and sign-extend the result.
+pub fn alu_word(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let lhs = resize(register_value(instruction.rs1, mode), 32, false)?;
+    let rhs = match instruction.opcode {
+        Opcode::Addiw | Opcode::Slliw | Opcode::Srliw | Opcode::Sraiw => {
+            expr_const(instruction.imm as u64, 32)
+        }
+        _ => resize(register_value(instruction.rs2, mode), 32, false)?,
+    };
+    let result = resize(
+        binary_operation(instruction.opcode, lhs, rhs)?,
+        mode.bits(),
+        true,
+    )?;
+
+    single_block(control_flow_graph, |block| {
+        set_register(block, instruction.rd, result, mode);
+        Ok(())
+    })
+}
+
+pub fn lui(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    single_block(control_flow_graph, |block| {
+        set_register(block, instruction.rd, immediate(instruction, mode), mode);
+        Ok(())
+    })
+}
+
+pub fn auipc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let value = instruction.address.wrapping_add(instruction.imm as u64);
+
+    single_block(control_flow_graph, |block| {
+        set_register(block, instruction.rd, expr_const(value, mode.bits()), mode);
+        Ok(())
+    })
+}
+
+/// Jump and link. Jumps which discard the link are lifted as nops, and
+/// followed through the successors of the block.
+pub fn jal(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let target = instruction.address.wrapping_add(instruction.imm as u64);
+
+    single_block(control_flow_graph, |block| {
+        if instruction.rd == 0 {
+            block.nop();
+        } else {
+            set_register(
+                block,
+                instruction.rd,
+                expr_const(instruction.next_address(), mode.bits()),
+                mode,
+            );
+            block.branch(expr_const(target, mode.bits()));
+        }
+        Ok(())
+    })
+}
+
+/// Jump and link to a register. The lowest bit of the target is cleared.
+pub fn jalr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let target = Expr::and(
+        memory_address(instruction, mode)?,
+        expr_const(!1, mode.bits()),
+    )?;
+
+    single_block(control_flow_graph, |block| {
+        // the target may be read from the link register, so read it first
+        let target_temp = temp(instruction, 0, mode.bits());
+        block.assign(target_temp.clone(), target);
+        set_register(
+            block,
+            instruction.rd,
+            expr_const(instruction.next_address(), mode.bits()),
+            mode,
+        );
+        block.branch(target_temp.into());
+        Ok(())
+    })
+}
+
+pub fn load(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let (bits, signed) = match instruction.opcode {
+        Opcode::Load { bits, signed } => (bits, signed),
+        Opcode::Lr { bits } => (bits, true),
+        _ => bail!("{} is not a load", instruction.mnemonic),
+    };
+    let address = memory_address(instruction, mode)?;
+
+    single_block(control_flow_graph, |block| {
+        let value = temp(instruction, 0, bits);
+        block.load(value.clone(), address);
+        set_register(
+            block,
+            instruction.rd,
+            resize(value.into(), mode.bits(), signed)?,
+            mode,
+        );
+        Ok(())
+    })
+}
+
+pub fn store(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let bits = match instruction.opcode {
+        Opcode::Store { bits } | Opcode::Sc { bits } => bits,
+        _ => bail!("{} is not a store", instruction.mnemonic),
+    };
+    let address = memory_address(instruction, mode)?;
+    let value = resize(register_value(instruction.rs2, mode), bits, false)?;
+
+    single_block(control_flow_graph, |block| {
+        block.store(address, value);
+        Ok(())
+    })
+}
+
+/// Store conditional. We do not model reservations, so the store always
+/// succeeds and writes zero to rd.
+pub fn sc(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    store(control_flow_graph, instruction, mode)?;
+
+    let exit = control_flow_graph.exit().unwrap();
+    set_register(
+        control_flow_graph.block_mut(exit)?,
+        instruction.rd,
+        expr_const(0, mode.bits()),
+        mode,
+    );
+
+    Ok(())
+}
+
+/// Atomic memory operations. rd receives the sign-extended value loaded from
+/// memory, and the result of the operation is stored back.
+pub fn amo(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let (op, bits) = match instruction.opcode {
+        Opcode::Amo { op, bits } => (op, bits),
+        _ => bail!("{} is not an atomic memory operation", instruction.mnemonic),
+    };
+    let address = register_value(instruction.rs1, mode);
+    let source = resize(register_value(instruction.rs2, mode), bits, false)?;
+
+    single_block(control_flow_graph, |block| {
+        let loaded = temp(instruction, 0, bits);
+        block.load(loaded.clone(), address.clone());
+
+        let lhs: Expr = loaded.clone().into();
+        let result = match op {
+            AmoOp::Swap => source,
+            AmoOp::Add => Expr::add(lhs, source)?,
+            AmoOp::Xor => Expr::xor(lhs, source)?,
+            AmoOp::And => Expr::and(lhs, source)?,
+            AmoOp::Or => Expr::or(lhs, source)?,
+            AmoOp::Min => Expr::ite(Expr::cmplts(lhs.clone(), source.clone())?, lhs, source)?,
+            AmoOp::Max => Expr::ite(Expr::cmplts(lhs.clone(), source.clone())?, source, lhs)?,
+            AmoOp::Minu => Expr::ite(Expr::cmpltu(lhs.clone(), source.clone())?, lhs, source)?,
+            AmoOp::Maxu => Expr::ite(Expr::cmpltu(lhs.clone(), source.clone())?, source, lhs)?,
+        };
+        block.store(address, result);
+
+        set_register(
+            block,
+            instruction.rd,
+            resize(loaded.into(), mode.bits(), true)?,
+            mode,
+        );
+        Ok(())
+    })
+}
+
+/// Semantics for conditional branches, fences and hints, which have no
+/// effect on our state.
+pub fn nop(control_flow_graph: &mut ControlFlowGraph, _: &Instruction, _: Mode) -> Result<()> {
+    single_block(control_flow_graph, |block| {
+        block.nop();
+        Ok(())
+    })
+}
+
+/// Semantics for ecall and ebreak, which we lift to intrinsics.
+pub fn exception(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    _: Mode,
+) -> Result<()> {
+    let mnemonic = instruction.mnemonic.trim_start_matches("c.");
+
+    single_block(control_flow_graph, |block| {
+        block.intrinsic(Intrinsic::new(
+            mnemonic,
+            mnemonic,
+            Vec::new(),
+            Some(Vec::new()),
+            Some(Vec::new()),
+            instruction.bytes.clone(),
+        ));
+        Ok(())
+    })
+}
+
+/// Semantics for the control and status register instructions, which we
+/// lift to intrinsics.
+///
+/// The arguments of the intrinsic are the number of the csr, and the value
+/// written to it.
+pub fn csr(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    mode: Mode,
+) -> Result<()> {
+    let immediate_form = instruction.mnemonic.ends_with('i');
+    let (source, source_str) = if immediate_form {
+        (
+            expr_const(instruction.rs1 as u64, mode.bits()),
+            format!("{}", instruction.rs1),
+        )
+    } else {
+        (
+            register_value(instruction.rs1, mode),
+            register_name(instruction.rs1).to_string(),
+        )
+    };
+
+    let written = if instruction.rd == 0 {
+        Vec::new()
+    } else {
+        vec![register_value(instruction.rd, mode)]
+    };
+    let read = if immediate_form || instruction.rs1 == 0 {
+        Vec::new()
+    } else {
+        vec![source.clone()]
+    };
+
+    single_block(control_flow_graph, |block| {
+        block.intrinsic(Intrinsic::new(
+            instruction.mnemonic,
+            format!(
+                "{} {}, 0x{:x}, {}",
+                instruction.mnemonic,
+                register_name(instruction.rd),
+                instruction.imm,
+                source_str
+            ),
+            vec![expr_const(instruction.imm as u64, 12), source],
+            Some(written),
+            Some(read),
+            instruction.bytes.clone(),
+        ));
+        Ok(())
+    })
+}
+
+/// Semantics for returns from trap handlers. The return address is held in
+/// a csr, so we lift these to intrinsics.
+pub fn trap_return(
+    control_flow_graph: &mut ControlFlowGraph,
+    instruction: &Instruction,
+    _: Mode,
+) -> Result<()> {
+    single_block(control_flow_graph, |block| {
+        block.intrinsic(Intrinsic::new(
+            instruction.mnemonic,
+            instruction.mnemonic,
+            Vec::new(),
+            None,
+            None,
+            instruction.bytes.clone(),
+        ));
+        Ok(())
+    })
+}
@@ -0,0 +1,747 @@
+use crate::architecture;
+use crate::architecture::{Architecture, Endian};
+use crate::executor::*;
+use crate::il::*;
+use crate::memory;
+use crate::translator::riscv::*;
+use crate::RC;
+
+fn architecture(bits: usize) -> Box<dyn Architecture> {
+    match bits {
+        32 => Box::new(architecture::Riscv32::new()),
+        _ => Box::new(architecture::Riscv64::new()),
+    }
+}
+
+fn init_driver_block(
+    bits: usize,
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory_: Memory,
+) -> Driver {
+    let mut bytes = instruction_bytes.to_vec();
+    // nop
+    bytes.append(&mut vec![0x13, 0x00, 0x00, 0x00]);
+
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        bytes.to_vec(),
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let function = architecture(bits)
+        .translator()
+        .translate_function(&backing, 0)
+        .unwrap();
+
+    let location = if function
+        .control_flow_graph()
+        .block(0)
+        .unwrap()
+        .instructions()
+        .is_empty()
+    {
+        ProgramLocation::new(Some(0), FunctionLocation::EmptyBlock(0))
+    } else {
+        ProgramLocation::new(Some(0), FunctionLocation::Instruction(0, 0))
+    };
+
+    let mut program = Program::new();
+    program.add_function(function);
+
+    let mut state = State::new(memory_);
+    for scalar in scalars {
+        state.set_scalar(scalar.0, scalar.1);
+    }
+
+    Driver::new(
+        RC::new(program),
+        location,
+        state,
+        RC::from(architecture(bits)),
+    )
+}
+
+fn init_driver_function(
+    bits: usize,
+    backing: memory::backing::Memory,
+    scalars: Vec<(&str, Constant)>,
+) -> Driver {
+    let memory = Memory::new_with_backing(Endian::Little, RC::new(backing));
+
+    let function = architecture(bits)
+        .translator()
+        .translate_function(&memory, 0)
+        .unwrap();
+    let mut program = Program::new();
+
+    program.add_function(function);
+
+    let location = ProgramLocation::new(Some(0), FunctionLocation::Instruction(0, 0));
+
+    let mut state = State::new(memory);
+    for scalar in scalars {
+        state.set_scalar(scalar.0, scalar.1);
+    }
+
+    Driver::new(
+        RC::new(program),
+        location,
+        state,
+        RC::from(architecture(bits)),
+    )
+}
+
+fn run(mut driver: Driver) -> Driver {
+    while !driver
+        .location()
+        .apply(driver.program())
+        .unwrap()
+        .forward()
+        .unwrap()
+        .is_empty()
+    {
+        driver = driver.step().unwrap();
+    }
+    driver
+}
+
+fn get_scalar(
+    bits: usize,
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+    result_scalar: &str,
+) -> Constant {
+    let driver = run(init_driver_block(bits, instruction_bytes, scalars, memory));
+    driver.state().get_scalar(result_scalar).unwrap().clone()
+}
+
+fn get_state(
+    bits: usize,
+    instruction_bytes: &[u8],
+    scalars: Vec<(&str, Constant)>,
+    memory: Memory,
+) -> State {
+    let driver = run(init_driver_block(bits, instruction_bytes, scalars, memory));
+    driver.state().clone()
+}
+
+fn get_intrinsic(bits: usize, instruction_bytes: &[u8]) -> Intrinsic {
+    let mut driver =
+        init_driver_block(bits, instruction_bytes, vec![], Memory::new(Endian::Little));
+
+    loop {
+        {
+            let location = driver.location().apply(driver.program()).unwrap();
+            if let Some(instruction) = location.instruction() {
+                if let Operation::Intrinsic { ref intrinsic } = *instruction.operation() {
+                    return intrinsic.clone();
+                }
+            }
+        }
+        driver = driver.step().unwrap();
+    }
+}
+
+fn step_to(mut driver: Driver, target_address: u64) -> Driver {
+    loop {
+        driver = driver.step().unwrap();
+        if let Some(address) = driver.location().apply(driver.program()).unwrap().address() {
+            if address == target_address {
+                return driver;
+            }
+        }
+    }
+}
+
+#[test]
+fn add() {
+    // add a0, a1, a2
+    let instruction_bytes = &[0x33, 0x85, 0xc5, 0x00];
+
+    let result = get_scalar(
+        32,
+        instruction_bytes,
+        vec![("a1", const_(1, 32)), ("a2", const_(2, 32))],
+        Memory::new(Endian::Little),
+        "a0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 3);
+}
+
+#[test]
+fn sub() {
+    // sub a0, a1, a2
+    let instruction_bytes = &[0x33, 0x85, 0xc5, 0x40];
+
+    let result = get_scalar(
+        64,
+        instruction_bytes,
+        vec![("a1", const_(1, 64)), ("a2", const_(2, 64))],
+        Memory::new(Endian::Little),
+        "a0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_ffff);
+}
+
+#[test]
+fn zero_register() {
+    // add zero, a1, a2
+    // add a0, zero, a1
+    let instruction_bytes = &[0x33, 0x80, 0xc5, 0x00, 0x33, 0x05, 0xb0, 0x00];
+
+    let state = get_state(
+        32,
+        instruction_bytes,
+        vec![("a1", const_(1, 32)), ("a2", const_(2, 32))],
+        Memory::new(Endian::Little),
+    );
+    assert!(state.get_scalar("zero").is_none());
+    assert_eq!(state.get_scalar("a0").unwrap().value_u64().unwrap(), 1);
+}
+
+#[test]
+fn addi() {
+    // addi a0, a1, -1
+    let instruction_bytes = &[0x13, 0x85, 0xf5, 0xff];
+
+    let result = get_scalar(
+        32,
+        instruction_bytes,
+        vec![("a1", const_(0, 32))],
+        Memory::new(Endian::Little),
+        "a0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+}
+
+#[test]
+fn slt() {
+    // slt a0, a1, a2
+    let slt = &[0x33, 0xa5, 0xc5, 0x00];
+    // sltu a0, a1, a2
+    let sltu = &[0x33, 0xb5, 0xc5, 0x00];
+    // sltiu a0, a1, -1
+    let sltiu = &[0x13, 0xb5, 0xf5, 0xff];
+
+    let scalars = vec![("a1", const_(0xffff_ffff, 32)), ("a2", const_(1, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(32, slt, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 1);
+    let result = get_scalar(32, sltu, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0);
+
+    let scalars = vec![("a1", const_(5, 32))];
+    let result = get_scalar(32, sltiu, scalars, memory, "a0");
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn shifts() {
+    // sra a0, a1, a2
+    let sra = &[0x33, 0xd5, 0xc5, 0x40];
+    // srai a0, a1, 63
+    let srai = &[0x13, 0xd5, 0xf5, 0x43];
+    // srl a0, a1, a2
+    let srl = &[0x33, 0xd5, 0xc5, 0x00];
+
+    // the shift amount is taken from the low 5 bits of a2
+    let scalars = vec![("a1", const_(0x8000_0000, 32)), ("a2", const_(0x24, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(32, sra, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xf800_0000);
+
+    let scalars = vec![("a1", const_(0x8000_0000_0000_0000, 64))];
+    let result = get_scalar(64, srai, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_ffff);
+
+    // the shift amount is taken from the low 6 bits of a2
+    let scalars = vec![
+        ("a1", const_(0x8000_0000_0000_0000, 64)),
+        ("a2", const_(0x43, 64)),
+    ];
+    let result = get_scalar(64, srl, scalars, memory, "a0");
+    assert_eq!(result.value_u64().unwrap(), 0x1000_0000_0000_0000);
+}
+
+#[test]
+fn lui_auipc() {
+    // lui a0, 0xfffff
+    let lui = &[0x37, 0xf5, 0xff, 0xff];
+    // auipc a0, 0xfffff
+    let auipc = &[0x17, 0xf5, 0xff, 0xff];
+
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(32, lui, vec![], memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_f000);
+    let result = get_scalar(64, lui, vec![], memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_f000);
+    let result = get_scalar(64, auipc, vec![], memory, "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_f000);
+}
+
+#[test]
+fn multiply() {
+    // mul a0, a1, a2
+    let mul = &[0x33, 0x85, 0xc5, 0x02];
+    // mulh a0, a1, a2
+    let mulh = &[0x33, 0x95, 0xc5, 0x02];
+    // mulhsu a0, a1, a2
+    let mulhsu = &[0x33, 0xa5, 0xc5, 0x02];
+    // mulhu a0, a1, a2
+    let mulhu = &[0x33, 0xb5, 0xc5, 0x02];
+
+    let scalars = vec![("a1", const_(0xffff_ffff, 32)), ("a2", const_(2, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(32, mul, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffe);
+    let result = get_scalar(32, mulh, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+    let result = get_scalar(32, mulhsu, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+    let result = get_scalar(32, mulhu, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 1);
+
+    let scalars = vec![
+        ("a1", const_(0xffff_ffff_ffff_ffff, 64)),
+        ("a2", const_(0xffff_ffff_ffff_ffff, 64)),
+    ];
+    let result = get_scalar(64, mulhu, scalars, memory, "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_fffe);
+}
+
+#[test]
+fn divide() {
+    // div a0, a1, a2
+    let div = &[0x33, 0xc5, 0xc5, 0x02];
+    // divu a0, a1, a2
+    let divu = &[0x33, 0xd5, 0xc5, 0x02];
+    // rem a0, a1, a2
+    let rem = &[0x33, 0xe5, 0xc5, 0x02];
+    // remu a0, a1, a2
+    let remu = &[0x33, 0xf5, 0xc5, 0x02];
+
+    let scalars = vec![("a1", const_(0xffff_fff9, 32)), ("a2", const_(2, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(32, div, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_fffd);
+    let result = get_scalar(32, rem, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+    let result = get_scalar(32, divu, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0x7fff_fffc);
+    let result = get_scalar(32, remu, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 1);
+
+    // overflow
+    let scalars = vec![
+        ("a1", const_(0x8000_0000, 32)),
+        ("a2", const_(0xffff_ffff, 32)),
+    ];
+    let result = get_scalar(32, div, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0x8000_0000);
+    let result = get_scalar(32, rem, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn divide_by_zero() {
+    // div a0, a1, a2
+    let div = &[0x33, 0xc5, 0xc5, 0x02];
+    // divu a0, a1, a2
+    let divu = &[0x33, 0xd5, 0xc5, 0x02];
+    // remu a0, a1, a2
+    let remu = &[0x33, 0xf5, 0xc5, 0x02];
+    // remw a0, a1, a2
+    let remw = &[0x3b, 0xe5, 0xc5, 0x02];
+
+    let scalars = vec![("a1", const_(7, 32)), ("a2", const_(0, 32))];
+    let memory = Memory::new(Endian::Little);
+    let result = get_scalar(32, div, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff);
+    let result = get_scalar(32, remu, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 7);
+
+    let scalars = vec![("a1", const_(7, 64)), ("a2", const_(0, 64))];
+    let result = get_scalar(64, divu, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_ffff);
+
+    let scalars = vec![
+        ("a1", const_(0x1_8000_0000, 64)),
+        ("a2", const_(0x1_0000_0000, 64)),
+    ];
+    let result = get_scalar(64, remw, scalars, memory, "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0000);
+}
+
+#[test]
+fn word_operations() {
+    // addw a0, a1, a2
+    let addw = &[0x3b, 0x85, 0xc5, 0x00];
+    // addiw a0, a1, 1
+    let addiw = &[0x1b, 0x85, 0x15, 0x00];
+    // sllw a0, a1, a2
+    let sllw = &[0x3b, 0x95, 0xc5, 0x00];
+    // sraiw a0, a1, 1
+    let sraiw = &[0x1b, 0xd5, 0x15, 0x40];
+    // srliw a0, a1, 1
+    let srliw = &[0x1b, 0xd5, 0x15, 0x00];
+    // divuw a0, a1, a2
+    let divuw = &[0x3b, 0xd5, 0xc5, 0x02];
+
+    let memory = Memory::new(Endian::Little);
+    let scalars = vec![("a1", const_(0x7fff_ffff, 64)), ("a2", const_(1, 64))];
+    let result = get_scalar(64, addw, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0000);
+    let result = get_scalar(64, addiw, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0000);
+
+    let scalars = vec![("a1", const_(1, 64)), ("a2", const_(0x3f, 64))];
+    let result = get_scalar(64, sllw, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8000_0000);
+
+    let scalars = vec![("a1", const_(0x1_8000_0000, 64))];
+    let result = get_scalar(64, sraiw, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_c000_0000);
+    let result = get_scalar(64, srliw, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0x4000_0000);
+
+    let scalars = vec![
+        ("a1", const_(0xffff_ffff_ffff_fffe, 64)),
+        ("a2", const_(0x1_0000_0001, 64)),
+    ];
+    let result = get_scalar(64, divuw, scalars, memory, "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_ffff_fffe);
+}
+
+#[test]
+fn loads() {
+    // lw a0, 4(a1)
+    let lw = &[0x03, 0xa5, 0x45, 0x00];
+    // lb a0, -1(a1)
+    let lb = &[0x03, 0x85, 0xf5, 0xff];
+    // lbu a0, -1(a1)
+    let lbu = &[0x03, 0xc5, 0xf5, 0xff];
+    // lhu a0, 2(a1)
+    let lhu = &[0x03, 0xd5, 0x25, 0x00];
+    // lwu a0, 0(a1)
+    let lwu = &[0x03, 0xe5, 0x05, 0x00];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0xfff, const_(0x80, 8)).unwrap();
+    memory.store(0x1000, const_(0xdead_beef, 32)).unwrap();
+    memory.store(0x1004, const_(0x8765_4321, 32)).unwrap();
+
+    let scalars = vec![("a1", const_(0x1000, 32))];
+    let result = get_scalar(32, lw, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0x8765_4321);
+    let result = get_scalar(32, lb, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ff80);
+    let result = get_scalar(32, lbu, scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0x80);
+    let result = get_scalar(32, lhu, scalars, memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xdead);
+
+    let scalars = vec![("a1", const_(0x1000, 64))];
+    let result = get_scalar(64, &lw[..], scalars.clone(), memory.clone(), "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xffff_ffff_8765_4321);
+    let result = get_scalar(64, lwu, scalars, memory, "a0");
+    assert_eq!(result.value_u64().unwrap(), 0xdead_beef);
+}
+
+#[test]
+fn stores() {
+    // sw a2, -4(a1)
+    // sb a2, 1(a1)
+    let instruction_bytes = &[0x23, 0xae, 0xc5, 0xfe, 0xa3, 0x80, 0xc5, 0x00];
+
+    let state = get_state(
+        32,
+        instruction_bytes,
+        vec![("a1", const_(0x1004, 32)), ("a2", const_(0x1234_5678, 32))],
+        Memory::new(Endian::Little),
+    );
+    let result = state.memory().load(0x1000, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0x1234_5678);
+    let result = state.memory().load(0x1005, 8).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0x78);
+}
+
+#[test]
+fn load_store_doubleword() {
+    // sd a2, 16(a1)
+    // ld a0, 16(a1)
+    let instruction_bytes = &[0x23, 0xb8, 0xc5, 0x00, 0x03, 0xb5, 0x05, 0x01];
+
+    let result = get_scalar(
+        64,
+        instruction_bytes,
+        vec![
+            ("a1", const_(0x1000, 64)),
+            ("a2", const_(0x1122_3344_5566_7788, 64)),
+        ],
+        Memory::new(Endian::Little),
+        "a0",
+    );
+    assert_eq!(result.value_u64().unwrap(), 0x1122_3344_5566_7788);
+}
+
+#[test]
+fn compressed() {
+    // c.li a0, -3
+    // c.mv a1, a0
+    // c.addi16sp sp, -32
+    let instruction_bytes = &[0x75, 0x55, 0xaa, 0x85, 0x3d, 0x71];
+
+    let state = get_state(
+        32,
+        instruction_bytes,
+        vec![("sp", const_(0x1000, 32))],
+        Memory::new(Endian::Little),
+    );
+    assert_eq!(
+        state.get_scalar("a1").unwrap().value_u64().unwrap(),
+        0xffff_fffd
+    );
+    assert_eq!(state.get_scalar("sp").unwrap().value_u64().unwrap(), 0xfe0);
+
+    let result = Riscv32::new()
+        .translate_block(instruction_bytes, 0x100)
+        .unwrap();
+    let addresses = result
+        .instructions()
+        .iter()
+        .map(|instruction| instruction.0)
+        .collect::<Vec<u64>>();
+    assert_eq!(addresses, vec![0x100, 0x102, 0x104]);
+    assert_eq!(result.length(), 6);
+}
+
+#[test]
+fn compressed_rv64() {
+    // c.sdsp a0, 8(sp)
+    // c.addiw a0, -1
+    // c.ldsp a1, 8(sp)
+    let instruction_bytes = &[0x2a, 0xe4, 0x7d, 0x35, 0xa2, 0x65];
+
+    let state = get_state(
+        64,
+        instruction_bytes,
+        vec![
+            ("sp", const_(0x1000, 64)),
+            ("a0", const_(0x1_0000_0000, 64)),
+        ],
+        Memory::new(Endian::Little),
+    );
+    assert_eq!(
+        state.get_scalar("a0").unwrap().value_u64().unwrap(),
+        0xffff_ffff_ffff_ffff
+    );
+    assert_eq!(
+        state.get_scalar("a1").unwrap().value_u64().unwrap(),
+        0x1_0000_0000
+    );
+}
+
+#[test]
+fn load_reserved_store_conditional() {
+    // lr.w a0, (a1)
+    // addi a0, a0, 1
+    // sc.w a3, a0, (a1)
+    let instruction_bytes = &[
+        0x2f, 0xa5, 0x05, 0x10, 0x13, 0x05, 0x15, 0x00, 0xaf, 0xa6, 0xa5, 0x18,
+    ];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, const_(0xffff_ffff, 32)).unwrap();
+
+    let state = get_state(
+        64,
+        instruction_bytes,
+        vec![("a1", const_(0x1000, 64)), ("a3", const_(1, 64))],
+        memory,
+    );
+    assert_eq!(state.get_scalar("a0").unwrap().value_u64().unwrap(), 0);
+    assert_eq!(state.get_scalar("a3").unwrap().value_u64().unwrap(), 0);
+    let result = state.memory().load(0x1000, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0);
+}
+
+#[test]
+fn atomic_memory_operations() {
+    // amoadd.w a0, a2, (a1)
+    let amoadd_w = &[0x2f, 0xa5, 0xc5, 0x00];
+    // amoswap.d a0, a2, (a1)
+    let amoswap_d = &[0x2f, 0xb5, 0xc5, 0x08];
+    // amomax.w a0, a2, (a1)
+    let amomax_w = &[0x2f, 0xa5, 0xc5, 0xa0];
+    // amominu.d a0, a2, (a1)
+    let amominu_d = &[0x2f, 0xb5, 0xc5, 0xc0];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1000, const_(0x0000_0001_8000_0000, 64))
+        .unwrap();
+    let scalars = vec![("a1", const_(0x1000, 64)), ("a2", const_(2, 64))];
+
+    let state = get_state(64, amoadd_w, scalars.clone(), memory.clone());
+    assert_eq!(
+        state.get_scalar("a0").unwrap().value_u64().unwrap(),
+        0xffff_ffff_8000_0000
+    );
+    let result = state.memory().load(0x1000, 64).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 0x0000_0001_8000_0002);
+
+    let state = get_state(64, amoswap_d, scalars.clone(), memory.clone());
+    assert_eq!(
+        state.get_scalar("a0").unwrap().value_u64().unwrap(),
+        0x0000_0001_8000_0000
+    );
+    let result = state.memory().load(0x1000, 64).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 2);
+
+    let state = get_state(64, amomax_w, scalars.clone(), memory.clone());
+    let result = state.memory().load(0x1000, 32).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 2);
+
+    let state = get_state(64, amominu_d, scalars, memory);
+    let result = state.memory().load(0x1000, 64).unwrap().unwrap();
+    assert_eq!(result.value_u64().unwrap(), 2);
+}
+
+#[test]
+fn ecall() {
+    // ecall
+    let intrinsic = get_intrinsic(64, &[0x73, 0x00, 0x00, 0x00]);
+    assert_eq!(intrinsic.mnemonic(), "ecall");
+
+    // c.ebreak
+    let intrinsic = get_intrinsic(32, &[0x02, 0x90]);
+    assert_eq!(intrinsic.mnemonic(), "ebreak");
+}
+
+#[test]
+fn csr() {
+    // csrrw a0, mstatus, a1
+    let intrinsic = get_intrinsic(32, &[0x73, 0x95, 0x05, 0x30]);
+    assert_eq!(intrinsic.mnemonic(), "csrrw");
+    assert_eq!(intrinsic.instruction_str(), "csrrw a0, 0x300, a1");
+    assert_eq!(intrinsic.arguments()[0], expr_const(0x300, 12),);
+}
+
+#[test]
+fn conditional_branch() {
+    // 0x00: beq a0, a1, 12
+    // 0x04: addi a2, zero, 1
+    // 0x08: jalr zero, 0(ra)
+    // 0x0c: addi a2, zero, 2
+    // 0x10: jalr zero, 0(ra)
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        vec![
+            0x63, 0x06, 0xb5, 0x00, 0x13, 0x06, 0x10, 0x00, 0x67, 0x80, 0x00, 0x00, 0x13, 0x06,
+            0x20, 0x00, 0x67, 0x80, 0x00, 0x00,
+        ],
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let driver = run(init_driver_function(
+        32,
+        backing.clone(),
+        vec![
+            ("a0", const_(5, 32)),
+            ("a1", const_(5, 32)),
+            ("ra", const_(0x100, 32)),
+        ],
+    ));
+    let result = driver.state().get_scalar("a2").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 2);
+
+    let driver = run(init_driver_function(
+        32,
+        backing,
+        vec![
+            ("a0", const_(4, 32)),
+            ("a1", const_(5, 32)),
+            ("ra", const_(0x100, 32)),
+        ],
+    ));
+    let result = driver.state().get_scalar("a2").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 1);
+}
+
+#[test]
+fn block_successors() {
+    // bltu a0, a1, -8
+    let result = Riscv64::new()
+        .translate_block(&[0xe3, 0x6c, 0xb5, 0xfe], 0x100)
+        .unwrap();
+    let mut successors = result
+        .successors()
+        .iter()
+        .map(|successor| successor.0)
+        .collect::<Vec<u64>>();
+    successors.sort();
+    assert_eq!(successors, vec![0xf8, 0x104]);
+
+    // c.j 6
+    let result = Riscv32::new()
+        .translate_block(&[0x19, 0xa0], 0x100)
+        .unwrap();
+    assert_eq!(result.successors().len(), 1);
+    assert_eq!(result.successors()[0].0, 0x106);
+
+    // ret
+    let result = Riscv32::new()
+        .translate_block(&[0x82, 0x80, 0x01, 0x00], 0x100)
+        .unwrap();
+    assert_eq!(result.length(), 2);
+    assert!(result.successors().is_empty());
+}
+
+#[test]
+fn jal() {
+    // 0x00: jal ra, 12
+    // 0x04: nop
+    // 0x08: nop
+    // 0x0c: addi a0, zero, 7
+    // 0x10: jalr zero, 0(ra)
+    let mut backing = memory::backing::Memory::new(Endian::Little);
+    backing.set_memory(
+        0,
+        vec![
+            0xef, 0x00, 0xc0, 0x00, 0x13, 0x00, 0x00, 0x00, 0x13, 0x00, 0x00, 0x00, 0x13, 0x05,
+            0x70, 0x00, 0x67, 0x80, 0x00, 0x00,
+        ],
+        memory::MemoryPermissions::EXECUTE | memory::MemoryPermissions::READ,
+    );
+
+    let driver = init_driver_function(64, backing, vec![]);
+    let driver = step_to(driver, 4);
+    let result = driver.state().get_scalar("a0").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 7);
+    let result = driver.state().get_scalar("ra").unwrap();
+    assert_eq!(result.value_u64().unwrap(), 4);
+}
+
+#[test]
+fn truncated_instruction() {
+    // the first half of addi a0, a0, 1
+    let result = Riscv32::new().translate_block(&[0x13, 0x05], 0);
+    assert!(result.is_err());
+
+    // c.addi a0, 1, followed by the first half of addi a0, a0, 1
+    let result = Riscv32::new()
+        .translate_block(&[0x05, 0x05, 0x13, 0x05], 0)
+        .unwrap();
+    assert_eq!(result.length(), 2);
+    assert_eq!(result.successors()[0].0, 2);
+}
+
+#[test]
+fn unhandled_instruction() {
+    // c.flw fa0, 0(a1)
+    assert!(Riscv32::new().translate_block(&[0x88, 0x61], 0).is_err());
+}
@@ -6,6 +6,7 @@ use crate::translator::x86::Mode;
 use falcon_capstone::capstone;
 use falcon_capstone::capstone::cs_x86_op;
 use falcon_capstone::capstone_sys::{x86_op_type, x86_reg};
+use std::cmp::Ordering;
 
 pub(crate) struct Semantics<'s> {
     mode: &'s Mode,
@@ -48,12 +49,23 @@ impl<'s> Semantics<'s> {
             .operand_load(&mut block, operand, self.instruction())
     }
 
+    /// Stores a value in an operand.
+    ///
+    /// VEX-encoded instructions which write an xmm register zero the upper
+    /// bits of its ymm register.
     pub fn operand_store(
         &self,
         mut block: &mut Block,
         operand: &cs_x86_op,
         value: Expression,
     ) -> Result<()> {
+        if self.is_vex() && operand.type_ == x86_op_type::X86_OP_REG {
+            let register = self.get_register(operand.reg())?;
+            if register.bits() == 128 && !register.is_full() {
+                let full_reg = register.get_full()?;
+                return full_reg.set(block, Expr::zext(full_reg.bits(), value)?);
+            }
+        }
         self.mode
             .operand_store(&mut block, operand, value, self.instruction())
     }
@@ -106,380 +118,393 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    /// Returns a condition which is true if a conditional instruction should be
-    /// executed. Used for setcc, jcc and cmovcc.
-    pub fn cc_condition(&self) -> Result<Expression> {
-        if let capstone::InstrIdArch::X86(instruction_id) = self.instruction().id {
-            match instruction_id {
-                capstone::x86_insn::X86_INS_CMOVA
-                | capstone::x86_insn::X86_INS_JA
-                | capstone::x86_insn::X86_INS_SETA => {
-                    let cf = Expr::cmpeq(expr_scalar("CF", 1), expr_const(0, 1))?;
-                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?;
-                    Expr::and(cf, zf)
-                }
-                capstone::x86_insn::X86_INS_CMOVAE
-                | capstone::x86_insn::X86_INS_JAE
-                | capstone::x86_insn::X86_INS_SETAE => {
-                    Expr::cmpeq(expr_scalar("CF", 1), expr_const(0, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVB
-                | capstone::x86_insn::X86_INS_JB
-                | capstone::x86_insn::X86_INS_SETB => {
-                    Expr::cmpeq(expr_scalar("CF", 1), expr_const(1, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVBE
-                | capstone::x86_insn::X86_INS_JBE
-                | capstone::x86_insn::X86_INS_SETBE => {
-                    let cf = Expr::cmpeq(expr_scalar("CF", 1), expr_const(1, 1))?;
-                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?;
-                    Expr::or(cf, zf)
-                }
-                capstone::x86_insn::X86_INS_JCXZ => {
-                    let cx = self.get_register(x86_reg::X86_REG_CX)?.get()?;
-                    Expr::cmpeq(cx, expr_const(0, 16))
-                }
-                capstone::x86_insn::X86_INS_JECXZ => {
-                    let cx = self.get_register(x86_reg::X86_REG_ECX)?.get()?;
-                    Expr::cmpeq(cx, expr_const(0, 32))
-                }
-                capstone::x86_insn::X86_INS_CMOVE
-                | capstone::x86_insn::X86_INS_JE
-                | capstone::x86_insn::X86_INS_SETE => {
-                    Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVG
-                | capstone::x86_insn::X86_INS_JG
-                | capstone::x86_insn::X86_INS_SETG => {
-                    let sfof = Expr::cmpeq(expr_scalar("SF", 1), expr_scalar("OF", 1))?;
-                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?;
-                    Expr::and(sfof, zf)
-                }
-                capstone::x86_insn::X86_INS_CMOVGE
-                | capstone::x86_insn::X86_INS_JGE
-                | capstone::x86_insn::X86_INS_SETGE => {
-                    Expr::cmpeq(expr_scalar("SF", 1), expr_scalar("OF", 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVL
-                | capstone::x86_insn::X86_INS_JL
-                | capstone::x86_insn::X86_INS_SETL => {
-                    Expr::cmpneq(expr_scalar("SF", 1), expr_scalar("OF", 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVLE
-                | capstone::x86_insn::X86_INS_JLE
-                | capstone::x86_insn::X86_INS_SETLE => {
-                    let sfof = Expr::cmpneq(expr_scalar("SF", 1), expr_scalar("OF", 1))?;
-                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?;
-                    Expr::or(sfof, zf)
-                }
-                capstone::x86_insn::X86_INS_CMOVNE
-                | capstone::x86_insn::X86_INS_JNE
-                | capstone::x86_insn::X86_INS_SETNE => {
-                    Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVNO
-                | capstone::x86_insn::X86_INS_JNO
-                | capstone::x86_insn::X86_INS_SETNO => {
-                    Expr::cmpeq(expr_scalar("OF", 1), expr_const(0, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVNP
-                | capstone::x86_insn::X86_INS_JNP
-                | capstone::x86_insn::X86_INS_SETNP => {
-                    Expr::cmpeq(expr_scalar("PF", 1), expr_const(0, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVNS
-                | capstone::x86_insn::X86_INS_JNS
-                | capstone::x86_insn::X86_INS_SETNS => {
-                    Expr::cmpeq(expr_scalar("SF", 1), expr_const(0, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVO
-                | capstone::x86_insn::X86_INS_JO
-                | capstone::x86_insn::X86_INS_SETO => {
-                    Expr::cmpeq(expr_scalar("OF", 1), expr_const(1, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVP
-                | capstone::x86_insn::X86_INS_JP
-                | capstone::x86_insn::X86_INS_SETP => {
-                    Expr::cmpeq(expr_scalar("PF", 1), expr_const(1, 1))
-                }
-                capstone::x86_insn::X86_INS_CMOVS
-                | capstone::x86_insn::X86_INS_JS
-                | capstone::x86_insn::X86_INS_SETS => {
-                    Expr::cmpeq(expr_scalar("SF", 1), expr_const(1, 1))
-                }
-                _ => bail!("unhandled jcc"),
-            }
-        } else {
-            bail!("not an x86 instruction")
+    /// Returns true if this is a VEX-encoded instruction, such as `vpxor`.
+    fn is_vex(&self) -> bool {
+        self.instruction.mnemonic.starts_with('v')
+    }
+
+    /// Returns the capstone id of this instruction.
+    fn instruction_id(&self) -> Result<capstone::x86_insn> {
+        match self.instruction().id {
+            capstone::InstrIdArch::X86(instruction_id) => Ok(instruction_id),
+            _ => bail!("not an x86 instruction"),
         }
     }
 
-    /// Returns a condition which is true if a loop should be taken
-    pub fn loop_condition(&self) -> Result<Expression> {
-        let cx = self.get_register(x86_reg::X86_REG_ECX)?.get_full()?;
+    /// Returns the x87 register st(index).
+    ///
+    /// The x87 register stack is modelled as eight double-precision scalars,
+    /// st0 through st7, which are shifted on every push and pop.
+    fn st(&self, index: usize) -> Scalar {
+        scalar(format!("st{}", index), 64)
+    }
 
-        if let capstone::InstrIdArch::X86(instruction_id) = self.instruction().id {
-            match instruction_id {
-                capstone::x86_insn::X86_INS_LOOP => {
-                    Expr::cmpneq(cx.get()?, expr_const(0, cx.bits()))
-                }
-                capstone::x86_insn::X86_INS_LOOPE => {
-                    let expr = Expr::cmpneq(cx.get()?, expr_const(0, cx.bits()))?;
-                    Expr::and(expr, Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?)
-                }
-                capstone::x86_insn::X86_INS_LOOPNE => {
-                    let expr = Expr::cmpneq(cx.get()?, expr_const(0, cx.bits()))?;
-                    Expr::and(expr, Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?)
-                }
-                _ => bail!("unhandled loop"),
-            }
-        } else {
-            bail!("not an x86 instruction")
+    /// Loads an x87 operand, converting single and extended precision memory
+    /// operands to double precision.
+    fn fpu_operand_load(&self, block: &mut Block, operand: &cs_x86_op) -> Result<Expression> {
+        let value = self.operand_load(block, operand)?;
+        match value.bits() {
+            64 => Ok(value),
+            _ => Expr::fconv(64, value),
         }
     }
 
-    /// Wraps the given instruction graph with the rep prefix inplace
-    pub fn rep_prefix(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        if control_flow_graph.entry().is_none() || control_flow_graph.exit().is_none() {
-            bail!("control_flow_graph entry/exit was none");
+    /// Stores a double-precision value to an x87 operand, converting it to the
+    /// precision of a memory operand.
+    fn fpu_operand_store(
+        &self,
+        block: &mut Block,
+        operand: &cs_x86_op,
+        value: Expression,
+    ) -> Result<()> {
+        let value = match operand.type_ {
+            x86_op_type::X86_OP_MEM if operand.size != 8 => {
+                Expr::fconv(operand.size as usize * 8, value)?
+            }
+            _ => value,
+        };
+        self.operand_store(block, operand, value)
+    }
+
+    /// Pushes a value onto the x87 register stack.
+    fn fpu_push(&self, block: &mut Block, value: Expression) {
+        // The value may read the stack, so hold it before shifting.
+        let temp = self.temp(0, 64);
+        block.assign(temp.clone(), value);
+        for i in (1..8).rev() {
+            block.assign(self.st(i), self.st(i - 1).into());
         }
+        block.assign(self.st(0), temp.into());
+    }
 
-        let cx = self.get_register(x86_reg::X86_REG_ECX)?.get_full()?;
+    /// Pops the x87 register stack.
+    fn fpu_pop(&self, block: &mut Block) {
+        for i in 0..7 {
+            block.assign(self.st(i), self.st(i + 1).into());
+        }
+    }
 
-        let head_index = control_flow_graph.new_block()?.index();
+    /// Compares two floats, returning expressions for less-than, unordered
+    /// and equal, where less-than and equal are also set when unordered. These
+    /// are the x87 condition codes c0, c2 and c3, and the CF, PF and ZF set by
+    /// fcomi and comisd.
+    fn float_compare(
+        &self,
+        lhs: Expression,
+        rhs: Expression,
+    ) -> Result<(Expression, Expression, Expression)> {
+        let unordered = Expr::fcmpuno(lhs.clone(), rhs.clone())?;
+        let below = Expr::or(Expr::fcmplt(lhs.clone(), rhs.clone())?, unordered.clone())?;
+        let equal = Expr::or(Expr::fcmpeq(lhs, rhs)?, unordered.clone())?;
+        Ok((below, unordered, equal))
+    }
 
-        let loop_index = {
-            let mut loop_block = control_flow_graph.new_block()?;
-            cx.set(
-                &mut loop_block,
-                Expr::sub(cx.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
-            loop_block.index()
-        };
+    /// Shared semantics for the x87 arithmetic instructions.
+    ///
+    /// The result is written to st(0), or to st(i) for the register forms
+    /// which name it as the destination. When reverse is set the operands of
+    /// op are swapped, as in fsubr and fdivr.
+    fn fpu_arithmetic<F>(
+        &self,
+        control_flow_graph: &mut ControlFlowGraph,
+        op: F,
+        reverse: bool,
+    ) -> Result<()>
+    where
+        F: Fn(Expression, Expression) -> Result<Expression>,
+    {
+        let detail = self.details()?;
 
-        let terminating_index = control_flow_graph.new_block()?.index();
+        let instruction_id = self.instruction_id()?;
+        let pop = matches!(
+            instruction_id,
+            capstone::x86_insn::X86_INS_FADDP
+                | capstone::x86_insn::X86_INS_FSUBP
+                | capstone::x86_insn::X86_INS_FSUBRP
+                | capstone::x86_insn::X86_INS_FMULP
+                | capstone::x86_insn::X86_INS_FDIVP
+                | capstone::x86_insn::X86_INS_FDIVRP
+        );
+        let integer = matches!(
+            instruction_id,
+            capstone::x86_insn::X86_INS_FIADD
+                | capstone::x86_insn::X86_INS_FISUB
+                | capstone::x86_insn::X86_INS_FISUBR
+                | capstone::x86_insn::X86_INS_FIMUL
+                | capstone::x86_insn::X86_INS_FIDIV
+                | capstone::x86_insn::X86_INS_FIDIVR
+        );
 
-        let entry = control_flow_graph.entry().clone().unwrap();
-        let exit = control_flow_graph.exit().clone().unwrap();
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-        // head -> entry
-        // head -> terminating
-        control_flow_graph.conditional_edge(
-            head_index,
-            entry,
-            Expr::cmpneq(cx.get()?, expr_const(0, self.mode().bits()))?,
-        )?;
-        control_flow_graph.conditional_edge(
-            head_index,
-            terminating_index,
-            Expr::cmpeq(cx.get()?, expr_const(0, self.mode().bits()))?,
-        )?;
+            let (dst, src) = if detail.op_count == 0 {
+                (self.get_register(x86_reg::X86_REG_ST1)?, self.st(0).into())
+            } else if detail.op_count == 2 {
+                let src = self.fpu_operand_load(block, &detail.operands[1])?;
+                (self.get_register(detail.operands[0].reg())?, src)
+            } else if pop {
+                (
+                    self.get_register(detail.operands[0].reg())?,
+                    self.st(0).into(),
+                )
+            } else if integer {
+                let src = self.operand_load(block, &detail.operands[0])?;
+                (
+                    self.get_register(x86_reg::X86_REG_ST0)?,
+                    Expr::itof(64, src)?,
+                )
+            } else {
+                let src = self.fpu_operand_load(block, &detail.operands[0])?;
+                (self.get_register(x86_reg::X86_REG_ST0)?, src)
+            };
 
-        // exit -> loop
-        control_flow_graph.unconditional_edge(exit, loop_index)?;
+            let result = if reverse {
+                op(src, dst.get()?)?
+            } else {
+                op(dst.get()?, src)?
+            };
+            dst.set(block, result)?;
 
-        if let capstone::InstrIdArch::X86(instruction_id) = self.instruction().id {
-            match instruction_id {
-                capstone::x86_insn::X86_INS_CMPSB
-                | capstone::x86_insn::X86_INS_CMPSW
-                | capstone::x86_insn::X86_INS_CMPSD
-                | capstone::x86_insn::X86_INS_CMPSQ
-                | capstone::x86_insn::X86_INS_SCASB
-                | capstone::x86_insn::X86_INS_SCASW
-                | capstone::x86_insn::X86_INS_SCASD
-                | capstone::x86_insn::X86_INS_SCASQ => {
-                    // loop -> head
-                    control_flow_graph.conditional_edge(
-                        loop_index,
-                        head_index,
-                        Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?,
-                    )?;
-                    // loop -> terminating
-                    control_flow_graph.conditional_edge(
-                        loop_index,
-                        terminating_index,
-                        Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?,
-                    )?;
-                }
-                capstone::x86_insn::X86_INS_STOSB
-                | capstone::x86_insn::X86_INS_STOSW
-                | capstone::x86_insn::X86_INS_STOSD
-                | capstone::x86_insn::X86_INS_STOSQ
-                | capstone::x86_insn::X86_INS_MOVSB
-                | capstone::x86_insn::X86_INS_MOVSW
-                | capstone::x86_insn::X86_INS_MOVSD
-                | capstone::x86_insn::X86_INS_MOVSQ => {
-                    // loop -> head
-                    control_flow_graph.unconditional_edge(loop_index, head_index)?;
-                }
-                _ => bail!(
-                    "unsupported instruction for rep prefix, 0x{:x}",
-                    self.instruction().address
-                ),
+            if pop {
+                self.fpu_pop(block);
             }
-        }
 
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(terminating_index)?;
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
         Ok(())
     }
 
-    /// Wraps the given instruction graph with the rep prefix inplace
-    pub fn repne_prefix(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        if control_flow_graph.entry().is_none() || control_flow_graph.exit().is_none() {
-            bail!("control_flow_graph entry/exit was none");
+    /// Returns the width of the floats operated on by a scalar SSE instruction.
+    fn sse_float_bits(&self) -> usize {
+        if self.instruction().mnemonic.ends_with("ss") {
+            32
+        } else {
+            64
         }
+    }
 
-        let cx = self.get_register(x86_reg::X86_REG_ECX)?.get_full()?;
+    /// Loads the low bits of a scalar SSE operand.
+    fn sse_scalar_load(
+        &self,
+        block: &mut Block,
+        operand: &cs_x86_op,
+        bits: usize,
+    ) -> Result<Expression> {
+        let value = self.operand_load(block, operand)?;
+        if value.bits() > bits {
+            Expr::trun(bits, value)
+        } else {
+            Ok(value)
+        }
+    }
 
-        let head_index = control_flow_graph.new_block()?.index();
+    /// Stores a value to a scalar SSE operand, preserving the upper bits of an
+    /// xmm register.
+    fn sse_scalar_store(
+        &self,
+        block: &mut Block,
+        operand: &cs_x86_op,
+        value: Expression,
+    ) -> Result<()> {
+        if operand.type_ == x86_op_type::X86_OP_REG {
+            let register = self.get_register(operand.reg())?;
+            if register.bits() > value.bits() {
+                let shift = expr_const(value.bits() as u64, register.bits());
+                let upper = Expr::shl(Expr::shr(register.get()?, shift.clone())?, shift)?;
+                let value = Expr::or(upper, Expr::zext(register.bits(), value)?)?;
+                return register.set(block, value);
+            }
+        }
+        self.operand_store(block, operand, value)
+    }
 
-        let loop_index = {
-            let mut loop_block = control_flow_graph.new_block()?;
-            cx.set(
-                &mut loop_block,
-                Expr::sub(cx.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
-            loop_block.index()
+    /// Shared semantics for the scalar SSE arithmetic instructions.
+    fn sse_scalar_arithmetic<F>(
+        &self,
+        control_flow_graph: &mut ControlFlowGraph,
+        op: F,
+    ) -> Result<()>
+    where
+        F: Fn(Expression, Expression) -> Result<Expression>,
+    {
+        let detail = self.details()?;
+        let bits = self.sse_float_bits();
+
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            let lhs = self.sse_scalar_load(block, &detail.operands[0], bits)?;
+            let rhs = self.sse_scalar_load(block, &detail.operands[1], bits)?;
+            self.sse_scalar_store(block, &detail.operands[0], op(lhs, rhs)?)?;
+
+            block.index()
         };
 
-        let terminating_index = control_flow_graph.new_block()?.index();
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
-        let entry = control_flow_graph.entry().clone().unwrap();
-        let exit = control_flow_graph.exit().clone().unwrap();
+        Ok(())
+    }
 
-        // head -> entry
-        // head -> terminating
-        control_flow_graph.conditional_edge(
-            head_index,
-            entry,
-            Expr::cmpneq(cx.get()?, expr_const(0, self.mode().bits()))?,
-        )?;
-        control_flow_graph.conditional_edge(
-            head_index,
-            terminating_index,
-            Expr::cmpeq(cx.get()?, expr_const(0, self.mode().bits()))?,
-        )?;
+    /// Shared semantics for movss and movsd.
+    fn sse_scalar_move(
+        &self,
+        control_flow_graph: &mut ControlFlowGraph,
+        bits: usize,
+    ) -> Result<()> {
+        let detail = self.details()?;
 
-        // exit -> loop
-        control_flow_graph.unconditional_edge(exit, loop_index)?;
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-        if let capstone::InstrIdArch::X86(instruction_id) = self.instruction().id {
-            match instruction_id {
-                capstone::x86_insn::X86_INS_CMPSB
-                | capstone::x86_insn::X86_INS_CMPSW
-                | capstone::x86_insn::X86_INS_CMPSD
-                | capstone::x86_insn::X86_INS_CMPSQ
-                | capstone::x86_insn::X86_INS_SCASB
-                | capstone::x86_insn::X86_INS_SCASW
-                | capstone::x86_insn::X86_INS_SCASD
-                | capstone::x86_insn::X86_INS_SCASQ => {
-                    // loop -> head
-                    control_flow_graph.conditional_edge(
-                        loop_index,
-                        head_index,
-                        Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?,
-                    )?;
-                    // loop -> terminating
-                    control_flow_graph.conditional_edge(
-                        loop_index,
-                        terminating_index,
-                        Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?,
-                    )?;
-                }
-                capstone::x86_insn::X86_INS_STOSB
-                | capstone::x86_insn::X86_INS_STOSW
-                | capstone::x86_insn::X86_INS_STOSD
-                | capstone::x86_insn::X86_INS_STOSQ
-                | capstone::x86_insn::X86_INS_MOVSB
-                | capstone::x86_insn::X86_INS_MOVSW
-                | capstone::x86_insn::X86_INS_MOVSD
-                | capstone::x86_insn::X86_INS_MOVSQ => {
-                    // loop -> head
-                    control_flow_graph.unconditional_edge(loop_index, head_index)?;
-                }
-                _ => bail!(
-                    "unsupported instruction for rep prefix, \
-                     instruction: {} {}, address: 0x{:x}",
-                    self.instruction().mnemonic,
-                    self.instruction().op_str,
-                    self.instruction().address
-                ),
+            let src = self.sse_scalar_load(block, &detail.operands[1], bits)?;
+
+            // A load from memory zeroes the upper bits of the destination.
+            if detail.operands[1].type_ == x86_op_type::X86_OP_MEM {
+                let register = self.get_register(detail.operands[0].reg())?;
+                register.set(block, Expr::zext(register.bits(), src)?)?;
+            } else {
+                self.sse_scalar_store(block, &detail.operands[0], src)?;
             }
-        }
 
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(terminating_index)?;
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
         Ok(())
     }
 
-    pub fn adc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Returns the value of the trailing immediate operand of this
+    /// instruction, such as the shuffle control of `pshufd`.
+    fn immediate(&self) -> Result<u64> {
         let detail = self.details()?;
+        match detail.operands[0..detail.op_count as usize].last() {
+            Some(operand) if operand.type_ == x86_op_type::X86_OP_IMM => Ok(operand.imm() as u64),
+            _ => bail!("Expected an immediate operand"),
+        }
+    }
 
-        // create a block for this instruction
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+    /// Returns the width of the lanes of a packed integer instruction, given
+    /// by the last letter of its mnemonic, as in `paddw`.
+    fn packed_lane_bits(&self) -> Result<usize> {
+        match self.instruction.mnemonic.chars().last() {
+            Some('b') => Ok(8),
+            Some('w') => Ok(16),
+            Some('d') => Ok(32),
+            Some('q') => Ok(64),
+            _ => bail!("Unknown lane width for {}", self.instruction.mnemonic),
+        }
+    }
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+    /// Returns true if a packed integer instruction treats its lanes as
+    /// signed, given by the second to last letter of its mnemonic, as in
+    /// `pminsw`.
+    fn packed_signed(&self) -> bool {
+        self.instruction.mnemonic.chars().rev().nth(1) == Some('s')
+    }
 
-            let result = self.temp(0, lhs.bits());
+    /// Returns lane `index` of `value`, `lane_bits` wide.
+    fn lane(value: &Expression, lane_bits: usize, index: usize) -> Result<Expression> {
+        let value = match index {
+            0 => value.clone(),
+            _ => Expr::shr(
+                value.clone(),
+                expr_const((index * lane_bits) as u64, value.bits()),
+            )?,
+        };
+        if value.bits() == lane_bits {
+            Ok(value)
+        } else {
+            Expr::trun(lane_bits, value)
+        }
+    }
 
-            // perform addition
-            let addition = Expr::add(lhs.clone(), rhs.clone())?;
-            let zext_cf = Expr::zext(lhs.bits(), expr_scalar("CF", 1))?;
-            block.assign(result.clone(), Expr::add(addition, zext_cf)?);
+    /// Assigns the concatenation of `lanes`, least significant first, to a
+    /// temporary, and returns the temporary.
+    fn concat_lanes(&self, block: &mut Block, lanes: Vec<Expression>) -> Result<Expression> {
+        let bits = lanes.iter().map(|lane| lane.bits()).sum();
+        let temp = self.temp(0, bits);
 
-            // calculate flags
-            self.set_zf(&mut block, result.clone().into())?;
-            self.set_sf(&mut block, result.clone().into())?;
-            self.set_of(&mut block, result.clone().into(), lhs.clone(), rhs)?;
+        block.assign(temp.clone(), expr_const(0, bits));
+
+        let mut offset = 0;
+        for lane in lanes {
+            let lane_bits = lane.bits();
+            let lane = if lane_bits == bits {
+                lane
+            } else {
+                Expr::zext(bits, lane)?
+            };
             block.assign(
-                scalar("CF", 1),
-                Expression::cmpltu(result.clone().into(), lhs)?,
+                temp.clone(),
+                Expr::or(
+                    temp.clone().into(),
+                    Expr::shl(lane, expr_const(offset as u64, bits))?,
+                )?,
             );
+            offset += lane_bits;
+        }
 
-            // store result
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+        Ok(temp.into())
+    }
 
-            block.index()
+    /// Saturates the signed value `value` to a `bits` wide signed, or
+    /// unsigned, integer.
+    fn saturate(value: Expression, bits: usize, signed: bool) -> Result<Expression> {
+        let wide = value.bits();
+        let (min, max) = if signed {
+            (
+                expr_const((1u64 << (bits - 1)).wrapping_neg(), wide),
+                expr_const((1 << (bits - 1)) - 1, wide),
+            )
+        } else {
+            (expr_const(0, wide), expr_const((1 << bits) - 1, wide))
         };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
+        Expr::trun(
+            bits,
+            Expr::ite(
+                Expr::cmplts(value.clone(), min.clone())?,
+                min,
+                Expr::ite(Expr::cmplts(max.clone(), value.clone())?, max, value)?,
+            )?,
+        )
     }
 
-    pub fn add(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Lifts a vector instruction whose result is computed by `op` from its
+    /// two source operands. In the two operand forms the destination is also
+    /// the first source. A trailing immediate operand is left to `op`.
+    fn vector_binary<F>(&self, control_flow_graph: &mut ControlFlowGraph, op: F) -> Result<()>
+    where
+        F: Fn(&mut Block, Expression, Expression) -> Result<Expression>,
+    {
         let detail = self.details()?;
 
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+        let mut operands = &detail.operands[0..detail.op_count as usize];
+        if let Some(operand) = operands.last() {
+            if operand.type_ == x86_op_type::X86_OP_IMM {
+                operands = &operands[0..operands.len() - 1];
+            }
+        }
+        if operands.len() < 2 {
+            bail!("Expected two source operands");
+        }
 
-            let result = self.temp(0, lhs.bits());
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-            // perform addition
-            block.assign(result.clone(), Expr::add(lhs.clone(), rhs.clone())?);
+            let lhs = self.operand_load(block, &operands[operands.len() - 2])?;
+            let rhs = self.operand_load(block, &operands[operands.len() - 1])?;
 
-            // calculate flags
-            self.set_zf(&mut block, result.clone().into())?;
-            self.set_sf(&mut block, result.clone().into())?;
-            self.set_of(&mut block, result.clone().into(), lhs.clone(), rhs)?;
-            block.assign(
-                scalar("CF", 1),
-                Expression::cmpltu(result.clone().into(), lhs)?,
-            );
+            let result = op(block, lhs, rhs)?;
 
-            // store result
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+            self.operand_store(block, &operands[0], result)?;
 
             block.index()
         };
@@ -490,33 +515,80 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn and(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Lifts a packed instruction, which sets each `lane_bits` wide lane of
+    /// the destination to `op` applied to the corresponding lanes of the
+    /// sources.
+    fn packed_binary<F>(
+        &self,
+        control_flow_graph: &mut ControlFlowGraph,
+        lane_bits: usize,
+        op: F,
+    ) -> Result<()>
+    where
+        F: Fn(Expression, Expression) -> Result<Expression>,
+    {
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let lanes = (0..lhs.bits() / lane_bits)
+                .map(|i| {
+                    op(
+                        Self::lane(&lhs, lane_bits, i)?,
+                        Self::lane(&rhs, lane_bits, i)?,
+                    )
+                })
+                .collect::<Result<Vec<Expression>>>()?;
+            self.concat_lanes(block, lanes)
+        })
+    }
+
+    /// Lifts a packed shift. `op` is given each lane, the shift count
+    /// truncated to the width of the lane, and whether the count is less than
+    /// the width of the lane.
+    ///
+    /// The count is the trailing immediate operand, or the low quadword of
+    /// the last operand.
+    fn packed_shift<F>(&self, control_flow_graph: &mut ControlFlowGraph, op: F) -> Result<()>
+    where
+        F: Fn(Expression, Expression, Expression) -> Result<Expression>,
+    {
         let detail = self.details()?;
+        let operands = &detail.operands[0..detail.op_count as usize];
+        if operands.len() < 2 {
+            bail!("Expected a source and a count operand");
+        }
 
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+        let lane_bits = self.packed_lane_bits()?;
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-            if rhs.bits() != lhs.bits() {
-                rhs = Expr::sext(lhs.bits(), rhs)?;
-            }
+            let src = self.operand_load(block, &operands[operands.len() - 2])?;
+            let count = self.operand_load(block, &operands[operands.len() - 1])?;
 
-            let result = self.temp(0, lhs.bits());
+            let count = match count.bits() {
+                64 => count,
+                bits if bits > 64 => Expr::trun(64, count)?,
+                _ => Expr::zext(64, count)?,
+            };
+            let in_range = Expr::cmpltu(count.clone(), expr_const(lane_bits as u64, 64))?;
+            let count = if lane_bits < 64 {
+                Expr::trun(lane_bits, count)?
+            } else {
+                count
+            };
 
-            // perform addition
-            block.assign(result.clone(), Expr::and(lhs, rhs)?);
+            let lanes = (0..src.bits() / lane_bits)
+                .map(|i| {
+                    op(
+                        Self::lane(&src, lane_bits, i)?,
+                        count.clone(),
+                        in_range.clone(),
+                    )
+                })
+                .collect::<Result<Vec<Expression>>>()?;
 
-            // calculate flags
-            self.set_zf(&mut block, result.clone().into())?;
-            self.set_sf(&mut block, result.clone().into())?;
-            block.assign(scalar("CF", 1), expr_const(0, 1));
-            block.assign(scalar("OF", 1), expr_const(0, 1));
+            let result = self.concat_lanes(block, lanes)?;
 
-            // store result
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+            self.operand_store(block, &operands[0], result)?;
 
             block.index()
         };
@@ -527,106 +599,164 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    /*
-        BSF scans the bits in the second word or doubleword operand starting with
-        bit 0. The ZF flag is cleared if the bits are all 0; otherwise, the ZF flag
-        is set and the destination register is loaded with the bit index of the
-        first set bit.
-    */
-    pub fn bsf(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        // create our head block
-        let (head_index, rhs, counter) = {
-            let (head_index, rhs) = {
-                let mut head_block = control_flow_graph.new_block()?;
-
-                // get started
-                let rhs = self.operand_load(&mut head_block, &detail.operands[1])?;
-
-                (head_block.index(), rhs)
-            };
-
-            let counter = { control_flow_graph.temp(rhs.bits()) };
-
-            let head_block = control_flow_graph.block_mut(head_index)?;
-
-            // This is the loop preamble, and we'll always execute it
-            head_block.assign(scalar("ZF", 1), expr_const(0, 1));
-            head_block.assign(counter.clone(), expr_const(0, rhs.bits()));
-
-            (head_index, rhs, counter)
+    /// Lifts the unpack instructions, which interleave the lanes of the low,
+    /// or `high`, halves of the sources.
+    fn unpack(&self, control_flow_graph: &mut ControlFlowGraph, high: bool) -> Result<()> {
+        let lane_bits = match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_PUNPCKLBW | capstone::x86_insn::X86_INS_PUNPCKHBW => 8,
+            capstone::x86_insn::X86_INS_PUNPCKLWD | capstone::x86_insn::X86_INS_PUNPCKHWD => 16,
+            capstone::x86_insn::X86_INS_PUNPCKLDQ
+            | capstone::x86_insn::X86_INS_PUNPCKHDQ
+            | capstone::x86_insn::X86_INS_UNPCKLPS
+            | capstone::x86_insn::X86_INS_UNPCKHPS => 32,
+            _ => 64,
         };
 
-        // if rhs == 0 then ZF = 1 and we are done.
-        let zero_index = {
-            let zero_block = control_flow_graph.new_block()?;
-            zero_block.assign(scalar("ZF", 1), expr_const(1, 1));
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let count = lhs.bits() / lane_bits / 2;
+            let first = if high { count } else { 0 };
 
-            zero_block.index()
-        };
+            let mut lanes = Vec::new();
+            for i in first..(first + count) {
+                lanes.push(Self::lane(&lhs, lane_bits, i)?);
+                lanes.push(Self::lane(&rhs, lane_bits, i)?);
+            }
 
-        // The loop body checks if the bits for our counter is set.
-        let (bitfield, loop_index) = {
-            let bitfield = control_flow_graph.temp(rhs.bits());
+            self.concat_lanes(block, lanes)
+        })
+    }
 
-            let loop_block = control_flow_graph.new_block()?;
-            loop_block.assign(
-                bitfield.clone(),
-                Expr::and(
-                    Expr::shr(rhs.clone(), counter.clone().into())?,
-                    expr_const(1, rhs.bits()),
-                )?,
-            );
+    /// Returns true if this is a string instruction, the only instructions
+    /// given meaning by the rep, repe and repne prefixes.
+    pub fn is_string_instruction(&self) -> Result<bool> {
+        Ok(match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_CMPSB
+            | capstone::x86_insn::X86_INS_CMPSW
+            | capstone::x86_insn::X86_INS_CMPSQ
+            | capstone::x86_insn::X86_INS_LODSB
+            | capstone::x86_insn::X86_INS_LODSW
+            | capstone::x86_insn::X86_INS_LODSD
+            | capstone::x86_insn::X86_INS_LODSQ
+            | capstone::x86_insn::X86_INS_MOVSB
+            | capstone::x86_insn::X86_INS_MOVSW
+            | capstone::x86_insn::X86_INS_MOVSQ
+            | capstone::x86_insn::X86_INS_SCASB
+            | capstone::x86_insn::X86_INS_SCASW
+            | capstone::x86_insn::X86_INS_SCASD
+            | capstone::x86_insn::X86_INS_SCASQ
+            | capstone::x86_insn::X86_INS_STOSB
+            | capstone::x86_insn::X86_INS_STOSW
+            | capstone::x86_insn::X86_INS_STOSD
+            | capstone::x86_insn::X86_INS_STOSQ => true,
+            // cmpsd and movsd are also SSE2 instructions, whose string forms
+            // take two memory operands
+            capstone::x86_insn::X86_INS_CMPSD | capstone::x86_insn::X86_INS_MOVSD => {
+                let detail = self.details()?;
+                detail.operands[0..detail.op_count as usize]
+                    .iter()
+                    .all(|operand| operand.type_ == x86_op_type::X86_OP_MEM)
+            }
+            _ => false,
+        })
+    }
 
-            (bitfield, loop_block.index())
-        };
+    /// Returns true if this is a string instruction which sets the flags, and
+    /// so is terminated by ZF under the repe and repne prefixes.
+    fn is_string_compare(&self) -> Result<bool> {
+        Ok(matches!(
+            self.instruction_id()?,
+            capstone::x86_insn::X86_INS_CMPSB
+                | capstone::x86_insn::X86_INS_CMPSW
+                | capstone::x86_insn::X86_INS_CMPSD
+                | capstone::x86_insn::X86_INS_CMPSQ
+                | capstone::x86_insn::X86_INS_SCASB
+                | capstone::x86_insn::X86_INS_SCASW
+                | capstone::x86_insn::X86_INS_SCASD
+                | capstone::x86_insn::X86_INS_SCASQ
+        ))
+    }
 
-        // While our bitfield == 0, we increment counter and keep looping
-        let iterate_index = {
-            let iterate_block = control_flow_graph.new_block()?;
+    /// Wraps the given instruction graph with the rep, or repe, prefix
+    /// inplace.
+    pub fn rep_prefix(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        if self.is_string_compare()? {
+            self.rep_loop(control_flow_graph, Some(true))
+        } else {
+            self.rep_loop(control_flow_graph, None)
+        }
+    }
 
-            iterate_block.assign(
-                counter.clone(),
-                Expr::add(counter.clone().into(), expr_const(1, counter.bits()))?,
-            );
+    /// Wraps the given instruction graph with the repne prefix inplace.
+    pub fn repne_prefix(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        if self.is_string_compare()? {
+            self.rep_loop(control_flow_graph, Some(false))
+        } else {
+            self.rep_loop(control_flow_graph, None)
+        }
+    }
 
-            iterate_block.index()
+    /// Wraps the given instruction graph in a loop, which executes it while
+    /// the counter register is not zero, decrementing the counter after each
+    /// iteration.
+    ///
+    /// If `zf` is given, the loop also terminates after any iteration which
+    /// leaves ZF different from `zf`.
+    fn rep_loop(&self, control_flow_graph: &mut ControlFlowGraph, zf: Option<bool>) -> Result<()> {
+        let (entry, exit) = match (control_flow_graph.entry(), control_flow_graph.exit()) {
+            (Some(entry), Some(exit)) => (entry, exit),
+            _ => bail!("control_flow_graph entry/exit was none"),
         };
 
-        // In our terminating block, we set the result to counter
-        let terminating_index = {
-            let mut terminating_block = control_flow_graph.new_block()?;
+        let cx = self.get_register(x86_reg::X86_REG_ECX)?.get_full()?;
+        let bits = self.mode().bits();
 
-            self.operand_store(&mut terminating_block, &detail.operands[0], counter.into())?;
+        let head_index = control_flow_graph.new_block()?.index();
 
-            terminating_block.index()
+        let loop_index = {
+            let block = control_flow_graph.new_block()?;
+            cx.set(block, Expr::sub(cx.get()?, expr_const(1, bits))?)?;
+            block.index()
         };
 
+        let terminating_index = control_flow_graph.new_block()?.index();
+
+        // head -> entry
+        // head -> terminating
         control_flow_graph.conditional_edge(
             head_index,
-            zero_index,
-            Expr::cmpeq(rhs.clone(), expr_const(0, rhs.bits()))?,
+            entry,
+            Expr::cmpneq(cx.get()?, expr_const(0, bits))?,
         )?;
         control_flow_graph.conditional_edge(
             head_index,
-            loop_index,
-            Expr::cmpneq(rhs.clone(), expr_const(0, rhs.bits()))?,
+            terminating_index,
+            Expr::cmpeq(cx.get()?, expr_const(0, bits))?,
         )?;
 
-        control_flow_graph.unconditional_edge(zero_index, terminating_index)?;
+        // exit -> loop
+        control_flow_graph.unconditional_edge(exit, loop_index)?;
 
-        control_flow_graph.conditional_edge(
-            loop_index,
-            iterate_index,
-            Expr::cmpeq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
-        )?;
-        control_flow_graph.conditional_edge(
-            loop_index,
-            terminating_index,
-            Expr::cmpneq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
-        )?;
-        control_flow_graph.unconditional_edge(iterate_index, loop_index)?;
+        match zf {
+            Some(zf) => {
+                let zf = expr_const(if zf { 1 } else { 0 }, 1);
+                // loop -> head
+                control_flow_graph.conditional_edge(
+                    loop_index,
+                    head_index,
+                    Expr::cmpeq(expr_scalar("ZF", 1), zf.clone())?,
+                )?;
+                // loop -> terminating
+                control_flow_graph.conditional_edge(
+                    loop_index,
+                    terminating_index,
+                    Expr::cmpneq(expr_scalar("ZF", 1), zf)?,
+                )?;
+            }
+            None => {
+                // loop -> head
+                control_flow_graph.unconditional_edge(loop_index, head_index)?;
+            }
+        }
 
         control_flow_graph.set_entry(head_index)?;
         control_flow_graph.set_exit(terminating_index)?;
@@ -634,148 +764,1176 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    /*
-        BSR scans the bits in the second word or doubleword operand from the most
-        significant bit to the least significant bit. The ZF flag is cleared if the
-        bits are all 0; otherwise, ZF is set and the destination register is loaded
-        with the bit index of the first set bit found when scanning in the reverse
-        direction.
-    */
-    pub fn bsr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
+    /// Completes the graph of a string instruction, whose operation is in the
+    /// block `head_index`, by advancing each of `registers` by `bytes`
+    /// forwards, or backwards if DF is set.
+    fn string_advance(
+        &self,
+        control_flow_graph: &mut ControlFlowGraph,
+        head_index: usize,
+        registers: &[&X86Register],
+        bytes: usize,
+    ) -> Result<()> {
+        let bits = self.mode().bits();
 
-        let (head_index, rhs, counter) = {
-            let (head_index, rhs) = {
-                let mut head_block = control_flow_graph.new_block()?;
+        let inc_index = {
+            let block = control_flow_graph.new_block()?;
+            for register in registers {
+                register.set(
+                    block,
+                    Expr::add(register.get()?, expr_const(bytes as u64, bits))?,
+                )?;
+            }
+            block.index()
+        };
 
-                // get started
+        let dec_index = {
+            let block = control_flow_graph.new_block()?;
+            for register in registers {
+                register.set(
+                    block,
+                    Expr::sub(register.get()?, expr_const(bytes as u64, bits))?,
+                )?;
+            }
+            block.index()
+        };
+
+        let tail_index = control_flow_graph.new_block()?.index();
+
+        control_flow_graph.conditional_edge(
+            head_index,
+            inc_index,
+            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
+        )?;
+        control_flow_graph.conditional_edge(
+            head_index,
+            dec_index,
+            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
+        )?;
+
+        control_flow_graph.unconditional_edge(inc_index, tail_index)?;
+        control_flow_graph.unconditional_edge(dec_index, tail_index)?;
+
+        control_flow_graph.set_entry(head_index)?;
+        control_flow_graph.set_exit(tail_index)?;
+
+        Ok(())
+    }
+
+    /// Sets the flags for the subtraction `lhs - rhs`, as for cmp.
+    fn compare_flags(&self, block: &mut Block, lhs: Expression, rhs: Expression) -> Result<()> {
+        let expr = Expr::sub(lhs.clone(), rhs.clone())?;
+
+        self.set_zf(block, expr.clone())?;
+        self.set_sf(block, expr.clone())?;
+        self.set_of(block, expr.clone(), lhs.clone(), rhs)?;
+        self.set_cf(block, expr, lhs)
+    }
+
+    /// Returns a condition which is true if a conditional instruction should be
+    /// executed. Used for setcc, jcc and cmovcc.
+    pub fn cc_condition(&self) -> Result<Expression> {
+        if let capstone::InstrIdArch::X86(instruction_id) = self.instruction().id {
+            match instruction_id {
+                capstone::x86_insn::X86_INS_CMOVA
+                | capstone::x86_insn::X86_INS_JA
+                | capstone::x86_insn::X86_INS_SETA => {
+                    let cf = Expr::cmpeq(expr_scalar("CF", 1), expr_const(0, 1))?;
+                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?;
+                    Expr::and(cf, zf)
+                }
+                capstone::x86_insn::X86_INS_CMOVAE
+                | capstone::x86_insn::X86_INS_JAE
+                | capstone::x86_insn::X86_INS_SETAE => {
+                    Expr::cmpeq(expr_scalar("CF", 1), expr_const(0, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVB
+                | capstone::x86_insn::X86_INS_JB
+                | capstone::x86_insn::X86_INS_SETB => {
+                    Expr::cmpeq(expr_scalar("CF", 1), expr_const(1, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVBE
+                | capstone::x86_insn::X86_INS_JBE
+                | capstone::x86_insn::X86_INS_SETBE => {
+                    let cf = Expr::cmpeq(expr_scalar("CF", 1), expr_const(1, 1))?;
+                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?;
+                    Expr::or(cf, zf)
+                }
+                capstone::x86_insn::X86_INS_JCXZ => {
+                    let cx = self.get_register(x86_reg::X86_REG_CX)?.get()?;
+                    Expr::cmpeq(cx, expr_const(0, 16))
+                }
+                capstone::x86_insn::X86_INS_JECXZ => {
+                    let cx = self.get_register(x86_reg::X86_REG_ECX)?.get()?;
+                    Expr::cmpeq(cx, expr_const(0, 32))
+                }
+                capstone::x86_insn::X86_INS_CMOVE
+                | capstone::x86_insn::X86_INS_JE
+                | capstone::x86_insn::X86_INS_SETE => {
+                    Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVG
+                | capstone::x86_insn::X86_INS_JG
+                | capstone::x86_insn::X86_INS_SETG => {
+                    let sfof = Expr::cmpeq(expr_scalar("SF", 1), expr_scalar("OF", 1))?;
+                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?;
+                    Expr::and(sfof, zf)
+                }
+                capstone::x86_insn::X86_INS_CMOVGE
+                | capstone::x86_insn::X86_INS_JGE
+                | capstone::x86_insn::X86_INS_SETGE => {
+                    Expr::cmpeq(expr_scalar("SF", 1), expr_scalar("OF", 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVL
+                | capstone::x86_insn::X86_INS_JL
+                | capstone::x86_insn::X86_INS_SETL => {
+                    Expr::cmpneq(expr_scalar("SF", 1), expr_scalar("OF", 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVLE
+                | capstone::x86_insn::X86_INS_JLE
+                | capstone::x86_insn::X86_INS_SETLE => {
+                    let sfof = Expr::cmpneq(expr_scalar("SF", 1), expr_scalar("OF", 1))?;
+                    let zf = Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?;
+                    Expr::or(sfof, zf)
+                }
+                capstone::x86_insn::X86_INS_CMOVNE
+                | capstone::x86_insn::X86_INS_JNE
+                | capstone::x86_insn::X86_INS_SETNE => {
+                    Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVNO
+                | capstone::x86_insn::X86_INS_JNO
+                | capstone::x86_insn::X86_INS_SETNO => {
+                    Expr::cmpeq(expr_scalar("OF", 1), expr_const(0, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVNP
+                | capstone::x86_insn::X86_INS_JNP
+                | capstone::x86_insn::X86_INS_SETNP => {
+                    Expr::cmpeq(expr_scalar("PF", 1), expr_const(0, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVNS
+                | capstone::x86_insn::X86_INS_JNS
+                | capstone::x86_insn::X86_INS_SETNS => {
+                    Expr::cmpeq(expr_scalar("SF", 1), expr_const(0, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVO
+                | capstone::x86_insn::X86_INS_JO
+                | capstone::x86_insn::X86_INS_SETO => {
+                    Expr::cmpeq(expr_scalar("OF", 1), expr_const(1, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVP
+                | capstone::x86_insn::X86_INS_JP
+                | capstone::x86_insn::X86_INS_SETP => {
+                    Expr::cmpeq(expr_scalar("PF", 1), expr_const(1, 1))
+                }
+                capstone::x86_insn::X86_INS_CMOVS
+                | capstone::x86_insn::X86_INS_JS
+                | capstone::x86_insn::X86_INS_SETS => {
+                    Expr::cmpeq(expr_scalar("SF", 1), expr_const(1, 1))
+                }
+                _ => bail!("unhandled jcc"),
+            }
+        } else {
+            bail!("not an x86 instruction")
+        }
+    }
+
+    /// Returns a condition which is true if a loop should be taken
+    pub fn loop_condition(&self) -> Result<Expression> {
+        let cx = self.get_register(x86_reg::X86_REG_ECX)?.get_full()?;
+
+        if let capstone::InstrIdArch::X86(instruction_id) = self.instruction().id {
+            match instruction_id {
+                capstone::x86_insn::X86_INS_LOOP => {
+                    Expr::cmpneq(cx.get()?, expr_const(0, cx.bits()))
+                }
+                capstone::x86_insn::X86_INS_LOOPE => {
+                    let expr = Expr::cmpneq(cx.get()?, expr_const(0, cx.bits()))?;
+                    Expr::and(expr, Expr::cmpeq(expr_scalar("ZF", 1), expr_const(1, 1))?)
+                }
+                capstone::x86_insn::X86_INS_LOOPNE => {
+                    let expr = Expr::cmpneq(cx.get()?, expr_const(0, cx.bits()))?;
+                    Expr::and(expr, Expr::cmpeq(expr_scalar("ZF", 1), expr_const(0, 1))?)
+                }
+                _ => bail!("unhandled loop"),
+            }
+        } else {
+            bail!("not an x86 instruction")
+        }
+    }
+
+    pub fn adc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        // create a block for this instruction
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get operands
+            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
+            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+
+            let result = self.temp(0, lhs.bits());
+
+            // perform addition
+            let addition = Expr::add(lhs.clone(), rhs.clone())?;
+            let zext_cf = Expr::zext(lhs.bits(), expr_scalar("CF", 1))?;
+            block.assign(result.clone(), Expr::add(addition, zext_cf)?);
+
+            // calculate flags
+            self.set_zf(&mut block, result.clone().into())?;
+            self.set_sf(&mut block, result.clone().into())?;
+            self.set_of(&mut block, result.clone().into(), lhs.clone(), rhs)?;
+            block.assign(
+                scalar("CF", 1),
+                Expression::cmpltu(result.clone().into(), lhs)?,
+            );
+
+            // store result
+            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn add(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get operands
+            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
+            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+
+            let result = self.temp(0, lhs.bits());
+
+            // perform addition
+            block.assign(result.clone(), Expr::add(lhs.clone(), rhs.clone())?);
+
+            // calculate flags
+            self.set_zf(&mut block, result.clone().into())?;
+            self.set_sf(&mut block, result.clone().into())?;
+            self.set_of(&mut block, result.clone().into(), lhs.clone(), rhs)?;
+            block.assign(
+                scalar("CF", 1),
+                Expression::cmpltu(result.clone().into(), lhs)?,
+            );
+
+            // store result
+            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn adds(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.sse_scalar_arithmetic(control_flow_graph, Expr::fadd)
+    }
+
+    pub fn and(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get operands
+            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
+            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
+
+            if rhs.bits() != lhs.bits() {
+                rhs = Expr::sext(lhs.bits(), rhs)?;
+            }
+
+            let result = self.temp(0, lhs.bits());
+
+            // perform addition
+            block.assign(result.clone(), Expr::and(lhs, rhs)?);
+
+            // calculate flags
+            self.set_zf(&mut block, result.clone().into())?;
+            self.set_sf(&mut block, result.clone().into())?;
+            block.assign(scalar("CF", 1), expr_const(0, 1));
+            block.assign(scalar("OF", 1), expr_const(0, 1));
+
+            // store result
+            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    /*
+        BSF scans the bits in the second word or doubleword operand starting with
+        bit 0. The ZF flag is cleared if the bits are all 0; otherwise, the ZF flag
+        is set and the destination register is loaded with the bit index of the
+        first set bit.
+    */
+    pub fn andnps(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.vector_binary(control_flow_graph, |_, lhs, rhs| {
+            let bits = lhs.bits();
+            let not_lhs = Expr::xor(lhs, Expr::sub(expr_const(0, bits), expr_const(1, bits))?)?;
+            Expr::and(not_lhs, rhs)
+        })
+    }
+
+    pub fn andps(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.vector_binary(control_flow_graph, |_, lhs, rhs| Expr::and(lhs, rhs))
+    }
+
+    pub fn bsf(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        // create our head block
+        let (head_index, rhs, counter) = {
+            let (head_index, rhs) = {
+                let mut head_block = control_flow_graph.new_block()?;
+
+                // get started
+                let rhs = self.operand_load(&mut head_block, &detail.operands[1])?;
+
+                (head_block.index(), rhs)
+            };
+
+            let counter = { control_flow_graph.temp(rhs.bits()) };
+
+            let head_block = control_flow_graph.block_mut(head_index)?;
+
+            // This is the loop preamble, and we'll always execute it
+            head_block.assign(scalar("ZF", 1), expr_const(0, 1));
+            head_block.assign(counter.clone(), expr_const(0, rhs.bits()));
+
+            (head_index, rhs, counter)
+        };
+
+        // if rhs == 0 then ZF = 1 and we are done.
+        let zero_index = {
+            let zero_block = control_flow_graph.new_block()?;
+            zero_block.assign(scalar("ZF", 1), expr_const(1, 1));
+
+            zero_block.index()
+        };
+
+        // The loop body checks if the bits for our counter is set.
+        let (bitfield, loop_index) = {
+            let bitfield = control_flow_graph.temp(rhs.bits());
+
+            let loop_block = control_flow_graph.new_block()?;
+            loop_block.assign(
+                bitfield.clone(),
+                Expr::and(
+                    Expr::shr(rhs.clone(), counter.clone().into())?,
+                    expr_const(1, rhs.bits()),
+                )?,
+            );
+
+            (bitfield, loop_block.index())
+        };
+
+        // While our bitfield == 0, we increment counter and keep looping
+        let iterate_index = {
+            let iterate_block = control_flow_graph.new_block()?;
+
+            iterate_block.assign(
+                counter.clone(),
+                Expr::add(counter.clone().into(), expr_const(1, counter.bits()))?,
+            );
+
+            iterate_block.index()
+        };
+
+        // In our terminating block, we set the result to counter
+        let terminating_index = {
+            let mut terminating_block = control_flow_graph.new_block()?;
+
+            self.operand_store(&mut terminating_block, &detail.operands[0], counter.into())?;
+
+            terminating_block.index()
+        };
+
+        control_flow_graph.conditional_edge(
+            head_index,
+            zero_index,
+            Expr::cmpeq(rhs.clone(), expr_const(0, rhs.bits()))?,
+        )?;
+        control_flow_graph.conditional_edge(
+            head_index,
+            loop_index,
+            Expr::cmpneq(rhs.clone(), expr_const(0, rhs.bits()))?,
+        )?;
+
+        control_flow_graph.unconditional_edge(zero_index, terminating_index)?;
+
+        control_flow_graph.conditional_edge(
+            loop_index,
+            iterate_index,
+            Expr::cmpeq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
+        )?;
+        control_flow_graph.conditional_edge(
+            loop_index,
+            terminating_index,
+            Expr::cmpneq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
+        )?;
+        control_flow_graph.unconditional_edge(iterate_index, loop_index)?;
+
+        control_flow_graph.set_entry(head_index)?;
+        control_flow_graph.set_exit(terminating_index)?;
+
+        Ok(())
+    }
+
+    /*
+        BSR scans the bits in the second word or doubleword operand from the most
+        significant bit to the least significant bit. The ZF flag is cleared if the
+        bits are all 0; otherwise, ZF is set and the destination register is loaded
+        with the bit index of the first set bit found when scanning in the reverse
+        direction.
+    */
+    pub fn bsr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let (head_index, rhs, counter) = {
+            let (head_index, rhs) = {
+                let mut head_block = control_flow_graph.new_block()?;
+
+                // get started
                 let rhs = self.operand_load(&mut head_block, &detail.operands[1])?;
 
-                (head_block.index(), rhs)
-            };
+                (head_block.index(), rhs)
+            };
+
+            let counter = { control_flow_graph.temp(rhs.bits()) };
+
+            let head_block = control_flow_graph.block_mut(head_index)?;
+
+            // This is the loop preamble, and we'll always execute it
+            head_block.assign(scalar("ZF", 1), expr_const(0, 1));
+            head_block.assign(
+                counter.clone(),
+                expr_const((rhs.bits() - 1) as u64, rhs.bits()),
+            );
+
+            (head_index, rhs, counter)
+        };
+
+        // if rhs == 0 then ZF = 1 and we are done.
+        let zero_index = {
+            let zero_block = control_flow_graph.new_block()?;
+            zero_block.assign(scalar("ZF", 1), expr_const(1, 1));
+
+            zero_block.index()
+        };
+
+        // The loop body checks if the bits for our counter is set
+        let (bitfield, loop_index) = {
+            let bitfield = control_flow_graph.temp(rhs.bits());
+
+            let loop_block = control_flow_graph.new_block()?;
+            loop_block.assign(
+                bitfield.clone(),
+                Expr::and(
+                    Expr::shr(rhs.clone(), counter.clone().into())?,
+                    expr_const(1, rhs.bits()),
+                )?,
+            );
+
+            (bitfield, loop_block.index())
+        };
+
+        // While our bitfield == 0, we decrement counter and keep looping
+        let iterate_index = {
+            let iterate_block = control_flow_graph.new_block()?;
+
+            iterate_block.assign(
+                counter.clone(),
+                Expr::sub(counter.clone().into(), expr_const(1, counter.bits()))?,
+            );
+
+            iterate_block.index()
+        };
+
+        // In our terminating block, we set the result to counter
+        let terminating_index = {
+            let mut terminating_block = control_flow_graph.new_block()?;
+
+            self.operand_store(&mut terminating_block, &detail.operands[0], counter.into())?;
+
+            terminating_block.index()
+        };
+
+        control_flow_graph.conditional_edge(
+            head_index,
+            zero_index,
+            Expr::cmpeq(rhs.clone(), expr_const(0, rhs.bits()))?,
+        )?;
+        control_flow_graph.conditional_edge(
+            head_index,
+            loop_index,
+            Expr::cmpneq(rhs.clone(), expr_const(0, rhs.bits()))?,
+        )?;
+
+        control_flow_graph.unconditional_edge(zero_index, terminating_index)?;
+
+        control_flow_graph.conditional_edge(
+            loop_index,
+            iterate_index,
+            Expr::cmpeq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
+        )?;
+        control_flow_graph.conditional_edge(
+            loop_index,
+            terminating_index,
+            Expr::cmpneq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
+        )?;
+        control_flow_graph.unconditional_edge(iterate_index, loop_index)?;
+
+        control_flow_graph.set_entry(head_index)?;
+        control_flow_graph.set_exit(terminating_index)?;
+
+        Ok(())
+    }
+
+    /*
+        BT saves the value of the bit indicated by the base (first operand) and the
+        bit offset (second operand) into the carry flag.
+
+        CF ← BIT[LeftSRC, RightSRC];
+
+        0F A3 BT r/m16,r16 3/12 Save bit in carry flag
+        0F A3 BT r/m32,r32 3/12 Save bit in carry flag
+        0F BA /4 ib BT r/m16,imm8 3/6 Save bit in carry flag
+        0F BA /4 ib BT r/m32,imm8 3/6 Save bit in carry flag
+    */
+    pub fn bt(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        // create our head block
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get started
+            let base = self.operand_load(&mut block, &detail.operands[0])?;
+            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
+
+            // let's ensure we have equal sorts
+            if offset.bits() != base.bits() {
+                let temp = self.temp(0, base.bits());
+                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
+                offset = temp.into();
+            }
+
+            let temp = self.temp(0, base.bits());
+            block.assign(temp.clone(), Expr::shr(base, offset)?);
+            block.assign(scalar("CF", 1), Expr::trun(1, temp.into())?);
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    /*
+        BTC saves the value of the bit indicated by the base (first operand) and the
+        bit offset (second operand) into the carry flag and then complements the
+        bit.
+
+        CF ← BIT[LeftSRC, RightSRC];
+        BIT[LeftSRC, RightSRC] ← NOT BIT[LeftSRC, RightSRC];
+
+        0F BB BTC r/m16,r16 6/13 Save bit in carry flag and complement
+        0F BB BTC r/m32,r32 6/13 Save bit in carry flag and complement
+        0F BA /7 ib BTC r/m16,imm8 6/8 Save bit in carry flag and complement
+        0F BA /7 ib BTC r/m32,imm8 6/8 Save bit in carry flag and complement
+    */
+    pub fn btc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        // create our head block
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get started
+            let base = self.operand_load(&mut block, &detail.operands[0])?;
+            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
+
+            // let's ensure we have equal sorts
+            if offset.bits() != base.bits() {
+                let temp = self.temp(0, base.bits());
+                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
+                offset = temp.into();
+            }
+
+            // this handles the assign to CF
+            let temp = self.temp(1, base.bits());
+            block.assign(temp.clone(), Expr::shr(base, offset.clone())?);
+            block.assign(scalar("CF", 1), Expr::trun(1, temp.clone().into())?);
+
+            let expr = Expr::xor(temp.clone().into(), expr_const(1, temp.bits()))?;
+            let expr = Expr::shl(expr, offset)?;
+            self.operand_store(&mut block, &detail.operands[0], expr)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    /*
+        BTR saves the value of the bit indicated by the base (first operand) and the
+        bit offset (second operand) into the carry flag and then stores 0 in the
+        bit.
+
+        CF ← BIT[LeftSRC, RightSRC];
+        BIT[LeftSRC, RightSRC] ← 0;
+
+        0F B3 BTR r/m16,r16 6/13 Save bit in carry flag and reset
+        0F B3 BTR r/m32,r32 6/13 Save bit in carry flag and reset
+        0F BA /6 ib BTR r/m16,imm8 6/8 Save bit in carry flag and reset
+        0F BA /6 ib BTR r/m32,imm8 6/8 Save bit in carry flag and reset
+    */
+    pub fn btr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        // create our head block
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get started
+            let base = self.operand_load(&mut block, &detail.operands[0])?;
+            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
+
+            // let's ensure we have equal sorts
+            if offset.bits() != base.bits() {
+                let temp = self.temp(0, base.bits());
+                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
+                offset = temp.into();
+            }
+
+            // this handles the assign to CF
+            let temp = self.temp(1, base.bits());
+            block.assign(temp.clone(), Expr::shr(base.clone(), offset.clone())?);
+            block.assign(scalar("CF", 1), Expr::trun(1, temp.into())?);
+
+            let expr = Expr::shl(expr_const(1, base.bits()), offset)?;
+            let expr = Expr::xor(expr, expr_const(0xffff_ffff_ffff_ffff, base.bits()))?;
+            let expr = Expr::and(base, expr)?;
+
+            self.operand_store(&mut block, &detail.operands[0], expr)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    /*
+        BTS saves the value of the bit indicated by the base (first operand) and the
+        bit offset (second operand) into the carry flag and then stores 1 in the
+        bit.
+
+        CF ← BIT[LeftSRC, RightSRC];
+        BIT[LeftSRC, RightSRC] ← 1;
+
+        0F AB BTS r/m16,r16 6/13 Save bit in carry flag and set
+        0F AB BTS r/m32,r32 6/13 Save bit in carry flag and set
+        0F BA /5 ib BTS r/m16,imm8 6/8 Save bit in carry flag and set
+        0F BA /5 ib BTS r/m32,imm8 6/8 Save bit in carry flag and set
+    */
+    pub fn bts(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        // create our head block
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get started
+            let base = self.operand_load(&mut block, &detail.operands[0])?;
+            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
+
+            // let's ensure we have equal sorts
+            if offset.bits() != base.bits() {
+                let temp = self.temp(0, base.bits());
+                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
+                offset = temp.into();
+            }
+
+            // this handles the assign to CF
+            let temp = self.temp(1, base.bits());
+            block.assign(temp.clone(), Expr::shr(base.clone(), offset.clone())?);
+            block.assign(scalar("CF", 1), Expr::trun(1, temp.into())?);
+
+            let expr = Expr::shl(expr_const(1, base.bits()), offset)?;
+            let expr = Expr::or(base, expr)?;
+
+            self.operand_store(&mut block, &detail.operands[0], expr)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn bswap(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get operands
+            let src = self.operand_load(&mut block, &detail.operands[0])?;
+
+            let expr = match self.mode {
+                Mode::X86 => Expr::or(
+                    Expr::or(
+                        Expr::and(
+                            Expr::shl(src.clone(), expr_const(24, 32))?,
+                            expr_const(0xff00_0000, 32),
+                        )?,
+                        Expr::and(
+                            Expr::shl(src.clone(), expr_const(8, 32))?,
+                            expr_const(0x00ff_0000, 32),
+                        )?,
+                    )?,
+                    Expr::or(
+                        Expr::and(
+                            Expr::shr(src.clone(), expr_const(8, 32))?,
+                            expr_const(0x0000_ff00, 32),
+                        )?,
+                        Expr::and(
+                            Expr::shr(src, expr_const(24, 32))?,
+                            expr_const(0x0000_00ff, 32),
+                        )?,
+                    )?,
+                )?,
+                Mode::Amd64 => Expr::or(
+                    Expr::or(
+                        Expr::or(
+                            Expr::and(
+                                Expr::shl(src.clone(), expr_const(56, 64))?,
+                                expr_const(0xff00_0000_0000_0000, 64),
+                            )?,
+                            Expr::and(
+                                Expr::shl(src.clone(), expr_const(40, 64))?,
+                                expr_const(0x00ff_0000_0000_0000, 64),
+                            )?,
+                        )?,
+                        Expr::or(
+                            Expr::and(
+                                Expr::shl(src.clone(), expr_const(24, 64))?,
+                                expr_const(0x0000_ff00_0000_0000, 64),
+                            )?,
+                            Expr::and(
+                                Expr::shl(src.clone(), expr_const(8, 64))?,
+                                expr_const(0x0000_00ff_0000_0000, 64),
+                            )?,
+                        )?,
+                    )?,
+                    Expr::or(
+                        Expr::or(
+                            Expr::and(
+                                Expr::shr(src.clone(), expr_const(8, 64))?,
+                                expr_const(0x0000_0000_ff00_0000, 64),
+                            )?,
+                            Expr::and(
+                                Expr::shr(src.clone(), expr_const(24, 64))?,
+                                expr_const(0x0000_0000_00ff_0000, 64),
+                            )?,
+                        )?,
+                        Expr::or(
+                            Expr::and(
+                                Expr::shr(src.clone(), expr_const(40, 64))?,
+                                expr_const(0x0000_0000_0000_ff00, 64),
+                            )?,
+                            Expr::and(
+                                Expr::shr(src, expr_const(56, 64))?,
+                                expr_const(0x0000_0000_0000_00ff, 64),
+                            )?,
+                        )?,
+                    )?,
+                )?,
+            };
+
+            self.operand_store(&mut block, &detail.operands[0], expr)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn call(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            // get started
+            let dst = self.operand_load(&mut block, &detail.operands[0])?;
+
+            let ret_addr = self.instruction().address + self.instruction().size as u64;
+
+            self.mode()
+                .push_value(&mut block, expr_const(ret_addr, self.mode().bits()))?;
+
+            block.branch(dst);
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn cbw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            let al = self.get_register(x86_reg::X86_REG_AL)?;
+            let ax = self.get_register(x86_reg::X86_REG_AX)?;
+
+            ax.set(&mut block, Expr::sext(16, al.get()?)?)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn cdq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            let eax = self.get_register(x86_reg::X86_REG_EAX)?;
+            let edx = self.get_register(x86_reg::X86_REG_EDX)?;
+
+            // isolate the sign bits of ax
+            let expr = Expr::shr(eax.get()?, expr_const(31, 32))?;
+            let expr = Expr::trun(1, expr)?;
+            let expr = Expr::sext(32, expr)?;
+
+            edx.set(&mut block, expr)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn cdqe(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            let eax = self.get_register(x86_reg::X86_REG_EAX)?;
+            let rax = self.get_register(x86_reg::X86_REG_RAX)?;
+
+            rax.set(&mut block, Expr::sext(64, eax.get()?)?)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
+    pub fn clc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-            let counter = { control_flow_graph.temp(rhs.bits()) };
+            block.assign(scalar("CF", 1), expr_const(0, 1));
 
-            let head_block = control_flow_graph.block_mut(head_index)?;
+            block.index()
+        };
 
-            // This is the loop preamble, and we'll always execute it
-            head_block.assign(scalar("ZF", 1), expr_const(0, 1));
-            head_block.assign(
-                counter.clone(),
-                expr_const((rhs.bits() - 1) as u64, rhs.bits()),
-            );
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
-            (head_index, rhs, counter)
+        Ok(())
+    }
+
+    pub fn cld(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            block.assign(scalar("DF", 1), expr_const(0, 1));
+
+            block.index()
         };
 
-        // if rhs == 0 then ZF = 1 and we are done.
-        let zero_index = {
-            let zero_block = control_flow_graph.new_block()?;
-            zero_block.assign(scalar("ZF", 1), expr_const(1, 1));
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
-            zero_block.index()
+        Ok(())
+    }
+
+    pub fn cli(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            block.assign(scalar("IF", 1), expr_const(0, 1));
+
+            block.index()
         };
 
-        // The loop body checks if the bits for our counter is set
-        let (bitfield, loop_index) = {
-            let bitfield = control_flow_graph.temp(rhs.bits());
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
-            let loop_block = control_flow_graph.new_block()?;
-            loop_block.assign(
-                bitfield.clone(),
-                Expr::and(
-                    Expr::shr(rhs.clone(), counter.clone().into())?,
-                    expr_const(1, rhs.bits()),
-                )?,
-            );
+        Ok(())
+    }
 
-            (bitfield, loop_block.index())
+    pub fn cmc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            let expr = Expr::xor(expr_scalar("CF", 1), expr_const(1, 1))?;
+            block.assign(scalar("CF", 1), expr);
+
+            block.index()
         };
 
-        // While our bitfield == 0, we decrement counter and keep looping
-        let iterate_index = {
-            let iterate_block = control_flow_graph.new_block()?;
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
-            iterate_block.assign(
-                counter.clone(),
-                Expr::sub(counter.clone().into(), expr_const(1, counter.bits()))?,
-            );
+        Ok(())
+    }
 
-            iterate_block.index()
+    pub fn cmovcc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let head_index = {
+            let block = control_flow_graph.new_block()?;
+
+            // This nop allows us to find this instruction in traces, even when
+            // then false branch is taken and no instruction is executed.
+            block.nop();
+
+            block.index()
         };
 
-        // In our terminating block, we set the result to counter
-        let terminating_index = {
-            let mut terminating_block = control_flow_graph.new_block()?;
+        let tail_index = {
+            let block = control_flow_graph.new_block()?;
 
-            self.operand_store(&mut terminating_block, &detail.operands[0], counter.into())?;
+            block.index()
+        };
 
-            terminating_block.index()
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            let src = self.operand_load(&mut block, &detail.operands[1])?;
+
+            self.operand_store(&mut block, &detail.operands[0], src)?;
+
+            block.index()
         };
 
+        let condition = self.cc_condition()?;
+
+        control_flow_graph.conditional_edge(head_index, block_index, condition.clone())?;
         control_flow_graph.conditional_edge(
             head_index,
-            zero_index,
-            Expr::cmpeq(rhs.clone(), expr_const(0, rhs.bits()))?,
-        )?;
-        control_flow_graph.conditional_edge(
-            head_index,
-            loop_index,
-            Expr::cmpneq(rhs.clone(), expr_const(0, rhs.bits()))?,
+            tail_index,
+            Expr::cmpeq(condition, expr_const(0, 1))?,
         )?;
+        control_flow_graph.unconditional_edge(block_index, tail_index)?;
 
-        control_flow_graph.unconditional_edge(zero_index, terminating_index)?;
+        control_flow_graph.set_entry(head_index)?;
+        control_flow_graph.set_exit(tail_index)?;
 
-        control_flow_graph.conditional_edge(
-            loop_index,
-            iterate_index,
-            Expr::cmpeq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
-        )?;
-        control_flow_graph.conditional_edge(
-            loop_index,
-            terminating_index,
-            Expr::cmpneq(bitfield.clone().into(), expr_const(0, bitfield.bits()))?,
-        )?;
-        control_flow_graph.unconditional_edge(iterate_index, loop_index)?;
+        Ok(())
+    }
 
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(terminating_index)?;
+    pub fn cmp(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let block_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
+            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
+
+            if rhs.bits() != lhs.bits() {
+                rhs = Expr::sext(lhs.bits(), rhs)?;
+            }
+
+            let expr = Expr::sub(lhs.clone(), rhs.clone())?;
+
+            self.set_zf(&mut block, expr.clone())?;
+            self.set_sf(&mut block, expr.clone())?;
+            self.set_of(&mut block, expr.clone(), lhs.clone(), rhs)?;
+            self.set_cf(&mut block, expr, lhs)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
         Ok(())
     }
 
-    /*
-        BT saves the value of the bit indicated by the base (first operand) and the
-        bit offset (second operand) into the carry flag.
+    pub fn cmps(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
 
-        CF ← BIT[LeftSRC, RightSRC];
+        let bits = detail.operands[0].size as usize * 8;
+
+        let si = self.get_register(x86_reg::X86_REG_SI)?.get_full()?;
+        let di = self.get_register(x86_reg::X86_REG_DI)?.get_full()?;
+
+        let head_index = {
+            let block = control_flow_graph.new_block()?;
+
+            let lhs = self.temp(0, bits);
+            let rhs = self.temp(1, bits);
+            block.load(lhs.clone(), si.get()?);
+            block.load(rhs.clone(), di.get()?);
+
+            self.compare_flags(block, lhs.into(), rhs.into())?;
+
+            block.index()
+        };
+
+        self.string_advance(control_flow_graph, head_index, &[si, di], bits / 8)
+    }
+
+    /// Semantics for cmpsd, which is both the SSE2 scalar compare and the
+    /// string compare. Only the string compare is lifted.
+    pub fn cmpsd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        if self.is_string_instruction()? {
+            return self.cmps(control_flow_graph);
+        }
+
+        bail!(
+            "Unhandled instruction {} {} at 0x{:x}",
+            self.instruction().mnemonic,
+            self.instruction().op_str,
+            self.instruction().address
+        )
+    }
+
+    pub fn cmpxchg(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let (head_index, dest, lhs, rhs) = {
+            let mut block = control_flow_graph.new_block()?;
+
+            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
+            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+
+            let dest = match rhs.bits() {
+                8 => self.get_register(x86_reg::X86_REG_AL)?,
+                16 => self.get_register(x86_reg::X86_REG_AX)?,
+                32 => self.get_register(x86_reg::X86_REG_EAX)?,
+                64 => self.get_register(x86_reg::X86_REG_RAX)?,
+                _ => bail!(
+                    "can't figure out dest for xmpxchg, rhs.bits()={}",
+                    rhs.bits()
+                ),
+            };
+
+            (block.index(), dest, lhs, rhs)
+        };
+
+        let taken_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            block.assign(scalar("ZF", 1), expr_const(1, 1));
+            self.operand_store(&mut block, &detail.operands[0], rhs.clone())?;
+
+            block.index()
+        };
+
+        let not_taken_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            block.assign(scalar("ZF", 1), expr_const(0, 1));
+            dest.set(&mut block, lhs.clone())?;
+
+            block.index()
+        };
+
+        let tail_index = {
+            let mut block = control_flow_graph.new_block()?;
+
+            let result = Expr::sub(lhs.clone(), rhs.clone())?;
+            self.set_sf(&mut block, result.clone())?;
+            self.set_of(&mut block, result.clone(), lhs.clone(), rhs)?;
+            self.set_cf(&mut block, result, lhs.clone())?;
+
+            block.index()
+        };
+
+        let condition = Expr::cmpeq(dest.get()?, lhs)?;
+
+        control_flow_graph.conditional_edge(head_index, taken_index, condition.clone())?;
+        control_flow_graph.conditional_edge(
+            head_index,
+            not_taken_index,
+            Expr::cmpeq(condition, expr_const(0, 1))?,
+        )?;
+        control_flow_graph.unconditional_edge(taken_index, tail_index)?;
+        control_flow_graph.unconditional_edge(not_taken_index, tail_index)?;
+
+        control_flow_graph.set_entry(head_index)?;
+        control_flow_graph.set_exit(tail_index)?;
+
+        Ok(())
+    }
 
-        0F A3 BT r/m16,r16 3/12 Save bit in carry flag
-        0F A3 BT r/m32,r32 3/12 Save bit in carry flag
-        0F BA /4 ib BT r/m16,imm8 3/6 Save bit in carry flag
-        0F BA /4 ib BT r/m32,imm8 3/6 Save bit in carry flag
-    */
-    pub fn bt(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Semantics for comiss, comisd, ucomiss and ucomisd.
+    pub fn comis(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
+        let bits = self.sse_float_bits();
 
-        // create our head block
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get started
-            let base = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
+            let block = control_flow_graph.new_block()?;
 
-            // let's ensure we have equal sorts
-            if offset.bits() != base.bits() {
-                let temp = self.temp(0, base.bits());
-                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
-                offset = temp.into();
-            }
+            let lhs = self.sse_scalar_load(block, &detail.operands[0], bits)?;
+            let rhs = self.sse_scalar_load(block, &detail.operands[1], bits)?;
 
-            let temp = self.temp(0, base.bits());
-            block.assign(temp.clone(), Expr::shr(base, offset)?);
-            block.assign(scalar("CF", 1), Expr::trun(1, temp.into())?);
+            let (below, unordered, equal) = self.float_compare(lhs, rhs)?;
+            block.assign(scalar("CF", 1), below);
+            block.assign(scalar("PF", 1), unordered);
+            block.assign(scalar("ZF", 1), equal);
+            block.assign(scalar("OF", 1), expr_const(0, 1));
+            block.assign(scalar("SF", 1), expr_const(0, 1));
 
             block.index()
         };
@@ -786,45 +1944,28 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    /*
-        BTC saves the value of the bit indicated by the base (first operand) and the
-        bit offset (second operand) into the carry flag and then complements the
-        bit.
-
-        CF ← BIT[LeftSRC, RightSRC];
-        BIT[LeftSRC, RightSRC] ← NOT BIT[LeftSRC, RightSRC];
-
-        0F BB BTC r/m16,r16 6/13 Save bit in carry flag and complement
-        0F BB BTC r/m32,r32 6/13 Save bit in carry flag and complement
-        0F BA /7 ib BTC r/m16,imm8 6/8 Save bit in carry flag and complement
-        0F BA /7 ib BTC r/m32,imm8 6/8 Save bit in carry flag and complement
-    */
-    pub fn btc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Semantics for cvtss2si, cvtsd2si, cvttss2si and cvttsd2si.
+    ///
+    /// The non-truncating forms round to nearest, the default rounding mode
+    /// of mxcsr.
+    pub fn cvts2si(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
-        // create our head block
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get started
-            let base = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
+        let (bits, truncate) = match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_CVTSS2SI => (32, false),
+            capstone::x86_insn::X86_INS_CVTTSS2SI => (32, true),
+            capstone::x86_insn::X86_INS_CVTSD2SI => (64, false),
+            _ => (64, true),
+        };
 
-            // let's ensure we have equal sorts
-            if offset.bits() != base.bits() {
-                let temp = self.temp(0, base.bits());
-                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
-                offset = temp.into();
-            }
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-            // this handles the assign to CF
-            let temp = self.temp(1, base.bits());
-            block.assign(temp.clone(), Expr::shr(base, offset.clone())?);
-            block.assign(scalar("CF", 1), Expr::trun(1, temp.clone().into())?);
+            let src = self.sse_scalar_load(block, &detail.operands[1], bits)?;
+            let src = if truncate { src } else { Expr::fround(src)? };
 
-            let expr = Expr::xor(temp.clone().into(), expr_const(1, temp.bits()))?;
-            let expr = Expr::shl(expr, offset)?;
-            self.operand_store(&mut block, &detail.operands[0], expr)?;
+            let dst_bits = self.get_register(detail.operands[0].reg())?.bits();
+            self.operand_store(block, &detail.operands[0], Expr::ftoi(dst_bits, src)?)?;
 
             block.index()
         };
@@ -835,47 +1976,14 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    /*
-        BTR saves the value of the bit indicated by the base (first operand) and the
-        bit offset (second operand) into the carry flag and then stores 0 in the
-        bit.
-
-        CF ← BIT[LeftSRC, RightSRC];
-        BIT[LeftSRC, RightSRC] ← 0;
-
-        0F B3 BTR r/m16,r16 6/13 Save bit in carry flag and reset
-        0F B3 BTR r/m32,r32 6/13 Save bit in carry flag and reset
-        0F BA /6 ib BTR r/m16,imm8 6/8 Save bit in carry flag and reset
-        0F BA /6 ib BTR r/m32,imm8 6/8 Save bit in carry flag and reset
-    */
-    pub fn btr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn cvtsd2ss(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
-        // create our head block
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get started
-            let base = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
-
-            // let's ensure we have equal sorts
-            if offset.bits() != base.bits() {
-                let temp = self.temp(0, base.bits());
-                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
-                offset = temp.into();
-            }
-
-            // this handles the assign to CF
-            let temp = self.temp(1, base.bits());
-            block.assign(temp.clone(), Expr::shr(base.clone(), offset.clone())?);
-            block.assign(scalar("CF", 1), Expr::trun(1, temp.into())?);
-
-            let expr = Expr::shl(expr_const(1, base.bits()), offset)?;
-            let expr = Expr::xor(expr, expr_const(0xffff_ffff_ffff_ffff, base.bits()))?;
-            let expr = Expr::and(base, expr)?;
+            let block = control_flow_graph.new_block()?;
 
-            self.operand_store(&mut block, &detail.operands[0], expr)?;
+            let src = self.sse_scalar_load(block, &detail.operands[1], 64)?;
+            self.sse_scalar_store(block, &detail.operands[0], Expr::fconv(32, src)?)?;
 
             block.index()
         };
@@ -886,46 +1994,16 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    /*
-        BTS saves the value of the bit indicated by the base (first operand) and the
-        bit offset (second operand) into the carry flag and then stores 1 in the
-        bit.
-
-        CF ← BIT[LeftSRC, RightSRC];
-        BIT[LeftSRC, RightSRC] ← 1;
-
-        0F AB BTS r/m16,r16 6/13 Save bit in carry flag and set
-        0F AB BTS r/m32,r32 6/13 Save bit in carry flag and set
-        0F BA /5 ib BTS r/m16,imm8 6/8 Save bit in carry flag and set
-        0F BA /5 ib BTS r/m32,imm8 6/8 Save bit in carry flag and set
-    */
-    pub fn bts(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Semantics for cvtsi2ss and cvtsi2sd.
+    pub fn cvtsi2s(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
+        let bits = self.sse_float_bits();
 
-        // create our head block
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get started
-            let base = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut offset = self.operand_load(&mut block, &detail.operands[1])?;
-
-            // let's ensure we have equal sorts
-            if offset.bits() != base.bits() {
-                let temp = self.temp(0, base.bits());
-                block.assign(temp.clone(), Expr::zext(base.bits(), offset.clone())?);
-                offset = temp.into();
-            }
-
-            // this handles the assign to CF
-            let temp = self.temp(1, base.bits());
-            block.assign(temp.clone(), Expr::shr(base.clone(), offset.clone())?);
-            block.assign(scalar("CF", 1), Expr::trun(1, temp.into())?);
-
-            let expr = Expr::shl(expr_const(1, base.bits()), offset)?;
-            let expr = Expr::or(base, expr)?;
+            let block = control_flow_graph.new_block()?;
 
-            self.operand_store(&mut block, &detail.operands[0], expr)?;
+            let src = self.operand_load(block, &detail.operands[1])?;
+            self.sse_scalar_store(block, &detail.operands[0], Expr::itof(bits, src)?)?;
 
             block.index()
         };
@@ -936,87 +2014,14 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn bswap(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn cvtss2sd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let src = self.operand_load(&mut block, &detail.operands[0])?;
-
-            let expr = match self.mode {
-                Mode::X86 => Expr::or(
-                    Expr::or(
-                        Expr::and(
-                            Expr::shl(src.clone(), expr_const(24, 32))?,
-                            expr_const(0xff00_0000, 32),
-                        )?,
-                        Expr::and(
-                            Expr::shl(src.clone(), expr_const(8, 32))?,
-                            expr_const(0x00ff_0000, 32),
-                        )?,
-                    )?,
-                    Expr::or(
-                        Expr::and(
-                            Expr::shr(src.clone(), expr_const(8, 32))?,
-                            expr_const(0x0000_ff00, 32),
-                        )?,
-                        Expr::and(
-                            Expr::shr(src, expr_const(24, 32))?,
-                            expr_const(0x0000_00ff, 32),
-                        )?,
-                    )?,
-                )?,
-                Mode::Amd64 => Expr::or(
-                    Expr::or(
-                        Expr::or(
-                            Expr::and(
-                                Expr::shl(src.clone(), expr_const(56, 64))?,
-                                expr_const(0xff00_0000_0000_0000, 64),
-                            )?,
-                            Expr::and(
-                                Expr::shl(src.clone(), expr_const(40, 64))?,
-                                expr_const(0x00ff_0000_0000_0000, 64),
-                            )?,
-                        )?,
-                        Expr::or(
-                            Expr::and(
-                                Expr::shl(src.clone(), expr_const(24, 64))?,
-                                expr_const(0x0000_ff00_0000_0000, 64),
-                            )?,
-                            Expr::and(
-                                Expr::shl(src.clone(), expr_const(8, 64))?,
-                                expr_const(0x0000_00ff_0000_0000, 64),
-                            )?,
-                        )?,
-                    )?,
-                    Expr::or(
-                        Expr::or(
-                            Expr::and(
-                                Expr::shr(src.clone(), expr_const(8, 64))?,
-                                expr_const(0x0000_0000_ff00_0000, 64),
-                            )?,
-                            Expr::and(
-                                Expr::shr(src.clone(), expr_const(24, 64))?,
-                                expr_const(0x0000_0000_00ff_0000, 64),
-                            )?,
-                        )?,
-                        Expr::or(
-                            Expr::and(
-                                Expr::shr(src.clone(), expr_const(40, 64))?,
-                                expr_const(0x0000_0000_0000_ff00, 64),
-                            )?,
-                            Expr::and(
-                                Expr::shr(src, expr_const(56, 64))?,
-                                expr_const(0x0000_0000_0000_00ff, 64),
-                            )?,
-                        )?,
-                    )?,
-                )?,
-            };
+            let block = control_flow_graph.new_block()?;
 
-            self.operand_store(&mut block, &detail.operands[0], expr)?;
+            let src = self.sse_scalar_load(block, &detail.operands[1], 32)?;
+            self.sse_scalar_store(block, &detail.operands[0], Expr::fconv(64, src)?)?;
 
             block.index()
         };
@@ -1027,21 +2032,19 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn call(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
+    pub fn cwd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let ax = self.get_register(x86_reg::X86_REG_AX)?;
+        let dx = self.get_register(x86_reg::X86_REG_DX)?;
 
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            // get started
-            let dst = self.operand_load(&mut block, &detail.operands[0])?;
-
-            let ret_addr = self.instruction().address + self.instruction().size as u64;
-
-            self.mode()
-                .push_value(&mut block, expr_const(ret_addr, self.mode().bits()))?;
+            // isolate the sign bits of ax
+            let expr = Expr::shr(ax.get()?, expr_const(15, 16))?;
+            let expr = Expr::trun(1, expr)?;
+            let expr = Expr::sext(16, expr)?;
 
-            block.branch(dst);
+            dx.set(&mut block, expr)?;
 
             block.index()
         };
@@ -1052,14 +2055,14 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cbw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn cwde(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let ax = self.get_register(x86_reg::X86_REG_AX)?;
+        let eax = self.get_register(x86_reg::X86_REG_EAX)?;
+
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            let al = self.get_register(x86_reg::X86_REG_AL)?;
-            let ax = self.get_register(x86_reg::X86_REG_AX)?;
-
-            ax.set(&mut block, Expr::sext(16, al.get()?)?)?;
+            eax.set(&mut block, Expr::sext(32, ax.get()?)?)?;
 
             block.index()
         };
@@ -1070,19 +2073,27 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cdq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn dec(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            let eax = self.get_register(x86_reg::X86_REG_EAX)?;
-            let edx = self.get_register(x86_reg::X86_REG_EDX)?;
+            let dst = self.operand_load(&mut block, &detail.operands[0])?;
 
-            // isolate the sign bits of ax
-            let expr = Expr::shr(eax.get()?, expr_const(31, 32))?;
-            let expr = Expr::trun(1, expr)?;
-            let expr = Expr::sext(32, expr)?;
+            let expr = Expr::sub(dst.clone(), expr_const(1, dst.bits()))?;
+
+            self.set_zf(&mut block, expr.clone())?;
+            self.set_sf(&mut block, expr.clone())?;
+            self.set_of(
+                &mut block,
+                expr.clone(),
+                dst.clone(),
+                expr_const(1, dst.bits()),
+            )?;
+            self.set_cf(&mut block, expr.clone(), dst)?;
 
-            edx.set(&mut block, expr)?;
+            self.operand_store(&mut block, &detail.operands[0], expr)?;
 
             block.index()
         };
@@ -1093,14 +2104,81 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cdqe(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn div(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            let eax = self.get_register(x86_reg::X86_REG_EAX)?;
-            let rax = self.get_register(x86_reg::X86_REG_RAX)?;
+            let divisor = self.operand_load(&mut block, &detail.operands[0])?;
+            let divisor = Expr::zext(divisor.bits() * 2, divisor)?;
 
-            rax.set(&mut block, Expr::sext(64, eax.get()?)?)?;
+            let dividend: Expr = match divisor.bits() {
+                16 => self.get_register(x86_reg::X86_REG_AX)?.get()?,
+                32 => {
+                    let expr_dx = Expr::zext(32, self.get_register(x86_reg::X86_REG_DX)?.get()?)?;
+                    let expr_dx = Expr::shl(expr_dx, expr_const(16, 32))?;
+                    Expr::or(
+                        expr_dx,
+                        Expr::zext(32, self.get_register(x86_reg::X86_REG_AX)?.get()?)?,
+                    )?
+                }
+                64 => {
+                    let expr_edx = Expr::zext(64, self.get_register(x86_reg::X86_REG_EDX)?.get()?)?;
+                    let expr_edx = Expr::shl(expr_edx, expr_const(32, 64))?;
+                    Expr::or(
+                        expr_edx,
+                        Expr::zext(64, self.get_register(x86_reg::X86_REG_EAX)?.get()?)?,
+                    )?
+                }
+                128 => {
+                    let expr_edx =
+                        Expr::zext(128, self.get_register(x86_reg::X86_REG_RDX)?.get()?)?;
+                    let expr_edx = Expr::shl(expr_edx, expr_const(64, 128))?;
+                    Expr::or(
+                        expr_edx,
+                        Expr::zext(128, self.get_register(x86_reg::X86_REG_EAX)?.get()?)?,
+                    )?
+                }
+                _ => return Err("invalid bit-width in x86 div".into()),
+            };
+
+            let quotient = self.temp(0, divisor.bits());
+            let remainder = self.temp(1, divisor.bits());
+
+            block.assign(
+                quotient.clone(),
+                Expr::divu(dividend.clone(), divisor.clone())?,
+            );
+            block.assign(remainder.clone(), Expr::modu(dividend, divisor.clone())?);
+
+            match divisor.bits() {
+                16 => {
+                    let al = self.get_register(x86_reg::X86_REG_AL)?;
+                    let ah = self.get_register(x86_reg::X86_REG_AH)?;
+                    al.set(&mut block, Expr::trun(8, quotient.into())?)?;
+                    ah.set(&mut block, Expr::trun(8, remainder.into())?)?;
+                }
+                32 => {
+                    let ax = self.get_register(x86_reg::X86_REG_AX)?;
+                    let dx = self.get_register(x86_reg::X86_REG_DX)?;
+                    ax.set(&mut block, Expr::trun(16, quotient.into())?)?;
+                    dx.set(&mut block, Expr::trun(16, remainder.into())?)?;
+                }
+                64 => {
+                    let eax = self.get_register(x86_reg::X86_REG_EAX)?;
+                    let edx = self.get_register(x86_reg::X86_REG_EDX)?;
+                    eax.set(&mut block, Expr::trun(32, quotient.into())?)?;
+                    edx.set(&mut block, Expr::trun(32, remainder.into())?)?;
+                }
+                128 => {
+                    let rax = self.get_register(x86_reg::X86_REG_RAX)?;
+                    let rdx = self.get_register(x86_reg::X86_REG_RDX)?;
+                    rax.set(&mut block, Expr::trun(64, quotient.into())?)?;
+                    rdx.set(&mut block, Expr::trun(64, remainder.into())?)?;
+                }
+                _ => return Err("invalid bit-width in x86 div".into()),
+            }
 
             block.index()
         };
@@ -1111,11 +2189,18 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn clc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    // This is essentially the exact same as div with the signs of the arith ops
+    // reversed.
+    pub fn divs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.sse_scalar_arithmetic(control_flow_graph, Expr::fdiv)
+    }
+
+    pub fn fabs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let block_index = {
             let block = control_flow_graph.new_block()?;
 
-            block.assign(scalar("CF", 1), expr_const(0, 1));
+            let value = Expr::and(self.st(0).into(), expr_const(0x7fff_ffff_ffff_ffff, 64))?;
+            block.assign(self.st(0), value);
 
             block.index()
         };
@@ -1126,11 +2211,16 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cld(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn fadd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.fpu_arithmetic(control_flow_graph, Expr::fadd, false)
+    }
+
+    pub fn fchs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let block_index = {
             let block = control_flow_graph.new_block()?;
 
-            block.assign(scalar("DF", 1), expr_const(0, 1));
+            let value = Expr::xor(self.st(0).into(), expr_const(0x8000_0000_0000_0000, 64))?;
+            block.assign(self.st(0), value);
 
             block.index()
         };
@@ -1141,11 +2231,45 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cli(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Semantics for the x87 compares which set the condition codes c0, c2
+    /// and c3: fcom, fucom, ficom, ftst and their popping forms.
+    pub fn fcom(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+        let instruction_id = self.instruction_id()?;
+
+        let pops = match instruction_id {
+            capstone::x86_insn::X86_INS_FCOMPP | capstone::x86_insn::X86_INS_FUCOMPP => 2,
+            capstone::x86_insn::X86_INS_FCOMP
+            | capstone::x86_insn::X86_INS_FUCOMP
+            | capstone::x86_insn::X86_INS_FICOMP => 1,
+            _ => 0,
+        };
+
         let block_index = {
             let block = control_flow_graph.new_block()?;
 
-            block.assign(scalar("IF", 1), expr_const(0, 1));
+            let rhs = match instruction_id {
+                capstone::x86_insn::X86_INS_FTST => expr_const(0, 64),
+                capstone::x86_insn::X86_INS_FICOM | capstone::x86_insn::X86_INS_FICOMP => {
+                    Expr::itof(64, self.operand_load(block, &detail.operands[0])?)?
+                }
+                _ => match detail.op_count {
+                    0 => self.st(1).into(),
+                    op_count => {
+                        let operand = &detail.operands[op_count as usize - 1];
+                        self.fpu_operand_load(block, operand)?
+                    }
+                },
+            };
+
+            let (below, unordered, equal) = self.float_compare(self.st(0).into(), rhs)?;
+            block.assign(scalar("C0", 1), below);
+            block.assign(scalar("C2", 1), unordered);
+            block.assign(scalar("C3", 1), equal);
+
+            for _ in 0..pops {
+                self.fpu_pop(block);
+            }
 
             block.index()
         };
@@ -1156,12 +2280,36 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cmc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Semantics for fcomi, fucomi and their popping forms, which set eflags.
+    pub fn fcomi(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let pop = !matches!(
+            self.instruction_id()?,
+            capstone::x86_insn::X86_INS_FCOMI | capstone::x86_insn::X86_INS_FUCOMI
+        );
+
         let block_index = {
             let block = control_flow_graph.new_block()?;
 
-            let expr = Expr::xor(expr_scalar("CF", 1), expr_const(1, 1))?;
-            block.assign(scalar("CF", 1), expr);
+            let rhs = match detail.op_count {
+                0 => self.st(1).into(),
+                op_count => {
+                    let operand = &detail.operands[op_count as usize - 1];
+                    self.fpu_operand_load(block, operand)?
+                }
+            };
+
+            let (below, unordered, equal) = self.float_compare(self.st(0).into(), rhs)?;
+            block.assign(scalar("CF", 1), below);
+            block.assign(scalar("PF", 1), unordered);
+            block.assign(scalar("ZF", 1), equal);
+            block.assign(scalar("OF", 1), expr_const(0, 1));
+            block.assign(scalar("SF", 1), expr_const(0, 1));
+
+            if pop {
+                self.fpu_pop(block);
+            }
 
             block.index()
         };
@@ -1172,70 +2320,63 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cmovcc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let head_index = {
-            let block = control_flow_graph.new_block()?;
-
-            // This nop allows us to find this instruction in traces, even when
-            // then false branch is taken and no instruction is executed.
-            block.nop();
-
-            block.index()
-        };
+    pub fn fdiv(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.fpu_arithmetic(control_flow_graph, Expr::fdiv, false)
+    }
 
-        let tail_index = {
-            let block = control_flow_graph.new_block()?;
+    pub fn fdivr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.fpu_arithmetic(control_flow_graph, Expr::fdiv, true)
+    }
 
-            block.index()
-        };
+    pub fn fild(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let src = self.operand_load(&mut block, &detail.operands[1])?;
+            let block = control_flow_graph.new_block()?;
 
-            self.operand_store(&mut block, &detail.operands[0], src)?;
+            let value = self.operand_load(block, &detail.operands[0])?;
+            self.fpu_push(block, Expr::itof(64, value)?);
 
             block.index()
         };
 
-        let condition = self.cc_condition()?;
-
-        control_flow_graph.conditional_edge(head_index, block_index, condition.clone())?;
-        control_flow_graph.conditional_edge(
-            head_index,
-            tail_index,
-            Expr::cmpeq(condition, expr_const(0, 1))?,
-        )?;
-        control_flow_graph.unconditional_edge(block_index, tail_index)?;
-
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
         Ok(())
     }
 
-    pub fn cmp(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Semantics for fist, fistp and fisttp.
+    ///
+    /// fist and fistp truncate when the rounding control of the x87 control
+    /// word, held in fpu_cw, is set to round toward zero, and otherwise round
+    /// to nearest.
+    pub fn fist(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
+        let instruction_id = self.instruction_id()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
+            let block = control_flow_graph.new_block()?;
 
-            if rhs.bits() != lhs.bits() {
-                rhs = Expr::sext(lhs.bits(), rhs)?;
-            }
+            let bits = detail.operands[0].size as usize * 8;
+            let st0: Expression = self.st(0).into();
 
-            let expr = Expr::sub(lhs.clone(), rhs.clone())?;
+            let value = if instruction_id == capstone::x86_insn::X86_INS_FISTTP {
+                Expr::ftoi(bits, st0)?
+            } else {
+                let rounding_control =
+                    Expr::trun(2, Expr::shr(expr_scalar("fpu_cw", 16), expr_const(10, 16))?)?;
+                Expr::ite(
+                    Expr::cmpeq(rounding_control, expr_const(3, 2))?,
+                    Expr::ftoi(bits, st0.clone())?,
+                    Expr::ftoi(bits, Expr::fround(st0)?)?,
+                )?
+            };
+            self.operand_store(block, &detail.operands[0], value)?;
 
-            self.set_zf(&mut block, expr.clone())?;
-            self.set_sf(&mut block, expr.clone())?;
-            self.set_of(&mut block, expr.clone(), lhs.clone(), rhs)?;
-            self.set_cf(&mut block, expr, lhs)?;
+            if instruction_id != capstone::x86_insn::X86_INS_FIST {
+                self.fpu_pop(block);
+            }
 
             block.index()
         };
@@ -1246,162 +2387,107 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cmpsb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn fld(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
-        let si = match *self.mode() {
-            Mode::X86 => self.get_register(x86_reg::X86_REG_ESI)?,
-            Mode::Amd64 => self.get_register(x86_reg::X86_REG_RSI)?,
-        };
-        let di = match *self.mode() {
-            Mode::X86 => self.get_register(x86_reg::X86_REG_EDI)?,
-            Mode::Amd64 => self.get_register(x86_reg::X86_REG_RDI)?,
-        };
-        let bits = self.mode().bits();
-
-        let head_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
-
-            if rhs.bits() != lhs.bits() {
-                rhs = Expr::sext(lhs.bits(), rhs)?;
-            }
-
-            let expr = Expr::sub(lhs.clone(), rhs.clone())?;
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-            self.set_zf(&mut block, expr.clone())?;
-            self.set_sf(&mut block, expr.clone())?;
-            self.set_of(&mut block, expr.clone(), lhs.clone(), rhs)?;
-            self.set_cf(&mut block, expr, lhs)?;
+            let value = self.fpu_operand_load(block, &detail.operands[0])?;
+            self.fpu_push(block, value);
 
             block.index()
         };
 
-        let inc_index = {
-            let mut block = control_flow_graph.new_block()?;
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
-            si.set(&mut block, Expr::add(si.get()?, expr_const(1, bits))?)?;
-            di.set(&mut block, Expr::add(di.get()?, expr_const(1, bits))?)?;
+        Ok(())
+    }
 
-            block.index()
+    /// Semantics for the instructions which push a constant onto the x87
+    /// register stack, such as fld1 and fldz.
+    pub fn fld_constant(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let value = match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_FLD1 => 1.0,
+            capstone::x86_insn::X86_INS_FLDL2E => std::f64::consts::LOG2_E,
+            capstone::x86_insn::X86_INS_FLDL2T => std::f64::consts::LOG2_10,
+            capstone::x86_insn::X86_INS_FLDLG2 => std::f64::consts::LOG10_2,
+            capstone::x86_insn::X86_INS_FLDLN2 => std::f64::consts::LN_2,
+            capstone::x86_insn::X86_INS_FLDPI => std::f64::consts::PI,
+            _ => 0.0,
         };
 
-        let dec_index = {
-            let mut block = control_flow_graph.new_block()?;
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-            si.set(&mut block, Expr::sub(si.get()?, expr_const(1, bits))?)?;
-            di.set(&mut block, Expr::sub(di.get()?, expr_const(1, bits))?)?;
+            self.fpu_push(block, expr_const(f64::to_bits(value), 64));
 
             block.index()
         };
 
-        let tail_index = { control_flow_graph.new_block()?.index() };
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            inc_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
-        )?;
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            dec_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
-        )?;
-
-        control_flow_graph.unconditional_edge(inc_index, tail_index)?;
-        control_flow_graph.unconditional_edge(dec_index, tail_index)?;
-
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
         Ok(())
     }
 
-    pub fn cmpxchg(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn fldcw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
-        let (head_index, dest, lhs, rhs) = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
-
-            let dest = match rhs.bits() {
-                8 => self.get_register(x86_reg::X86_REG_AL)?,
-                16 => self.get_register(x86_reg::X86_REG_AX)?,
-                32 => self.get_register(x86_reg::X86_REG_EAX)?,
-                64 => self.get_register(x86_reg::X86_REG_RAX)?,
-                _ => bail!(
-                    "can't figure out dest for xmpxchg, rhs.bits()={}",
-                    rhs.bits()
-                ),
-            };
-
-            (block.index(), dest, lhs, rhs)
-        };
-
-        let taken_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            block.assign(scalar("ZF", 1), expr_const(1, 1));
-            self.operand_store(&mut block, &detail.operands[0], rhs.clone())?;
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            let value = self.operand_load(block, &detail.operands[0])?;
+            block.assign(scalar("fpu_cw", 16), value);
 
             block.index()
         };
 
-        let not_taken_index = {
-            let mut block = control_flow_graph.new_block()?;
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
-            block.assign(scalar("ZF", 1), expr_const(0, 1));
-            dest.set(&mut block, lhs.clone())?;
+        Ok(())
+    }
 
-            block.index()
-        };
+    pub fn fmul(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.fpu_arithmetic(control_flow_graph, Expr::fmul, false)
+    }
 
-        let tail_index = {
-            let mut block = control_flow_graph.new_block()?;
+    pub fn fnstcw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
 
-            let result = Expr::sub(lhs.clone(), rhs.clone())?;
-            self.set_sf(&mut block, result.clone())?;
-            self.set_of(&mut block, result.clone(), lhs.clone(), rhs)?;
-            self.set_cf(&mut block, result, lhs.clone())?;
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            self.operand_store(block, &detail.operands[0], expr_scalar("fpu_cw", 16))?;
 
             block.index()
         };
 
-        let condition = Expr::cmpeq(dest.get()?, lhs)?;
-
-        control_flow_graph.conditional_edge(head_index, taken_index, condition.clone())?;
-        control_flow_graph.conditional_edge(
-            head_index,
-            not_taken_index,
-            Expr::cmpeq(condition, expr_const(0, 1))?,
-        )?;
-        control_flow_graph.unconditional_edge(taken_index, tail_index)?;
-        control_flow_graph.unconditional_edge(not_taken_index, tail_index)?;
-
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
         Ok(())
     }
 
-    pub fn cwd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let ax = self.get_register(x86_reg::X86_REG_AX)?;
-        let dx = self.get_register(x86_reg::X86_REG_DX)?;
+    /// Stores the x87 status word, composed of the condition codes. The top of
+    /// stack is not tracked, and is always stored as 0.
+    pub fn fnstsw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // isolate the sign bits of ax
-            let expr = Expr::shr(ax.get()?, expr_const(15, 16))?;
-            let expr = Expr::trun(1, expr)?;
-            let expr = Expr::sext(16, expr)?;
+            let block = control_flow_graph.new_block()?;
 
-            dx.set(&mut block, expr)?;
+            let mut status_word = expr_const(0, 16);
+            for &(condition_code, bit) in &[("C0", 8), ("C2", 10), ("C3", 14)] {
+                let bit = Expr::shl(
+                    Expr::zext(16, expr_scalar(condition_code, 1))?,
+                    expr_const(bit, 16),
+                )?;
+                status_word = Expr::or(status_word, bit)?;
+            }
+            self.operand_store(block, &detail.operands[0], status_word)?;
 
             block.index()
         };
@@ -1412,14 +2498,11 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn cwde(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let ax = self.get_register(x86_reg::X86_REG_AX)?;
-        let eax = self.get_register(x86_reg::X86_REG_EAX)?;
-
+    pub fn frndint(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+            let block = control_flow_graph.new_block()?;
 
-            eax.set(&mut block, Expr::sext(32, ax.get()?)?)?;
+            block.assign(self.st(0), Expr::fround(self.st(0).into())?);
 
             block.index()
         };
@@ -1430,27 +2513,18 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn dec(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    /// Semantics for fst and fstp.
+    pub fn fst(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let dst = self.operand_load(&mut block, &detail.operands[0])?;
-
-            let expr = Expr::sub(dst.clone(), expr_const(1, dst.bits()))?;
+            let block = control_flow_graph.new_block()?;
 
-            self.set_zf(&mut block, expr.clone())?;
-            self.set_sf(&mut block, expr.clone())?;
-            self.set_of(
-                &mut block,
-                expr.clone(),
-                dst.clone(),
-                expr_const(1, dst.bits()),
-            )?;
-            self.set_cf(&mut block, expr.clone(), dst)?;
+            self.fpu_operand_store(block, &detail.operands[0], self.st(0).into())?;
 
-            self.operand_store(&mut block, &detail.operands[0], expr)?;
+            if self.instruction_id()? == capstone::x86_insn::X86_INS_FSTP {
+                self.fpu_pop(block);
+            }
 
             block.index()
         };
@@ -1461,81 +2535,29 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn div(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn fsub(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.fpu_arithmetic(control_flow_graph, Expr::fsub, false)
+    }
+
+    pub fn fsubr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.fpu_arithmetic(control_flow_graph, Expr::fsub, true)
+    }
+
+    pub fn fxch(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let divisor = self.operand_load(&mut block, &detail.operands[0])?;
-            let divisor = Expr::zext(divisor.bits() * 2, divisor)?;
+            let block = control_flow_graph.new_block()?;
 
-            let dividend: Expr = match divisor.bits() {
-                16 => self.get_register(x86_reg::X86_REG_AX)?.get()?,
-                32 => {
-                    let expr_dx = Expr::zext(32, self.get_register(x86_reg::X86_REG_DX)?.get()?)?;
-                    let expr_dx = Expr::shl(expr_dx, expr_const(16, 32))?;
-                    Expr::or(
-                        expr_dx,
-                        Expr::zext(32, self.get_register(x86_reg::X86_REG_AX)?.get()?)?,
-                    )?
-                }
-                64 => {
-                    let expr_edx = Expr::zext(64, self.get_register(x86_reg::X86_REG_EDX)?.get()?)?;
-                    let expr_edx = Expr::shl(expr_edx, expr_const(32, 64))?;
-                    Expr::or(
-                        expr_edx,
-                        Expr::zext(64, self.get_register(x86_reg::X86_REG_EAX)?.get()?)?,
-                    )?
-                }
-                128 => {
-                    let expr_edx =
-                        Expr::zext(128, self.get_register(x86_reg::X86_REG_RDX)?.get()?)?;
-                    let expr_edx = Expr::shl(expr_edx, expr_const(64, 128))?;
-                    Expr::or(
-                        expr_edx,
-                        Expr::zext(128, self.get_register(x86_reg::X86_REG_EAX)?.get()?)?,
-                    )?
-                }
-                _ => return Err("invalid bit-width in x86 div".into()),
+            let register = match detail.op_count {
+                0 => self.get_register(x86_reg::X86_REG_ST1)?,
+                _ => self.get_register(detail.operands[0].reg())?,
             };
 
-            let quotient = self.temp(0, divisor.bits());
-            let remainder = self.temp(1, divisor.bits());
-
-            block.assign(
-                quotient.clone(),
-                Expr::divu(dividend.clone(), divisor.clone())?,
-            );
-            block.assign(remainder.clone(), Expr::modu(dividend, divisor.clone())?);
-
-            match divisor.bits() {
-                16 => {
-                    let al = self.get_register(x86_reg::X86_REG_AL)?;
-                    let ah = self.get_register(x86_reg::X86_REG_AH)?;
-                    al.set(&mut block, Expr::trun(8, quotient.into())?)?;
-                    ah.set(&mut block, Expr::trun(8, remainder.into())?)?;
-                }
-                32 => {
-                    let ax = self.get_register(x86_reg::X86_REG_AX)?;
-                    let dx = self.get_register(x86_reg::X86_REG_DX)?;
-                    ax.set(&mut block, Expr::trun(16, quotient.into())?)?;
-                    dx.set(&mut block, Expr::trun(16, remainder.into())?)?;
-                }
-                64 => {
-                    let eax = self.get_register(x86_reg::X86_REG_EAX)?;
-                    let edx = self.get_register(x86_reg::X86_REG_EDX)?;
-                    eax.set(&mut block, Expr::trun(32, quotient.into())?)?;
-                    edx.set(&mut block, Expr::trun(32, remainder.into())?)?;
-                }
-                128 => {
-                    let rax = self.get_register(x86_reg::X86_REG_RAX)?;
-                    let rdx = self.get_register(x86_reg::X86_REG_RDX)?;
-                    rax.set(&mut block, Expr::trun(64, quotient.into())?)?;
-                    rdx.set(&mut block, Expr::trun(64, remainder.into())?)?;
-                }
-                _ => return Err("invalid bit-width in x86 div".into()),
-            }
+            let temp = self.temp(0, 64);
+            block.assign(temp.clone(), self.st(0).into());
+            block.assign(self.st(0), register.get()?);
+            register.set(block, temp.into())?;
 
             block.index()
         };
@@ -1546,8 +2568,6 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    // This is essentially the exact same as div with the signs of the arith ops
-    // reversed.
     pub fn idiv(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
@@ -1885,137 +2905,66 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn lodsb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn lods(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
-        let si = self.get_register(x86_reg::X86_REG_ESI)?.get_full()?;
-
-        let head_index = {
-            let mut block = control_flow_graph.new_block()?;
+        let si = self.get_register(x86_reg::X86_REG_SI)?.get_full()?;
 
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+        let (head_index, bits) = {
+            let block = control_flow_graph.new_block()?;
 
-            self.get_register(x86_reg::X86_REG_AL)?
-                .set(&mut block, rhs)?;
+            let src = self.operand_load(block, &detail.operands[1])?;
+            let bits = src.bits();
+            self.operand_store(block, &detail.operands[0], src)?;
 
-            block.index()
+            (block.index(), bits)
         };
 
-        let inc_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            si.set(
-                &mut block,
-                Expr::add(si.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
-
-            block.index()
-        };
+        self.string_advance(control_flow_graph, head_index, &[si], bits / 8)
+    }
 
-        let dec_index = {
+    pub fn loop_(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            si.set(
+            let cx = self.get_register(x86_reg::X86_REG_CX)?.get_full()?;
+            cx.set(
                 &mut block,
-                Expr::sub(si.get()?, expr_const(1, self.mode().bits()))?,
+                Expr::sub(cx.get()?, expr_const(1, self.mode().bits()))?,
             )?;
 
             block.index()
         };
 
-        let tail_index = { control_flow_graph.new_block()?.index() };
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            inc_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
-        )?;
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            dec_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
-        )?;
-
-        control_flow_graph.unconditional_edge(inc_index, tail_index)?;
-        control_flow_graph.unconditional_edge(dec_index, tail_index)?;
-
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
 
         Ok(())
     }
 
-    pub fn lodsd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let si = self.get_register(x86_reg::X86_REG_ESI)?.get_full()?;
-
-        let head_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
-
-            self.get_register(x86_reg::X86_REG_EAX)?
-                .set(&mut block, rhs)?;
-
-            block.index()
-        };
-
-        let inc_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            si.set(
-                &mut block,
-                Expr::add(si.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
-
-            block.index()
-        };
-
-        let dec_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            si.set(
-                &mut block,
-                Expr::sub(si.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
-
-            block.index()
-        };
-
-        let tail_index = { control_flow_graph.new_block()?.index() };
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            inc_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
-        )?;
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            dec_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
-        )?;
-
-        control_flow_graph.unconditional_edge(inc_index, tail_index)?;
-        control_flow_graph.unconditional_edge(dec_index, tail_index)?;
-
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
+    pub fn maxs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        // The source is returned when either operand is NaN.
+        self.sse_scalar_arithmetic(control_flow_graph, |lhs, rhs| {
+            Expr::ite(Expr::fcmplt(rhs.clone(), lhs.clone())?, lhs, rhs)
+        })
+    }
 
-        Ok(())
+    pub fn mins(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        // The source is returned when either operand is NaN.
+        self.sse_scalar_arithmetic(control_flow_graph, |lhs, rhs| {
+            Expr::ite(Expr::fcmplt(lhs.clone(), rhs.clone())?, lhs, rhs)
+        })
     }
 
-    pub fn loop_(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn mov(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            let cx = self.get_register(x86_reg::X86_REG_CX)?.get_full()?;
-            cx.set(
-                &mut block,
-                Expr::sub(cx.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
+            let src = self.operand_load(&mut block, &detail.operands[1])?;
+
+            self.operand_store(&mut block, &detail.operands[0], src)?;
 
             block.index()
         };
@@ -2026,15 +2975,24 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn mov(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn movd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+            let block = control_flow_graph.new_block()?;
 
-            let src = self.operand_load(&mut block, &detail.operands[1])?;
+            let src = self.operand_load(block, &detail.operands[1])?;
+            let dst_bits = detail.operands[0].size as usize * 8;
 
-            self.operand_store(&mut block, &detail.operands[0], src)?;
+            // Moves into an xmm register zero the upper bits, and moves out of
+            // one take the low doubleword or quadword.
+            let value = match src.bits().cmp(&dst_bits) {
+                Ordering::Less => Expr::zext(dst_bits, src)?,
+                Ordering::Greater => Expr::trun(dst_bits, src)?,
+                Ordering::Equal => src,
+            };
+
+            self.operand_store(block, &detail.operands[0], value)?;
 
             block.index()
         };
@@ -2045,6 +3003,13 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn movhlps(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let lanes = vec![Self::lane(&rhs, 64, 1)?, Self::lane(&lhs, 64, 1)?];
+            self.concat_lanes(block, lanes)
+        })
+    }
+
     pub fn movhpd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
@@ -2081,6 +3046,13 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn movlhps(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let lanes = vec![Self::lane(&lhs, 64, 0)?, Self::lane(&rhs, 64, 0)?];
+            self.concat_lanes(block, lanes)
+        })
+    }
+
     pub fn movlpd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
@@ -2119,6 +3091,37 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn movmsk(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
+
+        let lane_bits = match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_MOVMSKPD => 64,
+            _ => 32,
+        };
+
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            let src = self.operand_load(block, &detail.operands[1])?;
+            let dst_bits = detail.operands[0].size as usize * 8;
+
+            // Gather the sign bit of each lane
+            let signs = (0..src.bits() / lane_bits)
+                .map(|i| Self::lane(&src, 1, (i + 1) * lane_bits - 1))
+                .collect::<Result<Vec<Expression>>>()?;
+            let mask = self.concat_lanes(block, signs)?;
+
+            self.operand_store(block, &detail.operands[0], Expr::zext(dst_bits, mask)?)?;
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
     pub fn movq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
@@ -2156,10 +3159,12 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    /// Semantics for movsd, which is both the SSE2 scalar move and the string
+    /// move.
     pub fn movs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
-        let bits_size = detail.operands[1].size as usize * 8;
+        let bits = detail.operands[1].size as usize * 8;
 
         let si = self.get_register(x86_reg::X86_REG_SI)?.get_full()?;
         let di = self.get_register(x86_reg::X86_REG_DI)?.get_full()?;
@@ -2167,78 +3172,31 @@ impl<'s> Semantics<'s> {
         let head_index = {
             let block = control_flow_graph.new_block()?;
 
-            let temp = self.temp(0, bits_size);
+            let temp = self.temp(0, bits);
             block.load(temp.clone(), si.get()?);
             block.store(di.get()?, temp.into());
 
             block.index()
         };
 
-        let inc_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            si.set(
-                &mut block,
-                Expr::add(
-                    si.get()?,
-                    expr_const((bits_size / 8) as u64, self.mode().bits()),
-                )?,
-            )?;
-
-            di.set(
-                &mut block,
-                Expr::add(
-                    di.get()?,
-                    expr_const((bits_size / 8) as u64, self.mode().bits()),
-                )?,
-            )?;
-
-            block.index()
-        };
-
-        let dec_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            si.set(
-                &mut block,
-                Expr::sub(
-                    si.get()?,
-                    expr_const((bits_size / 8) as u64, self.mode().bits()),
-                )?,
-            )?;
-
-            di.set(
-                &mut block,
-                Expr::sub(
-                    di.get()?,
-                    expr_const((bits_size / 8) as u64, self.mode().bits()),
-                )?,
-            )?;
-
-            block.index()
-        };
-
-        let tail_index = { control_flow_graph.new_block()?.index() };
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            inc_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
-        )?;
+        self.string_advance(control_flow_graph, head_index, &[si, di], bits / 8)
+    }
 
-        control_flow_graph.conditional_edge(
-            head_index,
-            dec_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
-        )?;
+    pub fn movsd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
 
-        control_flow_graph.unconditional_edge(inc_index, tail_index)?;
-        control_flow_graph.unconditional_edge(dec_index, tail_index)?;
+        if detail.operands[0..detail.op_count as usize]
+            .iter()
+            .all(|operand| operand.type_ == x86_op_type::X86_OP_MEM)
+        {
+            return self.movs(control_flow_graph);
+        }
 
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
+        self.sse_scalar_move(control_flow_graph, 64)
+    }
 
-        Ok(())
+    pub fn movss(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.sse_scalar_move(control_flow_graph, 32)
     }
 
     pub fn movsx(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
@@ -2345,213 +3303,13 @@ impl<'s> Semantics<'s> {
                         &mut block,
                         Expr::trun(64, Expr::shr(result.clone().into(), expr_const(64, 128))?)?,
                     )?;
-                    rax.set(&mut block, Expr::trun(64, result.into())?)?;
-                    block.assign(scalar("OF", 1), Expr::cmpeq(rdx.get()?, expr_const(0, 64))?);
-                    block.assign(scalar("CF", 1), expr_scalar("OF", 1));
-                }
-                _ => bail!("invalid bit-width for mul"),
-            }
-
-            block.index()
-        };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
-    }
-
-    pub fn neg(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let dst = self.operand_load(&mut block, &detail.operands[0])?;
-
-            let result = self.temp(0, dst.bits());
-
-            block.assign(
-                scalar("CF", 1),
-                Expr::cmpneq(dst.clone(), expr_const(0, dst.bits()))?,
-            );
-
-            block.assign(
-                result.clone(),
-                Expr::sub(expr_const(0, dst.bits()), dst.clone())?,
-            );
-
-            self.set_zf(&mut block, result.clone().into())?;
-            self.set_sf(&mut block, result.clone().into())?;
-            self.set_of(
-                &mut block,
-                result.clone().into(),
-                expr_const(0, dst.bits()),
-                dst,
-            )?;
-
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
-
-            block.index()
-        };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
-    }
-
-    pub fn nop(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let block_index = {
-            let block = control_flow_graph.new_block()?;
-
-            block.nop();
-
-            block.index()
-        };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
-    }
-
-    pub fn not(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            let dst = self.operand_load(&mut block, &detail.operands[0])?;
-
-            let expr = Expr::xor(dst.clone(), expr_const(!0, dst.bits()))?;
-
-            self.operand_store(&mut block, &detail.operands[0], expr)?;
-
-            block.index()
-        };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
-    }
-
-    pub fn or(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
-
-            let result = self.temp(0, lhs.bits());
-
-            if lhs.bits() != rhs.bits() {
-                rhs = Expr::sext(lhs.bits(), rhs)?;
-            }
-
-            // perform addition
-            block.assign(result.clone(), Expr::or(lhs, rhs)?);
-
-            // calculate flags
-            self.set_zf(&mut block, result.clone().into())?;
-            self.set_sf(&mut block, result.clone().into())?;
-            block.assign(scalar("CF", 1), expr_const(0, 1));
-            block.assign(scalar("OF", 1), expr_const(0, 1));
-
-            // store result
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
-
-            block.index()
-        };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
-    }
-
-    pub fn paddq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
-
-            if lhs.bits() == 64 {
-                self.operand_store(&mut block, &detail.operands[0], Expr::add(lhs, rhs)?)?;
-            } else if lhs.bits() == 128 {
-                let upper = Expr::shl(
-                    expr_const(64, 128),
-                    Expr::add(
-                        Expr::shr(expr_const(64, 128), lhs.clone())?,
-                        Expr::shr(expr_const(64, 128), lhs.clone())?,
-                    )?,
-                )?;
-                let lower =
-                    Expr::and(expr_const(0xffff_ffff_ffff_ffff, 128), Expr::add(lhs, rhs)?)?;
-                self.operand_store(&mut block, &detail.operands[0], Expr::or(upper, lower)?)?;
-            }
-
-            block.index()
-        };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
-    }
-
-    pub fn pcmpeqb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
-
-            let temp = self.temp(0, lhs.bits());
-
-            block.assign(
-                temp.clone(),
-                Expr::ite(
-                    Expr::cmpeq(Expr::trun(8, lhs.clone())?, Expr::trun(8, rhs.clone())?)?,
-                    expr_const(0xff, lhs.bits()),
-                    expr_const(0, lhs.bits()),
-                )?,
-            );
-
-            for i in 1..(lhs.bits() / 8) {
-                let shift_constant = expr_const((i * 8) as u64, lhs.bits());
-                let cmp_lhs = Expr::trun(8, Expr::shr(lhs.clone(), shift_constant.clone())?)?;
-                let cmp_rhs = Expr::trun(8, Expr::shr(rhs.clone(), shift_constant.clone())?)?;
-
-                block.assign(
-                    temp.clone(),
-                    Expr::or(
-                        temp.clone().into(),
-                        Expr::shl(
-                            Expr::ite(
-                                Expr::cmpeq(cmp_lhs, cmp_rhs)?,
-                                Expr::zext(lhs.bits(), expr_const(0xff, 8))?,
-                                Expr::zext(lhs.bits(), expr_const(0, 8))?,
-                            )?,
-                            shift_constant,
-                        )?,
-                    )?,
-                );
+                    rax.set(&mut block, Expr::trun(64, result.into())?)?;
+                    block.assign(scalar("OF", 1), Expr::cmpeq(rdx.get()?, expr_const(0, 64))?);
+                    block.assign(scalar("CF", 1), expr_scalar("OF", 1));
+                }
+                _ => bail!("invalid bit-width for mul"),
             }
 
-            self.operand_store(&mut block, &detail.operands[0], temp.into())?;
-
             block.index()
         };
 
@@ -2561,49 +3319,40 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn pcmpeqd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn muls(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.sse_scalar_arithmetic(control_flow_graph, Expr::fmul)
+    }
+
+    pub fn neg(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+            let dst = self.operand_load(&mut block, &detail.operands[0])?;
 
-            let temp = self.temp(0, lhs.bits());
+            let result = self.temp(0, dst.bits());
 
             block.assign(
-                temp.clone(),
-                Expr::ite(
-                    Expr::cmpeq(Expr::trun(32, lhs.clone())?, Expr::trun(32, rhs.clone())?)?,
-                    expr_const(0xffff_ffff, lhs.bits()),
-                    expr_const(0, lhs.bits()),
-                )?,
+                scalar("CF", 1),
+                Expr::cmpneq(dst.clone(), expr_const(0, dst.bits()))?,
             );
 
-            for i in 1..(lhs.bits() / 32) {
-                let shift_constant = expr_const((i * 32) as u64, lhs.bits());
-                let cmp_lhs = Expr::trun(32, Expr::shr(lhs.clone(), shift_constant.clone())?)?;
-                let cmp_rhs = Expr::trun(32, Expr::shr(rhs.clone(), shift_constant.clone())?)?;
+            block.assign(
+                result.clone(),
+                Expr::sub(expr_const(0, dst.bits()), dst.clone())?,
+            );
 
-                block.assign(
-                    temp.clone(),
-                    Expr::or(
-                        temp.clone().into(),
-                        Expr::shl(
-                            Expr::ite(
-                                Expr::cmpeq(cmp_lhs, cmp_rhs)?,
-                                Expr::zext(lhs.bits(), expr_const(0xffff_ffff, 32))?,
-                                Expr::zext(lhs.bits(), expr_const(0, 32))?,
-                            )?,
-                            shift_constant,
-                        )?,
-                    )?,
-                );
-            }
+            self.set_zf(&mut block, result.clone().into())?;
+            self.set_sf(&mut block, result.clone().into())?;
+            self.set_of(
+                &mut block,
+                result.clone().into(),
+                expr_const(0, dst.bits()),
+                dst,
+            )?;
 
-            self.operand_store(&mut block, &detail.operands[0], temp.into())?;
+            self.operand_store(&mut block, &detail.operands[0], result.into())?;
 
             block.index()
         };
@@ -2614,51 +3363,11 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn pmovmskb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
+    pub fn nop(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let dst = self.get_register(detail.operands[0].reg())?;
-            let src = self.operand_load(&mut block, &detail.operands[1])?;
-
-            let temp = self.temp(0, dst.bits());
-
-            block.assign(
-                temp.clone(),
-                Expr::ite(
-                    Expr::cmpeq(
-                        Expr::trun(1, Expr::shr(src.clone(), expr_const(7, src.bits()))?)?,
-                        expr_const(1, 1),
-                    )?,
-                    expr_const(1, dst.bits()),
-                    expr_const(0, dst.bits()),
-                )?,
-            );
-
-            for i in 1..(src.bits() / 8) {
-                let cmp = Expr::cmpeq(
-                    Expr::trun(
-                        1,
-                        Expr::shr(
-                            src.clone(),
-                            expr_const(((i as u64 + 1) * 8) - 1, src.bits()),
-                        )?,
-                    )?,
-                    expr_const(1, 1),
-                )?;
-                let bit = Expr::ite(
-                    cmp,
-                    expr_const(1 << i, dst.bits()),
-                    expr_const(0, dst.bits()),
-                )?;
-
-                block.assign(temp.clone(), Expr::or(temp.clone().into(), bit)?);
-            }
+            let block = control_flow_graph.new_block()?;
 
-            self.operand_store(&mut block, &detail.operands[0], temp.into())?;
+            block.nop();
 
             block.index()
         };
@@ -2669,39 +3378,17 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn pminub(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn not(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
-
-            let temp = self.temp(0, lhs.bits());
-
-            block.assign(
-                temp.clone(),
-                Expr::ite(
-                    Expr::cmpltu(Expr::trun(8, lhs.clone())?, Expr::trun(8, rhs.clone())?)?,
-                    Expr::and(lhs.clone(), expr_const(0xff, lhs.bits()))?,
-                    Expr::and(rhs.clone(), expr_const(0xff, rhs.bits()))?,
-                )?,
-            );
-
-            for i in 1..(lhs.bits() / 8) {
-                let mask = const_(0xff, lhs.bits()).shl(&const_(8 * i as u64, lhs.bits()))?;
-
-                let l8 = Expr::and(lhs.clone(), mask.clone().into())?;
-                let r8 = Expr::and(rhs.clone(), mask.into())?;
-
-                let ite = Expr::ite(Expr::cmpltu(l8.clone(), r8.clone())?, l8, r8)?;
+            let dst = self.operand_load(&mut block, &detail.operands[0])?;
 
-                block.assign(temp.clone(), Expr::or(temp.clone().into(), ite)?);
-            }
+            let expr = Expr::xor(dst.clone(), expr_const(!0, dst.bits()))?;
 
-            self.operand_store(&mut block, &detail.operands[0], temp.into())?;
+            self.operand_store(&mut block, &detail.operands[0], expr)?;
 
             block.index()
         };
@@ -2712,7 +3399,7 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn por(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn or(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
@@ -2720,9 +3407,25 @@ impl<'s> Semantics<'s> {
 
             // get operands
             let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
+
+            let result = self.temp(0, lhs.bits());
 
-            self.operand_store(&mut block, &detail.operands[0], Expr::or(lhs, rhs)?)?;
+            if lhs.bits() != rhs.bits() {
+                rhs = Expr::sext(lhs.bits(), rhs)?;
+            }
+
+            // perform addition
+            block.assign(result.clone(), Expr::or(lhs, rhs)?);
+
+            // calculate flags
+            self.set_zf(&mut block, result.clone().into())?;
+            self.set_sf(&mut block, result.clone().into())?;
+            block.assign(scalar("CF", 1), expr_const(0, 1));
+            block.assign(scalar("OF", 1), expr_const(0, 1));
+
+            // store result
+            self.operand_store(&mut block, &detail.operands[0], result.into())?;
 
             block.index()
         };
@@ -2733,116 +3436,144 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn pshufd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+    pub fn pabs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |_, src| {
+            let zero = expr_const(0, lane_bits);
+            Expr::ite(
+                Expr::cmplts(src.clone(), zero.clone())?,
+                Expr::sub(zero, src.clone())?,
+                src,
+            )
+        })
+    }
 
-            // get operands
-            let src = self.operand_load(&mut block, &detail.operands[1])?;
-            let mut order = self.operand_load(&mut block, &detail.operands[2])?;
+    pub fn pack(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let (lane_bits, signed) = match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_PACKSSWB => (16, true),
+            capstone::x86_insn::X86_INS_PACKSSDW => (32, true),
+            capstone::x86_insn::X86_INS_PACKUSWB => (16, false),
+            _ => (32, false),
+        };
 
-            if order.bits() < src.bits() {
-                order = Expression::zext(src.bits(), order)?;
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let mut lanes = Vec::new();
+            for src in [&lhs, &rhs].iter() {
+                for i in 0..(src.bits() / lane_bits) {
+                    lanes.push(Self::saturate(
+                        Self::lane(src, lane_bits, i)?,
+                        lane_bits / 2,
+                        signed,
+                    )?);
+                }
             }
+            self.concat_lanes(block, lanes)
+        })
+    }
 
-            let order0 = Expression::and(order.clone(), expr_const(3, order.bits()))?;
-            let order1 = Expression::and(
-                Expression::shr(order.clone(), expr_const(2, order.bits()))?,
-                expr_const(3, order.bits()),
-            )?;
-            let order2 = Expression::and(
-                Expression::shr(order.clone(), expr_const(4, order.bits()))?,
-                expr_const(3, order.bits()),
-            )?;
-            let order3 = Expression::and(
-                Expression::shr(order.clone(), expr_const(6, order.bits()))?,
-                expr_const(3, order.bits()),
-            )?;
+    pub fn padd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.packed_binary(control_flow_graph, self.packed_lane_bits()?, Expr::add)
+    }
 
-            let result0 = Expression::and(
-                Expression::shr(
-                    src.clone(),
-                    // We need to multiply by 32
-                    Expression::shl(order0, expr_const(5, src.bits()))?,
+    pub fn padds(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |lhs, rhs| {
+            Self::saturate(
+                Expr::add(
+                    Expr::sext(lane_bits * 2, lhs)?,
+                    Expr::sext(lane_bits * 2, rhs)?,
                 )?,
-                expr_const(0xffff_ffff, src.bits()),
-            )?;
+                lane_bits,
+                true,
+            )
+        })
+    }
 
-            let result1 = Expression::and(
-                Expression::shr(
-                    src.clone(),
-                    // We need to multiply by 32
-                    Expression::shl(order1, expr_const(5, src.bits()))?,
+    pub fn paddus(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |lhs, rhs| {
+            Self::saturate(
+                Expr::add(
+                    Expr::zext(lane_bits * 2, lhs)?,
+                    Expr::zext(lane_bits * 2, rhs)?,
                 )?,
-                expr_const(0xffff_ffff, src.bits()),
-            )?;
+                lane_bits,
+                false,
+            )
+        })
+    }
 
-            let result2 = Expression::and(
-                Expression::shr(
-                    src.clone(),
-                    // We need to multiply by 32
-                    Expression::shl(order2, expr_const(5, src.bits()))?,
+    pub fn palignr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let shift = self.immediate()? * 8;
+        self.vector_binary(control_flow_graph, |_, lhs, rhs| {
+            let bits = lhs.bits();
+            let concatenation = Expr::or(
+                Expr::shl(
+                    Expr::zext(bits * 2, lhs)?,
+                    expr_const(bits as u64, bits * 2),
                 )?,
-                expr_const(0xffff_ffff, src.bits()),
+                Expr::zext(bits * 2, rhs)?,
             )?;
+            Expr::trun(bits, Expr::shr(concatenation, expr_const(shift, bits * 2))?)
+        })
+    }
 
-            let result3 = Expression::and(
-                Expression::shr(
-                    src.clone(),
-                    // We need to multiply by 32
-                    Expression::shl(order3, expr_const(5, src.bits()))?,
+    pub fn pavg(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |lhs, rhs| {
+            let sum = Expr::add(
+                Expr::add(
+                    Expr::zext(lane_bits * 2, lhs)?,
+                    Expr::zext(lane_bits * 2, rhs)?,
                 )?,
-                expr_const(0xffff_ffff, src.bits()),
+                expr_const(1, lane_bits * 2),
             )?;
+            Expr::trun(lane_bits, Expr::shr(sum, expr_const(1, lane_bits * 2))?)
+        })
+    }
 
-            let temp = self.temp(0, detail.operands[0].size as usize * 8);
-
-            block.assign(temp.clone(), result0);
-            block.assign(
-                temp.clone(),
-                Expression::shl(result1, expr_const(32, temp.bits()))?,
-            );
-            block.assign(
-                temp.clone(),
-                Expression::shl(result2, expr_const(64, temp.bits()))?,
-            );
-            block.assign(
-                temp.clone(),
-                Expression::shl(result3, expr_const(96, temp.bits()))?,
-            );
-
-            self.operand_store(&mut block, &detail.operands[0], temp.into())?;
-
-            block.index()
-        };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
+    pub fn pcmpeq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |lhs, rhs| {
+            Expr::ite(
+                Expr::cmpeq(lhs, rhs)?,
+                expr_const(u64::MAX, lane_bits),
+                expr_const(0, lane_bits),
+            )
+        })
+    }
 
-        Ok(())
+    pub fn pcmpgt(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |lhs, rhs| {
+            Expr::ite(
+                Expr::cmplts(rhs, lhs)?,
+                expr_const(u64::MAX, lane_bits),
+                expr_const(0, lane_bits),
+            )
+        })
     }
 
-    pub fn pslldq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn pextr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+        let lane_bits = self.packed_lane_bits()?;
+        let index = self.immediate()? as usize;
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
 
-            if rhs.bits() < lhs.bits() {
-                rhs = Expression::zext(lhs.bits(), rhs)?;
-            }
+            let src = self.operand_load(block, &detail.operands[1])?;
+            let dst_bits = detail.operands[0].size as usize * 8;
 
-            if rhs.bits() < lhs.bits() {
-                rhs = Expression::zext(lhs.bits(), rhs)?;
-            }
+            let value = Self::lane(&src, lane_bits, index % (src.bits() / lane_bits))?;
+            let value = if dst_bits > lane_bits {
+                Expr::zext(dst_bits, value)?
+            } else {
+                value
+            };
 
-            self.operand_store(&mut block, &detail.operands[0], Expression::shl(lhs, rhs)?)?;
+            self.operand_store(block, &detail.operands[0], value)?;
 
             block.index()
         };
@@ -2853,73 +3584,113 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn psrldq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+    pub fn pinsr(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        let index = self.immediate()? as usize;
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let mut rhs = self.operand_load(&mut block, &detail.operands[1])?;
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let count = lhs.bits() / lane_bits;
+            let value = if rhs.bits() > lane_bits {
+                Expr::trun(lane_bits, rhs)?
+            } else {
+                rhs
+            };
 
-            if rhs.bits() < lhs.bits() {
-                rhs = Expression::zext(lhs.bits(), rhs)?;
-            }
+            let lanes = (0..count)
+                .map(|i| {
+                    if i == index % count {
+                        Ok(value.clone())
+                    } else {
+                        Self::lane(&lhs, lane_bits, i)
+                    }
+                })
+                .collect::<Result<Vec<Expression>>>()?;
 
-            self.operand_store(&mut block, &detail.operands[0], Expression::shr(lhs, rhs)?)?;
+            self.concat_lanes(block, lanes)
+        })
+    }
 
-            block.index()
-        };
+    pub fn pmaddwd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.packed_binary(control_flow_graph, 32, |lhs, rhs| {
+            let product = |i| -> Result<Expression> {
+                Expr::mul(
+                    Expr::sext(32, Self::lane(&lhs, 16, i)?)?,
+                    Expr::sext(32, Self::lane(&rhs, 16, i)?)?,
+                )
+            };
+            Expr::add(product(0)?, product(1)?)
+        })
+    }
 
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
+    pub fn pmax(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let signed = self.packed_signed();
+        self.packed_binary(control_flow_graph, self.packed_lane_bits()?, |lhs, rhs| {
+            let less = if signed {
+                Expr::cmplts(lhs.clone(), rhs.clone())?
+            } else {
+                Expr::cmpltu(lhs.clone(), rhs.clone())?
+            };
+            Expr::ite(less, rhs, lhs)
+        })
+    }
 
-        Ok(())
+    pub fn pmin(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let signed = self.packed_signed();
+        self.packed_binary(control_flow_graph, self.packed_lane_bits()?, |lhs, rhs| {
+            let less = if signed {
+                Expr::cmplts(lhs.clone(), rhs.clone())?
+            } else {
+                Expr::cmpltu(lhs.clone(), rhs.clone())?
+            };
+            Expr::ite(less, lhs, rhs)
+        })
     }
 
-    pub fn psubb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn pmovmskb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
             // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+            let dst = self.get_register(detail.operands[0].reg())?;
+            let src = self.operand_load(&mut block, &detail.operands[1])?;
 
-            let mut temp_vars = Vec::new();
+            let temp = self.temp(0, dst.bits());
 
-            for i in 0..(lhs.bits() / 8) {
-                let ll = Expression::trun(
-                    8,
-                    Expression::shr(lhs.clone(), expr_const(i as u64 * 8, lhs.bits()))?,
-                )?;
-                let rr = Expression::trun(
-                    8,
-                    Expression::shr(rhs.clone(), expr_const(i as u64 * 8, lhs.bits()))?,
-                )?;
-                let temp = self.temp(0, 8);
-                block.assign(temp.clone(), Expression::sub(ll, rr)?);
-                temp_vars.push(temp);
-            }
+            block.assign(
+                temp.clone(),
+                Expr::ite(
+                    Expr::cmpeq(
+                        Expr::trun(1, Expr::shr(src.clone(), expr_const(7, src.bits()))?)?,
+                        expr_const(1, 1),
+                    )?,
+                    expr_const(1, dst.bits()),
+                    expr_const(0, dst.bits()),
+                )?,
+            );
 
-            let result = self.temp(1, lhs.bits());
-            block.assign(result.clone(), expr_const(0, lhs.bits()));
-            for (i, var) in temp_vars.iter().enumerate() {
-                block.assign(
-                    result.clone(),
-                    Expression::or(
-                        result.clone().into(),
-                        Expression::shl(
-                            Expression::zext(lhs.bits(), var.clone().into())?,
-                            expr_const(i as u64 * 8, lhs.bits()),
+            for i in 1..(src.bits() / 8) {
+                let cmp = Expr::cmpeq(
+                    Expr::trun(
+                        1,
+                        Expr::shr(
+                            src.clone(),
+                            expr_const(((i as u64 + 1) * 8) - 1, src.bits()),
                         )?,
                     )?,
-                );
+                    expr_const(1, 1),
+                )?;
+                let bit = Expr::ite(
+                    cmp,
+                    expr_const(1 << i, dst.bits()),
+                    expr_const(0, dst.bits()),
+                )?;
+
+                block.assign(temp.clone(), Expr::or(temp.clone().into(), bit)?);
             }
 
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+            self.operand_store(&mut block, &detail.operands[0], temp.into())?;
 
             block.index()
         };
@@ -2930,30 +3701,38 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn psubq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn pmovx(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
+        let (src_bits, dst_bits) = match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_PMOVSXBW | capstone::x86_insn::X86_INS_PMOVZXBW => (8, 16),
+            capstone::x86_insn::X86_INS_PMOVSXBD | capstone::x86_insn::X86_INS_PMOVZXBD => (8, 32),
+            capstone::x86_insn::X86_INS_PMOVSXBQ | capstone::x86_insn::X86_INS_PMOVZXBQ => (8, 64),
+            capstone::x86_insn::X86_INS_PMOVSXWD | capstone::x86_insn::X86_INS_PMOVZXWD => (16, 32),
+            capstone::x86_insn::X86_INS_PMOVSXWQ | capstone::x86_insn::X86_INS_PMOVZXWQ => (16, 64),
+            _ => (32, 64),
+        };
+        let signed = self.instruction.mnemonic.contains("sx");
+
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+            let block = control_flow_graph.new_block()?;
+
+            let src = self.operand_load(block, &detail.operands[detail.op_count as usize - 1])?;
+            let bits = detail.operands[0].size as usize * 8;
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+            let lanes = (0..bits / dst_bits)
+                .map(|i| {
+                    let lane = Self::lane(&src, src_bits, i)?;
+                    if signed {
+                        Expr::sext(dst_bits, lane)
+                    } else {
+                        Expr::zext(dst_bits, lane)
+                    }
+                })
+                .collect::<Result<Vec<Expression>>>()?;
+            let result = self.concat_lanes(block, lanes)?;
 
-            if lhs.bits() == 64 {
-                self.operand_store(&mut block, &detail.operands[0], Expr::sub(lhs, rhs)?)?;
-            } else if lhs.bits() == 128 {
-                let upper = Expr::shl(
-                    expr_const(64, 128),
-                    Expr::sub(
-                        Expr::shr(expr_const(64, 128), lhs.clone())?,
-                        Expr::shr(expr_const(64, 128), lhs.clone())?,
-                    )?,
-                )?;
-                let lower =
-                    Expr::sub(expr_const(0xffff_ffff_ffff_ffff, 128), Expr::sub(lhs, rhs)?)?;
-                self.operand_store(&mut block, &detail.operands[0], Expr::or(upper, lower)?)?;
-            }
+            self.operand_store(block, &detail.operands[0], result)?;
 
             block.index()
         };
@@ -2964,6 +3743,45 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn pmul(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_PMULHW => {
+                self.packed_binary(control_flow_graph, 16, |lhs, rhs| {
+                    let product = Expr::mul(Expr::sext(32, lhs)?, Expr::sext(32, rhs)?)?;
+                    Expr::trun(16, Expr::shr(product, expr_const(16, 32))?)
+                })
+            }
+            capstone::x86_insn::X86_INS_PMULHUW => {
+                self.packed_binary(control_flow_graph, 16, |lhs, rhs| {
+                    let product = Expr::mul(Expr::zext(32, lhs)?, Expr::zext(32, rhs)?)?;
+                    Expr::trun(16, Expr::shr(product, expr_const(16, 32))?)
+                })
+            }
+            capstone::x86_insn::X86_INS_PMULUDQ => {
+                self.packed_binary(control_flow_graph, 64, |lhs, rhs| {
+                    Expr::mul(
+                        Expr::zext(64, Expr::trun(32, lhs)?)?,
+                        Expr::zext(64, Expr::trun(32, rhs)?)?,
+                    )
+                })
+            }
+            capstone::x86_insn::X86_INS_PMULDQ => {
+                self.packed_binary(control_flow_graph, 64, |lhs, rhs| {
+                    Expr::mul(
+                        Expr::sext(64, Expr::trun(32, lhs)?)?,
+                        Expr::sext(64, Expr::trun(32, rhs)?)?,
+                    )
+                })
+            }
+            // pmullw and pmulld keep the low half of each product
+            _ => self.packed_binary(control_flow_graph, self.packed_lane_bits()?, Expr::mul),
+        }
+    }
+
+    pub fn por(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.vector_binary(control_flow_graph, |_, lhs, rhs| Expr::or(lhs, rhs))
+    }
+
     pub fn pop(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
@@ -2996,109 +3814,169 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn push(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
-
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+    pub fn psadbw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.packed_binary(control_flow_graph, 64, |lhs, rhs| {
+            let mut sum = expr_const(0, 16);
+            for i in 0..8 {
+                let l = Self::lane(&lhs, 8, i)?;
+                let r = Self::lane(&rhs, 8, i)?;
+                let difference = Expr::ite(
+                    Expr::cmpltu(l.clone(), r.clone())?,
+                    Expr::sub(r.clone(), l.clone())?,
+                    Expr::sub(l, r)?,
+                )?;
+                sum = Expr::add(sum, Expr::zext(16, difference)?)?;
+            }
+            Expr::zext(64, sum)
+        })
+    }
 
-            let value = self.operand_load(&mut block, &detail.operands[0])?;
+    pub fn pshufb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let bits = lhs.bits();
+            let lanes = (0..bits / 8)
+                .map(|i| {
+                    let index = Self::lane(&rhs, 8, i)?;
+                    let offset = Expr::shl(
+                        Expr::zext(
+                            bits,
+                            Expr::and(index.clone(), expr_const(bits as u64 / 8 - 1, 8))?,
+                        )?,
+                        expr_const(3, bits),
+                    )?;
+                    let selected = Expr::trun(8, Expr::shr(lhs.clone(), offset)?)?;
+                    // A set high bit in the index zeroes the byte
+                    Expr::ite(
+                        Expr::cmplts(index, expr_const(0, 8))?,
+                        expr_const(0, 8),
+                        selected,
+                    )
+                })
+                .collect::<Result<Vec<Expression>>>()?;
+            self.concat_lanes(block, lanes)
+        })
+    }
 
-            self.mode().push_value(&mut block, value)?;
+    pub fn pshufd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let order = self.immediate()?;
+        self.vector_binary(control_flow_graph, |block, _, src| {
+            let lanes = (0..4)
+                .map(|i| Self::lane(&src, 32, ((order >> (i * 2)) & 3) as usize))
+                .collect::<Result<Vec<Expression>>>()?;
+            self.concat_lanes(block, lanes)
+        })
+    }
 
-            block.index()
+    pub fn pshufw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let order = self.immediate()?;
+        // pshufhw shuffles the words of the high quadword, and pshuflw the low
+        let first = match self.instruction_id()? {
+            capstone::x86_insn::X86_INS_PSHUFHW => 4,
+            _ => 0,
         };
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
-
-        Ok(())
+        self.vector_binary(control_flow_graph, |block, _, src| {
+            let lanes = (0..8)
+                .map(|i| {
+                    if i >= first && i < first + 4 {
+                        let index = first + ((order >> ((i - first) * 2)) & 3) as usize;
+                        Self::lane(&src, 16, index)
+                    } else {
+                        Self::lane(&src, 16, i)
+                    }
+                })
+                .collect::<Result<Vec<Expression>>>()?;
+            self.concat_lanes(block, lanes)
+        })
     }
 
-    pub fn punpcklbw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let detail = self.details()?;
+    pub fn psll(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.packed_shift(control_flow_graph, |lane, count, in_range| {
+            let bits = lane.bits();
+            Expr::ite(in_range, Expr::shl(lane, count)?, expr_const(0, bits))
+        })
+    }
 
-        let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+    pub fn pslldq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let shift = self.immediate()?.min(16) * 8;
+        self.vector_binary(control_flow_graph, |_, _, src| {
+            let bits = src.bits();
+            Expr::shl(src, expr_const(shift, bits))
+        })
+    }
 
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+    pub fn psra(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.packed_shift(control_flow_graph, |lane, count, in_range| {
+            // Counts past the width of the lane fill it with the sign bit
+            let bits = lane.bits();
+            let count = Expr::ite(in_range, count, expr_const(bits as u64 - 1, bits))?;
+            Expr::sra(lane, count)
+        })
+    }
 
-            let result = self.temp(0, lhs.bits());
-            block.assign(result.clone(), expr_const(0, result.bits()));
-            for i in 0..lhs.bits() / 16 {
-                let ll = Expression::and(
-                    Expression::shr(lhs.clone(), expr_const(i as u64 * 8, lhs.bits()))?,
-                    expr_const(0xff, lhs.bits()),
-                )?;
-                let rr = Expression::and(
-                    Expression::shr(rhs.clone(), expr_const(i as u64 * 8, lhs.bits()))?,
-                    expr_const(0xff, lhs.bits()),
-                )?;
-                block.assign(
-                    result.clone(),
-                    Expression::or(
-                        result.clone().into(),
-                        Expression::shl(ll, expr_const(i as u64 * 16, lhs.bits()))?,
-                    )?,
-                );
-                block.assign(
-                    result.clone(),
-                    Expression::or(
-                        result.clone().into(),
-                        Expression::shl(rr, expr_const(i as u64 * 16 + 8, rhs.bits()))?,
-                    )?,
-                );
-            }
+    pub fn psrl(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.packed_shift(control_flow_graph, |lane, count, in_range| {
+            let bits = lane.bits();
+            Expr::ite(in_range, Expr::shr(lane, count)?, expr_const(0, bits))
+        })
+    }
 
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+    pub fn psrldq(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let shift = self.immediate()?.min(16) * 8;
+        self.vector_binary(control_flow_graph, |_, _, src| {
+            let bits = src.bits();
+            Expr::shr(src, expr_const(shift, bits))
+        })
+    }
 
-            block.index()
-        };
+    pub fn psub(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.packed_binary(control_flow_graph, self.packed_lane_bits()?, Expr::sub)
+    }
 
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(block_index)?;
+    pub fn psubs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |lhs, rhs| {
+            Self::saturate(
+                Expr::sub(
+                    Expr::sext(lane_bits * 2, lhs)?,
+                    Expr::sext(lane_bits * 2, rhs)?,
+                )?,
+                lane_bits,
+                true,
+            )
+        })
+    }
 
-        Ok(())
+    pub fn psubus(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let lane_bits = self.packed_lane_bits()?;
+        self.packed_binary(control_flow_graph, lane_bits, |lhs, rhs| {
+            Self::saturate(
+                Expr::sub(
+                    Expr::zext(lane_bits * 2, lhs)?,
+                    Expr::zext(lane_bits * 2, rhs)?,
+                )?,
+                lane_bits,
+                false,
+            )
+        })
     }
 
-    pub fn punpcklwd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn ptest(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
-            let mut block = control_flow_graph.new_block()?;
+            let block = control_flow_graph.new_block()?;
 
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+            let lhs = self.operand_load(block, &detail.operands[0])?;
+            let rhs = self.operand_load(block, &detail.operands[1])?;
 
-            let result = self.temp(0, lhs.bits());
-            block.assign(result.clone(), expr_const(0, result.bits()));
-            for i in 0..lhs.bits() / 32 {
-                let ll = Expression::and(
-                    Expression::shr(lhs.clone(), expr_const(i as u64 * 16, lhs.bits()))?,
-                    expr_const(0xffff, lhs.bits()),
-                )?;
-                let rr = Expression::and(
-                    Expression::shr(rhs.clone(), expr_const(i as u64 * 16, lhs.bits()))?,
-                    expr_const(0xffff, lhs.bits()),
-                )?;
-                block.assign(
-                    result.clone(),
-                    Expression::or(
-                        result.clone().into(),
-                        Expression::shl(ll, expr_const(i as u64 * 32, lhs.bits()))?,
-                    )?,
-                );
-                block.assign(
-                    result.clone(),
-                    Expression::or(
-                        result.clone().into(),
-                        Expression::shl(rr, expr_const(i as u64 * 32 + 16, lhs.bits()))?,
-                    )?,
-                );
-            }
+            let and = Expr::and(lhs, rhs.clone())?;
 
-            self.operand_store(&mut block, &detail.operands[0], result.into())?;
+            // CF is set when every bit set in rhs is also set in lhs
+            block.assign(scalar("CF", 1), Expr::cmpeq(and.clone(), rhs)?);
+            self.set_zf(block, and)?;
+            block.assign(scalar("OF", 1), expr_const(0, 1));
+            block.assign(scalar("PF", 1), expr_const(0, 1));
+            block.assign(scalar("SF", 1), expr_const(0, 1));
 
             block.index()
         };
@@ -3109,17 +3987,23 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn pxor(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+    pub fn punpckh(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.unpack(control_flow_graph, true)
+    }
+
+    pub fn punpckl(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.unpack(control_flow_graph, false)
+    }
+
+    pub fn push(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
         let block_index = {
             let mut block = control_flow_graph.new_block()?;
 
-            // get operands
-            let lhs = self.operand_load(&mut block, &detail.operands[0])?;
-            let rhs = self.operand_load(&mut block, &detail.operands[1])?;
+            let value = self.operand_load(&mut block, &detail.operands[0])?;
 
-            self.operand_store(&mut block, &detail.operands[0], Expr::xor(lhs, rhs)?)?;
+            self.mode().push_value(&mut block, value)?;
 
             block.index()
         };
@@ -3130,6 +4014,10 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn pxor(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.vector_binary(control_flow_graph, |_, lhs, rhs| Expr::xor(lhs, rhs))
+    }
+
     pub fn ret(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
@@ -3314,16 +4202,16 @@ impl<'s> Semantics<'s> {
         let block_index = {
             let block = control_flow_graph.new_block()?;
 
-            let ax = self.get_register(x86_reg::X86_REG_AX)?.get()?;
+            let ah = self.get_register(x86_reg::X86_REG_AH)?.get()?;
 
-            let cf = Expr::trun(1, ax.clone())?;
-            // let pf = Expr::trun(1, Expr::shr(2, ax.clone())?)?;
-            // let af = Expr::trun(1, Expr::shr(4, ax.clone())?)?;
-            let zf = Expr::trun(1, Expr::shr(expr_const(6, 16), ax.clone())?)?;
-            let sf = Expr::trun(1, Expr::shr(expr_const(7, 16), ax)?)?;
+            let cf = Expr::trun(1, ah.clone())?;
+            let pf = Expr::trun(1, Expr::shr(ah.clone(), expr_const(2, 8))?)?;
+            // let af = Expr::trun(1, Expr::shr(ah.clone(), expr_const(4, 8))?)?;
+            let zf = Expr::trun(1, Expr::shr(ah.clone(), expr_const(6, 8))?)?;
+            let sf = Expr::trun(1, Expr::shr(ah, expr_const(7, 8))?)?;
 
             block.assign(scalar("CF", 1), cf);
-            // block.assign(scalar("PF", 1), pf);
+            block.assign(scalar("PF", 1), pf);
             // block.assign(scalar("AF", 1), af);
             block.assign(scalar("ZF", 1), zf);
             block.assign(scalar("SF", 1), sf);
@@ -3421,136 +4309,26 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
-    pub fn scasb(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let al = self.get_register(x86_reg::X86_REG_AL)?;
-        let di = self.get_register(x86_reg::X86_REG_DI)?.get_full()?;
-
-        let head_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let temp = self.temp(0, 8);
-            block.load(temp.clone(), di.get()?);
-            let expr = Expr::sub(al.get()?, temp.clone().into())?;
-
-            // calculate flags
-            self.set_zf(&mut block, expr.clone())?;
-            self.set_sf(&mut block, expr.clone())?;
-            self.set_of(&mut block, expr.clone(), al.get()?, temp.into())?;
-            self.set_cf(&mut block, expr, al.get()?)?;
-
-            block.index()
-        };
-
-        let inc_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            di.set(
-                &mut block,
-                Expr::add(di.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
-
-            block.index()
-        };
-
-        let dec_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            di.set(
-                &mut block,
-                Expr::sub(di.get()?, expr_const(1, self.mode().bits()))?,
-            )?;
-
-            block.index()
-        };
-
-        let tail_index = { control_flow_graph.new_block()?.index() };
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            inc_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
-        )?;
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            dec_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
-        )?;
-
-        control_flow_graph.unconditional_edge(inc_index, tail_index)?;
-        control_flow_graph.unconditional_edge(dec_index, tail_index)?;
-
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
-
-        Ok(())
-    }
+    pub fn scas(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let detail = self.details()?;
 
-    pub fn scasw(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
-        let ax = self.get_register(x86_reg::X86_REG_AX)?;
         let di = self.get_register(x86_reg::X86_REG_DI)?.get_full()?;
 
-        let head_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            // get operands
-            let temp = self.temp(0, 16);
-            block.load(temp.clone(), di.get()?);
-            let expr = Expr::sub(ax.get()?, temp.clone().into())?;
-
-            // calculate flags
-            self.set_zf(&mut block, expr.clone())?;
-            self.set_sf(&mut block, expr.clone())?;
-            self.set_of(&mut block, expr.clone(), ax.get()?, temp.clone().into())?;
-            self.set_cf(&mut block, expr, temp.into())?;
-
-            block.index()
-        };
-
-        let inc_index = {
-            let mut block = control_flow_graph.new_block()?;
-
-            di.set(
-                &mut block,
-                Expr::add(di.get()?, expr_const(2, self.mode().bits()))?,
-            )?;
+        let (head_index, bits) = {
+            let block = control_flow_graph.new_block()?;
 
-            block.index()
-        };
+            let lhs = self.operand_load(block, &detail.operands[0])?;
+            let bits = lhs.bits();
 
-        let dec_index = {
-            let mut block = control_flow_graph.new_block()?;
+            let rhs = self.temp(0, bits);
+            block.load(rhs.clone(), di.get()?);
 
-            di.set(
-                &mut block,
-                Expr::sub(di.get()?, expr_const(2, self.mode().bits()))?,
-            )?;
+            self.compare_flags(block, lhs, rhs.into())?;
 
-            block.index()
+            (block.index(), bits)
         };
 
-        let tail_index = { control_flow_graph.new_block()?.index() };
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            inc_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
-        )?;
-
-        control_flow_graph.conditional_edge(
-            head_index,
-            dec_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
-        )?;
-
-        control_flow_graph.unconditional_edge(inc_index, tail_index)?;
-        control_flow_graph.unconditional_edge(dec_index, tail_index)?;
-
-        control_flow_graph.set_entry(head_index)?;
-        control_flow_graph.set_exit(tail_index)?;
-
-        Ok(())
+        self.string_advance(control_flow_graph, head_index, &[di], bits / 8)
     }
 
     pub fn setcc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
@@ -3793,6 +4571,28 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn shufp(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let order = self.immediate()?;
+        let id = self.instruction_id()?;
+        // The low half of the result is selected from lhs, and the high half
+        // from rhs
+        self.vector_binary(control_flow_graph, |block, lhs, rhs| {
+            let lanes = match id {
+                capstone::x86_insn::X86_INS_SHUFPD => vec![
+                    Self::lane(&lhs, 64, (order & 1) as usize)?,
+                    Self::lane(&rhs, 64, ((order >> 1) & 1) as usize)?,
+                ],
+                _ => vec![
+                    Self::lane(&lhs, 32, (order & 3) as usize)?,
+                    Self::lane(&lhs, 32, ((order >> 2) & 3) as usize)?,
+                    Self::lane(&rhs, 32, ((order >> 4) & 3) as usize)?,
+                    Self::lane(&rhs, 32, ((order >> 6) & 3) as usize)?,
+                ],
+            };
+            self.concat_lanes(block, lanes)
+        })
+    }
+
     pub fn stc(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let block_index = {
             let block = control_flow_graph.new_block()?;
@@ -3837,58 +4637,17 @@ impl<'s> Semantics<'s> {
 
         let di = self.get_register(x86_reg::X86_REG_DI)?.get_full()?;
 
-        // create a block for this instruction
-        let (block_index, bits) = {
-            let mut block = control_flow_graph.new_block()?;
+        let (head_index, bits) = {
+            let block = control_flow_graph.new_block()?;
 
-            let src = self.operand_load(&mut block, &detail.operands[1])?;
+            let src = self.operand_load(block, &detail.operands[1])?;
             let bits = src.bits();
-            self.operand_store(&mut block, &detail.operands[0], src)?;
-
-            (block.index(), bits as u64)
-        };
-
-        let inc_index = {
-            let mut inc_block = control_flow_graph.new_block()?;
-
-            di.set(
-                &mut inc_block,
-                Expr::add(di.get()?, expr_const(bits / 8, self.mode().bits()))?,
-            )?;
-
-            inc_block.index()
-        };
-
-        let dec_index = {
-            let mut dec_block = control_flow_graph.new_block()?;
-
-            di.set(
-                &mut dec_block,
-                Expr::sub(di.get()?, expr_const(bits / 8, self.mode().bits()))?,
-            )?;
+            self.operand_store(block, &detail.operands[0], src)?;
 
-            dec_block.index()
+            (block.index(), bits)
         };
 
-        let terminating_index = { control_flow_graph.new_block()?.index() };
-
-        control_flow_graph.conditional_edge(
-            block_index,
-            inc_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(0, 1))?,
-        )?;
-        control_flow_graph.conditional_edge(
-            block_index,
-            dec_index,
-            Expr::cmpeq(expr_scalar("DF", 1), expr_const(1, 1))?,
-        )?;
-        control_flow_graph.unconditional_edge(inc_index, terminating_index)?;
-        control_flow_graph.unconditional_edge(dec_index, terminating_index)?;
-
-        control_flow_graph.set_entry(block_index)?;
-        control_flow_graph.set_exit(terminating_index)?;
-
-        Ok(())
+        self.string_advance(control_flow_graph, head_index, &[di], bits / 8)
     }
 
     pub fn sub(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
@@ -3927,6 +4686,10 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn subs(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        self.sse_scalar_arithmetic(control_flow_graph, Expr::fsub)
+    }
+
     pub fn syscall(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         // create a block for this instruction
         let block_index = {
@@ -4022,6 +4785,49 @@ impl<'s> Semantics<'s> {
         Ok(())
     }
 
+    pub fn vzeroupper(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
+        let registers = [
+            x86_reg::X86_REG_YMM0,
+            x86_reg::X86_REG_YMM1,
+            x86_reg::X86_REG_YMM2,
+            x86_reg::X86_REG_YMM3,
+            x86_reg::X86_REG_YMM4,
+            x86_reg::X86_REG_YMM5,
+            x86_reg::X86_REG_YMM6,
+            x86_reg::X86_REG_YMM7,
+            x86_reg::X86_REG_YMM8,
+            x86_reg::X86_REG_YMM9,
+            x86_reg::X86_REG_YMM10,
+            x86_reg::X86_REG_YMM11,
+            x86_reg::X86_REG_YMM12,
+            x86_reg::X86_REG_YMM13,
+            x86_reg::X86_REG_YMM14,
+            x86_reg::X86_REG_YMM15,
+        ];
+        // Only ymm0-ymm7 are addressable outside of 64-bit mode
+        let count = match *self.mode() {
+            Mode::X86 => 8,
+            Mode::Amd64 => 16,
+        };
+
+        let block_index = {
+            let block = control_flow_graph.new_block()?;
+
+            for register in registers[0..count].iter() {
+                let register = self.get_register(*register)?;
+                let value = Expr::zext(256, Expr::trun(128, register.get()?)?)?;
+                register.set(block, value)?;
+            }
+
+            block.index()
+        };
+
+        control_flow_graph.set_entry(block_index)?;
+        control_flow_graph.set_exit(block_index)?;
+
+        Ok(())
+    }
+
     pub fn xadd(&self, control_flow_graph: &mut ControlFlowGraph) -> Result<()> {
         let detail = self.details()?;
 
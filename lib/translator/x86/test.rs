@@ -63,6 +63,26 @@ fn step_to(mut driver: Driver, target_address: u64) -> Driver {
     }
 }
 
+/// Scalars for an empty x87 register stack, rounding to nearest.
+fn x87_scalars() -> Vec<(&'static str, il::Constant)> {
+    let mut scalars = vec![("fpu_cw", il::const_(0x37f, 16))];
+    for name in &["st0", "st1", "st2", "st3", "st4", "st5", "st6", "st7"] {
+        scalars.push((name, il::const_(0, 64)));
+    }
+    scalars
+}
+
+/// Scalars for zeroed ymm registers, as read by `vzeroupper`.
+fn ymm_scalars() -> Vec<(&'static str, il::Constant)> {
+    [
+        "ymm0", "ymm1", "ymm2", "ymm3", "ymm4", "ymm5", "ymm6", "ymm7", "ymm8", "ymm9", "ymm10",
+        "ymm11", "ymm12", "ymm13", "ymm14", "ymm15",
+    ]
+    .iter()
+    .map(|name| (*name, il::const_(0, 256)))
+    .collect()
+}
+
 fn mk128const(lo: u64, hi: u64) -> il::Constant {
     eval(
         &il::Expression::or(
@@ -78,6 +98,236 @@ fn mk128const(lo: u64, hi: u64) -> il::Constant {
     .unwrap()
 }
 
+/// Zero-extends an xmm value to the width of its ymm register.
+fn ymm(value: il::Constant) -> il::Constant {
+    value.zext(256).unwrap()
+}
+
+#[test]
+fn cvtsi2sd() {
+    // cvtsi2sd xmm0, eax
+    // divsd xmm0, xmm1
+    // cvttsd2si ecx, xmm0
+    // ucomisd xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![
+        0xf2, 0x0f, 0x2a, 0xc0, 0xf2, 0x0f, 0x5e, 0xc1, 0xf2, 0x0f, 0x2c, 0xc8, 0x66, 0x0f, 0x2e,
+        0xc1, 0x90,
+    ];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("rax", il::const_(7, 64)),
+            ("rcx", il::const_(0xffff_ffff_ffff_ffff, 64)),
+            (
+                "ymm0",
+                ymm(mk128const(0x11111111_11111111, 0x22222222_22222222)),
+            ),
+            ("ymm1", il::const_(2_f64.to_bits(), 64).zext(256).unwrap()),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x10);
+
+    let ymm0 = driver.state().get_scalar("ymm0").unwrap();
+    assert_eq!(
+        ymm0.trun(64).unwrap().value_u64().unwrap(),
+        3.5_f64.to_bits()
+    );
+    assert_eq!(
+        eval(&il::Expression::shr(ymm0.clone().into(), il::expr_const(64, 256)).unwrap())
+            .unwrap()
+            .trun(64)
+            .unwrap()
+            .value_u64()
+            .unwrap(),
+        0x11111111_11111111
+    );
+    assert_eq!(
+        driver.state().get_scalar("rcx").unwrap().value_u64(),
+        Some(3)
+    );
+    assert!(driver.state().get_scalar("CF").unwrap().is_zero());
+    assert!(driver.state().get_scalar("PF").unwrap().is_zero());
+    assert!(driver.state().get_scalar("ZF").unwrap().is_zero());
+}
+
+#[test]
+fn fcompp() {
+    // fld1
+    // fld1
+    // fcompp
+    // fnstsw ax
+    // sahf
+    // nop
+    let bytes: Vec<u8> = vec![0xd9, 0xe8, 0xd9, 0xe8, 0xde, 0xd9, 0xdf, 0xe0, 0x9e, 0x90];
+
+    let mut scalars = x87_scalars();
+    scalars.push(("rax", il::const_(0, 64)));
+
+    let driver = init_amd64_driver(bytes, scalars, Memory::new(Endian::Little));
+
+    let driver = step_to(driver, 0x9);
+
+    assert_eq!(
+        driver.state().get_scalar("rax").unwrap().value_u64(),
+        Some(0x4000)
+    );
+    assert!(driver.state().get_scalar("ZF").unwrap().is_one());
+    assert!(driver.state().get_scalar("PF").unwrap().is_zero());
+    assert!(driver.state().get_scalar("CF").unwrap().is_zero());
+}
+
+#[test]
+fn fistp() {
+    // fld qword ptr [rax]
+    // fmul qword ptr [rax + 8]
+    // fistp dword ptr [rax + 16]
+    // nop
+    let bytes: Vec<u8> = vec![0xdd, 0x00, 0xdc, 0x48, 0x08, 0xdb, 0x58, 0x10, 0x90];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1000, il::const_(2.5_f64.to_bits(), 64))
+        .unwrap();
+    memory
+        .store(0x1008, il::const_(3_f64.to_bits(), 64))
+        .unwrap();
+
+    // Round to nearest
+    let mut scalars = x87_scalars();
+    scalars.push(("rax", il::const_(0x1000, 64)));
+
+    let driver = init_amd64_driver(bytes.clone(), scalars.clone(), memory.clone());
+    let driver = step_to(driver, 0x8);
+    assert_eq!(
+        driver
+            .state()
+            .memory()
+            .load(0x1010, 32)
+            .unwrap()
+            .unwrap()
+            .value_u64(),
+        Some(8)
+    );
+
+    // Round toward zero
+    scalars.push(("fpu_cw", il::const_(0xf7f, 16)));
+
+    let driver = init_amd64_driver(bytes, scalars, memory);
+    let driver = step_to(driver, 0x8);
+    assert_eq!(
+        driver
+            .state()
+            .memory()
+            .load(0x1010, 32)
+            .unwrap()
+            .unwrap()
+            .value_u64(),
+        Some(7)
+    );
+}
+
+#[test]
+fn fld_extended() {
+    // fld dword ptr [rax]
+    // fstp tbyte ptr [rax + 8]
+    // fld tbyte ptr [rax + 8]
+    // fchs
+    // fstp qword ptr [rax + 24]
+    // nop
+    let bytes: Vec<u8> = vec![
+        0xd9, 0x00, 0xdb, 0x78, 0x08, 0xdb, 0x68, 0x08, 0xd9, 0xe0, 0xdd, 0x58, 0x18, 0x90,
+    ];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1000, il::const_(u64::from(1.5_f32.to_bits()), 32))
+        .unwrap();
+
+    let mut scalars = x87_scalars();
+    scalars.push(("rax", il::const_(0x1000, 64)));
+
+    let driver = init_amd64_driver(bytes, scalars, memory);
+
+    let driver = step_to(driver, 0xd);
+
+    let memory = driver.state().memory();
+    assert_eq!(
+        memory.load(0x1008, 64).unwrap().unwrap().value_u64(),
+        Some(0xc000_0000_0000_0000)
+    );
+    assert_eq!(
+        memory.load(0x1010, 16).unwrap().unwrap().value_u64(),
+        Some(0x3fff)
+    );
+    assert_eq!(
+        memory.load(0x1018, 64).unwrap().unwrap().value_u64(),
+        Some((-1.5_f64).to_bits())
+    );
+}
+
+#[test]
+fn fsubrp() {
+    // fld qword ptr [rax]
+    // fld qword ptr [rax + 8]
+    // fsubrp st(1), st(0)
+    // fstp qword ptr [rax + 16]
+    // nop
+    let bytes: Vec<u8> = vec![
+        0xdd, 0x00, 0xdd, 0x40, 0x08, 0xde, 0xe1, 0xdd, 0x58, 0x10, 0x90,
+    ];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1000, il::const_(10_f64.to_bits(), 64))
+        .unwrap();
+    memory
+        .store(0x1008, il::const_(4_f64.to_bits(), 64))
+        .unwrap();
+
+    let mut scalars = x87_scalars();
+    scalars.push(("rax", il::const_(0x1000, 64)));
+
+    let driver = init_amd64_driver(bytes, scalars, memory);
+
+    let driver = step_to(driver, 0xa);
+
+    assert_eq!(
+        driver
+            .state()
+            .memory()
+            .load(0x1010, 64)
+            .unwrap()
+            .unwrap()
+            .value_u64(),
+        Some((-6_f64).to_bits())
+    );
+}
+
+#[test]
+fn fucomip() {
+    // fld1
+    // fldz
+    // fucomip st(0), st(1)
+    // nop
+    let bytes: Vec<u8> = vec![0xd9, 0xe8, 0xd9, 0xee, 0xdf, 0xe9, 0x90];
+
+    let driver = init_amd64_driver(bytes, x87_scalars(), Memory::new(Endian::Little));
+
+    let driver = step_to(driver, 0x6);
+
+    assert!(driver.state().get_scalar("CF").unwrap().is_one());
+    assert!(driver.state().get_scalar("ZF").unwrap().is_zero());
+    assert!(driver.state().get_scalar("PF").unwrap().is_zero());
+    assert_eq!(
+        driver.state().get_scalar("st0").unwrap().value_u64(),
+        Some(1_f64.to_bits())
+    );
+}
+
 #[test]
 fn lea() {
     // lea ecx, [rax - 0x3]
@@ -88,6 +338,48 @@ fn lea() {
     let _ = translator.translate_block(&bytes, 0).unwrap();
 }
 
+#[test]
+fn lods() {
+    // lodsw
+    // lodsq
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0xad, 0x48, 0xad, 0x90];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory.store(0x1000, il::const_(0xbeef, 16)).unwrap();
+    memory
+        .store(0x1002, il::const_(0x01234567_89abcdef, 64))
+        .unwrap();
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("DF", il::const_(0, 1)),
+            ("rax", il::const_(0xffffffff_ffffffff, 64)),
+            ("rsi", il::const_(0x1000, 64)),
+        ],
+        memory,
+    );
+
+    let driver = step_to(driver, 0x2);
+
+    assert_eq!(
+        driver.state().get_scalar("rax").unwrap(),
+        &il::const_(0xffffffff_ffffbeef, 64)
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("rax").unwrap(),
+        &il::const_(0x01234567_89abcdef, 64)
+    );
+    assert_eq!(
+        driver.state().get_scalar("rsi").unwrap(),
+        &il::const_(0x100a, 64)
+    );
+}
+
 #[test]
 fn movd() {
     // movd xmm1, esi
@@ -97,7 +389,10 @@ fn movd() {
     let driver = init_amd64_driver(
         bytes.clone(),
         vec![
-            ("xmm1", mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            (
+                "ymm1",
+                ymm(mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            ),
             ("rsi", il::const_(0x11112222_deadbeef, 64)),
         ],
         Memory::new(Endian::Little),
@@ -105,12 +400,12 @@ fn movd() {
 
     let driver = step_to(driver, 0x4);
 
-    assert_eq!(driver.state().get_scalar("xmm1").unwrap().bits(), 128);
+    assert_eq!(driver.state().get_scalar("ymm1").unwrap().bits(), 256);
 
     assert!(eval(
         &il::Expression::cmpeq(
-            driver.state().get_scalar("xmm1").unwrap().clone().into(),
-            mk128const(0x00000000_00000000, 0x00000000_deadbeef).into()
+            driver.state().get_scalar("ymm1").unwrap().clone().into(),
+            ymm(mk128const(0x00000000_00000000, 0x00000000_deadbeef)).into()
         )
         .unwrap()
     )
@@ -118,6 +413,183 @@ fn movd() {
     .is_one());
 }
 
+#[test]
+fn movsd() {
+    // movsd xmm0, qword ptr [rax]
+    // addss xmm1, xmm2
+    // cvtss2sd xmm2, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![
+        0xf2, 0x0f, 0x10, 0x00, 0xf3, 0x0f, 0x58, 0xca, 0xf3, 0x0f, 0x5a, 0xd1, 0x90,
+    ];
+
+    let mut memory = Memory::new(Endian::Little);
+    memory
+        .store(0x1000, il::const_(1.25_f64.to_bits(), 64))
+        .unwrap();
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("rax", il::const_(0x1000, 64)),
+            (
+                "ymm0",
+                ymm(mk128const(0xffffffff_ffffffff, 0xffffffff_ffffffff)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(
+                    0x11111111_11111111,
+                    0x22222222_00000000 | 0x4010_0000,
+                )),
+            ),
+            (
+                "ymm2",
+                ymm(mk128const(
+                    0x33333333_33333333,
+                    0x44444444_00000000 | 0x3fc0_0000,
+                )),
+            ),
+        ],
+        memory,
+    );
+
+    let driver = step_to(driver, 0xc);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &il::const_(1.25_f64.to_bits(), 64).zext(256).unwrap()
+    );
+    // 2.25 + 1.5 in the low single, with the upper bits preserved
+    assert_eq!(
+        driver.state().get_scalar("ymm1").unwrap(),
+        &ymm(mk128const(
+            0x11111111_11111111,
+            0x22222222_00000000 | 0x4070_0000
+        ))
+    );
+    assert_eq!(
+        driver.state().get_scalar("ymm2").unwrap(),
+        &ymm(mk128const(0x33333333_33333333, 3.75_f64.to_bits()))
+    );
+}
+
+#[test]
+fn packuswb() {
+    // packuswb xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x67, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0xffff0000_ffff0000, 0x00ffffff_0000ff00))
+    );
+}
+
+#[test]
+fn paddq() {
+    // paddq xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0xd4, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x8200fe81_acf13568, 0xffdebd9d_00010100))
+    );
+}
+
+#[test]
+fn paddusb() {
+    // paddusb xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0xdc, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x81fffe81_acf0ffff, 0xffdebd9c_ffffffff))
+    );
+}
+
+#[test]
+fn palignr() {
+    // palignr xmm0, xmm1, 5
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x3a, 0x0f, 0xc1, 0x05, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x6);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x9800ff80_0101017f, 0x809abcde_f0010203))
+    );
+}
+
 #[test]
 fn pcmpeqd() {
     // pcmeqd xmm0, xmm1
@@ -127,8 +599,14 @@ fn pcmpeqd() {
     let driver = init_amd64_driver(
         bytes.clone(),
         vec![
-            ("xmm0", mk128const(0x00000000_11111111, 0x22222222_33333333)),
-            ("xmm1", mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            (
+                "ymm0",
+                ymm(mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            ),
         ],
         Memory::new(Endian::Little),
     );
@@ -137,8 +615,8 @@ fn pcmpeqd() {
 
     assert!(eval(
         &il::Expression::cmpeq(
-            driver.state().get_scalar("xmm0").unwrap().clone().into(),
-            mk128const(0xffffffff_ffffffff, 0xffffffff_ffffffff).into()
+            driver.state().get_scalar("ymm0").unwrap().clone().into(),
+            ymm(mk128const(0xffffffff_ffffffff, 0xffffffff_ffffffff)).into()
         )
         .unwrap()
     )
@@ -148,8 +626,14 @@ fn pcmpeqd() {
     let driver = init_amd64_driver(
         bytes,
         vec![
-            ("xmm0", mk128const(0x00000000_11111111, 0x22322222_33333333)),
-            ("xmm1", mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            (
+                "ymm0",
+                ymm(mk128const(0x00000000_11111111, 0x22322222_33333333)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            ),
         ],
         Memory::new(Endian::Little),
     );
@@ -158,8 +642,8 @@ fn pcmpeqd() {
 
     assert!(eval(
         &il::Expression::cmpeq(
-            driver.state().get_scalar("xmm0").unwrap().clone().into(),
-            mk128const(0xffffffff_ffffffff, 0x00000000_ffffffff).into()
+            driver.state().get_scalar("ymm0").unwrap().clone().into(),
+            ymm(mk128const(0xffffffff_ffffffff, 0x00000000_ffffffff)).into()
         )
         .unwrap()
     )
@@ -176,8 +660,14 @@ fn pcmpeqb() {
     let driver = init_amd64_driver(
         bytes.clone(),
         vec![
-            ("xmm0", mk128const(0x00000000_11111111, 0x22222222_33333333)),
-            ("xmm1", mk128const(0x00000000_11111111, 0x55555555_00113322)),
+            (
+                "ymm0",
+                ymm(mk128const(0x00000000_11111111, 0x22222222_33333333)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x00000000_11111111, 0x55555555_00113322)),
+            ),
         ],
         Memory::new(Endian::Little),
     );
@@ -186,8 +676,8 @@ fn pcmpeqb() {
 
     assert!(eval(
         &il::Expression::cmpeq(
-            driver.state().get_scalar("xmm0").unwrap().clone().into(),
-            mk128const(0xffffffff_ffffffff, 0x00000000_0000ff00).into()
+            driver.state().get_scalar("ymm0").unwrap().clone().into(),
+            ymm(mk128const(0xffffffff_ffffffff, 0x00000000_0000ff00)).into()
         )
         .unwrap()
     )
@@ -195,6 +685,130 @@ fn pcmpeqb() {
     .is_one());
 }
 
+#[test]
+fn pcmpgtb() {
+    // pcmpgtb xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x64, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x000000ff_ffffffff, 0x00000000_ff0000ff))
+    );
+}
+
+#[test]
+fn pinsrw() {
+    // pextrw eax, xmm0, 5
+    // pinsrw xmm1, eax, 3
+    // nop
+    let bytes: Vec<u8> = vec![
+        0x66, 0x0f, 0xc5, 0xc0, 0x05, 0x66, 0x0f, 0xc4, 0xc8, 0x03, 0x90,
+    ];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("rax", il::const_(0xffffffff_ffffffff, 64)),
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0xa);
+
+    assert_eq!(
+        driver.state().get_scalar("rax").unwrap(),
+        &il::const_(0x1234, 64)
+    );
+    assert_eq!(
+        driver.state().get_scalar("ymm1").unwrap(),
+        &ymm(mk128const(0x01017f80_9abcdef0, 0x12340304_ff0180ff))
+    );
+}
+
+#[test]
+fn pmaddwd() {
+    // pmaddwd xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0xf5, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x3ec17f7f_eda1c6b0, 0xff2d8c18_3f7f02fe))
+    );
+}
+
+#[test]
+fn pminsw() {
+    // pminsw xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0xea, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x80ff7f01_9abcdef0, 0xfedcba98_ff018001))
+    );
+}
+
 #[test]
 fn pmovmskb() {
     // pcmeqb xmm0, xmm1
@@ -203,7 +817,10 @@ fn pmovmskb() {
 
     let driver = init_amd64_driver(
         bytes.clone(),
-        vec![("xmm4", mk128const(0x00ff00ff_00000000, 0xffffffff_ff00ff00))],
+        vec![(
+            "ymm4",
+            ymm(mk128const(0x00ff00ff_00000000, 0xffffffff_ff00ff00)),
+        )],
         Memory::new(Endian::Little),
     );
 
@@ -220,6 +837,394 @@ fn pmovmskb() {
     );
 }
 
+#[test]
+fn pmovzxbw() {
+    // pmovzxbw xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x38, 0x30, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x5);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x00010002_00030004, 0x00ff0001_008000ff))
+    );
+}
+
+#[test]
+fn pshufb() {
+    // pshufb xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x38, 0x00, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x808f0102_03040506, 0x07080900_0a0b0c0d)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x5);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x000080ff_0098badc, 0xfe785601_3412017f))
+    );
+}
+
+#[test]
+fn pshufd() {
+    // pshufd xmm0, xmm1, 0x1b
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x70, 0xc1, 0x1b, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x5);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0xff0180ff_01020304, 0x9abcdef0_01017f80))
+    );
+}
+
+#[test]
+fn psraw() {
+    // psraw xmm0, 3
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x71, 0xe0, 0x03, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![(
+            "ymm0",
+            ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+        )],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x5);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0xf01f0fe0_02460acf, 0xffdbf753_001ff000))
+    );
+}
+
+#[test]
+fn psubb() {
+    // psubb xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0xf8, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x7ffe0081_78787888, 0xfddab794_01fe0002))
+    );
+}
+
+#[test]
+fn psubsb() {
+    // psubsb xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0xe8, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x80fe007f_7878787f, 0xfddab794_01fe0002))
+    );
+}
+
+#[test]
+fn ptest() {
+    // ptest xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x38, 0x17, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x80000000_00000000, 0x00000000_00ff0000)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x5);
+
+    assert!(driver.state().get_scalar("CF").unwrap().is_one());
+    assert!(driver.state().get_scalar("ZF").unwrap().is_zero());
+}
+
+#[test]
+fn punpckhbw() {
+    // punpckhbw xmm0, xmm1
+    // nop
+    let bytes: Vec<u8> = vec![0x66, 0x0f, 0x68, 0xc1, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            (
+                "ymm0",
+                ymm(mk128const(0x80ff7f01_12345678, 0xfedcba98_00ff8001)),
+            ),
+            (
+                "ymm1",
+                ymm(mk128const(0x01017f80_9abcdef0, 0x01020304_ff0180ff)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x018001ff_7f7f8001, 0x9a12bc34_de56f078))
+    );
+}
+
+#[test]
+fn rep_movsb() {
+    // rep movsb
+    // rep movsb
+    // nop
+    let bytes: Vec<u8> = vec![0xf3, 0xa4, 0xf3, 0xa4, 0x90];
+
+    let mut memory = Memory::new(Endian::Little);
+    for (i, byte) in b"hello".iter().enumerate() {
+        memory
+            .store(0x1000 + i as u64, il::const_(*byte as u64, 8))
+            .unwrap();
+    }
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("DF", il::const_(0, 1)),
+            ("rcx", il::const_(5, 64)),
+            ("rsi", il::const_(0x1000, 64)),
+            ("rdi", il::const_(0x2000, 64)),
+        ],
+        memory,
+    );
+
+    // The second rep movsb, with rcx zero, does nothing
+    let driver = step_to(driver, 0x4);
+
+    for (i, byte) in b"hello".iter().enumerate() {
+        assert_eq!(
+            driver
+                .state()
+                .memory()
+                .load(0x2000 + i as u64, 8)
+                .unwrap()
+                .unwrap(),
+            il::const_(*byte as u64, 8)
+        );
+    }
+    assert!(driver.state().get_scalar("rcx").unwrap().is_zero());
+    assert_eq!(
+        driver.state().get_scalar("rsi").unwrap(),
+        &il::const_(0x1005, 64)
+    );
+    assert_eq!(
+        driver.state().get_scalar("rdi").unwrap(),
+        &il::const_(0x2005, 64)
+    );
+}
+
+#[test]
+fn rep_stosq() {
+    // std
+    // rep stosq
+    // nop
+    let bytes: Vec<u8> = vec![0xfd, 0xf3, 0x48, 0xab, 0x90];
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("DF", il::const_(0, 1)),
+            ("rax", il::const_(0x11223344_55667788, 64)),
+            ("rcx", il::const_(2, 64)),
+            ("rdi", il::const_(0x2008, 64)),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x4);
+
+    // With DF set, rdi moves backwards
+    for address in &[0x2000, 0x2008] {
+        assert_eq!(
+            driver.state().memory().load(*address, 64).unwrap().unwrap(),
+            il::const_(0x11223344_55667788, 64)
+        );
+    }
+    assert!(driver.state().get_scalar("rcx").unwrap().is_zero());
+    assert_eq!(
+        driver.state().get_scalar("rdi").unwrap(),
+        &il::const_(0x1ff8, 64)
+    );
+}
+
+#[test]
+fn repe_cmpsb() {
+    // repe cmpsb
+    // nop
+    let bytes: Vec<u8> = vec![0xf3, 0xa6, 0x90];
+
+    let mut memory = Memory::new(Endian::Little);
+    for (i, (lhs, rhs)) in b"abcd".iter().zip(b"abXd".iter()).enumerate() {
+        memory
+            .store(0x1000 + i as u64, il::const_(*lhs as u64, 8))
+            .unwrap();
+        memory
+            .store(0x2000 + i as u64, il::const_(*rhs as u64, 8))
+            .unwrap();
+    }
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("DF", il::const_(0, 1)),
+            ("rcx", il::const_(4, 64)),
+            ("rsi", il::const_(0x1000, 64)),
+            ("rdi", il::const_(0x2000, 64)),
+        ],
+        memory,
+    );
+
+    let driver = step_to(driver, 0x2);
+
+    // The loop stops after comparing 'c' with 'X'
+    assert_eq!(
+        driver.state().get_scalar("rcx").unwrap(),
+        &il::const_(1, 64)
+    );
+    assert_eq!(
+        driver.state().get_scalar("rsi").unwrap(),
+        &il::const_(0x1003, 64)
+    );
+    assert!(driver.state().get_scalar("ZF").unwrap().is_zero());
+    assert!(driver.state().get_scalar("CF").unwrap().is_zero());
+}
+
+#[test]
+fn repne_scasb() {
+    // repne scasb
+    // nop
+    let bytes: Vec<u8> = vec![0xf2, 0xae, 0x90];
+
+    let mut memory = Memory::new(Endian::Little);
+    for (i, byte) in b"abc\0xyz".iter().enumerate() {
+        memory
+            .store(0x1000 + i as u64, il::const_(*byte as u64, 8))
+            .unwrap();
+    }
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("DF", il::const_(0, 1)),
+            ("rax", il::const_(0, 64)),
+            ("rcx", il::const_(0xffffffff_ffffffff, 64)),
+            ("rdi", il::const_(0x1000, 64)),
+        ],
+        memory,
+    );
+
+    let driver = step_to(driver, 0x2);
+
+    // The strlen idiom, !rcx - 1 is the length of the string
+    assert_eq!(
+        driver.state().get_scalar("rcx").unwrap(),
+        &il::const_(0xffffffff_fffffffb, 64)
+    );
+    assert_eq!(
+        driver.state().get_scalar("rdi").unwrap(),
+        &il::const_(0x1004, 64)
+    );
+    assert!(driver.state().get_scalar("ZF").unwrap().is_one());
+}
+
 #[test]
 fn rol() {
     // rol rax, 0x11
@@ -259,3 +1264,96 @@ fn ror() {
         &il::const_(0xbfeffffff690, 64)
     );
 }
+
+#[test]
+fn vpcmpeqb() {
+    // vpcmpeqb ymm1, ymm0, ymmword ptr [rax]
+    // vpmovmskb ecx, ymm1
+    // vzeroupper
+    // nop
+    let bytes: Vec<u8> = vec![
+        0xc5, 0xfd, 0x74, 0x08, 0xc5, 0xfd, 0xd7, 0xc9, 0xc5, 0xf8, 0x77, 0x90,
+    ];
+
+    let mut memory = Memory::new(Endian::Little);
+    for i in 0..32 {
+        let byte = if i == 5 || i == 20 { 0 } else { 0x41 };
+        memory.store(0x1000 + i, il::const_(byte, 8)).unwrap();
+    }
+
+    let mut scalars = ymm_scalars();
+    scalars.push(("rax", il::const_(0x1000, 64)));
+    scalars.push(("rcx", il::const_(0xffffffff_ffffffff, 64)));
+
+    let driver = init_amd64_driver(bytes, scalars, memory);
+
+    let driver = step_to(driver, 0xb);
+
+    assert_eq!(
+        driver.state().get_scalar("rcx").unwrap(),
+        &il::const_((1 << 5) | (1 << 20), 64)
+    );
+    // vzeroupper clears the match in the upper half of ymm1
+    assert_eq!(
+        driver.state().get_scalar("ymm1").unwrap(),
+        &il::const_(0xff << 40, 256)
+    );
+}
+
+#[test]
+fn vpxor() {
+    // vpxor xmm0, xmm1, xmm2
+    // pxor xmm3, xmm4
+    // nop
+    let bytes: Vec<u8> = vec![0xc5, 0xf1, 0xef, 0xc2, 0x66, 0x0f, 0xef, 0xdc, 0x90];
+
+    let upper = eval(
+        &il::Expression::shl(
+            ymm(mk128const(0x11111111_11111111, 0x22222222_22222222)).into(),
+            il::expr_const(128, 256),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let driver = init_amd64_driver(
+        bytes,
+        vec![
+            ("ymm0", upper.clone()),
+            (
+                "ymm1",
+                ymm(mk128const(0x00000000_ffffffff, 0xffffffff_00000000)),
+            ),
+            (
+                "ymm2",
+                ymm(mk128const(0x0000ffff_0000ffff, 0x0000ffff_0000ffff)),
+            ),
+            ("ymm3", upper.clone()),
+            (
+                "ymm4",
+                ymm(mk128const(0x12345678_12345678, 0x12345678_12345678)),
+            ),
+        ],
+        Memory::new(Endian::Little),
+    );
+
+    let driver = step_to(driver, 0x8);
+
+    // VEX-encoded writes zero the upper half of the ymm register
+    assert_eq!(
+        driver.state().get_scalar("ymm0").unwrap(),
+        &ymm(mk128const(0x0000ffff_ffff0000, 0xffff0000_0000ffff))
+    );
+    // and legacy SSE writes preserve it
+    assert_eq!(
+        driver.state().get_scalar("ymm3").unwrap(),
+        &eval(
+            &il::Expression::or(
+                upper.into(),
+                ymm(mk128const(0x12345678_12345678, 0x12345678_12345678)).into(),
+            )
+            .unwrap()
+        )
+        .unwrap()
+    );
+}
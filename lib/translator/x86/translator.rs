@@ -120,7 +120,18 @@ pub(crate) fn translate_block(
             match instruction_id {
                 capstone::x86_insn::X86_INS_ADC => semantics.adc(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_ADD => semantics.add(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_ADDSD | capstone::x86_insn::X86_INS_ADDSS => {
+                    semantics.adds(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_AND => semantics.and(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_ANDNPD
+                | capstone::x86_insn::X86_INS_ANDNPS
+                | capstone::x86_insn::X86_INS_VANDNPD
+                | capstone::x86_insn::X86_INS_VANDNPS => semantics.andnps(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_ANDPD
+                | capstone::x86_insn::X86_INS_ANDPS
+                | capstone::x86_insn::X86_INS_VANDPD
+                | capstone::x86_insn::X86_INS_VANDPS => semantics.andps(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_BSF => semantics.bsf(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_BSR => semantics.bsr(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_BSWAP => semantics.bswap(&mut instruction_graph),
@@ -153,62 +164,102 @@ pub(crate) fn translate_block(
                 | capstone::x86_insn::X86_INS_CMOVP
                 | capstone::x86_insn::X86_INS_CMOVS => semantics.cmovcc(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_CMP => semantics.cmp(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_CMPSB => semantics.cmpsb(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_CMPSB
+                | capstone::x86_insn::X86_INS_CMPSW
+                | capstone::x86_insn::X86_INS_CMPSQ => semantics.cmps(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_CMPSD => semantics.cmpsd(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_CMPXCHG => semantics.cmpxchg(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_COMISD
+                | capstone::x86_insn::X86_INS_COMISS
+                | capstone::x86_insn::X86_INS_UCOMISD
+                | capstone::x86_insn::X86_INS_UCOMISS => semantics.comis(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_CPUID => {
                     unhandled_intrinsic(&mut instruction_graph, &instruction)
                 }
+                capstone::x86_insn::X86_INS_CVTSD2SI
+                | capstone::x86_insn::X86_INS_CVTSS2SI
+                | capstone::x86_insn::X86_INS_CVTTSD2SI
+                | capstone::x86_insn::X86_INS_CVTTSS2SI => {
+                    semantics.cvts2si(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_CVTSD2SS => semantics.cvtsd2ss(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_CVTSI2SD | capstone::x86_insn::X86_INS_CVTSI2SS => {
+                    semantics.cvtsi2s(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_CVTSS2SD => semantics.cvtss2sd(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_CWD => semantics.cwd(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_CWDE => semantics.cwde(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_DEC => semantics.dec(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_DIV => semantics.div(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_DIVSD | capstone::x86_insn::X86_INS_DIVSS => {
+                    semantics.divs(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_F2XM1
-                | capstone::x86_insn::X86_INS_FABS
-                | capstone::x86_insn::X86_INS_FADD
-                | capstone::x86_insn::X86_INS_FADDP
-                | capstone::x86_insn::X86_INS_FCHS
-                | capstone::x86_insn::X86_INS_FCOMP
-                | capstone::x86_insn::X86_INS_FCOMPP
-                | capstone::x86_insn::X86_INS_FDIV
-                | capstone::x86_insn::X86_INS_FDIVR
-                | capstone::x86_insn::X86_INS_FDIVRP
                 | capstone::x86_insn::X86_INS_FFREE
-                | capstone::x86_insn::X86_INS_FILD
                 | capstone::x86_insn::X86_INS_FINCSTP
-                | capstone::x86_insn::X86_INS_FISTP
-                | capstone::x86_insn::X86_INS_FLDCW
-                | capstone::x86_insn::X86_INS_FLD
-                | capstone::x86_insn::X86_INS_FLD1
                 | capstone::x86_insn::X86_INS_FLDENV
-                | capstone::x86_insn::X86_INS_FLDL2E
-                | capstone::x86_insn::X86_INS_FLDLN2
-                | capstone::x86_insn::X86_INS_FLDZ
-                | capstone::x86_insn::X86_INS_FDIVP
-                | capstone::x86_insn::X86_INS_FMUL
-                | capstone::x86_insn::X86_INS_FMULP
                 | capstone::x86_insn::X86_INS_FNCLEX
                 | capstone::x86_insn::X86_INS_FNSTENV
-                | capstone::x86_insn::X86_INS_FNSTCW
-                | capstone::x86_insn::X86_INS_FNSTSW
-                | capstone::x86_insn::X86_INS_FRNDINT
                 | capstone::x86_insn::X86_INS_FSCALE
-                | capstone::x86_insn::X86_INS_FST
-                | capstone::x86_insn::X86_INS_FSTP
-                | capstone::x86_insn::X86_INS_FSUB
-                | capstone::x86_insn::X86_INS_FSUBP
-                | capstone::x86_insn::X86_INS_FSUBR
-                | capstone::x86_insn::X86_INS_FSUBRP
-                | capstone::x86_insn::X86_INS_FTST
-                | capstone::x86_insn::X86_INS_FUCOMI
                 | capstone::x86_insn::X86_INS_FXAM
-                | capstone::x86_insn::X86_INS_FXCH
                 | capstone::x86_insn::X86_INS_FYL2X => {
                     unhandled_intrinsic(&mut instruction_graph, &instruction)
                 }
-                #[cfg(not(feature = "capstone4"))]
-                capstone::x86_insn::X86_INS_FUCOMPI => {
-                    unhandled_intrinsic(&mut instruction_graph, &instruction)
+                capstone::x86_insn::X86_INS_FABS => semantics.fabs(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FADD
+                | capstone::x86_insn::X86_INS_FADDP
+                | capstone::x86_insn::X86_INS_FIADD => semantics.fadd(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FCHS => semantics.fchs(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FCOM
+                | capstone::x86_insn::X86_INS_FCOMP
+                | capstone::x86_insn::X86_INS_FCOMPP
+                | capstone::x86_insn::X86_INS_FICOM
+                | capstone::x86_insn::X86_INS_FICOMP
+                | capstone::x86_insn::X86_INS_FTST
+                | capstone::x86_insn::X86_INS_FUCOM
+                | capstone::x86_insn::X86_INS_FUCOMP
+                | capstone::x86_insn::X86_INS_FUCOMPP => semantics.fcom(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FCOMI
+                | capstone::x86_insn::X86_INS_FCOMIP
+                | capstone::x86_insn::X86_INS_FUCOMI
+                | capstone::x86_insn::X86_INS_FUCOMIP => semantics.fcomi(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FDIV
+                | capstone::x86_insn::X86_INS_FDIVP
+                | capstone::x86_insn::X86_INS_FIDIV => semantics.fdiv(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FDIVR
+                | capstone::x86_insn::X86_INS_FDIVRP
+                | capstone::x86_insn::X86_INS_FIDIVR => semantics.fdivr(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FILD => semantics.fild(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FIST
+                | capstone::x86_insn::X86_INS_FISTP
+                | capstone::x86_insn::X86_INS_FISTTP => semantics.fist(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FLD => semantics.fld(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FLD1
+                | capstone::x86_insn::X86_INS_FLDL2E
+                | capstone::x86_insn::X86_INS_FLDL2T
+                | capstone::x86_insn::X86_INS_FLDLG2
+                | capstone::x86_insn::X86_INS_FLDLN2
+                | capstone::x86_insn::X86_INS_FLDPI
+                | capstone::x86_insn::X86_INS_FLDZ => {
+                    semantics.fld_constant(&mut instruction_graph)
                 }
+                capstone::x86_insn::X86_INS_FLDCW => semantics.fldcw(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FMUL
+                | capstone::x86_insn::X86_INS_FMULP
+                | capstone::x86_insn::X86_INS_FIMUL => semantics.fmul(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FNSTCW => semantics.fnstcw(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FNSTSW => semantics.fnstsw(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FRNDINT => semantics.frndint(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FST | capstone::x86_insn::X86_INS_FSTP => {
+                    semantics.fst(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_FSUB
+                | capstone::x86_insn::X86_INS_FSUBP
+                | capstone::x86_insn::X86_INS_FISUB => semantics.fsub(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FSUBR
+                | capstone::x86_insn::X86_INS_FSUBRP
+                | capstone::x86_insn::X86_INS_FISUBR => semantics.fsubr(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_FXCH => semantics.fxch(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_HLT => semantics.nop(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_IDIV => semantics.idiv(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_IMUL => semantics.imul(&mut instruction_graph),
@@ -240,66 +291,237 @@ pub(crate) fn translate_block(
                 // unconditional jumps will only emit a brc if the destination is undetermined at
                 // translation time
                 capstone::x86_insn::X86_INS_JMP => semantics.jmp(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_LDDQU | capstone::x86_insn::X86_INS_VLDDQU => {
+                    semantics.mov(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_LEA => semantics.lea(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_LEAVE => semantics.leave(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_LODSB => semantics.lodsb(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_LODSD => semantics.lodsd(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_LODSB
+                | capstone::x86_insn::X86_INS_LODSW
+                | capstone::x86_insn::X86_INS_LODSD
+                | capstone::x86_insn::X86_INS_LODSQ => semantics.lods(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_LOOP => semantics.loop_(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_LOOPE => semantics.loop_(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_LOOPNE => semantics.loop_(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_MOVHPD => semantics.movhpd(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_MOVLPD => semantics.movlpd(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_MAXSD | capstone::x86_insn::X86_INS_MAXSS => {
+                    semantics.maxs(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_MINSD | capstone::x86_insn::X86_INS_MINSS => {
+                    semantics.mins(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_MOVD | capstone::x86_insn::X86_INS_VMOVD => {
+                    semantics.movd(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_MOVHLPS => semantics.movhlps(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_MOVHPD | capstone::x86_insn::X86_INS_MOVHPS => {
+                    semantics.movhpd(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_MOVLHPS => semantics.movlhps(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_MOVLPD | capstone::x86_insn::X86_INS_MOVLPS => {
+                    semantics.movlpd(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_MOVMSKPD | capstone::x86_insn::X86_INS_MOVMSKPS => {
+                    semantics.movmsk(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_MOV
                 | capstone::x86_insn::X86_INS_MOVABS
                 | capstone::x86_insn::X86_INS_MOVAPS
                 | capstone::x86_insn::X86_INS_MOVAPD
                 | capstone::x86_insn::X86_INS_MOVDQA
                 | capstone::x86_insn::X86_INS_MOVDQU
+                | capstone::x86_insn::X86_INS_MOVNTDQ
+                | capstone::x86_insn::X86_INS_MOVNTDQA
                 | capstone::x86_insn::X86_INS_MOVNTI
-                | capstone::x86_insn::X86_INS_MOVUPS => semantics.mov(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_MOVQ => semantics.movq(&mut instruction_graph),
+                | capstone::x86_insn::X86_INS_MOVNTPD
+                | capstone::x86_insn::X86_INS_MOVNTPS
+                | capstone::x86_insn::X86_INS_MOVUPD
+                | capstone::x86_insn::X86_INS_MOVUPS
+                | capstone::x86_insn::X86_INS_VMOVAPD
+                | capstone::x86_insn::X86_INS_VMOVAPS
+                | capstone::x86_insn::X86_INS_VMOVDQA
+                | capstone::x86_insn::X86_INS_VMOVDQU
+                | capstone::x86_insn::X86_INS_VMOVNTDQ
+                | capstone::x86_insn::X86_INS_VMOVNTDQA
+                | capstone::x86_insn::X86_INS_VMOVNTPD
+                | capstone::x86_insn::X86_INS_VMOVNTPS
+                | capstone::x86_insn::X86_INS_VMOVUPD
+                | capstone::x86_insn::X86_INS_VMOVUPS => semantics.mov(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_MOVQ | capstone::x86_insn::X86_INS_VMOVQ => {
+                    semantics.movq(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_MOVSB
                 | capstone::x86_insn::X86_INS_MOVSW
-                | capstone::x86_insn::X86_INS_MOVSD
                 | capstone::x86_insn::X86_INS_MOVSQ => semantics.movs(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_MOVSD => semantics.movsd(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_MOVSS => semantics.movss(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_MOVSX => semantics.movsx(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_MOVSXD => semantics.movsx(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_MOVD | capstone::x86_insn::X86_INS_MOVZX => {
-                    semantics.movzx(&mut instruction_graph)
-                }
+                capstone::x86_insn::X86_INS_MOVZX => semantics.movzx(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_MUL => semantics.mul(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_MULSD | capstone::x86_insn::X86_INS_MULSS => {
+                    semantics.muls(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_NEG => semantics.neg(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_NOP => semantics.nop(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_NOT => semantics.not(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_OR => semantics.or(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PADDQ => semantics.paddq(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_ORPD
+                | capstone::x86_insn::X86_INS_ORPS
+                | capstone::x86_insn::X86_INS_VORPD
+                | capstone::x86_insn::X86_INS_VORPS => semantics.por(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PABSB
+                | capstone::x86_insn::X86_INS_PABSD
+                | capstone::x86_insn::X86_INS_PABSW => semantics.pabs(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PACKSSDW
+                | capstone::x86_insn::X86_INS_PACKSSWB
+                | capstone::x86_insn::X86_INS_PACKUSDW
+                | capstone::x86_insn::X86_INS_PACKUSWB => semantics.pack(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PADDB
+                | capstone::x86_insn::X86_INS_PADDD
+                | capstone::x86_insn::X86_INS_PADDQ
+                | capstone::x86_insn::X86_INS_PADDW => semantics.padd(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PADDSB | capstone::x86_insn::X86_INS_PADDSW => {
+                    semantics.padds(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PADDUSB | capstone::x86_insn::X86_INS_PADDUSW => {
+                    semantics.paddus(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PALIGNR => semantics.palignr(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PAND | capstone::x86_insn::X86_INS_VPAND => {
+                    semantics.andps(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PANDN | capstone::x86_insn::X86_INS_VPANDN => {
+                    semantics.andnps(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_PAUSE => semantics.nop(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PCMPEQB => semantics.pcmpeqb(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PCMPEQD => semantics.pcmpeqd(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PMOVMSKB => semantics.pmovmskb(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PMINUB => semantics.pminub(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PAVGB | capstone::x86_insn::X86_INS_PAVGW => {
+                    semantics.pavg(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PCMPEQB
+                | capstone::x86_insn::X86_INS_PCMPEQD
+                | capstone::x86_insn::X86_INS_PCMPEQQ
+                | capstone::x86_insn::X86_INS_PCMPEQW
+                | capstone::x86_insn::X86_INS_VPCMPEQB
+                | capstone::x86_insn::X86_INS_VPCMPEQD
+                | capstone::x86_insn::X86_INS_VPCMPEQQ
+                | capstone::x86_insn::X86_INS_VPCMPEQW => semantics.pcmpeq(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PCMPGTB
+                | capstone::x86_insn::X86_INS_PCMPGTD
+                | capstone::x86_insn::X86_INS_PCMPGTQ
+                | capstone::x86_insn::X86_INS_PCMPGTW
+                | capstone::x86_insn::X86_INS_VPCMPGTB
+                | capstone::x86_insn::X86_INS_VPCMPGTD
+                | capstone::x86_insn::X86_INS_VPCMPGTQ
+                | capstone::x86_insn::X86_INS_VPCMPGTW => semantics.pcmpgt(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PEXTRB
+                | capstone::x86_insn::X86_INS_PEXTRD
+                | capstone::x86_insn::X86_INS_PEXTRQ
+                | capstone::x86_insn::X86_INS_PEXTRW => semantics.pextr(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PINSRB
+                | capstone::x86_insn::X86_INS_PINSRD
+                | capstone::x86_insn::X86_INS_PINSRQ
+                | capstone::x86_insn::X86_INS_PINSRW => semantics.pinsr(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PMADDWD => semantics.pmaddwd(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PMAXSB
+                | capstone::x86_insn::X86_INS_PMAXSD
+                | capstone::x86_insn::X86_INS_PMAXSW
+                | capstone::x86_insn::X86_INS_PMAXUB
+                | capstone::x86_insn::X86_INS_PMAXUD
+                | capstone::x86_insn::X86_INS_PMAXUW
+                | capstone::x86_insn::X86_INS_VPMAXSB
+                | capstone::x86_insn::X86_INS_VPMAXSD
+                | capstone::x86_insn::X86_INS_VPMAXSW
+                | capstone::x86_insn::X86_INS_VPMAXUB
+                | capstone::x86_insn::X86_INS_VPMAXUD
+                | capstone::x86_insn::X86_INS_VPMAXUW => semantics.pmax(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PMINSB
+                | capstone::x86_insn::X86_INS_PMINSD
+                | capstone::x86_insn::X86_INS_PMINSW
+                | capstone::x86_insn::X86_INS_PMINUB
+                | capstone::x86_insn::X86_INS_PMINUD
+                | capstone::x86_insn::X86_INS_PMINUW
+                | capstone::x86_insn::X86_INS_VPMINSB
+                | capstone::x86_insn::X86_INS_VPMINSD
+                | capstone::x86_insn::X86_INS_VPMINSW
+                | capstone::x86_insn::X86_INS_VPMINUB
+                | capstone::x86_insn::X86_INS_VPMINUD
+                | capstone::x86_insn::X86_INS_VPMINUW => semantics.pmin(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PMOVMSKB | capstone::x86_insn::X86_INS_VPMOVMSKB => {
+                    semantics.pmovmskb(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PMOVSXBD
+                | capstone::x86_insn::X86_INS_PMOVSXBQ
+                | capstone::x86_insn::X86_INS_PMOVSXBW
+                | capstone::x86_insn::X86_INS_PMOVSXDQ
+                | capstone::x86_insn::X86_INS_PMOVSXWD
+                | capstone::x86_insn::X86_INS_PMOVSXWQ
+                | capstone::x86_insn::X86_INS_PMOVZXBD
+                | capstone::x86_insn::X86_INS_PMOVZXBQ
+                | capstone::x86_insn::X86_INS_PMOVZXBW
+                | capstone::x86_insn::X86_INS_PMOVZXDQ
+                | capstone::x86_insn::X86_INS_PMOVZXWD
+                | capstone::x86_insn::X86_INS_PMOVZXWQ => semantics.pmovx(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PMULDQ
+                | capstone::x86_insn::X86_INS_PMULHUW
+                | capstone::x86_insn::X86_INS_PMULHW
+                | capstone::x86_insn::X86_INS_PMULLD
+                | capstone::x86_insn::X86_INS_PMULLW
+                | capstone::x86_insn::X86_INS_PMULUDQ => semantics.pmul(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_POP => semantics.pop(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_POR => semantics.por(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_POR | capstone::x86_insn::X86_INS_VPOR => {
+                    semantics.por(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_PREFETCHT0 => semantics.nop(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_PREFETCHT1 => semantics.nop(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_PREFETCHT2 => semantics.nop(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_PREFETCHNTA => semantics.nop(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PSADBW => semantics.psadbw(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PSHUFB => semantics.pshufb(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_PSHUFD => semantics.pshufd(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PSHUFHW | capstone::x86_insn::X86_INS_PSHUFLW => {
+                    semantics.pshufw(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PSLLD
+                | capstone::x86_insn::X86_INS_PSLLQ
+                | capstone::x86_insn::X86_INS_PSLLW => semantics.psll(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_PSLLDQ => semantics.pslldq(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PSRAD | capstone::x86_insn::X86_INS_PSRAW => {
+                    semantics.psra(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PSRLD
+                | capstone::x86_insn::X86_INS_PSRLQ
+                | capstone::x86_insn::X86_INS_PSRLW => semantics.psrl(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_PSRLDQ => semantics.psrldq(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PSUBB => semantics.psubb(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PSUBQ => semantics.psubq(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_PUNPCKLBW => {
-                    semantics.punpcklbw(&mut instruction_graph)
+                capstone::x86_insn::X86_INS_PSUBB
+                | capstone::x86_insn::X86_INS_PSUBD
+                | capstone::x86_insn::X86_INS_PSUBQ
+                | capstone::x86_insn::X86_INS_PSUBW => semantics.psub(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PSUBSB | capstone::x86_insn::X86_INS_PSUBSW => {
+                    semantics.psubs(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PSUBUSB | capstone::x86_insn::X86_INS_PSUBUSW => {
+                    semantics.psubus(&mut instruction_graph)
                 }
-                capstone::x86_insn::X86_INS_PUNPCKLWD => {
-                    semantics.punpcklwd(&mut instruction_graph)
+                capstone::x86_insn::X86_INS_PTEST => semantics.ptest(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PUNPCKHBW
+                | capstone::x86_insn::X86_INS_PUNPCKHDQ
+                | capstone::x86_insn::X86_INS_PUNPCKHQDQ
+                | capstone::x86_insn::X86_INS_PUNPCKHWD => {
+                    semantics.punpckh(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_PUNPCKLBW
+                | capstone::x86_insn::X86_INS_PUNPCKLDQ
+                | capstone::x86_insn::X86_INS_PUNPCKLQDQ
+                | capstone::x86_insn::X86_INS_PUNPCKLWD => {
+                    semantics.punpckl(&mut instruction_graph)
                 }
                 capstone::x86_insn::X86_INS_PUSH => semantics.push(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_PUSHFD => {
                     unhandled_intrinsic(&mut instruction_graph, &instruction)
                 }
-                capstone::x86_insn::X86_INS_PXOR => semantics.pxor(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_PXOR | capstone::x86_insn::X86_INS_VPXOR => {
+                    semantics.pxor(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_RDTSC => {
                     unhandled_intrinsic(&mut instruction_graph, &instruction)
                 }
@@ -309,8 +531,10 @@ pub(crate) fn translate_block(
                 capstone::x86_insn::X86_INS_SAHF => semantics.sahf(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_SAR => semantics.sar(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_SBB => semantics.sbb(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_SCASB => semantics.scasb(&mut instruction_graph),
-                capstone::x86_insn::X86_INS_SCASW => semantics.scasw(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_SCASB
+                | capstone::x86_insn::X86_INS_SCASW
+                | capstone::x86_insn::X86_INS_SCASD
+                | capstone::x86_insn::X86_INS_SCASQ => semantics.scas(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_SETAE
                 | capstone::x86_insn::X86_INS_SETA
                 | capstone::x86_insn::X86_INS_SETBE
@@ -327,6 +551,9 @@ pub(crate) fn translate_block(
                 | capstone::x86_insn::X86_INS_SETO
                 | capstone::x86_insn::X86_INS_SETP
                 | capstone::x86_insn::X86_INS_SETS => semantics.setcc(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_SHUFPD | capstone::x86_insn::X86_INS_SHUFPS => {
+                    semantics.shufp(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_SHL => semantics.shl(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_SHR => semantics.shr(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_SHLD => semantics.shld(&mut instruction_graph),
@@ -339,17 +566,33 @@ pub(crate) fn translate_block(
                 capstone::x86_insn::X86_INS_STOSD => semantics.stos(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_STOSQ => semantics.stos(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_SUB => semantics.sub(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_SUBSD | capstone::x86_insn::X86_INS_SUBSS => {
+                    semantics.subs(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_SYSCALL => semantics.syscall(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_SYSENTER => semantics.sysenter(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_TEST => semantics.test(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_WAIT => semantics.nop(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_UD2 => semantics.ud2(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_UNPCKHPD | capstone::x86_insn::X86_INS_UNPCKHPS => {
+                    semantics.punpckh(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_UNPCKLPD | capstone::x86_insn::X86_INS_UNPCKLPS => {
+                    semantics.punpckl(&mut instruction_graph)
+                }
+                capstone::x86_insn::X86_INS_VZEROUPPER => {
+                    semantics.vzeroupper(&mut instruction_graph)
+                }
                 capstone::x86_insn::X86_INS_XADD => semantics.xadd(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_XCHG => semantics.xchg(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_XGETBV => {
                     unhandled_intrinsic(&mut instruction_graph, &instruction)
                 }
                 capstone::x86_insn::X86_INS_XOR => semantics.xor(&mut instruction_graph),
+                capstone::x86_insn::X86_INS_XORPD
+                | capstone::x86_insn::X86_INS_XORPS
+                | capstone::x86_insn::X86_INS_VXORPD
+                | capstone::x86_insn::X86_INS_VXORPS => semantics.pxor(&mut instruction_graph),
                 capstone::x86_insn::X86_INS_MFENCE
                 | capstone::x86_insn::X86_INS_SFENCE
                 | capstone::x86_insn::X86_INS_LFENCE => {
@@ -373,18 +616,21 @@ pub(crate) fn translate_block(
                 }
             }?;
 
-            let detail = semantics.details()?;
-            if detail
-                .prefix
-                .contains(&(capstone_sys::x86_prefix::X86_PREFIX_REP as u8))
-            {
-                semantics.rep_prefix(&mut instruction_graph)?;
-            }
-            if detail
-                .prefix
-                .contains(&(capstone_sys::x86_prefix::X86_PREFIX_REPNE as u8))
-            {
-                semantics.repne_prefix(&mut instruction_graph)?;
+            // The rep prefixes are only meaningful for string instructions, and
+            // are otherwise ignored, as in `rep ret`.
+            if semantics.is_string_instruction()? {
+                let detail = semantics.details()?;
+                if detail
+                    .prefix
+                    .contains(&(capstone_sys::x86_prefix::X86_PREFIX_REP as u8))
+                {
+                    semantics.rep_prefix(&mut instruction_graph)?;
+                } else if detail
+                    .prefix
+                    .contains(&(capstone_sys::x86_prefix::X86_PREFIX_REPNE as u8))
+                {
+                    semantics.repne_prefix(&mut instruction_graph)?;
+                }
             }
 
             length += instruction.size as usize;
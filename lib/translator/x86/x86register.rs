@@ -245,6 +245,198 @@ const X86REGISTERS: &[X86Register] = &[
         bits: 32,
         mode: Mode::X86,
     },
+    X86Register {
+        name: "ymm0",
+        capstone_reg: x86_reg::X86_REG_YMM0,
+        full_reg: x86_reg::X86_REG_YMM0,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm0",
+        capstone_reg: x86_reg::X86_REG_XMM0,
+        full_reg: x86_reg::X86_REG_YMM0,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "ymm1",
+        capstone_reg: x86_reg::X86_REG_YMM1,
+        full_reg: x86_reg::X86_REG_YMM1,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm1",
+        capstone_reg: x86_reg::X86_REG_XMM1,
+        full_reg: x86_reg::X86_REG_YMM1,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "ymm2",
+        capstone_reg: x86_reg::X86_REG_YMM2,
+        full_reg: x86_reg::X86_REG_YMM2,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm2",
+        capstone_reg: x86_reg::X86_REG_XMM2,
+        full_reg: x86_reg::X86_REG_YMM2,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "ymm3",
+        capstone_reg: x86_reg::X86_REG_YMM3,
+        full_reg: x86_reg::X86_REG_YMM3,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm3",
+        capstone_reg: x86_reg::X86_REG_XMM3,
+        full_reg: x86_reg::X86_REG_YMM3,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "ymm4",
+        capstone_reg: x86_reg::X86_REG_YMM4,
+        full_reg: x86_reg::X86_REG_YMM4,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm4",
+        capstone_reg: x86_reg::X86_REG_XMM4,
+        full_reg: x86_reg::X86_REG_YMM4,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "ymm5",
+        capstone_reg: x86_reg::X86_REG_YMM5,
+        full_reg: x86_reg::X86_REG_YMM5,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm5",
+        capstone_reg: x86_reg::X86_REG_XMM5,
+        full_reg: x86_reg::X86_REG_YMM5,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "ymm6",
+        capstone_reg: x86_reg::X86_REG_YMM6,
+        full_reg: x86_reg::X86_REG_YMM6,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm6",
+        capstone_reg: x86_reg::X86_REG_XMM6,
+        full_reg: x86_reg::X86_REG_YMM6,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "ymm7",
+        capstone_reg: x86_reg::X86_REG_YMM7,
+        full_reg: x86_reg::X86_REG_YMM7,
+        offset: 0,
+        bits: 256,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "xmm7",
+        capstone_reg: x86_reg::X86_REG_XMM7,
+        full_reg: x86_reg::X86_REG_YMM7,
+        offset: 0,
+        bits: 128,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st0",
+        capstone_reg: x86_reg::X86_REG_ST0,
+        full_reg: x86_reg::X86_REG_ST0,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st1",
+        capstone_reg: x86_reg::X86_REG_ST1,
+        full_reg: x86_reg::X86_REG_ST1,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st2",
+        capstone_reg: x86_reg::X86_REG_ST2,
+        full_reg: x86_reg::X86_REG_ST2,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st3",
+        capstone_reg: x86_reg::X86_REG_ST3,
+        full_reg: x86_reg::X86_REG_ST3,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st4",
+        capstone_reg: x86_reg::X86_REG_ST4,
+        full_reg: x86_reg::X86_REG_ST4,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st5",
+        capstone_reg: x86_reg::X86_REG_ST5,
+        full_reg: x86_reg::X86_REG_ST5,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st6",
+        capstone_reg: x86_reg::X86_REG_ST6,
+        full_reg: x86_reg::X86_REG_ST6,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
+    X86Register {
+        name: "st7",
+        capstone_reg: x86_reg::X86_REG_ST7,
+        full_reg: x86_reg::X86_REG_ST7,
+        offset: 0,
+        bits: 64,
+        mode: Mode::X86,
+    },
 ];
 
 const AMD64REGISTERS: &[X86Register] = &[
@@ -832,262 +1024,582 @@ const AMD64REGISTERS: &[X86Register] = &[
         bits: 64,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm0",
+        capstone_reg: x86_reg::X86_REG_YMM0,
+        full_reg: x86_reg::X86_REG_YMM0,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm0",
         capstone_reg: x86_reg::X86_REG_XMM0,
-        full_reg: x86_reg::X86_REG_XMM0,
+        full_reg: x86_reg::X86_REG_YMM0,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm1",
+        capstone_reg: x86_reg::X86_REG_YMM1,
+        full_reg: x86_reg::X86_REG_YMM1,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm1",
         capstone_reg: x86_reg::X86_REG_XMM1,
-        full_reg: x86_reg::X86_REG_XMM1,
+        full_reg: x86_reg::X86_REG_YMM1,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm2",
+        capstone_reg: x86_reg::X86_REG_YMM2,
+        full_reg: x86_reg::X86_REG_YMM2,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm2",
         capstone_reg: x86_reg::X86_REG_XMM2,
-        full_reg: x86_reg::X86_REG_XMM2,
+        full_reg: x86_reg::X86_REG_YMM2,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm3",
+        capstone_reg: x86_reg::X86_REG_YMM3,
+        full_reg: x86_reg::X86_REG_YMM3,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm3",
         capstone_reg: x86_reg::X86_REG_XMM3,
-        full_reg: x86_reg::X86_REG_XMM3,
+        full_reg: x86_reg::X86_REG_YMM3,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm4",
+        capstone_reg: x86_reg::X86_REG_YMM4,
+        full_reg: x86_reg::X86_REG_YMM4,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm4",
         capstone_reg: x86_reg::X86_REG_XMM4,
-        full_reg: x86_reg::X86_REG_XMM4,
+        full_reg: x86_reg::X86_REG_YMM4,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm5",
+        capstone_reg: x86_reg::X86_REG_YMM5,
+        full_reg: x86_reg::X86_REG_YMM5,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm5",
         capstone_reg: x86_reg::X86_REG_XMM5,
-        full_reg: x86_reg::X86_REG_XMM5,
+        full_reg: x86_reg::X86_REG_YMM5,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm6",
+        capstone_reg: x86_reg::X86_REG_YMM6,
+        full_reg: x86_reg::X86_REG_YMM6,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm6",
         capstone_reg: x86_reg::X86_REG_XMM6,
-        full_reg: x86_reg::X86_REG_XMM6,
+        full_reg: x86_reg::X86_REG_YMM6,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm7",
+        capstone_reg: x86_reg::X86_REG_YMM7,
+        full_reg: x86_reg::X86_REG_YMM7,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm7",
         capstone_reg: x86_reg::X86_REG_XMM7,
-        full_reg: x86_reg::X86_REG_XMM7,
+        full_reg: x86_reg::X86_REG_YMM7,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm8",
+        capstone_reg: x86_reg::X86_REG_YMM8,
+        full_reg: x86_reg::X86_REG_YMM8,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm8",
         capstone_reg: x86_reg::X86_REG_XMM8,
-        full_reg: x86_reg::X86_REG_XMM8,
+        full_reg: x86_reg::X86_REG_YMM8,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm9",
+        capstone_reg: x86_reg::X86_REG_YMM9,
+        full_reg: x86_reg::X86_REG_YMM9,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm9",
         capstone_reg: x86_reg::X86_REG_XMM9,
-        full_reg: x86_reg::X86_REG_XMM9,
+        full_reg: x86_reg::X86_REG_YMM9,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm10",
+        capstone_reg: x86_reg::X86_REG_YMM10,
+        full_reg: x86_reg::X86_REG_YMM10,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm10",
         capstone_reg: x86_reg::X86_REG_XMM10,
-        full_reg: x86_reg::X86_REG_XMM10,
+        full_reg: x86_reg::X86_REG_YMM10,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm11",
+        capstone_reg: x86_reg::X86_REG_YMM11,
+        full_reg: x86_reg::X86_REG_YMM11,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm11",
         capstone_reg: x86_reg::X86_REG_XMM11,
-        full_reg: x86_reg::X86_REG_XMM11,
+        full_reg: x86_reg::X86_REG_YMM11,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm12",
+        capstone_reg: x86_reg::X86_REG_YMM12,
+        full_reg: x86_reg::X86_REG_YMM12,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm12",
         capstone_reg: x86_reg::X86_REG_XMM12,
-        full_reg: x86_reg::X86_REG_XMM12,
+        full_reg: x86_reg::X86_REG_YMM12,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm13",
+        capstone_reg: x86_reg::X86_REG_YMM13,
+        full_reg: x86_reg::X86_REG_YMM13,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm13",
         capstone_reg: x86_reg::X86_REG_XMM13,
-        full_reg: x86_reg::X86_REG_XMM13,
+        full_reg: x86_reg::X86_REG_YMM13,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm14",
+        capstone_reg: x86_reg::X86_REG_YMM14,
+        full_reg: x86_reg::X86_REG_YMM14,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm14",
         capstone_reg: x86_reg::X86_REG_XMM14,
-        full_reg: x86_reg::X86_REG_XMM14,
+        full_reg: x86_reg::X86_REG_YMM14,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm15",
+        capstone_reg: x86_reg::X86_REG_YMM15,
+        full_reg: x86_reg::X86_REG_YMM15,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm15",
         capstone_reg: x86_reg::X86_REG_XMM15,
-        full_reg: x86_reg::X86_REG_XMM15,
+        full_reg: x86_reg::X86_REG_YMM15,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm16",
+        capstone_reg: x86_reg::X86_REG_YMM16,
+        full_reg: x86_reg::X86_REG_YMM16,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm16",
         capstone_reg: x86_reg::X86_REG_XMM16,
-        full_reg: x86_reg::X86_REG_XMM16,
+        full_reg: x86_reg::X86_REG_YMM16,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm17",
+        capstone_reg: x86_reg::X86_REG_YMM17,
+        full_reg: x86_reg::X86_REG_YMM17,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm17",
         capstone_reg: x86_reg::X86_REG_XMM17,
-        full_reg: x86_reg::X86_REG_XMM17,
+        full_reg: x86_reg::X86_REG_YMM17,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm18",
+        capstone_reg: x86_reg::X86_REG_YMM18,
+        full_reg: x86_reg::X86_REG_YMM18,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm18",
         capstone_reg: x86_reg::X86_REG_XMM18,
-        full_reg: x86_reg::X86_REG_XMM18,
+        full_reg: x86_reg::X86_REG_YMM18,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm19",
+        capstone_reg: x86_reg::X86_REG_YMM19,
+        full_reg: x86_reg::X86_REG_YMM19,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm19",
         capstone_reg: x86_reg::X86_REG_XMM19,
-        full_reg: x86_reg::X86_REG_XMM19,
+        full_reg: x86_reg::X86_REG_YMM19,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm20",
+        capstone_reg: x86_reg::X86_REG_YMM20,
+        full_reg: x86_reg::X86_REG_YMM20,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm20",
         capstone_reg: x86_reg::X86_REG_XMM20,
-        full_reg: x86_reg::X86_REG_XMM20,
+        full_reg: x86_reg::X86_REG_YMM20,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm21",
+        capstone_reg: x86_reg::X86_REG_YMM21,
+        full_reg: x86_reg::X86_REG_YMM21,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm21",
         capstone_reg: x86_reg::X86_REG_XMM21,
-        full_reg: x86_reg::X86_REG_XMM21,
+        full_reg: x86_reg::X86_REG_YMM21,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm22",
+        capstone_reg: x86_reg::X86_REG_YMM22,
+        full_reg: x86_reg::X86_REG_YMM22,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm22",
         capstone_reg: x86_reg::X86_REG_XMM22,
-        full_reg: x86_reg::X86_REG_XMM22,
+        full_reg: x86_reg::X86_REG_YMM22,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm23",
+        capstone_reg: x86_reg::X86_REG_YMM23,
+        full_reg: x86_reg::X86_REG_YMM23,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm23",
         capstone_reg: x86_reg::X86_REG_XMM23,
-        full_reg: x86_reg::X86_REG_XMM23,
+        full_reg: x86_reg::X86_REG_YMM23,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm24",
+        capstone_reg: x86_reg::X86_REG_YMM24,
+        full_reg: x86_reg::X86_REG_YMM24,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm24",
         capstone_reg: x86_reg::X86_REG_XMM24,
-        full_reg: x86_reg::X86_REG_XMM24,
+        full_reg: x86_reg::X86_REG_YMM24,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm25",
+        capstone_reg: x86_reg::X86_REG_YMM25,
+        full_reg: x86_reg::X86_REG_YMM25,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm25",
         capstone_reg: x86_reg::X86_REG_XMM25,
-        full_reg: x86_reg::X86_REG_XMM25,
+        full_reg: x86_reg::X86_REG_YMM25,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm26",
+        capstone_reg: x86_reg::X86_REG_YMM26,
+        full_reg: x86_reg::X86_REG_YMM26,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm26",
         capstone_reg: x86_reg::X86_REG_XMM26,
-        full_reg: x86_reg::X86_REG_XMM26,
+        full_reg: x86_reg::X86_REG_YMM26,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm27",
+        capstone_reg: x86_reg::X86_REG_YMM27,
+        full_reg: x86_reg::X86_REG_YMM27,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm27",
         capstone_reg: x86_reg::X86_REG_XMM27,
-        full_reg: x86_reg::X86_REG_XMM27,
+        full_reg: x86_reg::X86_REG_YMM27,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm28",
+        capstone_reg: x86_reg::X86_REG_YMM28,
+        full_reg: x86_reg::X86_REG_YMM28,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm28",
         capstone_reg: x86_reg::X86_REG_XMM28,
-        full_reg: x86_reg::X86_REG_XMM28,
+        full_reg: x86_reg::X86_REG_YMM28,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm29",
+        capstone_reg: x86_reg::X86_REG_YMM29,
+        full_reg: x86_reg::X86_REG_YMM29,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm29",
         capstone_reg: x86_reg::X86_REG_XMM29,
-        full_reg: x86_reg::X86_REG_XMM29,
+        full_reg: x86_reg::X86_REG_YMM29,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm30",
+        capstone_reg: x86_reg::X86_REG_YMM30,
+        full_reg: x86_reg::X86_REG_YMM30,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm30",
         capstone_reg: x86_reg::X86_REG_XMM30,
-        full_reg: x86_reg::X86_REG_XMM30,
+        full_reg: x86_reg::X86_REG_YMM30,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "ymm31",
+        capstone_reg: x86_reg::X86_REG_YMM31,
+        full_reg: x86_reg::X86_REG_YMM31,
+        offset: 0,
+        bits: 256,
+        mode: Mode::Amd64,
+    },
     X86Register {
         name: "xmm31",
         capstone_reg: x86_reg::X86_REG_XMM31,
-        full_reg: x86_reg::X86_REG_XMM31,
+        full_reg: x86_reg::X86_REG_YMM31,
         offset: 0,
         bits: 128,
         mode: Mode::Amd64,
     },
+    X86Register {
+        name: "st0",
+        capstone_reg: x86_reg::X86_REG_ST0,
+        full_reg: x86_reg::X86_REG_ST0,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
+    X86Register {
+        name: "st1",
+        capstone_reg: x86_reg::X86_REG_ST1,
+        full_reg: x86_reg::X86_REG_ST1,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
+    X86Register {
+        name: "st2",
+        capstone_reg: x86_reg::X86_REG_ST2,
+        full_reg: x86_reg::X86_REG_ST2,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
+    X86Register {
+        name: "st3",
+        capstone_reg: x86_reg::X86_REG_ST3,
+        full_reg: x86_reg::X86_REG_ST3,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
+    X86Register {
+        name: "st4",
+        capstone_reg: x86_reg::X86_REG_ST4,
+        full_reg: x86_reg::X86_REG_ST4,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
+    X86Register {
+        name: "st5",
+        capstone_reg: x86_reg::X86_REG_ST5,
+        full_reg: x86_reg::X86_REG_ST5,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
+    X86Register {
+        name: "st6",
+        capstone_reg: x86_reg::X86_REG_ST6,
+        full_reg: x86_reg::X86_REG_ST6,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
+    X86Register {
+        name: "st7",
+        capstone_reg: x86_reg::X86_REG_ST7,
+        full_reg: x86_reg::X86_REG_ST7,
+        offset: 0,
+        bits: 64,
+        mode: Mode::Amd64,
+    },
 ];
 
 /// Struct for dealing with x86 registers
@@ -1152,6 +1664,13 @@ impl X86Register {
                 let expr = Expr::and(full_reg.get()?, expr_const(mask, full_reg.bits))?;
                 let expr = Expr::or(expr, Expr::zext(full_reg.bits, value)?)?;
                 full_reg.set(block, expr)
+            } else if self.bits() > 64 {
+                // Legacy SSE writes to an xmm register preserve the upper bits
+                // of its ymm register
+                let shift = expr_const(self.bits as u64, full_reg.bits);
+                let expr = Expr::shl(Expr::shr(full_reg.get()?, shift.clone())?, shift)?;
+                let expr = Expr::or(expr, Expr::zext(full_reg.bits, value)?)?;
+                full_reg.set(block, expr)
             } else {
                 full_reg.set(block, Expr::zext(full_reg.bits, value)?)
             }
@@ -4,6 +4,19 @@ import copy
 import json
 
 
+# Binary Ninja architecture names, and the Falcon architecture names they map
+# to
+ARCHITECTURES = {
+    'aarch64': 'aarch64',
+    'armv7': 'arm',
+    'mips32': 'mips',
+    'mipsel32': 'mipsel',
+    'ppc': 'ppc',
+    'x86': 'x86',
+    'x86_64': 'amd64',
+}
+
+
 def falcon_export(bv) :
     filename = interaction.get_save_filename_input("Filename for Binja export")
 
@@ -11,7 +24,12 @@ def falcon_export(bv) :
     for segment in bv.segments :
         segments.append({
             'address': segment.start,
-            'bytes': base64.b64encode(bv.read(segment.start, segment.length))
+            'bytes': base64.b64encode(bv.read(segment.start, segment.length)),
+            'permissions': ''.join([
+                'r' if segment.readable else '-',
+                'w' if segment.writable else '-',
+                'x' if segment.executable else '-'
+            ])
         })
 
     functions = []
@@ -21,12 +39,19 @@ def falcon_export(bv) :
             'address': function.start,
         })
 
+    symbols = []
+    for symbol in bv.get_symbols() :
+        symbols.append({
+            'name': symbol.name,
+            'address': symbol.address,
+        })
 
     fh = open(filename, 'wb')
     fh.write(json.dumps({
         'functions': functions,
         'segments': segments,
-        'arch': bv.arch.name,
+        'symbols': symbols,
+        'arch': ARCHITECTURES.get(bv.arch.name, bv.arch.name),
         'entry': bv.entry_point
     }))
     fh.close()